@@ -0,0 +1,2 @@
+-- Drop admin sessions table
+DROP TABLE IF EXISTS admin_sessions;
@@ -0,0 +1,9 @@
+-- Server-side store for admin login sessions
+CREATE TABLE admin_sessions (
+    id VARCHAR(64) NOT NULL PRIMARY KEY,
+    username VARCHAR(255) NOT NULL,
+    role VARCHAR(20) NOT NULL,
+    database_id VARCHAR(255) NOT NULL,
+    created DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_seen DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,15 @@
+-- Record of every change made through Sorting Office
+CREATE TABLE audit_log (
+    id BIGINT NOT NULL AUTO_INCREMENT PRIMARY KEY,
+    created DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    admin VARCHAR(255) NOT NULL,
+    database_id VARCHAR(255) NOT NULL,
+    entity_type VARCHAR(32) NOT NULL,
+    entity_id VARCHAR(255) NOT NULL,
+    entity_name VARCHAR(255) NOT NULL,
+    action VARCHAR(32) NOT NULL,
+    changes TEXT NOT NULL,
+    INDEX idx_audit_log_entity (entity_type, entity_id),
+    INDEX idx_audit_log_admin (admin),
+    INDEX idx_audit_log_created (created)
+);
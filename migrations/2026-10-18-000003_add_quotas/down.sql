@@ -0,0 +1,9 @@
+-- Drop quota columns
+ALTER TABLE domains
+    DROP COLUMN quota_cap_bytes,
+    DROP COLUMN default_quota_messages,
+    DROP COLUMN default_quota_bytes;
+
+ALTER TABLE users
+    DROP COLUMN quota_messages,
+    DROP COLUMN quota_bytes;
@@ -0,0 +1,9 @@
+-- Mailbox quotas: NULL falls back to the domain default, 0 means unlimited
+ALTER TABLE users
+    ADD COLUMN quota_bytes BIGINT NULL,
+    ADD COLUMN quota_messages BIGINT NULL;
+
+ALTER TABLE domains
+    ADD COLUMN default_quota_bytes BIGINT NULL,
+    ADD COLUMN default_quota_messages BIGINT NULL,
+    ADD COLUMN quota_cap_bytes BIGINT NULL;
@@ -0,0 +1,2 @@
+-- Drop mailbox usage snapshots
+DROP TABLE IF EXISTS mailbox_usage;
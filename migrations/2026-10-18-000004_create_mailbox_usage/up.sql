@@ -0,0 +1,10 @@
+-- Mailbox usage snapshots written by the usage collector
+CREATE TABLE mailbox_usage (
+    id BIGINT NOT NULL AUTO_INCREMENT PRIMARY KEY,
+    user_id VARCHAR(255) NOT NULL,
+    bytes BIGINT NOT NULL,
+    messages BIGINT NOT NULL,
+    collected DATETIME NOT NULL,
+    INDEX idx_mailbox_usage_user (user_id, collected),
+    INDEX idx_mailbox_usage_collected (collected)
+);
@@ -0,0 +1,2 @@
+-- Drop vacation auto-replies
+DROP TABLE IF EXISTS vacation;
@@ -0,0 +1,12 @@
+-- Vacation auto-replies, one per mailbox
+CREATE TABLE vacation (
+    user_id VARCHAR(255) NOT NULL PRIMARY KEY,
+    subject VARCHAR(255) NOT NULL,
+    body TEXT NOT NULL,
+    active_from DATE NULL,
+    active_until DATE NULL,
+    reply_interval_days INT NOT NULL DEFAULT 7,
+    enabled TINYINT(1) NOT NULL DEFAULT 1,
+    created DATETIME NOT NULL,
+    modified DATETIME NOT NULL
+);
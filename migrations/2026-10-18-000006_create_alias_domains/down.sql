@@ -0,0 +1,2 @@
+-- Drop alias domains
+DROP TABLE IF EXISTS alias_domains;
@@ -0,0 +1,10 @@
+-- Alias domains: mail to user@alias_domain is delivered as user@target_domain
+CREATE TABLE alias_domains (
+    pkid INT NOT NULL AUTO_INCREMENT PRIMARY KEY,
+    alias_domain VARCHAR(255) NOT NULL UNIQUE,
+    target_domain VARCHAR(255) NOT NULL,
+    created DATETIME NOT NULL,
+    modified DATETIME NOT NULL,
+    enabled TINYINT(1) NOT NULL DEFAULT 1,
+    INDEX idx_alias_domains_target_domain (target_domain)
+);
@@ -0,0 +1,2 @@
+-- Destinations longer than 255 characters have to be shortened first
+ALTER TABLE aliases MODIFY destination VARCHAR(255) NOT NULL;
@@ -0,0 +1,2 @@
+-- Alias destinations are comma-separated lists that outgrow VARCHAR(255)
+ALTER TABLE aliases MODIFY destination TEXT NOT NULL;
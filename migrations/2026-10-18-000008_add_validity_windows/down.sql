@@ -0,0 +1,10 @@
+-- Drop the validity windows
+DROP INDEX aliases_valid_until_idx ON aliases;
+
+ALTER TABLE aliases
+    DROP COLUMN valid_from,
+    DROP COLUMN valid_until;
+
+ALTER TABLE relocated
+    DROP COLUMN valid_from,
+    DROP COLUMN valid_until;
@@ -0,0 +1,10 @@
+-- Optional validity windows for aliases and relocated entries, in UTC
+ALTER TABLE aliases
+    ADD COLUMN valid_from DATETIME NULL,
+    ADD COLUMN valid_until DATETIME NULL;
+
+ALTER TABLE relocated
+    ADD COLUMN valid_from DATETIME NULL,
+    ADD COLUMN valid_until DATETIME NULL;
+
+CREATE INDEX aliases_valid_until_idx ON aliases (valid_until);
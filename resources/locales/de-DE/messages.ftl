@@ -9,9 +9,16 @@ nav-aliases = Aliase
 nav-users = Benutzer
 nav-relays = Relays
 nav-relocated = Umgesiedelt
+nav-alias-domains = Alias-Domains
 nav-clients = Clients
 nav-statistics = Statistiken
 nav-reports = Berichte
+nav-audit = Änderungsprotokoll
+nav-import = Import
+nav-export = Export
+nav-restore = Wiederherstellen
+nav-legacy = Altdaten übernehmen
+nav-sync = Datenbankabgleich
 nav-config = Konfiguration
 nav-database = Datenbankauswahl
 nav-about = Über
@@ -106,6 +113,8 @@ dashboard-total-clients = Gesamte Clients
 dashboard-enabled-domains-and-backups = Aktivierte Domains & Backups
 dashboard-enabled-aliases = Aktivierte Aliase
 dashboard-enabled-users = Aktivierte Benutzer
+dashboard-allocated-quota = Zugewiesenes Kontingent
+dashboard-expiring-aliases = Bald ablaufende Aliase
 dashboard-quick-actions = Schnellaktionen
 dashboard-primary-actions = Primäre Aktionen
 dashboard-advanced-management = Erweiterte Verwaltung
@@ -267,7 +276,14 @@ aliases-placeholder-mail = alias@example.com
 aliases-placeholder-destination = ziel@example.com
 aliases-placeholder-domain = example.com
 aliases-tooltip-mail = Die E-Mail-Adresse für den Alias (z.B. alias@example.com)
-aliases-tooltip-destination = Die Ziel-E-Mail-Adresse, an die E-Mails weitergeleitet werden
+aliases-tooltip-destination = Die Adressen, an die E-Mails weitergeleitet werden; beliebig viele möglich
+aliases-destinations-add = Hinzufügen
+aliases-destinations-remove = Entfernen
+aliases-destinations-empty = Noch keine Ziele
+aliases-destinations-reaches = Erreicht:
+aliases-destinations-invalid = Keine gültige Adresse
+aliases-error-destination-missing = Mindestens ein Ziel angeben.
+aliases-error-destination-invalid = „{ $address }“ ist keine gültige Zieladresse.
 aliases-active = Aktiv
 aliases-tooltip-active = Diesen Alias für E-Mail-Weiterleitung aktivieren
 aliases-cancel = Abbrechen
@@ -275,6 +291,8 @@ aliases-update-alias = Alias aktualisieren
 aliases-create-alias = Alias erstellen
 aliases-search-no-results = Keine passenden Aliase gefunden
 aliases-search-select = Klicken zum Auswählen
+aliases-filter-all = Alle
+aliases-filter-expiring = Läuft bald ab
 
 # Users
 users-title = Benutzer
@@ -326,6 +344,11 @@ users-update-user = Benutzer aktualisieren
 users-password-change-required-label = Passwortänderung erforderlich
 users-password-change-required-yes = Ja
 users-password-change-required-no = Nein
+users-password-hash = Passwort-Hash
+users-password-hash-unknown = Unbekannt
+users-password-needs-rehash = Schwächer als das Schema dieser Datenbank
+users-generate-password = Sicheres Passwort erzeugen
+users-generated-password-notice = Die Passwortfelder wurden ausgefüllt. Kopieren Sie das Passwort jetzt; es wird nicht noch einmal angezeigt.
 users-password-management-title = Passwortverwaltung
 users-change-password-button = Passwort ändern
 users-require-password-change-button = Passwortänderung erzwingen
@@ -336,6 +359,230 @@ users-confirm-password-label = Passwort bestätigen
 users-confirm-password-placeholder = Neues Passwort bestätigen
 users-cancel-button = Abbrechen
 
+# Mailbox quotas
+quota-title = Postfachkontingent
+quota-size = Größenlimit
+quota-messages = Nachrichtenlimit
+quota-default-size = Standard-Größenlimit
+quota-default-messages = Standard-Nachrichtenlimit
+quota-cap = Gesamtgrößenobergrenze
+quota-size-hint = Bytes oder eine Größe wie 512M oder 2G. 0 bedeutet unbegrenzt.
+quota-user-hint = Leer lassen, um den Standard der Domain zu verwenden. 0 bedeutet unbegrenzt.
+quota-domain-hint = Gilt für Postfächer ohne eigenes Kontingent. Leer lassen für unbegrenzt.
+quota-cap-hint = Obergrenze für die Summe der Größenkontingente aller Postfächer der Domain. Leer lassen für keine Obergrenze.
+quota-domain-default = Standard der Domain
+error-quota-cap = Das Kontingent passt nicht unter die Kontingentobergrenze der Domain. In einer begrenzten Domain braucht jedes Postfach ein Größenlimit, und zusammen müssen sie unter der Obergrenze bleiben.
+
+# Alias domains
+alias-domains-title = Alias-Domains
+alias-domains-list-description = Domains, die E-Mails für dieselben Adressen wie eine andere Domain annehmen: benutzer@alt.example wird als benutzer@example.com zugestellt.
+alias-domains-add = Alias-Domain hinzufügen
+alias-domains-new = Neue Alias-Domain
+alias-domains-edit = Alias-Domain bearbeiten
+alias-domains-table-header-alias-domain = Alias-Domain
+alias-domains-table-header-target-domain = Ziel-Domain
+alias-domains-table-header-enabled = Status
+alias-domains-table-header-actions = Aktionen
+alias-domains-empty-title = Keine Alias-Domains
+alias-domains-empty-description = Fügen Sie eine Alias-Domain hinzu, um E-Mails für einen weiteren Namen einer Ihrer Domains anzunehmen.
+alias-domains-show-title = Alias-Domain
+alias-domains-view-edit-settings = Einstellungen der Alias-Domain anzeigen und bearbeiten
+alias-domains-back-to-list = Zurück zu den Alias-Domains
+alias-domains-info-title = Informationen zur Alias-Domain
+alias-domains-info-description = E-Mails an eine Adresse hier gehen an denselben lokalen Teil der Ziel-Domain, wenn diese ein Postfach, einen Alias oder einen Catch-All dafür hat. Aliase auf der Alias-Domain selbst haben Vorrang.
+alias-domains-field-alias-domain = Alias-Domain
+alias-domains-field-target-domain = Ziel-Domain
+alias-domains-field-enabled = Aktiviert
+alias-domains-field-created = Erstellt
+alias-domains-field-modified = Geändert
+alias-domains-field-alias-domain-help = Der Domainname, für den E-Mails ankommen, z. B. alt.example
+alias-domains-field-target-domain-help = Die hiesige Domain, an die ihre E-Mails zugestellt werden
+alias-domains-placeholder-alias-domain = alt.example
+alias-domains-select-target = Domain auswählen
+alias-domains-delete-confirm = Möchten Sie diese Alias-Domain wirklich löschen?
+alias-domains-not-found = Alias-Domain nicht gefunden
+alias-domains-create-error = Alias-Domain konnte nicht erstellt werden
+alias-domains-update-error = Alias-Domain konnte nicht aktualisiert werden
+alias-domains-delete-error = Alias-Domain konnte nicht gelöscht werden
+alias-domains-toggle-error = Status der Alias-Domain konnte nicht geändert werden
+alias-domains-error-invalid-domain = Alias-Domain und Ziel müssen gültige Domainnamen sein.
+alias-domains-error-same-domain = Eine Alias-Domain kann nicht auf sich selbst zeigen.
+alias-domains-error-unknown-target = Das Ziel ist keine hier verwaltete Domain.
+alias-domains-error-domain-exists = Dieser Name ist bereits eine Domain oder Backup-Domain.
+alias-domains-error-exists = Diese Alias-Domain existiert bereits.
+domains-alias-domains-title = Alias-Domains
+domains-alias-domains-description = E-Mails an diese Domains werden an dieselben Adressen hier zugestellt.
+domains-alias-domains-empty = Keine Alias-Domains zeigen auf diese Domain.
+
+# Vacation auto-replies
+vacation-title = Abwesenheitsnotiz
+vacation-status-none = Es ist keine Abwesenheitsnotiz eingerichtet.
+vacation-status-active = Automatische Antworten werden gesendet.
+vacation-status-inactive = Automatische Antworten werden derzeit nicht gesendet.
+vacation-subject = Betreff
+vacation-body = Nachricht
+vacation-active-from = Von
+vacation-active-until = Bis
+vacation-dates-hint = Ein leeres Datum bedeutet keine Begrenzung auf dieser Seite.
+vacation-reply-interval = Tage zwischen Antworten an denselben Absender
+vacation-enabled = Aktiviert
+vacation-save = Abwesenheitsnotiz speichern
+vacation-delete = Abwesenheitsnotiz entfernen
+vacation-delete-confirm = Diese Abwesenheitsnotiz entfernen?
+vacation-saved = Abwesenheitsnotiz gespeichert.
+vacation-error-subject = Geben Sie einen Betreff für die Antwort ein.
+vacation-error-interval = Die Tage zwischen Antworten müssen zwischen 1 und 365 liegen.
+vacation-error-dates = Das Enddatum darf nicht vor dem Startdatum liegen.
+
+# Gültigkeitszeiträume
+validity-title = Gültigkeit
+validity-valid-from = Gültig ab
+validity-valid-until = Gültig bis
+validity-hint = Zeiten sind in UTC. Lassen Sie ein Feld leer, um auf dieser Seite keine Grenze zu setzen; der Eintrag wird zu diesen Zeiten automatisch aktiviert und deaktiviert.
+validity-starts = Geplant
+validity-expires = Läuft ab
+validity-expired = Abgelaufen
+validity-error-window = Gültig bis muss nach Gültig ab liegen.
+
+# Massenimport
+import-title = Massenimport
+import-description = Datensätze aus einer CSV- oder TSV-Datei anlegen und aktualisieren. Geschrieben wird erst, wenn Sie die Vorschau anwenden.
+import-kind = Datensätze
+import-file = Datei
+import-file-hint = Die erste Zeile benennt die Spalten. Komma-, semikolon- und tabulatorgetrennte Dateien bis 2 MB werden akzeptiert.
+import-update-existing = Bestehende Datensätze aktualisieren
+import-preview = Vorschau
+import-mapping-title = Spalten
+import-mapping-ignore = (ignorieren)
+import-update-preview = Vorschau aktualisieren
+import-apply = Import anwenden
+import-apply-hint = Neuanlagen und Änderungen werden in einer Transaktion geschrieben. Zeilen mit Konflikten oder Fehlern werden übersprungen.
+import-download-report = Fehlerbericht herunterladen
+import-start-over = Weitere Datei importieren
+import-table-header-line = Zeile
+import-table-header-key = Datensatz
+import-table-header-status = Ergebnis
+import-table-header-details = Details
+import-status-create = Anlegen
+import-status-update = Aktualisieren
+import-status-unchanged = Unverändert
+import-status-conflict = Konflikt
+import-status-error = Fehler
+import-no-file = Wählen Sie eine Datei zum Importieren.
+import-nothing-to-apply = Es gibt nichts anzulegen oder zu aktualisieren.
+import-applied = { $count } Datensätze geschrieben, { $skipped } Zeilen übersprungen.
+import-failed = Es wurde nichts geschrieben. { $detail }
+import-error-empty = Die Datei hat keine Kopfzeile.
+import-error-csv = Die Datei konnte nicht gelesen werden: { $detail }
+import-error-unknown-header = Die Datei hat keine Spalte „{ $detail }“.
+import-error-unknown-field = Unbekanntes Feld „{ $detail }“.
+import-error-missing-key = Keine Spalte ist { $detail } zugeordnet.
+import-error-duplicate-field = Mehr als eine Spalte ist { $detail } zugeordnet.
+import-error-missing = Die Spalte { $field } ist leer.
+import-error-invalid = Ungültiger Wert: { $detail }
+import-error-password-required = Neue Benutzer brauchen ein Passwort.
+import-error-out-of-scope = Die Adresse liegt außerhalb der Domains, die Sie verwalten.
+import-conflict-duplicate = Steht bereits in Zeile { $line } der Datei.
+import-conflict-exists = Existiert bereits, und das Aktualisieren bestehender Datensätze wurde nicht gewählt.
+
+# Export bundles
+export-title = Export
+export-description = Laden Sie alle Datensätze einer Datenbank als versioniertes JSON- oder YAML-Paket herunter, unabhängig von ihren Spaltennamen. Für externe Sicherungen, die Prüfung der Konfiguration in git oder den Umzug von Mandanten zwischen Servern.
+export-format = Format
+export-password-hashes = Passwort-Hashes einschließen
+export-password-hashes-hint = Wer das Paket besitzt, kann versuchen, sie zu knacken. Bewahren Sie es so sorgfältig auf wie die Datenbank selbst.
+export-download = Herunterladen
+export-no-databases = Sie haben keinen Zugriff auf eine exportierbare Datenbank.
+
+# Restoring bundles
+restore-title = Wiederherstellen
+restore-description = Laden Sie ein Exportpaket in eine Datenbank, auch in eine andere als die Quelle. Es wird nichts geschrieben, bevor Sie die Vorschau anwenden.
+restore-database = Zieldatenbank
+restore-mode = Modus
+restore-mode-merge = Zusammenführen: Datensätze anlegen und aktualisieren, andere behalten
+restore-mode-replace = Ersetzen: auch Datensätze löschen, die nicht im Paket sind
+restore-file = Paket
+restore-file-hint = Ein JSON- oder YAML-Paket aus dem Export, bis 2 MB. Für größere Pakete sortingoffice-cli restore verwenden.
+restore-preview = Vorschau
+restore-summary = Paket von { $source }, exportiert am { $exported }, wiederhergestellt in { $target } ({ $mode }).
+restore-apply = Wiederherstellung anwenden
+restore-apply-hint = Alle Änderungen werden in einer Transaktion geschrieben. Änderungen, die die Datenbank nicht erlaubt, werden übersprungen.
+restore-start-over = Anderes Paket wiederherstellen
+restore-no-changes = Die Datenbank entspricht bereits dem Paket.
+restore-table-header-table = Tabelle
+restore-table-header-key = Datensatz
+restore-table-header-status = Ergebnis
+restore-table-header-details = Details
+restore-status-create = Anlegen
+restore-status-update = Aktualisieren
+restore-status-delete = Löschen
+restore-status-unchanged = Unverändert
+restore-status-error = Nicht erlaubt
+restore-no-file = Wählen Sie ein Paket zur Wiederherstellung.
+restore-no-databases = Sie haben auf keine Datenbank Schreibzugriff, in die wiederhergestellt werden kann.
+restore-applied = { $count } Datensätze geschrieben, { $skipped } Änderungen übersprungen.
+restore-failed = Es wurde nichts geschrieben. { $detail }
+restore-error-syntax = Das Paket konnte nicht gelesen werden: { $detail }
+restore-error-version = Das Paket hat Version { $detail }, die diese Version von Sorting Office nicht lesen kann.
+restore-error-not-allowed = Die Einstellungen der Datenbank erlauben diese Änderung nicht.
+restore-error-password-required = Das Paket enthält keinen Passwort-Hash für diesen neuen Benutzer.
+
+# Legacy import
+legacy-title = Altdaten übernehmen
+legacy-description = Übernehmen Sie die Maildaten eines PostfixAdmin- oder ISPmail-Servers. Die Quelldatenbank wird nur gelesen; ihre Einträge werden umgewandelt und wie eine Wiederherstellung in der Vorschau gezeigt.
+legacy-schema = Aufbau der Quelle
+legacy-url = Quelldatenbank
+legacy-url-hint = Eine mysql://-URL, am besten eines Benutzers mit reinen SELECT-Rechten. Leer lassen, um stattdessen einen Dump hochzuladen.
+legacy-file = Dump
+legacy-file-hint = Ein mysqldump- oder phpMyAdmin-SQL-Export der Quelle, bis 2 MB. Für größere Dumps sortingoffice-cli legacy verwenden.
+legacy-home = Mail-Verzeichnis
+legacy-uid = UID
+legacy-gid = GID
+legacy-preview = Vorschau
+legacy-summary = { $count } Einträge aus { $source } für { $target } umgewandelt ({ $mode }).
+legacy-apply = Übernahme anwenden
+legacy-apply-hint = Alle Änderungen werden in einer Transaktion geschrieben. Änderungen, die die Datenbank nicht erlaubt, werden übersprungen.
+legacy-notes = Nicht übernommen
+legacy-table-header-note = Hinweis
+legacy-table-header-value = Wert
+legacy-no-source = Geben Sie die Quelldatenbank an oder wählen Sie einen Dump.
+legacy-error-connect = Keine Verbindung zur Quelldatenbank: { $detail }
+legacy-error-query = Die Quelldatenbank konnte nicht gelesen werden: { $detail }
+legacy-error-dump = Der Dump konnte nicht gelesen werden: { $detail }
+legacy-note-missing-table = Die Quelle hat keine solche Tabelle.
+legacy-note-dropped-field = Sorting Office hat kein Feld für diesen Wert.
+legacy-note-no-destination = Der Alias hat keine Ziele und wurde ausgelassen.
+legacy-note-autoreply = Abwesenheitsadresse entfernt; Abwesenheitsnotizen werden gesondert übernommen.
+legacy-note-unknown-hash = Der Passwort-Hash nennt sein Verfahren nicht und wurde unverändert übernommen.
+
+# Database sync
+sync-title = Datenbankabgleich
+sync-description = Vergleicht zwei Datenbanken Eintrag für Eintrag und überträgt die ausgewählten Unterschiede von der Quelle ins Ziel. Die Quelle wird nur gelesen.
+sync-source = Quelldatenbank
+sync-target = Zieldatenbank
+sync-tables = Tabellen
+sync-compare = Vergleichen
+sync-summary = { $source } verglichen mit { $target }.
+sync-no-databases = Sie benötigen Zugriff auf eine zweite Datenbank und Schreibrechte auf das Ziel.
+sync-same-database = Wählen Sie zwei verschiedene Datenbanken.
+sync-no-tables = Wählen Sie mindestens eine Tabelle zum Vergleichen.
+sync-no-differences = Die Datenbanken enthalten dieselben Einträge.
+sync-no-selection = Wählen Sie die abzugleichenden Einträge.
+sync-table-header-select = Abgleichen
+sync-table-header-source-modified = Geändert in Quelle
+sync-table-header-target-modified = Geändert im Ziel
+sync-status-create = Fehlt im Ziel
+sync-status-update = Abweichend
+sync-status-delete = Nur im Ziel
+sync-status-unchanged = Identisch
+sync-status-error = Nicht erlaubt
+sync-newer = neuer
+sync-password-differs = Die Passwort-Hashes unterscheiden sich.
+sync-apply = Ausgewählte Einträge abgleichen
+sync-apply-hint = Ausgewählte Einträge werden in einer Transaktion ins Ziel übertragen; Einträge, die nur im Ziel stehen, werden dort gelöscht.
+sync-applied = { $count } Einträge abgeglichen.
+sync-failed = Es wurde nichts geschrieben. { $detail }
+
 # Statistics
 stats-title = Statistiken
 stats-description = Detaillierte Statistiken über Ihren Mail-Server anzeigen.
@@ -360,6 +607,15 @@ stats-quota-usage-description = Überwachen Sie die Festplattennutzung über all
 stats-quota-usage-percentage = Nutzungsprozentsatz
 stats-quota-total = Gesamte Quote
 stats-quota-used = Verwendete Quote
+stats-usage-not-collected = Die Postfachnutzung wird nicht erfasst. Legen Sie die Quelle im Abschnitt [usage] der config.toml fest, um die tatsächliche Nutzung zu sehen.
+stats-usage-last-collected = Zuletzt erfasst
+stats-largest-mailboxes = Größte Postfächer
+stats-table-header-mailbox = Postfach
+stats-table-header-size = Größe
+stats-table-header-messages = Nachrichten
+stats-table-header-quota = Quote
+stats-table-header-growth = Wachstum (30 Tage)
+stats-usage-history = Nutzungsverlauf (30 Tage)
 stats-recent-activity-title = Letzte Aktivität
 stats-recent-domains = Letzte Domains
 stats-recent-users = Letzte Benutzer
@@ -387,7 +643,14 @@ error-duplicate-user = Ein Benutzer mit diesem Benutzernamen existiert bereits.
 error-constraint-violation = Die Daten erfüllen nicht die erforderlichen Einschränkungen. Bitte überprüfen Sie Ihre Eingabe.
 error-operation-not-allowed = Diese Operation ist auf der aktuellen Datenbank aufgrund von Einschränkungen nicht erlaubt.
 error-passwords-do-not-match = Die Passwörter stimmen nicht überein
-error-password-too-short = Das Passwort muss mindestens 8 Zeichen lang sein
+password-policy-too-short = Das Passwort muss mindestens { $min } Zeichen lang sein.
+password-policy-missing-lowercase = Das Passwort muss einen Kleinbuchstaben enthalten.
+password-policy-missing-uppercase = Das Passwort muss einen Großbuchstaben enthalten.
+password-policy-missing-digit = Das Passwort muss eine Ziffer enthalten.
+password-policy-missing-symbol = Das Passwort muss ein Sonderzeichen enthalten.
+password-policy-contains-address = Das Passwort darf die Postfachadresse nicht enthalten.
+password-policy-contains-name = Das Passwort darf den Namen des Benutzers nicht enthalten.
+password-policy-common = Das Passwort ist zu gebräuchlich.
 error-failed-to-update-password = Passwort konnte nicht aktualisiert werden
 
 # Success messages
@@ -403,6 +666,35 @@ login-sign-in = Anmelden
 login-error = Ungültiger Benutzername oder Passwort
 login-error-invalid-credentials = Ungültiger Benutzername oder Passwort. Bitte versuchen Sie es erneut.
 login-error-empty-fields = Bitte geben Sie sowohl Benutzername als auch Passwort ein.
+login-mailbox-link = Postfachbenutzer? Melden Sie sich an, um Ihr Postfach zu verwalten
+
+# Postfachportal
+portal-login-title = Bei Ihrem Postfach anmelden
+portal-login-address = E-Mail-Adresse
+portal-login-admin-link = Anmeldung für Administratoren
+portal-title = Mein Postfach
+portal-sign-out = Abmelden
+portal-must-change-password = Sie müssen ein neues Passwort wählen, bevor Sie fortfahren können.
+portal-password-title = Passwort ändern
+portal-current-password = Aktuelles Passwort
+portal-password-blocked = Passwortänderungen sind für dieses Postfach derzeit deaktiviert.
+portal-password-changed = Ihr Passwort wurde geändert.
+portal-error-current-password = Ihr aktuelles Passwort ist falsch.
+portal-error-same-password = Das neue Passwort muss sich vom aktuellen unterscheiden.
+portal-aliases-title = Adressen, die hierher zustellen
+portal-aliases-help = E-Mails an diese Aliase landen in Ihrem Postfach.
+portal-aliases-empty = Keine Aliase stellen in dieses Postfach zu.
+portal-forwarding-title = Weiterleitung
+portal-forwarding-help = Leiten Sie eingehende E-Mails an andere Adressen weiter. Leer lassen, um die Weiterleitung zu beenden.
+portal-forwarding-addresses = Weiterleiten an (eine Adresse pro Zeile)
+portal-forwarding-keep-copy = Eine Kopie in diesem Postfach behalten
+portal-forwarding-save = Weiterleitung speichern
+portal-forwarding-saved = Ihre Weiterleitung wurde gespeichert.
+portal-forwarding-failed = Ihre Weiterleitung konnte nicht gespeichert werden.
+portal-forwarding-disabled = Die Weiterleitung dieser Adresse wurde von einem Administrator deaktiviert.
+portal-forwarding-blocked = Die Weiterleitung kann derzeit nicht geändert werden.
+portal-forwarding-invalid-address = „{ $address }" ist keine gültige E-Mail-Adresse.
+portal-forwarding-too-many = Sie können an höchstens { $max } Adressen weiterleiten.
 
 # Roles and Permissions
 
@@ -526,12 +818,46 @@ config-feature-no-new-domains = "Keine neuen Domains"
 config-feature-no-password-updates = "Keine Passwort-Updates"
 config-feature-database-disabled = "Datenbank deaktiviert"
 config-status-enabled = "Aktiviert"
-config-status-disabled = "Deaktiviert"
 
 # Database Feature Toggles
 config-database-features-header = "Datenbank-Feature-Toggles"
 config-database-features-description = "Datenbankspezifische Feature-Einschränkungen. Diese überschreiben globale Einstellungen, wenn sie aktiviert sind."
 config-database-disabled-badge = "Deaktiviert"
+config-saved = "Konfiguration gespeichert."
+config-save-failed = "Die Konfiguration konnte nicht gespeichert werden: { $error }"
+config-reload-failed = "Das Neuladen der Konfigurationsdatei ist um { $time } fehlgeschlagen, daher wird weiterhin die vorherige Konfiguration verwendet: { $error }"
+config-mail-config-link = "Mailserver-Konfiguration"
+
+# Mail Server Config
+mail-config-title = "Mailserver-Konfiguration"
+mail-config-description = "Postfix- und Dovecot-Dateien für SQL-Abfragen, erzeugt aus dem Schema und der Feldzuordnung jeder Datenbank. Dazu Sieve-Skripte für Abwesenheitsnotizen (Pigeonhole)."
+mail-config-download-all = "Alles herunterladen (ZIP)"
+mail-config-download-database = "ZIP herunterladen"
+mail-config-no-databases = "Sie haben auf keine Datenbank Bearbeitungszugriff."
+mail-config-credentials-warning = "Diese Dateien enthalten Benutzername und Passwort der Datenbank. Speichern Sie sie nur für Postfix und Dovecot lesbar."
+
+# Delivery Simulator
+reports-delivery-title = "Zustellungssimulator"
+reports-delivery-description = "Verfolgt E-Mail an eine Adresse durch Umzüge, Domains, Aliase und Postfächer so, wie Postfix sie auflöst."
+reports-delivery-address-label = "Empfängeradresse"
+reports-delivery-submit = "Verfolgen"
+reports-delivery-route-header = "Weg"
+reports-delivery-final-header = "Landet bei"
+reports-delivery-no-final = "Die E-Mail wird nirgends zugestellt."
+reports-delivery-problems = "Dieser Weg enthält Schleifen oder Sackgassen."
+reports-delivery-disabled = "Übersprungen, da deaktiviert:"
+reports-delivery-outcome-relocated = "Umgezogen, mit der neuen Adresse abgewiesen"
+reports-delivery-outcome-alias = "Alias"
+reports-delivery-outcome-alias-domain = "Alias-Domain, auf die Ziel-Domain umgeschrieben"
+reports-delivery-outcome-catch-all = "Catch-All-Alias"
+reports-delivery-outcome-mailbox = "Postfach"
+reports-delivery-outcome-relay = "Für Backup-Domain weitergeleitet"
+reports-delivery-outcome-external = "Externe Adresse"
+reports-delivery-outcome-loop = "Schleife, bereits weiter oben aufgelöst"
+reports-delivery-outcome-unknown-user = "Unbekannter Benutzer, abgewiesen"
+reports-delivery-outcome-unknown-domain = "Domain nicht angenommen, abgewiesen"
+reports-delivery-outcome-invalid-address = "Keine E-Mail-Adresse"
+reports-delivery-outcome-too-deep = "Alias-Kette zu tief, nicht verfolgt"
 
 # Matrix Report
 reports-matrix-title = "Domain-Alias-Matrix-Bericht"
@@ -540,6 +866,7 @@ reports-status-present = "Vorhanden und aktiviert"
 reports-status-missing = "Fehlend"
 reports-status-disabled = "Vorhanden aber deaktiviert"
 reports-legend-title = "Status-Legende"
+reports-alias-domain-of = "Alias-Domain von"
 
 # Reports List
 reports-list-title = "Berichte"
@@ -664,3 +991,40 @@ reports-no-password-updates = "No Password Updates"
 reports-database-status-header = "Database Status"
 reports-enabled = "Enabled"
 reports-disabled = "Disabled"
+
+# Audit Log
+audit-title = Änderungsprotokoll
+audit-description = Alle Änderungen an Domains, Benutzern, Aliasen, Backups, Relays, Umzügen und Clients.
+audit-filter-admin = Administrator
+audit-filter-entity = Objekt
+audit-filter-from = Von
+audit-filter-to = Bis
+audit-filter-all = Alle
+audit-filter-apply = Filtern
+audit-filter-reset = Zurücksetzen
+audit-table-header-time = Zeitpunkt
+audit-table-header-admin = Administrator
+audit-table-header-database = Datenbank
+audit-table-header-entity = Objekt
+audit-table-header-name = Element
+audit-table-header-action = Aktion
+audit-table-header-changes = Änderungen
+audit-empty-title = Keine Protokolleinträge
+audit-empty-description = Keine Änderungen entsprechen den ausgewählten Filtern.
+audit-no-field-changes = Keine Feldänderungen
+audit-history-title = Änderungsverlauf
+audit-history-empty = Für dieses Element wurden noch keine Änderungen protokolliert.
+audit-entity-domain = Domain
+audit-entity-user = Benutzer
+audit-entity-alias = Alias
+audit-entity-backup = Backup
+audit-entity-relay = Relay
+audit-entity-relocated = Umgezogen
+audit-entity-client = Client
+audit-entity-vacation = Abwesenheitsnotiz
+audit-entity-alias-domain = Alias-Domain
+audit-action-create = Erstellt
+audit-action-update = Aktualisiert
+audit-action-delete = Gelöscht
+audit-action-toggle = Umgeschaltet
+audit-action-password = Passwort geändert
@@ -9,9 +9,16 @@ nav-aliases = Aliases
 nav-users = Users
 nav-relays = Relays
 nav-relocated = Relocated
+nav-alias-domains = Alias Domains
 nav-clients = Clients
 nav-statistics = Statistics
 nav-reports = Reports
+nav-audit = Audit Log
+nav-import = Import
+nav-export = Export
+nav-restore = Restore
+nav-legacy = Legacy import
+nav-sync = Database sync
 nav-config = Configuration
 nav-database = Database Selection
 nav-about = About
@@ -105,6 +112,8 @@ dashboard-total-clients = Total Clients
 dashboard-enabled-domains-and-backups = Enabled Domains & Backups
 dashboard-enabled-aliases = Enabled Aliases
 dashboard-enabled-users = Enabled Users
+dashboard-allocated-quota = Allocated Quota
+dashboard-expiring-aliases = Aliases expiring soon
 dashboard-quick-actions = Quick Actions
 dashboard-primary-actions = Primary Actions
 dashboard-advanced-management = Advanced Management
@@ -253,7 +262,14 @@ aliases-destination = Destination
 aliases-placeholder-mail = alias@example.com
 aliases-placeholder-destination = destination@example.com
 aliases-tooltip-mail = The email address for the alias (e.g., alias@example.com)
-aliases-tooltip-destination = The destination email address where mail will be forwarded
+aliases-tooltip-destination = The addresses mail is forwarded to; add as many as needed
+aliases-destinations-add = Add
+aliases-destinations-remove = Remove
+aliases-destinations-empty = No destinations yet
+aliases-destinations-reaches = Reaches:
+aliases-destinations-invalid = Not a valid address
+aliases-error-destination-missing = Add at least one destination.
+aliases-error-destination-invalid = "{ $address }" is not a valid destination address.
 aliases-active = Active
 aliases-tooltip-active = Enable this alias for mail forwarding
 aliases-cancel = Cancel
@@ -261,6 +277,8 @@ aliases-update-alias = Update Alias
 aliases-create-alias = Create Alias
 aliases-search-no-results = No matching aliases found
 aliases-search-select = Click to select
+aliases-filter-all = All
+aliases-filter-expiring = Expiring soon
 
 # Users
 users-title = Users
@@ -315,6 +333,11 @@ users-placeholder-password = Enter new password (leave empty to keep existing)
 users-password-change-required-label = Password Change Required
 users-password-change-required-yes = Yes
 users-password-change-required-no = No
+users-password-hash = Password hash
+users-password-hash-unknown = Unknown
+users-password-needs-rehash = Weaker than this database's scheme
+users-generate-password = Generate strong password
+users-generated-password-notice = The password fields have been filled in. Copy the password now; it will not be shown again.
 users-password-management-title = Password Management
 users-change-password-button = Change Password
 users-require-password-change-button = Require Password Change
@@ -325,6 +348,230 @@ users-confirm-password-label = Confirm Password
 users-confirm-password-placeholder = Confirm new password
 users-cancel-button = Cancel
 
+# Mailbox quotas
+quota-title = Mailbox quota
+quota-size = Size limit
+quota-messages = Message limit
+quota-default-size = Default size limit
+quota-default-messages = Default message limit
+quota-cap = Total size cap
+quota-size-hint = Bytes, or a size such as 512M or 2G. 0 means unlimited.
+quota-user-hint = Leave empty to use the domain default. 0 means unlimited.
+quota-domain-hint = Applies to mailboxes without their own quota. Leave empty for unlimited.
+quota-cap-hint = Limit on the sum of all mailbox size quotas in the domain. Leave empty for no cap.
+quota-domain-default = Domain default
+error-quota-cap = The quota does not fit under the domain's quota cap. In a capped domain every mailbox needs a size limit, and together they must stay within the cap.
+
+# Alias domains
+alias-domains-title = Alias Domains
+alias-domains-list-description = Domains that take mail for the same addresses as another domain: user@old.example is delivered as user@example.com.
+alias-domains-add = Add Alias Domain
+alias-domains-new = New Alias Domain
+alias-domains-edit = Edit Alias Domain
+alias-domains-table-header-alias-domain = Alias Domain
+alias-domains-table-header-target-domain = Target Domain
+alias-domains-table-header-enabled = Status
+alias-domains-table-header-actions = Actions
+alias-domains-empty-title = No alias domains
+alias-domains-empty-description = Add an alias domain to accept mail for another name of one of your domains.
+alias-domains-show-title = Alias Domain
+alias-domains-view-edit-settings = View and edit alias domain settings
+alias-domains-back-to-list = Back to Alias Domains
+alias-domains-info-title = Alias Domain Information
+alias-domains-info-description = Mail to an address here goes to the same local part on the target domain, if the target has a mailbox, alias or catch-all for it. Aliases on the alias domain itself take precedence.
+alias-domains-field-alias-domain = Alias Domain
+alias-domains-field-target-domain = Target Domain
+alias-domains-field-enabled = Enabled
+alias-domains-field-created = Created
+alias-domains-field-modified = Modified
+alias-domains-field-alias-domain-help = The domain name mail arrives for, e.g. old.example
+alias-domains-field-target-domain-help = The domain here that its mail is delivered to
+alias-domains-placeholder-alias-domain = old.example
+alias-domains-select-target = Select a domain
+alias-domains-delete-confirm = Are you sure you want to delete this alias domain?
+alias-domains-not-found = Alias domain not found
+alias-domains-create-error = Failed to create alias domain
+alias-domains-update-error = Failed to update alias domain
+alias-domains-delete-error = Failed to delete alias domain
+alias-domains-toggle-error = Failed to toggle alias domain
+alias-domains-error-invalid-domain = Both the alias domain and the target must be valid domain names.
+alias-domains-error-same-domain = An alias domain cannot point at itself.
+alias-domains-error-unknown-target = The target is not a domain managed here.
+alias-domains-error-domain-exists = This name is already a domain or backup domain.
+alias-domains-error-exists = This alias domain already exists.
+domains-alias-domains-title = Alias Domains
+domains-alias-domains-description = Mail to these domains is delivered to the same addresses here.
+domains-alias-domains-empty = No alias domains point at this domain.
+
+# Vacation auto-replies
+vacation-title = Vacation auto-reply
+vacation-status-none = No vacation message is set.
+vacation-status-active = Auto-replies are being sent.
+vacation-status-inactive = Auto-replies are not being sent right now.
+vacation-subject = Subject
+vacation-body = Message
+vacation-active-from = From
+vacation-active-until = Until
+vacation-dates-hint = Leave a date empty for no limit on that side.
+vacation-reply-interval = Days between replies to the same sender
+vacation-enabled = Enabled
+vacation-save = Save vacation
+vacation-delete = Remove vacation
+vacation-delete-confirm = Remove this vacation message?
+vacation-saved = Vacation saved.
+vacation-error-subject = Enter a subject for the reply.
+vacation-error-interval = Days between replies must be between 1 and 365.
+vacation-error-dates = The end date must not be before the start date.
+
+# Validity windows
+validity-title = Validity
+validity-valid-from = Valid from
+validity-valid-until = Valid until
+validity-hint = Times are UTC. Leave a field empty for no limit on that side; the entry is enabled and disabled automatically at these times.
+validity-starts = Scheduled
+validity-expires = Expires
+validity-expired = Expired
+validity-error-window = Valid until must be later than valid from.
+
+# Bulk import
+import-title = Bulk Import
+import-description = Create and update records from a CSV or TSV file. Nothing is written until you apply the preview.
+import-kind = Records
+import-file = File
+import-file-hint = The first row names the columns. Comma, semicolon and tab separated files up to 2 MB are accepted.
+import-update-existing = Update records that already exist
+import-preview = Preview
+import-mapping-title = Columns
+import-mapping-ignore = (ignore)
+import-update-preview = Update preview
+import-apply = Apply import
+import-apply-hint = Creates and updates are written in one transaction. Rows with conflicts or errors are skipped.
+import-download-report = Download error report
+import-start-over = Import another file
+import-table-header-line = Line
+import-table-header-key = Record
+import-table-header-status = Result
+import-table-header-details = Details
+import-status-create = Create
+import-status-update = Update
+import-status-unchanged = Unchanged
+import-status-conflict = Conflict
+import-status-error = Error
+import-no-file = Choose a file to import.
+import-nothing-to-apply = There is nothing to create or update.
+import-applied = { $count } records written, { $skipped } rows skipped.
+import-failed = Nothing was written. { $detail }
+import-error-empty = The file has no header row.
+import-error-csv = The file could not be read: { $detail }
+import-error-unknown-header = The file has no column "{ $detail }".
+import-error-unknown-field = Unknown field "{ $detail }".
+import-error-missing-key = No column is mapped to { $detail }.
+import-error-duplicate-field = More than one column is mapped to { $detail }.
+import-error-missing = The { $field } column is empty.
+import-error-invalid = Invalid value: { $detail }
+import-error-password-required = A password is required for new users.
+import-error-out-of-scope = The address is outside the domains you manage.
+import-conflict-duplicate = Already in the file on line { $line }.
+import-conflict-exists = Already exists and updating existing records was not requested.
+
+# Export bundles
+export-title = Export
+export-description = Download every record of a database as a versioned JSON or YAML bundle, independent of its column names. Use it for off-site backups, reviewing configuration in git or moving tenants between servers.
+export-format = Format
+export-password-hashes = Include password hashes
+export-password-hashes-hint = Anyone holding the bundle can attempt to crack them. Store it as carefully as the database itself.
+export-download = Download
+export-no-databases = You do not have access to any database that can be exported.
+
+# Restoring bundles
+restore-title = Restore
+restore-description = Load an export bundle into a database, which may be another one than it was exported from. Nothing is written until you apply the preview.
+restore-database = Target database
+restore-mode = Mode
+restore-mode-merge = Merge: create and update records, keep the others
+restore-mode-replace = Replace: also delete records the bundle does not contain
+restore-file = Bundle
+restore-file-hint = A JSON or YAML bundle from Export, up to 2 MB. Use sortingoffice-cli restore for larger bundles.
+restore-preview = Preview
+restore-summary = Bundle of { $source } exported { $exported }, restored into { $target } ({ $mode }).
+restore-apply = Apply restore
+restore-apply-hint = All changes are written in one transaction. Changes the database does not allow are skipped.
+restore-start-over = Restore another bundle
+restore-no-changes = The database already matches the bundle.
+restore-table-header-table = Table
+restore-table-header-key = Record
+restore-table-header-status = Result
+restore-table-header-details = Details
+restore-status-create = Create
+restore-status-update = Update
+restore-status-delete = Delete
+restore-status-unchanged = Unchanged
+restore-status-error = Not allowed
+restore-no-file = Choose a bundle to restore.
+restore-no-databases = You do not have edit access to a database to restore into.
+restore-applied = { $count } records written, { $skipped } changes skipped.
+restore-failed = Nothing was written. { $detail }
+restore-error-syntax = The bundle could not be read: { $detail }
+restore-error-version = The bundle is version { $detail }, which this version of Sorting Office cannot read.
+restore-error-not-allowed = The database's settings do not allow this change.
+restore-error-password-required = The bundle has no password hash for this new user.
+
+# Legacy import
+legacy-title = Legacy import
+legacy-description = Take over the mail data of a PostfixAdmin or ISPmail server. The source database is only read; its records are converted and previewed like a restore.
+legacy-schema = Source layout
+legacy-url = Source database
+legacy-url-hint = A mysql:// URL, ideally of a user with only SELECT rights. Leave empty to upload a dump instead.
+legacy-file = Dump
+legacy-file-hint = A mysqldump or phpMyAdmin SQL export of the source, up to 2 MB. Use sortingoffice-cli legacy for larger dumps.
+legacy-home = Mail home
+legacy-uid = UID
+legacy-gid = GID
+legacy-preview = Preview
+legacy-summary = { $count } records converted from { $source } for { $target } ({ $mode }).
+legacy-apply = Apply import
+legacy-apply-hint = All changes are written in one transaction. Changes the database does not allow are skipped.
+legacy-notes = Not converted
+legacy-table-header-note = Note
+legacy-table-header-value = Value
+legacy-no-source = Enter the source database or choose a dump.
+legacy-error-connect = Could not connect to the source database: { $detail }
+legacy-error-query = Could not read the source database: { $detail }
+legacy-error-dump = Could not read the dump: { $detail }
+legacy-note-missing-table = The source has no such table.
+legacy-note-dropped-field = Sorting Office has no field for this value.
+legacy-note-no-destination = The alias has no destinations and was left out.
+legacy-note-autoreply = Vacation address removed; vacation replies are converted separately.
+legacy-note-unknown-hash = The password hash does not name its scheme and was copied unchanged.
+
+# Database sync
+sync-title = Database sync
+sync-description = Compare two databases record by record and copy the chosen differences from the source to the target. The source is only read.
+sync-source = Source database
+sync-target = Target database
+sync-tables = Tables
+sync-compare = Compare
+sync-summary = { $source } compared with { $target }.
+sync-no-databases = You need access to a second database and edit access to the target.
+sync-same-database = Choose two different databases.
+sync-no-tables = Choose at least one table to compare.
+sync-no-differences = The databases hold the same records.
+sync-no-selection = Choose the records to sync.
+sync-table-header-select = Sync
+sync-table-header-source-modified = Modified in source
+sync-table-header-target-modified = Modified in target
+sync-status-create = Missing in target
+sync-status-update = Different
+sync-status-delete = Only in target
+sync-status-unchanged = Identical
+sync-status-error = Not allowed
+sync-newer = newer
+sync-password-differs = The password hashes differ.
+sync-apply = Sync selected records
+sync-apply-hint = Selected records are copied to the target in one transaction; records only in the target are deleted from it.
+sync-applied = { $count } records synced.
+sync-failed = Nothing was written. { $detail }
+
 # Form actions
 form-create-user = Create User
 form-update-user = Update User
@@ -361,6 +608,15 @@ stats-quota-usage-description = Monitor disk space usage across all domains and
 stats-quota-usage-percentage = Usage Percentage
 stats-quota-total = Total Quota
 stats-quota-used = Used Quota
+stats-usage-not-collected = Mailbox usage is not being collected. Set the source in the [usage] section of config.toml to see real usage.
+stats-usage-last-collected = Last collected
+stats-largest-mailboxes = Largest Mailboxes
+stats-table-header-mailbox = Mailbox
+stats-table-header-size = Size
+stats-table-header-messages = Messages
+stats-table-header-quota = Quota
+stats-table-header-growth = Growth (30 days)
+stats-usage-history = Usage History (30 days)
 stats-recent-activity-title = Recent Activity
 stats-recent-domains = Recent Domains
 stats-recent-users = Recent Users
@@ -401,6 +657,37 @@ login-sign-in = Sign in
 login-error = Invalid username or password
 login-error-invalid-credentials = Invalid username or password. Please try again.
 login-error-empty-fields = Please enter both username and password.
+login-mailbox-link = Mailbox user? Sign in to manage your mailbox
+
+# Mailbox Portal
+portal-login-title = Sign in to your mailbox
+portal-login-address = Email address
+portal-login-admin-link = Administrator sign in
+portal-title = My Mailbox
+portal-sign-out = Sign out
+portal-must-change-password = You must choose a new password before you can continue.
+portal-password-title = Change Password
+portal-current-password = Current Password
+portal-password-blocked = Password changes are currently disabled for this mailbox.
+portal-password-changed = Your password has been changed.
+portal-error-current-password = Your current password is incorrect.
+portal-error-same-password = The new password must be different from the current one.
+portal-aliases-title = Addresses Delivering Here
+portal-aliases-help = Mail sent to these aliases ends up in your mailbox.
+portal-aliases-empty = No aliases deliver to this mailbox.
+portal-forwarding-title = Forwarding
+portal-forwarding-help = Forward incoming mail to other addresses. Leave empty to stop forwarding.
+portal-forwarding-addresses = Forward to (one address per line)
+portal-forwarding-keep-copy = Keep a copy in this mailbox
+portal-forwarding-save = Save Forwarding
+portal-forwarding-saved = Your forwarding has been saved.
+portal-forwarding-failed = Failed to save your forwarding.
+portal-forwarding-disabled = Forwarding for this address has been disabled by an administrator.
+portal-forwarding-blocked = Forwarding cannot be changed right now.
+portal-forwarding-invalid-address = "{ $address }" is not a valid email address.
+portal-forwarding-too-many = You can forward to at most { $max } addresses.
+error-passwords-do-not-match = Passwords do not match
+error-failed-to-update-password = Failed to update password
 
 # Roles and Permissions
 
@@ -525,12 +812,46 @@ config-feature-no-new-domains = "No New Domains"
 config-feature-no-password-updates = "No Password Updates"
 config-feature-database-disabled = "Database Disabled"
 config-status-enabled = "Enabled"
-config-status-disabled = "Disabled"
 
 # Database Feature Toggles
 config-database-features-header = "Database Feature Toggles"
 config-database-features-description = "Database-specific feature restrictions. These override global settings when enabled."
 config-database-disabled-badge = "Disabled"
+config-saved = "Configuration saved."
+config-save-failed = "Could not save the configuration: { $error }"
+config-reload-failed = "Reloading the config file failed at { $time }, so the previous configuration is still in use: { $error }"
+config-mail-config-link = "Mail Server Config"
+
+# Mail Server Config
+mail-config-title = "Mail Server Config"
+mail-config-description = "Postfix and Dovecot SQL lookup files generated from each database schema and field map. Includes Sieve scripts for vacation auto-replies (Pigeonhole)."
+mail-config-download-all = "Download All (ZIP)"
+mail-config-download-database = "Download ZIP"
+mail-config-no-databases = "You do not have edit access to any database."
+mail-config-credentials-warning = "These files contain the database username and password. Store them readable only by Postfix and Dovecot."
+
+# Delivery Simulator
+reports-delivery-title = "Delivery Simulator"
+reports-delivery-description = "Follow mail to an address through relocated entries, domains, aliases and mailboxes the way Postfix resolves it."
+reports-delivery-address-label = "Recipient address"
+reports-delivery-submit = "Trace"
+reports-delivery-route-header = "Route"
+reports-delivery-final-header = "Ends up at"
+reports-delivery-no-final = "The mail is not delivered anywhere."
+reports-delivery-problems = "This route has loops or dead ends."
+reports-delivery-disabled = "Skipped because disabled:"
+reports-delivery-outcome-relocated = "Relocated, bounced with the new address"
+reports-delivery-outcome-alias = "Alias"
+reports-delivery-outcome-alias-domain = "Alias domain, rewritten to the target domain"
+reports-delivery-outcome-catch-all = "Catch-all alias"
+reports-delivery-outcome-mailbox = "Mailbox"
+reports-delivery-outcome-relay = "Relayed for backup domain"
+reports-delivery-outcome-external = "External address"
+reports-delivery-outcome-loop = "Loop, already expanded above"
+reports-delivery-outcome-unknown-user = "Unknown user, rejected"
+reports-delivery-outcome-unknown-domain = "Domain not accepted, rejected"
+reports-delivery-outcome-invalid-address = "Not an email address"
+reports-delivery-outcome-too-deep = "Alias chain too deep, not followed"
 
 # Matrix Report
 reports-matrix-title = "Domain Alias Matrix Report"
@@ -539,6 +860,7 @@ reports-status-present = "Present and enabled"
 reports-status-missing = "Missing"
 reports-status-disabled = "Present but disabled"
 reports-legend-title = "Status Legend"
+reports-alias-domain-of = "alias domain of"
 
 # Reports List
 reports-list-title = "Reports"
@@ -708,3 +1030,50 @@ clients-status-disabled = Disabled
 # Domain Search
 domains-search-no-results = No domains found
 domains-search-select = Select a domain
+
+# Audit Log
+audit-title = Audit Log
+audit-description = Every change made to domains, users, aliases, backups, relays, relocated entries and clients.
+audit-filter-admin = Admin
+audit-filter-entity = Entity
+audit-filter-from = From
+audit-filter-to = To
+audit-filter-all = All
+audit-filter-apply = Filter
+audit-filter-reset = Reset
+audit-table-header-time = Time
+audit-table-header-admin = Admin
+audit-table-header-database = Database
+audit-table-header-entity = Entity
+audit-table-header-name = Item
+audit-table-header-action = Action
+audit-table-header-changes = Changes
+audit-empty-title = No audit entries
+audit-empty-description = No changes match the selected filters.
+audit-no-field-changes = No field changes
+audit-history-title = Change History
+audit-history-empty = No changes have been recorded for this item yet.
+audit-entity-domain = Domain
+audit-entity-user = User
+audit-entity-alias = Alias
+audit-entity-backup = Backup
+audit-entity-relay = Relay
+audit-entity-relocated = Relocated
+audit-entity-client = Client
+audit-entity-vacation = Vacation
+audit-entity-alias-domain = Alias domain
+audit-action-create = Created
+audit-action-update = Updated
+audit-action-delete = Deleted
+audit-action-toggle = Toggled
+audit-action-password = Password changed
+
+# Password Policy
+password-policy-too-short = Password must be at least { $min } characters long.
+password-policy-missing-lowercase = Password must contain a lowercase letter.
+password-policy-missing-uppercase = Password must contain an uppercase letter.
+password-policy-missing-digit = Password must contain a digit.
+password-policy-missing-symbol = Password must contain a symbol.
+password-policy-contains-address = Password must not contain the mailbox address.
+password-policy-contains-name = Password must not contain the user's name.
+password-policy-common = Password is too common.
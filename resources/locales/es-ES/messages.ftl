@@ -9,9 +9,16 @@ nav-aliases = "Alias"
 nav-users = "Usuarios"
 nav-relays = "Relés"
 nav-relocated = "Reubicados"
+nav-alias-domains = "Dominios alias"
 nav-clients = "Clientes"
 nav-statistics = "Estadísticas"
 nav-reports = "Informes"
+nav-audit = "Registro de auditoría"
+nav-import = "Importar"
+nav-export = "Exportar"
+nav-restore = "Restaurar"
+nav-legacy = "Importación heredada"
+nav-sync = "Sincronización"
 nav-config = "Configuración"
 nav-database = "Selección de Base de Datos"
 nav-about = "Acerca de"
@@ -104,6 +111,8 @@ dashboard-total-clients = Total de Clientes
 dashboard-enabled-domains-and-backups = Dominios y Copias Habilitados
 dashboard-enabled-aliases = Alias Habilitados
 dashboard-enabled-users = Usuarios Habilitados
+dashboard-allocated-quota = Cuota Asignada
+dashboard-expiring-aliases = Alias que caducan pronto
 dashboard-quick-actions = Acciones Rápidas
 dashboard-primary-actions = Acciones Principales
 dashboard-advanced-management = Gestión Avanzada
@@ -263,12 +272,21 @@ aliases-placeholder-mail = alias@ejemplo.com
 aliases-placeholder-destination = destino@ejemplo.com
 aliases-placeholder-domain = ejemplo.com
 aliases-tooltip-mail = La dirección de correo electrónico para el alias (ej., alias@ejemplo.com)
-aliases-tooltip-destination = La dirección de correo electrónico de destino donde se reenviará el correo
+aliases-tooltip-destination = Las direcciones a las que se reenvía el correo; añada tantas como necesite
+aliases-destinations-add = Añadir
+aliases-destinations-remove = Quitar
+aliases-destinations-empty = Todavía no hay destinos
+aliases-destinations-reaches = Llega a:
+aliases-destinations-invalid = No es una dirección válida
+aliases-error-destination-missing = Añada al menos un destino.
+aliases-error-destination-invalid = "{ $address }" no es una dirección de destino válida.
 aliases-active = Activo
 aliases-tooltip-active = Habilitar este alias para el reenvío de correo
 aliases-cancel = Cancelar
 aliases-update-alias = Actualizar Alias
 aliases-create-alias = Crear Alias
+aliases-filter-all = Todos
+aliases-filter-expiring = Caducan pronto
 
 # Users
 users-title = Usuarios
@@ -320,6 +338,11 @@ users-update-user = Actualizar Usuario
 users-password-change-required-label = Cambio de contraseña requerido
 users-password-change-required-yes = Sí
 users-password-change-required-no = No
+users-password-hash = Hash de la contraseña
+users-password-hash-unknown = Desconocido
+users-password-needs-rehash = Más débil que el esquema de esta base de datos
+users-generate-password = Generar contraseña segura
+users-generated-password-notice = Se han rellenado los campos de contraseña. Copie la contraseña ahora; no se volverá a mostrar.
 users-password-management-title = Gestión de contraseñas
 users-change-password-button = Cambiar contraseña
 users-require-password-change-button = Requerir cambio de contraseña
@@ -330,6 +353,230 @@ users-confirm-password-label = Confirmar contraseña
 users-confirm-password-placeholder = Confirme la nueva contraseña
 users-cancel-button = Cancelar
 
+# Mailbox quotas
+quota-title = Cuota del buzón
+quota-size = Límite de tamaño
+quota-messages = Límite de mensajes
+quota-default-size = Límite de tamaño predeterminado
+quota-default-messages = Límite de mensajes predeterminado
+quota-cap = Límite total de tamaño
+quota-size-hint = Bytes, o un tamaño como 512M o 2G. 0 significa ilimitado.
+quota-user-hint = Déjelo vacío para usar el valor predeterminado del dominio. 0 significa ilimitado.
+quota-domain-hint = Se aplica a los buzones sin cuota propia. Déjelo vacío para ilimitado.
+quota-cap-hint = Límite para la suma de las cuotas de tamaño de todos los buzones del dominio. Déjelo vacío para no limitar.
+quota-domain-default = Predeterminado del dominio
+error-quota-cap = La cuota no cabe dentro del límite de cuota del dominio. En un dominio limitado cada buzón necesita un límite de tamaño y juntos deben quedar dentro del límite.
+
+# Alias domains
+alias-domains-title = Dominios alias
+alias-domains-list-description = Dominios que reciben correo para las mismas direcciones que otro dominio: usuario@antiguo.example se entrega como usuario@example.com.
+alias-domains-add = Añadir dominio alias
+alias-domains-new = Nuevo dominio alias
+alias-domains-edit = Editar dominio alias
+alias-domains-table-header-alias-domain = Dominio alias
+alias-domains-table-header-target-domain = Dominio de destino
+alias-domains-table-header-enabled = Estado
+alias-domains-table-header-actions = Acciones
+alias-domains-empty-title = No hay dominios alias
+alias-domains-empty-description = Añada un dominio alias para aceptar correo para otro nombre de uno de sus dominios.
+alias-domains-show-title = Dominio alias
+alias-domains-view-edit-settings = Ver y editar la configuración del dominio alias
+alias-domains-back-to-list = Volver a dominios alias
+alias-domains-info-title = Información del dominio alias
+alias-domains-info-description = El correo a una dirección de este dominio va a la misma parte local en el dominio de destino, si este tiene un buzón, alias o catch-all para ella. Los alias del propio dominio alias tienen prioridad.
+alias-domains-field-alias-domain = Dominio alias
+alias-domains-field-target-domain = Dominio de destino
+alias-domains-field-enabled = Habilitado
+alias-domains-field-created = Creado
+alias-domains-field-modified = Modificado
+alias-domains-field-alias-domain-help = El nombre de dominio para el que llega el correo, p. ej. antiguo.example
+alias-domains-field-target-domain-help = El dominio de aquí al que se entrega su correo
+alias-domains-placeholder-alias-domain = antiguo.example
+alias-domains-select-target = Seleccione un dominio
+alias-domains-delete-confirm = ¿Está seguro de que desea eliminar este dominio alias?
+alias-domains-not-found = Dominio alias no encontrado
+alias-domains-create-error = Error al crear el dominio alias
+alias-domains-update-error = Error al actualizar el dominio alias
+alias-domains-delete-error = Error al eliminar el dominio alias
+alias-domains-toggle-error = Error al cambiar el estado del dominio alias
+alias-domains-error-invalid-domain = El dominio alias y el de destino deben ser nombres de dominio válidos.
+alias-domains-error-same-domain = Un dominio alias no puede apuntar a sí mismo.
+alias-domains-error-unknown-target = El destino no es un dominio gestionado aquí.
+alias-domains-error-domain-exists = Este nombre ya es un dominio o un dominio de respaldo.
+alias-domains-error-exists = Este dominio alias ya existe.
+domains-alias-domains-title = Dominios alias
+domains-alias-domains-description = El correo a estos dominios se entrega a las mismas direcciones de aquí.
+domains-alias-domains-empty = Ningún dominio alias apunta a este dominio.
+
+# Vacation auto-replies
+vacation-title = Respuesta automática de vacaciones
+vacation-status-none = No hay ningún mensaje de vacaciones.
+vacation-status-active = Se están enviando respuestas automáticas.
+vacation-status-inactive = Ahora mismo no se envían respuestas automáticas.
+vacation-subject = Asunto
+vacation-body = Mensaje
+vacation-active-from = Desde
+vacation-active-until = Hasta
+vacation-dates-hint = Deje una fecha vacía para no poner límite por ese lado.
+vacation-reply-interval = Días entre respuestas al mismo remitente
+vacation-enabled = Activado
+vacation-save = Guardar vacaciones
+vacation-delete = Eliminar vacaciones
+vacation-delete-confirm = ¿Eliminar este mensaje de vacaciones?
+vacation-saved = Vacaciones guardadas.
+vacation-error-subject = Introduzca un asunto para la respuesta.
+vacation-error-interval = Los días entre respuestas deben estar entre 1 y 365.
+vacation-error-dates = La fecha de fin no puede ser anterior a la de inicio.
+
+# Ventanas de validez
+validity-title = Validez
+validity-valid-from = Válido desde
+validity-valid-until = Válido hasta
+validity-hint = Las horas están en UTC. Deje un campo vacío para no tener límite por ese lado; la entrada se activa y desactiva automáticamente en esas horas.
+validity-starts = Programado
+validity-expires = Caduca
+validity-expired = Caducado
+validity-error-window = Válido hasta debe ser posterior a válido desde.
+
+# Importación masiva
+import-title = Importación masiva
+import-description = Cree y actualice registros desde un archivo CSV o TSV. No se escribe nada hasta que aplique la vista previa.
+import-kind = Registros
+import-file = Archivo
+import-file-hint = La primera fila nombra las columnas. Se aceptan archivos separados por comas, punto y coma o tabuladores de hasta 2 MB.
+import-update-existing = Actualizar los registros que ya existen
+import-preview = Vista previa
+import-mapping-title = Columnas
+import-mapping-ignore = (ignorar)
+import-update-preview = Actualizar vista previa
+import-apply = Aplicar importación
+import-apply-hint = Las creaciones y actualizaciones se escriben en una sola transacción. Las filas con conflictos o errores se omiten.
+import-download-report = Descargar informe de errores
+import-start-over = Importar otro archivo
+import-table-header-line = Línea
+import-table-header-key = Registro
+import-table-header-status = Resultado
+import-table-header-details = Detalles
+import-status-create = Crear
+import-status-update = Actualizar
+import-status-unchanged = Sin cambios
+import-status-conflict = Conflicto
+import-status-error = Error
+import-no-file = Elija un archivo para importar.
+import-nothing-to-apply = No hay nada que crear ni actualizar.
+import-applied = { $count } registros escritos, { $skipped } filas omitidas.
+import-failed = No se escribió nada. { $detail }
+import-error-empty = El archivo no tiene fila de encabezado.
+import-error-csv = No se pudo leer el archivo: { $detail }
+import-error-unknown-header = El archivo no tiene la columna "{ $detail }".
+import-error-unknown-field = Campo desconocido "{ $detail }".
+import-error-missing-key = Ninguna columna está asignada a { $detail }.
+import-error-duplicate-field = Más de una columna está asignada a { $detail }.
+import-error-missing = La columna { $field } está vacía.
+import-error-invalid = Valor no válido: { $detail }
+import-error-password-required = Los usuarios nuevos necesitan una contraseña.
+import-error-out-of-scope = La dirección está fuera de los dominios que administra.
+import-conflict-duplicate = Ya aparece en el archivo en la línea { $line }.
+import-conflict-exists = Ya existe y no se pidió actualizar los registros existentes.
+
+# Export bundles
+export-title = Exportar
+export-description = Descargue todos los registros de una base de datos como un paquete JSON o YAML versionado, independiente de los nombres de sus columnas. Úselo para copias de seguridad externas, revisar la configuración en git o mover clientes entre servidores.
+export-format = Formato
+export-password-hashes = Incluir hashes de contraseñas
+export-password-hashes-hint = Cualquiera que tenga el paquete puede intentar descifrarlos. Guárdelo con el mismo cuidado que la propia base de datos.
+export-download = Descargar
+export-no-databases = No tiene acceso a ninguna base de datos que se pueda exportar.
+
+# Restoring bundles
+restore-title = Restaurar
+restore-description = Cargue un paquete de exportación en una base de datos, que puede ser distinta de la de origen. No se escribe nada hasta que aplique la vista previa.
+restore-database = Base de datos de destino
+restore-mode = Modo
+restore-mode-merge = Combinar: crear y actualizar registros, conservar los demás
+restore-mode-replace = Reemplazar: también eliminar los registros que el paquete no contiene
+restore-file = Paquete
+restore-file-hint = Un paquete JSON o YAML de Exportar, de hasta 2 MB. Use sortingoffice-cli restore para paquetes más grandes.
+restore-preview = Vista previa
+restore-summary = Paquete de { $source } exportado el { $exported }, restaurado en { $target } ({ $mode }).
+restore-apply = Aplicar restauración
+restore-apply-hint = Todos los cambios se escriben en una transacción. Se omiten los cambios que la base de datos no permite.
+restore-start-over = Restaurar otro paquete
+restore-no-changes = La base de datos ya coincide con el paquete.
+restore-table-header-table = Tabla
+restore-table-header-key = Registro
+restore-table-header-status = Resultado
+restore-table-header-details = Detalles
+restore-status-create = Crear
+restore-status-update = Actualizar
+restore-status-delete = Eliminar
+restore-status-unchanged = Sin cambios
+restore-status-error = No permitido
+restore-no-file = Elija un paquete para restaurar.
+restore-no-databases = No tiene acceso de edición a ninguna base de datos en la que restaurar.
+restore-applied = { $count } registros escritos, { $skipped } cambios omitidos.
+restore-failed = No se escribió nada. { $detail }
+restore-error-syntax = No se pudo leer el paquete: { $detail }
+restore-error-version = El paquete es de la versión { $detail }, que esta versión de Sorting Office no puede leer.
+restore-error-not-allowed = La configuración de la base de datos no permite este cambio.
+restore-error-password-required = El paquete no tiene hash de contraseña para este usuario nuevo.
+
+# Legacy import
+legacy-title = Importación heredada
+legacy-description = Asuma los datos de correo de un servidor PostfixAdmin o ISPmail. La base de datos de origen solo se lee; sus registros se convierten y se previsualizan como una restauración.
+legacy-schema = Estructura del origen
+legacy-url = Base de datos de origen
+legacy-url-hint = Una URL mysql://, idealmente de un usuario con solo permisos SELECT. Déjela vacía para subir un volcado.
+legacy-file = Volcado
+legacy-file-hint = Un volcado de mysqldump o una exportación SQL de phpMyAdmin del origen, de hasta 2 MB. Use sortingoffice-cli legacy para volcados más grandes.
+legacy-home = Directorio de correo
+legacy-uid = UID
+legacy-gid = GID
+legacy-preview = Vista previa
+legacy-summary = { $count } registros convertidos de { $source } para { $target } ({ $mode }).
+legacy-apply = Aplicar importación
+legacy-apply-hint = Todos los cambios se escriben en una transacción. Los cambios que la base de datos no permite se omiten.
+legacy-notes = No convertido
+legacy-table-header-note = Nota
+legacy-table-header-value = Valor
+legacy-no-source = Indique la base de datos de origen o elija un volcado.
+legacy-error-connect = No se pudo conectar a la base de datos de origen: { $detail }
+legacy-error-query = No se pudo leer la base de datos de origen: { $detail }
+legacy-error-dump = No se pudo leer el volcado: { $detail }
+legacy-note-missing-table = El origen no tiene esta tabla.
+legacy-note-dropped-field = Sorting Office no tiene un campo para este valor.
+legacy-note-no-destination = El alias no tiene destinos y se omitió.
+legacy-note-autoreply = Dirección de vacaciones eliminada; las respuestas de vacaciones se convierten aparte.
+legacy-note-unknown-hash = El hash de la contraseña no indica su esquema y se copió sin cambios.
+
+# Database sync
+sync-title = Sincronización de bases de datos
+sync-description = Compara dos bases de datos registro a registro y copia las diferencias elegidas del origen al destino. El origen solo se lee.
+sync-source = Base de datos de origen
+sync-target = Base de datos de destino
+sync-tables = Tablas
+sync-compare = Comparar
+sync-summary = { $source } comparada con { $target }.
+sync-no-databases = Necesita acceso a una segunda base de datos y permiso de edición en el destino.
+sync-same-database = Elija dos bases de datos distintas.
+sync-no-tables = Elija al menos una tabla para comparar.
+sync-no-differences = Las bases de datos contienen los mismos registros.
+sync-no-selection = Elija los registros que desea sincronizar.
+sync-table-header-select = Sincronizar
+sync-table-header-source-modified = Modificado en origen
+sync-table-header-target-modified = Modificado en destino
+sync-status-create = Falta en destino
+sync-status-update = Distinto
+sync-status-delete = Solo en destino
+sync-status-unchanged = Idéntico
+sync-status-error = No permitido
+sync-newer = más reciente
+sync-password-differs = Los hashes de contraseña difieren.
+sync-apply = Sincronizar registros seleccionados
+sync-apply-hint = Los registros seleccionados se copian al destino en una sola transacción; los registros que solo están en el destino se eliminan de él.
+sync-applied = { $count } registros sincronizados.
+sync-failed = No se escribió nada. { $detail }
+
 # Statistics
 stats-title = Estadísticas
 stats-description = Ver estadísticas detalladas sobre su servidor de correo.
@@ -354,6 +601,15 @@ stats-quota-usage-description = Monitorear el uso de espacio en disco en todos l
 stats-quota-usage-percentage = Porcentaje de Uso
 stats-quota-total = Cuota Total
 stats-quota-used = Cuota Utilizada
+stats-usage-not-collected = No se está recopilando el uso de los buzones. Configure el origen en la sección [usage] de config.toml para ver el uso real.
+stats-usage-last-collected = Última recopilación
+stats-largest-mailboxes = Buzones Más Grandes
+stats-table-header-mailbox = Buzón
+stats-table-header-size = Tamaño
+stats-table-header-messages = Mensajes
+stats-table-header-quota = Cuota
+stats-table-header-growth = Crecimiento (30 días)
+stats-usage-history = Historial de Uso (30 días)
 stats-recent-activity-title = Actividad Reciente
 stats-recent-domains = Dominios Recientes
 stats-recent-users = Usuarios Recientes
@@ -381,7 +637,14 @@ error-duplicate-user = Ya existe un usuario con este nombre de usuario.
 error-constraint-violation = Los datos no cumplen con las restricciones requeridas. Por favor verifique su entrada.
 error-operation-not-allowed = Esta operación no está permitida en la base de datos actual debido a restricciones.
 error-passwords-do-not-match = Las contraseñas no coinciden
-error-password-too-short = La contraseña debe tener al menos 8 caracteres
+password-policy-too-short = La contraseña debe tener al menos { $min } caracteres.
+password-policy-missing-lowercase = La contraseña debe contener una letra minúscula.
+password-policy-missing-uppercase = La contraseña debe contener una letra mayúscula.
+password-policy-missing-digit = La contraseña debe contener un dígito.
+password-policy-missing-symbol = La contraseña debe contener un símbolo.
+password-policy-contains-address = La contraseña no debe contener la dirección del buzón.
+password-policy-contains-name = La contraseña no debe contener el nombre del usuario.
+password-policy-common = La contraseña es demasiado común.
 error-failed-to-update-password = No se pudo actualizar la contraseña
 
 # Success messages
@@ -395,6 +658,35 @@ login-user-id = ID de Usuario
 login-password = Contraseña
 login-sign-in = Iniciar sesión
 login-error = Error
+login-mailbox-link = ¿Usuario de buzón? Inicie sesión para gestionar su buzón
+
+# Portal del buzón
+portal-login-title = Iniciar sesión en su buzón
+portal-login-address = Dirección de correo
+portal-login-admin-link = Acceso de administradores
+portal-title = Mi buzón
+portal-sign-out = Cerrar sesión
+portal-must-change-password = Debe elegir una nueva contraseña antes de continuar.
+portal-password-title = Cambiar contraseña
+portal-current-password = Contraseña actual
+portal-password-blocked = Los cambios de contraseña están desactivados para este buzón.
+portal-password-changed = Su contraseña ha sido cambiada.
+portal-error-current-password = Su contraseña actual es incorrecta.
+portal-error-same-password = La nueva contraseña debe ser distinta de la actual.
+portal-aliases-title = Direcciones que entregan aquí
+portal-aliases-help = El correo enviado a estos alias llega a su buzón.
+portal-aliases-empty = Ningún alias entrega en este buzón.
+portal-forwarding-title = Reenvío
+portal-forwarding-help = Reenvíe el correo entrante a otras direcciones. Déjelo vacío para dejar de reenviar.
+portal-forwarding-addresses = Reenviar a (una dirección por línea)
+portal-forwarding-keep-copy = Conservar una copia en este buzón
+portal-forwarding-save = Guardar reenvío
+portal-forwarding-saved = Su reenvío ha sido guardado.
+portal-forwarding-failed = No se pudo guardar su reenvío.
+portal-forwarding-disabled = Un administrador ha desactivado el reenvío de esta dirección.
+portal-forwarding-blocked = El reenvío no se puede cambiar en este momento.
+portal-forwarding-invalid-address = "{ $address }" no es una dirección de correo válida.
+portal-forwarding-too-many = Puede reenviar a un máximo de { $max } direcciones.
 
 # Roles and Permissions
 
@@ -518,12 +810,46 @@ config-feature-no-new-domains = "Sin Nuevos Dominios"
 config-feature-no-password-updates = "Sin Actualizaciones de Contraseña"
 config-feature-database-disabled = "Base de Datos Deshabilitada"
 config-status-enabled = "Habilitado"
-config-status-disabled = "Deshabilitado"
 
 # Database Feature Toggles
 config-database-features-header = "Interruptores de Características de Base de Datos"
 config-database-features-description = "Restricciones de características específicas de la base de datos. Estos anulan los ajustes globales cuando están habilitados."
 config-database-disabled-badge = "Deshabilitado"
+config-saved = "Configuración guardada."
+config-save-failed = "No se pudo guardar la configuración: { $error }"
+config-reload-failed = "La recarga del archivo de configuración falló a las { $time }, por lo que se sigue usando la configuración anterior: { $error }"
+config-mail-config-link = "Configuración del servidor de correo"
+
+# Mail Server Config
+mail-config-title = "Configuración del servidor de correo"
+mail-config-description = "Archivos de consulta SQL de Postfix y Dovecot generados a partir del esquema y el mapa de campos de cada base de datos. Incluye scripts Sieve para las respuestas automáticas de vacaciones (Pigeonhole)."
+mail-config-download-all = "Descargar todo (ZIP)"
+mail-config-download-database = "Descargar ZIP"
+mail-config-no-databases = "No tiene acceso de edición a ninguna base de datos."
+mail-config-credentials-warning = "Estos archivos contienen el usuario y la contraseña de la base de datos. Guárdelos con lectura solo para Postfix y Dovecot."
+
+# Delivery Simulator
+reports-delivery-title = "Simulador de entrega"
+reports-delivery-description = "Sigue el correo a una dirección a través de reubicaciones, dominios, alias y buzones tal como lo resuelve Postfix."
+reports-delivery-address-label = "Dirección del destinatario"
+reports-delivery-submit = "Rastrear"
+reports-delivery-route-header = "Ruta"
+reports-delivery-final-header = "Termina en"
+reports-delivery-no-final = "El correo no se entrega en ningún sitio."
+reports-delivery-problems = "Esta ruta tiene bucles o callejones sin salida."
+reports-delivery-disabled = "Omitido por estar deshabilitado:"
+reports-delivery-outcome-relocated = "Reubicado, rebotado con la nueva dirección"
+reports-delivery-outcome-alias = "Alias"
+reports-delivery-outcome-alias-domain = "Dominio alias, reescrito al dominio de destino"
+reports-delivery-outcome-catch-all = "Alias comodín"
+reports-delivery-outcome-mailbox = "Buzón"
+reports-delivery-outcome-relay = "Reenviado para dominio de respaldo"
+reports-delivery-outcome-external = "Dirección externa"
+reports-delivery-outcome-loop = "Bucle, ya expandido arriba"
+reports-delivery-outcome-unknown-user = "Usuario desconocido, rechazado"
+reports-delivery-outcome-unknown-domain = "Dominio no aceptado, rechazado"
+reports-delivery-outcome-invalid-address = "No es una dirección de correo"
+reports-delivery-outcome-too-deep = "Cadena de alias demasiado profunda, no se sigue"
 
 # Matrix Report
 reports-matrix-title = "Reporte de Matriz de Alias de Dominios"
@@ -532,6 +858,7 @@ reports-status-present = "Presente y habilitado"
 reports-status-missing = "Faltante"
 reports-status-disabled = "Presente pero deshabilitado"
 reports-legend-title = "Leyenda de Estado"
+reports-alias-domain-of = "dominio alias de"
 
 # Reports List
 reports-list-title = "Informes"
@@ -645,3 +972,40 @@ clients-action-enable = Habilitar
 clients-action-disable = Deshabilitar
 clients-status-enabled = Habilitado
 clients-status-disabled = Deshabilitado
+
+# Audit Log
+audit-title = "Registro de auditoría"
+audit-description = "Todos los cambios realizados en dominios, usuarios, alias, copias de seguridad, relays, reubicaciones y clientes."
+audit-filter-admin = "Administrador"
+audit-filter-entity = "Entidad"
+audit-filter-from = "Desde"
+audit-filter-to = "Hasta"
+audit-filter-all = "Todos"
+audit-filter-apply = "Filtrar"
+audit-filter-reset = "Restablecer"
+audit-table-header-time = "Fecha"
+audit-table-header-admin = "Administrador"
+audit-table-header-database = "Base de datos"
+audit-table-header-entity = "Entidad"
+audit-table-header-name = "Elemento"
+audit-table-header-action = "Acción"
+audit-table-header-changes = "Cambios"
+audit-empty-title = "Sin entradas de auditoría"
+audit-empty-description = "Ningún cambio coincide con los filtros seleccionados."
+audit-no-field-changes = "Sin cambios de campos"
+audit-history-title = "Historial de cambios"
+audit-history-empty = "Todavía no se han registrado cambios para este elemento."
+audit-entity-domain = "Dominio"
+audit-entity-user = "Usuario"
+audit-entity-alias = "Alias"
+audit-entity-backup = "Copia de seguridad"
+audit-entity-relay = "Relay"
+audit-entity-relocated = "Reubicado"
+audit-entity-client = "Cliente"
+audit-entity-vacation = "Respuesta automática"
+audit-entity-alias-domain = "Dominio alias"
+audit-action-create = "Creado"
+audit-action-update = "Actualizado"
+audit-action-delete = "Eliminado"
+audit-action-toggle = "Activado/desactivado"
+audit-action-password = "Contraseña cambiada"
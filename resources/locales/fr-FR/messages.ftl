@@ -9,9 +9,16 @@ nav-aliases = Alias
 nav-users = Utilisateurs
 nav-relays = Relais
 nav-relocated = Réacheminés
+nav-alias-domains = Domaines alias
 nav-clients = Clients
 nav-statistics = Statistiques
 nav-reports = Rapports
+nav-audit = Journal d'audit
+nav-import = Importer
+nav-export = Exporter
+nav-restore = Restaurer
+nav-legacy = Reprise de l'existant
+nav-sync = Synchronisation
 nav-config = Configuration
 nav-database = Sélection de Base de Données
 nav-about = À Propos
@@ -104,6 +111,8 @@ dashboard-total-clients = Total des Clients
 dashboard-enabled-domains-and-backups = Domaines et Sauvegardes Activés
 dashboard-enabled-aliases = Alias Activés
 dashboard-enabled-users = Utilisateurs Activés
+dashboard-allocated-quota = Quota Alloué
+dashboard-expiring-aliases = Alias expirant bientôt
 dashboard-quick-actions = Actions Rapides
 dashboard-primary-actions = Actions Principales
 dashboard-advanced-management = Gestion Avancée
@@ -260,11 +269,20 @@ aliases-active = Actif
 aliases-placeholder-mail = user@exemple.com
 aliases-placeholder-destination = destination@exemple.com
 aliases-tooltip-mail = L'adresse de courrier électronique pour l'alias
-aliases-tooltip-destination = L'adresse de destination où le courrier sera transféré
+aliases-tooltip-destination = Les adresses vers lesquelles le courrier est transféré ; ajoutez-en autant que nécessaire
+aliases-destinations-add = Ajouter
+aliases-destinations-remove = Retirer
+aliases-destinations-empty = Aucune destination pour l'instant
+aliases-destinations-reaches = Aboutit à :
+aliases-destinations-invalid = Adresse non valide
+aliases-error-destination-missing = Ajoutez au moins une destination.
+aliases-error-destination-invalid = « { $address } » n'est pas une adresse de destination valide.
 aliases-tooltip-active = Activer cet alias
 aliases-cancel = Annuler
 aliases-update-alias = Mettre à Jour l'Alias
 aliases-create-alias = Créer l'Alias
+aliases-filter-all = Tous
+aliases-filter-expiring = Expirant bientôt
 
 # Users
 users-title = Utilisateurs
@@ -313,6 +331,11 @@ users-disable = Désactiver
 users-password-change-required-label = Changement de mot de passe requis
 users-password-change-required-yes = Oui
 users-password-change-required-no = Non
+users-password-hash = Hachage du mot de passe
+users-password-hash-unknown = Inconnu
+users-password-needs-rehash = Plus faible que le schéma de cette base de données
+users-generate-password = Générer un mot de passe robuste
+users-generated-password-notice = Les champs de mot de passe ont été remplis. Copiez le mot de passe maintenant ; il ne sera plus affiché.
 users-password-management-title = Gestion des mots de passe
 users-change-password-button = Changer le mot de passe
 users-require-password-change-button = Exiger le changement de mot de passe
@@ -323,12 +346,243 @@ users-confirm-password-label = Confirmer le mot de passe
 users-confirm-password-placeholder = Confirmez le nouveau mot de passe
 users-cancel-button = Annuler
 error-passwords-do-not-match = Les mots de passe ne correspondent pas
-error-password-too-short = Le mot de passe doit comporter au moins 8 caractères
+password-policy-too-short = Le mot de passe doit comporter au moins { $min } caractères.
+password-policy-missing-lowercase = Le mot de passe doit contenir une lettre minuscule.
+password-policy-missing-uppercase = Le mot de passe doit contenir une lettre majuscule.
+password-policy-missing-digit = Le mot de passe doit contenir un chiffre.
+password-policy-missing-symbol = Le mot de passe doit contenir un symbole.
+password-policy-contains-address = Le mot de passe ne doit pas contenir l'adresse de la boîte aux lettres.
+password-policy-contains-name = Le mot de passe ne doit pas contenir le nom de l'utilisateur.
+password-policy-common = Le mot de passe est trop courant.
 error-failed-to-update-password = Échec de la mise à jour du mot de passe
 users-maildir = Chemin Maildir
 users-tooltip-maildir = Le dossier racine de la boîte aux lettres de cet utilisateur (relatif à la racine du stockage mail)
 users-placeholder-maildir = ex. example.com/utilisateur/
 
+# Mailbox quotas
+quota-title = Quota de la boîte aux lettres
+quota-size = Limite de taille
+quota-messages = Limite de messages
+quota-default-size = Limite de taille par défaut
+quota-default-messages = Limite de messages par défaut
+quota-cap = Plafond de taille total
+quota-size-hint = Octets, ou une taille comme 512M ou 2G. 0 signifie illimité.
+quota-user-hint = Laissez vide pour utiliser la valeur par défaut du domaine. 0 signifie illimité.
+quota-domain-hint = S'applique aux boîtes aux lettres sans quota propre. Laissez vide pour illimité.
+quota-cap-hint = Limite de la somme des quotas de taille de toutes les boîtes aux lettres du domaine. Laissez vide pour aucun plafond.
+quota-domain-default = Valeur par défaut du domaine
+error-quota-cap = Le quota dépasse le plafond de quota du domaine. Dans un domaine plafonné, chaque boîte aux lettres doit avoir une limite de taille et leur total doit rester sous le plafond.
+
+# Alias domains
+alias-domains-title = Domaines alias
+alias-domains-list-description = Domaines qui reçoivent le courrier des mêmes adresses qu'un autre domaine : utilisateur@ancien.example est distribué à utilisateur@example.com.
+alias-domains-add = Ajouter un domaine alias
+alias-domains-new = Nouveau domaine alias
+alias-domains-edit = Modifier le domaine alias
+alias-domains-table-header-alias-domain = Domaine alias
+alias-domains-table-header-target-domain = Domaine cible
+alias-domains-table-header-enabled = Statut
+alias-domains-table-header-actions = Actions
+alias-domains-empty-title = Aucun domaine alias
+alias-domains-empty-description = Ajoutez un domaine alias pour accepter le courrier d'un autre nom de l'un de vos domaines.
+alias-domains-show-title = Domaine alias
+alias-domains-view-edit-settings = Voir et modifier les paramètres du domaine alias
+alias-domains-back-to-list = Retour aux domaines alias
+alias-domains-info-title = Informations du domaine alias
+alias-domains-info-description = Le courrier adressé ici va à la même partie locale sur le domaine cible, si celui-ci a une boîte, un alias ou un catch-all pour elle. Les alias du domaine alias lui-même sont prioritaires.
+alias-domains-field-alias-domain = Domaine alias
+alias-domains-field-target-domain = Domaine cible
+alias-domains-field-enabled = Activé
+alias-domains-field-created = Créé
+alias-domains-field-modified = Modifié
+alias-domains-field-alias-domain-help = Le nom de domaine pour lequel le courrier arrive, p. ex. ancien.example
+alias-domains-field-target-domain-help = Le domaine d'ici auquel son courrier est distribué
+alias-domains-placeholder-alias-domain = ancien.example
+alias-domains-select-target = Choisir un domaine
+alias-domains-delete-confirm = Êtes-vous sûr de vouloir supprimer ce domaine alias ?
+alias-domains-not-found = Domaine alias non trouvé
+alias-domains-create-error = Échec de la création du domaine alias
+alias-domains-update-error = Échec de la mise à jour du domaine alias
+alias-domains-delete-error = Échec de la suppression du domaine alias
+alias-domains-toggle-error = Échec du changement de statut du domaine alias
+alias-domains-error-invalid-domain = Le domaine alias et la cible doivent être des noms de domaine valides.
+alias-domains-error-same-domain = Un domaine alias ne peut pas pointer vers lui-même.
+alias-domains-error-unknown-target = La cible n'est pas un domaine géré ici.
+alias-domains-error-domain-exists = Ce nom est déjà un domaine ou un domaine de secours.
+alias-domains-error-exists = Ce domaine alias existe déjà.
+domains-alias-domains-title = Domaines alias
+domains-alias-domains-description = Le courrier adressé à ces domaines est distribué aux mêmes adresses ici.
+domains-alias-domains-empty = Aucun domaine alias ne pointe vers ce domaine.
+
+# Vacation auto-replies
+vacation-title = Réponse automatique d'absence
+vacation-status-none = Aucun message d'absence n'est défini.
+vacation-status-active = Les réponses automatiques sont envoyées.
+vacation-status-inactive = Les réponses automatiques ne sont pas envoyées actuellement.
+vacation-subject = Objet
+vacation-body = Message
+vacation-active-from = Du
+vacation-active-until = Au
+vacation-dates-hint = Laissez une date vide pour ne pas limiter de ce côté.
+vacation-reply-interval = Jours entre deux réponses au même expéditeur
+vacation-enabled = Activé
+vacation-save = Enregistrer l'absence
+vacation-delete = Supprimer l'absence
+vacation-delete-confirm = Supprimer ce message d'absence ?
+vacation-saved = Absence enregistrée.
+vacation-error-subject = Saisissez un objet pour la réponse.
+vacation-error-interval = Le nombre de jours entre les réponses doit être compris entre 1 et 365.
+vacation-error-dates = La date de fin ne peut pas précéder la date de début.
+
+# Fenêtres de validité
+validity-title = Validité
+validity-valid-from = Valide à partir du
+validity-valid-until = Valide jusqu'au
+validity-hint = Les heures sont en UTC. Laissez un champ vide pour ne pas avoir de limite de ce côté ; l'entrée est activée et désactivée automatiquement à ces heures.
+validity-starts = Planifié
+validity-expires = Expire
+validity-expired = Expiré
+validity-error-window = La fin de validité doit être postérieure au début.
+
+# Import en masse
+import-title = Import en masse
+import-description = Créez et mettez à jour des enregistrements depuis un fichier CSV ou TSV. Rien n'est écrit avant d'appliquer l'aperçu.
+import-kind = Enregistrements
+import-file = Fichier
+import-file-hint = La première ligne nomme les colonnes. Les fichiers séparés par des virgules, points-virgules ou tabulations jusqu'à 2 Mo sont acceptés.
+import-update-existing = Mettre à jour les enregistrements existants
+import-preview = Aperçu
+import-mapping-title = Colonnes
+import-mapping-ignore = (ignorer)
+import-update-preview = Actualiser l'aperçu
+import-apply = Appliquer l'import
+import-apply-hint = Les créations et mises à jour sont écrites dans une seule transaction. Les lignes en conflit ou en erreur sont ignorées.
+import-download-report = Télécharger le rapport d'erreurs
+import-start-over = Importer un autre fichier
+import-table-header-line = Ligne
+import-table-header-key = Enregistrement
+import-table-header-status = Résultat
+import-table-header-details = Détails
+import-status-create = Création
+import-status-update = Mise à jour
+import-status-unchanged = Inchangé
+import-status-conflict = Conflit
+import-status-error = Erreur
+import-no-file = Choisissez un fichier à importer.
+import-nothing-to-apply = Il n'y a rien à créer ni à mettre à jour.
+import-applied = { $count } enregistrements écrits, { $skipped } lignes ignorées.
+import-failed = Rien n'a été écrit. { $detail }
+import-error-empty = Le fichier n'a pas de ligne d'en-tête.
+import-error-csv = Le fichier n'a pas pu être lu : { $detail }
+import-error-unknown-header = Le fichier n'a pas de colonne « { $detail } ».
+import-error-unknown-field = Champ inconnu « { $detail } ».
+import-error-missing-key = Aucune colonne n'est associée à { $detail }.
+import-error-duplicate-field = Plusieurs colonnes sont associées à { $detail }.
+import-error-missing = La colonne { $field } est vide.
+import-error-invalid = Valeur invalide : { $detail }
+import-error-password-required = Un mot de passe est requis pour les nouveaux utilisateurs.
+import-error-out-of-scope = L'adresse est en dehors des domaines que vous gérez.
+import-conflict-duplicate = Déjà présent dans le fichier à la ligne { $line }.
+import-conflict-exists = Existe déjà et la mise à jour des enregistrements existants n'a pas été demandée.
+
+# Export bundles
+export-title = Exporter
+export-description = Téléchargez tous les enregistrements d'une base de données sous forme de paquet JSON ou YAML versionné, indépendant des noms de ses colonnes. Utile pour les sauvegardes externes, la relecture de la configuration dans git ou le déplacement de clients entre serveurs.
+export-format = Format
+export-password-hashes = Inclure les empreintes des mots de passe
+export-password-hashes-hint = Quiconque détient le paquet peut tenter de les casser. Conservez-le avec autant de soin que la base elle-même.
+export-download = Télécharger
+export-no-databases = Vous n'avez accès à aucune base de données exportable.
+
+# Restoring bundles
+restore-title = Restaurer
+restore-description = Chargez un paquet d'export dans une base de données, éventuellement différente de celle d'origine. Rien n'est écrit avant d'appliquer l'aperçu.
+restore-database = Base de données cible
+restore-mode = Mode
+restore-mode-merge = Fusionner : créer et mettre à jour les enregistrements, conserver les autres
+restore-mode-replace = Remplacer : supprimer aussi les enregistrements absents du paquet
+restore-file = Paquet
+restore-file-hint = Un paquet JSON ou YAML issu d'Exporter, jusqu'à 2 Mo. Utilisez sortingoffice-cli restore pour les paquets plus volumineux.
+restore-preview = Aperçu
+restore-summary = Paquet de { $source } exporté le { $exported }, restauré dans { $target } ({ $mode }).
+restore-apply = Appliquer la restauration
+restore-apply-hint = Toutes les modifications sont écrites en une transaction. Les modifications que la base n'autorise pas sont ignorées.
+restore-start-over = Restaurer un autre paquet
+restore-no-changes = La base de données correspond déjà au paquet.
+restore-table-header-table = Table
+restore-table-header-key = Enregistrement
+restore-table-header-status = Résultat
+restore-table-header-details = Détails
+restore-status-create = Créer
+restore-status-update = Mettre à jour
+restore-status-delete = Supprimer
+restore-status-unchanged = Inchangé
+restore-status-error = Non autorisé
+restore-no-file = Choisissez un paquet à restaurer.
+restore-no-databases = Vous n'avez d'accès en modification à aucune base de données où restaurer.
+restore-applied = { $count } enregistrements écrits, { $skipped } modifications ignorées.
+restore-failed = Rien n'a été écrit. { $detail }
+restore-error-syntax = Le paquet n'a pas pu être lu : { $detail }
+restore-error-version = Le paquet est en version { $detail }, que cette version de Sorting Office ne sait pas lire.
+restore-error-not-allowed = Les paramètres de la base de données n'autorisent pas cette modification.
+restore-error-password-required = Le paquet n'a pas d'empreinte de mot de passe pour ce nouvel utilisateur.
+
+# Legacy import
+legacy-title = Reprise de l'existant
+legacy-description = Reprenez les données de messagerie d'un serveur PostfixAdmin ou ISPmail. La base de données source est seulement lue ; ses enregistrements sont convertis et prévisualisés comme une restauration.
+legacy-schema = Structure de la source
+legacy-url = Base de données source
+legacy-url-hint = Une URL mysql://, idéalement d'un utilisateur n'ayant que le droit SELECT. Laissez vide pour envoyer un dump à la place.
+legacy-file = Dump
+legacy-file-hint = Un dump mysqldump ou un export SQL phpMyAdmin de la source, jusqu'à 2 Mo. Utilisez sortingoffice-cli legacy pour les dumps plus volumineux.
+legacy-home = Répertoire de courrier
+legacy-uid = UID
+legacy-gid = GID
+legacy-preview = Aperçu
+legacy-summary = { $count } enregistrements convertis depuis { $source } pour { $target } ({ $mode }).
+legacy-apply = Appliquer la reprise
+legacy-apply-hint = Toutes les modifications sont écrites en une transaction. Les modifications que la base de données n'autorise pas sont ignorées.
+legacy-notes = Non converti
+legacy-table-header-note = Remarque
+legacy-table-header-value = Valeur
+legacy-no-source = Indiquez la base de données source ou choisissez un dump.
+legacy-error-connect = Connexion à la base de données source impossible : { $detail }
+legacy-error-query = La base de données source n'a pas pu être lue : { $detail }
+legacy-error-dump = Le dump n'a pas pu être lu : { $detail }
+legacy-note-missing-table = La source n'a pas cette table.
+legacy-note-dropped-field = Sorting Office n'a pas de champ pour cette valeur.
+legacy-note-no-destination = L'alias n'a pas de destination et a été ignoré.
+legacy-note-autoreply = Adresse de réponse automatique retirée ; les réponses d'absence sont converties à part.
+legacy-note-unknown-hash = L'empreinte du mot de passe n'indique pas son schéma et a été copiée telle quelle.
+
+# Database sync
+sync-title = Synchronisation des bases
+sync-description = Compare deux bases enregistrement par enregistrement et copie les différences choisies de la source vers la cible. La source est seulement lue.
+sync-source = Base source
+sync-target = Base cible
+sync-tables = Tables
+sync-compare = Comparer
+sync-summary = { $source } comparée à { $target }.
+sync-no-databases = Vous devez avoir accès à une seconde base et les droits de modification sur la cible.
+sync-same-database = Choisissez deux bases différentes.
+sync-no-tables = Choisissez au moins une table à comparer.
+sync-no-differences = Les bases contiennent les mêmes enregistrements.
+sync-no-selection = Choisissez les enregistrements à synchroniser.
+sync-table-header-select = Synchroniser
+sync-table-header-source-modified = Modifié dans la source
+sync-table-header-target-modified = Modifié dans la cible
+sync-status-create = Absent de la cible
+sync-status-update = Différent
+sync-status-delete = Seulement dans la cible
+sync-status-unchanged = Identique
+sync-status-error = Non autorisé
+sync-newer = plus récent
+sync-password-differs = Les empreintes des mots de passe diffèrent.
+sync-apply = Synchroniser la sélection
+sync-apply-hint = Les enregistrements choisis sont copiés vers la cible en une seule transaction ; ceux qui n'existent que dans la cible y sont supprimés.
+sync-applied = { $count } enregistrements synchronisés.
+sync-failed = Rien n'a été écrit. { $detail }
+
 # Relays
 relays-title = Relais
 relays-add = Ajouter un Relais
@@ -474,6 +728,15 @@ stats-quota-usage-description = Surveiller l'utilisation de l'espace disque sur
 stats-quota-usage-percentage = Pourcentage d'Utilisation
 stats-quota-total = Quota Total
 stats-quota-used = Quota Utilisé
+stats-usage-not-collected = L'utilisation des boîtes aux lettres n'est pas collectée. Définissez la source dans la section [usage] de config.toml pour voir l'utilisation réelle.
+stats-usage-last-collected = Dernière collecte
+stats-largest-mailboxes = Plus Grandes Boîtes aux Lettres
+stats-table-header-mailbox = Boîte aux lettres
+stats-table-header-size = Taille
+stats-table-header-messages = Messages
+stats-table-header-quota = Quota
+stats-table-header-growth = Croissance (30 jours)
+stats-usage-history = Historique d'Utilisation (30 jours)
 stats-recent-activity-title = Activité Récente
 stats-recent-domains = Domaines Récents
 stats-recent-users = Utilisateurs Récents
@@ -526,12 +789,47 @@ config-feature-no-new-domains = "Pas de Nouveaux Domaines"
 config-feature-no-password-updates = "Pas de Mises à Jour de Mot de Passe"
 config-feature-database-disabled = "Base de Données Désactivée"
 config-status-enabled = "Activé"
-config-status-disabled = "Désactivé"
 
 # Database Feature Toggles
 config-database-features-header = "Interrupteurs de Fonctionnalités de Base de Données"
 config-database-features-description = "Restrictions de fonctionnalités spécifiques à la base de données. Celles-ci remplacent les paramètres globaux lorsqu'elles sont activées."
 config-database-disabled-badge = "Désactivé"
+config-saved = "Configuration enregistrée."
+config-save-failed = "Impossible d'enregistrer la configuration : { $error }"
+config-reload-failed = "Le rechargement du fichier de configuration a échoué à { $time }, la configuration précédente reste donc active : { $error }"
+config-mail-config-link = "Configuration du serveur de messagerie"
+
+# Mail Server Config
+mail-config-title = "Configuration du serveur de messagerie"
+mail-config-description = "Fichiers de recherche SQL Postfix et Dovecot générés à partir du schéma et de la correspondance des champs de chaque base de données. Inclut des scripts Sieve pour les réponses automatiques d'absence (Pigeonhole)."
+mail-config-download-all = "Tout télécharger (ZIP)"
+mail-config-download-database = "Télécharger le ZIP"
+mail-config-no-databases = "Vous n'avez accès en modification à aucune base de données."
+mail-config-credentials-warning = "Ces fichiers contiennent l'utilisateur et le mot de passe de la base de données. Rendez-les lisibles uniquement par Postfix et Dovecot."
+
+# Delivery Simulator
+reports-delivery-title = "Simulateur de distribution"
+reports-delivery-description = "Suit le courrier vers une adresse à travers les relocalisations, domaines, alias et boîtes aux lettres comme Postfix le résout."
+reports-delivery-address-label = "Adresse du destinataire"
+reports-delivery-submit = "Tracer"
+reports-delivery-route-header = "Parcours"
+reports-delivery-final-header = "Aboutit à"
+reports-delivery-no-final = "Le courrier n'est distribué nulle part."
+reports-delivery-problems = "Ce parcours contient des boucles ou des impasses."
+reports-delivery-disabled = "Ignoré car désactivé :"
+reports-delivery-outcome-relocated = "Relocalisé, renvoyé avec la nouvelle adresse"
+reports-delivery-outcome-alias = "Alias"
+reports-delivery-outcome-alias-domain = "Domaine alias, réécrit vers le domaine cible"
+reports-delivery-outcome-catch-all = "Alias attrape-tout"
+reports-delivery-outcome-mailbox = "Boîte aux lettres"
+reports-delivery-outcome-relay = "Relayé pour le domaine de secours"
+reports-delivery-outcome-external = "Adresse externe"
+reports-delivery-outcome-loop = "Boucle, déjà développé plus haut"
+reports-delivery-outcome-unknown-user = "Utilisateur inconnu, rejeté"
+reports-delivery-outcome-unknown-domain = "Domaine non accepté, rejeté"
+reports-delivery-outcome-invalid-address = "Pas une adresse e-mail"
+reports-delivery-outcome-too-deep = "Chaîne d'alias trop profonde, non suivie"
+reports-alias-domain-of = "domaine alias de"
 
 # Forms
 form-error = Erreur
@@ -937,5 +1235,71 @@ login-user-id = Identifiant utilisateur
 login-password = Mot de passe
 login-sign-in = Se connecter
 login-error = Nom d'utilisateur ou mot de passe invalide
+login-mailbox-link = Utilisateur de boîte mail ? Connectez-vous pour gérer votre boîte
+
+# Portail de la boîte mail
+portal-login-title = Se connecter à votre boîte mail
+portal-login-address = Adresse e-mail
+portal-login-admin-link = Connexion administrateur
+portal-title = Ma boîte mail
+portal-sign-out = Se déconnecter
+portal-must-change-password = Vous devez choisir un nouveau mot de passe avant de continuer.
+portal-password-title = Changer le mot de passe
+portal-current-password = Mot de passe actuel
+portal-password-blocked = Les changements de mot de passe sont actuellement désactivés pour cette boîte.
+portal-password-changed = Votre mot de passe a été changé.
+portal-error-current-password = Votre mot de passe actuel est incorrect.
+portal-error-same-password = Le nouveau mot de passe doit être différent de l'actuel.
+portal-aliases-title = Adresses livrées ici
+portal-aliases-help = Le courrier envoyé à ces alias arrive dans votre boîte.
+portal-aliases-empty = Aucun alias ne livre dans cette boîte.
+portal-forwarding-title = Transfert
+portal-forwarding-help = Transférez le courrier entrant vers d'autres adresses. Laissez vide pour arrêter le transfert.
+portal-forwarding-addresses = Transférer à (une adresse par ligne)
+portal-forwarding-keep-copy = Garder une copie dans cette boîte
+portal-forwarding-save = Enregistrer le transfert
+portal-forwarding-saved = Votre transfert a été enregistré.
+portal-forwarding-failed = Échec de l'enregistrement de votre transfert.
+portal-forwarding-disabled = Le transfert de cette adresse a été désactivé par un administrateur.
+portal-forwarding-blocked = Le transfert ne peut pas être modifié pour le moment.
+portal-forwarding-invalid-address = « { $address } » n'est pas une adresse e-mail valide.
+portal-forwarding-too-many = Vous pouvez transférer vers { $max } adresses au maximum.
 
 # Roles and Permissions
+
+# Audit Log
+audit-title = Journal d'audit
+audit-description = Toutes les modifications apportées aux domaines, utilisateurs, alias, sauvegardes, relais, relocalisations et clients.
+audit-filter-admin = Administrateur
+audit-filter-entity = Entité
+audit-filter-from = Du
+audit-filter-to = Au
+audit-filter-all = Tous
+audit-filter-apply = Filtrer
+audit-filter-reset = Réinitialiser
+audit-table-header-time = Date
+audit-table-header-admin = Administrateur
+audit-table-header-database = Base de données
+audit-table-header-entity = Entité
+audit-table-header-name = Élément
+audit-table-header-action = Action
+audit-table-header-changes = Modifications
+audit-empty-title = Aucune entrée d'audit
+audit-empty-description = Aucune modification ne correspond aux filtres sélectionnés.
+audit-no-field-changes = Aucun champ modifié
+audit-history-title = Historique des modifications
+audit-history-empty = Aucune modification n'a encore été enregistrée pour cet élément.
+audit-entity-domain = Domaine
+audit-entity-user = Utilisateur
+audit-entity-alias = Alias
+audit-entity-backup = Sauvegarde
+audit-entity-relay = Relais
+audit-entity-relocated = Relocalisé
+audit-entity-client = Client
+audit-entity-vacation = Réponse automatique
+audit-entity-alias-domain = Domaine alias
+audit-action-create = Créé
+audit-action-update = Modifié
+audit-action-delete = Supprimé
+audit-action-toggle = Activé/désactivé
+audit-action-password = Mot de passe changé
@@ -9,9 +9,16 @@ nav-aliases = Aliaser
 nav-users = Brukere
 nav-relays = Videresendinger
 nav-relocated = Flyttet
+nav-alias-domains = Aliasdomener
 nav-clients = Klienter
 nav-statistics = Statistikk
 nav-reports = Rapporter
+nav-audit = Revisjonslogg
+nav-import = Importer
+nav-export = Eksporter
+nav-restore = Gjenopprett
+nav-legacy = Overta gammelt oppsett
+nav-sync = Databasesynkronisering
 nav-config = Konfigurasjon
 nav-database = Databasevalg
 nav-about = Om
@@ -105,6 +112,8 @@ dashboard-total-clients = Totalt Antall Klienter
 dashboard-enabled-domains-and-backups = Aktiverte Domener & Backups
 dashboard-enabled-aliases = Aktiverte Aliaser
 dashboard-enabled-users = Aktiverte Brukere
+dashboard-allocated-quota = Tildelt Kvote
+dashboard-expiring-aliases = Aliaser som snart utløper
 dashboard-quick-actions = Raske Handlinger
 dashboard-primary-actions = Hovedhandlinger
 dashboard-advanced-management = Avansert Administrasjon
@@ -237,6 +246,13 @@ aliases-alias-information = Aliasinformasjon
 aliases-alias-details = Aliasinformasjon og konfigurasjon.
 aliases-mail = E-post
 aliases-destination = Destinasjon
+aliases-destinations-add = Legg til
+aliases-destinations-remove = Fjern
+aliases-destinations-empty = Ingen destinasjoner ennå
+aliases-destinations-reaches = Når fram til:
+aliases-destinations-invalid = Ikke en gyldig adresse
+aliases-error-destination-missing = Legg til minst én destinasjon.
+aliases-error-destination-invalid = «{ $address }» er ikke en gyldig destinasjonsadresse.
 aliases-status = Status
 aliases-created = Opprettet
 aliases-modified = Endret
@@ -250,6 +266,8 @@ aliases-not-found = Alias ikke funnet
 aliases-form-mail = E-post
 aliases-form-destination = Destinasjon
 aliases-form-enabled = Aktivert
+aliases-filter-all = Alle
+aliases-filter-expiring = Utløper snart
 
 # Users
 users-title = Brukere
@@ -287,6 +305,11 @@ users-form-enabled = Aktivert
 users-password-change-required-label = Passordendring påkrevd
 users-password-change-required-yes = Ja
 users-password-change-required-no = Nei
+users-password-hash = Passordhash
+users-password-hash-unknown = Ukjent
+users-password-needs-rehash = Svakere enn skjemaet til denne databasen
+users-generate-password = Generer sterkt passord
+users-generated-password-notice = Passordfeltene er fylt ut. Kopier passordet nå; det vises ikke igjen.
 users-password-management-title = Passordadministrasjon
 users-change-password-button = Endre passord
 users-require-password-change-button = Krev passordendring
@@ -300,6 +323,230 @@ users-maildir = Maildir-sti
 users-tooltip-maildir = Rotmappen for denne brukerens postboks (relativ til e-postlagringsroten)
 users-placeholder-maildir = f.eks. example.com/bruker/
 
+# Mailbox quotas
+quota-title = Postbokskvote
+quota-size = Størrelsesgrense
+quota-messages = Meldingsgrense
+quota-default-size = Standard størrelsesgrense
+quota-default-messages = Standard meldingsgrense
+quota-cap = Totalt størrelsestak
+quota-size-hint = Byte, eller en størrelse som 512M eller 2G. 0 betyr ubegrenset.
+quota-user-hint = La stå tomt for å bruke domenets standard. 0 betyr ubegrenset.
+quota-domain-hint = Gjelder postbokser uten egen kvote. La stå tomt for ubegrenset.
+quota-cap-hint = Grense for summen av størrelseskvotene til alle postbokser i domenet. La stå tomt for ingen grense.
+quota-domain-default = Domenets standard
+error-quota-cap = Kvoten får ikke plass under domenets kvotetak. I et domene med tak må hver postboks ha en størrelsesgrense, og til sammen må de holde seg innenfor taket.
+
+# Alias domains
+alias-domains-title = Aliasdomener
+alias-domains-list-description = Domener som tar imot e-post for de samme adressene som et annet domene: bruker@gammel.example leveres som bruker@example.com.
+alias-domains-add = Legg til aliasdomene
+alias-domains-new = Nytt aliasdomene
+alias-domains-edit = Rediger aliasdomene
+alias-domains-table-header-alias-domain = Aliasdomene
+alias-domains-table-header-target-domain = Måldomene
+alias-domains-table-header-enabled = Status
+alias-domains-table-header-actions = Handlinger
+alias-domains-empty-title = Ingen aliasdomener
+alias-domains-empty-description = Legg til et aliasdomene for å ta imot e-post for et annet navn på et av domenene dine.
+alias-domains-show-title = Aliasdomene
+alias-domains-view-edit-settings = Vis og rediger innstillinger for aliasdomenet
+alias-domains-back-to-list = Tilbake til aliasdomener
+alias-domains-info-title = Informasjon om aliasdomenet
+alias-domains-info-description = E-post til en adresse her går til samme lokale del på måldomenet, hvis måldomenet har en postkasse, et alias eller en catch-all for den. Alias på selve aliasdomenet går foran.
+alias-domains-field-alias-domain = Aliasdomene
+alias-domains-field-target-domain = Måldomene
+alias-domains-field-enabled = Aktivert
+alias-domains-field-created = Opprettet
+alias-domains-field-modified = Endret
+alias-domains-field-alias-domain-help = Domenenavnet e-posten kommer til, f.eks. gammel.example
+alias-domains-field-target-domain-help = Domenet her som e-posten leveres til
+alias-domains-placeholder-alias-domain = gammel.example
+alias-domains-select-target = Velg et domene
+alias-domains-delete-confirm = Er du sikker på at du vil slette dette aliasdomenet?
+alias-domains-not-found = Aliasdomenet ble ikke funnet
+alias-domains-create-error = Kunne ikke opprette aliasdomenet
+alias-domains-update-error = Kunne ikke oppdatere aliasdomenet
+alias-domains-delete-error = Kunne ikke slette aliasdomenet
+alias-domains-toggle-error = Kunne ikke endre status for aliasdomenet
+alias-domains-error-invalid-domain = Både aliasdomenet og målet må være gyldige domenenavn.
+alias-domains-error-same-domain = Et aliasdomene kan ikke peke på seg selv.
+alias-domains-error-unknown-target = Målet er ikke et domene som administreres her.
+alias-domains-error-domain-exists = Dette navnet er allerede et domene eller et reservedomene.
+alias-domains-error-exists = Dette aliasdomenet finnes allerede.
+domains-alias-domains-title = Aliasdomener
+domains-alias-domains-description = E-post til disse domenene leveres til de samme adressene her.
+domains-alias-domains-empty = Ingen aliasdomener peker på dette domenet.
+
+# Vacation auto-replies
+vacation-title = Fraværsmelding
+vacation-status-none = Ingen fraværsmelding er satt opp.
+vacation-status-active = Automatiske svar sendes.
+vacation-status-inactive = Automatiske svar sendes ikke akkurat nå.
+vacation-subject = Emne
+vacation-body = Melding
+vacation-active-from = Fra
+vacation-active-until = Til
+vacation-dates-hint = La en dato stå tom for ingen grense på den siden.
+vacation-reply-interval = Dager mellom svar til samme avsender
+vacation-enabled = Aktivert
+vacation-save = Lagre fraværsmelding
+vacation-delete = Fjern fraværsmelding
+vacation-delete-confirm = Fjerne denne fraværsmeldingen?
+vacation-saved = Fraværsmelding lagret.
+vacation-error-subject = Skriv inn et emne for svaret.
+vacation-error-interval = Dager mellom svar må være mellom 1 og 365.
+vacation-error-dates = Sluttdatoen kan ikke være før startdatoen.
+
+# Gyldighetsperioder
+validity-title = Gyldighet
+validity-valid-from = Gyldig fra
+validity-valid-until = Gyldig til
+validity-hint = Tidene er i UTC. La et felt stå tomt for ingen grense på den siden; oppføringen aktiveres og deaktiveres automatisk på disse tidspunktene.
+validity-starts = Planlagt
+validity-expires = Utløper
+validity-expired = Utløpt
+validity-error-window = Gyldig til må være senere enn gyldig fra.
+
+# Masseimport
+import-title = Masseimport
+import-description = Opprett og oppdater oppføringer fra en CSV- eller TSV-fil. Ingenting skrives før du bruker forhåndsvisningen.
+import-kind = Oppføringer
+import-file = Fil
+import-file-hint = Første rad navngir kolonnene. Filer skilt med komma, semikolon eller tabulator på opptil 2 MB godtas.
+import-update-existing = Oppdater oppføringer som allerede finnes
+import-preview = Forhåndsvis
+import-mapping-title = Kolonner
+import-mapping-ignore = (ignorer)
+import-update-preview = Oppdater forhåndsvisning
+import-apply = Utfør import
+import-apply-hint = Opprettelser og oppdateringer skrives i én transaksjon. Rader med konflikter eller feil hoppes over.
+import-download-report = Last ned feilrapport
+import-start-over = Importer en annen fil
+import-table-header-line = Linje
+import-table-header-key = Oppføring
+import-table-header-status = Resultat
+import-table-header-details = Detaljer
+import-status-create = Opprett
+import-status-update = Oppdater
+import-status-unchanged = Uendret
+import-status-conflict = Konflikt
+import-status-error = Feil
+import-no-file = Velg en fil å importere.
+import-nothing-to-apply = Det er ingenting å opprette eller oppdatere.
+import-applied = { $count } oppføringer skrevet, { $skipped } rader hoppet over.
+import-failed = Ingenting ble skrevet. { $detail }
+import-error-empty = Filen har ingen overskriftsrad.
+import-error-csv = Filen kunne ikke leses: { $detail }
+import-error-unknown-header = Filen har ingen kolonne «{ $detail }».
+import-error-unknown-field = Ukjent felt «{ $detail }».
+import-error-missing-key = Ingen kolonne er koblet til { $detail }.
+import-error-duplicate-field = Mer enn én kolonne er koblet til { $detail }.
+import-error-missing = Kolonnen { $field } er tom.
+import-error-invalid = Ugyldig verdi: { $detail }
+import-error-password-required = Nye brukere må ha et passord.
+import-error-out-of-scope = Adressen er utenfor domenene du administrerer.
+import-conflict-duplicate = Finnes allerede i filen på linje { $line }.
+import-conflict-exists = Finnes allerede, og oppdatering av eksisterende oppføringer ble ikke valgt.
+
+# Export bundles
+export-title = Eksporter
+export-description = Last ned alle oppføringene i en database som en versjonert JSON- eller YAML-pakke, uavhengig av kolonnenavnene. Brukes til sikkerhetskopier utenfor huset, gjennomgang av konfigurasjon i git eller flytting av kunder mellom servere.
+export-format = Format
+export-password-hashes = Ta med passordhasher
+export-password-hashes-hint = Alle som har pakken kan forsøke å knekke dem. Oppbevar den like forsiktig som selve databasen.
+export-download = Last ned
+export-no-databases = Du har ikke tilgang til noen database som kan eksporteres.
+
+# Restoring bundles
+restore-title = Gjenopprett
+restore-description = Last inn en eksportpakke i en database, gjerne en annen enn den ble eksportert fra. Ingenting skrives før du tar i bruk forhåndsvisningen.
+restore-database = Måldatabase
+restore-mode = Modus
+restore-mode-merge = Slå sammen: opprett og oppdater oppføringer, behold de andre
+restore-mode-replace = Erstatt: slett også oppføringer som ikke er i pakken
+restore-file = Pakke
+restore-file-hint = En JSON- eller YAML-pakke fra Eksporter, opptil 2 MB. Bruk sortingoffice-cli restore for større pakker.
+restore-preview = Forhåndsvis
+restore-summary = Pakke fra { $source } eksportert { $exported }, gjenopprettet i { $target } ({ $mode }).
+restore-apply = Utfør gjenoppretting
+restore-apply-hint = Alle endringer skrives i én transaksjon. Endringer databasen ikke tillater hoppes over.
+restore-start-over = Gjenopprett en annen pakke
+restore-no-changes = Databasen stemmer allerede med pakken.
+restore-table-header-table = Tabell
+restore-table-header-key = Oppføring
+restore-table-header-status = Resultat
+restore-table-header-details = Detaljer
+restore-status-create = Opprett
+restore-status-update = Oppdater
+restore-status-delete = Slett
+restore-status-unchanged = Uendret
+restore-status-error = Ikke tillatt
+restore-no-file = Velg en pakke å gjenopprette.
+restore-no-databases = Du har ikke redigeringstilgang til noen database å gjenopprette i.
+restore-applied = { $count } oppføringer skrevet, { $skipped } endringer hoppet over.
+restore-failed = Ingenting ble skrevet. { $detail }
+restore-error-syntax = Pakken kunne ikke leses: { $detail }
+restore-error-version = Pakken er versjon { $detail }, som denne versjonen av Sorting Office ikke kan lese.
+restore-error-not-allowed = Databasens innstillinger tillater ikke denne endringen.
+restore-error-password-required = Pakken har ingen passordhash for denne nye brukeren.
+
+# Legacy import
+legacy-title = Overta gammelt oppsett
+legacy-description = Overta e-postdataene til en PostfixAdmin- eller ISPmail-server. Kildedatabasen blir bare lest; postene konverteres og forhåndsvises som en gjenoppretting.
+legacy-schema = Kildens oppbygning
+legacy-url = Kildedatabase
+legacy-url-hint = En mysql://-URL, helst for en bruker med bare SELECT-rettigheter. La stå tom for å laste opp en dump i stedet.
+legacy-file = Dump
+legacy-file-hint = En mysqldump- eller phpMyAdmin-SQL-eksport av kilden, opptil 2 MB. Bruk sortingoffice-cli legacy for større dumper.
+legacy-home = E-postkatalog
+legacy-uid = UID
+legacy-gid = GID
+legacy-preview = Forhåndsvis
+legacy-summary = { $count } poster konvertert fra { $source } for { $target } ({ $mode }).
+legacy-apply = Utfør overtakelse
+legacy-apply-hint = Alle endringer skrives i én transaksjon. Endringer databasen ikke tillater, hoppes over.
+legacy-notes = Ikke konvertert
+legacy-table-header-note = Merknad
+legacy-table-header-value = Verdi
+legacy-no-source = Oppgi kildedatabasen eller velg en dump.
+legacy-error-connect = Kunne ikke koble til kildedatabasen: { $detail }
+legacy-error-query = Kunne ikke lese kildedatabasen: { $detail }
+legacy-error-dump = Kunne ikke lese dumpen: { $detail }
+legacy-note-missing-table = Kilden har ingen slik tabell.
+legacy-note-dropped-field = Sorting Office har ikke noe felt for denne verdien.
+legacy-note-no-destination = Aliaset har ingen mål og ble utelatt.
+legacy-note-autoreply = Fraværsadresse fjernet; fraværssvar konverteres for seg.
+legacy-note-unknown-hash = Passordhashen oppgir ikke ordningen sin og ble kopiert uendret.
+
+# Database sync
+sync-title = Databasesynkronisering
+sync-description = Sammenligner to databaser oppføring for oppføring og kopierer de valgte forskjellene fra kilden til målet. Kilden blir bare lest.
+sync-source = Kildedatabase
+sync-target = Måldatabase
+sync-tables = Tabeller
+sync-compare = Sammenlign
+sync-summary = { $source } sammenlignet med { $target }.
+sync-no-databases = Du trenger tilgang til en database til og redigeringstilgang til målet.
+sync-same-database = Velg to forskjellige databaser.
+sync-no-tables = Velg minst én tabell å sammenligne.
+sync-no-differences = Databasene har de samme oppføringene.
+sync-no-selection = Velg oppføringene som skal synkroniseres.
+sync-table-header-select = Synkroniser
+sync-table-header-source-modified = Endret i kilden
+sync-table-header-target-modified = Endret i målet
+sync-status-create = Mangler i målet
+sync-status-update = Forskjellig
+sync-status-delete = Bare i målet
+sync-status-unchanged = Lik
+sync-status-error = Ikke tillatt
+sync-newer = nyere
+sync-password-differs = Passordhashene er forskjellige.
+sync-apply = Synkroniser valgte oppføringer
+sync-apply-hint = Valgte oppføringer kopieres til målet i én transaksjon; oppføringer som bare finnes i målet, slettes der.
+sync-applied = { $count } oppføringer synkronisert.
+sync-failed = Ingenting ble skrevet. { $detail }
+
 # Relays
 relays-title = Videresendinger
 relays-add = Legg til videresending
@@ -425,6 +672,15 @@ stats-quota-usage-description = Overvåk diskplassbruk på tvers av alle domener
 stats-quota-usage-percentage = Bruksprosent
 stats-quota-total = Total Kvote
 stats-quota-used = Brukt Kvote
+stats-usage-not-collected = Postboksbruk samles ikke inn. Angi kilden i [usage]-seksjonen i config.toml for å se faktisk bruk.
+stats-usage-last-collected = Sist innsamlet
+stats-largest-mailboxes = Største Postbokser
+stats-table-header-mailbox = Postboks
+stats-table-header-size = Størrelse
+stats-table-header-messages = Meldinger
+stats-table-header-quota = Kvote
+stats-table-header-growth = Vekst (30 dager)
+stats-usage-history = Brukshistorikk (30 dager)
 stats-recent-activity-title = Nylig Aktivitet
 stats-recent-domains = Nylig Domener
 stats-recent-users = Nylig Brukere
@@ -464,6 +720,7 @@ reports-status-present = "Tilstede og aktivert"
 reports-status-missing = "Mangler"
 reports-status-disabled = "Tilstede men deaktivert"
 reports-legend-title = "Statusforklaring"
+reports-alias-domain-of = "aliasdomene for"
 reports-list-title = "Rapporter"
 reports-list-description = "Vis og analyser e-postserverdata med omfattende rapporter"
 reports-view-report = "Vis rapport"
@@ -521,12 +778,46 @@ config-feature-no-new-domains = "Ingen Nye Domener"
 config-feature-no-password-updates = "Ingen Passordoppdateringer"
 config-feature-database-disabled = "Database Deaktivert"
 config-status-enabled = "Aktivert"
-config-status-disabled = "Deaktivert"
 
 # Database Feature Toggles
 config-database-features-header = "Database Funksjonsbrytere"
 config-database-features-description = "Databasespesifikke funksjonsrestriksjoner. Disse overstyrer globale innstillinger når de er aktivert."
 config-database-disabled-badge = "Deaktivert"
+config-saved = "Konfigurasjonen er lagret."
+config-save-failed = "Kunne ikke lagre konfigurasjonen: { $error }"
+config-reload-failed = "Innlasting av konfigurasjonsfilen feilet { $time }, så den forrige konfigurasjonen er fortsatt i bruk: { $error }"
+config-mail-config-link = "E-postserverkonfigurasjon"
+
+# Mail Server Config
+mail-config-title = "E-postserverkonfigurasjon"
+mail-config-description = "Postfix- og Dovecot-filer for SQL-oppslag generert fra skjemaet og feltkartet til hver database. Inkluderer Sieve-skript for fraværsmeldinger (Pigeonhole)."
+mail-config-download-all = "Last ned alt (ZIP)"
+mail-config-download-database = "Last ned ZIP"
+mail-config-no-databases = "Du har ikke redigeringstilgang til noen database."
+mail-config-credentials-warning = "Disse filene inneholder brukernavn og passord for databasen. Lagre dem slik at bare Postfix og Dovecot kan lese dem."
+
+# Delivery Simulator
+reports-delivery-title = "Leveringssimulator"
+reports-delivery-description = "Følg e-post til en adresse gjennom flyttede adresser, domener, aliaser og postbokser slik Postfix løser den opp."
+reports-delivery-address-label = "Mottakeradresse"
+reports-delivery-submit = "Spor"
+reports-delivery-route-header = "Rute"
+reports-delivery-final-header = "Havner hos"
+reports-delivery-no-final = "E-posten leveres ikke noe sted."
+reports-delivery-problems = "Denne ruten har løkker eller blindveier."
+reports-delivery-disabled = "Hoppet over fordi deaktivert:"
+reports-delivery-outcome-relocated = "Flyttet, returnert med den nye adressen"
+reports-delivery-outcome-alias = "Alias"
+reports-delivery-outcome-alias-domain = "Aliasdomene, skrevet om til måldomenet"
+reports-delivery-outcome-catch-all = "Oppsamlingsalias"
+reports-delivery-outcome-mailbox = "Postboks"
+reports-delivery-outcome-relay = "Videresendt for reservedomene"
+reports-delivery-outcome-external = "Ekstern adresse"
+reports-delivery-outcome-loop = "Løkke, allerede utvidet over"
+reports-delivery-outcome-unknown-user = "Ukjent bruker, avvist"
+reports-delivery-outcome-unknown-domain = "Domenet godtas ikke, avvist"
+reports-delivery-outcome-invalid-address = "Ikke en e-postadresse"
+reports-delivery-outcome-too-deep = "Aliaskjeden er for dyp, følges ikke"
 
 # Error messages
 error-internal-server = Intern serverfeil
@@ -537,7 +828,14 @@ error-database-error = Databasefeil
 error-validation-error = Valideringsfeil
 error-operation-not-allowed = Denne operasjonen er ikke tillatt på den nåværende databasen på grunn av begrensninger.
 error-passwords-do-not-match = Passordene samsvarer ikke
-error-password-too-short = Passordet må være minst 8 tegn
+password-policy-too-short = Passordet må være minst { $min } tegn.
+password-policy-missing-lowercase = Passordet må inneholde en liten bokstav.
+password-policy-missing-uppercase = Passordet må inneholde en stor bokstav.
+password-policy-missing-digit = Passordet må inneholde et siffer.
+password-policy-missing-symbol = Passordet må inneholde et spesialtegn.
+password-policy-contains-address = Passordet kan ikke inneholde postboksadressen.
+password-policy-contains-name = Passordet kan ikke inneholde brukerens navn.
+password-policy-common = Passordet er for vanlig.
 error-failed-to-update-password = Kunne ikke oppdatere passordet
 
 # Success messages
@@ -558,5 +856,71 @@ login-sign-in = Logg inn
 login-error = Ugyldig brukernavn eller passord
 login-error-invalid-credentials = Ugyldig brukernavn eller passord. Vennligst prøv igjen.
 login-error-empty-fields = Vennligst skriv inn både brukernavn og passord.
+login-mailbox-link = Postboksbruker? Logg inn for å administrere postboksen din
+
+# Postboksportal
+portal-login-title = Logg inn på postboksen din
+portal-login-address = E-postadresse
+portal-login-admin-link = Innlogging for administratorer
+portal-title = Min postboks
+portal-sign-out = Logg ut
+portal-must-change-password = Du må velge et nytt passord før du kan fortsette.
+portal-password-title = Endre passord
+portal-current-password = Nåværende passord
+portal-password-blocked = Passordendringer er for øyeblikket deaktivert for denne postboksen.
+portal-password-changed = Passordet ditt er endret.
+portal-error-current-password = Det nåværende passordet ditt er feil.
+portal-error-same-password = Det nye passordet må være forskjellig fra det nåværende.
+portal-aliases-title = Adresser som leveres hit
+portal-aliases-help = E-post sendt til disse aliasene havner i postboksen din.
+portal-aliases-empty = Ingen aliaser leverer til denne postboksen.
+portal-forwarding-title = Videresending
+portal-forwarding-help = Videresend innkommende e-post til andre adresser. La feltet stå tomt for å slutte å videresende.
+portal-forwarding-addresses = Videresend til (én adresse per linje)
+portal-forwarding-keep-copy = Behold en kopi i denne postboksen
+portal-forwarding-save = Lagre videresending
+portal-forwarding-saved = Videresendingen din er lagret.
+portal-forwarding-failed = Kunne ikke lagre videresendingen din.
+portal-forwarding-disabled = Videresending for denne adressen er deaktivert av en administrator.
+portal-forwarding-blocked = Videresending kan ikke endres akkurat nå.
+portal-forwarding-invalid-address = «{ $address }» er ikke en gyldig e-postadresse.
+portal-forwarding-too-many = Du kan videresende til maksimalt { $max } adresser.
 
 # Roles and Permissions
+
+# Audit Log
+audit-title = Revisjonslogg
+audit-description = Alle endringer gjort på domener, brukere, aliaser, sikkerhetskopier, reléer, flyttede adresser og klienter.
+audit-filter-admin = Administrator
+audit-filter-entity = Type
+audit-filter-from = Fra
+audit-filter-to = Til
+audit-filter-all = Alle
+audit-filter-apply = Filtrer
+audit-filter-reset = Tilbakestill
+audit-table-header-time = Tidspunkt
+audit-table-header-admin = Administrator
+audit-table-header-database = Database
+audit-table-header-entity = Type
+audit-table-header-name = Element
+audit-table-header-action = Handling
+audit-table-header-changes = Endringer
+audit-empty-title = Ingen revisjonsoppføringer
+audit-empty-description = Ingen endringer samsvarer med de valgte filtrene.
+audit-no-field-changes = Ingen feltendringer
+audit-history-title = Endringshistorikk
+audit-history-empty = Ingen endringer er registrert for dette elementet ennå.
+audit-entity-domain = Domene
+audit-entity-user = Bruker
+audit-entity-alias = Alias
+audit-entity-backup = Sikkerhetskopi
+audit-entity-relay = Relé
+audit-entity-relocated = Flyttet
+audit-entity-client = Klient
+audit-entity-vacation = Fraværsmelding
+audit-entity-alias-domain = Aliasdomene
+audit-action-create = Opprettet
+audit-action-update = Oppdatert
+audit-action-delete = Slettet
+audit-action-toggle = Aktivert/deaktivert
+audit-action-password = Passord endret
@@ -0,0 +1,19 @@
+use clap::Parser;
+use sortingoffice::cli::{self, Cli};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+#[tokio::main]
+async fn main() {
+    dotenvy::dotenv().ok();
+
+    // Logs go to stderr so stdout stays machine-readable
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
+        .with(tracing_subscriber::filter::EnvFilter::from_default_env())
+        .init();
+
+    if let Err(e) = cli::run(Cli::parse()).await {
+        eprintln!("error: {e:#}");
+        std::process::exit(1);
+    }
+}
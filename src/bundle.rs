@@ -0,0 +1,440 @@
+//! Portable export bundles of a managed database
+//!
+//! A bundle is a versioned JSON or YAML document holding every record of one
+//! database under the logical field names used throughout Sorting Office, so
+//! it reads the same whatever the database's [`DatabaseConfig::field_map`]
+//! says. Surrogate keys and timestamps are left out; records are identified
+//! by their natural keys and sorted by them, which keeps bundles stable
+//! enough to keep in git. Password hashes are only included on request.
+
+use crate::config::DatabaseConfig;
+use crate::models::*;
+use crate::{db, DbPool};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use diesel::result::Error;
+use serde::{Deserialize, Serialize};
+
+/// Version written to new bundles; bump when the layout changes incompatibly
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Document format of a bundle
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BundleFormat {
+    #[default]
+    Json,
+    Yaml,
+}
+
+impl BundleFormat {
+    /// The format implied by a file name's extension, if it names one
+    pub fn from_path(path: &std::path::Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "json" => Some(BundleFormat::Json),
+            "yaml" | "yml" => Some(BundleFormat::Yaml),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            BundleFormat::Json => "json",
+            BundleFormat::Yaml => "yaml",
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            BundleFormat::Json => "application/json",
+            BundleFormat::Yaml => "application/yaml",
+        }
+    }
+}
+
+/// Why a document could not be read as a bundle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BundleError {
+    /// Neither valid JSON nor YAML of a bundle
+    Syntax(String),
+    /// Written by a newer version of Sorting Office
+    Version(u32),
+}
+
+impl BundleError {
+    /// Fluent message ID, which takes the detail as `$detail`
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            BundleError::Syntax(_) => "restore-error-syntax",
+            BundleError::Version(_) => "restore-error-version",
+        }
+    }
+
+    pub fn detail(&self) -> String {
+        match self {
+            BundleError::Syntax(detail) => detail.clone(),
+            BundleError::Version(version) => version.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for BundleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BundleError::Syntax(detail) => write!(f, "The bundle could not be read: {detail}"),
+            BundleError::Version(version) => write!(
+                f,
+                "The bundle is version {version}, newer than the supported version {BUNDLE_VERSION}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BundleError {}
+
+/// Reads a JSON or YAML bundle
+pub fn parse(content: &str) -> Result<Bundle, BundleError> {
+    let content = content.trim_start_matches('\u{feff}');
+    let bundle: Bundle = if content.trim_start().starts_with('{') {
+        serde_json::from_str(content).map_err(|e| BundleError::Syntax(e.to_string()))?
+    } else {
+        serde_yaml::from_str(content).map_err(|e| BundleError::Syntax(e.to_string()))?
+    };
+    if bundle.version > BUNDLE_VERSION {
+        return Err(BundleError::Version(bundle.version));
+    }
+    Ok(bundle)
+}
+
+/// Everything stored in one database
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bundle {
+    pub version: u32,
+    /// Id of the database the bundle was exported from
+    pub database: String,
+    pub exported_at: DateTime<Utc>,
+    /// Whether `users` carry their password hashes
+    #[serde(default)]
+    pub password_hashes: bool,
+    #[serde(default)]
+    pub domains: Vec<BundleDomain>,
+    #[serde(default)]
+    pub backups: Vec<BundleBackup>,
+    #[serde(default)]
+    pub users: Vec<BundleUser>,
+    #[serde(default)]
+    pub aliases: Vec<BundleAlias>,
+    #[serde(default)]
+    pub alias_domains: Vec<BundleAliasDomain>,
+    #[serde(default)]
+    pub relays: Vec<BundleRelay>,
+    #[serde(default)]
+    pub relocated: Vec<BundleRelocated>,
+    #[serde(default)]
+    pub clients: Vec<BundleClient>,
+    #[serde(default)]
+    pub vacations: Vec<BundleVacation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleDomain {
+    pub domain: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transport: Option<String>,
+    pub enabled: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_quota_bytes: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_quota_messages: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quota_cap_bytes: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleBackup {
+    pub domain: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transport: Option<String>,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleUser {
+    pub id: String,
+    pub name: String,
+    pub maildir: String,
+    pub home: String,
+    pub uid: u16,
+    pub gid: u16,
+    pub enabled: bool,
+    pub change_password: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quota_bytes: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quota_messages: Option<i64>,
+    /// Stored hash, only present in bundles exported with password hashes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleAlias {
+    pub mail: String,
+    pub destination: String,
+    pub enabled: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_from: Option<NaiveDateTime>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleAliasDomain {
+    pub alias_domain: String,
+    pub target_domain: String,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleRelay {
+    pub recipient: String,
+    pub status: String,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleRelocated {
+    pub old_address: String,
+    pub new_address: String,
+    pub enabled: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_from: Option<NaiveDateTime>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleClient {
+    pub client: String,
+    pub status: String,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleVacation {
+    pub user_id: String,
+    pub subject: String,
+    pub body: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_from: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_until: Option<NaiveDate>,
+    pub reply_interval_days: i32,
+    pub enabled: bool,
+}
+
+impl From<Domain> for BundleDomain {
+    fn from(domain: Domain) -> Self {
+        BundleDomain {
+            domain: domain.domain,
+            transport: domain.transport,
+            enabled: domain.enabled,
+            default_quota_bytes: domain.default_quota_bytes,
+            default_quota_messages: domain.default_quota_messages,
+            quota_cap_bytes: domain.quota_cap_bytes,
+        }
+    }
+}
+
+impl From<Backup> for BundleBackup {
+    fn from(backup: Backup) -> Self {
+        BundleBackup {
+            domain: backup.domain,
+            transport: backup.transport,
+            enabled: backup.enabled,
+        }
+    }
+}
+
+impl BundleUser {
+    /// The user's record, with its hash only when `password_hashes` is set
+    pub fn new(user: User, password_hashes: bool) -> Self {
+        BundleUser {
+            password_hash: password_hashes.then_some(user.crypt),
+            id: user.id,
+            name: user.name,
+            maildir: user.maildir,
+            home: user.home,
+            uid: user.uid,
+            gid: user.gid,
+            enabled: user.enabled,
+            change_password: user.change_password,
+            quota_bytes: user.quota_bytes,
+            quota_messages: user.quota_messages,
+        }
+    }
+}
+
+impl From<Alias> for BundleAlias {
+    fn from(alias: Alias) -> Self {
+        BundleAlias {
+            mail: alias.mail,
+            destination: alias.destination,
+            enabled: alias.enabled,
+            valid_from: alias.valid_from,
+            valid_until: alias.valid_until,
+        }
+    }
+}
+
+impl From<AliasDomain> for BundleAliasDomain {
+    fn from(alias_domain: AliasDomain) -> Self {
+        BundleAliasDomain {
+            alias_domain: alias_domain.alias_domain,
+            target_domain: alias_domain.target_domain,
+            enabled: alias_domain.enabled,
+        }
+    }
+}
+
+impl From<Relay> for BundleRelay {
+    fn from(relay: Relay) -> Self {
+        BundleRelay {
+            recipient: relay.recipient,
+            status: relay.status,
+            enabled: relay.enabled,
+        }
+    }
+}
+
+impl From<Relocated> for BundleRelocated {
+    fn from(relocated: Relocated) -> Self {
+        BundleRelocated {
+            old_address: relocated.old_address,
+            new_address: relocated.new_address,
+            enabled: relocated.enabled,
+            valid_from: relocated.valid_from,
+            valid_until: relocated.valid_until,
+        }
+    }
+}
+
+impl From<Client> for BundleClient {
+    fn from(client: Client) -> Self {
+        BundleClient {
+            client: client.client,
+            status: client.status,
+            enabled: client.enabled,
+        }
+    }
+}
+
+impl From<Vacation> for BundleVacation {
+    fn from(vacation: Vacation) -> Self {
+        BundleVacation {
+            user_id: vacation.user_id,
+            subject: vacation.subject,
+            body: vacation.body,
+            active_from: vacation.active_from,
+            active_until: vacation.active_until,
+            reply_interval_days: vacation.reply_interval_days,
+            enabled: vacation.enabled,
+        }
+    }
+}
+
+/// Converts records and sorts them by `key`
+fn sorted<T, B: From<T>, K: Ord>(records: Vec<T>, key: impl Fn(&B) -> K) -> Vec<B> {
+    let mut records: Vec<B> = records.into_iter().map(B::from).collect();
+    records.sort_by_key(key);
+    records
+}
+
+impl Bundle {
+    /// An empty bundle of the current version for `database`
+    pub fn new(database: &str, password_hashes: bool) -> Self {
+        Bundle {
+            version: BUNDLE_VERSION,
+            database: database.to_string(),
+            exported_at: Utc::now(),
+            password_hashes,
+            domains: Vec::new(),
+            backups: Vec::new(),
+            users: Vec::new(),
+            aliases: Vec::new(),
+            alias_domains: Vec::new(),
+            relays: Vec::new(),
+            relocated: Vec::new(),
+            clients: Vec::new(),
+            vacations: Vec::new(),
+        }
+    }
+
+    /// Number of records across all tables
+    pub fn record_count(&self) -> usize {
+        self.domains.len()
+            + self.backups.len()
+            + self.users.len()
+            + self.aliases.len()
+            + self.alias_domains.len()
+            + self.relays.len()
+            + self.relocated.len()
+            + self.clients.len()
+            + self.vacations.len()
+    }
+
+    /// The bundle as a JSON or YAML document
+    pub fn to_string(&self, format: BundleFormat) -> Result<String, String> {
+        match format {
+            BundleFormat::Json => serde_json::to_string_pretty(self)
+                .map(|json| json + "\n")
+                .map_err(|e| e.to_string()),
+            BundleFormat::Yaml => serde_yaml::to_string(self).map_err(|e| e.to_string()),
+        }
+    }
+
+    /// Download name of the bundle, e.g. `sortingoffice-main-20261018-093000.json`
+    pub fn file_name(&self, format: BundleFormat) -> String {
+        format!(
+            "sortingoffice-{}-{}.{}",
+            self.database,
+            self.exported_at.format("%Y%m%d-%H%M%S"),
+            format.extension()
+        )
+    }
+}
+
+/// Reads every record of `db_config`'s database into a bundle, reading users,
+/// domains and aliases through its field map
+pub fn export(
+    pool: &DbPool,
+    db_config: &DatabaseConfig,
+    password_hashes: bool,
+) -> Result<Bundle, Error> {
+    let mut bundle = Bundle::new(&db_config.id, password_hashes);
+    bundle.domains = sorted(
+        db::get_domains_with_field_map(pool, db_config)?,
+        |d: &BundleDomain| d.domain.clone(),
+    );
+    bundle.backups = sorted(db::get_backups(pool)?, |b: &BundleBackup| b.domain.clone());
+    bundle.users = db::get_users_with_field_map(pool, db_config)?
+        .into_iter()
+        .map(|user| BundleUser::new(user, password_hashes))
+        .collect();
+    bundle.users.sort_by(|a, b| a.id.cmp(&b.id));
+    bundle.aliases = sorted(
+        db::get_aliases_with_field_map(pool, db_config)?,
+        |a: &BundleAlias| a.mail.clone(),
+    );
+    bundle.alias_domains = sorted(db::get_alias_domains(pool)?, |a: &BundleAliasDomain| {
+        a.alias_domain.clone()
+    });
+    bundle.relays = sorted(db::get_relays(pool)?, |r: &BundleRelay| r.recipient.clone());
+    bundle.relocated = sorted(db::get_relocated(pool)?, |r: &BundleRelocated| {
+        r.old_address.clone()
+    });
+    bundle.clients = sorted(db::get_clients(pool)?, |c: &BundleClient| c.client.clone());
+    bundle.vacations = sorted(db::get_vacations(pool)?, |v: &BundleVacation| {
+        v.user_id.clone()
+    });
+    Ok(bundle)
+}
@@ -0,0 +1,1710 @@
+//! `sortingoffice-cli`: scripted administration without the web interface
+//!
+//! The CLI loads the same `Config` and `DatabaseManager` as the server, calls the
+//! `db` functions directly and applies the database feature toggles through
+//! [`check_config_restrictions`], so cron jobs and Ansible get the same
+//! behaviour as the web UI. Changes are recorded in the audit log as
+//! `cli:<user>`.
+
+use crate::bundle::{self, Bundle, BundleFormat};
+use crate::config::{Config, DatabaseConfig, UsageSource};
+use crate::db::{self, AuditContext, DatabaseManager};
+use crate::delivery;
+use crate::handlers::utils::check_config_restrictions;
+use crate::import::{self, ImportKind, ImportOptions, ReportRow};
+use crate::legacy::{self, LegacyOptions, LegacySchema};
+use crate::mail_config;
+use crate::models::*;
+use crate::password;
+use crate::quota;
+use crate::restore::{self, BundleTable, RestoreAction, RestoreChange, RestoreMode};
+use crate::sync::{self, SyncRow};
+use crate::usage;
+use crate::validity;
+use crate::DbPool;
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "sortingoffice-cli",
+    version,
+    about = "Command-line administration for Sorting Office"
+)]
+pub struct Cli {
+    /// Database ID from the config (defaults to the first configured database)
+    #[arg(long, global = true)]
+    pub database: Option<String>,
+
+    /// Output format
+    #[arg(long, alias = "format", value_enum, default_value_t = OutputFormat::Table, global = true)]
+    pub output: OutputFormat,
+
+    /// Config file (defaults to the same search paths as the server)
+    #[arg(long, global = true)]
+    pub config: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Table,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Manage mail domains
+    Domain {
+        #[command(subcommand)]
+        action: DomainCommand,
+    },
+    /// Manage mail users
+    User {
+        #[command(subcommand)]
+        action: UserCommand,
+    },
+    /// Manage aliases
+    Alias {
+        #[command(subcommand)]
+        action: AliasCommand,
+    },
+    /// Manage relay recipients
+    Relay {
+        #[command(subcommand)]
+        action: RelayCommand,
+    },
+    /// Manage relocated addresses
+    Relocated {
+        #[command(subcommand)]
+        action: RelocatedCommand,
+    },
+    /// Manage client access rules
+    Client {
+        #[command(subcommand)]
+        action: ClientCommand,
+    },
+    /// Run pending database migrations
+    Migrate {
+        /// Migrate every configured database instead of only `--database`
+        #[arg(long)]
+        all: bool,
+    },
+    /// Print a report
+    Report {
+        #[command(subcommand)]
+        report: ReportCommand,
+    },
+    /// Collect and inspect mailbox usage
+    Usage {
+        #[command(subcommand)]
+        action: UsageCommand,
+    },
+    /// Import records from a CSV or TSV file
+    ///
+    /// Prints what the import would do; nothing is written without `--apply`.
+    Import {
+        /// The records the file holds
+        kind: ImportKind,
+        /// The file to read, or `-` for standard input
+        file: PathBuf,
+        /// Import a column into a field, e.g. `goto=destination`; `HEADER=-` skips it
+        #[arg(long = "map", value_name = "HEADER=FIELD", value_parser = parse_mapping)]
+        map: Vec<(String, String)>,
+        /// Update records that already exist instead of reporting conflicts
+        #[arg(long)]
+        update: bool,
+        /// Write the creates and updates in one transaction
+        #[arg(long)]
+        apply: bool,
+        /// Write the conflicts and errors to this CSV file
+        #[arg(long)]
+        report: Option<PathBuf>,
+    },
+    /// Restore a JSON or YAML export bundle into the database
+    ///
+    /// Prints the changes the restore makes; nothing is written without `--apply`.
+    Restore {
+        /// The bundle to read, or `-` for standard input
+        file: PathBuf,
+        /// `merge` keeps records the bundle does not contain, `replace` deletes them
+        #[arg(long, value_enum, default_value = "merge")]
+        mode: RestoreMode,
+        /// Write the changes in one transaction
+        #[arg(long)]
+        apply: bool,
+    },
+    /// Convert a PostfixAdmin or ISPmail database into the database
+    ///
+    /// Reads the source database, which is only queried, or a dump of it and
+    /// prints the changes and what could not be converted; nothing is written
+    /// without `--apply`.
+    Legacy {
+        /// The layout of the source
+        schema: LegacySchema,
+        /// A `mysql://` URL of the source database, or a dump file (`-` for standard input)
+        source: String,
+        /// `merge` keeps records the source does not contain, `replace` deletes them
+        #[arg(long, value_enum, default_value = "merge")]
+        mode: RestoreMode,
+        /// The mail home the converted users' maildirs are relative to
+        #[arg(long, default_value = "/var/vmail")]
+        home: String,
+        /// The uid of the converted users
+        #[arg(long, default_value_t = 5000)]
+        uid: u16,
+        /// The gid of the converted users
+        #[arg(long, default_value_t = 5000)]
+        gid: u16,
+        /// Also write the converted records to this bundle file
+        #[arg(long)]
+        bundle: Option<PathBuf>,
+        /// Write the changes in one transaction
+        #[arg(long)]
+        apply: bool,
+    },
+    /// Compare another database with this one and sync records into it
+    ///
+    /// Prints the records missing from either database or differing between
+    /// them, with when each side last modified them; nothing is written
+    /// without `--apply`.
+    Sync {
+        /// Database ID to copy records from; `--database` is the target
+        from: String,
+        /// Table to compare, repeatable (default: domains, backups and relays)
+        #[arg(long = "table", value_parser = parse_table)]
+        tables: Vec<BundleTable>,
+        /// Only sync this record, as `table:key`, repeatable
+        #[arg(long)]
+        only: Vec<String>,
+        /// Also delete records only the target has
+        #[arg(long)]
+        delete: bool,
+        /// Write the changes in one transaction
+        #[arg(long)]
+        apply: bool,
+    },
+    /// Export every record of the database to a JSON or YAML bundle
+    Export {
+        /// The file to write, or `-` for standard output; `.yaml` and `.yml` write YAML
+        file: Option<PathBuf>,
+        /// Write YAML instead of JSON
+        #[arg(long)]
+        yaml: bool,
+        /// Include the users' password hashes
+        #[arg(long)]
+        password_hashes: bool,
+    },
+    /// Generate Postfix and Dovecot SQL lookup config
+    ///
+    /// Prints the files of `--database` unless `--zip` or `--dir` is given.
+    MailConfig {
+        /// Generate the files for every configured database
+        #[arg(long)]
+        all: bool,
+        /// Write a ZIP archive with one directory per database
+        #[arg(long, conflicts_with = "dir")]
+        zip: Option<PathBuf>,
+        /// Write the files into this directory, one subdirectory per database
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum DomainCommand {
+    /// List domains
+    List,
+    /// Add a domain
+    Add {
+        domain: String,
+        #[arg(long, default_value = "virtual")]
+        transport: String,
+        /// Create the domain disabled
+        #[arg(long)]
+        disabled: bool,
+        /// Size quota for mailboxes without their own, e.g. `2G`
+        #[arg(long, value_parser = quota::parse_size)]
+        default_quota: Option<i64>,
+        /// Message quota for mailboxes without their own
+        #[arg(long, value_parser = quota::parse_count)]
+        default_quota_messages: Option<i64>,
+        /// Most the domain's mailbox size quotas may add up to, e.g. `100G`
+        #[arg(long, value_parser = quota::parse_size)]
+        quota_cap: Option<i64>,
+    },
+    /// Enable a domain
+    Enable { domain: String },
+    /// Disable a domain
+    Disable { domain: String },
+    /// Delete a domain
+    Rm { domain: String },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum UserCommand {
+    /// List users
+    List,
+    /// Add a user; the password is read from stdin unless `--password` is given
+    Add {
+        email: String,
+        #[arg(long, default_value = "")]
+        name: String,
+        #[arg(long)]
+        password: Option<String>,
+        /// Create the user disabled
+        #[arg(long)]
+        disabled: bool,
+        /// Mailbox size quota, e.g. `2G`; the domain default applies when omitted
+        #[arg(long, value_parser = quota::parse_size)]
+        quota: Option<i64>,
+        /// Mailbox message quota; the domain default applies when omitted
+        #[arg(long, value_parser = quota::parse_count)]
+        quota_messages: Option<i64>,
+    },
+    /// Set a user's password; read from stdin unless `--password` is given
+    Passwd {
+        email: String,
+        #[arg(long)]
+        password: Option<String>,
+    },
+    /// Enable a user
+    Enable { email: String },
+    /// Disable a user
+    Disable { email: String },
+    /// Delete a user
+    Rm { email: String },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum AliasCommand {
+    /// List aliases
+    List,
+    /// Add an alias
+    Add {
+        mail: String,
+        /// Destination addresses, separately or comma-separated
+        #[arg(required = true)]
+        destinations: Vec<String>,
+        /// Create the alias disabled
+        #[arg(long)]
+        disabled: bool,
+        /// Start of the validity window, e.g. `2026-11-02T09:00` (UTC)
+        #[arg(long, value_parser = parse_datetime)]
+        valid_from: Option<chrono::NaiveDateTime>,
+        /// End of the validity window, e.g. `2026-11-06T18:00` (UTC)
+        #[arg(long, value_parser = parse_datetime)]
+        valid_until: Option<chrono::NaiveDateTime>,
+    },
+    /// Enable an alias
+    Enable { mail: String },
+    /// Disable an alias
+    Disable { mail: String },
+    /// Delete an alias
+    Rm { mail: String },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum RelayCommand {
+    /// List relay recipients
+    List,
+    /// Add a relay recipient
+    Add {
+        recipient: String,
+        #[arg(long, default_value = "OK")]
+        status: String,
+    },
+    /// Enable a relay recipient
+    Enable { recipient: String },
+    /// Disable a relay recipient
+    Disable { recipient: String },
+    /// Delete a relay recipient
+    Rm { recipient: String },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum RelocatedCommand {
+    /// List relocated addresses
+    List,
+    /// Add a relocated address
+    Add {
+        old_address: String,
+        new_address: String,
+        /// Start of the validity window, e.g. `2026-11-02T09:00` (UTC)
+        #[arg(long, value_parser = parse_datetime)]
+        valid_from: Option<chrono::NaiveDateTime>,
+        /// End of the validity window, e.g. `2027-05-01` (UTC)
+        #[arg(long, value_parser = parse_datetime)]
+        valid_until: Option<chrono::NaiveDateTime>,
+    },
+    /// Enable a relocated address
+    Enable { old_address: String },
+    /// Disable a relocated address
+    Disable { old_address: String },
+    /// Delete a relocated address
+    Rm { old_address: String },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ClientCommand {
+    /// List client access rules
+    List,
+    /// Add a client access rule
+    Add {
+        client: String,
+        #[arg(long, default_value = "allowed")]
+        status: String,
+    },
+    /// Enable a client access rule
+    Enable { client: String },
+    /// Disable a client access rule
+    Disable { client: String },
+    /// Delete a client access rule
+    Rm { client: String },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ReportCommand {
+    /// Aliases and users whose domain does not exist
+    Orphaned,
+    /// Aliases forwarding outside the managed domains
+    ExternalForwarders,
+    /// Domains missing required aliases
+    MissingAliases,
+    /// Catch-all aliases per domain
+    CatchAll,
+    /// Where mail to an address ends up, hop by hop
+    Delivery { address: String },
+    /// Users whose password hash is weaker than the database's scheme
+    WeakHashes,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum UsageCommand {
+    /// Record a usage snapshot now, using the `[usage]` config
+    Collect {
+        /// Collect for every configured database instead of only `--database`
+        #[arg(long)]
+        all: bool,
+    },
+    /// List the biggest mailboxes at the latest collection
+    Top {
+        #[arg(long, default_value_t = 10)]
+        limit: i64,
+        /// Days over which growth is measured
+        #[arg(long, default_value_t = 30)]
+        days: i64,
+    },
+}
+
+/// Parse a validity window boundary given on the command line
+fn parse_datetime(text: &str) -> Result<chrono::NaiveDateTime, String> {
+    parse_datetime_input(text).ok_or_else(|| format!("Invalid date and time: {text}"))
+}
+
+fn datetime_cell(value: Option<chrono::NaiveDateTime>) -> String {
+    value
+        .map(|value| value.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+/// Records that can be printed as a table or CSV
+pub trait TableRow {
+    const HEADERS: &'static [&'static str];
+
+    fn cells(&self) -> Vec<String>;
+}
+
+impl TableRow for Domain {
+    const HEADERS: &'static [&'static str] = &["id", "domain", "transport", "enabled"];
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.pkid.to_string(),
+            self.domain.clone(),
+            self.transport.clone().unwrap_or_default(),
+            self.enabled.to_string(),
+        ]
+    }
+}
+
+impl TableRow for PublicUser {
+    const HEADERS: &'static [&'static str] = &["id", "name", "maildir", "quota", "enabled"];
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.id.clone(),
+            self.name.clone(),
+            self.maildir.clone(),
+            self.quota_bytes.map(quota::format_size).unwrap_or_default(),
+            self.enabled.to_string(),
+        ]
+    }
+}
+
+impl TableRow for Alias {
+    const HEADERS: &'static [&'static str] = &[
+        "id",
+        "mail",
+        "destination",
+        "enabled",
+        "valid_from",
+        "valid_until",
+    ];
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.pkid.to_string(),
+            self.mail.clone(),
+            self.destination.clone(),
+            self.enabled.to_string(),
+            datetime_cell(self.valid_from),
+            datetime_cell(self.valid_until),
+        ]
+    }
+}
+
+impl TableRow for Relay {
+    const HEADERS: &'static [&'static str] = &["id", "recipient", "status", "enabled"];
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.pkid.to_string(),
+            self.recipient.clone(),
+            self.status.clone(),
+            self.enabled.to_string(),
+        ]
+    }
+}
+
+impl TableRow for Relocated {
+    const HEADERS: &'static [&'static str] = &[
+        "id",
+        "old_address",
+        "new_address",
+        "enabled",
+        "valid_from",
+        "valid_until",
+    ];
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.pkid.to_string(),
+            self.old_address.clone(),
+            self.new_address.clone(),
+            self.enabled.to_string(),
+            datetime_cell(self.valid_from),
+            datetime_cell(self.valid_until),
+        ]
+    }
+}
+
+impl TableRow for Client {
+    const HEADERS: &'static [&'static str] = &["id", "client", "status", "enabled"];
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.client.clone(),
+            self.status.clone(),
+            self.enabled.to_string(),
+        ]
+    }
+}
+
+impl TableRow for ReportRow {
+    const HEADERS: &'static [&'static str] = &["line", "key", "status", "message"];
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.line.to_string(),
+            self.key.clone(),
+            self.status.to_string(),
+            self.message.clone(),
+        ]
+    }
+}
+
+impl TableRow for &RestoreChange {
+    const HEADERS: &'static [&'static str] = &["table", "key", "status", "details"];
+
+    fn cells(&self) -> Vec<String> {
+        let details = match self.issue {
+            Some(issue) => issue.to_string(),
+            None => self
+                .changes
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("; "),
+        };
+        vec![
+            self.table.name().to_string(),
+            self.key.clone(),
+            self.status().to_string(),
+            details,
+        ]
+    }
+}
+
+impl TableRow for SyncRow<'_> {
+    const HEADERS: &'static [&'static str] = &[
+        "table",
+        "key",
+        "status",
+        "details",
+        "source_modified",
+        "target_modified",
+        "newer",
+    ];
+
+    fn cells(&self) -> Vec<String> {
+        let mut cells = self.change.cells();
+        for modified in [self.source_modified, self.target_modified] {
+            cells.push(modified.map(|m| m.to_string()).unwrap_or_default());
+        }
+        cells.push(
+            self.newer
+                .map(|side| side.name())
+                .unwrap_or_default()
+                .to_string(),
+        );
+        cells
+    }
+}
+
+impl TableRow for MailboxUsageSummary {
+    const HEADERS: &'static [&'static str] = &[
+        "mailbox",
+        "bytes",
+        "messages",
+        "quota",
+        "growth",
+        "collected",
+    ];
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.user_id.clone(),
+            self.bytes.to_string(),
+            self.messages.to_string(),
+            self.quota_bytes.to_string(),
+            self.growth_bytes.to_string(),
+            self.collected.to_string(),
+        ]
+    }
+}
+
+/// Render rows as an aligned text table or CSV
+pub fn render_rows(format: OutputFormat, headers: &[&str], rows: &[Vec<String>]) -> Result<String> {
+    match format {
+        OutputFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(vec![]);
+            writer.write_record(headers)?;
+            for row in rows {
+                writer.write_record(row)?;
+            }
+            Ok(String::from_utf8(writer.into_inner()?)?)
+        }
+        _ => {
+            let mut widths: Vec<usize> = headers.iter().map(|h| h.len()).collect();
+            for row in rows {
+                for (width, cell) in widths.iter_mut().zip(row) {
+                    *width = (*width).max(cell.chars().count());
+                }
+            }
+            let format_line = |cells: Vec<&str>| {
+                let line = cells
+                    .iter()
+                    .zip(&widths)
+                    .map(|(cell, width)| format!("{cell:<width$}"))
+                    .collect::<Vec<_>>()
+                    .join("  ");
+                format!("{}\n", line.trim_end())
+            };
+            let mut output = format_line(headers.to_vec());
+            for row in rows {
+                output.push_str(&format_line(row.iter().map(String::as_str).collect()));
+            }
+            Ok(output)
+        }
+    }
+}
+
+/// Render records in the requested format
+pub fn render_records<T: Serialize + TableRow>(
+    format: OutputFormat,
+    records: &[T],
+) -> Result<String> {
+    match format {
+        OutputFormat::Json => Ok(format!("{}\n", serde_json::to_string_pretty(records)?)),
+        _ => {
+            let rows: Vec<Vec<String>> = records.iter().map(TableRow::cells).collect();
+            render_rows(format, T::HEADERS, &rows)
+        }
+    }
+}
+
+/// Render a report: JSON keeps its structure, table and CSV use flattened rows
+pub fn render_report<T: Serialize>(
+    format: OutputFormat,
+    report: &T,
+    headers: &[&str],
+    rows: &[Vec<String>],
+) -> Result<String> {
+    match format {
+        OutputFormat::Json => Ok(format!("{}\n", serde_json::to_string_pretty(report)?)),
+        _ => render_rows(format, headers, rows),
+    }
+}
+
+/// Load the config from `--config` or the server's default search paths
+pub fn load_config(path: Option<&PathBuf>) -> Result<Config> {
+    match path {
+        Some(path) => Config::from_file(path)
+            .map_err(|e| anyhow!("Failed to load config {}: {e}", path.display())),
+        None => Config::load().map_err(|e| anyhow!("Failed to load config: {e}")),
+    }
+}
+
+struct CliContext {
+    config: Config,
+    db_manager: DatabaseManager,
+    database_id: String,
+    output: OutputFormat,
+    audit: AuditContext,
+}
+
+impl CliContext {
+    async fn pool(&self) -> Result<DbPool> {
+        self.db_manager
+            .get_pool(&self.database_id)
+            .await
+            .ok_or_else(|| anyhow!("Unknown database '{}'", self.database_id))
+    }
+
+    fn check(&self, operation: &str) -> Result<()> {
+        check_config_restrictions(&self.config, None, &self.database_id, operation).map_err(|_| {
+            anyhow!(
+                "Operation '{operation}' is not allowed on database '{}'",
+                self.database_id
+            )
+        })
+    }
+
+    fn print_records<T: Serialize + TableRow>(&self, records: &[T]) -> Result<()> {
+        print!("{}", render_records(self.output, records)?);
+        Ok(())
+    }
+}
+
+/// Run a parsed command line
+pub async fn run(cli: Cli) -> Result<()> {
+    let config = load_config(cli.config.as_ref())?;
+    // Generating the mail server config needs no database connection
+    if let Command::MailConfig { all, zip, dir } = cli.command {
+        return run_mail_config(&config, cli.database, cli.output, all, zip, dir);
+    }
+    let db_manager = DatabaseManager::from_config(&config)
+        .await
+        .map_err(|e| anyhow!("Failed to connect to the databases: {e}"))?;
+    let database_id = cli
+        .database
+        .unwrap_or_else(|| db_manager.get_default_db_id().to_string());
+    if !db_manager.has_database(&database_id).await {
+        bail!("Unknown database '{database_id}'");
+    }
+
+    let user = std::env::var("USER").unwrap_or_else(|_| "unknown".to_string());
+    let ctx = CliContext {
+        audit: AuditContext::new(format!("cli:{user}"), database_id.clone()),
+        config,
+        db_manager,
+        database_id,
+        output: cli.output,
+    };
+
+    match cli.command {
+        Command::Domain { action } => run_domain(&ctx, action).await,
+        Command::User { action } => run_user(&ctx, action).await,
+        Command::Alias { action } => run_alias(&ctx, action).await,
+        Command::Relay { action } => run_relay(&ctx, action).await,
+        Command::Relocated { action } => run_relocated(&ctx, action).await,
+        Command::Client { action } => run_client(&ctx, action).await,
+        Command::Migrate { all } => run_migrate(&ctx, all).await,
+        Command::Report { report } => run_report(&ctx, report).await,
+        Command::Usage { action } => run_usage(&ctx, action).await,
+        Command::Import {
+            kind,
+            file,
+            map,
+            update,
+            apply,
+            report,
+        } => run_import(&ctx, kind, &file, &map, update, apply, report.as_deref()).await,
+        Command::Restore { file, mode, apply } => run_restore(&ctx, &file, mode, apply).await,
+        Command::Legacy {
+            schema,
+            source,
+            mode,
+            home,
+            uid,
+            gid,
+            bundle,
+            apply,
+        } => {
+            let options = LegacyOptions { home, uid, gid };
+            run_legacy(
+                &ctx,
+                schema,
+                &source,
+                &options,
+                bundle.as_deref(),
+                mode,
+                apply,
+            )
+            .await
+        }
+        Command::Sync {
+            from,
+            tables,
+            only,
+            delete,
+            apply,
+        } => run_sync(&ctx, &from, &tables, &only, delete, apply).await,
+        Command::Export {
+            file,
+            yaml,
+            password_hashes,
+        } => run_export(&ctx, file.as_deref(), yaml, password_hashes).await,
+        Command::MailConfig { .. } => unreachable!("handled before connecting"),
+    }
+}
+
+/// Read a password from the first line of stdin
+fn read_password() -> Result<String> {
+    let mut line = String::new();
+    std::io::stdin()
+        .read_line(&mut line)
+        .context("Failed to read password from stdin")?;
+    let password = line.trim_end_matches(['\r', '\n']).to_string();
+    if password.is_empty() {
+        bail!("Password must not be empty");
+    }
+    Ok(password)
+}
+
+/// Reject a new mailbox password that breaks the database's policy
+fn check_password_policy(
+    ctx: &CliContext,
+    password: &str,
+    address: &str,
+    name: &str,
+) -> Result<()> {
+    let policy = ctx.config.password_policy(&ctx.database_id);
+    let violations = password::check_policy(policy, password, address, name);
+    if !violations.is_empty() {
+        let messages: Vec<String> = violations.iter().map(ToString::to_string).collect();
+        bail!("{}", messages.join("; "));
+    }
+    Ok(())
+}
+
+fn deleted(count: usize, what: &str, name: &str) -> Result<()> {
+    if count == 0 {
+        bail!("{what} '{name}' not found");
+    }
+    Ok(())
+}
+
+/// Toggle a record only if it is not already in the wanted state, so repeated
+/// runs from cron or Ansible are no-ops
+fn set_enabled<T: Serialize + TableRow>(
+    ctx: &CliContext,
+    record: T,
+    is_enabled: bool,
+    wanted: bool,
+    operation: &str,
+    toggle: impl FnOnce() -> Result<T, diesel::result::Error>,
+) -> Result<()> {
+    let record = if is_enabled == wanted {
+        record
+    } else {
+        ctx.check(operation)?;
+        toggle()?
+    };
+    ctx.print_records(&[record])
+}
+
+async fn run_domain(ctx: &CliContext, action: DomainCommand) -> Result<()> {
+    let pool = ctx.pool().await?;
+    let find = |name: &str| {
+        db::get_domain_by_name(&pool, name).with_context(|| format!("Domain '{name}' not found"))
+    };
+    match action {
+        DomainCommand::List => ctx.print_records(&db::get_domains(&pool)?),
+        DomainCommand::Add {
+            domain,
+            transport,
+            disabled,
+            default_quota,
+            default_quota_messages,
+            quota_cap,
+        } => {
+            ctx.check("create_domain")?;
+            let new_domain = NewDomain {
+                domain,
+                transport: Some(transport),
+                enabled: !disabled,
+                default_quota_bytes: default_quota,
+                default_quota_messages,
+                quota_cap_bytes: quota_cap,
+            };
+            ctx.print_records(&[db::create_domain(&pool, new_domain, &ctx.audit)?])
+        }
+        DomainCommand::Enable { ref domain } | DomainCommand::Disable { ref domain } => {
+            let wanted = matches!(action, DomainCommand::Enable { .. });
+            let record = find(domain)?;
+            let (id, enabled) = (record.pkid, record.enabled);
+            set_enabled(ctx, record, enabled, wanted, "toggle_domain", || {
+                db::toggle_domain_enabled(&pool, id, &ctx.audit)
+            })
+        }
+        DomainCommand::Rm { domain } => {
+            ctx.check("delete_domain")?;
+            let record = find(&domain)?;
+            deleted(
+                db::delete_domain(&pool, record.pkid, &ctx.audit)?,
+                "Domain",
+                &domain,
+            )
+        }
+    }
+}
+
+async fn run_user(ctx: &CliContext, action: UserCommand) -> Result<()> {
+    let pool = ctx.pool().await?;
+    let find = |email: &str| {
+        db::get_user_by_id(&pool, email).with_context(|| format!("User '{email}' not found"))
+    };
+    match action {
+        UserCommand::List => {
+            let users: Vec<PublicUser> = db::get_users(&pool)?
+                .into_iter()
+                .map(PublicUser::from)
+                .collect();
+            ctx.print_records(&users)
+        }
+        UserCommand::Add {
+            email,
+            name,
+            password,
+            disabled,
+            quota,
+            quota_messages,
+        } => {
+            ctx.check("create_user")?;
+            let password = match password {
+                Some(password) => password,
+                None => read_password()?,
+            };
+            check_password_policy(ctx, &password, &email, &name)?;
+            let form = UserForm {
+                id: email,
+                password,
+                name,
+                maildir: String::new(),
+                enabled: !disabled,
+                change_password: false,
+                quota_bytes: quota,
+                quota_messages,
+            };
+            let user = db::create_user(
+                &pool,
+                form,
+                ctx.config.password_scheme(&ctx.database_id),
+                &ctx.audit,
+            )?;
+            ctx.print_records(&[PublicUser::from(user)])
+        }
+        UserCommand::Passwd { email, password } => {
+            ctx.check("update_user")?;
+            let record = find(&email)?;
+            let password = match password {
+                Some(password) => password,
+                None => read_password()?,
+            };
+            check_password_policy(ctx, &password, &record.id, &record.name)?;
+            db::update_user_password(
+                &pool,
+                record.id.clone(),
+                &password,
+                ctx.config.password_scheme(&ctx.database_id),
+                &ctx.audit,
+            )?;
+            ctx.print_records(&[PublicUser::from(record)])
+        }
+        UserCommand::Enable { ref email } | UserCommand::Disable { ref email } => {
+            let wanted = matches!(action, UserCommand::Enable { .. });
+            let record = find(email)?;
+            let enabled = record.enabled;
+            set_enabled(ctx, record.into(), enabled, wanted, "toggle_user", || {
+                db::toggle_user_enabled(&pool, email.to_string(), &ctx.audit).map(PublicUser::from)
+            })
+        }
+        UserCommand::Rm { email } => {
+            ctx.check("delete_user")?;
+            find(&email)?;
+            deleted(
+                db::delete_user(&pool, email.clone(), &ctx.audit)?,
+                "User",
+                &email,
+            )
+        }
+    }
+}
+
+async fn run_alias(ctx: &CliContext, action: AliasCommand) -> Result<()> {
+    let pool = ctx.pool().await?;
+    let find = |mail: &str| {
+        db::get_alias_by_mail(&pool, mail).with_context(|| format!("Alias '{mail}' not found"))
+    };
+    match action {
+        AliasCommand::List => ctx.print_records(&db::get_aliases(&pool)?),
+        AliasCommand::Add {
+            mail,
+            destinations,
+            disabled,
+            valid_from,
+            valid_until,
+        } => {
+            ctx.check("create_alias")?;
+            let form = AliasForm {
+                mail,
+                destination: destinations.join(", "),
+                enabled: !disabled,
+                valid_from,
+                valid_until,
+                return_url: None,
+            }
+            .normalized();
+            form.validate().map_err(|e| anyhow!("{e}"))?;
+            validity::check_window(valid_from, valid_until).map_err(|e| anyhow!("{e}"))?;
+            ctx.print_records(&[db::create_alias(&pool, form, &ctx.audit)?])
+        }
+        AliasCommand::Enable { ref mail } | AliasCommand::Disable { ref mail } => {
+            let wanted = matches!(action, AliasCommand::Enable { .. });
+            let record = find(mail)?;
+            let (id, enabled) = (record.pkid, record.enabled);
+            set_enabled(ctx, record, enabled, wanted, "toggle_alias", || {
+                db::toggle_alias_enabled(&pool, id, &ctx.audit)
+            })
+        }
+        AliasCommand::Rm { mail } => {
+            ctx.check("delete_alias")?;
+            let record = find(&mail)?;
+            deleted(
+                db::delete_alias(&pool, record.pkid, &ctx.audit)?,
+                "Alias",
+                &mail,
+            )
+        }
+    }
+}
+
+async fn run_relay(ctx: &CliContext, action: RelayCommand) -> Result<()> {
+    let pool = ctx.pool().await?;
+    let find = |recipient: &str| {
+        db::get_relay_by_recipient(&pool, recipient)
+            .with_context(|| format!("Relay '{recipient}' not found"))
+    };
+    match action {
+        RelayCommand::List => ctx.print_records(&db::get_relays(&pool)?),
+        RelayCommand::Add { recipient, status } => {
+            ctx.check("create_relay")?;
+            let form = RelayForm {
+                recipient,
+                status,
+                enabled: true,
+            };
+            ctx.print_records(&[db::create_relay(&pool, form, &ctx.audit)?])
+        }
+        RelayCommand::Enable { ref recipient } | RelayCommand::Disable { ref recipient } => {
+            let wanted = matches!(action, RelayCommand::Enable { .. });
+            let record = find(recipient)?;
+            let (id, enabled) = (record.pkid, record.enabled);
+            set_enabled(ctx, record, enabled, wanted, "toggle_relay", || {
+                db::toggle_relay_enabled(&pool, id, &ctx.audit)
+            })
+        }
+        RelayCommand::Rm { recipient } => {
+            ctx.check("delete_relay")?;
+            let record = find(&recipient)?;
+            deleted(
+                db::delete_relay(&pool, record.pkid, &ctx.audit)?,
+                "Relay",
+                &recipient,
+            )
+        }
+    }
+}
+
+async fn run_relocated(ctx: &CliContext, action: RelocatedCommand) -> Result<()> {
+    let pool = ctx.pool().await?;
+    let find = |old_address: &str| {
+        db::get_relocated_by_old_address(&pool, old_address)
+            .with_context(|| format!("Relocated entry '{old_address}' not found"))
+    };
+    match action {
+        RelocatedCommand::List => ctx.print_records(&db::get_relocated(&pool)?),
+        RelocatedCommand::Add {
+            old_address,
+            new_address,
+            valid_from,
+            valid_until,
+        } => {
+            ctx.check("create_relocated")?;
+            validity::check_window(valid_from, valid_until).map_err(|e| anyhow!("{e}"))?;
+            let form = RelocatedForm {
+                old_address,
+                new_address,
+                enabled: true,
+                valid_from,
+                valid_until,
+            };
+            ctx.print_records(&[db::create_relocated(&pool, form, &ctx.audit)?])
+        }
+        RelocatedCommand::Enable { ref old_address }
+        | RelocatedCommand::Disable { ref old_address } => {
+            let wanted = matches!(action, RelocatedCommand::Enable { .. });
+            let record = find(old_address)?;
+            let (id, enabled) = (record.pkid, record.enabled);
+            set_enabled(ctx, record, enabled, wanted, "toggle_relocated", || {
+                db::toggle_relocated_enabled(&pool, id, &ctx.audit)
+            })
+        }
+        RelocatedCommand::Rm { old_address } => {
+            ctx.check("delete_relocated")?;
+            let record = find(&old_address)?;
+            deleted(
+                db::delete_relocated(&pool, record.pkid, &ctx.audit)?,
+                "Relocated entry",
+                &old_address,
+            )
+        }
+    }
+}
+
+async fn run_client(ctx: &CliContext, action: ClientCommand) -> Result<()> {
+    let pool = ctx.pool().await?;
+    let find = |client: &str| {
+        db::get_client_by_name(&pool, client)
+            .with_context(|| format!("Client '{client}' not found"))
+    };
+    match action {
+        ClientCommand::List => ctx.print_records(&db::get_clients(&pool)?),
+        ClientCommand::Add { client, status } => {
+            ctx.check("create_client")?;
+            let form = ClientForm {
+                client,
+                status,
+                enabled: true,
+            };
+            ctx.print_records(&[db::create_client(&pool, form, &ctx.audit)?])
+        }
+        ClientCommand::Enable { ref client } | ClientCommand::Disable { ref client } => {
+            let wanted = matches!(action, ClientCommand::Enable { .. });
+            let record = find(client)?;
+            let (id, enabled) = (record.id, record.enabled);
+            set_enabled(ctx, record, enabled, wanted, "toggle_client", || {
+                db::toggle_client_enabled(&pool, id, &ctx.audit)
+            })
+        }
+        ClientCommand::Rm { client } => {
+            ctx.check("delete_client")?;
+            let record = find(&client)?;
+            deleted(
+                db::delete_client(&pool, record.id, &ctx.audit)?,
+                "Client",
+                &client,
+            )
+        }
+    }
+}
+
+async fn run_migrate(ctx: &CliContext, all: bool) -> Result<()> {
+    let migrated: Vec<String> = if all {
+        ctx.db_manager
+            .run_migrations_on_all_databases()
+            .await
+            .map_err(|e| anyhow!("{e}"))?;
+        ctx.db_manager
+            .get_configs()
+            .iter()
+            .map(|config| config.id.clone())
+            .collect()
+    } else {
+        ctx.db_manager
+            .run_migrations_on_database(&ctx.database_id)
+            .await
+            .map_err(|e| anyhow!("{e}"))?;
+        vec![ctx.database_id.clone()]
+    };
+
+    let rows: Vec<Vec<String>> = migrated
+        .iter()
+        .map(|id| vec![id.clone(), "migrated".to_string()])
+        .collect();
+    print!(
+        "{}",
+        render_report(ctx.output, &migrated, &["database", "status"], &rows)?
+    );
+    Ok(())
+}
+
+async fn run_usage(ctx: &CliContext, action: UsageCommand) -> Result<()> {
+    match action {
+        UsageCommand::Collect { all } => {
+            if ctx.config.usage.source == UsageSource::None {
+                bail!("No usage source is configured; set `source` in the [usage] section");
+            }
+            let database_id = (!all).then_some(ctx.database_id.as_str());
+            let results =
+                usage::collect_databases(&ctx.db_manager, &ctx.config.usage, database_id).await;
+
+            let mut collected = Vec::new();
+            let mut rows = Vec::new();
+            let mut failed = false;
+            for (database_id, result) in results {
+                match result {
+                    Ok(count) => {
+                        rows.push(vec![database_id.clone(), count.to_string()]);
+                        collected.push((database_id, count));
+                    }
+                    Err(e) => {
+                        eprintln!("{database_id}: {e}");
+                        failed = true;
+                    }
+                }
+            }
+            print!(
+                "{}",
+                render_report(ctx.output, &collected, &["database", "mailboxes"], &rows)?
+            );
+            if failed {
+                bail!("Usage collection failed for some databases");
+            }
+            Ok(())
+        }
+        UsageCommand::Top { limit, days } => {
+            let pool = ctx.pool().await?;
+            ctx.print_records(&db::get_largest_mailboxes(&pool, limit, days)?)
+        }
+    }
+}
+
+/// Write a file readable only by its owner and group, as it holds database credentials
+fn write_private(path: &std::path::Path, contents: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o640);
+    }
+    options.open(path)?.write_all(contents)
+}
+
+/// A `--map` argument: a column header and the field it is imported into
+fn parse_mapping(value: &str) -> Result<(String, String), String> {
+    value
+        .rsplit_once('=')
+        .map(|(header, field)| (header.to_string(), field.to_string()))
+        .ok_or_else(|| format!("expected HEADER=FIELD, got '{value}'"))
+}
+
+/// Read a file, or standard input for `-`
+fn read_input(path: &Path) -> Result<String> {
+    if path == Path::new("-") {
+        let mut content = String::new();
+        std::io::stdin()
+            .read_to_string(&mut content)
+            .context("Failed to read standard input")?;
+        Ok(content)
+    } else {
+        std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))
+    }
+}
+
+async fn run_import(
+    ctx: &CliContext,
+    kind: ImportKind,
+    path: &Path,
+    map: &[(String, String)],
+    update: bool,
+    apply: bool,
+    report: Option<&Path>,
+) -> Result<()> {
+    let content = read_input(path)?;
+    let mut file = import::read(kind, &content)?;
+    for (header, field) in map {
+        file.map_column(header, field)?;
+    }
+    file.check_columns()?;
+
+    let pool = ctx.pool().await?;
+    let scope = db::AddressScope::All;
+    let options = ImportOptions {
+        update_existing: update,
+        scope: &scope,
+        policy: ctx.config.password_policy(&ctx.database_id),
+        can_create: ctx.check(kind.operation(true)).is_ok(),
+        can_update: ctx.check(kind.operation(false)).is_ok(),
+    };
+    let plan = import::plan(&pool, &file, &options)?;
+    let rows: Vec<ReportRow> = plan.rows.iter().map(ReportRow::from_planned).collect();
+    ctx.print_records(&rows)?;
+
+    let problems: Vec<ReportRow> = plan.problems().map(ReportRow::from_planned).collect();
+    if let Some(report) = report {
+        std::fs::write(report, import::report_csv(&problems))
+            .with_context(|| format!("Failed to write {}", report.display()))?;
+    }
+    if !apply {
+        return Ok(());
+    }
+
+    let count = db::apply_import(
+        &pool,
+        plan.into_writes(),
+        ctx.config.password_scheme(&ctx.database_id),
+        &ctx.audit,
+    )
+    .map_err(|e| anyhow!("Nothing was written: {e}"))?;
+    eprintln!("{count} records written, {} rows skipped", problems.len());
+    Ok(())
+}
+
+/// The configuration of the selected database
+fn database_config(ctx: &CliContext) -> Result<DatabaseConfig> {
+    ctx.db_manager
+        .get_configs()
+        .into_iter()
+        .find(|db| db.id == ctx.database_id)
+        .ok_or_else(|| anyhow!("Unknown database '{}'", ctx.database_id))
+}
+
+async fn run_restore(ctx: &CliContext, path: &Path, mode: RestoreMode, apply: bool) -> Result<()> {
+    let incoming = bundle::parse(&read_input(path)?)?;
+    restore_bundle(ctx, &incoming, mode, apply).await
+}
+
+/// Print the changes restoring `incoming` makes and write them if `apply`
+async fn restore_bundle(
+    ctx: &CliContext,
+    incoming: &Bundle,
+    mode: RestoreMode,
+    apply: bool,
+) -> Result<()> {
+    let db_config = database_config(ctx)?;
+    let pool = ctx.pool().await?;
+    let plan = restore::plan_for(&pool, &db_config, incoming, mode, |operation| {
+        ctx.check(operation).is_ok()
+    })?;
+
+    let changes: Vec<&RestoreChange> = plan
+        .changes
+        .iter()
+        .filter(|change| change.action != RestoreAction::Unchanged)
+        .collect();
+    ctx.print_records(&changes)?;
+    eprintln!(
+        "{} to create, {} to update, {} to delete, {} unchanged, {} not allowed",
+        plan.count(RestoreAction::Create),
+        plan.count(RestoreAction::Update),
+        plan.count(RestoreAction::Delete),
+        plan.count(RestoreAction::Unchanged),
+        plan.problems().count()
+    );
+    if !apply {
+        return Ok(());
+    }
+
+    let count = db::apply_restore(&pool, &db_config, &plan, &ctx.audit)
+        .map_err(|e| anyhow!("Nothing was written: {e}"))?;
+    eprintln!("{count} records written to '{}'", ctx.database_id);
+    Ok(())
+}
+
+async fn run_legacy(
+    ctx: &CliContext,
+    schema: LegacySchema,
+    source: &str,
+    options: &LegacyOptions,
+    bundle_path: Option<&Path>,
+    mode: RestoreMode,
+    apply: bool,
+) -> Result<()> {
+    let tables = if source.starts_with("mysql://") {
+        db::read_legacy_tables(source, schema.tables())?
+    } else {
+        legacy::read_dump(&read_input(Path::new(source))?)?
+    };
+    let conversion = legacy::convert(schema, &tables, options);
+    for note in &conversion.notes {
+        eprintln!("{note}");
+    }
+    if let Some(path) = bundle_path {
+        let format = BundleFormat::from_path(path).unwrap_or_default();
+        let document = conversion
+            .bundle
+            .to_string(format)
+            .map_err(|e| anyhow!(e))?;
+        std::fs::write(path, document)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        eprintln!(
+            "Wrote {} converted records to {}",
+            conversion.bundle.record_count(),
+            path.display()
+        );
+    }
+    restore_bundle(ctx, &conversion.bundle, mode, apply).await
+}
+
+fn parse_table(name: &str) -> Result<BundleTable, String> {
+    BundleTable::parse(name).ok_or_else(|| {
+        let names: Vec<&str> = BundleTable::ALL.iter().map(BundleTable::name).collect();
+        format!("expected one of {}", names.join(", "))
+    })
+}
+
+/// Print the differences between `from` and the selected database and sync
+/// the chosen ones into it if `apply`
+async fn run_sync(
+    ctx: &CliContext,
+    from: &str,
+    tables: &[BundleTable],
+    only: &[String],
+    delete: bool,
+    apply: bool,
+) -> Result<()> {
+    if from == ctx.database_id {
+        bail!("Cannot sync database '{from}' into itself");
+    }
+    let tables = if tables.is_empty() {
+        &sync::DEFAULT_TABLES[..]
+    } else {
+        tables
+    };
+    let source_config = ctx
+        .db_manager
+        .get_configs()
+        .into_iter()
+        .find(|db| db.id == from)
+        .ok_or_else(|| anyhow!("Unknown database '{from}'"))?;
+    let source_pool = ctx
+        .db_manager
+        .get_pool(from)
+        .await
+        .ok_or_else(|| anyhow!("Unknown database '{from}'"))?;
+    let target_config = database_config(ctx)?;
+    let target_pool = ctx.pool().await?;
+    let diff = sync::diff(
+        &source_pool,
+        &source_config,
+        &target_pool,
+        &target_config,
+        tables,
+        |operation| ctx.check(operation).is_ok(),
+    )?;
+
+    ctx.print_records(&diff.rows())?;
+    let plan = &diff.plan;
+    eprintln!(
+        "{} only in '{from}', {} only in '{}', {} different, {} identical, {} not allowed",
+        plan.count(RestoreAction::Create),
+        plan.count(RestoreAction::Delete),
+        ctx.database_id,
+        plan.count(RestoreAction::Update),
+        plan.count(RestoreAction::Unchanged),
+        plan.problems().count()
+    );
+    if !apply {
+        return Ok(());
+    }
+
+    let chosen: Vec<String> = diff
+        .differences()
+        .map(sync::row_id)
+        .filter(|id| only.is_empty() || only.contains(id))
+        .collect();
+    let mut selection = diff.selection(&chosen);
+    if !delete {
+        selection
+            .changes
+            .retain(|change| change.action != RestoreAction::Delete);
+    }
+    let count = db::apply_restore(&target_pool, &target_config, &selection, &ctx.audit)
+        .map_err(|e| anyhow!("Nothing was written: {e}"))?;
+    eprintln!(
+        "{count} records synced from '{from}' to '{}'",
+        ctx.database_id
+    );
+    Ok(())
+}
+
+async fn run_export(
+    ctx: &CliContext,
+    path: Option<&Path>,
+    yaml: bool,
+    password_hashes: bool,
+) -> Result<()> {
+    let path = path.filter(|path| *path != Path::new("-"));
+    let format = if yaml {
+        BundleFormat::Yaml
+    } else {
+        path.and_then(BundleFormat::from_path).unwrap_or_default()
+    };
+    let db_config = database_config(ctx)?;
+    let pool = ctx.pool().await?;
+    let bundle = bundle::export(&pool, &db_config, password_hashes)?;
+    let document = bundle.to_string(format).map_err(|e| anyhow!(e))?;
+
+    match path {
+        Some(path) => {
+            std::fs::write(path, document)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            eprintln!(
+                "Exported {} records of '{}' to {}",
+                bundle.record_count(),
+                ctx.database_id,
+                path.display()
+            );
+        }
+        None => print!("{document}"),
+    }
+    Ok(())
+}
+
+fn run_mail_config(
+    config: &Config,
+    database: Option<String>,
+    output: OutputFormat,
+    all: bool,
+    zip: Option<PathBuf>,
+    dir: Option<PathBuf>,
+) -> Result<()> {
+    let mut databases = db::database_configs(config).map_err(|e| anyhow!(e))?;
+    if !all {
+        let database_id = database.unwrap_or_else(|| databases[0].id.clone());
+        databases.retain(|db| db.id == database_id);
+        if databases.is_empty() {
+            bail!("Unknown database '{database_id}'");
+        }
+    }
+
+    let mut generated = Vec::new();
+    for db in &databases {
+        generated.push((
+            db.id.clone(),
+            mail_config::generate(db).map_err(|e| anyhow!(e))?,
+        ));
+    }
+
+    if zip.is_none() && dir.is_none() {
+        for (database_id, files) in &generated {
+            for file in files {
+                println!("==> {database_id}/{} <==", file.path);
+                println!("{}", file.contents);
+            }
+        }
+        return Ok(());
+    }
+
+    let mut written = Vec::new();
+    if let Some(path) = zip {
+        let bytes = mail_config::zip_archive(&generated).map_err(|e| anyhow!(e))?;
+        write_private(&path, &bytes)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        written.push(path.display().to_string());
+    }
+    if let Some(dir) = dir {
+        for (database_id, files) in &generated {
+            for file in files {
+                let path = dir.join(database_id).join(&file.path);
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)
+                        .with_context(|| format!("Failed to create {}", parent.display()))?;
+                }
+                write_private(&path, file.contents.as_bytes())
+                    .with_context(|| format!("Failed to write {}", path.display()))?;
+                written.push(path.display().to_string());
+            }
+        }
+    }
+
+    let rows: Vec<Vec<String>> = written.iter().map(|path| vec![path.clone()]).collect();
+    print!("{}", render_report(output, &written, &["written"], &rows)?);
+    Ok(())
+}
+
+async fn run_report(ctx: &CliContext, report: ReportCommand) -> Result<()> {
+    let pool = ctx.pool().await?;
+    let output = match report {
+        ReportCommand::Orphaned => {
+            let report = db::get_orphaned_aliases_report(&pool)?;
+            let mut rows = vec![];
+            for alias in &report.orphaned_aliases {
+                rows.push(vec![
+                    "orphaned_alias".to_string(),
+                    alias.mail.clone(),
+                    alias.destination.clone(),
+                    alias.domain.clone(),
+                    alias.enabled.to_string(),
+                ]);
+            }
+            for user in &report.orphaned_users {
+                rows.push(vec![
+                    "orphaned_user".to_string(),
+                    user.id.clone(),
+                    user.name.clone(),
+                    user.domain.clone(),
+                    user.enabled.to_string(),
+                ]);
+            }
+            for user in &report.users_without_aliases {
+                rows.push(vec![
+                    "user_without_alias".to_string(),
+                    user.id.clone(),
+                    user.name.clone(),
+                    user.domain.clone(),
+                    user.enabled.to_string(),
+                ]);
+            }
+            render_report(
+                ctx.output,
+                &report,
+                &["kind", "address", "detail", "domain", "enabled"],
+                &rows,
+            )?
+        }
+        ReportCommand::ExternalForwarders => {
+            let report = db::get_external_forwarders_report(&pool)?;
+            let rows: Vec<Vec<String>> = report
+                .external_forwarders
+                .iter()
+                .map(|forwarder| {
+                    vec![
+                        forwarder.mail.clone(),
+                        forwarder.destination.clone(),
+                        forwarder.domain.clone(),
+                        forwarder.enabled.to_string(),
+                    ]
+                })
+                .collect();
+            render_report(
+                ctx.output,
+                &report,
+                &["mail", "destination", "domain", "enabled"],
+                &rows,
+            )?
+        }
+        ReportCommand::MissingAliases => {
+            let report = db::get_missing_aliases_report(&pool)?;
+            let rows: Vec<Vec<String>> = report
+                .domains_missing_aliases
+                .iter()
+                .map(|domain| {
+                    vec![
+                        domain.domain.clone(),
+                        domain.missing_required_aliases.join(" "),
+                        domain.has_catch_all.to_string(),
+                    ]
+                })
+                .collect();
+            render_report(
+                ctx.output,
+                &report,
+                &["domain", "missing", "has_catch_all"],
+                &rows,
+            )?
+        }
+        ReportCommand::CatchAll => {
+            let report = db::get_catch_all_report(&pool)?;
+            let rows: Vec<Vec<String>> = report
+                .iter()
+                .map(|domain| {
+                    vec![
+                        domain.domain.clone(),
+                        domain.catch_all_alias.clone(),
+                        domain.catch_all_destination.clone(),
+                    ]
+                })
+                .collect();
+            render_report(
+                ctx.output,
+                &report,
+                &["domain", "catch_all_alias", "destination"],
+                &rows,
+            )?
+        }
+        ReportCommand::Delivery { address } => {
+            let trace = delivery::trace(&pool, &address)?;
+            let rows: Vec<Vec<String>> = trace
+                .hops
+                .iter()
+                .map(|hop| {
+                    let disabled: Vec<String> = hop
+                        .disabled
+                        .iter()
+                        .map(|entry| format!("{} {}", entry.entity, entry.name))
+                        .collect();
+                    vec![
+                        hop.depth.to_string(),
+                        format!("{}{}", "  ".repeat(hop.depth), hop.address),
+                        hop.outcome.kind().to_string(),
+                        hop.outcome.detail(),
+                        disabled.join(", "),
+                    ]
+                })
+                .collect();
+            render_report(
+                ctx.output,
+                &trace,
+                &["depth", "address", "outcome", "detail", "disabled"],
+                &rows,
+            )?
+        }
+        ReportCommand::WeakHashes => {
+            let scheme = ctx.config.password_scheme(&ctx.database_id);
+            let report = db::get_weak_password_hashes(&pool, scheme)?;
+            let rows: Vec<Vec<String>> = report
+                .iter()
+                .map(|hash| {
+                    vec![
+                        hash.user_id.clone(),
+                        hash.scheme.clone().unwrap_or_else(|| "unknown".to_string()),
+                        hash.prefixed.to_string(),
+                        hash.enabled.to_string(),
+                    ]
+                })
+                .collect();
+            render_report(
+                ctx.output,
+                &report,
+                &["user", "scheme", "prefixed", "enabled"],
+                &rows,
+            )?
+        }
+    };
+    print!("{output}");
+    Ok(())
+}
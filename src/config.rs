@@ -1,8 +1,20 @@
+use crate::db::AddressScope;
 use crate::models::RequiredAliasConfig;
+use crate::password::{PasswordPolicy, PasswordScheme};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+use toml_edit::{DocumentMut, InlineTable, Item, Table, TableLike, Value};
+
+/// Files searched for the configuration, in order
+pub const CONFIG_PATHS: [&str; 3] = [
+    "config/config.toml",
+    "/etc/sortingoffice/config.toml",
+    "./config.toml",
+];
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
 pub enum AdminRole {
@@ -13,6 +25,25 @@ pub enum AdminRole {
     Edit,
 }
 
+impl AdminRole {
+    /// The string form used in config files and session storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AdminRole::ReadOnly => "read-only",
+            AdminRole::Edit => "edit",
+        }
+    }
+
+    /// Parse a role from its config/session string form
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "read-only" => Some(AdminRole::ReadOnly),
+            "edit" => Some(AdminRole::Edit),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct DomainOverride {
     pub required: Vec<String>,
@@ -25,6 +56,66 @@ pub struct AdminCredentials {
     pub password_hash: String,
     #[serde(default)]
     pub role: AdminRole,
+    /// Per-database grants keyed by database ID (`"*"` matches any other database).
+    /// When absent, `role` applies to every database.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub databases: Option<HashMap<String, AdminRole>>,
+    /// Bearer tokens for the JSON API, acting as this admin
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub api_tokens: Vec<ApiToken>,
+    /// Domains a delegated domain admin manages. When set, the admin only sees
+    /// users and aliases under these domains on their one granted database.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub domains: Vec<String>,
+}
+
+/// An API token belonging to an admin. Only the SHA-256 hash of the token is
+/// stored; requests made with it use the admin's role on `database`.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ApiToken {
+    pub name: String,
+    /// Hex-encoded SHA-256 of the token, e.g. `echo -n "$TOKEN" | sha256sum`
+    pub token_hash: String,
+    /// Database ID every request made with this token operates on
+    pub database: String,
+}
+
+/// Hex-encoded SHA-256 of an API token, as stored in `token_hash`
+pub fn hash_api_token(token: &str) -> String {
+    use sha2::{Digest, Sha256};
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+impl AdminCredentials {
+    /// Role granted on a database, or `None` if the admin may not access it
+    pub fn role_for_database(&self, database_id: &str) -> Option<AdminRole> {
+        match &self.databases {
+            None => Some(self.role.clone()),
+            Some(grants) => grants.get(database_id).or_else(|| grants.get("*")).cloned(),
+        }
+    }
+
+    /// Whether the admin may access a database at all
+    pub fn can_access_database(&self, database_id: &str) -> bool {
+        self.role_for_database(database_id).is_some()
+    }
+
+    /// Whether the admin is limited to the users and aliases of some domains
+    pub fn is_domain_admin(&self) -> bool {
+        !self.domains.is_empty()
+    }
+
+    /// The addresses the admin may see and change
+    pub fn address_scope(&self) -> AddressScope {
+        if self.is_domain_admin() {
+            AddressScope::Domains(self.domains.clone())
+        } else {
+            AddressScope::All
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -36,6 +127,12 @@ pub struct DatabaseConfig {
     pub features: DatabaseFeatures,
     #[serde(default)]
     pub field_map: std::collections::HashMap<String, String>,
+    /// Scheme for new mailbox password hashes; hashes of any supported scheme still verify
+    #[serde(default)]
+    pub password_scheme: PasswordScheme,
+    /// Replaces the global `[password_policy]` for this database
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password_policy: Option<PasswordPolicy>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -87,6 +184,122 @@ impl Default for GlobalFeatures {
     }
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub enum SessionStoreKind {
+    #[serde(rename = "memory")]
+    #[default]
+    Memory,
+    #[serde(rename = "mysql")]
+    Mysql,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct SessionConfig {
+    /// Secret used to sign session cookies. A random one is generated at
+    /// startup when unset, which logs everyone out on restart.
+    #[serde(default)]
+    pub secret: Option<String>,
+    #[serde(default = "default_idle_timeout_minutes")]
+    pub idle_timeout_minutes: u64,
+    #[serde(default = "default_absolute_timeout_hours")]
+    pub absolute_timeout_hours: u64,
+    #[serde(default)]
+    pub store: SessionStoreKind,
+    /// Database ID holding the `admin_sessions` table when `store = "mysql"`.
+    /// Defaults to the first configured database.
+    #[serde(default)]
+    pub database: Option<String>,
+    #[serde(default)]
+    pub secure_cookie: bool,
+}
+
+fn default_idle_timeout_minutes() -> u64 {
+    30
+}
+
+fn default_absolute_timeout_hours() -> u64 {
+    24
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        SessionConfig {
+            secret: None,
+            idle_timeout_minutes: default_idle_timeout_minutes(),
+            absolute_timeout_hours: default_absolute_timeout_hours(),
+            store: SessionStoreKind::Memory,
+            database: None,
+            secure_cookie: false,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default)]
+pub enum UsageSource {
+    /// Usage is not collected
+    #[serde(rename = "none")]
+    #[default]
+    None,
+    /// Parse `doveadm -f tab quota get -A` output
+    #[serde(rename = "doveadm")]
+    Doveadm,
+    /// Walk each user's maildir (`users.maildir` under `users.home`)
+    #[serde(rename = "maildir")]
+    Maildir,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct UsageConfig {
+    #[serde(default)]
+    pub source: UsageSource,
+    /// Command run for the `doveadm` source; it must print `doveadm -f tab` quota output
+    #[serde(default = "default_usage_command")]
+    pub command: Vec<String>,
+    #[serde(default = "default_usage_interval_minutes")]
+    pub interval_minutes: u64,
+    /// Snapshots older than this are deleted after each collection
+    #[serde(default = "default_usage_retention_days")]
+    pub retention_days: u32,
+}
+
+fn default_usage_command() -> Vec<String> {
+    ["doveadm", "-f", "tab", "quota", "get", "-A"]
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect()
+}
+
+fn default_usage_interval_minutes() -> u64 {
+    60
+}
+
+fn default_usage_retention_days() -> u32 {
+    90
+}
+
+impl Default for UsageConfig {
+    fn default() -> Self {
+        UsageConfig {
+            source: UsageSource::None,
+            command: default_usage_command(),
+            interval_minutes: default_usage_interval_minutes(),
+            retention_days: default_usage_retention_days(),
+        }
+    }
+}
+
+/// Vacation auto-reply settings
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct VacationConfig {
+    /// Domain routed to Postfix's `vacation` transport, e.g. `autoreply.example.com`
+    ///
+    /// When set, mailboxes with an active vacation get a copy of their mail
+    /// sent to `user#domain@<transport_domain>` through their alias entry.
+    /// Leave it unset on servers that answer with Sieve (Dovecot Pigeonhole).
+    #[serde(default)]
+    pub transport_domain: Option<String>,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Config {
     pub required_aliases: Vec<String>,
@@ -101,13 +314,26 @@ pub struct Config {
     pub databases: Vec<DatabaseConfig>,
     #[serde(default)]
     pub global_features: GlobalFeatures,
+    #[serde(default)]
+    pub session: SessionConfig,
+    #[serde(default)]
+    pub usage: UsageConfig,
+    #[serde(default)]
+    pub vacation: VacationConfig,
+    #[serde(default)]
+    pub password_policy: PasswordPolicy,
 }
 
 impl Config {
     /// Load configuration from a TOML file
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn std::error::Error>> {
         let content = fs::read_to_string(path)?;
-        let mut config: Config = toml::from_str(&content)?;
+        Ok(Self::from_toml(&content)?)
+    }
+
+    /// Parse configuration from TOML text
+    fn from_toml(content: &str) -> Result<Self, toml::de::Error> {
+        let mut config: Config = toml::from_str(content)?;
         // Migrate old config: if admins is empty and admin is present, push admin into admins
         if config.admins.is_empty() {
             if let Some(admin) = config.admin.take() {
@@ -117,20 +343,18 @@ impl Config {
         Ok(config)
     }
 
+    /// Find the default config file, if there is one
+    pub fn locate() -> Option<PathBuf> {
+        CONFIG_PATHS
+            .iter()
+            .map(PathBuf::from)
+            .find(|path| path.exists())
+    }
+
     /// Load configuration from the default config file
     pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
-        let config_paths = [
-            // "config/required_aliases.toml",
-            "config/config.toml",
-            // "/etc/sortingoffice/required_aliases.toml",
-            "/etc/sortingoffice/config.toml",
-            "./config.toml",
-        ];
-
-        for path in &config_paths {
-            if Path::new(path).exists() {
-                return Self::from_file(path);
-            }
+        if let Some(path) = Self::locate() {
+            return Self::from_file(path);
         }
 
         // Return default config if no file found
@@ -164,6 +388,10 @@ impl Config {
             admin: None,
             databases: vec![],
             global_features: GlobalFeatures::default(),
+            session: SessionConfig::default(),
+            usage: UsageConfig::default(),
+            vacation: VacationConfig::default(),
+            password_policy: PasswordPolicy::default(),
         })
     }
 
@@ -220,6 +448,47 @@ impl Config {
         None
     }
 
+    /// Find a configured admin by username
+    pub fn find_admin(&self, username: &str) -> Option<&AdminCredentials> {
+        self.admins.iter().find(|admin| admin.username == username)
+    }
+
+    /// Find the admin and token matching a presented API token
+    pub fn find_api_token(&self, token: &str) -> Option<(&AdminCredentials, &ApiToken)> {
+        let hash = hash_api_token(token);
+        self.admins.iter().find_map(|admin| {
+            admin
+                .api_tokens
+                .iter()
+                .find(|api_token| api_token.token_hash.eq_ignore_ascii_case(&hash))
+                .map(|api_token| (admin, api_token))
+        })
+    }
+
+    /// Role an admin holds on a specific database, or `None` if they have no access
+    pub fn admin_role_for_database(&self, username: &str, database_id: &str) -> Option<AdminRole> {
+        self.find_admin(username)
+            .and_then(|admin| admin.role_for_database(database_id))
+    }
+
+    /// Scheme for new password hashes on a database
+    pub fn password_scheme(&self, database_id: &str) -> PasswordScheme {
+        self.databases
+            .iter()
+            .find(|db| db.id == database_id)
+            .map(|db| db.password_scheme)
+            .unwrap_or_default()
+    }
+
+    /// The password policy of a database: its own, or the global one
+    pub fn password_policy(&self, database_id: &str) -> &PasswordPolicy {
+        self.databases
+            .iter()
+            .find(|db| db.id == database_id)
+            .and_then(|db| db.password_policy.as_ref())
+            .unwrap_or(&self.password_policy)
+    }
+
     /// Get database features for a specific database ID
     pub fn get_database_features(&self, database_id: &str) -> Option<&DatabaseFeatures> {
         self.databases
@@ -323,6 +592,106 @@ impl Config {
         }
         false
     }
+
+    /// Check if an admin may change the configuration. The settings apply to
+    /// every database, so this needs edit access to all of them.
+    pub fn can_edit_config(&self, admin: &AdminCredentials) -> bool {
+        admin.role == AdminRole::Edit
+            && self
+                .databases
+                .iter()
+                .all(|db| admin.role_for_database(&db.id) == Some(AdminRole::Edit))
+    }
+
+    /// Copy of this configuration with the settings from the config page applied
+    pub fn apply_update(&self, update: &ConfigUpdate) -> Result<Config, ConfigError> {
+        let mut config = self.clone();
+        config.required_aliases = update.required_aliases.clone();
+        config.common_aliases = update.common_aliases.clone();
+        config.domain_overrides = update.domain_overrides.clone();
+        config.global_features = update.global_features.clone();
+        for (database_id, features) in &update.database_features {
+            let database = config
+                .databases
+                .iter_mut()
+                .find(|db| &db.id == database_id)
+                .ok_or_else(|| ConfigError::UnknownDatabase(database_id.clone()))?;
+            database.features = features.clone();
+        }
+        Ok(config)
+    }
+
+    /// Check the alias lists, domain overrides and database IDs before they are
+    /// saved or reloaded
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        check_alias_lists(&self.required_aliases, &self.common_aliases)?;
+        for (domain, domain_override) in &self.domain_overrides {
+            if !is_valid_domain(domain) {
+                return Err(ConfigError::InvalidDomain(domain.clone()));
+            }
+            check_alias_lists(&domain_override.required, &domain_override.common)?;
+        }
+        for admin in &self.admins {
+            if let Some(domain) = admin.domains.iter().find(|d| !is_valid_domain(d)) {
+                return Err(ConfigError::InvalidDomain(domain.clone()));
+            }
+            // A domain scope only makes sense on the one database holding those domains
+            let single_database = admin
+                .databases
+                .as_ref()
+                .is_some_and(|grants| grants.len() == 1 && !grants.contains_key("*"));
+            if admin.is_domain_admin() && !single_database {
+                return Err(ConfigError::DomainAdminDatabases(admin.username.clone()));
+            }
+        }
+        if let Some(domain) = &self.vacation.transport_domain {
+            if !is_valid_domain(domain) {
+                return Err(ConfigError::InvalidDomain(domain.clone()));
+            }
+        }
+        let mut database_ids = HashSet::new();
+        for database in &self.databases {
+            if !database_ids.insert(&database.id) {
+                return Err(ConfigError::DuplicateDatabase(database.id.clone()));
+            }
+            if !database.password_scheme.can_hash() {
+                return Err(ConfigError::PasswordScheme(
+                    database.id.clone(),
+                    database.password_scheme,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the settings from the config page into the TOML file at `path`
+    ///
+    /// Only the edited keys are replaced, so comments, admins and database
+    /// connections are left as they are. The result is validated before it is
+    /// written, the previous file is kept as `<path>.bak` and the new file is
+    /// renamed into place so readers never see a partial write.
+    pub fn save_update(path: &Path, update: &ConfigUpdate) -> Result<Config, ConfigError> {
+        let content = fs::read_to_string(path)?;
+        let mut document: DocumentMut = content
+            .parse()
+            .map_err(|e: toml_edit::TomlError| ConfigError::Parse(e.to_string()))?;
+        apply_update_to_document(&mut document, update)?;
+
+        let content = document.to_string();
+        let config = Self::from_toml(&content).map_err(|e| ConfigError::Parse(e.to_string()))?;
+        config.validate()?;
+
+        let temp_path = path_with_suffix(path, ".tmp");
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+        // The file holds password hashes; keep its permissions
+        fs::set_permissions(&temp_path, fs::metadata(path)?.permissions())?;
+
+        fs::copy(path, path_with_suffix(path, ".bak"))?;
+        fs::rename(&temp_path, path)?;
+        Ok(config)
+    }
 }
 
 impl Default for Config {
@@ -358,14 +727,312 @@ impl Default for Config {
                 password_hash: "$2a$12$o8thacsiGCRhN1JN8xnW6e0KqNb7KrSgM67xxa62RKoAC9fOPf.aO"
                     .to_string(),
                 role: AdminRole::Edit,
+                databases: None,
+                api_tokens: vec![],
+                domains: vec![],
             }],
             admin: None,
             databases: vec![],
             global_features: GlobalFeatures::default(),
+            session: SessionConfig::default(),
+            usage: UsageConfig::default(),
+            vacation: VacationConfig::default(),
+            password_policy: PasswordPolicy::default(),
         }
     }
 }
 
+/// The settings edited on the config page
+#[derive(Debug, Clone, Default)]
+pub struct ConfigUpdate {
+    pub required_aliases: Vec<String>,
+    pub common_aliases: Vec<String>,
+    pub domain_overrides: HashMap<String, DomainOverride>,
+    pub global_features: GlobalFeatures,
+    /// Feature toggles keyed by database ID
+    pub database_features: HashMap<String, DatabaseFeatures>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("'{0}' is not a valid alias")]
+    InvalidAlias(String),
+    #[error("Alias '{0}' is listed more than once")]
+    DuplicateAlias(String),
+    #[error("'{0}' is not a valid domain")]
+    InvalidDomain(String),
+    #[error("Unknown database '{0}'")]
+    UnknownDatabase(String),
+    #[error("Database '{0}' is configured more than once")]
+    DuplicateDatabase(String),
+    #[error("Database '{0}' cannot hash new passwords with {1}")]
+    PasswordScheme(String, PasswordScheme),
+    #[error("Domain admin '{0}' must be granted exactly one database by ID")]
+    DomainAdminDatabases(String),
+    #[error("No configuration file was loaded, so there is nothing to save to")]
+    NoConfigFile,
+    #[error("Configuration file error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid configuration file: {0}")]
+    Parse(String),
+}
+
+impl ConfigError {
+    /// Whether the error is a problem with the submitted settings rather than the file
+    pub fn is_validation_error(&self) -> bool {
+        matches!(
+            self,
+            ConfigError::InvalidAlias(_)
+                | ConfigError::DuplicateAlias(_)
+                | ConfigError::InvalidDomain(_)
+                | ConfigError::UnknownDatabase(_)
+                | ConfigError::DuplicateDatabase(_)
+                | ConfigError::PasswordScheme(..)
+        )
+    }
+}
+
+/// A reload of the config file that was rejected, leaving the previous
+/// configuration in use
+#[derive(Debug, Clone)]
+pub struct ReloadFailure {
+    pub error: String,
+    pub failed_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// The running configuration, shared by all requests
+///
+/// Handlers take a snapshot with [`SharedConfig::get`]; saving the config page
+/// or reloading the config file swaps in the new configuration without a
+/// restart.
+#[derive(Clone)]
+pub struct SharedConfig {
+    current: Arc<RwLock<Arc<Config>>>,
+    path: Option<PathBuf>,
+    save_lock: Arc<Mutex<()>>,
+    reload_failure: Arc<RwLock<Option<ReloadFailure>>>,
+}
+
+impl SharedConfig {
+    pub fn new(config: Config, path: Option<PathBuf>) -> Self {
+        Self {
+            current: Arc::new(RwLock::new(Arc::new(config))),
+            path,
+            save_lock: Arc::new(Mutex::new(())),
+            reload_failure: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Load the default config file, remembering where it came from
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        match Config::locate() {
+            Some(path) => Ok(Self::new(Config::from_file(&path)?, Some(path))),
+            None => Ok(Self::new(Config::load()?, None)),
+        }
+    }
+
+    /// The current configuration
+    pub fn get(&self) -> Arc<Config> {
+        self.current
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Replace the running configuration
+    pub fn replace(&self, config: Config) {
+        *self.current.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(config);
+    }
+
+    /// The file the configuration was loaded from
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Read and validate the config file again, without applying it
+    pub fn read_file(&self) -> Result<Config, ConfigError> {
+        let path = self.path().ok_or(ConfigError::NoConfigFile)?;
+        let content = fs::read_to_string(path)?;
+        let config = Config::from_toml(&content).map_err(|e| ConfigError::Parse(e.to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// The last failed reload, if the config file has not loaded cleanly since
+    pub fn reload_failure(&self) -> Option<ReloadFailure> {
+        self.reload_failure
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    pub fn set_reload_failure(&self, failure: Option<ReloadFailure>) {
+        *self
+            .reload_failure
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = failure;
+    }
+
+    /// Save the settings from the config page to the config file and start using them
+    pub fn save(&self, update: &ConfigUpdate) -> Result<Arc<Config>, ConfigError> {
+        let path = self.path().ok_or(ConfigError::NoConfigFile)?;
+        let _guard = self
+            .save_lock
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        self.get().apply_update(update)?;
+        let config = Config::save_update(path, update)?;
+        self.replace(config);
+        Ok(self.get())
+    }
+}
+
+impl From<Config> for SharedConfig {
+    fn from(config: Config) -> Self {
+        Self::new(config, None)
+    }
+}
+
+fn is_valid_alias(alias: &str) -> bool {
+    !alias.is_empty()
+        && alias.len() <= 64
+        && !alias.starts_with('.')
+        && !alias.ends_with('.')
+        && alias
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '+' | '-'))
+}
+
+pub(crate) fn is_valid_domain(domain: &str) -> bool {
+    domain.len() <= 253
+        && domain.contains('.')
+        && domain.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
+/// Aliases must be valid local parts and may only appear once across both lists
+fn check_alias_lists(required: &[String], common: &[String]) -> Result<(), ConfigError> {
+    let mut seen = HashSet::new();
+    for alias in required.iter().chain(common) {
+        if !is_valid_alias(alias) {
+            return Err(ConfigError::InvalidAlias(alias.clone()));
+        }
+        if !seen.insert(alias.to_lowercase()) {
+            return Err(ConfigError::DuplicateAlias(alias.clone()));
+        }
+    }
+    Ok(())
+}
+
+fn path_with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+fn string_array(values: &[String]) -> Value {
+    Value::Array(values.iter().collect())
+}
+
+/// Set a key, keeping the comments around an existing value
+fn set_value(table: &mut dyn TableLike, key: &str, mut value: Value) {
+    match table.get_mut(key).and_then(Item::as_value_mut) {
+        Some(existing) => {
+            *value.decor_mut() = existing.decor().clone();
+            *existing = value;
+        }
+        None => {
+            table.insert(key, Item::Value(value));
+        }
+    }
+}
+
+/// Get a key as a table, creating it (as `empty`) if it is missing
+fn table_like_mut<'a>(table: &'a mut Table, key: &str, empty: Item) -> &'a mut dyn TableLike {
+    let item = table.entry(key).or_insert(empty);
+    if item.as_table_like().is_none() {
+        *item = Item::Table(Table::new());
+    }
+    item.as_table_like_mut().expect("item is a table")
+}
+
+fn set_features(table: &mut dyn TableLike, flags: &[(&str, bool)]) {
+    for (key, enabled) in flags {
+        set_value(table, key, Value::from(*enabled));
+    }
+}
+
+fn apply_update_to_document(
+    document: &mut DocumentMut,
+    update: &ConfigUpdate,
+) -> Result<(), ConfigError> {
+    let root = document.as_table_mut();
+    set_value(
+        root,
+        "required_aliases",
+        string_array(&update.required_aliases),
+    );
+    set_value(root, "common_aliases", string_array(&update.common_aliases));
+
+    let mut overrides = Table::new();
+    overrides.set_implicit(true);
+    let mut domains: Vec<_> = update.domain_overrides.iter().collect();
+    domains.sort_by_key(|(domain, _)| *domain);
+    for (domain, domain_override) in domains {
+        let mut table = Table::new();
+        table.insert(
+            "required",
+            Item::Value(string_array(&domain_override.required)),
+        );
+        table.insert("common", Item::Value(string_array(&domain_override.common)));
+        overrides.insert(domain, Item::Table(table));
+    }
+    root.insert("domain_overrides", Item::Table(overrides));
+
+    let global = &update.global_features;
+    set_features(
+        table_like_mut(root, "global_features", Item::Table(Table::new())),
+        &[
+            ("read_only", global.read_only),
+            ("no_new_users", global.no_new_users),
+            ("no_new_domains", global.no_new_domains),
+            ("no_password_updates", global.no_password_updates),
+        ],
+    );
+
+    for (database_id, features) in &update.database_features {
+        let database = root
+            .get_mut("databases")
+            .and_then(Item::as_array_of_tables_mut)
+            .and_then(|databases| {
+                databases
+                    .iter_mut()
+                    .find(|db| db.get("id").and_then(Item::as_str) == Some(database_id))
+            })
+            .ok_or_else(|| ConfigError::UnknownDatabase(database_id.clone()))?;
+        set_features(
+            table_like_mut(
+                database,
+                "features",
+                Item::Value(Value::InlineTable(InlineTable::new())),
+            ),
+            &[
+                ("read_only", features.read_only),
+                ("no_new_users", features.no_new_users),
+                ("no_new_domains", features.no_new_domains),
+                ("no_password_updates", features.no_password_updates),
+                ("disabled", features.disabled),
+            ],
+        );
+    }
+    Ok(())
+}
+
 impl DatabaseConfig {
     /// Get the mapped field name for a table-qualified logical field, or the logical name if not mapped
     ///
@@ -397,19 +1064,16 @@ impl DatabaseConfig {
     /// - `field_for_table("users", "id")` -> looks for "users.id" or "id" in field_map
     /// - `field_for_table("domains", "enabled")` -> looks for "domains.enabled" or "enabled" in field_map
     pub fn field_for_table<'a>(&'a self, table: &'a str, field: &'a str) -> &'a str {
-        let qualified = format!("{}.{}", table, field);
-
-        // First try the qualified name
-        if let Some(mapped) = self.field_map.get(&qualified) {
-            return mapped.as_str();
-        }
-
-        // Then try just the field name
-        if let Some(mapped) = self.field_map.get(field) {
-            return mapped.as_str();
-        }
-
         // Fall back to the original field name
-        field
+        self.mapped_field(table, field).unwrap_or(field)
+    }
+
+    /// The column the field map names for a table's field, trying "table.field"
+    /// before "field", or `None` if the field is not mapped
+    pub fn mapped_field(&self, table: &str, field: &str) -> Option<&str> {
+        self.field_map
+            .get(&format!("{table}.{field}"))
+            .or_else(|| self.field_map.get(field))
+            .map(String::as_str)
     }
 }
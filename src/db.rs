@@ -1,13 +1,15 @@
-use crate::config::DatabaseConfig;
+use crate::config::{AdminCredentials, Config, DatabaseConfig};
 use crate::models::*;
+use crate::password::{self, PasswordScheme};
 use crate::schema::*;
 use crate::DbPool;
 use chrono::{NaiveDateTime, Utc};
-use diesel::mysql::MysqlConnection;
+use diesel::mysql::{Mysql, MysqlConnection};
 use diesel::prelude::*;
 use diesel::r2d2::{self, ConnectionManager};
 use diesel::result::Error;
 use diesel::sql_query;
+use diesel::sql_types::Bool;
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -17,33 +19,106 @@ use tokio::sync::RwLock;
 #[derive(Clone)]
 pub struct DatabaseManager {
     pools: Arc<RwLock<HashMap<String, DbPool>>>,
-    configs: Vec<DatabaseConfig>,
-    default_db: String,
+    configs: Arc<std::sync::RwLock<Vec<DatabaseConfig>>>,
+}
+
+/// Create a connection pool for a configured database
+fn build_pool(config: &DatabaseConfig) -> Result<DbPool, String> {
+    let manager = ConnectionManager::<MysqlConnection>::new(&config.url);
+    r2d2::Pool::builder()
+        .build(manager)
+        .map_err(|e| format!("Failed to create pool for {}: {}", config.id, e))
+}
+
+/// The databases a config describes, falling back to a single "primary"
+/// database from `DATABASE_URL` when none are configured
+pub fn database_configs(config: &Config) -> Result<Vec<DatabaseConfig>, String> {
+    if config.databases.is_empty() {
+        let db_url = std::env::var("DATABASE_URL").map_err(|_| "DATABASE_URL must be set")?;
+        Ok(vec![DatabaseConfig {
+            id: "primary".to_string(),
+            label: "Primary Database".to_string(),
+            url: db_url,
+            features: crate::config::DatabaseFeatures::default(),
+            field_map: HashMap::new(),
+            password_scheme: crate::password::PasswordScheme::default(),
+            password_policy: None,
+        }])
+    } else {
+        Ok(config.databases.clone())
+    }
 }
 
 impl DatabaseManager {
     /// Create a new database manager with multiple database connections
     pub async fn new(configs: Vec<DatabaseConfig>) -> Result<Self, Box<dyn std::error::Error>> {
         let mut pools = HashMap::new();
-        let default_db = configs
-            .first()
-            .map(|c| c.id.clone())
-            .unwrap_or_else(|| "primary".to_string());
-
         for config in &configs {
-            let manager = ConnectionManager::<MysqlConnection>::new(&config.url);
-            let pool = r2d2::Pool::builder()
-                .build(manager)
-                .map_err(|e| format!("Failed to create pool for {}: {}", config.id, e))?;
-
-            pools.insert(config.id.clone(), pool);
+            pools.insert(config.id.clone(), build_pool(config)?);
         }
 
         Ok(DatabaseManager {
             pools: Arc::new(RwLock::new(pools)),
-            configs,
-            default_db,
+            configs: Arc::new(std::sync::RwLock::new(configs)),
+        })
+    }
+
+    /// Create a database manager for the databases in a config, falling back to
+    /// a single "primary" database from `DATABASE_URL` when none are configured
+    pub async fn from_config(config: &Config) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::new(database_configs(config)?).await
+    }
+
+    /// Bring the pools in line with a reloaded config
+    ///
+    /// Pools are created for new databases (and databases whose URL changed) and
+    /// migrated before anything is swapped in, so a database that cannot be
+    /// reached leaves the current pools and configs untouched. Pools of removed
+    /// databases are dropped.
+    pub async fn reload(
+        &self,
+        config: &Config,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let configs = database_configs(config)?;
+        let current = self.get_configs();
+
+        let changed: Vec<DatabaseConfig> = configs
+            .iter()
+            .filter(|config| {
+                !current
+                    .iter()
+                    .any(|existing| existing.id == config.id && existing.url == config.url)
+            })
+            .cloned()
+            .collect();
+        // Connecting may block for the pool's connection timeout
+        let new_pools = tokio::task::spawn_blocking(move || {
+            changed
+                .iter()
+                .map(|config| {
+                    let pool = build_pool(config)?;
+                    let mut conn = pool
+                        .get()
+                        .map_err(|e| format!("Failed to connect to {}: {}", config.id, e))?;
+                    conn.run_pending_migrations(crate::MIGRATIONS)
+                        .map_err(|e| format!("Failed to run migrations on {}: {}", config.id, e))?;
+                    Ok((config.id.clone(), pool))
+                })
+                .collect::<Result<Vec<_>, String>>()
         })
+        .await??;
+
+        let mut pools = self.pools.write().await;
+        pools.retain(|id, _| configs.iter().any(|config| &config.id == id));
+        for (id, pool) in new_pools {
+            tracing::info!("Connected database pool: {}", id);
+            pools.insert(id, pool);
+        }
+        *self
+            .configs
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = configs;
+        Ok(())
     }
 
     /// Get a database pool by ID
@@ -54,17 +129,56 @@ impl DatabaseManager {
 
     /// Get the default database pool
     pub async fn get_default_pool(&self) -> Option<DbPool> {
-        self.get_pool(&self.default_db).await
+        self.get_pool(&self.get_default_db_id()).await
     }
 
     /// Get all available database configurations
-    pub fn get_configs(&self) -> &[DatabaseConfig] {
-        &self.configs
+    pub fn get_configs(&self) -> Vec<DatabaseConfig> {
+        self.configs
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Get the default database ID: the first configured database
+    pub fn get_default_db_id(&self) -> String {
+        self.get_configs()
+            .first()
+            .map(|config| config.id.clone())
+            .unwrap_or_else(|| "primary".to_string())
+    }
+
+    /// Get the database configurations an admin has been granted access to
+    pub fn get_configs_for_admin(&self, admin: &AdminCredentials) -> Vec<DatabaseConfig> {
+        self.get_configs()
+            .into_iter()
+            .filter(|config| admin.can_access_database(&config.id))
+            .collect()
+    }
+
+    /// Get the database an admin should land on: the default if granted, otherwise
+    /// the first granted database
+    pub fn get_default_db_id_for_admin(&self, admin: &AdminCredentials) -> Option<String> {
+        let default_db = self.get_default_db_id();
+        if admin.can_access_database(&default_db) {
+            return Some(default_db);
+        }
+        self.get_configs()
+            .into_iter()
+            .find(|config| admin.can_access_database(&config.id))
+            .map(|config| config.id)
     }
 
-    /// Get the default database ID
-    pub fn get_default_db_id(&self) -> &str {
-        &self.default_db
+    /// Get a database pool by ID, only if the admin has been granted access to it
+    pub async fn get_pool_for_admin(
+        &self,
+        admin: &AdminCredentials,
+        db_id: &str,
+    ) -> Option<DbPool> {
+        if !admin.can_access_database(db_id) {
+            return None;
+        }
+        self.get_pool(db_id).await
     }
 
     /// Check if a database ID exists
@@ -81,7 +195,7 @@ impl DatabaseManager {
 
         let pools = self.pools.read().await;
 
-        for config in &self.configs {
+        for config in &self.get_configs() {
             if let Some(pool) = pools.get(&config.id) {
                 tracing::info!("Running migrations on database: {}", config.id);
 
@@ -195,8 +309,21 @@ pub fn get_domain_by_name(pool: &DbPool, domain_name: &str) -> Result<Domain, Er
         .first::<Domain>(&mut conn)
 }
 
-pub fn create_domain(pool: &DbPool, new_domain: NewDomain) -> Result<Domain, Error> {
+pub fn create_domain(
+    pool: &DbPool,
+    new_domain: NewDomain,
+    audit: &AuditContext,
+) -> Result<Domain, Error> {
     let mut conn = pool.get().unwrap();
+    conn.transaction(|conn| insert_domain(conn, new_domain, audit))
+}
+
+/// Insert a domain inside the caller's transaction
+fn insert_domain(
+    conn: &mut MysqlConnection,
+    new_domain: NewDomain,
+    audit: &AuditContext,
+) -> Result<Domain, Error> {
     let now = Utc::now().naive_utc();
 
     diesel::insert_into(domains::table)
@@ -204,38 +331,189 @@ pub fn create_domain(pool: &DbPool, new_domain: NewDomain) -> Result<Domain, Err
             domains::domain.eq(new_domain.domain),
             domains::transport.eq(new_domain.transport.clone()),
             domains::enabled.eq(new_domain.enabled),
+            domains::default_quota_bytes.eq(new_domain.default_quota_bytes),
+            domains::default_quota_messages.eq(new_domain.default_quota_messages),
+            domains::quota_cap_bytes.eq(new_domain.quota_cap_bytes),
             domains::created.eq(now),
             domains::modified.eq(now),
         ))
-        .execute(&mut conn)?;
+        .execute(conn)?;
 
-    domains::table
+    let domain = domains::table
         .order(domains::pkid.desc())
         .select(Domain::as_select())
-        .first::<Domain>(&mut conn)
+        .first::<Domain>(conn)?;
+
+    record_audit(conn, audit, AuditAction::Create, None, Some(&domain))?;
+    Ok(domain)
 }
 
 pub fn update_domain(
     pool: &DbPool,
     domain_id: i32,
     domain_data: DomainForm,
+    audit: &AuditContext,
 ) -> Result<Domain, Error> {
     let mut conn = pool.get().unwrap();
+    conn.transaction(|conn| update_domain_row(conn, domain_id, domain_data, audit))
+}
+
+/// Update a domain inside the caller's transaction
+fn update_domain_row(
+    conn: &mut MysqlConnection,
+    domain_id: i32,
+    domain_data: DomainForm,
+    audit: &AuditContext,
+) -> Result<Domain, Error> {
+    let before = domains::table
+        .find(domain_id)
+        .select(Domain::as_select())
+        .first::<Domain>(conn)?;
+
+    if let Some(cap) = domain_data.quota_cap_bytes.filter(|cap| *cap > 0) {
+        let (allocated, unlimited) = quota_allocation(
+            conn,
+            &domain_data.domain,
+            domain_data.default_quota_bytes,
+            &[],
+        )?;
+        if unlimited > 0 {
+            return Err(quota_cap_error(format!(
+                "{} mailboxes in {} have no size quota, so the domain cannot be capped",
+                unlimited, domain_data.domain
+            )));
+        }
+        if allocated > cap {
+            return Err(quota_cap_error(format!(
+                "Mailbox quotas in {} already add up to {}, more than the cap of {}",
+                domain_data.domain,
+                crate::quota::human_size(allocated),
+                crate::quota::human_size(cap)
+            )));
+        }
+    }
+
     diesel::update(domains::table.find(domain_id))
         .set((
             domains::domain.eq(domain_data.domain),
             domains::transport.eq(domain_data.transport.clone()),
             domains::enabled.eq(domain_data.enabled),
+            domains::default_quota_bytes.eq(domain_data.default_quota_bytes),
+            domains::default_quota_messages.eq(domain_data.default_quota_messages),
+            domains::quota_cap_bytes.eq(domain_data.quota_cap_bytes),
             domains::modified.eq(Utc::now().naive_utc()),
         ))
-        .execute(&mut conn)?;
+        .execute(conn)?;
+
+    let after = domains::table
+        .find(domain_id)
+        .select(Domain::as_select())
+        .first::<Domain>(conn)?;
+
+    record_audit(
+        conn,
+        audit,
+        AuditAction::Update,
+        Some(&before),
+        Some(&after),
+    )?;
+    Ok(after)
+}
+
+/// A validation error for a quota that does not fit under its domain's cap
+fn quota_cap_error(message: String) -> Error {
+    Error::DatabaseError(
+        diesel::result::DatabaseErrorKind::CheckViolation,
+        Box::new(message),
+    )
+}
 
-    get_domain(pool, domain_id)
+/// Sum the effective size quotas of a domain's mailboxes, skipping `exclude`
+///
+/// Returns the allocated bytes and the number of mailboxes without a limit.
+fn quota_allocation(
+    conn: &mut MysqlConnection,
+    domain: &str,
+    default_quota_bytes: Option<i64>,
+    exclude: &[&str],
+) -> Result<(i64, i64), Error> {
+    let quotas: Vec<(String, Option<i64>)> = users::table
+        .filter(users::id.like(format!("%@{domain}")))
+        .select((users::id, users::quota_bytes))
+        .load(conn)?;
+
+    let mut allocated = 0i64;
+    let mut unlimited = 0i64;
+    for (id, quota_bytes) in quotas {
+        if exclude.contains(&id.as_str()) {
+            continue;
+        }
+        match crate::quota::effective(quota_bytes, default_quota_bytes) {
+            0 => unlimited += 1,
+            bytes => allocated = allocated.saturating_add(bytes),
+        }
+    }
+    Ok((allocated, unlimited))
+}
+
+/// Reject a mailbox quota that would take its domain past the domain's cap
+fn check_quota_cap(
+    conn: &mut MysqlConnection,
+    user_id: &str,
+    quota_bytes: Option<i64>,
+    exclude: &[&str],
+) -> Result<(), Error> {
+    let Some((_, domain_name)) = user_id.rsplit_once('@') else {
+        return Ok(());
+    };
+    let Some(domain) = domains::table
+        .filter(domains::domain.eq(domain_name))
+        .select(Domain::as_select())
+        .first::<Domain>(conn)
+        .optional()?
+    else {
+        return Ok(());
+    };
+    let Some(cap) = domain.quota_cap_bytes.filter(|cap| *cap > 0) else {
+        return Ok(());
+    };
+
+    let quota = crate::quota::effective(quota_bytes, domain.default_quota_bytes);
+    if quota == 0 {
+        return Err(quota_cap_error(format!(
+            "{domain_name} has a quota cap, so {user_id} needs a size quota"
+        )));
+    }
+    let (allocated, _) = quota_allocation(conn, domain_name, domain.default_quota_bytes, exclude)?;
+    if allocated.saturating_add(quota) > cap {
+        return Err(quota_cap_error(format!(
+            "A quota of {} for {} would exceed the {} cap of {} ({} already allocated)",
+            crate::quota::human_size(quota),
+            user_id,
+            domain_name,
+            crate::quota::human_size(cap),
+            crate::quota::human_size(allocated)
+        )));
+    }
+    Ok(())
 }
 
-pub fn delete_domain(pool: &DbPool, domain_id: i32) -> Result<usize, Error> {
+pub fn delete_domain(pool: &DbPool, domain_id: i32, audit: &AuditContext) -> Result<usize, Error> {
     let mut conn = pool.get().unwrap();
-    diesel::delete(domains::table.find(domain_id)).execute(&mut conn)
+    conn.transaction(|conn| {
+        let Some(before) = domains::table
+            .find(domain_id)
+            .select(Domain::as_select())
+            .first::<Domain>(conn)
+            .optional()?
+        else {
+            return Ok(0);
+        };
+
+        let deleted = diesel::delete(domains::table.find(domain_id)).execute(conn)?;
+        record_audit(conn, audit, AuditAction::Delete, Some(&before), None)?;
+        Ok(deleted)
+    })
 }
 
 pub fn get_users(pool: &DbPool) -> Result<Vec<User>, Error> {
@@ -255,6 +533,33 @@ pub fn get_user(pool: &DbPool, user_id: String) -> Result<User, Error> {
         .first::<User>(&mut conn)
 }
 
+/// All users under the scope, ordered by address
+pub fn get_users_in_scope(pool: &DbPool, scope: &AddressScope) -> Result<Vec<User>, Error> {
+    let mut conn = pool.get().unwrap();
+    let mut query = users::table.select(User::as_select()).into_boxed();
+    if let Some(filter) = user_scope_filter(scope) {
+        query = query.filter(filter);
+    }
+    query.order(users::id.asc()).load::<User>(&mut conn)
+}
+
+/// A user, treating users outside the scope as missing
+pub fn get_user_in_scope(
+    pool: &DbPool,
+    user_id: &str,
+    scope: &AddressScope,
+) -> Result<User, Error> {
+    let mut conn = pool.get().unwrap();
+    let mut query = users::table
+        .filter(users::id.eq(user_id))
+        .select(User::as_select())
+        .into_boxed();
+    if let Some(filter) = user_scope_filter(scope) {
+        query = query.filter(filter);
+    }
+    query.first::<User>(&mut conn)
+}
+
 pub fn get_user_by_id(pool: &DbPool, user_id: &str) -> Result<User, Error> {
     let mut conn = pool.get().unwrap();
     users::table
@@ -263,17 +568,31 @@ pub fn get_user_by_id(pool: &DbPool, user_id: &str) -> Result<User, Error> {
         .first::<User>(&mut conn)
 }
 
-pub fn create_user(pool: &DbPool, user_data: UserForm) -> Result<User, Error> {
+/// Hash a mailbox password, reporting failures as a database error
+fn hash_user_password(password: &str, scheme: PasswordScheme) -> Result<String, Error> {
+    password::hash_password(password, scheme)
+        .map_err(|e| Error::DatabaseError(diesel::result::DatabaseErrorKind::Unknown, Box::new(e)))
+}
+
+pub fn create_user(
+    pool: &DbPool,
+    user_data: UserForm,
+    scheme: PasswordScheme,
+    audit: &AuditContext,
+) -> Result<User, Error> {
     let mut conn = pool.get().unwrap();
+    conn.transaction(|conn| insert_user(conn, user_data, scheme, audit))
+}
 
+/// Insert a user inside the caller's transaction
+fn insert_user(
+    conn: &mut MysqlConnection,
+    user_data: UserForm,
+    scheme: PasswordScheme,
+    audit: &AuditContext,
+) -> Result<User, Error> {
     // Hash the password
-    let hashed_password = bcrypt::hash(user_data.password.as_bytes(), bcrypt::DEFAULT_COST)
-        .map_err(|e| {
-            Error::DatabaseError(
-                diesel::result::DatabaseErrorKind::Unknown,
-                Box::new(e.to_string()),
-            )
-        })?;
+    let hashed_password = hash_user_password(&user_data.password, scheme)?;
 
     let maildir = format!("{}/", user_data.id);
 
@@ -287,13 +606,17 @@ pub fn create_user(pool: &DbPool, user_data: UserForm) -> Result<User, Error> {
         gid: 5000,
         enabled: user_data.enabled,
         change_password: false,
+        quota_bytes: user_data.quota_bytes,
+        quota_messages: user_data.quota_messages,
     };
 
     let now = Utc::now().naive_utc();
 
+    check_quota_cap(conn, &new_user.id, new_user.quota_bytes, &[&new_user.id])?;
+
     diesel::insert_into(users::table)
         .values((
-            users::id.eq(new_user.id),
+            users::id.eq(&new_user.id),
             users::crypt.eq(new_user.crypt),
             users::name.eq(new_user.name),
             users::maildir.eq(new_user.maildir),
@@ -301,82 +624,200 @@ pub fn create_user(pool: &DbPool, user_data: UserForm) -> Result<User, Error> {
             users::uid.eq(new_user.uid),
             users::gid.eq(new_user.gid),
             users::enabled.eq(new_user.enabled),
+            users::quota_bytes.eq(new_user.quota_bytes),
+            users::quota_messages.eq(new_user.quota_messages),
             users::created.eq(now),
             users::modified.eq(now),
         ))
-        .execute(&mut conn)?;
+        .execute(conn)?;
 
-    users::table
-        .order(users::id.desc())
+    let user = users::table
+        .filter(users::id.eq(&new_user.id))
         .select(User::as_select())
-        .first::<User>(&mut conn)
+        .first::<User>(conn)?;
+
+    record_audit(conn, audit, AuditAction::Create, None, Some(&user))?;
+    Ok(user)
 }
 
-pub fn update_user(pool: &DbPool, user_id: String, user_data: UserForm) -> Result<User, Error> {
-    use crate::schema::users::dsl::*;
+pub fn update_user(
+    pool: &DbPool,
+    user_id: String,
+    user_data: UserForm,
+    audit: &AuditContext,
+) -> Result<User, Error> {
     let mut conn = pool.get().unwrap();
+    conn.transaction(|conn| update_user_row(conn, user_id, user_data, audit))
+}
+
+/// Update a user inside the caller's transaction
+fn update_user_row(
+    conn: &mut MysqlConnection,
+    user_id: String,
+    user_data: UserForm,
+    audit: &AuditContext,
+) -> Result<User, Error> {
+    use crate::schema::users::dsl::*;
 
     // First get the current user to preserve unchanged fields
-    let _current_user = get_user(pool, user_id.clone())?;
+    let before = users
+        .filter(id.eq(&user_id))
+        .select(User::as_select())
+        .first::<User>(conn)?;
+
+    check_quota_cap(
+        conn,
+        &user_data.id,
+        user_data.quota_bytes,
+        &[&user_id, &user_data.id],
+    )?;
 
     // Update the user - include id if it's different from the current one
     if user_data.id != user_id {
         diesel::update(users.filter(id.eq(user_id.clone())))
             .set((
                 id.eq(user_data.id.clone()),
-                name.eq(user_data.name),
+                name.eq(&user_data.name),
                 enabled.eq(user_data.enabled),
                 change_password.eq(user_data.change_password),
+                quota_bytes.eq(user_data.quota_bytes),
+                quota_messages.eq(user_data.quota_messages),
             ))
-            .execute(&mut conn)?;
+            .execute(conn)?;
     } else {
         diesel::update(users.filter(id.eq(user_id.clone())))
             .set((
-                name.eq(user_data.name),
+                name.eq(&user_data.name),
                 enabled.eq(user_data.enabled),
                 change_password.eq(user_data.change_password),
+                quota_bytes.eq(user_data.quota_bytes),
+                quota_messages.eq(user_data.quota_messages),
             ))
-            .execute(&mut conn)?;
+            .execute(conn)?;
     }
 
     // Return the updated user using the new ID if it changed
-    let final_user_id = if user_data.id != user_id {
-        user_data.id
-    } else {
-        user_id
-    };
-    get_user(pool, final_user_id)
+    let after = users
+        .filter(id.eq(&user_data.id))
+        .select(User::as_select())
+        .first::<User>(conn)?;
+
+    record_audit(
+        conn,
+        audit,
+        AuditAction::Update,
+        Some(&before),
+        Some(&after),
+    )?;
+    Ok(after)
 }
 
 pub fn update_user_password(
     pool: &DbPool,
     user_id: String,
     new_password: &str,
+    scheme: PasswordScheme,
+    audit: &AuditContext,
+) -> Result<(), Error> {
+    set_user_password(pool, user_id, new_password, scheme, false, audit)
+}
+
+/// Set the password a mailbox user chose themselves, clearing `change_password`
+pub fn update_own_password(
+    pool: &DbPool,
+    user_id: String,
+    new_password: &str,
+    scheme: PasswordScheme,
+    audit: &AuditContext,
+) -> Result<(), Error> {
+    set_user_password(pool, user_id, new_password, scheme, true, audit)
+}
+
+fn set_user_password(
+    pool: &DbPool,
+    user_id: String,
+    new_password: &str,
+    scheme: PasswordScheme,
+    clear_change_password: bool,
+    audit: &AuditContext,
 ) -> Result<(), Error> {
-    use crate::schema::users::dsl::*;
     let mut conn = pool.get().unwrap();
+    conn.transaction(|conn| {
+        write_user_password(
+            conn,
+            user_id,
+            new_password,
+            scheme,
+            clear_change_password,
+            audit,
+        )
+    })
+}
+
+/// Set a user's password inside the caller's transaction
+fn write_user_password(
+    conn: &mut MysqlConnection,
+    user_id: String,
+    new_password: &str,
+    scheme: PasswordScheme,
+    clear_change_password: bool,
+    audit: &AuditContext,
+) -> Result<(), Error> {
+    use crate::schema::users::dsl::*;
 
     // Hash the new password
-    let hashed_password = bcrypt::hash(new_password, bcrypt::DEFAULT_COST).map_err(|e| {
-        Error::DatabaseError(
-            diesel::result::DatabaseErrorKind::Unknown,
-            Box::new(e.to_string()),
-        )
-    })?;
+    let hashed_password = hash_user_password(new_password, scheme)?;
+
+    let before = users
+        .filter(id.eq(&user_id))
+        .select(User::as_select())
+        .first::<User>(conn)?;
 
     // Update the password
-    diesel::update(users.filter(id.eq(user_id)))
+    diesel::update(users.filter(id.eq(&user_id)))
         .set(crypt.eq(hashed_password))
-        .execute(&mut conn)?;
+        .execute(conn)?;
+    if clear_change_password {
+        diesel::update(users.filter(id.eq(&user_id)))
+            .set(change_password.eq(false))
+            .execute(conn)?;
+    }
 
+    let after = users
+        .filter(id.eq(&user_id))
+        .select(User::as_select())
+        .first::<User>(conn)?;
+
+    record_audit(
+        conn,
+        audit,
+        AuditAction::Password,
+        Some(&before),
+        Some(&after),
+    )?;
     Ok(())
 }
 
-pub fn delete_user(pool: &DbPool, user_id: String) -> Result<usize, Error> {
+pub fn delete_user(pool: &DbPool, user_id: String, audit: &AuditContext) -> Result<usize, Error> {
     use crate::schema::users::dsl::*;
     let mut conn = pool.get().unwrap();
 
-    diesel::delete(users.filter(id.eq(user_id))).execute(&mut conn)
+    conn.transaction(|conn| {
+        let Some(before) = users
+            .filter(id.eq(&user_id))
+            .select(User::as_select())
+            .first::<User>(conn)
+            .optional()?
+        else {
+            return Ok(0);
+        };
+
+        let deleted = diesel::delete(users.filter(id.eq(&user_id))).execute(conn)?;
+        // The vacation scheduler would otherwise keep recreating its alias
+        diesel::delete(vacation::table.find(&user_id)).execute(conn)?;
+        record_audit(conn, audit, AuditAction::Delete, Some(&before), None)?;
+        Ok(deleted)
+    })
 }
 
 pub fn get_aliases(pool: &DbPool) -> Result<Vec<Alias>, Error> {
@@ -395,9 +836,75 @@ pub fn get_alias(pool: &DbPool, alias_id: i32) -> Result<Alias, Error> {
         .first::<Alias>(&mut conn)
 }
 
-pub fn create_alias(pool: &DbPool, alias_data: AliasForm) -> Result<Alias, Error> {
+/// All aliases under the scope, ordered by address
+pub fn get_aliases_in_scope(pool: &DbPool, scope: &AddressScope) -> Result<Vec<Alias>, Error> {
+    let mut conn = pool.get().unwrap();
+    let mut query = aliases::table.select(Alias::as_select()).into_boxed();
+    if let Some(filter) = alias_scope_filter(scope) {
+        query = query.filter(filter);
+    }
+    query.order(aliases::mail.asc()).load::<Alias>(&mut conn)
+}
+
+/// An alias, treating aliases outside the scope as missing
+pub fn get_alias_in_scope(
+    pool: &DbPool,
+    alias_id: i32,
+    scope: &AddressScope,
+) -> Result<Alias, Error> {
+    let mut conn = pool.get().unwrap();
+    let mut query = aliases::table
+        .filter(aliases::pkid.eq(alias_id))
+        .select(Alias::as_select())
+        .into_boxed();
+    if let Some(filter) = alias_scope_filter(scope) {
+        query = query.filter(filter);
+    }
+    query.first::<Alias>(&mut conn)
+}
+
+pub fn get_alias_by_mail(pool: &DbPool, alias_mail: &str) -> Result<Alias, Error> {
+    let mut conn = pool.get().unwrap();
+    aliases::table
+        .filter(aliases::mail.eq(alias_mail))
+        .select(Alias::as_select())
+        .first::<Alias>(&mut conn)
+}
+
+/// Aliases that deliver to `address`, other than the address's own alias
+///
+/// Destinations are matched exactly after splitting, so `bob@example.com`
+/// does not pick up `jimbob@example.com`.
+pub fn get_aliases_for_destination(pool: &DbPool, address: &str) -> Result<Vec<Alias>, Error> {
+    let mut conn = pool.get().unwrap();
+    let address = address.to_lowercase();
+    let candidates = aliases::table
+        .filter(aliases::destination.like(format!("%{address}%")))
+        .filter(aliases::mail.ne(&address))
+        .select(Alias::as_select())
+        .order(aliases::mail.asc())
+        .load::<Alias>(&mut conn)?;
+    Ok(candidates
+        .into_iter()
+        .filter(|alias| crate::delivery::split_destinations(&alias.destination).contains(&address))
+        .collect())
+}
+
+pub fn create_alias(
+    pool: &DbPool,
+    alias_data: AliasForm,
+    audit: &AuditContext,
+) -> Result<Alias, Error> {
     let mut conn = pool.get().unwrap();
+    conn.transaction(|conn| insert_alias(conn, alias_data, audit))
+}
 
+/// Insert an alias inside the caller's transaction
+fn insert_alias(
+    conn: &mut MysqlConnection,
+    alias_data: AliasForm,
+    audit: &AuditContext,
+) -> Result<Alias, Error> {
     let now = Utc::now().naive_utc();
 
     diesel::insert_into(aliases::table)
@@ -405,85 +912,251 @@ pub fn create_alias(pool: &DbPool, alias_data: AliasForm) -> Result<Alias, Error
             aliases::mail.eq(alias_data.mail),
             aliases::destination.eq(alias_data.destination),
             aliases::enabled.eq(alias_data.enabled),
+            aliases::valid_from.eq(alias_data.valid_from),
+            aliases::valid_until.eq(alias_data.valid_until),
             aliases::created.eq(now),
             aliases::modified.eq(now),
         ))
-        .execute(&mut conn)?;
+        .execute(conn)?;
 
-    aliases::table
+    let alias = aliases::table
         .order(aliases::pkid.desc())
         .select(Alias::as_select())
-        .first::<Alias>(&mut conn)
+        .first::<Alias>(conn)?;
+
+    record_audit(conn, audit, AuditAction::Create, None, Some(&alias))?;
+    Ok(alias)
 }
 
-pub fn update_alias(pool: &DbPool, alias_id: i32, alias_data: AliasForm) -> Result<Alias, Error> {
+pub fn update_alias(
+    pool: &DbPool,
+    alias_id: i32,
+    alias_data: AliasForm,
+    audit: &AuditContext,
+) -> Result<Alias, Error> {
     let mut conn = pool.get().unwrap();
+    conn.transaction(|conn| update_alias_row(conn, alias_id, alias_data, audit))
+}
+
+/// Update an alias inside the caller's transaction
+fn update_alias_row(
+    conn: &mut MysqlConnection,
+    alias_id: i32,
+    alias_data: AliasForm,
+    audit: &AuditContext,
+) -> Result<Alias, Error> {
+    let before = aliases::table
+        .find(alias_id)
+        .select(Alias::as_select())
+        .first::<Alias>(conn)?;
+
     diesel::update(aliases::table.find(alias_id))
         .set((
             aliases::mail.eq(alias_data.mail),
             aliases::destination.eq(alias_data.destination),
             aliases::enabled.eq(alias_data.enabled),
+            aliases::valid_from.eq(alias_data.valid_from),
+            aliases::valid_until.eq(alias_data.valid_until),
             aliases::modified.eq(Utc::now().naive_utc()),
         ))
-        .execute(&mut conn)?;
+        .execute(conn)?;
 
-    get_alias(pool, alias_id)
+    let after = aliases::table
+        .find(alias_id)
+        .select(Alias::as_select())
+        .first::<Alias>(conn)?;
+
+    record_audit(
+        conn,
+        audit,
+        AuditAction::Update,
+        Some(&before),
+        Some(&after),
+    )?;
+    Ok(after)
 }
 
-pub fn delete_alias(pool: &DbPool, alias_id: i32) -> Result<usize, Error> {
+pub fn delete_alias(pool: &DbPool, alias_id: i32, audit: &AuditContext) -> Result<usize, Error> {
     let mut conn = pool.get().unwrap();
-    diesel::delete(aliases::table.find(alias_id)).execute(&mut conn)
+    conn.transaction(|conn| {
+        let Some(before) = aliases::table
+            .find(alias_id)
+            .select(Alias::as_select())
+            .first::<Alias>(conn)
+            .optional()?
+        else {
+            return Ok(0);
+        };
+
+        let deleted = diesel::delete(aliases::table.find(alias_id)).execute(conn)?;
+        record_audit(conn, audit, AuditAction::Delete, Some(&before), None)?;
+        Ok(deleted)
+    })
 }
 
-// Toggle functions for enable/disable functionality
-pub fn toggle_domain_enabled(pool: &DbPool, domain_id: i32) -> Result<Domain, Error> {
+/// Aliases with a validity window, for the scheduler
+pub fn get_scheduled_aliases(pool: &DbPool) -> Result<Vec<Alias>, Error> {
     let mut conn = pool.get().unwrap();
+    aliases::table
+        .filter(
+            aliases::valid_from
+                .is_not_null()
+                .or(aliases::valid_until.is_not_null()),
+        )
+        .select(Alias::as_select())
+        .order(aliases::mail.asc())
+        .load::<Alias>(&mut conn)
+}
 
-    // First get the current domain to check its enabled status
-    let current_domain = get_domain(pool, domain_id)?;
-    let new_enabled_status = !current_domain.enabled;
+/// Enable or disable an alias, recording the change as a toggle
+pub fn set_alias_enabled(
+    pool: &DbPool,
+    alias_id: i32,
+    enabled: bool,
+    audit: &AuditContext,
+) -> Result<Alias, Error> {
+    let mut conn = pool.get().unwrap();
+    conn.transaction(|conn| {
+        let before = aliases::table
+            .find(alias_id)
+            .select(Alias::as_select())
+            .first::<Alias>(conn)?;
 
-    diesel::update(domains::table.find(domain_id))
-        .set((
-            domains::enabled.eq(new_enabled_status),
-            domains::modified.eq(Utc::now().naive_utc()),
-        ))
-        .execute(&mut conn)?;
+        diesel::update(aliases::table.find(alias_id))
+            .set((
+                aliases::enabled.eq(enabled),
+                aliases::modified.eq(Utc::now().naive_utc()),
+            ))
+            .execute(conn)?;
 
-    get_domain(pool, domain_id)
+        let after = aliases::table
+            .find(alias_id)
+            .select(Alias::as_select())
+            .first::<Alias>(conn)?;
+
+        record_audit(
+            conn,
+            audit,
+            AuditAction::Toggle,
+            Some(&before),
+            Some(&after),
+        )?;
+        Ok(after)
+    })
 }
 
-pub fn toggle_user_enabled(pool: &DbPool, user_id: String) -> Result<User, Error> {
-    use crate::schema::users::dsl::*;
+// Toggle functions for enable/disable functionality
+pub fn toggle_domain_enabled(
+    pool: &DbPool,
+    domain_id: i32,
+    audit: &AuditContext,
+) -> Result<Domain, Error> {
     let mut conn = pool.get().unwrap();
 
-    // Get current user
-    let current_user = get_user(pool, user_id.clone())?;
+    conn.transaction(|conn| {
+        // First get the current domain to check its enabled status
+        let before = domains::table
+            .find(domain_id)
+            .select(Domain::as_select())
+            .first::<Domain>(conn)?;
+        let new_enabled_status = !before.enabled;
+
+        diesel::update(domains::table.find(domain_id))
+            .set((
+                domains::enabled.eq(new_enabled_status),
+                domains::modified.eq(Utc::now().naive_utc()),
+            ))
+            .execute(conn)?;
+
+        let after = domains::table
+            .find(domain_id)
+            .select(Domain::as_select())
+            .first::<Domain>(conn)?;
+
+        record_audit(
+            conn,
+            audit,
+            AuditAction::Toggle,
+            Some(&before),
+            Some(&after),
+        )?;
+        Ok(after)
+    })
+}
 
-    // Toggle the enabled status
-    diesel::update(users.filter(id.eq(user_id.clone())))
-        .set(enabled.eq(!current_user.enabled))
-        .execute(&mut conn)?;
+pub fn toggle_user_enabled(
+    pool: &DbPool,
+    user_id: String,
+    audit: &AuditContext,
+) -> Result<User, Error> {
+    use crate::schema::users::dsl::*;
+    let mut conn = pool.get().unwrap();
 
-    // Return the updated user
-    get_user(pool, user_id)
+    conn.transaction(|conn| {
+        // Get current user
+        let before = users
+            .filter(id.eq(&user_id))
+            .select(User::as_select())
+            .first::<User>(conn)?;
+
+        // Toggle the enabled status
+        diesel::update(users.filter(id.eq(&user_id)))
+            .set(enabled.eq(!before.enabled))
+            .execute(conn)?;
+
+        // Return the updated user
+        let after = users
+            .filter(id.eq(&user_id))
+            .select(User::as_select())
+            .first::<User>(conn)?;
+
+        record_audit(
+            conn,
+            audit,
+            AuditAction::Toggle,
+            Some(&before),
+            Some(&after),
+        )?;
+        Ok(after)
+    })
 }
 
-pub fn toggle_alias_enabled(pool: &DbPool, alias_id: i32) -> Result<Alias, Error> {
+pub fn toggle_alias_enabled(
+    pool: &DbPool,
+    alias_id: i32,
+    audit: &AuditContext,
+) -> Result<Alias, Error> {
     let mut conn = pool.get().unwrap();
 
-    // First get the current alias to check its enabled status
-    let current_alias = get_alias(pool, alias_id)?;
-    let new_enabled_status = !current_alias.enabled;
+    conn.transaction(|conn| {
+        // First get the current alias to check its enabled status
+        let before = aliases::table
+            .find(alias_id)
+            .select(Alias::as_select())
+            .first::<Alias>(conn)?;
+        let new_enabled_status = !before.enabled;
 
-    diesel::update(aliases::table.find(alias_id))
-        .set((
-            aliases::enabled.eq(new_enabled_status),
-            aliases::modified.eq(Utc::now().naive_utc()),
-        ))
-        .execute(&mut conn)?;
+        diesel::update(aliases::table.find(alias_id))
+            .set((
+                aliases::enabled.eq(new_enabled_status),
+                aliases::modified.eq(Utc::now().naive_utc()),
+            ))
+            .execute(conn)?;
 
-    get_alias(pool, alias_id)
+        let after = aliases::table
+            .find(alias_id)
+            .select(Alias::as_select())
+            .first::<Alias>(conn)?;
+
+        record_audit(
+            conn,
+            audit,
+            AuditAction::Toggle,
+            Some(&before),
+            Some(&after),
+        )?;
+        Ok(after)
+    })
 }
 
 // Statistics functions
@@ -599,13 +1272,28 @@ pub fn get_system_stats(pool: &DbPool) -> Result<SystemStats, Error> {
         .count()
         .get_result(&mut conn)?;
 
-    // Quota (still 0, as not implemented)
-    let total_quota: i64 = 0;
-    let used_quota: i64 = 0;
-    let quota_usage_percent: f64 = 0.0;
+    // Quota allocated to mailboxes, and usage from the latest collection
+    let total_quota: i64 = quota_allocations(&mut conn)?
+        .iter()
+        .map(|allocation| allocation.total_quota)
+        .sum();
+    let used_quota: i64 = usage_by_domain(&mut conn)?
+        .iter()
+        .map(|usage| usage.used_quota)
+        .sum();
+    let quota_usage_percent: f64 = if total_quota > 0 {
+        used_quota as f64 / total_quota as f64 * 100.0
+    } else {
+        0.0
+    };
 
     // Combined enabled stats for dashboard
     let enabled_domains_and_backups = enabled_domains + enabled_backups;
+    let expiring_aliases: i64 = aliases::table
+        .filter(aliases::valid_until.gt(now))
+        .filter(aliases::valid_until.le(crate::validity::expiring_soon_cutoff(now)))
+        .count()
+        .get_result(&mut conn)?;
 
     Ok(SystemStats {
         total_domains,
@@ -640,14 +1328,45 @@ pub fn get_system_stats(pool: &DbPool) -> Result<SystemStats, Error> {
         used_quota,
         quota_usage_percent,
         enabled_domains_and_backups,
+        expiring_aliases,
     })
 }
 
+/// Effective quota allocated per domain, from the part of the user id after `@`
+#[derive(QueryableByName)]
+struct QuotaAllocation {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    domain: String,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    total_quota: i64,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    unlimited_users: i64,
+}
+
+fn quota_allocations(conn: &mut MysqlConnection) -> Result<Vec<QuotaAllocation>, Error> {
+    sql_query(
+        "SELECT SUBSTRING_INDEX(users.id, '@', -1) AS domain, \
+         CAST(SUM(COALESCE(users.quota_bytes, domains.default_quota_bytes, 0)) AS SIGNED) AS total_quota, \
+         CAST(SUM(COALESCE(users.quota_bytes, domains.default_quota_bytes, 0) = 0) AS SIGNED) AS unlimited_users \
+         FROM users LEFT JOIN domains ON domains.domain = SUBSTRING_INDEX(users.id, '@', -1) \
+         GROUP BY SUBSTRING_INDEX(users.id, '@', -1)",
+    )
+    .load::<QuotaAllocation>(conn)
+}
+
 pub fn get_domain_stats(pool: &DbPool) -> Result<Vec<DomainStats>, Error> {
     let mut conn = pool.get().unwrap();
 
     // This is a simplified version - in a real implementation you'd want to use proper SQL aggregation
     let domains = get_domains(pool)?;
+    let allocations: HashMap<String, QuotaAllocation> = quota_allocations(&mut conn)?
+        .into_iter()
+        .map(|allocation| (allocation.domain.clone(), allocation))
+        .collect();
+    let usage: HashMap<String, i64> = usage_by_domain(&mut conn)?
+        .into_iter()
+        .map(|usage| (usage.domain, usage.used_quota))
+        .collect();
     let mut stats = Vec::new();
 
     for domain in domains {
@@ -663,26 +1382,323 @@ pub fn get_domain_stats(pool: &DbPool) -> Result<Vec<DomainStats>, Error> {
             .count()
             .get_result(&mut conn)?;
 
-        let total_quota: i64 = 0; // Quota field removed from users table
+        let (total_quota, unlimited_users) = allocations
+            .get(&domain.domain)
+            .map_or((0, 0), |allocation| {
+                (allocation.total_quota, allocation.unlimited_users)
+            });
+        let used_quota = usage.get(&domain.domain).copied().unwrap_or(0);
 
         stats.push(DomainStats {
             domain: domain.domain,
             user_count,
             alias_count,
             total_quota,
-            used_quota: 0, // This would need to be calculated from actual disk usage
+            used_quota,
+            unlimited_users,
+            quota_cap: domain.quota_cap_bytes,
         });
     }
 
     Ok(stats)
 }
 
-// Backup functions
-pub fn get_backups(pool: &DbPool) -> Result<Vec<Backup>, Error> {
-    let mut conn = pool.get().unwrap();
-    backups::table
-        .select(Backup::as_select())
-        .order(backups::domain.asc())
+/// Only the latest collection counts as current usage; every sample of a run
+/// shares its timestamp
+const LATEST_USAGE: &str = "mailbox_usage.collected = (SELECT MAX(collected) FROM mailbox_usage)";
+
+/// Mailbox usage per domain at the latest collection, for mailboxes that still exist
+#[derive(QueryableByName)]
+struct DomainUsage {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    domain: String,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    used_quota: i64,
+}
+
+fn usage_by_domain(conn: &mut MysqlConnection) -> Result<Vec<DomainUsage>, Error> {
+    sql_query(format!(
+        "SELECT SUBSTRING_INDEX(users.id, '@', -1) AS domain, \
+         CAST(SUM(mailbox_usage.bytes) AS SIGNED) AS used_quota \
+         FROM mailbox_usage JOIN users ON users.id = mailbox_usage.user_id \
+         WHERE {LATEST_USAGE} \
+         GROUP BY SUBSTRING_INDEX(users.id, '@', -1)"
+    ))
+    .load::<DomainUsage>(conn)
+}
+
+/// Store one collection run of mailbox usage samples
+pub fn record_mailbox_usage(
+    pool: &DbPool,
+    samples: &[crate::usage::UsageSample],
+    collected: NaiveDateTime,
+) -> Result<usize, Error> {
+    let mut conn = pool.get().unwrap();
+    let rows: Vec<NewMailboxUsage> = samples
+        .iter()
+        .map(|sample| NewMailboxUsage {
+            user_id: sample.user_id.clone(),
+            bytes: sample.bytes,
+            messages: sample.messages,
+            collected,
+        })
+        .collect();
+    diesel::insert_into(mailbox_usage::table)
+        .values(&rows)
+        .execute(&mut conn)
+}
+
+/// Delete usage snapshots collected before `before`
+pub fn prune_mailbox_usage(pool: &DbPool, before: NaiveDateTime) -> Result<usize, Error> {
+    let mut conn = pool.get().unwrap();
+    diesel::delete(mailbox_usage::table.filter(mailbox_usage::collected.lt(before)))
+        .execute(&mut conn)
+}
+
+/// The biggest mailboxes at the latest collection, with their growth over the
+/// last `growth_days`
+pub fn get_largest_mailboxes(
+    pool: &DbPool,
+    limit: i64,
+    growth_days: i64,
+) -> Result<Vec<MailboxUsageSummary>, Error> {
+    let mut conn = pool.get().unwrap();
+    let since = Utc::now().naive_utc() - chrono::Duration::days(growth_days);
+    sql_query(format!(
+        "SELECT mailbox_usage.user_id, mailbox_usage.bytes, mailbox_usage.messages, \
+         mailbox_usage.collected, \
+         CAST(COALESCE(users.quota_bytes, domains.default_quota_bytes, 0) AS SIGNED) AS quota_bytes, \
+         CAST(mailbox_usage.bytes - COALESCE((SELECT earliest.bytes FROM mailbox_usage earliest \
+         WHERE earliest.user_id = mailbox_usage.user_id AND earliest.collected >= ? \
+         ORDER BY earliest.collected LIMIT 1), mailbox_usage.bytes) AS SIGNED) AS growth_bytes \
+         FROM mailbox_usage JOIN users ON users.id = mailbox_usage.user_id \
+         LEFT JOIN domains ON domains.domain = SUBSTRING_INDEX(users.id, '@', -1) \
+         WHERE {LATEST_USAGE} \
+         ORDER BY mailbox_usage.bytes DESC, mailbox_usage.user_id LIMIT ?"
+    ))
+    .bind::<diesel::sql_types::Timestamp, _>(since)
+    .bind::<diesel::sql_types::BigInt, _>(limit)
+    .load::<MailboxUsageSummary>(&mut conn)
+}
+
+/// Total usage per collection run
+#[derive(QueryableByName)]
+struct UsageRun {
+    #[diesel(sql_type = diesel::sql_types::Timestamp)]
+    collected: NaiveDateTime,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    bytes: i64,
+}
+
+/// Total mailbox usage per day over the last `days`, from each day's last collection
+pub fn get_usage_history(pool: &DbPool, days: i64) -> Result<Vec<UsageHistoryPoint>, Error> {
+    let mut conn = pool.get().unwrap();
+    let since = Utc::now().naive_utc() - chrono::Duration::days(days);
+    let runs = sql_query(
+        "SELECT collected, CAST(SUM(bytes) AS SIGNED) AS bytes FROM mailbox_usage \
+         WHERE collected >= ? GROUP BY collected ORDER BY collected",
+    )
+    .bind::<diesel::sql_types::Timestamp, _>(since)
+    .load::<UsageRun>(&mut conn)?;
+
+    Ok(daily_usage(
+        runs.into_iter().map(|run| (run.collected, run.bytes)),
+    ))
+}
+
+/// Keep the last run of each day, in order, scaling bars to the largest day
+pub fn daily_usage(runs: impl IntoIterator<Item = (NaiveDateTime, i64)>) -> Vec<UsageHistoryPoint> {
+    let mut points: Vec<UsageHistoryPoint> = Vec::new();
+    for (collected, bytes) in runs {
+        let day = collected.date();
+        match points.last_mut() {
+            Some(point) if point.day == day => point.bytes = bytes,
+            _ => points.push(UsageHistoryPoint {
+                day,
+                bytes,
+                bar_percent: 0,
+            }),
+        }
+    }
+    let max = points.iter().map(|point| point.bytes).max().unwrap_or(0);
+    if max > 0 {
+        for point in &mut points {
+            point.bar_percent = point.bytes * 100 / max;
+        }
+    }
+    points
+}
+
+// Vacation functions
+pub fn get_vacation(pool: &DbPool, user_id: &str) -> Result<Option<Vacation>, Error> {
+    let mut conn = pool.get().unwrap();
+    vacation::table
+        .find(user_id)
+        .select(Vacation::as_select())
+        .first::<Vacation>(&mut conn)
+        .optional()
+}
+
+pub fn get_vacations(pool: &DbPool) -> Result<Vec<Vacation>, Error> {
+    let mut conn = pool.get().unwrap();
+    vacation::table
+        .select(Vacation::as_select())
+        .order(vacation::user_id.asc())
+        .load::<Vacation>(&mut conn)
+}
+
+/// Create or replace the vacation of `user_id`
+pub fn save_vacation(
+    pool: &DbPool,
+    user_id: &str,
+    vacation_data: VacationForm,
+    audit: &AuditContext,
+) -> Result<Vacation, Error> {
+    let mut conn = pool.get().unwrap();
+    let now = Utc::now().naive_utc();
+
+    conn.transaction(|conn| {
+        let before = vacation::table
+            .find(user_id)
+            .select(Vacation::as_select())
+            .first::<Vacation>(conn)
+            .optional()?;
+
+        let values = (
+            vacation::subject.eq(vacation_data.subject),
+            vacation::body.eq(vacation_data.body),
+            vacation::active_from.eq(vacation_data.active_from),
+            vacation::active_until.eq(vacation_data.active_until),
+            vacation::reply_interval_days.eq(vacation_data.reply_interval_days),
+            vacation::enabled.eq(vacation_data.enabled),
+            vacation::modified.eq(now),
+        );
+        if before.is_some() {
+            diesel::update(vacation::table.find(user_id))
+                .set(values)
+                .execute(conn)?;
+        } else {
+            diesel::insert_into(vacation::table)
+                .values((
+                    vacation::user_id.eq(user_id),
+                    values,
+                    vacation::created.eq(now),
+                ))
+                .execute(conn)?;
+        }
+
+        let after = vacation::table
+            .find(user_id)
+            .select(Vacation::as_select())
+            .first::<Vacation>(conn)?;
+
+        let action = if before.is_some() {
+            AuditAction::Update
+        } else {
+            AuditAction::Create
+        };
+        record_audit(conn, audit, action, before.as_ref(), Some(&after))?;
+        Ok(after)
+    })
+}
+
+pub fn delete_vacation(pool: &DbPool, user_id: &str, audit: &AuditContext) -> Result<usize, Error> {
+    let mut conn = pool.get().unwrap();
+    conn.transaction(|conn| {
+        let Some(before) = vacation::table
+            .find(user_id)
+            .select(Vacation::as_select())
+            .first::<Vacation>(conn)
+            .optional()?
+        else {
+            return Ok(0);
+        };
+
+        let deleted = diesel::delete(vacation::table.find(user_id)).execute(conn)?;
+        record_audit(conn, audit, AuditAction::Delete, Some(&before), None)?;
+        Ok(deleted)
+    })
+}
+
+/// Add `transport_address` to the alias on `user_id` while `active`, remove it otherwise
+///
+/// See [`crate::vacation::alias_change`]. Returns whether the alias changed.
+pub fn sync_vacation_alias(
+    pool: &DbPool,
+    user_id: &str,
+    transport_address: &str,
+    active: bool,
+    audit: &AuditContext,
+) -> Result<bool, Error> {
+    use crate::vacation::AliasChange;
+    let mut conn = pool.get().unwrap();
+
+    conn.transaction(|conn| {
+        let before = aliases::table
+            .filter(aliases::mail.eq(user_id))
+            .select(Alias::as_select())
+            .first::<Alias>(conn)
+            .optional()?;
+        let change = crate::vacation::alias_change(
+            user_id,
+            before.as_ref().map(|alias| alias.destination.as_str()),
+            transport_address,
+            active,
+        );
+        let now = Utc::now().naive_utc();
+
+        match (change, before) {
+            (Some(AliasChange::Create(destination)), None) => {
+                diesel::insert_into(aliases::table)
+                    .values((
+                        aliases::mail.eq(user_id.to_lowercase()),
+                        aliases::destination.eq(destination),
+                        aliases::enabled.eq(true),
+                        aliases::created.eq(now),
+                        aliases::modified.eq(now),
+                    ))
+                    .execute(conn)?;
+                let created = aliases::table
+                    .order(aliases::pkid.desc())
+                    .select(Alias::as_select())
+                    .first::<Alias>(conn)?;
+                record_audit(conn, audit, AuditAction::Create, None, Some(&created))?;
+            }
+            (Some(AliasChange::Update(destination)), Some(before)) => {
+                diesel::update(aliases::table.find(before.pkid))
+                    .set((
+                        aliases::destination.eq(destination),
+                        aliases::modified.eq(now),
+                    ))
+                    .execute(conn)?;
+                let after = aliases::table
+                    .find(before.pkid)
+                    .select(Alias::as_select())
+                    .first::<Alias>(conn)?;
+                record_audit(
+                    conn,
+                    audit,
+                    AuditAction::Update,
+                    Some(&before),
+                    Some(&after),
+                )?;
+            }
+            (Some(AliasChange::Delete), Some(before)) => {
+                diesel::delete(aliases::table.find(before.pkid)).execute(conn)?;
+                record_audit(conn, audit, AuditAction::Delete, Some(&before), None)?;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })
+}
+
+// Backup functions
+pub fn get_backups(pool: &DbPool) -> Result<Vec<Backup>, Error> {
+    let mut conn = pool.get().unwrap();
+    backups::table
+        .select(Backup::as_select())
+        .order(backups::domain.asc())
         .load::<Backup>(&mut conn)
 }
 
@@ -702,64 +1718,127 @@ pub fn get_backup_by_name(pool: &DbPool, backup_name: &str) -> Result<Backup, Er
         .first::<Backup>(&mut conn)
 }
 
-pub fn create_backup(pool: &DbPool, new_backup: NewBackup) -> Result<Backup, Error> {
+pub fn create_backup(
+    pool: &DbPool,
+    new_backup: NewBackup,
+    audit: &AuditContext,
+) -> Result<Backup, Error> {
     let mut conn = pool.get().unwrap();
     let now = Utc::now().naive_utc();
 
-    diesel::insert_into(backups::table)
-        .values((
-            backups::domain.eq(new_backup.domain),
-            backups::transport.eq(new_backup.transport.clone()),
-            backups::enabled.eq(new_backup.enabled),
-            backups::created.eq(now),
-            backups::modified.eq(now),
-        ))
-        .execute(&mut conn)?;
+    conn.transaction(|conn| {
+        diesel::insert_into(backups::table)
+            .values((
+                backups::domain.eq(new_backup.domain),
+                backups::transport.eq(new_backup.transport.clone()),
+                backups::enabled.eq(new_backup.enabled),
+                backups::created.eq(now),
+                backups::modified.eq(now),
+            ))
+            .execute(conn)?;
 
-    backups::table
-        .order(backups::pkid.desc())
-        .select(Backup::as_select())
-        .first::<Backup>(&mut conn)
+        let backup = backups::table
+            .order(backups::pkid.desc())
+            .select(Backup::as_select())
+            .first::<Backup>(conn)?;
+
+        record_audit(conn, audit, AuditAction::Create, None, Some(&backup))?;
+        Ok(backup)
+    })
 }
 
 pub fn update_backup(
     pool: &DbPool,
     backup_id: i32,
     backup_data: BackupForm,
+    audit: &AuditContext,
 ) -> Result<Backup, Error> {
     let mut conn = pool.get().unwrap();
-    diesel::update(backups::table.find(backup_id))
-        .set((
-            backups::domain.eq(backup_data.domain),
-            backups::transport.eq(backup_data.transport.clone()),
-            backups::enabled.eq(backup_data.enabled),
-            backups::modified.eq(Utc::now().naive_utc()),
-        ))
-        .execute(&mut conn)?;
+    conn.transaction(|conn| {
+        let before = backups::table
+            .find(backup_id)
+            .select(Backup::as_select())
+            .first::<Backup>(conn)?;
 
-    get_backup(pool, backup_id)
+        diesel::update(backups::table.find(backup_id))
+            .set((
+                backups::domain.eq(backup_data.domain),
+                backups::transport.eq(backup_data.transport.clone()),
+                backups::enabled.eq(backup_data.enabled),
+                backups::modified.eq(Utc::now().naive_utc()),
+            ))
+            .execute(conn)?;
+
+        let after = backups::table
+            .find(backup_id)
+            .select(Backup::as_select())
+            .first::<Backup>(conn)?;
+
+        record_audit(
+            conn,
+            audit,
+            AuditAction::Update,
+            Some(&before),
+            Some(&after),
+        )?;
+        Ok(after)
+    })
 }
 
-pub fn delete_backup(pool: &DbPool, backup_id: i32) -> Result<usize, Error> {
+pub fn delete_backup(pool: &DbPool, backup_id: i32, audit: &AuditContext) -> Result<usize, Error> {
     let mut conn = pool.get().unwrap();
-    diesel::delete(backups::table.find(backup_id)).execute(&mut conn)
+    conn.transaction(|conn| {
+        let Some(before) = backups::table
+            .find(backup_id)
+            .select(Backup::as_select())
+            .first::<Backup>(conn)
+            .optional()?
+        else {
+            return Ok(0);
+        };
+
+        let deleted = diesel::delete(backups::table.find(backup_id)).execute(conn)?;
+        record_audit(conn, audit, AuditAction::Delete, Some(&before), None)?;
+        Ok(deleted)
+    })
 }
 
-pub fn toggle_backup_enabled(pool: &DbPool, backup_id: i32) -> Result<Backup, Error> {
+pub fn toggle_backup_enabled(
+    pool: &DbPool,
+    backup_id: i32,
+    audit: &AuditContext,
+) -> Result<Backup, Error> {
     let mut conn = pool.get().unwrap();
 
-    // First get the current backup to check its enabled status
-    let current_backup = get_backup(pool, backup_id)?;
-    let new_enabled_status = !current_backup.enabled;
-
-    diesel::update(backups::table.find(backup_id))
-        .set((
-            backups::enabled.eq(new_enabled_status),
-            backups::modified.eq(Utc::now().naive_utc()),
-        ))
-        .execute(&mut conn)?;
+    conn.transaction(|conn| {
+        // First get the current backup to check its enabled status
+        let before = backups::table
+            .find(backup_id)
+            .select(Backup::as_select())
+            .first::<Backup>(conn)?;
+        let new_enabled_status = !before.enabled;
 
-    get_backup(pool, backup_id)
+        diesel::update(backups::table.find(backup_id))
+            .set((
+                backups::enabled.eq(new_enabled_status),
+                backups::modified.eq(Utc::now().naive_utc()),
+            ))
+            .execute(conn)?;
+
+        let after = backups::table
+            .find(backup_id)
+            .select(Backup::as_select())
+            .first::<Backup>(conn)?;
+
+        record_audit(
+            conn,
+            audit,
+            AuditAction::Toggle,
+            Some(&before),
+            Some(&after),
+        )?;
+        Ok(after)
+    })
 }
 
 // Relay database functions
@@ -787,8 +1866,21 @@ pub fn get_relay_by_recipient(pool: &DbPool, recipient: &str) -> Result<Relay, E
         .first::<Relay>(&mut conn)
 }
 
-pub fn create_relay(pool: &DbPool, relay_data: RelayForm) -> Result<Relay, Error> {
+pub fn create_relay(
+    pool: &DbPool,
+    relay_data: RelayForm,
+    audit: &AuditContext,
+) -> Result<Relay, Error> {
     let mut conn = pool.get().unwrap();
+    conn.transaction(|conn| insert_relay(conn, relay_data, audit))
+}
+
+/// Insert a relay inside the caller's transaction
+fn insert_relay(
+    conn: &mut MysqlConnection,
+    relay_data: RelayForm,
+    audit: &AuditContext,
+) -> Result<Relay, Error> {
     let now = Utc::now().naive_utc();
 
     let new_relay = NewRelay {
@@ -805,16 +1897,39 @@ pub fn create_relay(pool: &DbPool, relay_data: RelayForm) -> Result<Relay, Error
             relays::created.eq(now),
             relays::modified.eq(now),
         ))
-        .execute(&mut conn)?;
+        .execute(conn)?;
 
-    relays::table
+    let relay = relays::table
         .order(relays::pkid.desc())
         .select(Relay::as_select())
-        .first::<Relay>(&mut conn)
+        .first::<Relay>(conn)?;
+
+    record_audit(conn, audit, AuditAction::Create, None, Some(&relay))?;
+    Ok(relay)
 }
 
-pub fn update_relay(pool: &DbPool, relay_id: i32, relay_data: RelayForm) -> Result<Relay, Error> {
+pub fn update_relay(
+    pool: &DbPool,
+    relay_id: i32,
+    relay_data: RelayForm,
+    audit: &AuditContext,
+) -> Result<Relay, Error> {
     let mut conn = pool.get().unwrap();
+    conn.transaction(|conn| update_relay_row(conn, relay_id, relay_data, audit))
+}
+
+/// Update a relay inside the caller's transaction
+fn update_relay_row(
+    conn: &mut MysqlConnection,
+    relay_id: i32,
+    relay_data: RelayForm,
+    audit: &AuditContext,
+) -> Result<Relay, Error> {
+    let before = relays::table
+        .find(relay_id)
+        .select(Relay::as_select())
+        .first::<Relay>(conn)?;
+
     diesel::update(relays::table.find(relay_id))
         .set((
             relays::recipient.eq(relay_data.recipient),
@@ -822,122 +1937,1100 @@ pub fn update_relay(pool: &DbPool, relay_id: i32, relay_data: RelayForm) -> Resu
             relays::enabled.eq(relay_data.enabled),
             relays::modified.eq(Utc::now().naive_utc()),
         ))
-        .execute(&mut conn)?;
+        .execute(conn)?;
 
-    get_relay(pool, relay_id)
+    let after = relays::table
+        .find(relay_id)
+        .select(Relay::as_select())
+        .first::<Relay>(conn)?;
+
+    record_audit(
+        conn,
+        audit,
+        AuditAction::Update,
+        Some(&before),
+        Some(&after),
+    )?;
+    Ok(after)
 }
 
-pub fn delete_relay(pool: &DbPool, relay_id: i32) -> Result<usize, Error> {
+pub fn delete_relay(pool: &DbPool, relay_id: i32, audit: &AuditContext) -> Result<usize, Error> {
     let mut conn = pool.get().unwrap();
-    diesel::delete(relays::table.find(relay_id)).execute(&mut conn)
+    conn.transaction(|conn| {
+        let Some(before) = relays::table
+            .find(relay_id)
+            .select(Relay::as_select())
+            .first::<Relay>(conn)
+            .optional()?
+        else {
+            return Ok(0);
+        };
+
+        let deleted = diesel::delete(relays::table.find(relay_id)).execute(conn)?;
+        record_audit(conn, audit, AuditAction::Delete, Some(&before), None)?;
+        Ok(deleted)
+    })
 }
 
-pub fn toggle_relay_enabled(pool: &DbPool, relay_id: i32) -> Result<Relay, Error> {
+pub fn toggle_relay_enabled(
+    pool: &DbPool,
+    relay_id: i32,
+    audit: &AuditContext,
+) -> Result<Relay, Error> {
     let mut conn = pool.get().unwrap();
 
-    // Get current relay
-    let current_relay = get_relay(pool, relay_id)?;
+    conn.transaction(|conn| {
+        // Get current relay
+        let before = relays::table
+            .find(relay_id)
+            .select(Relay::as_select())
+            .first::<Relay>(conn)?;
 
-    // Toggle enabled status
-    diesel::update(relays::table.find(relay_id))
+        // Toggle enabled status
+        diesel::update(relays::table.find(relay_id))
+            .set((
+                relays::enabled.eq(!before.enabled),
+                relays::modified.eq(Utc::now().naive_utc()),
+            ))
+            .execute(conn)?;
+
+        let after = relays::table
+            .find(relay_id)
+            .select(Relay::as_select())
+            .first::<Relay>(conn)?;
+
+        record_audit(
+            conn,
+            audit,
+            AuditAction::Toggle,
+            Some(&before),
+            Some(&after),
+        )?;
+        Ok(after)
+    })
+}
+
+// Alias domain functions
+pub fn get_alias_domains(pool: &DbPool) -> Result<Vec<AliasDomain>, Error> {
+    let mut conn = pool.get().unwrap();
+    alias_domains::table
+        .select(AliasDomain::as_select())
+        .order(alias_domains::alias_domain.asc())
+        .load::<AliasDomain>(&mut conn)
+}
+
+pub fn get_alias_domain_by_id(pool: &DbPool, alias_domain_id: i32) -> Result<AliasDomain, Error> {
+    let mut conn = pool.get().unwrap();
+    alias_domains::table
+        .find(alias_domain_id)
+        .select(AliasDomain::as_select())
+        .first::<AliasDomain>(&mut conn)
+}
+
+pub fn get_alias_domain_by_name(pool: &DbPool, alias_domain: &str) -> Result<AliasDomain, Error> {
+    let mut conn = pool.get().unwrap();
+    alias_domains::table
+        .filter(alias_domains::alias_domain.eq(alias_domain))
+        .select(AliasDomain::as_select())
+        .first::<AliasDomain>(&mut conn)
+}
+
+/// Alias domains that deliver to `target_domain`
+pub fn get_alias_domains_for_target(
+    pool: &DbPool,
+    target_domain: &str,
+) -> Result<Vec<AliasDomain>, Error> {
+    let mut conn = pool.get().unwrap();
+    alias_domains::table
+        .filter(alias_domains::target_domain.eq(target_domain))
+        .select(AliasDomain::as_select())
+        .order(alias_domains::alias_domain.asc())
+        .load::<AliasDomain>(&mut conn)
+}
+
+/// Check an alias domain against the database, returning the translation key of the problem
+///
+/// The target has to be a domain managed here, and the alias domain must not
+/// already be a domain, backup domain or another alias domain. `alias_domain_id`
+/// is the entry being edited, which may keep its own name.
+pub fn alias_domain_conflict(
+    pool: &DbPool,
+    form: &AliasDomainForm,
+    alias_domain_id: Option<i32>,
+) -> Result<Option<&'static str>, Error> {
+    let mut conn = pool.get().unwrap();
+
+    let target_exists: i64 = domains::table
+        .filter(domains::domain.eq(&form.target_domain))
+        .count()
+        .get_result(&mut conn)?;
+    if target_exists == 0 {
+        return Ok(Some("alias-domains-error-unknown-target"));
+    }
+
+    let domain_exists: i64 = domains::table
+        .filter(domains::domain.eq(&form.alias_domain))
+        .count()
+        .get_result(&mut conn)?;
+    let backup_exists: i64 = backups::table
+        .filter(backups::domain.eq(&form.alias_domain))
+        .count()
+        .get_result(&mut conn)?;
+    if domain_exists > 0 || backup_exists > 0 {
+        return Ok(Some("alias-domains-error-domain-exists"));
+    }
+
+    let existing = alias_domains::table
+        .filter(alias_domains::alias_domain.eq(&form.alias_domain))
+        .select(alias_domains::pkid)
+        .first::<i32>(&mut conn)
+        .optional()?;
+    if existing.is_some_and(|pkid| Some(pkid) != alias_domain_id) {
+        return Ok(Some("alias-domains-error-exists"));
+    }
+
+    Ok(None)
+}
+
+pub fn create_alias_domain(
+    pool: &DbPool,
+    alias_domain_data: AliasDomainForm,
+    audit: &AuditContext,
+) -> Result<AliasDomain, Error> {
+    let mut conn = pool.get().unwrap();
+    let now = Utc::now().naive_utc();
+
+    let new_alias_domain = NewAliasDomain {
+        alias_domain: alias_domain_data.alias_domain,
+        target_domain: alias_domain_data.target_domain,
+        enabled: alias_domain_data.enabled,
+    };
+
+    conn.transaction(|conn| {
+        diesel::insert_into(alias_domains::table)
+            .values((
+                alias_domains::alias_domain.eq(new_alias_domain.alias_domain),
+                alias_domains::target_domain.eq(new_alias_domain.target_domain),
+                alias_domains::enabled.eq(new_alias_domain.enabled),
+                alias_domains::created.eq(now),
+                alias_domains::modified.eq(now),
+            ))
+            .execute(conn)?;
+
+        let created = alias_domains::table
+            .order(alias_domains::pkid.desc())
+            .select(AliasDomain::as_select())
+            .first::<AliasDomain>(conn)?;
+
+        record_audit(conn, audit, AuditAction::Create, None, Some(&created))?;
+        Ok(created)
+    })
+}
+
+pub fn update_alias_domain(
+    pool: &DbPool,
+    alias_domain_id: i32,
+    alias_domain_data: AliasDomainForm,
+    audit: &AuditContext,
+) -> Result<AliasDomain, Error> {
+    let mut conn = pool.get().unwrap();
+    conn.transaction(|conn| {
+        let before = alias_domains::table
+            .find(alias_domain_id)
+            .select(AliasDomain::as_select())
+            .first::<AliasDomain>(conn)?;
+
+        diesel::update(alias_domains::table.find(alias_domain_id))
+            .set((
+                alias_domains::alias_domain.eq(alias_domain_data.alias_domain),
+                alias_domains::target_domain.eq(alias_domain_data.target_domain),
+                alias_domains::enabled.eq(alias_domain_data.enabled),
+                alias_domains::modified.eq(Utc::now().naive_utc()),
+            ))
+            .execute(conn)?;
+
+        let after = alias_domains::table
+            .find(alias_domain_id)
+            .select(AliasDomain::as_select())
+            .first::<AliasDomain>(conn)?;
+
+        record_audit(
+            conn,
+            audit,
+            AuditAction::Update,
+            Some(&before),
+            Some(&after),
+        )?;
+        Ok(after)
+    })
+}
+
+pub fn delete_alias_domain(
+    pool: &DbPool,
+    alias_domain_id: i32,
+    audit: &AuditContext,
+) -> Result<usize, Error> {
+    let mut conn = pool.get().unwrap();
+    conn.transaction(|conn| {
+        let Some(before) = alias_domains::table
+            .find(alias_domain_id)
+            .select(AliasDomain::as_select())
+            .first::<AliasDomain>(conn)
+            .optional()?
+        else {
+            return Ok(0);
+        };
+
+        let deleted = diesel::delete(alias_domains::table.find(alias_domain_id)).execute(conn)?;
+        record_audit(conn, audit, AuditAction::Delete, Some(&before), None)?;
+        Ok(deleted)
+    })
+}
+
+pub fn toggle_alias_domain_enabled(
+    pool: &DbPool,
+    alias_domain_id: i32,
+    audit: &AuditContext,
+) -> Result<AliasDomain, Error> {
+    let mut conn = pool.get().unwrap();
+
+    conn.transaction(|conn| {
+        let before = alias_domains::table
+            .find(alias_domain_id)
+            .select(AliasDomain::as_select())
+            .first::<AliasDomain>(conn)?;
+
+        diesel::update(alias_domains::table.find(alias_domain_id))
+            .set((
+                alias_domains::enabled.eq(!before.enabled),
+                alias_domains::modified.eq(Utc::now().naive_utc()),
+            ))
+            .execute(conn)?;
+
+        let after = alias_domains::table
+            .find(alias_domain_id)
+            .select(AliasDomain::as_select())
+            .first::<AliasDomain>(conn)?;
+
+        record_audit(
+            conn,
+            audit,
+            AuditAction::Toggle,
+            Some(&before),
+            Some(&after),
+        )?;
+        Ok(after)
+    })
+}
+
+// Relocated functions
+pub fn get_relocated(pool: &DbPool) -> Result<Vec<Relocated>, Error> {
+    let mut conn = pool.get().unwrap();
+    relocated::table
+        .select(Relocated::as_select())
+        .order(relocated::old_address.asc())
+        .load::<Relocated>(&mut conn)
+}
+
+pub fn get_relocated_by_id(pool: &DbPool, relocated_id: i32) -> Result<Relocated, Error> {
+    let mut conn = pool.get().unwrap();
+    relocated::table
+        .find(relocated_id)
+        .select(Relocated::as_select())
+        .first::<Relocated>(&mut conn)
+}
+
+pub fn get_relocated_by_old_address(pool: &DbPool, old_address: &str) -> Result<Relocated, Error> {
+    let mut conn = pool.get().unwrap();
+    relocated::table
+        .filter(relocated::old_address.eq(old_address))
+        .select(Relocated::as_select())
+        .first::<Relocated>(&mut conn)
+}
+
+pub fn create_relocated(
+    pool: &DbPool,
+    relocated_data: RelocatedForm,
+    audit: &AuditContext,
+) -> Result<Relocated, Error> {
+    let mut conn = pool.get().unwrap();
+    conn.transaction(|conn| insert_relocated(conn, relocated_data, audit))
+}
+
+/// Insert a relocated entry inside the caller's transaction
+fn insert_relocated(
+    conn: &mut MysqlConnection,
+    relocated_data: RelocatedForm,
+    audit: &AuditContext,
+) -> Result<Relocated, Error> {
+    let now = Utc::now().naive_utc();
+
+    let new_relocated = NewRelocated {
+        old_address: relocated_data.old_address,
+        new_address: relocated_data.new_address,
+        enabled: relocated_data.enabled,
+        valid_from: relocated_data.valid_from,
+        valid_until: relocated_data.valid_until,
+    };
+
+    diesel::insert_into(relocated::table)
+        .values((
+            relocated::old_address.eq(new_relocated.old_address),
+            relocated::new_address.eq(new_relocated.new_address),
+            relocated::enabled.eq(new_relocated.enabled),
+            relocated::valid_from.eq(new_relocated.valid_from),
+            relocated::valid_until.eq(new_relocated.valid_until),
+            relocated::created.eq(now),
+            relocated::modified.eq(now),
+        ))
+        .execute(conn)?;
+
+    let created = relocated::table
+        .order(relocated::pkid.desc())
+        .select(Relocated::as_select())
+        .first::<Relocated>(conn)?;
+
+    record_audit(conn, audit, AuditAction::Create, None, Some(&created))?;
+    Ok(created)
+}
+
+pub fn update_relocated(
+    pool: &DbPool,
+    relocated_id: i32,
+    relocated_data: RelocatedForm,
+    audit: &AuditContext,
+) -> Result<Relocated, Error> {
+    let mut conn = pool.get().unwrap();
+    conn.transaction(|conn| update_relocated_row(conn, relocated_id, relocated_data, audit))
+}
+
+/// Update a relocated entry inside the caller's transaction
+fn update_relocated_row(
+    conn: &mut MysqlConnection,
+    relocated_id: i32,
+    relocated_data: RelocatedForm,
+    audit: &AuditContext,
+) -> Result<Relocated, Error> {
+    let before = relocated::table
+        .find(relocated_id)
+        .select(Relocated::as_select())
+        .first::<Relocated>(conn)?;
+
+    diesel::update(relocated::table.find(relocated_id))
         .set((
-            relays::enabled.eq(!current_relay.enabled),
-            relays::modified.eq(Utc::now().naive_utc()),
+            relocated::old_address.eq(relocated_data.old_address),
+            relocated::new_address.eq(relocated_data.new_address),
+            relocated::enabled.eq(relocated_data.enabled),
+            relocated::valid_from.eq(relocated_data.valid_from),
+            relocated::valid_until.eq(relocated_data.valid_until),
+            relocated::modified.eq(Utc::now().naive_utc()),
+        ))
+        .execute(conn)?;
+
+    let after = relocated::table
+        .find(relocated_id)
+        .select(Relocated::as_select())
+        .first::<Relocated>(conn)?;
+
+    record_audit(
+        conn,
+        audit,
+        AuditAction::Update,
+        Some(&before),
+        Some(&after),
+    )?;
+    Ok(after)
+}
+
+pub fn delete_relocated(
+    pool: &DbPool,
+    relocated_id: i32,
+    audit: &AuditContext,
+) -> Result<usize, Error> {
+    let mut conn = pool.get().unwrap();
+    conn.transaction(|conn| {
+        let Some(before) = relocated::table
+            .find(relocated_id)
+            .select(Relocated::as_select())
+            .first::<Relocated>(conn)
+            .optional()?
+        else {
+            return Ok(0);
+        };
+
+        let deleted = diesel::delete(relocated::table.find(relocated_id)).execute(conn)?;
+        record_audit(conn, audit, AuditAction::Delete, Some(&before), None)?;
+        Ok(deleted)
+    })
+}
+
+/// Relocated entries with a validity window, for the scheduler
+pub fn get_scheduled_relocated(pool: &DbPool) -> Result<Vec<Relocated>, Error> {
+    let mut conn = pool.get().unwrap();
+    relocated::table
+        .filter(
+            relocated::valid_from
+                .is_not_null()
+                .or(relocated::valid_until.is_not_null()),
+        )
+        .select(Relocated::as_select())
+        .order(relocated::old_address.asc())
+        .load::<Relocated>(&mut conn)
+}
+
+/// Enable or disable a relocated entry, recording the change as a toggle
+pub fn set_relocated_enabled(
+    pool: &DbPool,
+    relocated_id: i32,
+    enabled: bool,
+    audit: &AuditContext,
+) -> Result<Relocated, Error> {
+    let mut conn = pool.get().unwrap();
+    conn.transaction(|conn| {
+        let before = relocated::table
+            .find(relocated_id)
+            .select(Relocated::as_select())
+            .first::<Relocated>(conn)?;
+
+        diesel::update(relocated::table.find(relocated_id))
+            .set((
+                relocated::enabled.eq(enabled),
+                relocated::modified.eq(Utc::now().naive_utc()),
+            ))
+            .execute(conn)?;
+
+        let after = relocated::table
+            .find(relocated_id)
+            .select(Relocated::as_select())
+            .first::<Relocated>(conn)?;
+
+        record_audit(
+            conn,
+            audit,
+            AuditAction::Toggle,
+            Some(&before),
+            Some(&after),
+        )?;
+        Ok(after)
+    })
+}
+
+pub fn toggle_relocated_enabled(
+    pool: &DbPool,
+    relocated_id: i32,
+    audit: &AuditContext,
+) -> Result<Relocated, Error> {
+    let mut conn = pool.get().unwrap();
+
+    conn.transaction(|conn| {
+        // Get current relocated
+        let before = relocated::table
+            .find(relocated_id)
+            .select(Relocated::as_select())
+            .first::<Relocated>(conn)?;
+
+        // Toggle enabled status
+        diesel::update(relocated::table.find(relocated_id))
+            .set((
+                relocated::enabled.eq(!before.enabled),
+                relocated::modified.eq(Utc::now().naive_utc()),
+            ))
+            .execute(conn)?;
+
+        let after = relocated::table
+            .find(relocated_id)
+            .select(Relocated::as_select())
+            .first::<Relocated>(conn)?;
+
+        record_audit(
+            conn,
+            audit,
+            AuditAction::Toggle,
+            Some(&before),
+            Some(&after),
+        )?;
+        Ok(after)
+    })
+}
+
+/// Write the creates and updates of an import file in one transaction
+///
+/// Either every write succeeds or none is kept; the failure names the line of
+/// the file whose write failed. Returns the number of records written.
+pub fn apply_import(
+    pool: &DbPool,
+    writes: Vec<(usize, crate::import::ImportWrite)>,
+    scheme: PasswordScheme,
+    audit: &AuditContext,
+) -> Result<usize, crate::import::ImportFailure> {
+    use crate::import::{ImportFailure, ImportWrite};
+
+    let mut conn = pool.get().unwrap();
+    conn.transaction(|conn| {
+        let count = writes.len();
+        for (line, write) in writes {
+            let result = match write {
+                ImportWrite::CreateDomain(form) => {
+                    let new_domain = NewDomain {
+                        domain: form.domain,
+                        transport: Some(form.transport),
+                        enabled: form.enabled,
+                        default_quota_bytes: form.default_quota_bytes,
+                        default_quota_messages: form.default_quota_messages,
+                        quota_cap_bytes: form.quota_cap_bytes,
+                    };
+                    insert_domain(conn, new_domain, audit).map(|_| ())
+                }
+                ImportWrite::UpdateDomain(id, form) => {
+                    update_domain_row(conn, id, form, audit).map(|_| ())
+                }
+                ImportWrite::CreateUser(form) => insert_user(conn, form, scheme, audit).map(|_| ()),
+                ImportWrite::UpdateUser(id, form) => {
+                    let new_password = form.password.clone();
+                    let user_id = form.id.clone();
+                    update_user_row(conn, id, form, audit).and_then(|_| {
+                        if new_password.is_empty() {
+                            Ok(())
+                        } else {
+                            write_user_password(conn, user_id, &new_password, scheme, false, audit)
+                        }
+                    })
+                }
+                ImportWrite::CreateAlias(form) => insert_alias(conn, form, audit).map(|_| ()),
+                ImportWrite::UpdateAlias(id, form) => {
+                    update_alias_row(conn, id, form, audit).map(|_| ())
+                }
+                ImportWrite::CreateRelay(form) => insert_relay(conn, form, audit).map(|_| ()),
+                ImportWrite::UpdateRelay(id, form) => {
+                    update_relay_row(conn, id, form, audit).map(|_| ())
+                }
+                ImportWrite::CreateRelocated(form) => {
+                    insert_relocated(conn, form, audit).map(|_| ())
+                }
+                ImportWrite::UpdateRelocated(id, form) => {
+                    update_relocated_row(conn, id, form, audit).map(|_| ())
+                }
+                ImportWrite::CreateClient(form) => insert_client(conn, form, audit).map(|_| ()),
+                ImportWrite::UpdateClient(id, form) => {
+                    update_client_row(conn, id, form, audit).map(|_| ())
+                }
+            };
+            result.map_err(|error| ImportFailure {
+                line: Some(line),
+                error,
+            })?;
+        }
+        Ok(count)
+    })
+}
+
+/// Bind a JSON value of a bundle record to the next placeholder
+fn bind_restore_value(
+    query: diesel::query_builder::BoxedSqlQuery<'static, Mysql, diesel::query_builder::SqlQuery>,
+    value: &serde_json::Value,
+) -> diesel::query_builder::BoxedSqlQuery<'static, Mysql, diesel::query_builder::SqlQuery> {
+    use diesel::sql_types::{BigInt, Nullable, Text};
+    use serde_json::Value;
+
+    match value {
+        Value::Bool(value) => query.bind::<Bool, _>(*value),
+        Value::Number(number) => match number.as_i64() {
+            Some(value) => query.bind::<BigInt, _>(value),
+            None => query.bind::<Text, _>(number.to_string()),
+        },
+        // Dates and times bind as ISO 8601 text, which MySQL converts
+        Value::String(value) => query.bind::<Text, _>(value.clone()),
+        _ => query.bind::<Nullable<Text>, _>(None::<String>),
+    }
+}
+
+/// Write the changes of a restore plan in one transaction, through the
+/// target's field map, returning the number of records written
+///
+/// Deletes go first, in reverse table order. Audit entries are keyed by the
+/// natural key, as restored records have no ID before they are written.
+pub fn apply_restore(
+    pool: &DbPool,
+    db_config: &DatabaseConfig,
+    plan: &crate::restore::RestorePlan,
+    audit: &AuditContext,
+) -> Result<usize, crate::restore::RestoreFailure> {
+    use crate::restore::{self, RestoreAction, RestoreFailure};
+
+    let (mut writes, upserts): (Vec<_>, Vec<_>) = plan
+        .writes()
+        .partition(|change| change.action == RestoreAction::Delete);
+    writes.reverse();
+    writes.extend(upserts);
+
+    let mut conn = pool.get().unwrap();
+    conn.transaction(|conn| {
+        let mut count = 0;
+        for change in writes {
+            let table = change.table;
+            let (sql, values) = match change.action {
+                RestoreAction::Create => {
+                    let after = change.after.clone().unwrap_or_default();
+                    let fields: Vec<&str> = table
+                        .fields()
+                        .iter()
+                        .copied()
+                        .filter(|field| after.contains_key(*field))
+                        .collect();
+                    let values = fields.iter().map(|field| after[*field].clone()).collect();
+                    (restore::insert_sql(table, &fields, db_config), values)
+                }
+                RestoreAction::Update => {
+                    let after = change.after.clone().unwrap_or_default();
+                    let fields: Vec<&str> = change.changes.iter().map(|c| c.field).collect();
+                    let mut values: Vec<serde_json::Value> =
+                        fields.iter().map(|field| after[*field].clone()).collect();
+                    values.push(serde_json::Value::String(change.key.clone()));
+                    (restore::update_sql(table, &fields, db_config), values)
+                }
+                RestoreAction::Delete => (
+                    restore::delete_sql(table, db_config),
+                    vec![serde_json::Value::String(change.key.clone())],
+                ),
+                RestoreAction::Unchanged => continue,
+            };
+
+            let query = values
+                .iter()
+                .fold(sql_query(sql).into_boxed(), bind_restore_value);
+            let action = match change.action {
+                RestoreAction::Create => AuditAction::Create,
+                RestoreAction::Delete => AuditAction::Delete,
+                _ => AuditAction::Update,
+            };
+            query
+                .execute(conn)
+                .and_then(|_| {
+                    let as_json = |record: &Option<restore::Record>| {
+                        record.clone().map(serde_json::Value::Object)
+                    };
+                    record_audit_entry(
+                        conn,
+                        audit,
+                        table.entity(),
+                        action,
+                        change.key.clone(),
+                        change.key.clone(),
+                        audit_diff(as_json(&change.before), as_json(&change.after)),
+                    )
+                })
+                .map_err(|error| RestoreFailure {
+                    record: Some((table, change.key.clone())),
+                    error,
+                })?;
+            count += 1;
+        }
+        Ok(count)
+    })
+}
+
+#[derive(QueryableByName)]
+struct ModifiedTime {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    record_key: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Timestamp>)]
+    modified: Option<NaiveDateTime>,
+}
+
+/// When each record of `table` was last modified, by lower-cased natural key
+pub fn get_modified_times(
+    pool: &DbPool,
+    db_config: &DatabaseConfig,
+    table: crate::restore::BundleTable,
+) -> Result<HashMap<String, NaiveDateTime>, Error> {
+    let mut conn = pool.get().unwrap();
+    let times: Vec<ModifiedTime> =
+        sql_query(crate::restore::modified_sql(table, db_config)).load(&mut conn)?;
+    Ok(times
+        .into_iter()
+        .filter_map(|time| Some((time.record_key.to_lowercase(), time.modified?)))
+        .collect())
+}
+
+// Legacy source functions
+
+#[derive(QueryableByName)]
+struct LegacyColumn {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    name: String,
+}
+
+#[derive(QueryableByName)]
+struct LegacyRecord {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    record: String,
+}
+
+/// Read `tables` of a PostfixAdmin or ISPmail database at `url`, leaving out
+/// those it lacks
+///
+/// The session is made read-only before anything is read. Each row is fetched
+/// as a JSON object, so tables of any column layout can be read.
+pub fn read_legacy_tables(
+    url: &str,
+    tables: &[&str],
+) -> Result<crate::legacy::Tables, crate::legacy::LegacyError> {
+    use crate::legacy::{LegacyError, Tables};
+
+    let query_error = |e: Error| LegacyError::Query(e.to_string());
+    let mut conn =
+        MysqlConnection::establish(url).map_err(|e| LegacyError::Connect(e.to_string()))?;
+    sql_query("SET SESSION TRANSACTION READ ONLY")
+        .execute(&mut conn)
+        .map_err(query_error)?;
+
+    let mut result = Tables::new();
+    for table in tables {
+        let columns: Vec<LegacyColumn> = sql_query(
+            "SELECT COLUMN_NAME AS name FROM information_schema.COLUMNS \
+             WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ? ORDER BY ORDINAL_POSITION",
+        )
+        .bind::<diesel::sql_types::Text, _>(*table)
+        .load(&mut conn)
+        .map_err(query_error)?;
+        if columns.is_empty() {
+            continue;
+        }
+
+        let pairs: Vec<String> = columns
+            .iter()
+            .map(|column| {
+                format!(
+                    "'{}', `{}`",
+                    column.name.replace('\'', "''"),
+                    column.name.replace('`', "``")
+                )
+            })
+            .collect();
+        let records: Vec<LegacyRecord> = sql_query(format!(
+            "SELECT CAST(JSON_OBJECT({}) AS CHAR) AS record FROM `{}`",
+            pairs.join(", "),
+            table.replace('`', "``")
         ))
-        .execute(&mut conn)?;
+        .load(&mut conn)
+        .map_err(query_error)?;
+
+        let mut rows = Vec::new();
+        for record in records {
+            let object: serde_json::Map<String, serde_json::Value> =
+                serde_json::from_str(&record.record)
+                    .map_err(|e| LegacyError::Query(e.to_string()))?;
+            let row = object
+                .into_iter()
+                .map(|(column, value)| {
+                    let value = match value {
+                        serde_json::Value::Null => None,
+                        serde_json::Value::String(text) => Some(text),
+                        other => Some(other.to_string()),
+                    };
+                    (column.to_lowercase(), value)
+                })
+                .collect();
+            rows.push(row);
+        }
+        result.insert(table.to_lowercase(), rows);
+    }
+    Ok(result)
+}
+
+// Audit log functions
+
+/// Who made a change and against which database, recorded with every mutation
+#[derive(Debug, Clone)]
+pub struct AuditContext {
+    pub admin: String,
+    pub database_id: String,
+}
+
+impl AuditContext {
+    pub fn new(admin: impl Into<String>, database_id: impl Into<String>) -> Self {
+        Self {
+            admin: admin.into(),
+            database_id: database_id.into(),
+        }
+    }
+}
+
+/// The addresses an admin may see and change
+///
+/// Delegated domain admins are limited to the users and aliases under their
+/// domains; everyone else sees the whole database.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum AddressScope {
+    #[default]
+    All,
+    Domains(Vec<String>),
+}
+
+impl AddressScope {
+    /// Whether an address (or a catch-all `@domain`) falls under the scope
+    pub fn allows(&self, address: &str) -> bool {
+        match address.rsplit_once('@') {
+            Some((_, domain)) => self.allows_domain(domain),
+            None => matches!(self, AddressScope::All),
+        }
+    }
+
+    /// Whether a domain falls under the scope
+    pub fn allows_domain(&self, domain: &str) -> bool {
+        match self {
+            AddressScope::All => true,
+            AddressScope::Domains(domains) => {
+                domains.iter().any(|d| d.eq_ignore_ascii_case(domain))
+            }
+        }
+    }
+
+    /// LIKE patterns matching every address under the scope, or `None` for all
+    fn like_patterns(&self) -> Option<Vec<String>> {
+        match self {
+            AddressScope::All => None,
+            AddressScope::Domains(domains) => Some(
+                domains
+                    .iter()
+                    .map(|domain| {
+                        let escaped = domain
+                            .replace('\\', "\\\\")
+                            .replace('%', "\\%")
+                            .replace('_', "\\_");
+                        format!("%@{escaped}")
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
+
+type ScopeFilter<T> = Box<dyn BoxableExpression<T, Mysql, SqlType = Bool>>;
+
+/// Restrict users to the scope, or `None` when it covers everything
+fn user_scope_filter(scope: &AddressScope) -> Option<ScopeFilter<users::table>> {
+    scope.like_patterns().map(|patterns| {
+        patterns.into_iter().fold(
+            Box::new(false.into_sql::<Bool>()) as ScopeFilter<users::table>,
+            |filter, pattern| Box::new(filter.or(users::id.like(pattern))),
+        )
+    })
+}
+
+/// Restrict aliases to the scope, or `None` when it covers everything
+fn alias_scope_filter(scope: &AddressScope) -> Option<ScopeFilter<aliases::table>> {
+    scope.like_patterns().map(|patterns| {
+        patterns.into_iter().fold(
+            Box::new(false.into_sql::<Bool>()) as ScopeFilter<aliases::table>,
+            |filter, pattern| Box::new(filter.or(aliases::mail.like(pattern))),
+        )
+    })
+}
+
+/// Fields that change on every write and would only add noise to the diff
+const AUDIT_IGNORED_FIELDS: &[&str] = &["created", "modified", "created_at", "updated_at"];
+
+/// Fields whose values must never be written to the audit log
+const AUDIT_REDACTED_FIELDS: &[&str] = &["crypt"];
+
+/// Build a `{field: {old, new}}` JSON diff between two serialized records.
+/// Only changed fields are included; either side may be missing for creates and deletes.
+pub fn audit_diff(
+    before: Option<serde_json::Value>,
+    after: Option<serde_json::Value>,
+) -> serde_json::Value {
+    use serde_json::{Map, Value};
+
+    let as_object = |value: Option<Value>| match value {
+        Some(Value::Object(map)) => map,
+        _ => Map::new(),
+    };
+    let before = as_object(before);
+    let after = as_object(after);
 
-    get_relay(pool, relay_id)
-}
+    let mut fields: Vec<&String> = before.keys().chain(after.keys()).collect();
+    fields.sort();
+    fields.dedup();
 
-// Relocated functions
-pub fn get_relocated(pool: &DbPool) -> Result<Vec<Relocated>, Error> {
-    let mut conn = pool.get().unwrap();
-    relocated::table
-        .select(Relocated::as_select())
-        .order(relocated::old_address.asc())
-        .load::<Relocated>(&mut conn)
+    let mut diff = Map::new();
+    for field in fields {
+        if AUDIT_IGNORED_FIELDS.contains(&field.as_str()) {
+            continue;
+        }
+        let old = before.get(field).cloned().unwrap_or(Value::Null);
+        let new = after.get(field).cloned().unwrap_or(Value::Null);
+        if old == new {
+            continue;
+        }
+        let (old, new) = if AUDIT_REDACTED_FIELDS.contains(&field.as_str()) {
+            let redact = |v: Value| {
+                if v.is_null() {
+                    v
+                } else {
+                    Value::String("[redacted]".to_string())
+                }
+            };
+            (redact(old), redact(new))
+        } else {
+            (old, new)
+        };
+        diff.insert(field.clone(), serde_json::json!({ "old": old, "new": new }));
+    }
+
+    Value::Object(diff)
 }
 
-pub fn get_relocated_by_id(pool: &DbPool, relocated_id: i32) -> Result<Relocated, Error> {
-    let mut conn = pool.get().unwrap();
-    relocated::table
-        .find(relocated_id)
-        .select(Relocated::as_select())
-        .first::<Relocated>(&mut conn)
+/// Write an audit row on the given connection, so it commits or rolls back with the change
+fn record_audit<T: Audited>(
+    conn: &mut MysqlConnection,
+    audit: &AuditContext,
+    action: AuditAction,
+    before: Option<&T>,
+    after: Option<&T>,
+) -> Result<(), Error> {
+    let Some(subject) = after.or(before) else {
+        return Ok(());
+    };
+    let to_json = |record: Option<&T>| record.and_then(|r| serde_json::to_value(r).ok());
+    let changes = audit_diff(to_json(before), to_json(after));
+    record_audit_entry(
+        conn,
+        audit,
+        T::ENTITY,
+        action,
+        subject.audit_id(),
+        subject.audit_name(),
+        changes,
+    )
 }
 
-pub fn get_relocated_by_old_address(pool: &DbPool, old_address: &str) -> Result<Relocated, Error> {
-    let mut conn = pool.get().unwrap();
-    relocated::table
-        .filter(relocated::old_address.eq(old_address))
-        .select(Relocated::as_select())
-        .first::<Relocated>(&mut conn)
+/// Write an audit row for a change described by its JSON diff
+fn record_audit_entry(
+    conn: &mut MysqlConnection,
+    audit: &AuditContext,
+    entity: AuditEntity,
+    action: AuditAction,
+    entity_id: String,
+    entity_name: String,
+    changes: serde_json::Value,
+) -> Result<(), Error> {
+    let entry = NewAuditLogEntry {
+        created: Utc::now().naive_utc(),
+        admin: audit.admin.clone(),
+        database_id: audit.database_id.clone(),
+        entity_type: entity.as_str().to_string(),
+        entity_id,
+        entity_name,
+        action: action.as_str().to_string(),
+        changes: changes.to_string(),
+    };
+
+    diesel::insert_into(audit_log::table)
+        .values(&entry)
+        .execute(conn)?;
+    Ok(())
 }
 
-pub fn create_relocated(pool: &DbPool, relocated_data: RelocatedForm) -> Result<Relocated, Error> {
+pub fn get_audit_log_paginated(
+    pool: &DbPool,
+    filter: &AuditLogFilter,
+    page: i64,
+    per_page: i64,
+) -> Result<PaginatedResult<AuditLogEntry>, Error> {
     let mut conn = pool.get().unwrap();
-    let now = Utc::now().naive_utc();
 
-    let new_relocated = NewRelocated {
-        old_address: relocated_data.old_address,
-        new_address: relocated_data.new_address,
-        enabled: relocated_data.enabled,
+    let offset = (page - 1) * per_page;
+    let parse_date = |value: &Option<String>| {
+        value
+            .as_deref()
+            .filter(|v| !v.is_empty())
+            .and_then(|v| chrono::NaiveDate::parse_from_str(v, "%Y-%m-%d").ok())
+    };
+    let from = parse_date(&filter.from).and_then(|d| d.and_hms_opt(0, 0, 0));
+    // The "to" date is inclusive, so filter up to the start of the following day
+    let to = parse_date(&filter.to)
+        .and_then(|d| d.succ_opt())
+        .and_then(|d| d.and_hms_opt(0, 0, 0));
+    let admin = filter.admin.clone().filter(|v| !v.is_empty());
+    let entity_type = filter.entity_type.clone().filter(|v| !v.is_empty());
+
+    let build_query = || {
+        let mut query = audit_log::table.into_boxed();
+        if let Some(admin) = &admin {
+            query = query.filter(audit_log::admin.eq(admin.clone()));
+        }
+        if let Some(entity_type) = &entity_type {
+            query = query.filter(audit_log::entity_type.eq(entity_type.clone()));
+        }
+        if let Some(from) = from {
+            query = query.filter(audit_log::created.ge(from));
+        }
+        if let Some(to) = to {
+            query = query.filter(audit_log::created.lt(to));
+        }
+        query
     };
 
-    diesel::insert_into(relocated::table)
-        .values((
-            relocated::old_address.eq(new_relocated.old_address),
-            relocated::new_address.eq(new_relocated.new_address),
-            relocated::enabled.eq(new_relocated.enabled),
-            relocated::created.eq(now),
-            relocated::modified.eq(now),
-        ))
-        .execute(&mut conn)?;
+    // Get total count
+    let total_count: i64 = build_query().count().get_result(&mut conn)?;
 
-    relocated::table
-        .order(relocated::pkid.desc())
-        .select(Relocated::as_select())
-        .first::<Relocated>(&mut conn)
+    // Get paginated results
+    let entries = build_query()
+        .select(AuditLogEntry::as_select())
+        .order(audit_log::id.desc())
+        .limit(per_page)
+        .offset(offset)
+        .load::<AuditLogEntry>(&mut conn)?;
+
+    Ok(PaginatedResult::new(entries, total_count, page, per_page))
 }
 
-pub fn update_relocated(
+pub fn get_entity_audit_history(
     pool: &DbPool,
-    relocated_id: i32,
-    relocated_data: RelocatedForm,
-) -> Result<Relocated, Error> {
+    entity: AuditEntity,
+    entity_id: &str,
+    limit: i64,
+) -> Result<Vec<AuditLogEntry>, Error> {
     let mut conn = pool.get().unwrap();
-    diesel::update(relocated::table.find(relocated_id))
-        .set((
-            relocated::old_address.eq(relocated_data.old_address),
-            relocated::new_address.eq(relocated_data.new_address),
-            relocated::enabled.eq(relocated_data.enabled),
-            relocated::modified.eq(Utc::now().naive_utc()),
-        ))
-        .execute(&mut conn)?;
-
-    get_relocated_by_id(pool, relocated_id)
+    audit_log::table
+        .filter(audit_log::entity_type.eq(entity.as_str()))
+        .filter(audit_log::entity_id.eq(entity_id))
+        .select(AuditLogEntry::as_select())
+        .order(audit_log::id.desc())
+        .limit(limit)
+        .load::<AuditLogEntry>(&mut conn)
 }
 
-pub fn delete_relocated(pool: &DbPool, relocated_id: i32) -> Result<usize, Error> {
+pub fn get_audit_admins(pool: &DbPool) -> Result<Vec<String>, Error> {
     let mut conn = pool.get().unwrap();
-    diesel::delete(relocated::table.find(relocated_id)).execute(&mut conn)
+    audit_log::table
+        .select(audit_log::admin)
+        .distinct()
+        .order(audit_log::admin.asc())
+        .load::<String>(&mut conn)
 }
 
-pub fn toggle_relocated_enabled(pool: &DbPool, relocated_id: i32) -> Result<Relocated, Error> {
-    let mut conn = pool.get().unwrap();
-
-    // Get current relocated
-    let current_relocated = get_relocated_by_id(pool, relocated_id)?;
-
-    // Toggle enabled status
-    diesel::update(relocated::table.find(relocated_id))
-        .set((
-            relocated::enabled.eq(!current_relocated.enabled),
-            relocated::modified.eq(Utc::now().naive_utc()),
-        ))
-        .execute(&mut conn)?;
+// Weak password hash report functions
 
-    get_relocated_by_id(pool, relocated_id)
+/// Users whose stored hash should be replaced with one of `scheme`
+pub fn get_weak_password_hashes(
+    pool: &DbPool,
+    scheme: PasswordScheme,
+) -> Result<Vec<WeakPasswordHash>, Error> {
+    Ok(get_users(pool)?
+        .into_iter()
+        .filter(|user| password::needs_rehash(&user.crypt, scheme))
+        .map(|user| {
+            let info = password::identify(&user.crypt);
+            WeakPasswordHash {
+                user_id: user.id,
+                scheme: info.map(|info| info.scheme.to_string()),
+                prefixed: info.is_some_and(|info| info.prefixed),
+                enabled: user.enabled,
+            }
+        })
+        .collect())
 }
 
 // Catch-all report functions
@@ -985,18 +3078,9 @@ pub fn get_catch_all_report(pool: &DbPool) -> Result<Vec<CatchAllReport>, Error>
 }
 
 // Enhanced alias report functions
-pub fn get_alias_report(pool: &DbPool) -> Result<AliasReport, Error> {
+pub fn get_alias_report(pool: &DbPool, config: &Config) -> Result<AliasReport, Error> {
     let mut conn = pool.get().unwrap();
 
-    // Load configuration
-    let config = match crate::config::Config::load() {
-        Ok(config) => config,
-        Err(e) => {
-            tracing::warn!("Failed to load config, using defaults: {:?}", e);
-            crate::config::Config::default()
-        }
-    };
-
     // Get all domains
     let domains = get_domains(pool)?;
     let mut domains_with_catch_all = Vec::new();
@@ -1075,18 +3159,12 @@ pub fn get_alias_report(pool: &DbPool) -> Result<AliasReport, Error> {
 }
 
 // Matrix report functions
-pub fn get_domain_alias_matrix_report(pool: &DbPool) -> Result<DomainAliasMatrixReport, Error> {
+pub fn get_domain_alias_matrix_report(
+    pool: &DbPool,
+    config: &Config,
+) -> Result<DomainAliasMatrixReport, Error> {
     let mut conn = pool.get().unwrap();
 
-    // Load configuration
-    let config = match crate::config::Config::load() {
-        Ok(config) => config,
-        Err(e) => {
-            tracing::warn!("Failed to load config, using defaults: {:?}", e);
-            crate::config::Config::default()
-        }
-    };
-
     // Get all domains
     let domains = get_domains(pool)?;
     let mut matrix_rows = Vec::new();
@@ -1139,11 +3217,43 @@ pub fn get_domain_alias_matrix_report(pool: &DbPool) -> Result<DomainAliasMatrix
 
         matrix_rows.push(DomainAliasMatrixRow {
             domain: domain.domain,
+            alias_for: None,
             catch_all_status,
             required_aliases: required_matrix_items,
         });
     }
 
+    // Alias domains get their own rows; while enabled, their target's aliases count too
+    for alias_domain in get_alias_domains(pool)? {
+        let own = local_part_aliases(&mut conn, &alias_domain.alias_domain)?;
+        let target = if alias_domain.enabled {
+            local_part_aliases(&mut conn, &alias_domain.target_domain)?
+        } else {
+            std::collections::HashMap::new()
+        };
+        let status = |local_part: &str| {
+            matrix_status(&[
+                own.get(local_part).copied(),
+                target.get(local_part).copied(),
+            ])
+        };
+
+        let required_aliases = config
+            .get_required_aliases_for_domain(&alias_domain.alias_domain)
+            .iter()
+            .map(|alias| RequiredAliasMatrixItem {
+                alias: alias.clone(),
+                status: status(alias),
+            })
+            .collect();
+        matrix_rows.push(DomainAliasMatrixRow {
+            domain: alias_domain.alias_domain,
+            alias_for: Some(alias_domain.target_domain),
+            catch_all_status: status(""),
+            required_aliases,
+        });
+    }
+
     // Get the list of required aliases for the header
     let required_aliases_list = config.get_required_aliases_for_domain(""); // Get global required aliases
 
@@ -1153,22 +3263,46 @@ pub fn get_domain_alias_matrix_report(pool: &DbPool) -> Result<DomainAliasMatrix
     })
 }
 
+/// Whether each local part on `domain` has an alias and if it is enabled; `""` is the catch-all
+fn local_part_aliases(
+    conn: &mut MysqlConnection,
+    domain: &str,
+) -> Result<HashMap<String, bool>, Error> {
+    Ok(aliases::table
+        .filter(aliases::mail.like(format!("%@{domain}")))
+        .select((aliases::mail, aliases::enabled))
+        .load::<(String, bool)>(conn)?
+        .into_iter()
+        .filter_map(|(mail, enabled)| {
+            let (local_part, mail_domain) = mail.rsplit_once('@')?;
+            mail_domain
+                .eq_ignore_ascii_case(domain)
+                .then(|| (local_part.to_string(), enabled))
+        })
+        .collect())
+}
+
+/// The matrix status of an alias that any of several lookups can satisfy
+///
+/// Each entry is whether that lookup found an alias and if it is enabled.
+pub(crate) fn matrix_status(lookups: &[Option<bool>]) -> AliasStatus {
+    if lookups.contains(&Some(true)) {
+        AliasStatus::Present
+    } else if lookups.contains(&Some(false)) {
+        AliasStatus::Disabled
+    } else {
+        AliasStatus::Missing
+    }
+}
+
 // Get alias report for a specific domain
 pub fn get_domain_alias_report(
     pool: &DbPool,
+    config: &Config,
     domain_name: &str,
 ) -> Result<DomainAliasReport, Error> {
     let mut conn = pool.get().unwrap();
 
-    // Load configuration
-    let config = match crate::config::Config::load() {
-        Ok(config) => config,
-        Err(e) => {
-            tracing::warn!("Failed to load config, using defaults: {:?}", e);
-            crate::config::Config::default()
-        }
-    };
-
     // Check if this domain has a catch-all alias
     let catch_all_alias = aliases::table
         .filter(aliases::mail.eq(format!("@{domain_name}")))
@@ -1255,8 +3389,21 @@ pub fn get_client_by_name(pool: &DbPool, client_name: &str) -> Result<Client, Er
         .first::<Client>(&mut conn)
 }
 
-pub fn create_client(pool: &DbPool, client_data: ClientForm) -> Result<Client, Error> {
+pub fn create_client(
+    pool: &DbPool,
+    client_data: ClientForm,
+    audit: &AuditContext,
+) -> Result<Client, Error> {
     let mut conn = pool.get().unwrap();
+    conn.transaction(|conn| insert_client(conn, client_data, audit))
+}
+
+/// Insert a client inside the caller's transaction
+fn insert_client(
+    conn: &mut MysqlConnection,
+    client_data: ClientForm,
+    audit: &AuditContext,
+) -> Result<Client, Error> {
     let now = Utc::now().naive_utc();
 
     diesel::insert_into(clients::table)
@@ -1267,20 +3414,38 @@ pub fn create_client(pool: &DbPool, client_data: ClientForm) -> Result<Client, E
             clients::created_at.eq(now),
             clients::updated_at.eq(now),
         ))
-        .execute(&mut conn)?;
+        .execute(conn)?;
 
-    clients::table
+    let client = clients::table
         .order(clients::id.desc())
         .select(Client::as_select())
-        .first::<Client>(&mut conn)
+        .first::<Client>(conn)?;
+
+    record_audit(conn, audit, AuditAction::Create, None, Some(&client))?;
+    Ok(client)
 }
 
 pub fn update_client(
     pool: &DbPool,
     client_id: i32,
     client_data: ClientForm,
+    audit: &AuditContext,
 ) -> Result<Client, Error> {
     let mut conn = pool.get().unwrap();
+    conn.transaction(|conn| update_client_row(conn, client_id, client_data, audit))
+}
+
+/// Update a client inside the caller's transaction
+fn update_client_row(
+    conn: &mut MysqlConnection,
+    client_id: i32,
+    client_data: ClientForm,
+    audit: &AuditContext,
+) -> Result<Client, Error> {
+    let before = clients::table
+        .find(client_id)
+        .select(Client::as_select())
+        .first::<Client>(conn)?;
 
     diesel::update(clients::table.find(client_id))
         .set((
@@ -1289,41 +3454,81 @@ pub fn update_client(
             clients::enabled.eq(client_data.enabled),
             clients::updated_at.eq(Utc::now().naive_utc()),
         ))
-        .execute(&mut conn)?;
+        .execute(conn)?;
 
-    get_client(pool, client_id)
+    let after = clients::table
+        .find(client_id)
+        .select(Client::as_select())
+        .first::<Client>(conn)?;
+
+    record_audit(
+        conn,
+        audit,
+        AuditAction::Update,
+        Some(&before),
+        Some(&after),
+    )?;
+    Ok(after)
 }
 
-pub fn delete_client(pool: &DbPool, client_id: i32) -> Result<usize, Error> {
+pub fn delete_client(pool: &DbPool, client_id: i32, audit: &AuditContext) -> Result<usize, Error> {
     let mut conn = pool.get().unwrap();
-    diesel::delete(clients::table.find(client_id)).execute(&mut conn)
+    conn.transaction(|conn| {
+        let Some(before) = clients::table
+            .find(client_id)
+            .select(Client::as_select())
+            .first::<Client>(conn)
+            .optional()?
+        else {
+            return Ok(0);
+        };
+
+        let deleted = diesel::delete(clients::table.find(client_id)).execute(conn)?;
+        record_audit(conn, audit, AuditAction::Delete, Some(&before), None)?;
+        Ok(deleted)
+    })
 }
 
-pub fn toggle_client_enabled(pool: &DbPool, client_id: i32) -> Result<Client, Error> {
+pub fn toggle_client_enabled(
+    pool: &DbPool,
+    client_id: i32,
+    audit: &AuditContext,
+) -> Result<Client, Error> {
     let mut conn = pool.get().unwrap();
     let now = Utc::now().naive_utc();
 
-    // First get the current client to check its enabled status
-    let current_client = clients::table
-        .filter(clients::id.eq(client_id))
-        .select(Client::as_select())
-        .first::<Client>(&mut conn)?;
-
-    // Toggle the enabled status
-    let new_enabled = !current_client.enabled;
+    conn.transaction(|conn| {
+        // First get the current client to check its enabled status
+        let before = clients::table
+            .filter(clients::id.eq(client_id))
+            .select(Client::as_select())
+            .first::<Client>(conn)?;
 
-    diesel::update(clients::table.filter(clients::id.eq(client_id)))
-        .set((
-            clients::enabled.eq(new_enabled),
-            clients::updated_at.eq(now),
-        ))
-        .execute(&mut conn)?;
+        // Toggle the enabled status
+        let new_enabled = !before.enabled;
 
-    // Return the updated client
-    clients::table
-        .filter(clients::id.eq(client_id))
-        .select(Client::as_select())
-        .first::<Client>(&mut conn)
+        diesel::update(clients::table.filter(clients::id.eq(client_id)))
+            .set((
+                clients::enabled.eq(new_enabled),
+                clients::updated_at.eq(now),
+            ))
+            .execute(conn)?;
+
+        // Return the updated client
+        let after = clients::table
+            .filter(clients::id.eq(client_id))
+            .select(Client::as_select())
+            .first::<Client>(conn)?;
+
+        record_audit(
+            conn,
+            audit,
+            AuditAction::Toggle,
+            Some(&before),
+            Some(&after),
+        )?;
+        Ok(after)
+    })
 }
 
 // Function to create multiple aliases for a domain
@@ -1331,47 +3536,52 @@ pub fn create_domain_aliases(
     pool: &DbPool,
     domain: &str,
     aliases: Vec<(String, String)>,
+    audit: &AuditContext,
 ) -> Result<Vec<Alias>, Error> {
     let mut conn = pool.get().unwrap();
     let now = Utc::now().naive_utc();
-    let mut created_aliases = Vec::new();
-
-    for (local_part, destination) in aliases {
-        let mail = if local_part == "@" {
-            format!("@{domain}")
-        } else {
-            format!("{local_part}@{domain}")
-        };
 
-        // Check if alias already exists
-        let existing = aliases::table
-            .filter(aliases::mail.eq(&mail))
-            .select(Alias::as_select())
-            .first::<Alias>(&mut conn)
-            .optional()?;
+    conn.transaction(|conn| {
+        let mut created_aliases = Vec::new();
 
-        if existing.is_none() {
-            diesel::insert_into(aliases::table)
-                .values((
-                    aliases::mail.eq(&mail),
-                    aliases::destination.eq(&destination),
-                    aliases::enabled.eq(true),
-                    aliases::created.eq(now),
-                    aliases::modified.eq(now),
-                ))
-                .execute(&mut conn)?;
+        for (local_part, destination) in aliases {
+            let mail = if local_part == "@" {
+                format!("@{domain}")
+            } else {
+                format!("{local_part}@{domain}")
+            };
 
-            // Get the created alias
-            let created_alias = aliases::table
+            // Check if alias already exists
+            let existing = aliases::table
                 .filter(aliases::mail.eq(&mail))
                 .select(Alias::as_select())
-                .first::<Alias>(&mut conn)?;
+                .first::<Alias>(conn)
+                .optional()?;
 
-            created_aliases.push(created_alias);
+            if existing.is_none() {
+                diesel::insert_into(aliases::table)
+                    .values((
+                        aliases::mail.eq(&mail),
+                        aliases::destination.eq(&destination),
+                        aliases::enabled.eq(true),
+                        aliases::created.eq(now),
+                        aliases::modified.eq(now),
+                    ))
+                    .execute(conn)?;
+
+                // Get the created alias
+                let created_alias = aliases::table
+                    .filter(aliases::mail.eq(&mail))
+                    .select(Alias::as_select())
+                    .first::<Alias>(conn)?;
+
+                record_audit(conn, audit, AuditAction::Create, None, Some(&created_alias))?;
+                created_aliases.push(created_alias);
+            }
         }
-    }
 
-    Ok(created_aliases)
+        Ok(created_aliases)
+    })
 }
 
 pub fn get_aliases_for_domain(pool: &DbPool, domain_name: &str) -> Result<Vec<Alias>, Error> {
@@ -1383,29 +3593,49 @@ pub fn get_aliases_for_domain(pool: &DbPool, domain_name: &str) -> Result<Vec<Al
         .load::<Alias>(&mut conn)
 }
 
-pub fn search_aliases(pool: &DbPool, query: &str, limit: i64) -> Result<Vec<Alias>, Error> {
+pub fn search_aliases(
+    pool: &DbPool,
+    query: &str,
+    limit: i64,
+    scope: &AddressScope,
+) -> Result<Vec<Alias>, Error> {
     let mut conn = pool.get().unwrap();
     let search_pattern = format!("%{}%", query);
 
-    aliases::table
+    let mut query = aliases::table
         .filter(
             aliases::destination
-                .like(&search_pattern)
-                .or(aliases::mail.like(&search_pattern)),
+                .like(search_pattern.clone())
+                .or(aliases::mail.like(search_pattern)),
         )
         .select(Alias::as_select())
+        .into_boxed();
+    if let Some(filter) = alias_scope_filter(scope) {
+        query = query.filter(filter);
+    }
+    query
         .order(aliases::destination.asc())
         .limit(limit)
         .load::<Alias>(&mut conn)
 }
 
-pub fn search_aliases_by_name(pool: &DbPool, query: &str, limit: i64) -> Result<Vec<Alias>, Error> {
+pub fn search_aliases_by_name(
+    pool: &DbPool,
+    query: &str,
+    limit: i64,
+    scope: &AddressScope,
+) -> Result<Vec<Alias>, Error> {
     let mut conn = pool.get().unwrap();
     let search_pattern = format!("{}%@%", query);
 
-    aliases::table
-        .filter(aliases::mail.like(&search_pattern))
+    let mut query = aliases::table
+        .filter(aliases::mail.like(search_pattern))
         .select(Alias::as_select())
+        .into_boxed();
+    if let Some(filter) = alias_scope_filter(scope) {
+        query = query.filter(filter);
+    }
+    query
         .order(aliases::mail.asc())
         .limit(limit)
         .load::<Alias>(&mut conn)
@@ -1453,22 +3683,45 @@ pub fn get_domains_paginated(
     Ok(PaginatedResult::new(domains, total_count, page, per_page))
 }
 
+/// A page of aliases under the scope; `expiring_only` keeps those whose
+/// validity window ends soon, soonest first
 pub fn get_aliases_paginated(
     pool: &DbPool,
     page: i64,
     per_page: i64,
+    scope: &AddressScope,
+    expiring_only: bool,
 ) -> Result<PaginatedResult<Alias>, Error> {
     let mut conn = pool.get().unwrap();
 
     let offset = (page - 1) * per_page;
 
+    let mut count_query = aliases::table.into_boxed();
+    let mut query = aliases::table.select(Alias::as_select()).into_boxed();
+    if let (Some(count_filter), Some(filter)) =
+        (alias_scope_filter(scope), alias_scope_filter(scope))
+    {
+        count_query = count_query.filter(count_filter);
+        query = query.filter(filter);
+    }
+    if expiring_only {
+        let now = Utc::now().naive_utc();
+        let cutoff = crate::validity::expiring_soon_cutoff(now);
+        count_query = count_query
+            .filter(aliases::valid_until.gt(now))
+            .filter(aliases::valid_until.le(cutoff));
+        query = query
+            .filter(aliases::valid_until.gt(now))
+            .filter(aliases::valid_until.le(cutoff))
+            .order(aliases::valid_until.asc());
+    }
+
     // Get total count
-    let total_count: i64 = aliases::table.count().get_result(&mut conn)?;
+    let total_count: i64 = count_query.count().get_result(&mut conn)?;
 
     // Get paginated results
-    let aliases = aliases::table
-        .select(Alias::as_select())
-        .order(aliases::mail.asc())
+    let aliases = query
+        .then_order_by(aliases::mail.asc())
         .limit(per_page)
         .offset(offset)
         .load::<Alias>(&mut conn)?;
@@ -1480,17 +3733,25 @@ pub fn get_users_paginated(
     pool: &DbPool,
     page: i64,
     per_page: i64,
+    scope: &AddressScope,
 ) -> Result<PaginatedResult<User>, Error> {
     let mut conn = pool.get().unwrap();
 
     let offset = (page - 1) * per_page;
 
+    let mut count_query = users::table.into_boxed();
+    let mut query = users::table.select(User::as_select()).into_boxed();
+    if let (Some(count_filter), Some(filter)) = (user_scope_filter(scope), user_scope_filter(scope))
+    {
+        count_query = count_query.filter(count_filter);
+        query = query.filter(filter);
+    }
+
     // Get total count
-    let total_count: i64 = users::table.count().get_result(&mut conn)?;
+    let total_count: i64 = count_query.count().get_result(&mut conn)?;
 
     // Get paginated results
-    let users = users::table
-        .select(User::as_select())
+    let users = query
         .order(users::id.asc())
         .limit(per_page)
         .offset(offset)
@@ -1545,6 +3806,29 @@ pub fn get_relays_paginated(
     Ok(PaginatedResult::new(relays, total_count, page, per_page))
 }
 
+pub fn get_backups_paginated(
+    pool: &DbPool,
+    page: i64,
+    per_page: i64,
+) -> Result<PaginatedResult<Backup>, Error> {
+    let mut conn = pool.get().unwrap();
+
+    let offset = (page - 1) * per_page;
+
+    // Get total count
+    let total_count: i64 = backups::table.count().get_result(&mut conn)?;
+
+    // Get paginated results
+    let backups = backups::table
+        .select(Backup::as_select())
+        .order(backups::domain.asc())
+        .limit(per_page)
+        .offset(offset)
+        .load::<Backup>(&mut conn)?;
+
+    Ok(PaginatedResult::new(backups, total_count, page, per_page))
+}
+
 pub fn get_relocated_paginated(
     pool: &DbPool,
     page: i64,
@@ -1593,8 +3877,16 @@ pub fn get_orphaned_aliases_report(pool: &DbPool) -> Result<OrphanedAliasReport,
                     .first::<(String, bool)>(&mut conn)
                     .optional()
                     .unwrap_or(None);
+                // Aliases on an enabled alias domain are looked up before the rewrite
+                let alias_domain_enabled: bool = alias_domains::table
+                    .filter(alias_domains::alias_domain.eq(mail_domain))
+                    .select(alias_domains::enabled)
+                    .first::<bool>(&mut conn)
+                    .optional()
+                    .unwrap_or(None)
+                    .unwrap_or(false);
                 // Consider orphaned if domain doesn't exist or is disabled
-                domain_exists.map_or(true, |(_, enabled)| !enabled)
+                domain_exists.is_none_or(|(_, enabled)| !enabled) && !alias_domain_enabled
             } else {
                 false
             }
@@ -1628,7 +3920,7 @@ pub fn get_orphaned_aliases_report(pool: &DbPool) -> Result<OrphanedAliasReport,
                     .optional()
                     .unwrap_or(None);
                 // Consider orphaned if domain doesn't exist or is disabled
-                domain_exists.map_or(true, |(_, enabled)| !enabled)
+                domain_exists.is_none_or(|(_, enabled)| !enabled)
             } else {
                 false
             }
@@ -1672,54 +3964,57 @@ pub fn get_orphaned_aliases_report(pool: &DbPool) -> Result<OrphanedAliasReport,
         })
         .collect();
 
+    // Find alias destinations that do not reach anyone, resolving each on its own
+    let lookup = crate::delivery::Snapshot::load(pool)?;
+    let mut unresolved_destinations = Vec::new();
+    for alias in get_aliases(pool)? {
+        let destinations = crate::delivery::split_destinations(&alias.destination);
+        for trace in crate::delivery::trace_destinations(&lookup, &alias.mail, &destinations)? {
+            let Some(hop) = trace.hops.into_iter().next() else {
+                continue;
+            };
+            if hop.outcome.is_problem() {
+                unresolved_destinations.push(UnresolvedDestination {
+                    mail: alias.mail.clone(),
+                    destination: trace.address,
+                    domain: alias.domain(),
+                    outcome: hop.outcome,
+                    enabled: alias.enabled,
+                });
+            }
+        }
+    }
+
     Ok(OrphanedAliasReport {
         orphaned_aliases,
         orphaned_users,
         users_without_aliases,
+        unresolved_destinations,
     })
 }
 
 pub fn get_external_forwarders_report(pool: &DbPool) -> Result<ExternalForwarderReport, Error> {
-    let mut conn = pool.get().unwrap();
-
-    // Find aliases where the destination is an external email address (contains @ and doesn't match any domain in the domains table)
-    let external_forwarders: Vec<ExternalForwarder> = aliases::table
-        .filter(aliases::destination.like("%@%"))
-        .select((
-            aliases::mail,
-            aliases::destination,
-            aliases::enabled,
-            aliases::created,
-        ))
-        .load::<(String, String, bool, NaiveDateTime)>(&mut conn)?
-        .into_iter()
-        .filter(|(_, destination, _, _)| {
-            // Extract the domain from the destination
-            if let Some(at_pos) = destination.rfind('@') {
-                let dest_domain = &destination[at_pos + 1..];
-                // Check if this domain exists in our domains table
-                let domain_exists: Option<String> = domains::table
-                    .filter(domains::domain.eq(dest_domain))
-                    .select(domains::domain)
-                    .first::<String>(&mut conn)
-                    .optional()
-                    .unwrap_or(None);
-                domain_exists.is_none()
-            } else {
-                false
-            }
-        })
-        .map(|(mail, destination, enabled, created)| {
-            let domain = mail.split('@').nth(1).unwrap_or("").to_string();
-            ExternalForwarder {
-                mail,
-                destination,
-                domain,
-                enabled,
-                created,
+    // Destinations are resolved one by one, as lists often mix local and external addresses
+    let lookup = crate::delivery::Snapshot::load(pool)?;
+    let mut external_forwarders = Vec::new();
+    for alias in get_aliases(pool)? {
+        let destinations = crate::delivery::split_destinations(&alias.destination);
+        for trace in crate::delivery::trace_destinations(&lookup, &alias.mail, &destinations)? {
+            let external = trace
+                .hops
+                .first()
+                .is_some_and(|hop| hop.outcome == DeliveryOutcome::External);
+            if external {
+                external_forwarders.push(ExternalForwarder {
+                    mail: alias.mail.clone(),
+                    destination: trace.address,
+                    domain: alias.domain(),
+                    enabled: alias.enabled,
+                    created: alias.created,
+                });
             }
-        })
-        .collect();
+        }
+    }
 
     Ok(ExternalForwarderReport {
         external_forwarders,
@@ -1767,6 +4062,7 @@ pub fn get_missing_aliases_report(pool: &DbPool) -> Result<MissingAliasReport, E
         if !missing_required_aliases.is_empty() && !has_catch_all {
             domains_missing_aliases.push(DomainMissingAliases {
                 domain,
+                alias_for: None,
                 missing_required_aliases,
                 has_catch_all,
                 catch_all_alias,
@@ -1774,6 +4070,56 @@ pub fn get_missing_aliases_report(pool: &DbPool) -> Result<MissingAliasReport, E
         }
     }
 
+    // An enabled alias domain is covered by aliases on its target as well
+    for alias_domain in get_alias_domains(pool)? {
+        let mut domains = vec![alias_domain.alias_domain.clone()];
+        if alias_domain.enabled {
+            domains.push(alias_domain.target_domain.clone());
+        }
+        let catch_all_alias: Option<String> = aliases::table
+            .filter(
+                aliases::mail.eq_any(
+                    domains
+                        .iter()
+                        .map(|domain| format!("@{domain}"))
+                        .collect::<Vec<_>>(),
+                ),
+            )
+            .select(aliases::mail)
+            .first::<String>(&mut conn)
+            .optional()?;
+        if catch_all_alias.is_some() {
+            continue;
+        }
+
+        let mut missing_required_aliases = Vec::new();
+        for required_alias in
+            get_required_aliases_for_domain(&mut conn, &alias_domain.alias_domain)?
+        {
+            let candidates: Vec<String> = domains
+                .iter()
+                .map(|domain| format!("{required_alias}@{domain}"))
+                .collect();
+            let alias_exists: i64 = aliases::table
+                .filter(aliases::mail.eq_any(candidates))
+                .count()
+                .get_result(&mut conn)?;
+            if alias_exists == 0 {
+                missing_required_aliases.push(required_alias);
+            }
+        }
+
+        if !missing_required_aliases.is_empty() {
+            domains_missing_aliases.push(DomainMissingAliases {
+                domain: alias_domain.alias_domain,
+                alias_for: Some(alias_domain.target_domain),
+                missing_required_aliases,
+                has_catch_all: false,
+                catch_all_alias: None,
+            });
+        }
+    }
+
     Ok(MissingAliasReport {
         domains_missing_aliases,
     })
@@ -1826,8 +4172,8 @@ fn get_required_aliases_for_domain(
 // Cross-database domain matrix report
 pub async fn get_cross_database_domain_matrix_report(
     db_manager: &DatabaseManager,
+    configs: &[DatabaseConfig],
 ) -> Result<CrossDatabaseDomainMatrixReport, Box<dyn std::error::Error>> {
-    let configs = db_manager.get_configs();
     let mut all_domains = std::collections::HashSet::new();
     let mut domain_presence_map = std::collections::HashMap::new();
 
@@ -1945,8 +4291,8 @@ pub async fn get_cross_database_domain_matrix_report(
 // Cross-database User Distribution Report
 pub async fn get_cross_database_user_distribution_report(
     db_manager: &DatabaseManager,
+    configs: &[DatabaseConfig],
 ) -> Result<CrossDatabaseUserDistributionReport, Box<dyn std::error::Error>> {
-    let configs = db_manager.get_configs();
     let mut all_users = std::collections::HashMap::new();
     let mut user_presence_map = std::collections::HashMap::new();
 
@@ -2068,9 +4414,8 @@ fn get_user_domain(pool: &DbPool, user_id: &str) -> Result<Option<String>, Error
 
 // Cross-database Feature Toggle Compliance Report
 pub async fn get_cross_database_feature_toggle_report(
-    db_manager: &DatabaseManager,
+    configs: &[DatabaseConfig],
 ) -> Result<CrossDatabaseFeatureToggleReport, Box<dyn std::error::Error>> {
-    let configs = db_manager.get_configs();
     let mut database_features = Vec::new();
 
     for config in configs {
@@ -2135,8 +4480,8 @@ pub async fn get_cross_database_feature_toggle_report(
 // Cross-database Migration Status Report
 pub async fn get_cross_database_migration_report(
     db_manager: &DatabaseManager,
+    configs: &[DatabaseConfig],
 ) -> Result<CrossDatabaseMigrationReport, Box<dyn std::error::Error>> {
-    let configs = db_manager.get_configs();
     let mut database_migrations = Vec::new();
     let mut latest_migration = None;
 
@@ -2246,9 +4591,11 @@ pub fn get_users_with_field_map(
     let created = db_config.field_for_table("users", "created");
     let modified = db_config.field_for_table("users", "modified");
     let change_password = db_config.field_for_table("users", "change_password");
+    let quota_bytes = db_config.field_for_table("users", "quota_bytes");
+    let quota_messages = db_config.field_for_table("users", "quota_messages");
 
     let sql = format!(
-        "SELECT {user_id} as id, {enabled} as enabled, {crypt} as crypt, {name} as name, {maildir} as maildir, {home} as home, {uid} as uid, {gid} as gid, {created} as created, {modified} as modified, {change_password} as change_password FROM users",
+        "SELECT {user_id} as id, {enabled} as enabled, {crypt} as crypt, {name} as name, {maildir} as maildir, {home} as home, {uid} as uid, {gid} as gid, {created} as created, {modified} as modified, {change_password} as change_password, {quota_bytes} as quota_bytes, {quota_messages} as quota_messages FROM users",
         user_id = user_id,
         enabled = enabled,
         crypt = crypt,
@@ -2259,7 +4606,9 @@ pub fn get_users_with_field_map(
         gid = gid,
         created = created,
         modified = modified,
-        change_password = change_password
+        change_password = change_password,
+        quota_bytes = quota_bytes,
+        quota_messages = quota_messages
     );
 
     sql_query(sql).load::<User>(&mut conn)
@@ -2271,10 +4620,11 @@ pub fn build_field_mapped_query(
     fields: &[(&str, &str)], // (logical_name, alias_name)
     db_config: &crate::config::DatabaseConfig,
 ) -> String {
+    // Unmapped fields are read from the column of this schema, e.g. `id` from `pkid`
     let mapped_fields: Vec<String> = fields
         .iter()
         .map(|(logical, alias)| {
-            let mapped_field = db_config.field_for_table(table, logical);
+            let mapped_field = db_config.mapped_field(table, logical).unwrap_or(alias);
             format!("{} as {}", mapped_field, alias)
         })
         .collect();
@@ -2296,6 +4646,9 @@ pub fn get_domains_with_field_map(
         ("created", "created"),
         ("modified", "modified"),
         ("enabled", "enabled"),
+        ("default_quota_bytes", "default_quota_bytes"),
+        ("default_quota_messages", "default_quota_messages"),
+        ("quota_cap_bytes", "quota_cap_bytes"),
     ];
 
     let sql = build_field_mapped_query("domains", &fields, db_config);
@@ -2316,6 +4669,8 @@ pub fn get_aliases_with_field_map(
         ("created", "created"),
         ("modified", "modified"),
         ("enabled", "enabled"),
+        ("valid_from", "valid_from"),
+        ("valid_until", "valid_until"),
     ];
 
     let sql = build_field_mapped_query("aliases", &fields, db_config);
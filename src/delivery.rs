@@ -0,0 +1,415 @@
+//! Delivery simulator: where does mail to an address end up?
+//!
+//! Walks the tables in the order the generated Postfix lookups use them:
+//! `relocated_maps`, domain membership (`virtual_mailbox_domains`,
+//! `relay_domains` and `virtual_alias_domains`), `virtual_alias_maps` with
+//! exact matches before alias domain rewrites before `@domain` catch-alls,
+//! and finally `virtual_mailbox_maps`. Alias
+//! destinations are resolved recursively. Postfix only sees enabled entries,
+//! so disabled ones are skipped and reported on the hop they would have matched.
+//! Aliases and relocated entries outside their validity window count as
+//! disabled, like in the generated queries.
+
+use crate::models::{
+    Alias, AliasDomain, Backup, DeliveryHop, DeliveryOutcome, DeliveryTrace, DisabledEntry, Domain,
+    Relocated, User,
+};
+use crate::{db, DbPool};
+use chrono::Utc;
+use diesel::result::Error;
+use diesel::OptionalExtension;
+use std::collections::HashMap;
+
+/// Deepest alias chain followed before giving up
+pub const MAX_DEPTH: usize = 20;
+
+/// The lookups the simulator needs, so it can run against the database or test data
+pub trait DeliveryLookup {
+    fn relocated(&self, address: &str) -> Result<Option<Relocated>, Error>;
+    fn domain(&self, domain: &str) -> Result<Option<Domain>, Error>;
+    fn backup(&self, domain: &str) -> Result<Option<Backup>, Error>;
+    fn alias_domain(&self, domain: &str) -> Result<Option<AliasDomain>, Error>;
+    fn alias(&self, mail: &str) -> Result<Option<Alias>, Error>;
+    fn user(&self, id: &str) -> Result<Option<User>, Error>;
+}
+
+impl DeliveryLookup for DbPool {
+    fn relocated(&self, address: &str) -> Result<Option<Relocated>, Error> {
+        db::get_relocated_by_old_address(self, address).optional()
+    }
+
+    fn domain(&self, domain: &str) -> Result<Option<Domain>, Error> {
+        db::get_domain_by_name(self, domain).optional()
+    }
+
+    fn backup(&self, domain: &str) -> Result<Option<Backup>, Error> {
+        db::get_backup_by_name(self, domain).optional()
+    }
+
+    fn alias_domain(&self, domain: &str) -> Result<Option<AliasDomain>, Error> {
+        db::get_alias_domain_by_name(self, domain).optional()
+    }
+
+    fn alias(&self, mail: &str) -> Result<Option<Alias>, Error> {
+        db::get_alias_by_mail(self, mail).optional()
+    }
+
+    fn user(&self, id: &str) -> Result<Option<User>, Error> {
+        db::get_user_by_id(self, id).optional()
+    }
+}
+
+/// Every lookup table loaded at once, for reports that resolve many addresses
+pub struct Snapshot {
+    relocated: HashMap<String, Relocated>,
+    domains: HashMap<String, Domain>,
+    backups: HashMap<String, Backup>,
+    alias_domains: HashMap<String, AliasDomain>,
+    aliases: HashMap<String, Alias>,
+    users: HashMap<String, User>,
+}
+
+/// Key rows by a lower-cased name, as the case-insensitive MySQL lookups match them
+fn keyed<T>(rows: Vec<T>, key: impl Fn(&T) -> &str) -> HashMap<String, T> {
+    rows.into_iter()
+        .map(|row| (key(&row).to_lowercase(), row))
+        .collect()
+}
+
+impl Snapshot {
+    pub fn load(pool: &DbPool) -> Result<Self, Error> {
+        Ok(Snapshot {
+            relocated: keyed(db::get_relocated(pool)?, |entry| &entry.old_address),
+            domains: keyed(db::get_domains(pool)?, |domain| &domain.domain),
+            backups: keyed(db::get_backups(pool)?, |backup| &backup.domain),
+            alias_domains: keyed(db::get_alias_domains(pool)?, |entry| &entry.alias_domain),
+            aliases: keyed(db::get_aliases(pool)?, |alias| &alias.mail),
+            users: keyed(db::get_users(pool)?, |user| &user.id),
+        })
+    }
+}
+
+impl DeliveryLookup for Snapshot {
+    fn relocated(&self, address: &str) -> Result<Option<Relocated>, Error> {
+        Ok(self.relocated.get(&address.to_lowercase()).cloned())
+    }
+
+    fn domain(&self, domain: &str) -> Result<Option<Domain>, Error> {
+        Ok(self.domains.get(&domain.to_lowercase()).cloned())
+    }
+
+    fn backup(&self, domain: &str) -> Result<Option<Backup>, Error> {
+        Ok(self.backups.get(&domain.to_lowercase()).cloned())
+    }
+
+    fn alias_domain(&self, domain: &str) -> Result<Option<AliasDomain>, Error> {
+        Ok(self.alias_domains.get(&domain.to_lowercase()).cloned())
+    }
+
+    fn alias(&self, mail: &str) -> Result<Option<Alias>, Error> {
+        Ok(self.aliases.get(&mail.to_lowercase()).cloned())
+    }
+
+    fn user(&self, id: &str) -> Result<Option<User>, Error> {
+        Ok(self.users.get(&id.to_lowercase()).cloned())
+    }
+}
+
+/// Split an alias destination into addresses; Postfix accepts commas and whitespace
+pub fn split_destinations(destination: &str) -> Vec<String> {
+    destination
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .map(str::trim)
+        .filter(|address| !address.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Why an alias's destinations were rejected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DestinationError {
+    /// No destination was given
+    Missing,
+    /// A destination is not a full `local@domain` address
+    InvalidAddress(String),
+}
+
+impl DestinationError {
+    /// Fluent message ID describing the problem, which takes the address as `$address`
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            DestinationError::Missing => "aliases-error-destination-missing",
+            DestinationError::InvalidAddress(_) => "aliases-error-destination-invalid",
+        }
+    }
+
+    pub fn address(&self) -> &str {
+        match self {
+            DestinationError::Missing => "",
+            DestinationError::InvalidAddress(address) => address,
+        }
+    }
+}
+
+impl std::fmt::Display for DestinationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DestinationError::Missing => f.write_str("At least one destination is required"),
+            DestinationError::InvalidAddress(address) => {
+                write!(f, "'{address}' is not a valid destination address")
+            }
+        }
+    }
+}
+
+/// Whether a destination is a full address Postfix can deliver to
+pub fn is_valid_destination(address: &str) -> bool {
+    address.split_once('@').is_some_and(|(local, domain)| {
+        !local.is_empty()
+            && local.len() <= 64
+            && !local
+                .chars()
+                .any(|c| c.is_control() || c.is_whitespace() || "\"(),:;<>@[\\]".contains(c))
+            && crate::config::is_valid_domain(domain)
+    })
+}
+
+/// Check an alias's destinations, each of which must be a full address
+pub fn check_destinations(destinations: &[String]) -> Result<(), DestinationError> {
+    if destinations.is_empty() {
+        return Err(DestinationError::Missing);
+    }
+    match destinations
+        .iter()
+        .find(|address| !is_valid_destination(address))
+    {
+        Some(address) => Err(DestinationError::InvalidAddress(address.clone())),
+        None => Ok(()),
+    }
+}
+
+/// Trace mail to `address` through the lookup tables
+pub fn trace(lookup: &impl DeliveryLookup, address: &str) -> Result<DeliveryTrace, Error> {
+    let address = address.trim().to_lowercase();
+    let mut hops = Vec::new();
+    let mut path = Vec::new();
+    resolve(lookup, &address, 0, false, &mut path, &mut hops)?;
+    Ok(DeliveryTrace { address, hops })
+}
+
+/// Trace each destination of the alias on `mail` on its own
+///
+/// The destinations are resolved the way they are when the alias is
+/// expanded, so addresses on domains not managed here are external rather
+/// than rejected, and the alias's own address is its mailbox.
+pub fn trace_destinations(
+    lookup: &impl DeliveryLookup,
+    mail: &str,
+    destinations: &[String],
+) -> Result<Vec<DeliveryTrace>, Error> {
+    let mail = mail.trim().to_lowercase();
+    let mut traces = Vec::new();
+    for destination in destinations {
+        let address = destination.trim().to_lowercase();
+        let mut hops = Vec::new();
+        let mut path = vec![mail.clone()];
+        let self_reference = address == mail;
+        resolve(lookup, &address, 1, self_reference, &mut path, &mut hops)?;
+        traces.push(DeliveryTrace { address, hops });
+    }
+    Ok(traces)
+}
+
+fn disabled(entity: &str, name: &str) -> DisabledEntry {
+    DisabledEntry {
+        entity: entity.to_string(),
+        name: name.to_string(),
+    }
+}
+
+/// The active alias on `mail`, noting a disabled one on the hop
+fn enabled_alias(
+    lookup: &impl DeliveryLookup,
+    mail: &str,
+    hop: &mut DeliveryHop,
+) -> Result<Option<Alias>, Error> {
+    match lookup.alias(mail)? {
+        Some(alias) if alias.is_active(Utc::now().naive_utc()) => Ok(Some(alias)),
+        Some(alias) => {
+            hop.disabled.push(disabled("alias", &alias.mail));
+            Ok(None)
+        }
+        None => Ok(None),
+    }
+}
+
+/// Whether an alias domain rewrite to `address` is used
+///
+/// The generated map only rewrites addresses its target domain takes mail
+/// for (an enabled mailbox, alias or catch-all), so unknown recipients are
+/// still rejected.
+fn rewrite_accepted(lookup: &impl DeliveryLookup, address: &str) -> Result<bool, Error> {
+    if lookup.user(address)?.is_some_and(|user| user.enabled) {
+        return Ok(true);
+    }
+    let catch_all = match address.rsplit_once('@') {
+        Some((_, domain)) => format!("@{domain}"),
+        None => return Ok(false),
+    };
+    for mail in [address.to_string(), catch_all] {
+        if lookup
+            .alias(&mail)?
+            .is_some_and(|alias| alias.is_active(Utc::now().naive_utc()))
+        {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Resolve one address and push its hop, then the hops of any alias destinations
+///
+/// `self_reference` is set when an alias points at its own address, which
+/// Postfix delivers without expanding again.
+fn resolve(
+    lookup: &impl DeliveryLookup,
+    address: &str,
+    depth: usize,
+    self_reference: bool,
+    path: &mut Vec<String>,
+    hops: &mut Vec<DeliveryHop>,
+) -> Result<(), Error> {
+    let mut hop = DeliveryHop {
+        depth,
+        address: address.to_string(),
+        outcome: DeliveryOutcome::External,
+        disabled: Vec::new(),
+    };
+
+    let Some((local_part, domain_name)) = address.rsplit_once('@').filter(|(_, d)| !d.is_empty())
+    else {
+        hop.outcome = DeliveryOutcome::InvalidAddress;
+        hops.push(hop);
+        return Ok(());
+    };
+    if !self_reference && path.iter().any(|visited| visited == address) {
+        hop.outcome = DeliveryOutcome::Loop;
+        hops.push(hop);
+        return Ok(());
+    }
+    if depth > MAX_DEPTH {
+        hop.outcome = DeliveryOutcome::TooDeep;
+        hops.push(hop);
+        return Ok(());
+    }
+
+    if let Some(relocated) = lookup.relocated(address)? {
+        if relocated.is_active(Utc::now().naive_utc()) {
+            hop.outcome = DeliveryOutcome::Relocated {
+                new_address: relocated.new_address,
+            };
+            hops.push(hop);
+            return Ok(());
+        }
+        hop.disabled
+            .push(disabled("relocated", &relocated.old_address));
+    }
+
+    let mut local = None;
+    match lookup.domain(domain_name)? {
+        Some(domain) if domain.enabled => local = Some(domain),
+        Some(domain) => hop.disabled.push(disabled("domain", &domain.domain)),
+        None => {}
+    }
+    let mut relay = None;
+    if local.is_none() {
+        match lookup.backup(domain_name)? {
+            Some(backup) if backup.enabled => relay = Some(backup),
+            Some(backup) => hop.disabled.push(disabled("backup", &backup.domain)),
+            None => {}
+        }
+    }
+    let mut alias_domain = None;
+    if local.is_none() && relay.is_none() {
+        match lookup.alias_domain(domain_name)? {
+            Some(entry) if entry.enabled => alias_domain = Some(entry),
+            Some(entry) => hop
+                .disabled
+                .push(disabled("alias-domain", &entry.alias_domain)),
+            None => {}
+        }
+    }
+    // Only the recipient has to be accepted; expanded addresses may go anywhere
+    if depth == 0 && local.is_none() && relay.is_none() && alias_domain.is_none() {
+        hop.outcome = DeliveryOutcome::UnknownDomain;
+        hops.push(hop);
+        return Ok(());
+    }
+
+    if !self_reference {
+        let mut matched = enabled_alias(lookup, address, &mut hop)?.map(|alias| (alias, false));
+
+        if let Some(entry) = alias_domain.as_ref().filter(|_| matched.is_none()) {
+            let target = format!("{local_part}@{}", entry.target_domain.to_lowercase());
+            if !local_part.is_empty() && rewrite_accepted(lookup, &target)? {
+                hop.outcome = DeliveryOutcome::AliasDomain {
+                    alias_domain: entry.alias_domain.clone(),
+                    address: target.clone(),
+                };
+                hops.push(hop);
+
+                path.push(address.to_string());
+                resolve(lookup, &target, depth + 1, false, path, hops)?;
+                path.pop();
+                return Ok(());
+            }
+        }
+
+        if matched.is_none() {
+            matched = enabled_alias(lookup, &format!("@{domain_name}"), &mut hop)?
+                .map(|alias| (alias, true));
+        }
+
+        if let Some((alias, catch_all)) = matched {
+            let destinations = split_destinations(&alias.destination);
+            hop.outcome = DeliveryOutcome::Alias {
+                mail: alias.mail,
+                catch_all,
+                destinations: destinations.clone(),
+            };
+            hops.push(hop);
+
+            path.push(address.to_string());
+            for destination in &destinations {
+                let self_reference = destination == address;
+                resolve(lookup, destination, depth + 1, self_reference, path, hops)?;
+            }
+            path.pop();
+            return Ok(());
+        }
+    }
+
+    hop.outcome = if let Some(backup) = relay {
+        DeliveryOutcome::Relay {
+            domain: backup.domain,
+            transport: backup.transport,
+        }
+    } else if local.is_some() {
+        match lookup.user(address)? {
+            Some(user) if user.enabled => DeliveryOutcome::Mailbox {
+                maildir: user.maildir,
+            },
+            Some(user) => {
+                hop.disabled.push(disabled("user", &user.id));
+                DeliveryOutcome::UnknownUser
+            }
+            None => DeliveryOutcome::UnknownUser,
+        }
+    } else if alias_domain.is_some() {
+        DeliveryOutcome::UnknownUser
+    } else {
+        DeliveryOutcome::External
+    };
+    hops.push(hop);
+    Ok(())
+}
@@ -120,7 +120,7 @@ pub async fn index(State(state): State<AppState>, headers: HeaderMap) -> Html<St
     let content = content_template.render().unwrap();
 
     // Get current database id from session/cookie or default
-    let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+    let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
         .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
     // Get current database label from db_manager
     let current_db_label = state
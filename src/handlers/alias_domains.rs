@@ -0,0 +1,529 @@
+use crate::templates::alias_domains::*;
+use crate::{
+    db, get_entity_or_not_found, i18n::get_translation, models::*, render_template,
+    render_template_with_title, AppState, DbPool,
+};
+use askama::Template;
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    response::Html,
+    Form,
+};
+use diesel::result::Error;
+use tracing::{debug, error, info};
+
+const FORM_KEYS: &[&str] = &[
+    "alias-domains-new",
+    "alias-domains-edit",
+    "form-error",
+    "alias-domains-field-alias-domain",
+    "alias-domains-field-target-domain",
+    "alias-domains-field-enabled",
+    "alias-domains-field-alias-domain-help",
+    "alias-domains-field-target-domain-help",
+    "alias-domains-placeholder-alias-domain",
+    "alias-domains-select-target",
+    "action-save",
+    "action-cancel",
+];
+
+// List all alias domains
+pub async fn list(State(state): State<AppState>, headers: HeaderMap) -> Html<String> {
+    let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
+        .await
+        .expect("Failed to get database pool");
+    let locale = crate::handlers::utils::get_user_locale(&headers);
+
+    debug!("Handling alias domain list request");
+
+    let alias_domains = db::get_alias_domains(&pool).unwrap_or_else(|e| {
+        error!("Failed to retrieve alias domains: {:?}", e);
+        vec![]
+    });
+
+    let translations = crate::handlers::utils::get_translations_batch(
+        &state,
+        &locale,
+        &[
+            "alias-domains-title",
+            "alias-domains-list-description",
+            "alias-domains-add",
+            "alias-domains-table-header-alias-domain",
+            "alias-domains-table-header-target-domain",
+            "alias-domains-table-header-enabled",
+            "alias-domains-table-header-actions",
+            "status-enabled",
+            "status-disabled",
+            "action-view",
+            "action-enable",
+            "action-disable",
+            "alias-domains-empty-title",
+            "alias-domains-empty-description",
+        ],
+    )
+    .await;
+
+    let content_template = AliasDomainListTemplate {
+        title: &translations["alias-domains-title"],
+        description: &translations["alias-domains-list-description"],
+        add_alias_domain: &translations["alias-domains-add"],
+        table_header_alias_domain: &translations["alias-domains-table-header-alias-domain"],
+        table_header_target_domain: &translations["alias-domains-table-header-target-domain"],
+        table_header_enabled: &translations["alias-domains-table-header-enabled"],
+        table_header_actions: &translations["alias-domains-table-header-actions"],
+        status_enabled: &translations["status-enabled"],
+        status_disabled: &translations["status-disabled"],
+        action_view: &translations["action-view"],
+        action_enable: &translations["action-enable"],
+        action_disable: &translations["action-disable"],
+        empty_title: &translations["alias-domains-empty-title"],
+        empty_description: &translations["alias-domains-empty-description"],
+        alias_domains,
+    };
+
+    render_template_with_title!(
+        content_template,
+        &translations["alias-domains-title"],
+        &state,
+        &locale,
+        &headers
+    )
+}
+
+// Show a specific alias domain
+pub async fn show(
+    State(state): State<AppState>,
+    Path(alias_domain_id): Path<i32>,
+    headers: HeaderMap,
+) -> Html<String> {
+    let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
+        .await
+        .expect("Failed to get database pool");
+    let locale = crate::handlers::utils::get_user_locale(&headers);
+
+    debug!(
+        "Handling alias domain show request for ID: {}",
+        alias_domain_id
+    );
+
+    let alias_domain = get_entity_or_not_found!(
+        db::get_alias_domain_by_id(&pool, alias_domain_id),
+        &state,
+        &locale,
+        "alias-domains-not-found"
+    );
+    let target_domain_id = db::get_domain_by_name(&pool, &alias_domain.target_domain)
+        .ok()
+        .map(|domain| domain.pkid);
+
+    let translations = crate::handlers::utils::get_translations_batch(
+        &state,
+        &locale,
+        &[
+            "alias-domains-show-title",
+            "alias-domains-view-edit-settings",
+            "alias-domains-back-to-list",
+            "alias-domains-info-title",
+            "alias-domains-info-description",
+            "alias-domains-field-alias-domain",
+            "alias-domains-field-target-domain",
+            "alias-domains-field-enabled",
+            "alias-domains-field-created",
+            "alias-domains-field-modified",
+            "status-enabled",
+            "status-disabled",
+            "action-edit",
+            "action-enable",
+            "action-disable",
+            "action-delete",
+            "alias-domains-delete-confirm",
+        ],
+    )
+    .await;
+
+    let content_template = AliasDomainShowTemplate {
+        show_title: &translations["alias-domains-show-title"],
+        view_edit_settings: &translations["alias-domains-view-edit-settings"],
+        back_to_list: &translations["alias-domains-back-to-list"],
+        info_title: &translations["alias-domains-info-title"],
+        info_description: &translations["alias-domains-info-description"],
+        field_alias_domain: &translations["alias-domains-field-alias-domain"],
+        field_target_domain: &translations["alias-domains-field-target-domain"],
+        field_enabled: &translations["alias-domains-field-enabled"],
+        field_created: &translations["alias-domains-field-created"],
+        field_modified: &translations["alias-domains-field-modified"],
+        status_enabled: &translations["status-enabled"],
+        status_disabled: &translations["status-disabled"],
+        action_edit: &translations["action-edit"],
+        action_enable: &translations["action-enable"],
+        action_disable: &translations["action-disable"],
+        action_delete: &translations["action-delete"],
+        delete_confirm: &translations["alias-domains-delete-confirm"],
+        alias_domain,
+        target_domain_id,
+    };
+
+    render_template!(content_template, &state, &locale, &headers)
+}
+
+// The alias domains of a domain, shown on its page
+pub async fn for_domain(
+    State(state): State<AppState>,
+    Path(domain_id): Path<i32>,
+    headers: HeaderMap,
+) -> Html<String> {
+    let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
+        .await
+        .expect("Failed to get database pool");
+    let locale = crate::handlers::utils::get_user_locale(&headers);
+
+    let alias_domains = match db::get_domain(&pool, domain_id) {
+        Ok(domain) => db::get_alias_domains_for_target(&pool, &domain.domain).unwrap_or_else(|e| {
+            error!(
+                "Failed to retrieve alias domains for {}: {:?}",
+                domain.domain, e
+            );
+            vec![]
+        }),
+        Err(_) => return Html(get_translation(&state, &locale, "domains-not-found").await),
+    };
+
+    let translations = crate::handlers::utils::get_translations_batch(
+        &state,
+        &locale,
+        &[
+            "domains-alias-domains-title",
+            "domains-alias-domains-description",
+            "domains-alias-domains-empty",
+            "alias-domains-add",
+            "status-enabled",
+            "status-disabled",
+        ],
+    )
+    .await;
+
+    let template = DomainAliasDomainsTemplate {
+        title: &translations["domains-alias-domains-title"],
+        description: &translations["domains-alias-domains-description"],
+        empty: &translations["domains-alias-domains-empty"],
+        add_alias_domain: &translations["alias-domains-add"],
+        status_enabled: &translations["status-enabled"],
+        status_disabled: &translations["status-disabled"],
+        alias_domains,
+    };
+    match template.render() {
+        Ok(content) => Html(content),
+        Err(e) => {
+            error!("Failed to render alias domains template: {:?}", e);
+            Html("Error rendering template".to_string())
+        }
+    }
+}
+
+/// Render the create form, or the edit form for `alias_domain_id`
+async fn render_form(
+    state: &AppState,
+    headers: &HeaderMap,
+    pool: &DbPool,
+    alias_domain_id: Option<i32>,
+    form: AliasDomainForm,
+    error: Option<String>,
+) -> Html<String> {
+    let locale = crate::handlers::utils::get_user_locale(headers);
+    let translations =
+        crate::handlers::utils::get_translations_batch(state, &locale, FORM_KEYS).await;
+
+    let domains = db::get_domains(pool)
+        .map(|domains| domains.into_iter().map(|domain| domain.domain).collect())
+        .unwrap_or_else(|e| {
+            error!("Failed to retrieve domains: {:?}", e);
+            vec![]
+        });
+    let (title, action) = match alias_domain_id {
+        Some(id) => (
+            &translations["alias-domains-edit"],
+            format!("/alias-domains/{id}"),
+        ),
+        None => (
+            &translations["alias-domains-new"],
+            "/alias-domains".to_string(),
+        ),
+    };
+
+    let content_template = AliasDomainFormTemplate {
+        title,
+        action: &action,
+        editing: alias_domain_id.is_some(),
+        form,
+        domains,
+        error,
+        form_error: &translations["form-error"],
+        field_alias_domain: &translations["alias-domains-field-alias-domain"],
+        field_target_domain: &translations["alias-domains-field-target-domain"],
+        field_enabled: &translations["alias-domains-field-enabled"],
+        field_alias_domain_help: &translations["alias-domains-field-alias-domain-help"],
+        field_target_domain_help: &translations["alias-domains-field-target-domain-help"],
+        placeholder_alias_domain: &translations["alias-domains-placeholder-alias-domain"],
+        select_target: &translations["alias-domains-select-target"],
+        action_save: &translations["action-save"],
+        action_cancel: &translations["action-cancel"],
+    };
+
+    render_template_with_title!(content_template, title, state, &locale, headers)
+}
+
+/// Check the admin may save this alias domain, returning the translation key of the problem
+fn check_form(
+    state: &AppState,
+    headers: &HeaderMap,
+    pool: &DbPool,
+    form: &AliasDomainForm,
+    alias_domain_id: Option<i32>,
+) -> Option<&'static str> {
+    let current_db_id = crate::handlers::auth::get_selected_database(state, headers)
+        .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
+    let admin = crate::handlers::auth::get_session(state, headers)
+        .map(|session| session.username)
+        .unwrap_or_default();
+    let operation = if alias_domain_id.is_some() {
+        "update_alias_domain"
+    } else {
+        "create_alias_domain"
+    };
+    if crate::handlers::utils::check_database_restrictions(
+        state,
+        Some(&admin),
+        &current_db_id,
+        operation,
+    )
+    .is_err()
+    {
+        return Some("error-operation-not-allowed");
+    }
+
+    if let Err(key) = form.validate() {
+        return Some(key);
+    }
+    db::alias_domain_conflict(pool, form, alias_domain_id).unwrap_or_else(|e| {
+        error!(
+            "Failed to check alias domain {}: {:?}",
+            form.alias_domain, e
+        );
+        Some("alias-domains-create-error")
+    })
+}
+
+// Show form for creating a new alias domain
+pub async fn create_form(State(state): State<AppState>, headers: HeaderMap) -> Html<String> {
+    let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
+        .await
+        .expect("Failed to get database pool");
+
+    let form = AliasDomainForm {
+        alias_domain: String::new(),
+        target_domain: String::new(),
+        enabled: true,
+    };
+    render_form(&state, &headers, &pool, None, form, None).await
+}
+
+// Create a new alias domain
+pub async fn create(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Form(form): Form<AliasDomainForm>,
+) -> Html<String> {
+    let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
+        .await
+        .expect("Failed to get database pool");
+    let locale = crate::handlers::utils::get_user_locale(&headers);
+    let form = form.normalized();
+
+    if let Some(key) = check_form(&state, &headers, &pool, &form, None) {
+        let error = get_translation(&state, &locale, key).await;
+        return render_form(&state, &headers, &pool, None, form, Some(error)).await;
+    }
+
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
+    match db::create_alias_domain(&pool, form.clone(), &audit) {
+        Ok(alias_domain) => {
+            info!(
+                "Created alias domain {} for {}",
+                alias_domain.alias_domain, alias_domain.target_domain
+            );
+            Html(format!(
+                "<script>window.location.href='/alias-domains/{}';</script>",
+                alias_domain.pkid
+            ))
+        }
+        Err(e) => {
+            error!("Failed to create alias domain: {:?}", e);
+            let error = get_translation(&state, &locale, "alias-domains-create-error").await;
+            render_form(&state, &headers, &pool, None, form, Some(error)).await
+        }
+    }
+}
+
+// Show form for editing an alias domain
+pub async fn edit_form(
+    State(state): State<AppState>,
+    Path(alias_domain_id): Path<i32>,
+    headers: HeaderMap,
+) -> Html<String> {
+    let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
+        .await
+        .expect("Failed to get database pool");
+    let locale = crate::handlers::utils::get_user_locale(&headers);
+
+    let alias_domain = match db::get_alias_domain_by_id(&pool, alias_domain_id) {
+        Ok(alias_domain) => alias_domain,
+        Err(_) => {
+            return Html(get_translation(&state, &locale, "alias-domains-not-found").await);
+        }
+    };
+
+    let form = AliasDomainForm {
+        alias_domain: alias_domain.alias_domain,
+        target_domain: alias_domain.target_domain,
+        enabled: alias_domain.enabled,
+    };
+    render_form(&state, &headers, &pool, Some(alias_domain_id), form, None).await
+}
+
+// Update an alias domain
+pub async fn update(
+    State(state): State<AppState>,
+    Path(alias_domain_id): Path<i32>,
+    headers: HeaderMap,
+    Form(form): Form<AliasDomainForm>,
+) -> Html<String> {
+    let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
+        .await
+        .expect("Failed to get database pool");
+    let locale = crate::handlers::utils::get_user_locale(&headers);
+    let form = form.normalized();
+
+    if let Some(key) = check_form(&state, &headers, &pool, &form, Some(alias_domain_id)) {
+        let error = get_translation(&state, &locale, key).await;
+        return render_form(
+            &state,
+            &headers,
+            &pool,
+            Some(alias_domain_id),
+            form,
+            Some(error),
+        )
+        .await;
+    }
+
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
+    match db::update_alias_domain(&pool, alias_domain_id, form.clone(), &audit) {
+        Ok(alias_domain) => {
+            info!("Updated alias domain {}", alias_domain.alias_domain);
+            Html(format!(
+                "<script>window.location.href='/alias-domains/{}';</script>",
+                alias_domain.pkid
+            ))
+        }
+        Err(Error::NotFound) => {
+            Html(get_translation(&state, &locale, "alias-domains-not-found").await)
+        }
+        Err(e) => {
+            error!("Failed to update alias domain {}: {:?}", alias_domain_id, e);
+            let error = get_translation(&state, &locale, "alias-domains-update-error").await;
+            render_form(
+                &state,
+                &headers,
+                &pool,
+                Some(alias_domain_id),
+                form,
+                Some(error),
+            )
+            .await
+        }
+    }
+}
+
+// Delete an alias domain
+pub async fn delete(
+    State(state): State<AppState>,
+    Path(alias_domain_id): Path<i32>,
+    headers: HeaderMap,
+) -> Html<String> {
+    let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
+        .await
+        .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
+    let locale = crate::handlers::utils::get_user_locale(&headers);
+
+    match db::delete_alias_domain(&pool, alias_domain_id, &audit) {
+        Ok(_) => {
+            info!("Deleted alias domain ID: {}", alias_domain_id);
+            Html("<script>window.location.href='/alias-domains';</script>".to_string())
+        }
+        Err(e) => {
+            error!("Failed to delete alias domain {}: {:?}", alias_domain_id, e);
+            Html(get_translation(&state, &locale, "alias-domains-delete-error").await)
+        }
+    }
+}
+
+// Toggle alias domain enabled status
+pub async fn toggle_enabled(
+    State(state): State<AppState>,
+    Path(alias_domain_id): Path<i32>,
+    headers: HeaderMap,
+) -> Html<String> {
+    let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
+        .await
+        .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
+    let locale = crate::handlers::utils::get_user_locale(&headers);
+
+    match db::toggle_alias_domain_enabled(&pool, alias_domain_id, &audit) {
+        Ok(alias_domain) => {
+            let (status_key, button_key, badge_class) = if alias_domain.enabled {
+                (
+                    "status-enabled",
+                    "action-disable",
+                    "inline-flex rounded-full bg-green-100 dark:bg-green-900 px-2 text-xs font-semibold leading-5 text-green-800 dark:text-green-200",
+                )
+            } else {
+                (
+                    "status-disabled",
+                    "action-enable",
+                    "inline-flex rounded-full bg-red-100 dark:bg-red-900 px-2 text-xs font-semibold leading-5 text-red-800 dark:text-red-200",
+                )
+            };
+            let status = get_translation(&state, &locale, status_key).await;
+            let button_text = get_translation(&state, &locale, button_key).await;
+
+            // The list and show pages name their toggle buttons differently
+            let button_id = if headers
+                .get("hx-target")
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or("")
+                .contains("alias-domain-show-status")
+            {
+                format!("alias-domain-show-button-{alias_domain_id}")
+            } else {
+                format!("alias-domain-button-{alias_domain_id}")
+            };
+            Html(format!(
+                "<span class=\"{badge_class}\">{status}</span><script>document.getElementById('{button_id}').textContent = '{button_text}';</script>"
+            ))
+        }
+        Err(Error::NotFound) => {
+            let not_found_msg = get_translation(&state, &locale, "alias-domains-not-found").await;
+            Html(format!(
+                "<span class=\"text-danger\">{not_found_msg}</span>"
+            ))
+        }
+        Err(e) => {
+            error!("Failed to toggle alias domain {}: {:?}", alias_domain_id, e);
+            let error_msg = get_translation(&state, &locale, "alias-domains-toggle-error").await;
+            Html(format!("<span class=\"text-danger\">{error_msg}</span>"))
+        }
+    }
+}
@@ -1,5 +1,7 @@
+use crate::delivery::{self, DestinationError};
 use crate::templates::aliases::*;
 use crate::templates::layout::BaseTemplate;
+use crate::validity;
 use crate::{
     db, get_entity_or_not_found, i18n::get_translation, models::*, render_template,
     render_template_with_title, AppState,
@@ -12,6 +14,7 @@ use axum::{
     Form,
 };
 use serde::Deserialize;
+use std::collections::HashMap;
 
 // Use the shared is_htmx_request from utils
 use crate::handlers::utils::is_htmx_request;
@@ -22,13 +25,37 @@ pub struct AliasPrefill {
     pub alias: Option<String>,
 }
 
+#[derive(Deserialize)]
+pub struct AliasListQuery {
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+    /// `expiring` lists only the aliases whose validity window ends soon
+    pub filter: Option<String>,
+}
+
 #[derive(serde::Deserialize)]
 pub struct AliasSearchQuery {
+    #[serde(alias = "new_destination")]
     pub destination: Option<String>,
     pub alias: Option<String>,
     pub limit: Option<i64>,
 }
 
+/// The destination editor's fields, posted with the rest of the alias form
+#[derive(Deserialize)]
+pub struct DestinationEditorForm {
+    #[serde(default)]
+    pub mail: String,
+    #[serde(default)]
+    pub destination: String,
+    #[serde(default)]
+    pub new_destination: String,
+    /// Set by the add button: add the addresses in `new_destination`
+    pub add: Option<String>,
+    /// Set by a remove button: the destination to drop
+    pub remove: Option<String>,
+}
+
 #[derive(serde::Deserialize)]
 pub struct DomainSearchQuery {
     pub domain: Option<String>,
@@ -38,17 +65,20 @@ pub struct DomainSearchQuery {
 pub async fn list(
     State(state): State<AppState>,
     headers: HeaderMap,
-    Query(params): Query<PaginationParams>,
+    Query(params): Query<AliasListQuery>,
 ) -> Html<String> {
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
     let page = params.page.unwrap_or(1);
     let per_page = params.per_page.unwrap_or(20);
-    let paginated_aliases = match db::get_aliases_paginated(&pool, page, per_page) {
-        Ok(aliases) => aliases,
-        Err(_) => PaginatedResult::new(vec![], 0, 1, per_page),
-    };
+    let expiring_only = params.filter.as_deref() == Some("expiring");
+    let scope = crate::handlers::utils::get_address_scope(&state, &headers);
+    let paginated_aliases =
+        match db::get_aliases_paginated(&pool, page, per_page, &scope, expiring_only) {
+            Ok(aliases) => aliases,
+            Err(_) => PaginatedResult::new(vec![], 0, 1, per_page),
+        };
     let locale = crate::handlers::utils::get_user_locale(&headers);
     let translations = crate::handlers::utils::get_translations_batch(
         &state,
@@ -68,6 +98,8 @@ pub async fn list(
             "aliases-disable-alias",
             "aliases-empty-title",
             "aliases-empty-description",
+            "aliases-filter-all",
+            "aliases-filter-expiring",
         ],
     )
     .await;
@@ -101,6 +133,11 @@ pub async fn list(
         disable_alias: &translations["aliases-disable-alias"],
         empty_title: &translations["aliases-empty-title"],
         empty_description: &translations["aliases-empty-description"],
+        expiring_only,
+        filter_all: &translations["aliases-filter-all"],
+        filter_expiring: &translations["aliases-filter-expiring"],
+        validity: crate::handlers::utils::get_validity_labels(&state, &locale).await,
+        now: chrono::Utc::now().naive_utc(),
     };
     render_template_with_title!(
         content_template,
@@ -111,6 +148,83 @@ pub async fn list(
     )
 }
 
+const FORM_KEYS: &[&str] = &[
+    "aliases-add-title",
+    "aliases-edit-title",
+    "aliases-edit-alias",
+    "aliases-new-alias",
+    "aliases-form-error",
+    "aliases-mail-address",
+    "aliases-destination",
+    "aliases-placeholder-mail",
+    "aliases-tooltip-mail",
+    "aliases-tooltip-destination",
+    "aliases-active",
+    "aliases-tooltip-active",
+    "aliases-cancel",
+    "aliases-update-alias",
+    "aliases-create-alias",
+];
+
+/// The new or edit alias form, with an error from a rejected submission
+async fn render_form(
+    state: &AppState,
+    headers: &HeaderMap,
+    alias: Option<Alias>,
+    form: AliasForm,
+    return_url: Option<String>,
+    error: Option<String>,
+) -> Html<String> {
+    let locale = crate::handlers::utils::get_user_locale(headers);
+    let translations =
+        crate::handlers::utils::get_translations_batch(state, &locale, FORM_KEYS).await;
+    let title = if alias.is_some() {
+        &translations["aliases-edit-title"]
+    } else {
+        &translations["aliases-add-title"]
+    };
+    let content_template = AliasFormTemplate {
+        title,
+        alias,
+        form,
+        error,
+        return_url,
+        edit_alias: &translations["aliases-edit-alias"],
+        new_alias: &translations["aliases-new-alias"],
+        form_error: &translations["aliases-form-error"],
+        mail_address: &translations["aliases-mail-address"],
+        destination: &translations["aliases-destination"],
+        placeholder_mail: &translations["aliases-placeholder-mail"],
+        tooltip_mail: &translations["aliases-tooltip-mail"],
+        tooltip_destination: &translations["aliases-tooltip-destination"],
+        active: &translations["aliases-active"],
+        tooltip_active: &translations["aliases-tooltip-active"],
+        validity: crate::handlers::utils::get_validity_labels(state, &locale).await,
+        cancel: &translations["aliases-cancel"],
+        update_alias: &translations["aliases-update-alias"],
+        create_alias: &translations["aliases-create-alias"],
+    };
+    render_template_with_title!(content_template, title, state, &locale, headers)
+}
+
+/// The translated message for rejected destinations
+async fn destination_error(state: &AppState, locale: &str, error: &DestinationError) -> String {
+    let args = HashMap::from([("address".to_string(), error.address().to_string())]);
+    crate::i18n::get_translation_with_args(state, locale, error.message_id(), args).await
+}
+
+/// The translated problem with a submitted alias, if any
+async fn form_error(state: &AppState, headers: &HeaderMap, form: &AliasForm) -> Option<String> {
+    let locale = crate::handlers::utils::get_user_locale(headers);
+    if let Err(e) = form.validate() {
+        return Some(destination_error(state, &locale, &e).await);
+    }
+    if let Err(e) = validity::check_window(form.valid_from, form.valid_until) {
+        return Some(get_translation(state, &locale, e.message_id()).await);
+    }
+    None
+}
+
 pub async fn new(
     State(state): State<AppState>,
     headers: HeaderMap,
@@ -131,53 +245,11 @@ pub async fn new(
         mail,
         destination: "".to_string(),
         enabled: true,
+        valid_from: None,
+        valid_until: None,
         return_url: None,
     };
-    let locale = crate::handlers::utils::get_user_locale(&headers);
-    let translations = crate::handlers::utils::get_translations_batch(
-        &state,
-        &locale,
-        &[
-            "aliases-add-title",
-            "aliases-edit-alias",
-            "aliases-new-alias",
-            "aliases-form-error",
-            "aliases-mail-address",
-            "aliases-destination",
-            "aliases-placeholder-mail",
-            "aliases-placeholder-destination",
-            "aliases-tooltip-mail",
-            "aliases-tooltip-destination",
-            "aliases-active",
-            "aliases-tooltip-active",
-            "aliases-cancel",
-            "aliases-update-alias",
-            "aliases-create-alias",
-        ],
-    )
-    .await;
-    let content_template = AliasFormTemplate {
-        title: &translations["aliases-add-title"],
-        alias: None,
-        form,
-        error: None,
-        return_url,
-        edit_alias: &translations["aliases-edit-alias"],
-        new_alias: &translations["aliases-new-alias"],
-        form_error: &translations["aliases-form-error"],
-        mail_address: &translations["aliases-mail-address"],
-        destination: &translations["aliases-destination"],
-        placeholder_mail: &translations["aliases-placeholder-mail"],
-        placeholder_destination: &translations["aliases-placeholder-destination"],
-        tooltip_mail: &translations["aliases-tooltip-mail"],
-        tooltip_destination: &translations["aliases-tooltip-destination"],
-        active: &translations["aliases-active"],
-        tooltip_active: &translations["aliases-tooltip-active"],
-        cancel: &translations["aliases-cancel"],
-        update_alias: &translations["aliases-update-alias"],
-        create_alias: &translations["aliases-create-alias"],
-    };
-    render_template!(content_template, &state, &locale, &headers)
+    render_form(&state, &headers, None, form, return_url, None).await
 }
 
 pub async fn show(
@@ -188,8 +260,9 @@ pub async fn show(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let scope = crate::handlers::utils::get_address_scope(&state, &headers);
     let alias = get_entity_or_not_found!(
-        db::get_alias(&pool, id),
+        db::get_alias_in_scope(&pool, id, &scope),
         &state,
         &crate::handlers::utils::get_user_locale(&headers),
         "aliases-not-found"
@@ -237,6 +310,8 @@ pub async fn show(
         disable_alias_button: &translations["aliases-disable-alias-button"],
         delete_alias: &translations["aliases-delete-alias"],
         delete_confirm: &translations["aliases-delete-confirm"],
+        validity: crate::handlers::utils::get_validity_labels(&state, &locale).await,
+        now: chrono::Utc::now().naive_utc(),
         alias,
     };
     render_template!(content_template, &state, &locale, &headers)
@@ -250,8 +325,9 @@ pub async fn edit(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let scope = crate::handlers::utils::get_address_scope(&state, &headers);
 
-    let alias = match db::get_alias(&pool, id) {
+    let alias = match db::get_alias_in_scope(&pool, id, &scope) {
         Ok(alias) => alias,
         Err(_) => return Html("Alias not found".to_string()),
     };
@@ -260,75 +336,12 @@ pub async fn edit(
         mail: alias.mail.clone(),
         destination: alias.destination.clone(),
         enabled: alias.enabled,
+        valid_from: alias.valid_from,
+        valid_until: alias.valid_until,
         return_url: None,
     };
 
-    let locale = crate::handlers::language::get_user_locale(&headers);
-    let title = get_translation(&state, &locale, "aliases-edit-title").await;
-    let edit_alias = get_translation(&state, &locale, "aliases-edit-alias").await;
-    let new_alias = get_translation(&state, &locale, "aliases-new-alias").await;
-    let form_error = get_translation(&state, &locale, "aliases-form-error").await;
-    let mail_address = get_translation(&state, &locale, "aliases-mail-address").await;
-    let destination = get_translation(&state, &locale, "aliases-destination").await;
-    let placeholder_mail = get_translation(&state, &locale, "aliases-placeholder-mail").await;
-    let placeholder_destination =
-        get_translation(&state, &locale, "aliases-placeholder-destination").await;
-    let tooltip_mail = get_translation(&state, &locale, "aliases-tooltip-mail").await;
-    let tooltip_destination = get_translation(&state, &locale, "aliases-tooltip-destination").await;
-    let active = get_translation(&state, &locale, "aliases-active").await;
-    let tooltip_active = get_translation(&state, &locale, "aliases-tooltip-active").await;
-    let cancel = get_translation(&state, &locale, "aliases-cancel").await;
-    let update_alias = get_translation(&state, &locale, "aliases-update-alias").await;
-    let create_alias = get_translation(&state, &locale, "aliases-create-alias").await;
-
-    let content_template = AliasFormTemplate {
-        title: &title,
-        alias: Some(alias),
-        form,
-        error: None,
-        return_url: None,
-        edit_alias: &edit_alias,
-        new_alias: &new_alias,
-        form_error: &form_error,
-        mail_address: &mail_address,
-        destination: &destination,
-        placeholder_mail: &placeholder_mail,
-        placeholder_destination: &placeholder_destination,
-        tooltip_mail: &tooltip_mail,
-        tooltip_destination: &tooltip_destination,
-        active: &active,
-        tooltip_active: &tooltip_active,
-        cancel: &cancel,
-        update_alias: &update_alias,
-        create_alias: &create_alias,
-    };
-    let content = content_template.render().unwrap();
-
-    if is_htmx_request(&headers) {
-        Html(content)
-    } else {
-        let current_db_id = crate::handlers::auth::get_selected_database(&headers)
-            .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
-        let current_db_label = state
-            .db_manager
-            .get_configs()
-            .iter()
-            .find(|db| db.id == current_db_id)
-            .map(|db| db.label.clone())
-            .unwrap_or_else(|| current_db_id.clone());
-        let template = BaseTemplate::with_i18n(
-            get_translation(&state, &locale, "aliases-title").await,
-            content,
-            &state,
-            &locale,
-            current_db_label,
-            current_db_id,
-        )
-        .await
-        .unwrap();
-
-        Html(template.render().unwrap())
-    }
+    render_form(&state, &headers, Some(alias), form, None, None).await
 }
 
 pub async fn create(
@@ -339,8 +352,20 @@ pub async fn create(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
+    let scope = crate::handlers::utils::get_address_scope(&state, &headers);
+    if !scope.allows(&form.mail) {
+        let locale = crate::handlers::language::get_user_locale(&headers);
+        return Html(get_translation(&state, &locale, "error-operation-not-allowed").await);
+    }
 
-    match db::create_alias(&pool, form.clone()) {
+    let form = form.normalized();
+    if let Some(error) = form_error(&state, &headers, &form).await {
+        let return_url = form.return_url.clone();
+        return render_form(&state, &headers, None, form, return_url, Some(error)).await;
+    }
+
+    match db::create_alias(&pool, form.clone(), &audit) {
         Ok(created_alias) => {
             // Extract domain from the created alias and redirect to domain show page
             let domain_name = created_alias.domain();
@@ -409,7 +434,11 @@ pub async fn create(
                         get_translation(&state, &locale, "reports-no-missing-aliases").await;
 
                     // Get alias report for the domain
-                    let alias_report = match db::get_domain_alias_report(&pool, &domain.domain) {
+                    let alias_report = match db::get_domain_alias_report(
+                        &pool,
+                        &state.config.get(),
+                        &domain.domain,
+                    ) {
                         Ok(report) => Some(report),
                         Err(e) => {
                             eprintln!("Error getting domain alias report: {e:?}");
@@ -491,6 +520,7 @@ pub async fn create(
                         action_view: &action_view,
                         enable_alias: &enable_alias,
                         disable_alias: &disable_alias,
+                        quota: crate::handlers::utils::get_quota_labels(&state, &locale).await,
                     };
                     let content = content_template.render().unwrap();
 
@@ -498,8 +528,11 @@ pub async fn create(
                         Html(content)
                     } else {
                         let locale = crate::handlers::language::get_user_locale(&headers);
-                        let current_db_id = crate::handlers::auth::get_selected_database(&headers)
-                            .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
+                        let current_db_id =
+                            crate::handlers::auth::get_selected_database(&state, &headers)
+                                .unwrap_or_else(|| {
+                                    state.db_manager.get_default_db_id().to_string()
+                                });
                         let current_db_label = state
                             .db_manager
                             .get_configs()
@@ -516,14 +549,15 @@ pub async fn create(
                             current_db_id,
                         )
                         .await
-                        .unwrap();
+                        .unwrap()
+                        .for_request(&state, &headers);
                         Html(template.render().unwrap())
                     }
                 }
                 Err(e) => {
                     eprintln!("Error finding domain by name: {e:?}");
                     // Fallback to aliases list if domain not found
-                    let aliases = match db::get_aliases(&pool) {
+                    let aliases = match db::get_aliases_in_scope(&pool, &scope) {
                         Ok(aliases) => aliases,
                         Err(e) => {
                             eprintln!("Error getting aliases: {e:?}");
@@ -552,6 +586,9 @@ pub async fn create(
                     let empty_title = get_translation(&state, &locale, "aliases-empty-title").await;
                     let empty_description =
                         get_translation(&state, &locale, "aliases-empty-description").await;
+                    let filter_all = get_translation(&state, &locale, "aliases-filter-all").await;
+                    let filter_expiring =
+                        get_translation(&state, &locale, "aliases-filter-expiring").await;
                     let paginated = PaginatedResult::new(aliases.clone(), 0, 1, 20);
                     let page_range: Vec<i64> = (1..=paginated.total_pages).collect();
                     let max_item = std::cmp::min(
@@ -577,6 +614,12 @@ pub async fn create(
                         disable_alias: &disable_alias,
                         empty_title: &empty_title,
                         empty_description: &empty_description,
+                        expiring_only: false,
+                        filter_all: &filter_all,
+                        filter_expiring: &filter_expiring,
+                        validity: crate::handlers::utils::get_validity_labels(&state, &locale)
+                            .await,
+                        now: chrono::Utc::now().naive_utc(),
                     };
                     let content = content_template.render().unwrap();
 
@@ -584,8 +627,11 @@ pub async fn create(
                         Html(content)
                     } else {
                         let locale = crate::handlers::language::get_user_locale(&headers);
-                        let current_db_id = crate::handlers::auth::get_selected_database(&headers)
-                            .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
+                        let current_db_id =
+                            crate::handlers::auth::get_selected_database(&state, &headers)
+                                .unwrap_or_else(|| {
+                                    state.db_manager.get_default_db_id().to_string()
+                                });
                         let current_db_label = state
                             .db_manager
                             .get_configs()
@@ -602,7 +648,8 @@ pub async fn create(
                             current_db_id,
                         )
                         .await
-                        .unwrap();
+                        .unwrap()
+                        .for_request(&state, &headers);
                         Html(template.render().unwrap())
                     }
                 }
@@ -624,75 +671,16 @@ pub async fn create(
                 _ => "An unexpected error occurred while creating the alias. Please try again.".to_string(),
             };
 
-            // Return to form with error message
-            let locale = crate::handlers::language::get_user_locale(&headers);
-            let title = get_translation(&state, &locale, "aliases-add-title").await;
-            let edit_alias = get_translation(&state, &locale, "aliases-edit-alias").await;
-            let new_alias = get_translation(&state, &locale, "aliases-new-alias").await;
-            let form_error = get_translation(&state, &locale, "aliases-form-error").await;
-            let mail_address = get_translation(&state, &locale, "aliases-mail-address").await;
-            let destination = get_translation(&state, &locale, "aliases-destination").await;
-            let placeholder_mail =
-                get_translation(&state, &locale, "aliases-placeholder-mail").await;
-            let placeholder_destination =
-                get_translation(&state, &locale, "aliases-placeholder-destination").await;
-            let tooltip_mail = get_translation(&state, &locale, "aliases-tooltip-mail").await;
-            let tooltip_destination =
-                get_translation(&state, &locale, "aliases-tooltip-destination").await;
-            let tooltip_active = get_translation(&state, &locale, "aliases-tooltip-active").await;
-            let cancel = get_translation(&state, &locale, "aliases-cancel").await;
-            let update_alias = get_translation(&state, &locale, "aliases-update-alias").await;
-            let create_alias = get_translation(&state, &locale, "aliases-create-alias").await;
-
-            let active = get_translation(&state, &locale, "aliases-active").await;
-            let content_template = AliasFormTemplate {
-                title: &title,
-                alias: None,
-                form: form.clone(),
-                error: Some(error_message),
-                return_url: None,
-                edit_alias: &edit_alias,
-                new_alias: &new_alias,
-                form_error: &form_error,
-                mail_address: &mail_address,
-                destination: &destination,
-                placeholder_mail: &placeholder_mail,
-                placeholder_destination: &placeholder_destination,
-                tooltip_mail: &tooltip_mail,
-                tooltip_destination: &tooltip_destination,
-                active: &active,
-                tooltip_active: &tooltip_active,
-                cancel: &cancel,
-                update_alias: &update_alias,
-                create_alias: &create_alias,
-            };
-            let content = content_template.render().unwrap();
-
-            if is_htmx_request(&headers) {
-                Html(content)
-            } else {
-                let locale = crate::handlers::language::get_user_locale(&headers);
-                let current_db_id = crate::handlers::auth::get_selected_database(&headers)
-                    .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
-                let current_db_label = state
-                    .db_manager
-                    .get_configs()
-                    .iter()
-                    .find(|db| db.id == current_db_id)
-                    .map(|db| db.label.clone())
-                    .unwrap_or_else(|| current_db_id.clone());
-                let template = BaseTemplate::with_i18n(
-                    get_translation(&state, &locale, "aliases-add-title").await,
-                    content,
-                    &state,
-                    &locale,
-                    current_db_label,
-                    current_db_id,
-                )
-                .await
-                .unwrap();
-                Html(template.render().unwrap())
-            }
+            let return_url = form.return_url.clone();
+            render_form(
+                &state,
+                &headers,
+                None,
+                form,
+                return_url,
+                Some(error_message),
+            )
+            .await
         }
     }
 }
@@ -706,8 +694,22 @@ pub async fn update(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
+    // Domain admins may neither edit nor move aliases outside their domains
+    let scope = crate::handlers::utils::get_address_scope(&state, &headers);
+    let Ok(original) = db::get_alias_in_scope(&pool, id, &scope) else {
+        return Html("Alias not found".to_string());
+    };
+    if !scope.allows(&form.mail) {
+        let locale = crate::handlers::language::get_user_locale(&headers);
+        return Html(get_translation(&state, &locale, "error-operation-not-allowed").await);
+    }
+    let form = form.normalized();
+    if let Some(error) = form_error(&state, &headers, &form).await {
+        return render_form(&state, &headers, Some(original), form, None, Some(error)).await;
+    }
 
-    match db::update_alias(&pool, id, form.clone()) {
+    match db::update_alias(&pool, id, form.clone(), &audit) {
         Ok(_) => {
             let alias = match db::get_alias(&pool, id) {
                 Ok(alias) => alias,
@@ -757,6 +759,8 @@ pub async fn update(
                 disable_alias_button: &disable_alias_button,
                 delete_alias: &delete_alias,
                 delete_confirm: &delete_confirm,
+                validity: crate::handlers::utils::get_validity_labels(&state, &locale).await,
+                now: chrono::Utc::now().naive_utc(),
             };
             let content = content_template.render().unwrap();
 
@@ -764,7 +768,7 @@ pub async fn update(
                 Html(content)
             } else {
                 let locale = crate::handlers::language::get_user_locale(&headers);
-                let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+                let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
                     .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
                 let current_db_label = state
                     .db_manager
@@ -782,7 +786,8 @@ pub async fn update(
                     current_db_id,
                 )
                 .await
-                .unwrap();
+                .unwrap()
+                .for_request(&state, &headers);
                 Html(template.render().unwrap())
             }
         }
@@ -809,75 +814,15 @@ pub async fn update(
             // Get the original alias for the form
             let original_alias = db::get_alias(&pool, id).ok();
 
-            // Return to form with error message
-            let locale = crate::handlers::language::get_user_locale(&headers);
-            let title = get_translation(&state, &locale, "aliases-edit-title").await;
-            let edit_alias = get_translation(&state, &locale, "aliases-edit-alias").await;
-            let new_alias = get_translation(&state, &locale, "aliases-new-alias").await;
-            let form_error = get_translation(&state, &locale, "aliases-form-error").await;
-            let mail_address = get_translation(&state, &locale, "aliases-mail-address").await;
-            let destination = get_translation(&state, &locale, "aliases-destination").await;
-            let placeholder_mail =
-                get_translation(&state, &locale, "aliases-placeholder-mail").await;
-            let placeholder_destination =
-                get_translation(&state, &locale, "aliases-placeholder-destination").await;
-            let tooltip_mail = get_translation(&state, &locale, "aliases-tooltip-mail").await;
-            let tooltip_destination =
-                get_translation(&state, &locale, "aliases-tooltip-destination").await;
-            let tooltip_active = get_translation(&state, &locale, "aliases-tooltip-active").await;
-            let cancel = get_translation(&state, &locale, "aliases-cancel").await;
-            let update_alias = get_translation(&state, &locale, "aliases-update-alias").await;
-            let create_alias = get_translation(&state, &locale, "aliases-create-alias").await;
-
-            let active = get_translation(&state, &locale, "aliases-active").await;
-            let content_template = AliasFormTemplate {
-                title: &title,
-                alias: original_alias,
-                form: form.clone(),
-                error: Some(error_message),
-                return_url: None,
-                edit_alias: &edit_alias,
-                new_alias: &new_alias,
-                form_error: &form_error,
-                mail_address: &mail_address,
-                destination: &destination,
-                placeholder_mail: &placeholder_mail,
-                placeholder_destination: &placeholder_destination,
-                tooltip_mail: &tooltip_mail,
-                tooltip_destination: &tooltip_destination,
-                active: &active,
-                tooltip_active: &tooltip_active,
-                cancel: &cancel,
-                update_alias: &update_alias,
-                create_alias: &create_alias,
-            };
-            let content = content_template.render().unwrap();
-
-            if is_htmx_request(&headers) {
-                Html(content)
-            } else {
-                let locale = crate::handlers::language::get_user_locale(&headers);
-                let current_db_id = crate::handlers::auth::get_selected_database(&headers)
-                    .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
-                let current_db_label = state
-                    .db_manager
-                    .get_configs()
-                    .iter()
-                    .find(|db| db.id == current_db_id)
-                    .map(|db| db.label.clone())
-                    .unwrap_or_else(|| current_db_id.clone());
-                let template = BaseTemplate::with_i18n(
-                    get_translation(&state, &locale, "aliases-edit-title").await,
-                    content,
-                    &state,
-                    &locale,
-                    current_db_label,
-                    current_db_id,
-                )
-                .await
-                .unwrap();
-                Html(template.render().unwrap())
-            }
+            render_form(
+                &state,
+                &headers,
+                original_alias,
+                form,
+                None,
+                Some(error_message),
+            )
+            .await
         }
     }
 }
@@ -890,10 +835,15 @@ pub async fn delete(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
+    let scope = crate::handlers::utils::get_address_scope(&state, &headers);
+    if db::get_alias_in_scope(&pool, id, &scope).is_err() {
+        return Html("Alias not found".to_string());
+    }
 
-    match db::delete_alias(&pool, id) {
+    match db::delete_alias(&pool, id, &audit) {
         Ok(_) => {
-            let aliases = match db::get_aliases(&pool) {
+            let aliases = match db::get_aliases_in_scope(&pool, &scope) {
                 Ok(aliases) => aliases,
                 Err(e) => {
                     eprintln!("Error getting aliases: {e:?}");
@@ -920,6 +870,8 @@ pub async fn delete(
             let empty_title = get_translation(&state, &locale, "aliases-empty-title").await;
             let empty_description =
                 get_translation(&state, &locale, "aliases-empty-description").await;
+            let filter_all = get_translation(&state, &locale, "aliases-filter-all").await;
+            let filter_expiring = get_translation(&state, &locale, "aliases-filter-expiring").await;
             let paginated = PaginatedResult::new(aliases.clone(), 0, 1, 20);
             let page_range: Vec<i64> = (1..=paginated.total_pages).collect();
             let max_item = std::cmp::min(
@@ -945,6 +897,11 @@ pub async fn delete(
                 disable_alias: &disable_alias,
                 empty_title: &empty_title,
                 empty_description: &empty_description,
+                expiring_only: false,
+                filter_all: &filter_all,
+                filter_expiring: &filter_expiring,
+                validity: crate::handlers::utils::get_validity_labels(&state, &locale).await,
+                now: chrono::Utc::now().naive_utc(),
             };
             let content = content_template.render().unwrap();
 
@@ -952,7 +909,7 @@ pub async fn delete(
                 Html(content)
             } else {
                 let locale = crate::handlers::language::get_user_locale(&headers);
-                let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+                let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
                     .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
                 let current_db_label = state
                     .db_manager
@@ -970,7 +927,8 @@ pub async fn delete(
                     current_db_id,
                 )
                 .await
-                .unwrap();
+                .unwrap()
+                .for_request(&state, &headers);
                 Html(template.render().unwrap())
             }
         }
@@ -986,8 +944,13 @@ pub async fn toggle_enabled(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
+    let scope = crate::handlers::utils::get_address_scope(&state, &headers);
+    if db::get_alias_in_scope(&pool, id, &scope).is_err() {
+        return Html("Alias not found".to_string());
+    }
 
-    match db::toggle_alias_enabled(&pool, id) {
+    match db::toggle_alias_enabled(&pool, id, &audit) {
         Ok(_) => {
             let alias = match db::get_alias(&pool, id) {
                 Ok(alias) => alias,
@@ -1037,13 +1000,15 @@ pub async fn toggle_enabled(
                 disable_alias_button: &disable_alias_button,
                 delete_alias: &delete_alias,
                 delete_confirm: &delete_confirm,
+                validity: crate::handlers::utils::get_validity_labels(&state, &locale).await,
+                now: chrono::Utc::now().naive_utc(),
             };
             let content = content_template.render().unwrap();
             if is_htmx_request(&headers) {
                 Html(content)
             } else {
                 let locale = crate::handlers::language::get_user_locale(&headers);
-                let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+                let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
                     .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
                 let current_db_label = state
                     .db_manager
@@ -1061,7 +1026,8 @@ pub async fn toggle_enabled(
                     current_db_id,
                 )
                 .await
-                .unwrap();
+                .unwrap()
+                .for_request(&state, &headers);
                 Html(template.render().unwrap())
             }
         }
@@ -1077,9 +1043,14 @@ pub async fn toggle_enabled_list(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
-    match db::toggle_alias_enabled(&pool, id) {
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
+    let scope = crate::handlers::utils::get_address_scope(&state, &headers);
+    if db::get_alias_in_scope(&pool, id, &scope).is_err() {
+        return Html("Alias not found".to_string());
+    }
+    match db::toggle_alias_enabled(&pool, id, &audit) {
         Ok(_) => {
-            let aliases = match db::get_aliases(&pool) {
+            let aliases = match db::get_aliases_in_scope(&pool, &scope) {
                 Ok(aliases) => aliases,
                 Err(e) => {
                     eprintln!("Error getting aliases: {e:?}");
@@ -1106,6 +1077,8 @@ pub async fn toggle_enabled_list(
             let empty_title = get_translation(&state, &locale, "aliases-empty-title").await;
             let empty_description =
                 get_translation(&state, &locale, "aliases-empty-description").await;
+            let filter_all = get_translation(&state, &locale, "aliases-filter-all").await;
+            let filter_expiring = get_translation(&state, &locale, "aliases-filter-expiring").await;
             let paginated = PaginatedResult::new(aliases.clone(), 0, 1, 20);
             let page_range: Vec<i64> = (1..=paginated.total_pages).collect();
             let max_item = std::cmp::min(
@@ -1131,6 +1104,11 @@ pub async fn toggle_enabled_list(
                 disable_alias: &disable_alias,
                 empty_title: &empty_title,
                 empty_description: &empty_description,
+                expiring_only: false,
+                filter_all: &filter_all,
+                filter_expiring: &filter_expiring,
+                validity: crate::handlers::utils::get_validity_labels(&state, &locale).await,
+                now: chrono::Utc::now().naive_utc(),
             };
             let content = content_template.render().unwrap();
 
@@ -1138,7 +1116,7 @@ pub async fn toggle_enabled_list(
                 Html(content)
             } else {
                 let locale = crate::handlers::language::get_user_locale(&headers);
-                let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+                let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
                     .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
                 let current_db_label = state
                     .db_manager
@@ -1156,7 +1134,8 @@ pub async fn toggle_enabled_list(
                     current_db_id,
                 )
                 .await
-                .unwrap();
+                .unwrap()
+                .for_request(&state, &headers);
                 Html(template.render().unwrap())
             }
         }
@@ -1172,7 +1151,12 @@ pub async fn toggle_enabled_show(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
-    match db::toggle_alias_enabled(&pool, id) {
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
+    let scope = crate::handlers::utils::get_address_scope(&state, &headers);
+    if db::get_alias_in_scope(&pool, id, &scope).is_err() {
+        return Html("Alias not found".to_string());
+    }
+    match db::toggle_alias_enabled(&pool, id, &audit) {
         Ok(_) => {
             let alias = match db::get_alias(&pool, id) {
                 Ok(alias) => alias,
@@ -1222,6 +1206,8 @@ pub async fn toggle_enabled_show(
                 disable_alias_button: &disable_alias_button,
                 delete_alias: &delete_alias,
                 delete_confirm: &delete_confirm,
+                validity: crate::handlers::utils::get_validity_labels(&state, &locale).await,
+                now: chrono::Utc::now().naive_utc(),
             };
             let content = content_template.render().unwrap();
 
@@ -1229,7 +1215,7 @@ pub async fn toggle_enabled_show(
                 Html(content)
             } else {
                 let locale = crate::handlers::language::get_user_locale(&headers);
-                let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+                let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
                     .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
                 let current_db_label = state
                     .db_manager
@@ -1247,7 +1233,8 @@ pub async fn toggle_enabled_show(
                     current_db_id,
                 )
                 .await
-                .unwrap();
+                .unwrap()
+                .for_request(&state, &headers);
                 Html(template.render().unwrap())
             }
         }
@@ -1263,9 +1250,14 @@ pub async fn toggle_enabled_domain_show(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
+    let scope = crate::handlers::utils::get_address_scope(&state, &headers);
+    if db::get_alias_in_scope(&pool, id, &scope).is_err() {
+        return Html("Alias not found".to_string());
+    }
 
     // First toggle the alias
-    match db::toggle_alias_enabled(&pool, id) {
+    match db::toggle_alias_enabled(&pool, id, &audit) {
         Ok(_) => {
             // Get the alias to find its domain
             let alias = match db::get_alias(&pool, id) {
@@ -1306,7 +1298,8 @@ pub async fn toggle_enabled_domain_show(
             let delete_confirm = get_translation(&state, &locale, "domains-delete-confirm").await;
 
             // Get alias report for the domain
-            let alias_report = db::get_domain_alias_report(&pool, &domain.domain).ok();
+            let alias_report =
+                db::get_domain_alias_report(&pool, &state.config.get(), &domain.domain).ok();
 
             // Get existing aliases for the domain
             let existing_aliases =
@@ -1397,6 +1390,7 @@ pub async fn toggle_enabled_domain_show(
                 action_view: &action_view,
                 enable_alias: &enable_alias,
                 disable_alias: &disable_alias,
+                quota: crate::handlers::utils::get_quota_labels(&state, &locale).await,
             };
             let content = content_template.render().unwrap();
 
@@ -1404,7 +1398,7 @@ pub async fn toggle_enabled_domain_show(
                 Html(content)
             } else {
                 let locale = crate::handlers::language::get_user_locale(&headers);
-                let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+                let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
                     .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
                 let current_db_label = state
                     .db_manager
@@ -1422,7 +1416,8 @@ pub async fn toggle_enabled_domain_show(
                     current_db_id,
                 )
                 .await
-                .unwrap();
+                .unwrap()
+                .for_request(&state, &headers);
                 Html(template.render().unwrap())
             }
         }
@@ -1430,6 +1425,170 @@ pub async fn toggle_enabled_domain_show(
     }
 }
 
+const DESTINATION_KEYS: &[&str] = &[
+    "aliases-placeholder-destination",
+    "aliases-destinations-add",
+    "aliases-destinations-remove",
+    "aliases-destinations-empty",
+    "aliases-destinations-reaches",
+    "aliases-destinations-invalid",
+];
+
+/// Resolve each destination of the alias on `mail` on its own
+///
+/// Destinations outside a domain admin's scope are listed without resolving
+/// them, so the editor does not reveal other domains' mailboxes.
+async fn destination_rows(
+    state: &AppState,
+    headers: &HeaderMap,
+    pool: &crate::DbPool,
+    mail: &str,
+    destinations: &[String],
+) -> Vec<DestinationRow> {
+    let locale = crate::handlers::utils::get_user_locale(headers);
+    let mut keys = crate::handlers::reports::DELIVERY_KEYS.to_vec();
+    keys.push("aliases-destinations-invalid");
+    let translations = crate::handlers::utils::get_translations_batch(state, &locale, &keys).await;
+    let scope = crate::handlers::utils::get_address_scope(state, headers);
+
+    let mut rows = Vec::new();
+    for address in destinations {
+        let mut row = DestinationRow {
+            address: address.clone(),
+            label: String::new(),
+            problem: false,
+            reaches: Vec::new(),
+        };
+        if !delivery::is_valid_destination(address) {
+            row.label = translations["aliases-destinations-invalid"].clone();
+            row.problem = true;
+        } else if scope.allows(address) {
+            match delivery::trace_destinations(pool, mail, std::slice::from_ref(address)) {
+                Ok(traces) => {
+                    let trace = &traces[0];
+                    let key = crate::handlers::reports::outcome_key(&trace.hops[0].outcome);
+                    row.label = translations.get(&key).cloned().unwrap_or(key);
+                    row.problem = trace.has_problems();
+                    if !trace.hops[0].outcome.is_destination() {
+                        row.reaches = trace
+                            .final_destinations()
+                            .into_iter()
+                            .map(|hop| hop.address.clone())
+                            .filter(|reached| scope.allows(reached))
+                            .collect();
+                    }
+                }
+                Err(e) => tracing::error!("Error resolving destination {}: {:?}", address, e),
+            }
+        }
+        rows.push(row);
+    }
+    rows
+}
+
+/// The destination editor of the alias form, after adding or removing a destination
+pub async fn destination_editor(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Form(form): Form<DestinationEditorForm>,
+) -> Html<String> {
+    let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
+        .await
+        .expect("Failed to get database pool");
+    let locale = crate::handlers::utils::get_user_locale(&headers);
+    let translations =
+        crate::handlers::utils::get_translations_batch(&state, &locale, DESTINATION_KEYS).await;
+
+    let mut destinations = AliasForm {
+        mail: form.mail.clone(),
+        destination: form.destination,
+        enabled: true,
+        valid_from: None,
+        valid_until: None,
+        return_url: None,
+    }
+    .destinations();
+    let mut new_destination = form.new_destination;
+    let mut error = None;
+    if let Some(remove) = form.remove {
+        let remove = remove.trim().to_lowercase();
+        destinations.retain(|address| *address != remove);
+    } else if form.add.is_some() {
+        let added = delivery::split_destinations(&new_destination);
+        match added
+            .iter()
+            .find(|address| !delivery::is_valid_destination(address))
+        {
+            Some(invalid) => {
+                let e = DestinationError::InvalidAddress(invalid.clone());
+                error = Some(destination_error(&state, &locale, &e).await);
+            }
+            None => {
+                for address in added {
+                    if !destinations.contains(&address) {
+                        destinations.push(address);
+                    }
+                }
+                new_destination.clear();
+            }
+        }
+    }
+
+    let rows = destination_rows(&state, &headers, &pool, &form.mail, &destinations).await;
+    let destination = destinations.join(", ");
+    let template = DestinationEditorTemplate {
+        destination: &destination,
+        rows: &rows,
+        new_destination: &new_destination,
+        error: error.as_deref(),
+        placeholder: &translations["aliases-placeholder-destination"],
+        add: &translations["aliases-destinations-add"],
+        remove: &translations["aliases-destinations-remove"],
+        empty: &translations["aliases-destinations-empty"],
+        reaches: &translations["aliases-destinations-reaches"],
+    };
+    match template.render() {
+        Ok(content) => Html(content),
+        Err(e) => {
+            tracing::error!("Failed to render destination editor: {:?}", e);
+            Html("Failed to render destinations".to_string())
+        }
+    }
+}
+
+/// The destinations of an alias with where each one leads, for the alias page
+pub async fn destinations(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    headers: HeaderMap,
+) -> Html<String> {
+    let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
+        .await
+        .expect("Failed to get database pool");
+    let scope = crate::handlers::utils::get_address_scope(&state, &headers);
+    let Ok(alias) = db::get_alias_in_scope(&pool, id, &scope) else {
+        return Html("Alias not found".to_string());
+    };
+    let locale = crate::handlers::utils::get_user_locale(&headers);
+    let translations =
+        crate::handlers::utils::get_translations_batch(&state, &locale, DESTINATION_KEYS).await;
+
+    let destinations = delivery::split_destinations(&alias.destination);
+    let rows = destination_rows(&state, &headers, &pool, &alias.mail, &destinations).await;
+    let template = DestinationListTemplate {
+        rows: &rows,
+        empty: &translations["aliases-destinations-empty"],
+        reaches: &translations["aliases-destinations-reaches"],
+    };
+    match template.render() {
+        Ok(content) => Html(content),
+        Err(e) => {
+            tracing::error!("Failed to render alias destinations: {:?}", e);
+            Html(alias.destination)
+        }
+    }
+}
+
 pub async fn search(
     State(state): State<AppState>,
     headers: HeaderMap,
@@ -1466,18 +1625,22 @@ pub async fn search(
     }
 
     let limit = query.limit.unwrap_or(10);
+    let scope = crate::handlers::utils::get_address_scope(&state, &headers);
 
     // --- Collect all matching values from aliases and users ---
     let mut values = std::collections::HashSet::new();
 
     // 1. Alias mail and destination
-    if let Ok(aliases) = db::search_aliases(&pool, &query_string, limit * 2) {
+    if let Ok(aliases) = db::search_aliases(&pool, &query_string, limit * 2, &scope) {
         for alias in aliases {
             if alias.mail.contains(&query_string) {
                 values.insert(alias.mail);
             }
-            if alias.destination.contains(&query_string) {
-                values.insert(alias.destination);
+            // Suggest single addresses, not whole destination lists
+            for destination in delivery::split_destinations(&alias.destination) {
+                if destination.contains(&query_string) {
+                    values.insert(destination);
+                }
             }
         }
     }
@@ -1493,7 +1656,7 @@ pub async fn search(
             .limit(limit * 2)
             .load::<String>(&mut conn)
             .unwrap_or_default();
-        for user_id in user_ids {
+        for user_id in user_ids.into_iter().filter(|id| scope.allows(id)) {
             values.insert(user_id);
         }
     }
@@ -1564,10 +1727,14 @@ pub async fn domain_search(
     }
 
     let limit = query.limit.unwrap_or(10);
+    let scope = crate::handlers::utils::get_address_scope(&state, &headers);
     let search_results = db::search_domains(&pool, &query_string, limit);
 
     let domains = match search_results {
-        Ok(domains) => domains,
+        Ok(domains) => domains
+            .into_iter()
+            .filter(|domain| scope.allows_domain(&domain.domain))
+            .collect(),
         Err(_) => vec![],
     };
 
@@ -0,0 +1,1702 @@
+//! Versioned JSON API under `/api/v1/`
+//!
+//! Requests authenticate with `Authorization: Bearer <token>`, using tokens
+//! configured per admin in `config.toml`. Each token acts with its admin's
+//! role on the database it targets.
+
+use crate::config::AdminRole;
+use crate::db::{self, AddressScope, AuditContext};
+use crate::models::*;
+use crate::validity;
+use crate::{AppState, DbPool};
+use axum::{
+    extract::{
+        rejection::{JsonRejection, PathRejection, QueryRejection},
+        Path, Query, Request, State,
+    },
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use serde::{Deserialize, Serialize};
+use utoipa::openapi::security::{Http, HttpAuthScheme, SecurityScheme};
+use utoipa::{IntoParams, OpenApi, ToSchema};
+
+/// Largest page size a client may request
+const MAX_PER_PAGE: i64 = 500;
+
+/// Identity of an authenticated API request, set by [`require_api_token`]
+#[derive(Debug, Clone)]
+pub struct ApiAuth {
+    pub admin: String,
+    pub token_name: String,
+    pub database_id: String,
+    pub role: AdminRole,
+    /// Users and aliases the token may reach; domain admins are limited to theirs
+    pub scope: AddressScope,
+}
+
+impl ApiAuth {
+    fn audit(&self) -> AuditContext {
+        AuditContext::new(self.admin.clone(), self.database_id.clone())
+    }
+}
+
+/// Error returned by every API endpoint as `{"error": {"code", "message"}}`
+#[derive(Debug)]
+pub struct ApiError {
+    pub status: StatusCode,
+    pub code: &'static str,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApiErrorBody {
+    pub error: ApiErrorDetail,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApiErrorDetail {
+    pub code: String,
+    pub message: String,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::UNAUTHORIZED, "unauthorized", message)
+    }
+
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::FORBIDDEN, "forbidden", message)
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::NOT_FOUND, "not_found", message)
+    }
+
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, "bad_request", message)
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        Self::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "validation_error",
+            message,
+        )
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = ApiErrorBody {
+            error: ApiErrorDetail {
+                code: self.code.to_string(),
+                message: self.message,
+            },
+        };
+        let mut response = (self.status, Json(body)).into_response();
+        if self.status == StatusCode::UNAUTHORIZED {
+            response
+                .headers_mut()
+                .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+        }
+        response
+    }
+}
+
+impl From<DieselError> for ApiError {
+    fn from(error: DieselError) -> Self {
+        match error {
+            DieselError::NotFound => ApiError::not_found("Resource not found"),
+            DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, info) => {
+                ApiError::new(StatusCode::CONFLICT, "conflict", info.message())
+            }
+            DieselError::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, info) => {
+                ApiError::new(StatusCode::CONFLICT, "conflict", info.message())
+            }
+            DieselError::DatabaseError(DatabaseErrorKind::CheckViolation, info) => {
+                ApiError::validation(info.message())
+            }
+            other => {
+                tracing::error!("API database error: {:?}", other);
+                ApiError::new(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal_error",
+                    "Database error",
+                )
+            }
+        }
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        ApiError::bad_request(rejection.body_text())
+    }
+}
+
+impl From<QueryRejection> for ApiError {
+    fn from(rejection: QueryRejection) -> Self {
+        ApiError::bad_request(rejection.body_text())
+    }
+}
+
+impl From<PathRejection> for ApiError {
+    fn from(rejection: PathRejection) -> Self {
+        ApiError::bad_request(rejection.body_text())
+    }
+}
+
+pub type ApiResult<T> = Result<T, ApiError>;
+
+/// Middleware authenticating API requests with a bearer token
+pub async fn require_api_token(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path().to_string();
+    let token = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim)
+        .filter(|token| !token.is_empty());
+
+    let Some(token) = token else {
+        return ApiError::unauthorized("Missing bearer token").into_response();
+    };
+
+    let config = state.config.get();
+    let Some((admin, api_token)) = config.find_api_token(token) else {
+        tracing::warn!("Invalid API token used for: {path}");
+        return ApiError::unauthorized("Invalid API token").into_response();
+    };
+
+    let Some(role) = admin.role_for_database(&api_token.database) else {
+        tracing::warn!(
+            "API token '{}' of admin '{}' targets database '{}' without a grant",
+            api_token.name,
+            admin.username,
+            api_token.database
+        );
+        return ApiError::forbidden(format!("No access to database '{}'", api_token.database))
+            .into_response();
+    };
+
+    // Domain admins only manage users and aliases
+    if admin.is_domain_admin()
+        && !["/api/v1/users", "/api/v1/aliases"]
+            .iter()
+            .any(|prefix| path == *prefix || path.starts_with(&format!("{prefix}/")))
+    {
+        return ApiError::forbidden("Domain admins may only manage users and aliases")
+            .into_response();
+    }
+
+    request.extensions_mut().insert(ApiAuth {
+        admin: admin.username.clone(),
+        token_name: api_token.name.clone(),
+        database_id: api_token.database.clone(),
+        role,
+        scope: admin.address_scope(),
+    });
+    next.run(request).await
+}
+
+async fn api_pool(state: &AppState, auth: &ApiAuth) -> ApiResult<DbPool> {
+    state
+        .db_manager
+        .get_pool(&auth.database_id)
+        .await
+        .ok_or_else(|| {
+            ApiError::new(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "database_unavailable",
+                format!("Database '{}' is not available", auth.database_id),
+            )
+        })
+}
+
+/// Apply the admin's grant and the database feature toggles to a write
+fn check_write(state: &AppState, auth: &ApiAuth, operation: &str) -> ApiResult<()> {
+    crate::handlers::utils::check_database_restrictions(
+        state,
+        Some(&auth.admin),
+        &auth.database_id,
+        operation,
+    )
+    .map_err(|_| {
+        ApiError::forbidden(format!(
+            "Operation '{operation}' is not allowed on database '{}'",
+            auth.database_id
+        ))
+    })
+}
+
+/// Refuse new addresses outside a domain admin's domains
+fn check_scope(auth: &ApiAuth, address: &str) -> ApiResult<()> {
+    if auth.scope.allows(address) {
+        Ok(())
+    } else {
+        Err(ApiError::forbidden(format!(
+            "'{address}' is outside the domains this token may manage"
+        )))
+    }
+}
+
+fn require_field(field: &str, value: &str) -> ApiResult<()> {
+    if value.trim().is_empty() {
+        Err(ApiError::validation(format!("'{field}' is required")))
+    } else {
+        Ok(())
+    }
+}
+
+/// Reject a new mailbox password that breaks the database's policy
+fn check_password_policy(
+    state: &AppState,
+    auth: &ApiAuth,
+    password: &str,
+    address: &str,
+    name: &str,
+) -> ApiResult<()> {
+    let config = state.config.get();
+    let policy = config.password_policy(&auth.database_id);
+    let violations = crate::password::check_policy(policy, password, address, name);
+    if violations.is_empty() {
+        return Ok(());
+    }
+    let messages: Vec<String> = violations.iter().map(ToString::to_string).collect();
+    Err(ApiError::validation(messages.join("; ")))
+}
+
+fn page_params(params: &PaginationParams) -> (i64, i64) {
+    (
+        params.page.unwrap_or(1).max(1),
+        params.per_page.unwrap_or(20).clamp(1, MAX_PER_PAGE),
+    )
+}
+
+fn deleted(count: usize, what: &str) -> ApiResult<StatusCode> {
+    if count == 0 {
+        Err(ApiError::not_found(format!("{what} not found")))
+    } else {
+        Ok(StatusCode::NO_CONTENT)
+    }
+}
+
+// Domain endpoints
+
+/// List domains
+#[utoipa::path(
+    get,
+    path = "/api/v1/domains",
+    tag = "domains",
+    params(PaginationParams),
+    responses(
+        (status = 200, body = PaginatedResult<Domain>),
+        (status = 401, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn list_domains(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    query: Result<Query<PaginationParams>, QueryRejection>,
+) -> ApiResult<Json<PaginatedResult<Domain>>> {
+    let Query(params) = query?;
+    let pool = api_pool(&state, &auth).await?;
+    let (page, per_page) = page_params(&params);
+    Ok(Json(db::get_domains_paginated(&pool, page, per_page)?))
+}
+
+/// Show a domain
+#[utoipa::path(
+    get,
+    path = "/api/v1/domains/{id}",
+    tag = "domains",
+    params(("id" = i32, Path, description = "pkid of the domain")),
+    responses(
+        (status = 200, body = Domain),
+        (status = 404, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn show_domain(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<Json<Domain>> {
+    let Path(id) = path?;
+    let pool = api_pool(&state, &auth).await?;
+    Ok(Json(db::get_domain(&pool, id)?))
+}
+
+/// Create a domain
+#[utoipa::path(
+    post,
+    path = "/api/v1/domains",
+    tag = "domains",
+    request_body = DomainForm,
+    responses(
+        (status = 201, body = Domain),
+        (status = 403, body = ApiErrorBody),
+        (status = 409, body = ApiErrorBody),
+        (status = 422, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn create_domain(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    payload: Result<Json<DomainForm>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<Domain>)> {
+    check_write(&state, &auth, "create_domain")?;
+    let Json(form) = payload?;
+    require_field("domain", &form.domain)?;
+    let pool = api_pool(&state, &auth).await?;
+
+    let new_domain = NewDomain {
+        domain: form.domain.trim().to_string(),
+        transport: Some(form.transport),
+        enabled: form.enabled,
+        default_quota_bytes: form.default_quota_bytes,
+        default_quota_messages: form.default_quota_messages,
+        quota_cap_bytes: form.quota_cap_bytes,
+    };
+    let domain = db::create_domain(&pool, new_domain, &auth.audit())?;
+    Ok((StatusCode::CREATED, Json(domain)))
+}
+
+/// Update a domain
+#[utoipa::path(
+    put,
+    path = "/api/v1/domains/{id}",
+    tag = "domains",
+    params(("id" = i32, Path, description = "pkid of the domain")),
+    request_body = DomainForm,
+    responses(
+        (status = 200, body = Domain),
+        (status = 403, body = ApiErrorBody),
+        (status = 404, body = ApiErrorBody),
+        (status = 422, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn update_domain(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    path: Result<Path<i32>, PathRejection>,
+    payload: Result<Json<DomainForm>, JsonRejection>,
+) -> ApiResult<Json<Domain>> {
+    check_write(&state, &auth, "update_domain")?;
+    let Path(id) = path?;
+    let Json(mut form) = payload?;
+    require_field("domain", &form.domain)?;
+    form.domain = form.domain.trim().to_string();
+    let pool = api_pool(&state, &auth).await?;
+    Ok(Json(db::update_domain(&pool, id, form, &auth.audit())?))
+}
+
+/// Delete a domain
+#[utoipa::path(
+    delete,
+    path = "/api/v1/domains/{id}",
+    tag = "domains",
+    params(("id" = i32, Path, description = "pkid of the domain")),
+    responses(
+        (status = 204),
+        (status = 403, body = ApiErrorBody),
+        (status = 404, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn delete_domain(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<StatusCode> {
+    check_write(&state, &auth, "delete_domain")?;
+    let Path(id) = path?;
+    let pool = api_pool(&state, &auth).await?;
+    deleted(db::delete_domain(&pool, id, &auth.audit())?, "Domain")
+}
+
+/// Enable or disable a domain
+#[utoipa::path(
+    post,
+    path = "/api/v1/domains/{id}/toggle",
+    tag = "domains",
+    params(("id" = i32, Path, description = "pkid of the domain")),
+    responses(
+        (status = 200, body = Domain),
+        (status = 403, body = ApiErrorBody),
+        (status = 404, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn toggle_domain(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<Json<Domain>> {
+    check_write(&state, &auth, "toggle_domain")?;
+    let Path(id) = path?;
+    let pool = api_pool(&state, &auth).await?;
+    Ok(Json(db::toggle_domain_enabled(&pool, id, &auth.audit())?))
+}
+
+// User endpoints
+
+/// List users
+#[utoipa::path(
+    get,
+    path = "/api/v1/users",
+    tag = "users",
+    params(PaginationParams),
+    responses(
+        (status = 200, body = PaginatedResult<PublicUser>),
+        (status = 401, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn list_users(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    query: Result<Query<PaginationParams>, QueryRejection>,
+) -> ApiResult<Json<PaginatedResult<PublicUser>>> {
+    let Query(params) = query?;
+    let pool = api_pool(&state, &auth).await?;
+    let (page, per_page) = page_params(&params);
+    let users = db::get_users_paginated(&pool, page, per_page, &auth.scope)?;
+    Ok(Json(users.map(PublicUser::from)))
+}
+
+/// Show a user
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/{id}",
+    tag = "users",
+    params(("id" = String, Path, description = "Email address of the user")),
+    responses(
+        (status = 200, body = PublicUser),
+        (status = 404, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn show_user(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    path: Result<Path<String>, PathRejection>,
+) -> ApiResult<Json<PublicUser>> {
+    let Path(id) = path?;
+    let pool = api_pool(&state, &auth).await?;
+    Ok(Json(db::get_user_in_scope(&pool, &id, &auth.scope)?.into()))
+}
+
+/// Create a user
+#[utoipa::path(
+    post,
+    path = "/api/v1/users",
+    tag = "users",
+    request_body = UserForm,
+    responses(
+        (status = 201, body = PublicUser),
+        (status = 403, body = ApiErrorBody),
+        (status = 409, body = ApiErrorBody),
+        (status = 422, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn create_user(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    payload: Result<Json<UserForm>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<PublicUser>)> {
+    check_write(&state, &auth, "create_user")?;
+    let Json(mut form) = payload?;
+    require_field("id", &form.id)?;
+    require_field("password", &form.password)?;
+    form.id = form.id.trim().to_string();
+    check_scope(&auth, &form.id)?;
+    check_password_policy(&state, &auth, &form.password, &form.id, &form.name)?;
+    let pool = api_pool(&state, &auth).await?;
+    let scheme = state.config.get().password_scheme(&auth.database_id);
+    let user = db::create_user(&pool, form, scheme, &auth.audit())?;
+    Ok((StatusCode::CREATED, Json(user.into())))
+}
+
+/// Update a user; a non-empty `password` also replaces the password
+#[utoipa::path(
+    put,
+    path = "/api/v1/users/{id}",
+    tag = "users",
+    params(("id" = String, Path, description = "Email address of the user")),
+    request_body = UserForm,
+    responses(
+        (status = 200, body = PublicUser),
+        (status = 403, body = ApiErrorBody),
+        (status = 404, body = ApiErrorBody),
+        (status = 422, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn update_user(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    path: Result<Path<String>, PathRejection>,
+    payload: Result<Json<UserForm>, JsonRejection>,
+) -> ApiResult<Json<PublicUser>> {
+    check_write(&state, &auth, "update_user")?;
+    let Path(id) = path?;
+    let Json(mut form) = payload?;
+    require_field("id", &form.id)?;
+    form.id = form.id.trim().to_string();
+    check_scope(&auth, &form.id)?;
+    let pool = api_pool(&state, &auth).await?;
+    db::get_user_in_scope(&pool, &id, &auth.scope)?;
+
+    let audit = auth.audit();
+    let password = std::mem::take(&mut form.password);
+    if !password.is_empty() {
+        check_password_policy(&state, &auth, &password, &form.id, &form.name)?;
+    }
+    let mut user = db::update_user(&pool, id, form, &audit)?;
+    if !password.is_empty() {
+        let scheme = state.config.get().password_scheme(&auth.database_id);
+        db::update_user_password(&pool, user.id.clone(), &password, scheme, &audit)?;
+        user = db::get_user(&pool, user.id)?;
+    }
+    Ok(Json(user.into()))
+}
+
+/// Delete a user
+#[utoipa::path(
+    delete,
+    path = "/api/v1/users/{id}",
+    tag = "users",
+    params(("id" = String, Path, description = "Email address of the user")),
+    responses(
+        (status = 204),
+        (status = 403, body = ApiErrorBody),
+        (status = 404, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn delete_user(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    path: Result<Path<String>, PathRejection>,
+) -> ApiResult<StatusCode> {
+    check_write(&state, &auth, "delete_user")?;
+    let Path(id) = path?;
+    let pool = api_pool(&state, &auth).await?;
+    db::get_user_in_scope(&pool, &id, &auth.scope)?;
+    deleted(db::delete_user(&pool, id, &auth.audit())?, "User")
+}
+
+/// Enable or disable a user
+#[utoipa::path(
+    post,
+    path = "/api/v1/users/{id}/toggle",
+    tag = "users",
+    params(("id" = String, Path, description = "Email address of the user")),
+    responses(
+        (status = 200, body = PublicUser),
+        (status = 403, body = ApiErrorBody),
+        (status = 404, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn toggle_user(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    path: Result<Path<String>, PathRejection>,
+) -> ApiResult<Json<PublicUser>> {
+    check_write(&state, &auth, "toggle_user")?;
+    let Path(id) = path?;
+    let pool = api_pool(&state, &auth).await?;
+    db::get_user_in_scope(&pool, &id, &auth.scope)?;
+    Ok(Json(
+        db::toggle_user_enabled(&pool, id, &auth.audit())?.into(),
+    ))
+}
+
+// Alias endpoints
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AliasFilterQuery {
+    /// Only aliases whose validity window ends within the next week, soonest first
+    #[serde(default)]
+    pub expiring: bool,
+}
+
+/// List aliases
+#[utoipa::path(
+    get,
+    path = "/api/v1/aliases",
+    tag = "aliases",
+    params(PaginationParams, AliasFilterQuery),
+    responses(
+        (status = 200, body = PaginatedResult<Alias>),
+        (status = 401, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn list_aliases(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    query: Result<Query<PaginationParams>, QueryRejection>,
+    filter: Result<Query<AliasFilterQuery>, QueryRejection>,
+) -> ApiResult<Json<PaginatedResult<Alias>>> {
+    let Query(params) = query?;
+    let Query(filter) = filter?;
+    let pool = api_pool(&state, &auth).await?;
+    let (page, per_page) = page_params(&params);
+    Ok(Json(db::get_aliases_paginated(
+        &pool,
+        page,
+        per_page,
+        &auth.scope,
+        filter.expiring,
+    )?))
+}
+
+/// Show a alias
+#[utoipa::path(
+    get,
+    path = "/api/v1/aliases/{id}",
+    tag = "aliases",
+    params(("id" = i32, Path, description = "pkid of the alias")),
+    responses(
+        (status = 200, body = Alias),
+        (status = 404, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn show_alias(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<Json<Alias>> {
+    let Path(id) = path?;
+    let pool = api_pool(&state, &auth).await?;
+    Ok(Json(db::get_alias_in_scope(&pool, id, &auth.scope)?))
+}
+
+/// Create a alias
+#[utoipa::path(
+    post,
+    path = "/api/v1/aliases",
+    tag = "aliases",
+    request_body = AliasForm,
+    responses(
+        (status = 201, body = Alias),
+        (status = 403, body = ApiErrorBody),
+        (status = 409, body = ApiErrorBody),
+        (status = 422, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn create_alias(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    payload: Result<Json<AliasForm>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<Alias>)> {
+    check_write(&state, &auth, "create_alias")?;
+    let Json(form) = payload?;
+    require_field("mail", &form.mail)?;
+    let form = form.normalized();
+    form.validate()
+        .map_err(|e| ApiError::validation(e.to_string()))?;
+    validity::check_window(form.valid_from, form.valid_until)
+        .map_err(|e| ApiError::validation(e.to_string()))?;
+    check_scope(&auth, &form.mail)?;
+    let pool = api_pool(&state, &auth).await?;
+    let alias = db::create_alias(&pool, form, &auth.audit())?;
+    Ok((StatusCode::CREATED, Json(alias)))
+}
+
+/// Update a alias
+#[utoipa::path(
+    put,
+    path = "/api/v1/aliases/{id}",
+    tag = "aliases",
+    params(("id" = i32, Path, description = "pkid of the alias")),
+    request_body = AliasForm,
+    responses(
+        (status = 200, body = Alias),
+        (status = 403, body = ApiErrorBody),
+        (status = 404, body = ApiErrorBody),
+        (status = 422, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn update_alias(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    path: Result<Path<i32>, PathRejection>,
+    payload: Result<Json<AliasForm>, JsonRejection>,
+) -> ApiResult<Json<Alias>> {
+    check_write(&state, &auth, "update_alias")?;
+    let Path(id) = path?;
+    let Json(form) = payload?;
+    require_field("mail", &form.mail)?;
+    let form = form.normalized();
+    form.validate()
+        .map_err(|e| ApiError::validation(e.to_string()))?;
+    validity::check_window(form.valid_from, form.valid_until)
+        .map_err(|e| ApiError::validation(e.to_string()))?;
+    check_scope(&auth, &form.mail)?;
+    let pool = api_pool(&state, &auth).await?;
+    db::get_alias_in_scope(&pool, id, &auth.scope)?;
+    Ok(Json(db::update_alias(&pool, id, form, &auth.audit())?))
+}
+
+/// Delete a alias
+#[utoipa::path(
+    delete,
+    path = "/api/v1/aliases/{id}",
+    tag = "aliases",
+    params(("id" = i32, Path, description = "pkid of the alias")),
+    responses(
+        (status = 204),
+        (status = 403, body = ApiErrorBody),
+        (status = 404, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn delete_alias(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<StatusCode> {
+    check_write(&state, &auth, "delete_alias")?;
+    let Path(id) = path?;
+    let pool = api_pool(&state, &auth).await?;
+    db::get_alias_in_scope(&pool, id, &auth.scope)?;
+    deleted(db::delete_alias(&pool, id, &auth.audit())?, "Alias")
+}
+
+/// Enable or disable a alias
+#[utoipa::path(
+    post,
+    path = "/api/v1/aliases/{id}/toggle",
+    tag = "aliases",
+    params(("id" = i32, Path, description = "pkid of the alias")),
+    responses(
+        (status = 200, body = Alias),
+        (status = 403, body = ApiErrorBody),
+        (status = 404, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn toggle_alias(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<Json<Alias>> {
+    check_write(&state, &auth, "toggle_alias")?;
+    let Path(id) = path?;
+    let pool = api_pool(&state, &auth).await?;
+    db::get_alias_in_scope(&pool, id, &auth.scope)?;
+    Ok(Json(db::toggle_alias_enabled(&pool, id, &auth.audit())?))
+}
+
+// Backup endpoints
+
+/// List backup domains
+#[utoipa::path(
+    get,
+    path = "/api/v1/backups",
+    tag = "backups",
+    params(PaginationParams),
+    responses(
+        (status = 200, body = PaginatedResult<Backup>),
+        (status = 401, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn list_backups(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    query: Result<Query<PaginationParams>, QueryRejection>,
+) -> ApiResult<Json<PaginatedResult<Backup>>> {
+    let Query(params) = query?;
+    let pool = api_pool(&state, &auth).await?;
+    let (page, per_page) = page_params(&params);
+    Ok(Json(db::get_backups_paginated(&pool, page, per_page)?))
+}
+
+/// Show a backup domain
+#[utoipa::path(
+    get,
+    path = "/api/v1/backups/{id}",
+    tag = "backups",
+    params(("id" = i32, Path, description = "pkid of the backup domain")),
+    responses(
+        (status = 200, body = Backup),
+        (status = 404, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn show_backup(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<Json<Backup>> {
+    let Path(id) = path?;
+    let pool = api_pool(&state, &auth).await?;
+    Ok(Json(db::get_backup(&pool, id)?))
+}
+
+/// Create a backup domain
+#[utoipa::path(
+    post,
+    path = "/api/v1/backups",
+    tag = "backups",
+    request_body = BackupForm,
+    responses(
+        (status = 201, body = Backup),
+        (status = 403, body = ApiErrorBody),
+        (status = 409, body = ApiErrorBody),
+        (status = 422, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn create_backup(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    payload: Result<Json<BackupForm>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<Backup>)> {
+    check_write(&state, &auth, "create_backup")?;
+    let Json(form) = payload?;
+    require_field("domain", &form.domain)?;
+    let pool = api_pool(&state, &auth).await?;
+
+    let new_backup = NewBackup {
+        domain: form.domain.trim().to_string(),
+        transport: Some(form.transport),
+        enabled: form.enabled,
+    };
+    let backup = db::create_backup(&pool, new_backup, &auth.audit())?;
+    Ok((StatusCode::CREATED, Json(backup)))
+}
+
+/// Update a backup domain
+#[utoipa::path(
+    put,
+    path = "/api/v1/backups/{id}",
+    tag = "backups",
+    params(("id" = i32, Path, description = "pkid of the backup domain")),
+    request_body = BackupForm,
+    responses(
+        (status = 200, body = Backup),
+        (status = 403, body = ApiErrorBody),
+        (status = 404, body = ApiErrorBody),
+        (status = 422, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn update_backup(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    path: Result<Path<i32>, PathRejection>,
+    payload: Result<Json<BackupForm>, JsonRejection>,
+) -> ApiResult<Json<Backup>> {
+    check_write(&state, &auth, "update_backup")?;
+    let Path(id) = path?;
+    let Json(mut form) = payload?;
+    require_field("domain", &form.domain)?;
+    form.domain = form.domain.trim().to_string();
+    let pool = api_pool(&state, &auth).await?;
+    Ok(Json(db::update_backup(&pool, id, form, &auth.audit())?))
+}
+
+/// Delete a backup domain
+#[utoipa::path(
+    delete,
+    path = "/api/v1/backups/{id}",
+    tag = "backups",
+    params(("id" = i32, Path, description = "pkid of the backup domain")),
+    responses(
+        (status = 204),
+        (status = 403, body = ApiErrorBody),
+        (status = 404, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn delete_backup(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<StatusCode> {
+    check_write(&state, &auth, "delete_backup")?;
+    let Path(id) = path?;
+    let pool = api_pool(&state, &auth).await?;
+    deleted(db::delete_backup(&pool, id, &auth.audit())?, "Backup")
+}
+
+/// Enable or disable a backup domain
+#[utoipa::path(
+    post,
+    path = "/api/v1/backups/{id}/toggle",
+    tag = "backups",
+    params(("id" = i32, Path, description = "pkid of the backup domain")),
+    responses(
+        (status = 200, body = Backup),
+        (status = 403, body = ApiErrorBody),
+        (status = 404, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn toggle_backup(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<Json<Backup>> {
+    check_write(&state, &auth, "toggle_backup")?;
+    let Path(id) = path?;
+    let pool = api_pool(&state, &auth).await?;
+    Ok(Json(db::toggle_backup_enabled(&pool, id, &auth.audit())?))
+}
+
+// Relay endpoints
+
+/// List relays
+#[utoipa::path(
+    get,
+    path = "/api/v1/relays",
+    tag = "relays",
+    params(PaginationParams),
+    responses(
+        (status = 200, body = PaginatedResult<Relay>),
+        (status = 401, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn list_relays(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    query: Result<Query<PaginationParams>, QueryRejection>,
+) -> ApiResult<Json<PaginatedResult<Relay>>> {
+    let Query(params) = query?;
+    let pool = api_pool(&state, &auth).await?;
+    let (page, per_page) = page_params(&params);
+    Ok(Json(db::get_relays_paginated(&pool, page, per_page)?))
+}
+
+/// Show a relay
+#[utoipa::path(
+    get,
+    path = "/api/v1/relays/{id}",
+    tag = "relays",
+    params(("id" = i32, Path, description = "pkid of the relay")),
+    responses(
+        (status = 200, body = Relay),
+        (status = 404, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn show_relay(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<Json<Relay>> {
+    let Path(id) = path?;
+    let pool = api_pool(&state, &auth).await?;
+    Ok(Json(db::get_relay(&pool, id)?))
+}
+
+/// Create a relay
+#[utoipa::path(
+    post,
+    path = "/api/v1/relays",
+    tag = "relays",
+    request_body = RelayForm,
+    responses(
+        (status = 201, body = Relay),
+        (status = 403, body = ApiErrorBody),
+        (status = 409, body = ApiErrorBody),
+        (status = 422, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn create_relay(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    payload: Result<Json<RelayForm>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<Relay>)> {
+    check_write(&state, &auth, "create_relay")?;
+    let Json(form) = payload?;
+    require_field("recipient", &form.recipient)?;
+    require_field("status", &form.status)?;
+    let pool = api_pool(&state, &auth).await?;
+    let relay = db::create_relay(&pool, form, &auth.audit())?;
+    Ok((StatusCode::CREATED, Json(relay)))
+}
+
+/// Update a relay
+#[utoipa::path(
+    put,
+    path = "/api/v1/relays/{id}",
+    tag = "relays",
+    params(("id" = i32, Path, description = "pkid of the relay")),
+    request_body = RelayForm,
+    responses(
+        (status = 200, body = Relay),
+        (status = 403, body = ApiErrorBody),
+        (status = 404, body = ApiErrorBody),
+        (status = 422, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn update_relay(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    path: Result<Path<i32>, PathRejection>,
+    payload: Result<Json<RelayForm>, JsonRejection>,
+) -> ApiResult<Json<Relay>> {
+    check_write(&state, &auth, "update_relay")?;
+    let Path(id) = path?;
+    let Json(form) = payload?;
+    require_field("recipient", &form.recipient)?;
+    require_field("status", &form.status)?;
+    let pool = api_pool(&state, &auth).await?;
+    Ok(Json(db::update_relay(&pool, id, form, &auth.audit())?))
+}
+
+/// Delete a relay
+#[utoipa::path(
+    delete,
+    path = "/api/v1/relays/{id}",
+    tag = "relays",
+    params(("id" = i32, Path, description = "pkid of the relay")),
+    responses(
+        (status = 204),
+        (status = 403, body = ApiErrorBody),
+        (status = 404, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn delete_relay(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<StatusCode> {
+    check_write(&state, &auth, "delete_relay")?;
+    let Path(id) = path?;
+    let pool = api_pool(&state, &auth).await?;
+    deleted(db::delete_relay(&pool, id, &auth.audit())?, "Relay")
+}
+
+/// Enable or disable a relay
+#[utoipa::path(
+    post,
+    path = "/api/v1/relays/{id}/toggle",
+    tag = "relays",
+    params(("id" = i32, Path, description = "pkid of the relay")),
+    responses(
+        (status = 200, body = Relay),
+        (status = 403, body = ApiErrorBody),
+        (status = 404, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn toggle_relay(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<Json<Relay>> {
+    check_write(&state, &auth, "toggle_relay")?;
+    let Path(id) = path?;
+    let pool = api_pool(&state, &auth).await?;
+    Ok(Json(db::toggle_relay_enabled(&pool, id, &auth.audit())?))
+}
+
+// Relocated endpoints
+
+/// List relocated entries
+#[utoipa::path(
+    get,
+    path = "/api/v1/relocated",
+    tag = "relocated",
+    params(PaginationParams),
+    responses(
+        (status = 200, body = PaginatedResult<Relocated>),
+        (status = 401, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn list_relocated(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    query: Result<Query<PaginationParams>, QueryRejection>,
+) -> ApiResult<Json<PaginatedResult<Relocated>>> {
+    let Query(params) = query?;
+    let pool = api_pool(&state, &auth).await?;
+    let (page, per_page) = page_params(&params);
+    Ok(Json(db::get_relocated_paginated(&pool, page, per_page)?))
+}
+
+/// Show a relocated entry
+#[utoipa::path(
+    get,
+    path = "/api/v1/relocated/{id}",
+    tag = "relocated",
+    params(("id" = i32, Path, description = "pkid of the relocated entry")),
+    responses(
+        (status = 200, body = Relocated),
+        (status = 404, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn show_relocated(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<Json<Relocated>> {
+    let Path(id) = path?;
+    let pool = api_pool(&state, &auth).await?;
+    Ok(Json(db::get_relocated_by_id(&pool, id)?))
+}
+
+/// Create a relocated entry
+#[utoipa::path(
+    post,
+    path = "/api/v1/relocated",
+    tag = "relocated",
+    request_body = RelocatedForm,
+    responses(
+        (status = 201, body = Relocated),
+        (status = 403, body = ApiErrorBody),
+        (status = 409, body = ApiErrorBody),
+        (status = 422, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn create_relocated(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    payload: Result<Json<RelocatedForm>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<Relocated>)> {
+    check_write(&state, &auth, "create_relocated")?;
+    let Json(form) = payload?;
+    require_field("old_address", &form.old_address)?;
+    require_field("new_address", &form.new_address)?;
+    validity::check_window(form.valid_from, form.valid_until)
+        .map_err(|e| ApiError::validation(e.to_string()))?;
+    let pool = api_pool(&state, &auth).await?;
+    let relocated = db::create_relocated(&pool, form, &auth.audit())?;
+    Ok((StatusCode::CREATED, Json(relocated)))
+}
+
+/// Update a relocated entry
+#[utoipa::path(
+    put,
+    path = "/api/v1/relocated/{id}",
+    tag = "relocated",
+    params(("id" = i32, Path, description = "pkid of the relocated entry")),
+    request_body = RelocatedForm,
+    responses(
+        (status = 200, body = Relocated),
+        (status = 403, body = ApiErrorBody),
+        (status = 404, body = ApiErrorBody),
+        (status = 422, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn update_relocated(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    path: Result<Path<i32>, PathRejection>,
+    payload: Result<Json<RelocatedForm>, JsonRejection>,
+) -> ApiResult<Json<Relocated>> {
+    check_write(&state, &auth, "update_relocated")?;
+    let Path(id) = path?;
+    let Json(form) = payload?;
+    require_field("old_address", &form.old_address)?;
+    require_field("new_address", &form.new_address)?;
+    validity::check_window(form.valid_from, form.valid_until)
+        .map_err(|e| ApiError::validation(e.to_string()))?;
+    let pool = api_pool(&state, &auth).await?;
+    Ok(Json(db::update_relocated(&pool, id, form, &auth.audit())?))
+}
+
+/// Delete a relocated entry
+#[utoipa::path(
+    delete,
+    path = "/api/v1/relocated/{id}",
+    tag = "relocated",
+    params(("id" = i32, Path, description = "pkid of the relocated entry")),
+    responses(
+        (status = 204),
+        (status = 403, body = ApiErrorBody),
+        (status = 404, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn delete_relocated(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<StatusCode> {
+    check_write(&state, &auth, "delete_relocated")?;
+    let Path(id) = path?;
+    let pool = api_pool(&state, &auth).await?;
+    deleted(
+        db::delete_relocated(&pool, id, &auth.audit())?,
+        "Relocated entry",
+    )
+}
+
+/// Enable or disable a relocated entry
+#[utoipa::path(
+    post,
+    path = "/api/v1/relocated/{id}/toggle",
+    tag = "relocated",
+    params(("id" = i32, Path, description = "pkid of the relocated entry")),
+    responses(
+        (status = 200, body = Relocated),
+        (status = 403, body = ApiErrorBody),
+        (status = 404, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn toggle_relocated(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<Json<Relocated>> {
+    check_write(&state, &auth, "toggle_relocated")?;
+    let Path(id) = path?;
+    let pool = api_pool(&state, &auth).await?;
+    Ok(Json(db::toggle_relocated_enabled(
+        &pool,
+        id,
+        &auth.audit(),
+    )?))
+}
+
+// Client endpoints
+
+/// List clients
+#[utoipa::path(
+    get,
+    path = "/api/v1/clients",
+    tag = "clients",
+    params(PaginationParams),
+    responses(
+        (status = 200, body = PaginatedResult<Client>),
+        (status = 401, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn list_clients(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    query: Result<Query<PaginationParams>, QueryRejection>,
+) -> ApiResult<Json<PaginatedResult<Client>>> {
+    let Query(params) = query?;
+    let pool = api_pool(&state, &auth).await?;
+    let (page, per_page) = page_params(&params);
+    Ok(Json(db::get_clients_paginated(&pool, page, per_page)?))
+}
+
+/// Show a client
+#[utoipa::path(
+    get,
+    path = "/api/v1/clients/{id}",
+    tag = "clients",
+    params(("id" = i32, Path, description = "id of the client")),
+    responses(
+        (status = 200, body = Client),
+        (status = 404, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn show_client(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<Json<Client>> {
+    let Path(id) = path?;
+    let pool = api_pool(&state, &auth).await?;
+    Ok(Json(db::get_client(&pool, id)?))
+}
+
+/// Create a client
+#[utoipa::path(
+    post,
+    path = "/api/v1/clients",
+    tag = "clients",
+    request_body = ClientForm,
+    responses(
+        (status = 201, body = Client),
+        (status = 403, body = ApiErrorBody),
+        (status = 409, body = ApiErrorBody),
+        (status = 422, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn create_client(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    payload: Result<Json<ClientForm>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<Client>)> {
+    check_write(&state, &auth, "create_client")?;
+    let Json(form) = payload?;
+    require_field("client", &form.client)?;
+    require_field("status", &form.status)?;
+    let pool = api_pool(&state, &auth).await?;
+    let client = db::create_client(&pool, form, &auth.audit())?;
+    Ok((StatusCode::CREATED, Json(client)))
+}
+
+/// Update a client
+#[utoipa::path(
+    put,
+    path = "/api/v1/clients/{id}",
+    tag = "clients",
+    params(("id" = i32, Path, description = "id of the client")),
+    request_body = ClientForm,
+    responses(
+        (status = 200, body = Client),
+        (status = 403, body = ApiErrorBody),
+        (status = 404, body = ApiErrorBody),
+        (status = 422, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn update_client(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    path: Result<Path<i32>, PathRejection>,
+    payload: Result<Json<ClientForm>, JsonRejection>,
+) -> ApiResult<Json<Client>> {
+    check_write(&state, &auth, "update_client")?;
+    let Path(id) = path?;
+    let Json(form) = payload?;
+    require_field("client", &form.client)?;
+    require_field("status", &form.status)?;
+    let pool = api_pool(&state, &auth).await?;
+    Ok(Json(db::update_client(&pool, id, form, &auth.audit())?))
+}
+
+/// Delete a client
+#[utoipa::path(
+    delete,
+    path = "/api/v1/clients/{id}",
+    tag = "clients",
+    params(("id" = i32, Path, description = "id of the client")),
+    responses(
+        (status = 204),
+        (status = 403, body = ApiErrorBody),
+        (status = 404, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn delete_client(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<StatusCode> {
+    check_write(&state, &auth, "delete_client")?;
+    let Path(id) = path?;
+    let pool = api_pool(&state, &auth).await?;
+    deleted(db::delete_client(&pool, id, &auth.audit())?, "Client")
+}
+
+/// Enable or disable a client
+#[utoipa::path(
+    post,
+    path = "/api/v1/clients/{id}/toggle",
+    tag = "clients",
+    params(("id" = i32, Path, description = "id of the client")),
+    responses(
+        (status = 200, body = Client),
+        (status = 403, body = ApiErrorBody),
+        (status = 404, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn toggle_client(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<Json<Client>> {
+    check_write(&state, &auth, "toggle_client")?;
+    let Path(id) = path?;
+    let pool = api_pool(&state, &auth).await?;
+    Ok(Json(db::toggle_client_enabled(&pool, id, &auth.audit())?))
+}
+
+// Report endpoints
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AliasReportQuery {
+    /// Local part of the alias, e.g. `postmaster`
+    pub alias: String,
+}
+
+/// Catch-all aliases per domain with their required aliases
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/catch-all",
+    tag = "reports",
+    responses((status = 200, body = Vec<CatchAllReport>)),
+    security(("bearer" = []))
+)]
+pub async fn catch_all_report(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+) -> ApiResult<Json<Vec<CatchAllReport>>> {
+    let pool = api_pool(&state, &auth).await?;
+    Ok(Json(db::get_catch_all_report(&pool)?))
+}
+
+/// Domains with and without catch-all aliases
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/aliases",
+    tag = "reports",
+    responses((status = 200, body = AliasReport)),
+    security(("bearer" = []))
+)]
+pub async fn alias_report(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+) -> ApiResult<Json<AliasReport>> {
+    let pool = api_pool(&state, &auth).await?;
+    Ok(Json(db::get_alias_report(&pool, &state.config.get())?))
+}
+
+/// Required alias status for every domain
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/matrix",
+    tag = "reports",
+    responses((status = 200, body = DomainAliasMatrixReport)),
+    security(("bearer" = []))
+)]
+pub async fn matrix_report(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+) -> ApiResult<Json<DomainAliasMatrixReport>> {
+    let pool = api_pool(&state, &auth).await?;
+    Ok(Json(db::get_domain_alias_matrix_report(
+        &pool,
+        &state.config.get(),
+    )?))
+}
+
+/// Domains missing required aliases
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/missing-aliases",
+    tag = "reports",
+    responses((status = 200, body = MissingAliasReport)),
+    security(("bearer" = []))
+)]
+pub async fn missing_aliases_report(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+) -> ApiResult<Json<MissingAliasReport>> {
+    let pool = api_pool(&state, &auth).await?;
+    Ok(Json(db::get_missing_aliases_report(&pool)?))
+}
+
+/// Aliases and users whose domain does not exist
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/orphaned",
+    tag = "reports",
+    responses((status = 200, body = OrphanedAliasReport)),
+    security(("bearer" = []))
+)]
+pub async fn orphaned_report(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+) -> ApiResult<Json<OrphanedAliasReport>> {
+    let pool = api_pool(&state, &auth).await?;
+    Ok(Json(db::get_orphaned_aliases_report(&pool)?))
+}
+
+/// Aliases forwarding to addresses outside the managed domains
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/external-forwarders",
+    tag = "reports",
+    responses((status = 200, body = ExternalForwarderReport)),
+    security(("bearer" = []))
+)]
+pub async fn external_forwarders_report(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+) -> ApiResult<Json<ExternalForwarderReport>> {
+    let pool = api_pool(&state, &auth).await?;
+    Ok(Json(db::get_external_forwarders_report(&pool)?))
+}
+
+/// Where an alias local part is used across domains
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/alias-cross-domain",
+    tag = "reports",
+    params(AliasReportQuery),
+    responses(
+        (status = 200, body = AliasCrossDomainReport),
+        (status = 400, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn alias_cross_domain_report(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    query: Result<Query<AliasReportQuery>, QueryRejection>,
+) -> ApiResult<Json<AliasCrossDomainReport>> {
+    let Query(query) = query?;
+    require_field("alias", &query.alias)?;
+    let pool = api_pool(&state, &auth).await?;
+    Ok(Json(db::get_alias_cross_domain_report(
+        &pool,
+        query.alias.trim(),
+    )?))
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DeliveryReportQuery {
+    /// Recipient address, e.g. `info@example.com`
+    pub address: String,
+}
+
+/// Where mail to an address ends up, hop by hop
+#[utoipa::path(
+    get,
+    path = "/api/v1/reports/delivery",
+    tag = "reports",
+    params(DeliveryReportQuery),
+    responses(
+        (status = 200, body = DeliveryTrace),
+        (status = 400, body = ApiErrorBody),
+    ),
+    security(("bearer" = []))
+)]
+pub async fn delivery_report(
+    State(state): State<AppState>,
+    Extension(auth): Extension<ApiAuth>,
+    query: Result<Query<DeliveryReportQuery>, QueryRejection>,
+) -> ApiResult<Json<DeliveryTrace>> {
+    let Query(query) = query?;
+    require_field("address", &query.address)?;
+    let pool = api_pool(&state, &auth).await?;
+    Ok(Json(crate::delivery::trace(&pool, &query.address)?))
+}
+
+// OpenAPI document
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Sorting Office API",
+        description = "JSON API for managing mail domains, users and aliases"
+    ),
+    paths(
+        list_domains,
+        create_domain,
+        show_domain,
+        update_domain,
+        delete_domain,
+        toggle_domain,
+        list_users,
+        create_user,
+        show_user,
+        update_user,
+        delete_user,
+        toggle_user,
+        list_aliases,
+        create_alias,
+        show_alias,
+        update_alias,
+        delete_alias,
+        toggle_alias,
+        list_backups,
+        create_backup,
+        show_backup,
+        update_backup,
+        delete_backup,
+        toggle_backup,
+        list_relays,
+        create_relay,
+        show_relay,
+        update_relay,
+        delete_relay,
+        toggle_relay,
+        list_relocated,
+        create_relocated,
+        show_relocated,
+        update_relocated,
+        delete_relocated,
+        toggle_relocated,
+        list_clients,
+        create_client,
+        show_client,
+        update_client,
+        delete_client,
+        toggle_client,
+        catch_all_report,
+        alias_report,
+        matrix_report,
+        missing_aliases_report,
+        orphaned_report,
+        external_forwarders_report,
+        alias_cross_domain_report,
+        delivery_report,
+    ),
+    components(schemas(PaginationParams)),
+    modifiers(&BearerAuth),
+    tags(
+        (name = "domains", description = "Mail domains"),
+        (name = "users", description = "Mail users"),
+        (name = "aliases", description = "Aliases"),
+        (name = "backups", description = "Backup MX domains"),
+        (name = "relays", description = "Relay recipients"),
+        (name = "relocated", description = "Relocated addresses"),
+        (name = "clients", description = "Client access rules"),
+        (name = "reports", description = "Read-only reports"),
+    )
+)]
+pub struct ApiDoc;
+
+struct BearerAuth;
+
+impl utoipa::Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(Http::new(HttpAuthScheme::Bearer)),
+        );
+    }
+}
+
+/// The OpenAPI document describing `/api/v1/`
+pub async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
@@ -0,0 +1,219 @@
+use crate::templates::audit::*;
+use crate::{db, models::*, render_template_with_title, AppState};
+use askama::Template;
+use axum::{
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    response::Html,
+};
+use std::collections::HashMap;
+
+/// Number of entries shown in the history panel on show pages
+const HISTORY_LIMIT: i64 = 20;
+
+const LABEL_KEYS: &[&str] = &[
+    "audit-entity-domain",
+    "audit-entity-user",
+    "audit-entity-alias",
+    "audit-entity-backup",
+    "audit-entity-relay",
+    "audit-entity-relocated",
+    "audit-entity-client",
+    "audit-entity-vacation",
+    "audit-entity-alias-domain",
+    "audit-action-create",
+    "audit-action-update",
+    "audit-action-delete",
+    "audit-action-toggle",
+    "audit-action-password",
+];
+
+fn label(labels: &HashMap<String, String>, prefix: &str, value: &str) -> String {
+    labels
+        .get(&format!("{prefix}-{value}"))
+        .cloned()
+        .unwrap_or_else(|| value.to_string())
+}
+
+fn build_rows(entries: &[AuditLogEntry], labels: &HashMap<String, String>) -> Vec<AuditRow> {
+    entries
+        .iter()
+        .map(|entry| AuditRow {
+            entity_label: label(labels, "audit-entity", &entry.entity_type),
+            action_label: label(labels, "audit-action", &entry.action),
+            changes: entry.field_changes(),
+            url: entry.entity_url(),
+            entry: entry.clone(),
+        })
+        .collect()
+}
+
+pub async fn list(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(filter): Query<AuditLogFilter>,
+) -> Html<String> {
+    let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
+        .await
+        .expect("Failed to get database pool");
+    let locale = crate::handlers::utils::get_user_locale(&headers);
+    let page = filter.page.unwrap_or(1).max(1);
+    let per_page = filter.per_page.unwrap_or(50).clamp(1, 500);
+
+    let paginated_entries = match db::get_audit_log_paginated(&pool, &filter, page, per_page) {
+        Ok(entries) => entries,
+        Err(e) => {
+            tracing::error!("Failed to retrieve audit log: {:?}", e);
+            PaginatedResult::new(vec![], 0, 1, per_page)
+        }
+    };
+    let admins = match db::get_audit_admins(&pool) {
+        Ok(admins) => admins,
+        Err(e) => {
+            tracing::error!("Failed to retrieve audit log admins: {:?}", e);
+            vec![]
+        }
+    };
+
+    let translations = crate::handlers::utils::get_translations_batch(
+        &state,
+        &locale,
+        &[
+            "audit-title",
+            "audit-description",
+            "audit-filter-admin",
+            "audit-filter-entity",
+            "audit-filter-from",
+            "audit-filter-to",
+            "audit-filter-all",
+            "audit-filter-apply",
+            "audit-filter-reset",
+            "audit-table-header-time",
+            "audit-table-header-admin",
+            "audit-table-header-database",
+            "audit-table-header-entity",
+            "audit-table-header-name",
+            "audit-table-header-action",
+            "audit-table-header-changes",
+            "audit-empty-title",
+            "audit-empty-description",
+            "audit-no-field-changes",
+        ],
+    )
+    .await;
+    let labels = crate::handlers::utils::get_translations_batch(&state, &locale, LABEL_KEYS).await;
+
+    let rows = build_rows(&paginated_entries.items, &labels);
+    let entity_options: Vec<(String, String)> = AuditEntity::ALL
+        .iter()
+        .map(|entity| {
+            (
+                entity.as_str().to_string(),
+                label(&labels, "audit-entity", entity.as_str()),
+            )
+        })
+        .collect();
+    let page_range: Vec<i64> = (1..=paginated_entries.total_pages).collect();
+    let max_item = std::cmp::min(
+        paginated_entries.current_page * paginated_entries.per_page,
+        paginated_entries.total_count,
+    );
+
+    let content_template = AuditListTemplate {
+        title: &translations["audit-title"],
+        description: &translations["audit-description"],
+        filter_admin: &translations["audit-filter-admin"],
+        filter_entity: &translations["audit-filter-entity"],
+        filter_from: &translations["audit-filter-from"],
+        filter_to: &translations["audit-filter-to"],
+        filter_all: &translations["audit-filter-all"],
+        filter_apply: &translations["audit-filter-apply"],
+        filter_reset: &translations["audit-filter-reset"],
+        table_header_time: &translations["audit-table-header-time"],
+        table_header_admin: &translations["audit-table-header-admin"],
+        table_header_database: &translations["audit-table-header-database"],
+        table_header_entity: &translations["audit-table-header-entity"],
+        table_header_name: &translations["audit-table-header-name"],
+        table_header_action: &translations["audit-table-header-action"],
+        table_header_changes: &translations["audit-table-header-changes"],
+        empty_title: &translations["audit-empty-title"],
+        empty_description: &translations["audit-empty-description"],
+        no_field_changes: &translations["audit-no-field-changes"],
+        admins: &admins,
+        entity_options: &entity_options,
+        selected_admin: filter.admin.as_deref().unwrap_or(""),
+        selected_entity: filter.entity_type.as_deref().unwrap_or(""),
+        selected_from: filter.from.as_deref().unwrap_or(""),
+        selected_to: filter.to.as_deref().unwrap_or(""),
+        rows: &rows,
+        pagination: &paginated_entries,
+        page_range: &page_range,
+        max_item,
+    };
+    render_template_with_title!(
+        content_template,
+        content_template.title,
+        &state,
+        &locale,
+        &headers
+    )
+}
+
+/// History panel loaded into each entity's show page
+pub async fn history(
+    State(state): State<AppState>,
+    Path((entity_type, entity_id)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Html<String> {
+    let Some(entity) = AuditEntity::parse(&entity_type) else {
+        return Html("Unknown entity type".to_string());
+    };
+    let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
+        .await
+        .expect("Failed to get database pool");
+    let locale = crate::handlers::utils::get_user_locale(&headers);
+
+    let entries = match db::get_entity_audit_history(&pool, entity, &entity_id, HISTORY_LIMIT) {
+        Ok(entries) => entries,
+        Err(e) => {
+            tracing::error!("Failed to retrieve audit history: {:?}", e);
+            vec![]
+        }
+    };
+
+    let translations = crate::handlers::utils::get_translations_batch(
+        &state,
+        &locale,
+        &[
+            "audit-history-title",
+            "audit-history-empty",
+            "audit-table-header-time",
+            "audit-table-header-admin",
+            "audit-table-header-action",
+            "audit-table-header-changes",
+            "audit-no-field-changes",
+        ],
+    )
+    .await;
+    let labels = crate::handlers::utils::get_translations_batch(&state, &locale, LABEL_KEYS).await;
+    let rows = build_rows(&entries, &labels);
+
+    let template = AuditHistoryTemplate {
+        title: &translations["audit-history-title"],
+        empty_message: &translations["audit-history-empty"],
+        table_header_time: &translations["audit-table-header-time"],
+        table_header_admin: &translations["audit-table-header-admin"],
+        table_header_action: &translations["audit-table-header-action"],
+        table_header_changes: &translations["audit-table-header-changes"],
+        no_field_changes: &translations["audit-no-field-changes"],
+        rows: &rows,
+    };
+
+    match template.render() {
+        Ok(content) => Html(content),
+        Err(e) => {
+            tracing::error!("Failed to render audit history: {:?}", e);
+            Html("Error rendering template".to_string())
+        }
+    }
+}
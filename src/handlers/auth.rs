@@ -1,4 +1,5 @@
-use crate::config::AdminRole;
+use crate::config::{AdminCredentials, AdminRole, DatabaseConfig};
+use crate::models::AdminSession;
 use crate::templates::auth::LoginTemplate;
 use crate::AppState;
 use askama::Template;
@@ -9,34 +10,72 @@ use axum::{
     Form,
 };
 use serde::Deserialize;
-use std::time::{SystemTime, UNIX_EPOCH};
 
 pub async fn login_form(State(state): State<AppState>, headers: HeaderMap) -> Html<String> {
     let locale = crate::handlers::language::get_user_locale(&headers);
+    Html(render_login_page(&state, &locale, "", LoginKind::Admin).await)
+}
+
+/// Who a sign-in page is for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoginKind {
+    /// Admins listed in the configuration
+    Admin,
+    /// Mailbox users signing in to the self-service portal
+    Mailbox,
+}
 
-    let title = crate::i18n::get_translation(&state, &locale, "login-title").await;
-    let user_id = crate::i18n::get_translation(&state, &locale, "login-user-id").await;
-    let password = crate::i18n::get_translation(&state, &locale, "login-password").await;
-    let sign_in = crate::i18n::get_translation(&state, &locale, "login-sign-in").await;
-    let app_title = crate::i18n::get_translation(&state, &locale, "app-title").await;
-    let app_subtitle = crate::i18n::get_translation(&state, &locale, "app-subtitle").await;
-    let language_selector =
-        crate::i18n::get_translation(&state, &locale, "language-selector").await;
-    let theme_toggle = crate::i18n::get_translation(&state, &locale, "theme-toggle").await;
-    let language_english = crate::i18n::get_translation(&state, &locale, "language-english").await;
-    let language_spanish = crate::i18n::get_translation(&state, &locale, "language-spanish").await;
-    let language_french = crate::i18n::get_translation(&state, &locale, "language-french").await;
+/// Render the sign-in page for admins or mailbox users
+///
+/// Both share the page; each links to the other.
+pub async fn render_login_page(
+    state: &AppState,
+    locale: &str,
+    error: &str,
+    kind: LoginKind,
+) -> String {
+    let (title_key, user_id_key, form_action, switch_url, switch_key) = match kind {
+        LoginKind::Admin => (
+            "login-title",
+            "login-user-id",
+            "/login",
+            "/portal/login",
+            "login-mailbox-link",
+        ),
+        LoginKind::Mailbox => (
+            "portal-login-title",
+            "portal-login-address",
+            "/portal/login",
+            "/login",
+            "portal-login-admin-link",
+        ),
+    };
+    let title = crate::i18n::get_translation(state, locale, title_key).await;
+    let user_id = crate::i18n::get_translation(state, locale, user_id_key).await;
+    let password = crate::i18n::get_translation(state, locale, "login-password").await;
+    let sign_in = crate::i18n::get_translation(state, locale, "login-sign-in").await;
+    let switch_label = crate::i18n::get_translation(state, locale, switch_key).await;
+    let app_title = crate::i18n::get_translation(state, locale, "app-title").await;
+    let app_subtitle = crate::i18n::get_translation(state, locale, "app-subtitle").await;
+    let language_selector = crate::i18n::get_translation(state, locale, "language-selector").await;
+    let theme_toggle = crate::i18n::get_translation(state, locale, "theme-toggle").await;
+    let language_english = crate::i18n::get_translation(state, locale, "language-english").await;
+    let language_spanish = crate::i18n::get_translation(state, locale, "language-spanish").await;
+    let language_french = crate::i18n::get_translation(state, locale, "language-french").await;
     let language_norwegian =
-        crate::i18n::get_translation(&state, &locale, "language-norwegian").await;
-    let language_german = crate::i18n::get_translation(&state, &locale, "language-german").await;
+        crate::i18n::get_translation(state, locale, "language-norwegian").await;
+    let language_german = crate::i18n::get_translation(state, locale, "language-german").await;
 
     let template = LoginTemplate {
         title: &title,
-        error: "",
+        error,
         login_title: &title,
         user_id: &user_id,
         password: &password,
         sign_in: &sign_in,
+        form_action,
+        switch_url,
+        switch_label: &switch_label,
         app_title: &app_title,
         app_subtitle: &app_subtitle,
         language_selector: &language_selector,
@@ -46,10 +85,29 @@ pub async fn login_form(State(state): State<AppState>, headers: HeaderMap) -> Ht
         language_french: &language_french,
         language_norwegian: &language_norwegian,
         language_german: &language_german,
-        current_locale: &locale,
+        current_locale: locale,
     };
+    template.render().unwrap()
+}
 
-    Html(template.render().unwrap())
+/// The error box swapped into the sign-in page for HTMX requests
+pub fn login_error_fragment(error: &str) -> String {
+    format!(
+        r#"<div id="error-message">
+            <div class="bg-red-100 dark:bg-red-900 border border-red-400 dark:border-red-700 text-red-700 dark:text-red-200 px-4 py-3 rounded mb-4">
+                <div class="flex">
+                    <div class="flex-shrink-0">
+                        <svg class="h-5 w-5 text-red-400 dark:text-red-300" viewBox="0 0 20 20" fill="currentColor">
+                            <path fill-rule="evenodd" d="M10 18a8 8 0 100-16 8 8 0 000 16zM8.707 7.293a1 1 0 00-1.414 1.414L8.586 10l-1.293 1.293a1 1 0 101.414 1.414L10 11.414l1.293 1.293a1 1 0 001.414-1.414L11.414 10l1.293-1.293a1 1 0 00-1.414-1.414L10 8.586 8.707 7.293z" clip-rule="evenodd" />
+                        </svg>
+                    </div>
+                    <div class="ml-3">
+                        <p class="text-sm">{error}</p>
+                    </div>
+                </div>
+            </div>
+        </div>"#
+    )
 }
 
 #[derive(Deserialize)]
@@ -80,91 +138,39 @@ pub async fn login(
             // Return just the error message for HTMX requests
             return Ok(Response::builder()
                 .status(StatusCode::OK)
-                .body(format!(
-                    r#"<div id="error-message">
-                        <div class="bg-red-100 dark:bg-red-900 border border-red-400 dark:border-red-700 text-red-700 dark:text-red-200 px-4 py-3 rounded mb-4">
-                            <div class="flex">
-                                <div class="flex-shrink-0">
-                                    <svg class="h-5 w-5 text-red-400 dark:text-red-300" viewBox="0 0 20 20" fill="currentColor">
-                                        <path fill-rule="evenodd" d="M10 18a8 8 0 100-16 8 8 0 000 16zM8.707 7.293a1 1 0 00-1.414 1.414L8.586 10l-1.293 1.293a1 1 0 101.414 1.414L10 11.414l1.293 1.293a1 1 0 001.414-1.414L11.414 10l1.293-1.293a1 1 0 00-1.414-1.414L10 8.586 8.707 7.293z" clip-rule="evenodd" />
-                                    </svg>
-                                </div>
-                                <div class="ml-3">
-                                    <p class="text-sm">{error}</p>
-                                </div>
-                            </div>
-                        </div>
-                    </div>"#
-                ).into())
+                .body(login_error_fragment(&error).into())
                 .unwrap());
         } else {
             // Return full page for regular requests
-            let title = crate::i18n::get_translation(&state, &locale, "login-title").await;
-            let user_id = crate::i18n::get_translation(&state, &locale, "login-user-id").await;
-            let password = crate::i18n::get_translation(&state, &locale, "login-password").await;
-            let sign_in = crate::i18n::get_translation(&state, &locale, "login-sign-in").await;
-            let app_title = crate::i18n::get_translation(&state, &locale, "app-title").await;
-            let app_subtitle = crate::i18n::get_translation(&state, &locale, "app-subtitle").await;
-            let language_selector =
-                crate::i18n::get_translation(&state, &locale, "language-selector").await;
-            let theme_toggle = crate::i18n::get_translation(&state, &locale, "theme-toggle").await;
-            let language_english =
-                crate::i18n::get_translation(&state, &locale, "language-english").await;
-            let language_spanish =
-                crate::i18n::get_translation(&state, &locale, "language-spanish").await;
-            let language_french =
-                crate::i18n::get_translation(&state, &locale, "language-french").await;
-            let language_norwegian =
-                crate::i18n::get_translation(&state, &locale, "language-norwegian").await;
-            let language_german =
-                crate::i18n::get_translation(&state, &locale, "language-german").await;
-
-            let template = LoginTemplate {
-                title: &title,
-                error: &error,
-                login_title: &title,
-                user_id: &user_id,
-                password: &password,
-                sign_in: &sign_in,
-                app_title: &app_title,
-                app_subtitle: &app_subtitle,
-                language_selector: &language_selector,
-                theme_toggle: &theme_toggle,
-                language_english: &language_english,
-                language_spanish: &language_spanish,
-                language_french: &language_french,
-                language_norwegian: &language_norwegian,
-                language_german: &language_german,
-                current_locale: &locale,
-            };
-            return Err(Html(template.render().unwrap()));
+            return Err(Html(
+                render_login_page(&state, &locale, &error, LoginKind::Admin).await,
+            ));
         }
     }
 
     // Verify admin credentials from config
-    if let Some(role) = state
-        .config
-        .verify_admin_credentials(request.id.trim(), &request.password)
-    {
+    let config = state.config.get();
+    if let Some(role) = config.verify_admin_credentials(request.id.trim(), &request.password) {
         println!(
             "🔐 [AUTH] ✅ Login successful for user '{}' with role: {:?}",
             request.id, role
         );
-        // Set authentication cookie with role
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        let expiry = now + (24 * 60 * 60); // 24 hours
-        let role_str = match role {
-            AdminRole::ReadOnly => "read-only",
-            AdminRole::Edit => "edit",
+        // Start a server-side session on the first database the admin may access
+        let default_db = config
+            .find_admin(request.id.trim())
+            .and_then(|admin| state.db_manager.get_default_db_id_for_admin(admin))
+            .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
+        let session = match state.sessions.create(request.id.trim(), &role, &default_db) {
+            Ok(session) => session,
+            Err(e) => {
+                tracing::error!("Failed to create session: {:?}", e);
+                return Ok(Response::builder()
+                    .status(StatusCode::INTERNAL_SERVER_ERROR)
+                    .body("Failed to create session".into())
+                    .unwrap());
+            }
         };
-        // Set default database to the first available database
-        let default_db = state.db_manager.get_default_db_id();
-        let cookie_value = format!(
-            "authenticated={expiry}:{role_str}:{default_db}; Path=/; Max-Age=86400; HttpOnly; SameSite=Lax"
-        );
+        let cookie_value = state.sessions.cookie_header(&session);
         if is_htmx {
             // For htmx, use HX-Redirect header to force a full page reload
             return Ok(Response::builder()
@@ -196,220 +202,158 @@ pub async fn login(
         // Return just the error message for HTMX requests
         Ok(Response::builder()
             .status(StatusCode::OK)
-            .body(format!(
-                r#"<div id="error-message">
-                    <div class="bg-red-100 dark:bg-red-900 border border-red-400 dark:border-red-700 text-red-700 dark:text-red-200 px-4 py-3 rounded mb-4">
-                        <div class="flex">
-                            <div class="flex-shrink-0">
-                                <svg class="h-5 w-5 text-red-400 dark:text-red-300" viewBox="0 0 20 20" fill="currentColor">
-                                    <path fill-rule="evenodd" d="M10 18a8 8 0 100-16 8 8 0 000 16zM8.707 7.293a1 1 0 00-1.414 1.414L8.586 10l-1.293 1.293a1 1 0 101.414 1.414L10 11.414l1.293 1.293a1 1 0 001.414-1.414L11.414 10l1.293-1.293a1 1 0 00-1.414-1.414L10 8.586 8.707 7.293z" clip-rule="evenodd" />
-                                </svg>
-                            </div>
-                            <div class="ml-3">
-                                <p class="text-sm">{error}</p>
-                            </div>
-                        </div>
-                    </div>
-                </div>"#
-            ).into())
+            .body(login_error_fragment(&error).into())
             .unwrap())
     } else {
         // Return full page for regular requests
-        let title = crate::i18n::get_translation(&state, &locale, "login-title").await;
-        let user_id = crate::i18n::get_translation(&state, &locale, "login-user-id").await;
-        let password = crate::i18n::get_translation(&state, &locale, "login-password").await;
-        let sign_in = crate::i18n::get_translation(&state, &locale, "login-sign-in").await;
-        let app_title = crate::i18n::get_translation(&state, &locale, "app-title").await;
-        let app_subtitle = crate::i18n::get_translation(&state, &locale, "app-subtitle").await;
-        let language_selector =
-            crate::i18n::get_translation(&state, &locale, "language-selector").await;
-        let theme_toggle = crate::i18n::get_translation(&state, &locale, "theme-toggle").await;
-        let language_english =
-            crate::i18n::get_translation(&state, &locale, "language-english").await;
-        let language_spanish =
-            crate::i18n::get_translation(&state, &locale, "language-spanish").await;
-        let language_french =
-            crate::i18n::get_translation(&state, &locale, "language-french").await;
-        let language_norwegian =
-            crate::i18n::get_translation(&state, &locale, "language-norwegian").await;
-        let language_german =
-            crate::i18n::get_translation(&state, &locale, "language-german").await;
-
-        let template = LoginTemplate {
-            title: &title,
-            error: &error,
-            login_title: &title,
-            user_id: &user_id,
-            password: &password,
-            sign_in: &sign_in,
-            app_title: &app_title,
-            app_subtitle: &app_subtitle,
-            language_selector: &language_selector,
-            theme_toggle: &theme_toggle,
-            language_english: &language_english,
-            language_spanish: &language_spanish,
-            language_french: &language_french,
-            language_norwegian: &language_norwegian,
-            language_german: &language_german,
-            current_locale: &locale,
-        };
-        Err(Html(template.render().unwrap()))
+        Err(Html(
+            render_login_page(&state, &locale, &error, LoginKind::Admin).await,
+        ))
     }
 }
 
-pub async fn logout() -> Response {
-    // Clear authentication cookie
-    let cookie_value = "authenticated=; Path=/; Max-Age=0; HttpOnly; SameSite=Lax";
+pub async fn logout(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    // Revoke the server-side session and clear the cookie
+    state.sessions.revoke_from_headers(&headers);
 
     Response::builder()
         .status(StatusCode::FOUND)
         .header("Location", "/login")
-        .header("Set-Cookie", cookie_value)
+        .header("Set-Cookie", state.sessions.clear_cookie_header())
         .body("".into())
         .unwrap()
 }
 
-/// Check if user is authenticated and return their role
-pub fn get_user_role(headers: &HeaderMap) -> Option<AdminRole> {
-    if let Some(cookie_header) = headers.get("cookie") {
-        if let Ok(cookie_str) = cookie_header.to_str() {
-            for cookie in cookie_str.split(';') {
-                let cookie = cookie.trim();
-
-                if let Some(stripped) = cookie.strip_prefix("authenticated=") {
-                    // Correctly extract the value after 'authenticated='
-                    let value_part = &stripped.split(';').next().unwrap_or("");
-
-                    let parts: Vec<&str> = value_part.split(':').collect();
-
-                    if parts.len() >= 2 {
-                        if let Ok(expiry) = parts[0].parse::<u64>() {
-                            let now = SystemTime::now()
-                                .duration_since(UNIX_EPOCH)
-                                .unwrap()
-                                .as_secs();
-
-                            if expiry > now {
-                                // Parse role (handle both old format with 2 parts and new format with 3 parts)
-                                match parts[1] {
-                                    "read-only" => return Some(AdminRole::ReadOnly),
-                                    "edit" => return Some(AdminRole::Edit),
-                                    _ => return None,
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+/// Get the live session for this request, if any
+pub fn get_session(state: &AppState, headers: &HeaderMap) -> Option<AdminSession> {
+    state.sessions.from_headers(headers)
+}
+
+/// Get the configured admin behind this request's session
+///
+/// Returns `None` when there is no session or the admin has since been
+/// removed from the config.
+pub fn get_admin(state: &AppState, headers: &HeaderMap) -> Option<AdminCredentials> {
+    get_session(state, headers)
+        .and_then(|session| state.config.get().find_admin(&session.username).cloned())
+}
+
+/// Check if user is authenticated and return their role on the selected database
+pub fn get_user_role(state: &AppState, headers: &HeaderMap) -> Option<AdminRole> {
+    let session = get_session(state, headers)?;
+    state
+        .config
+        .get()
+        .admin_role_for_database(&session.username, &session.database_id)
+}
+
+/// Get the databases the current admin has been granted access to
+pub fn get_accessible_databases(state: &AppState, headers: &HeaderMap) -> Vec<DatabaseConfig> {
+    let Some(admin) = get_admin(state, headers) else {
+        return Vec::new();
+    };
+    // Feature toggles can change at runtime, so take them from the current config
+    let config = state.config.get();
+    let mut databases = state.db_manager.get_configs_for_admin(&admin);
+    for database in &mut databases {
+        if let Some(features) = config.get_database_features(&database.id) {
+            database.features = features.clone();
         }
     }
-
-    None
+    databases
 }
 
 /// Update the session with a new database selection
-pub fn update_session_database(headers: &HeaderMap, new_database: &str) -> Option<String> {
-    if let Some(cookie_header) = headers.get("cookie") {
-        if let Ok(cookie_str) = cookie_header.to_str() {
-            for cookie in cookie_str.split(';') {
-                let cookie = cookie.trim();
-
-                if let Some(stripped) = cookie.strip_prefix("authenticated=") {
-                    // Correctly extract the value after 'authenticated='
-                    let value_part = &stripped.split(';').next().unwrap_or("");
-
-                    let parts: Vec<&str> = value_part.split(':').collect();
-
-                    if parts.len() >= 2 {
-                        if let Ok(expiry) = parts[0].parse::<u64>() {
-                            let now = SystemTime::now()
-                                .duration_since(UNIX_EPOCH)
-                                .unwrap()
-                                .as_secs();
-
-                            if expiry > now {
-                                // Create new cookie with updated database
-                                let role_str = parts[1];
-                                return Some(format!(
-                                    "authenticated={expiry}:{role_str}:{new_database}; Path=/; Max-Age=86400; HttpOnly; SameSite=Lax"
-                                ));
-                            }
-                        }
-                    }
-                }
-            }
+pub fn update_session_database(state: &AppState, headers: &HeaderMap, new_database: &str) -> bool {
+    let Some(session) = get_session(state, headers) else {
+        return false;
+    };
+
+    match state.sessions.set_database(&session.id, new_database) {
+        Ok(updated) => updated,
+        Err(e) => {
+            tracing::error!("Failed to update session database: {:?}", e);
+            false
         }
     }
-
-    None
 }
 
 /// Check if user is authenticated
-pub fn is_authenticated(headers: &HeaderMap) -> bool {
-    get_user_role(headers).is_some()
+pub fn is_authenticated(state: &AppState, headers: &HeaderMap) -> bool {
+    get_admin(state, headers).is_some()
 }
 
 /// Check if user has edit permissions
-pub fn has_edit_permissions(headers: &HeaderMap) -> bool {
-    matches!(get_user_role(headers), Some(AdminRole::Edit))
+pub fn has_edit_permissions(state: &AppState, headers: &HeaderMap) -> bool {
+    matches!(get_user_role(state, headers), Some(AdminRole::Edit))
 }
 
 /// Get the selected database from the session
-pub fn get_selected_database(headers: &HeaderMap) -> Option<String> {
-    if let Some(cookie_header) = headers.get("cookie") {
-        if let Ok(cookie_str) = cookie_header.to_str() {
-            for cookie in cookie_str.split(';') {
-                let cookie = cookie.trim();
-
-                if let Some(stripped) = cookie.strip_prefix("authenticated=") {
-                    // Correctly extract the value after 'authenticated='
-                    let value_part = &stripped.split(';').next().unwrap_or("");
-
-                    let parts: Vec<&str> = value_part.split(':').collect();
-
-                    if parts.len() >= 3 {
-                        if let Ok(expiry) = parts[0].parse::<u64>() {
-                            let now = SystemTime::now()
-                                .duration_since(UNIX_EPOCH)
-                                .unwrap()
-                                .as_secs();
-
-                            if expiry > now {
-                                // Return the database ID (third part)
-                                return Some(parts[2].to_string());
-                            }
-                        }
-                    } else if parts.len() == 2 {
-                        // Handle old format (no database selected) - return None
-                        if let Ok(expiry) = parts[0].parse::<u64>() {
-                            let now = SystemTime::now()
-                                .duration_since(UNIX_EPOCH)
-                                .unwrap()
-                                .as_secs();
-
-                            if expiry > now {
-                                return None;
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    None
+pub fn get_selected_database(state: &AppState, headers: &HeaderMap) -> Option<String> {
+    get_session(state, headers).map(|session| session.database_id)
 }
 
 /// Authentication middleware
 pub async fn require_auth(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     headers: HeaderMap,
     request: axum::extract::Request,
     next: axum::middleware::Next,
 ) -> Result<Response, StatusCode> {
     let path = request.uri().path();
 
-    if is_authenticated(&headers) {
+    if let Some(session) = get_session(&state, &headers) {
+        let config = state.config.get();
+        let Some(admin) = config.find_admin(&session.username) else {
+            println!(
+                "🔐 [AUTH] ❌ Session for unknown admin '{}' used for: {path}",
+                session.username
+            );
+            state.sessions.revoke(&session.id);
+            return Ok(Response::builder()
+                .status(StatusCode::FOUND)
+                .header("Location", "/login")
+                .body("".into())
+                .unwrap());
+        };
+
+        // The selected database may no longer be granted; move the admin to one that is
+        if !admin.can_access_database(&session.database_id) {
+            let Some(fallback_db) = state.db_manager.get_default_db_id_for_admin(admin) else {
+                println!(
+                    "🔐 [AUTH] ❌ Admin '{}' has no database access for: {path}",
+                    session.username
+                );
+                return Ok(Response::builder()
+                    .status(StatusCode::FORBIDDEN)
+                    .body("No database access".into())
+                    .unwrap());
+            };
+            if let Err(e) = state.sessions.set_database(&session.id, &fallback_db) {
+                tracing::error!("Failed to update session database: {:?}", e);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        }
+
+        // Domain admins only manage users and aliases; their home is the user list
+        if admin.is_domain_admin() && !is_domain_admin_path(path) {
+            if path == "/" {
+                return Ok(Response::builder()
+                    .status(StatusCode::FOUND)
+                    .header("Location", "/users")
+                    .body("".into())
+                    .unwrap());
+            }
+            println!(
+                "🔐 [AUTH] ❌ Domain admin '{}' denied access to: {path}",
+                session.username
+            );
+            return Ok(Response::builder()
+                .status(StatusCode::FORBIDDEN)
+                .body("Insufficient permissions".into())
+                .unwrap());
+        }
+
         Ok(next.run(request).await)
     } else {
         println!("🔐 [AUTH] ❌ Unauthenticated access attempt to: {path}");
@@ -422,16 +366,27 @@ pub async fn require_auth(
     }
 }
 
+/// Whether a delegated domain admin may open a path
+///
+/// Users and aliases are filtered to the admin's domains by the handlers;
+/// everything else, such as domains, relays and the config, is off limits.
+pub fn is_domain_admin_path(path: &str) -> bool {
+    matches!(path, "/database/select" | "/database/dropdown")
+        || ["/users", "/aliases"]
+            .iter()
+            .any(|prefix| path == *prefix || path.starts_with(&format!("{prefix}/")))
+}
+
 /// Edit permissions middleware
 pub async fn require_edit_permissions(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     headers: HeaderMap,
     request: axum::extract::Request,
     next: axum::middleware::Next,
 ) -> Result<Response, StatusCode> {
     let path = request.uri().path();
 
-    if has_edit_permissions(&headers) {
+    if has_edit_permissions(&state, &headers) {
         Ok(next.run(request).await)
     } else {
         println!("🔐 [AUTH] ❌ Insufficient permissions for access to: {path}");
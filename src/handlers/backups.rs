@@ -79,7 +79,7 @@ pub async fn show(
     let content = content_template.render().unwrap();
 
     // Get current database id from session/cookie or default
-    let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+    let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
         .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
     // Get current database label from db_manager
     let current_db_label = state
@@ -157,6 +157,7 @@ pub async fn create(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
     let locale = crate::handlers::language::get_user_locale(&headers);
 
     // Validate form data
@@ -196,7 +197,7 @@ pub async fn create(
         enabled: form.enabled,
     };
 
-    match db::create_backup(&pool, new_backup) {
+    match db::create_backup(&pool, new_backup, &audit) {
         Ok(_) => {
             // Redirect to domains page after creating backup
             Html("<script>window.location.href='/domains';</script>".to_string())
@@ -247,7 +248,7 @@ pub async fn create(
             let content = content_template.render().unwrap();
 
             // Get current database id from session/cookie or default
-            let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+            let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
                 .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
             // Get current database label from db_manager
             let current_db_label = state
@@ -283,6 +284,7 @@ pub async fn update(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
     let locale = crate::handlers::language::get_user_locale(&headers);
 
     // Validate form data
@@ -316,7 +318,7 @@ pub async fn update(
         return Html(content_template.render().unwrap());
     }
 
-    match db::update_backup(&pool, id, form.clone()) {
+    match db::update_backup(&pool, id, form.clone(), &audit) {
         Ok(_) => {
             let backup = match db::get_backup(&pool, id) {
                 Ok(backup) => backup,
@@ -402,8 +404,9 @@ pub async fn delete(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
 
-    match db::delete_backup(&pool, id) {
+    match db::delete_backup(&pool, id, &audit) {
         Ok(_) => {
             // Redirect to domains page after deleting backup
             Html("<script>window.location.href='/domains';</script>".to_string())
@@ -420,9 +423,10 @@ pub async fn toggle_enabled(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
     let locale = crate::handlers::language::get_user_locale(&headers);
 
-    match db::toggle_backup_enabled(&pool, id) {
+    match db::toggle_backup_enabled(&pool, id, &audit) {
         Ok(_) => {
             let backup = match db::get_backup(&pool, id) {
                 Ok(backup) => backup,
@@ -465,8 +469,9 @@ pub async fn toggle_enabled_show(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
     let locale = crate::handlers::language::get_user_locale(&headers);
-    match db::toggle_backup_enabled(&pool, id) {
+    match db::toggle_backup_enabled(&pool, id, &audit) {
         Ok(_) => {
             let backup = match db::get_backup(&pool, id) {
                 Ok(backup) => backup,
@@ -118,7 +118,7 @@ pub async fn list_clients(
     if is_htmx_request(&headers) {
         Html(content)
     } else {
-        let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+        let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
             .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
         let current_db_label = state
             .db_manager
@@ -218,7 +218,7 @@ pub async fn show_client(
     if is_htmx_request(&headers) {
         Html(content)
     } else {
-        let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+        let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
             .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
         let current_db_label = state
             .db_manager
@@ -296,7 +296,7 @@ pub async fn create_client_form(State(state): State<AppState>, headers: HeaderMa
     if is_htmx_request(&headers) {
         Html(content)
     } else {
-        let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+        let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
             .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
         let current_db_label = state
             .db_manager
@@ -389,7 +389,7 @@ pub async fn edit_client_form(
     if is_htmx_request(&headers) {
         Html(content)
     } else {
-        let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+        let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
             .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
         let current_db_label = state
             .db_manager
@@ -423,7 +423,9 @@ pub async fn create_client(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
-    let client = db::create_client(&pool, client_data).map_err(|e| {
+
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
+    let client = db::create_client(&pool, client_data, &audit).map_err(|e| {
         warn!("Failed to create client: {:?}", e);
         (
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -447,7 +449,9 @@ pub async fn update_client(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
-    let client = db::update_client(&pool, client_id, client_data).map_err(|e| {
+
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
+    let client = db::update_client(&pool, client_id, client_data, &audit).map_err(|e| {
         warn!("Failed to update client {}: {:?}", client_id, e);
         (
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -470,7 +474,9 @@ pub async fn delete_client(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
-    db::delete_client(&pool, client_id).map_err(|e| {
+
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
+    db::delete_client(&pool, client_id, &audit).map_err(|e| {
         warn!("Failed to delete client {}: {:?}", client_id, e);
         (
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -494,7 +500,9 @@ pub async fn toggle_client(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
-    let client = db::toggle_client_enabled(&pool, client_id).map_err(|e| {
+
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
+    let client = db::toggle_client_enabled(&pool, client_id, &audit).map_err(|e| {
         warn!("Failed to toggle client {}: {:?}", client_id, e);
         (
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -1,98 +1,284 @@
+use crate::config::{Config, ConfigUpdate, DatabaseFeatures, DomainOverride, GlobalFeatures};
 use crate::templates::config::ConfigTemplate;
 use crate::templates::layout::BaseTemplate;
-use crate::{config::Config, i18n::get_translation, AppState};
+use crate::{
+    i18n::{get_translation, get_translation_with_args},
+    AppState,
+};
 use askama::Template;
 use axum::{
-    extract::State,
+    extract::{Form, Query, State},
     http::{HeaderMap, StatusCode},
-    response::Html,
+    response::{Html, IntoResponse, Redirect, Response},
 };
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Deserialize)]
+pub struct ConfigQuery {
+    #[serde(default)]
+    pub saved: bool,
+}
 
 pub async fn view_config(
     State(state): State<AppState>,
     headers: HeaderMap,
+    Query(query): Query<ConfigQuery>,
 ) -> Result<Html<String>, StatusCode> {
     let locale = crate::handlers::language::get_user_locale(&headers);
+    let success_message = if query.saved {
+        Some(get_translation(&state, &locale, "config-saved").await)
+    } else {
+        None
+    };
+    let config = state.config.get();
+    render_config_page(&state, &headers, &config, success_message, None).await
+}
+
+/// Save the config page to the config file and apply it to the running server
+pub async fn update_config(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Form(fields): Form<Vec<(String, String)>>,
+) -> Response {
+    let locale = crate::handlers::language::get_user_locale(&headers);
+    let current = state.config.get();
+
+    let Some(admin) = crate::handlers::auth::get_admin(&state, &headers) else {
+        return StatusCode::UNAUTHORIZED.into_response();
+    };
+    if !current.can_edit_config(&admin) {
+        tracing::warn!(
+            "Admin '{}' tried to change the configuration without edit access to every database",
+            admin.username
+        );
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    let update = parse_config_form(&current, &fields);
+    match state.config.save(&update) {
+        Ok(_) => {
+            tracing::info!("Configuration updated by admin '{}'", admin.username);
+            Redirect::to("/config?saved=true").into_response()
+        }
+        Err(e) => {
+            let status = if e.is_validation_error() {
+                StatusCode::UNPROCESSABLE_ENTITY
+            } else {
+                tracing::error!("Failed to save configuration: {:?}", e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            };
+            let error_message = get_translation_with_args(
+                &state,
+                &locale,
+                "config-save-failed",
+                HashMap::from([("error".to_string(), e.to_string())]),
+            )
+            .await;
+            // Show the submitted settings again so nothing has to be re-entered
+            let submitted = current
+                .apply_update(&update)
+                .unwrap_or_else(|_| (*current).clone());
+            match render_config_page(&state, &headers, &submitted, None, Some(error_message)).await
+            {
+                Ok(page) => (status, page).into_response(),
+                Err(status) => status.into_response(),
+            }
+        }
+    }
+}
+
+/// Turn the submitted config page into an update
+///
+/// The alias lists arrive as repeated `required_aliases[]`, `common_aliases[]` and
+/// `domain_overrides[<domain>][required|common][]` fields, and the feature toggles
+/// as checkboxes named `global_features[<flag>]` and
+/// `database_features[<database>][<flag>]`. Unchecked boxes are not submitted, so
+/// every configured database starts with all features off.
+pub fn parse_config_form(config: &Config, fields: &[(String, String)]) -> ConfigUpdate {
+    let mut update = ConfigUpdate {
+        required_aliases: Vec::new(),
+        common_aliases: Vec::new(),
+        domain_overrides: HashMap::new(),
+        global_features: GlobalFeatures::default(),
+        database_features: config
+            .databases
+            .iter()
+            .map(|db| (db.id.clone(), DatabaseFeatures::default()))
+            .collect(),
+    };
+
+    for (name, value) in fields {
+        let value = value.trim();
+        if name == "required_aliases[]" {
+            push_alias(&mut update.required_aliases, value);
+        } else if name == "common_aliases[]" {
+            push_alias(&mut update.common_aliases, value);
+        } else if let Some(rest) = name.strip_prefix("domain_overrides[") {
+            let Some((domain, list)) = rest.split_once("][") else {
+                continue;
+            };
+            let domain_override = update
+                .domain_overrides
+                .entry(domain.trim().to_lowercase())
+                .or_insert_with(|| DomainOverride {
+                    required: Vec::new(),
+                    common: Vec::new(),
+                });
+            match list {
+                "required][]" => push_alias(&mut domain_override.required, value),
+                "common][]" => push_alias(&mut domain_override.common, value),
+                _ => {}
+            }
+        } else if let Some(flag) = name
+            .strip_prefix("global_features[")
+            .and_then(|rest| rest.strip_suffix(']'))
+        {
+            let global = &mut update.global_features;
+            match flag {
+                "read_only" => global.read_only = true,
+                "no_new_users" => global.no_new_users = true,
+                "no_new_domains" => global.no_new_domains = true,
+                "no_password_updates" => global.no_password_updates = true,
+                _ => {}
+            }
+        } else if let Some((database_id, flag)) = name
+            .strip_prefix("database_features[")
+            .and_then(|rest| rest.strip_suffix(']'))
+            .and_then(|rest| rest.split_once("]["))
+        {
+            // Unknown databases are kept so that saving reports them
+            let features = update
+                .database_features
+                .entry(database_id.to_string())
+                .or_default();
+            match flag {
+                "read_only" => features.read_only = true,
+                "no_new_users" => features.no_new_users = true,
+                "no_new_domains" => features.no_new_domains = true,
+                "no_password_updates" => features.no_password_updates = true,
+                "disabled" => features.disabled = true,
+                _ => {}
+            }
+        }
+    }
+
+    update
+}
+
+fn push_alias(aliases: &mut Vec<String>, alias: &str) {
+    if !alias.is_empty() {
+        aliases.push(alias.to_string());
+    }
+}
+
+async fn render_config_page(
+    state: &AppState,
+    headers: &HeaderMap,
+    config: &Config,
+    success_message: Option<String>,
+    error_message: Option<String>,
+) -> Result<Html<String>, StatusCode> {
+    let locale = crate::handlers::language::get_user_locale(headers);
 
     // Get translations
-    let title = get_translation(&state, &locale, "config-title").await;
-    let description = get_translation(&state, &locale, "config-description").await;
+    let title = get_translation(state, &locale, "config-title").await;
+    let description = get_translation(state, &locale, "config-description").await;
     let required_aliases_header =
-        get_translation(&state, &locale, "config-required-aliases-header").await;
+        get_translation(state, &locale, "config-required-aliases-header").await;
     let common_aliases_header =
-        get_translation(&state, &locale, "config-common-aliases-header").await;
+        get_translation(state, &locale, "config-common-aliases-header").await;
     let domain_overrides_header =
-        get_translation(&state, &locale, "config-domain-overrides-header").await;
-    let save_button = get_translation(&state, &locale, "config-save-button").await;
-    let cancel_button = get_translation(&state, &locale, "config-cancel-button").await;
+        get_translation(state, &locale, "config-domain-overrides-header").await;
+    let save_button = get_translation(state, &locale, "config-save-button").await;
+    let cancel_button = get_translation(state, &locale, "config-cancel-button").await;
     let add_required_alias_button =
-        get_translation(&state, &locale, "config-add-required-alias-button").await;
+        get_translation(state, &locale, "config-add-required-alias-button").await;
     let add_common_alias_button =
-        get_translation(&state, &locale, "config-add-common-alias-button").await;
-    let remove_alias_button = get_translation(&state, &locale, "config-remove-alias-button").await;
-    let promote_button = get_translation(&state, &locale, "config-promote-button").await;
-    let demote_button = get_translation(&state, &locale, "config-demote-button").await;
+        get_translation(state, &locale, "config-add-common-alias-button").await;
+    let remove_alias_button = get_translation(state, &locale, "config-remove-alias-button").await;
+    let promote_button = get_translation(state, &locale, "config-promote-button").await;
+    let demote_button = get_translation(state, &locale, "config-demote-button").await;
     let required_aliases_description =
-        get_translation(&state, &locale, "config-required-aliases-description").await;
+        get_translation(state, &locale, "config-required-aliases-description").await;
     let common_aliases_description =
-        get_translation(&state, &locale, "config-common-aliases-description").await;
+        get_translation(state, &locale, "config-common-aliases-description").await;
     let domain_overrides_description =
-        get_translation(&state, &locale, "config-domain-overrides-description").await;
+        get_translation(state, &locale, "config-domain-overrides-description").await;
     let add_domain_override_button =
-        get_translation(&state, &locale, "config-add-domain-override-button").await;
-    let remove_domain_button =
-        get_translation(&state, &locale, "config-remove-domain-button").await;
+        get_translation(state, &locale, "config-add-domain-override-button").await;
+    let remove_domain_button = get_translation(state, &locale, "config-remove-domain-button").await;
     let required_aliases_label =
-        get_translation(&state, &locale, "config-required-aliases-label").await;
-    let common_aliases_label =
-        get_translation(&state, &locale, "config-common-aliases-label").await;
-    let remove_button = get_translation(&state, &locale, "config-remove-button").await;
-    let add_alias_button = get_translation(&state, &locale, "config-add-alias-button").await;
+        get_translation(state, &locale, "config-required-aliases-label").await;
+    let common_aliases_label = get_translation(state, &locale, "config-common-aliases-label").await;
+    let remove_button = get_translation(state, &locale, "config-remove-button").await;
+    let add_alias_button = get_translation(state, &locale, "config-add-alias-button").await;
     let placeholder_required_alias =
-        get_translation(&state, &locale, "config-placeholder-required-alias").await;
+        get_translation(state, &locale, "config-placeholder-required-alias").await;
     let placeholder_common_alias =
-        get_translation(&state, &locale, "config-placeholder-common-alias").await;
-    let placeholder_domain = get_translation(&state, &locale, "config-placeholder-domain").await;
+        get_translation(state, &locale, "config-placeholder-common-alias").await;
+    let placeholder_domain = get_translation(state, &locale, "config-placeholder-domain").await;
     let placeholder_domain_alias =
-        get_translation(&state, &locale, "config-placeholder-domain-alias").await;
+        get_translation(state, &locale, "config-placeholder-domain-alias").await;
 
     // Global Feature Toggles translations
     let global_features_header =
-        get_translation(&state, &locale, "config-global-features-header").await;
+        get_translation(state, &locale, "config-global-features-header").await;
     let global_features_description =
-        get_translation(&state, &locale, "config-global-features-description").await;
-    let feature_read_only = get_translation(&state, &locale, "config-feature-read-only").await;
-    let feature_no_new_users =
-        get_translation(&state, &locale, "config-feature-no-new-users").await;
+        get_translation(state, &locale, "config-global-features-description").await;
+    let feature_read_only = get_translation(state, &locale, "config-feature-read-only").await;
+    let feature_no_new_users = get_translation(state, &locale, "config-feature-no-new-users").await;
     let feature_no_new_domains =
-        get_translation(&state, &locale, "config-feature-no-new-domains").await;
+        get_translation(state, &locale, "config-feature-no-new-domains").await;
     let feature_no_password_updates =
-        get_translation(&state, &locale, "config-feature-no-password-updates").await;
+        get_translation(state, &locale, "config-feature-no-password-updates").await;
     let feature_database_disabled =
-        get_translation(&state, &locale, "config-feature-database-disabled").await;
-    let status_enabled = get_translation(&state, &locale, "config-status-enabled").await;
-    let status_disabled = get_translation(&state, &locale, "config-status-disabled").await;
+        get_translation(state, &locale, "config-feature-database-disabled").await;
+    let status_enabled = get_translation(state, &locale, "config-status-enabled").await;
 
     // Database Feature Toggles translations
     let database_features_header =
-        get_translation(&state, &locale, "config-database-features-header").await;
+        get_translation(state, &locale, "config-database-features-header").await;
     let database_features_description =
-        get_translation(&state, &locale, "config-database-features-description").await;
+        get_translation(state, &locale, "config-database-features-description").await;
     let database_disabled_badge =
-        get_translation(&state, &locale, "config-database-disabled-badge").await;
+        get_translation(state, &locale, "config-database-disabled-badge").await;
 
-    // Load current configuration
-    let config = match Config::load() {
-        Ok(config) => config,
-        Err(e) => {
-            tracing::error!("Error loading config: {:?}", e);
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
-        }
+    let reload_error = match state.config.reload_failure() {
+        Some(failure) => Some(
+            get_translation_with_args(
+                state,
+                &locale,
+                "config-reload-failed",
+                HashMap::from([
+                    (
+                        "time".to_string(),
+                        failure
+                            .failed_at
+                            .format("%Y-%m-%d %H:%M:%S UTC")
+                            .to_string(),
+                    ),
+                    ("error".to_string(), failure.error),
+                ]),
+            )
+            .await,
+        ),
+        None => None,
     };
 
+    let mail_config_link = get_translation(state, &locale, "config-mail-config-link").await;
+    let can_edit = crate::handlers::auth::has_edit_permissions(state, headers);
+
     // Create the config template
-    let domain_overrides_vec: Vec<(&String, &crate::config::DomainOverride)> =
-        config.domain_overrides.iter().collect();
+    let mut domain_overrides_vec: Vec<(&String, &DomainOverride, String)> = config
+        .domain_overrides
+        .iter()
+        .map(|(domain, domain_override)| {
+            let placeholder = placeholder_domain_alias.replace("{domain}", domain);
+            (domain, domain_override, placeholder)
+        })
+        .collect();
+    domain_overrides_vec.sort_by_key(|(domain, _, _)| *domain);
     let content_template = ConfigTemplate {
         title: &title,
         description: &description,
@@ -128,12 +314,15 @@ pub async fn view_config(
         feature_no_password_updates: &feature_no_password_updates,
         feature_database_disabled: &feature_database_disabled,
         status_enabled: &status_enabled,
-        status_disabled: &status_disabled,
         // Database Feature Toggles
         database_features_header: &database_features_header,
         database_features_description: &database_features_description,
         database_disabled_badge: &database_disabled_badge,
-        config: &config,
+        success_message: success_message.as_deref(),
+        error_message: error_message.as_deref(),
+        reload_error: reload_error.as_deref(),
+        mail_config_link: can_edit.then_some(mail_config_link.as_str()),
+        config,
         domain_overrides_vec,
     };
 
@@ -147,7 +336,7 @@ pub async fn view_config(
 
     // Create the base template
     // Get current database id from session/cookie or default
-    let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+    let current_db_id = crate::handlers::auth::get_selected_database(state, headers)
         .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
     // Get current database label from db_manager
     let current_db_label = state
@@ -161,7 +350,7 @@ pub async fn view_config(
     let template = match BaseTemplate::with_i18n(
         title,
         content,
-        &state,
+        state,
         &locale,
         current_db_label,
         current_db_id,
@@ -32,6 +32,8 @@ pub async fn index(State(state): State<AppState>, headers: HeaderMap) -> Html<St
             "dashboard-enabled-domains-and-backups",
             "dashboard-enabled-aliases",
             "dashboard-enabled-users",
+            "dashboard-allocated-quota",
+            "dashboard-expiring-aliases",
             "dashboard-quick-actions",
             "dashboard-primary-actions",
             "dashboard-advanced-management",
@@ -86,6 +88,8 @@ pub async fn index(State(state): State<AppState>, headers: HeaderMap) -> Html<St
         enabled_domains_and_backups: &common_translations["dashboard-enabled-domains-and-backups"],
         enabled_aliases: &common_translations["dashboard-enabled-aliases"],
         enabled_users: &common_translations["dashboard-enabled-users"],
+        allocated_quota: &common_translations["dashboard-allocated-quota"],
+        expiring_aliases: &common_translations["dashboard-expiring-aliases"],
         quick_actions: &common_translations["dashboard-quick-actions"],
         primary_actions: &common_translations["dashboard-primary-actions"],
         advanced_management: &common_translations["dashboard-advanced-management"],
@@ -2,6 +2,7 @@ use askama::Template;
 use axum::{extract::State, http::StatusCode, response::Html, Form};
 use serde::Deserialize;
 
+use crate::config::AdminRole;
 use crate::{render_template_with_title, AppState};
 
 #[derive(Deserialize)]
@@ -17,14 +18,14 @@ pub struct MigrationForm {
 
 /// Show the database selection page
 pub async fn index(State(state): State<AppState>, headers: axum::http::HeaderMap) -> Html<String> {
-    let databases = state.db_manager.get_configs();
+    let databases = crate::handlers::auth::get_accessible_databases(&state, &headers);
 
     // Get the currently selected database from the session, or fall back to default
-    let current_db = crate::handlers::auth::get_selected_database(&headers)
+    let current_db = crate::handlers::auth::get_selected_database(&state, &headers)
         .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
 
     let content_template = crate::templates::database::DatabaseSelectionTemplate {
-        databases,
+        databases: &databases,
         current_db: &current_db,
     };
 
@@ -48,6 +49,19 @@ pub async fn select(
         return Err(StatusCode::BAD_REQUEST);
     }
 
+    // Only allow switching to databases the admin has been granted
+    let Some(admin) = crate::handlers::auth::get_admin(&state, &headers) else {
+        return Err(StatusCode::UNAUTHORIZED);
+    };
+    if !admin.can_access_database(&form.database_id) {
+        tracing::warn!(
+            "Admin '{}' denied access to database '{}'",
+            admin.username,
+            form.database_id
+        );
+        return Err(StatusCode::FORBIDDEN);
+    }
+
     // Determine redirect target
     let redirect_url = form.redirect.as_deref().unwrap_or("/");
     let redirect_url = if redirect_url.is_empty() {
@@ -57,7 +71,9 @@ pub async fn select(
     };
 
     // Update the session with the new database selection
-    let new_cookie = crate::handlers::auth::update_session_database(&headers, &form.database_id);
+    if !crate::handlers::auth::update_session_database(&state, &headers, &form.database_id) {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
 
     // Check if this is an HTMX request
     let is_htmx = headers
@@ -68,40 +84,41 @@ pub async fn select(
 
     if is_htmx {
         // For HTMX requests, return a response with HX-Redirect header
-        let mut response_builder = axum::response::Response::builder()
+        Ok(axum::response::Response::builder()
             .status(axum::http::StatusCode::OK)
-            .header("HX-Redirect", redirect_url);
-
-        if let Some(cookie) = new_cookie {
-            response_builder = response_builder.header("Set-Cookie", cookie);
-        }
-
-        Ok(response_builder.body("".into()).unwrap())
+            .header("HX-Redirect", redirect_url)
+            .body("".into())
+            .unwrap())
     } else {
         // For regular requests, return a standard redirect
-        let mut response_builder = axum::response::Response::builder()
+        Ok(axum::response::Response::builder()
             .status(axum::http::StatusCode::FOUND)
-            .header("Location", redirect_url);
-
-        if let Some(cookie) = new_cookie {
-            response_builder = response_builder.header("Set-Cookie", cookie);
-        }
-
-        Ok(response_builder.body("".into()).unwrap())
+            .header("Location", redirect_url)
+            .body("".into())
+            .unwrap())
     }
 }
 
 /// Run migrations on databases
 pub async fn run_migrations(
     State(state): State<AppState>,
+    headers: axum::http::HeaderMap,
     Form(form): Form<MigrationForm>,
 ) -> Result<axum::response::Response, StatusCode> {
+    let Some(admin) = crate::handlers::auth::get_admin(&state, &headers) else {
+        return Err(StatusCode::UNAUTHORIZED);
+    };
+    let can_migrate = |db_id: &str| admin.role_for_database(db_id) == Some(AdminRole::Edit);
+
     match form.database_id {
         Some(db_id) => {
             // Run migrations on specific database
             if !state.db_manager.has_database(&db_id).await {
                 return Err(StatusCode::BAD_REQUEST);
             }
+            if !can_migrate(&db_id) {
+                return Err(StatusCode::FORBIDDEN);
+            }
 
             match state.db_manager.run_migrations_on_database(&db_id).await {
                 Ok(_) => {
@@ -119,7 +136,15 @@ pub async fn run_migrations(
             }
         }
         None => {
-            // Run migrations on all databases
+            // Run migrations on all databases, which needs edit access to every one of them
+            if !state
+                .db_manager
+                .get_configs()
+                .iter()
+                .all(|config| can_migrate(&config.id))
+            {
+                return Err(StatusCode::FORBIDDEN);
+            }
             match state.db_manager.run_migrations_on_all_databases().await {
                 Ok(_) => {
                     tracing::info!("Migrations completed successfully on all databases");
@@ -141,8 +166,9 @@ pub async fn run_migrations(
 /// Get available databases as JSON (for API use)
 pub async fn list_databases(
     State(state): State<AppState>,
+    headers: axum::http::HeaderMap,
 ) -> Result<axum::Json<Vec<crate::config::DatabaseConfig>>, StatusCode> {
-    let configs = state.db_manager.get_configs().to_vec();
+    let configs = crate::handlers::auth::get_accessible_databases(&state, &headers);
     Ok(axum::Json(configs))
 }
 
@@ -151,8 +177,8 @@ pub async fn dropdown(
     State(state): State<AppState>,
     headers: axum::http::HeaderMap,
 ) -> Html<String> {
-    let databases = state.db_manager.get_configs();
-    let current_db = crate::handlers::auth::get_selected_database(&headers)
+    let databases = crate::handlers::auth::get_accessible_databases(&state, &headers);
+    let current_db = crate::handlers::auth::get_selected_database(&state, &headers)
         .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
     // Try to get the current URL from Referer header, fallback to "/"
     let current_url = headers
@@ -160,7 +186,7 @@ pub async fn dropdown(
         .and_then(|v| v.to_str().ok())
         .unwrap_or("/");
     let content_template = crate::templates::database::DatabaseDropdownTemplate {
-        databases,
+        databases: &databases,
         current_db: &current_db,
         current_url,
     };
@@ -130,6 +130,9 @@ pub async fn new(State(state): State<AppState>, headers: HeaderMap) -> Html<Stri
         domain: "".to_string(),
         transport: "virtual".to_string(),
         enabled: true,
+        default_quota_bytes: None,
+        default_quota_messages: None,
+        quota_cap_bytes: None,
     };
     let translations = crate::handlers::utils::get_translations_batch(
         &state,
@@ -168,6 +171,7 @@ pub async fn new(State(state): State<AppState>, headers: HeaderMap) -> Html<Stri
         form_tooltip_domain: &translations["form-tooltip-domain"],
         form_tooltip_transport: &translations["form-tooltip-transport"],
         form_tooltip_enable: &translations["form-tooltip-enable"],
+        quota: crate::handlers::utils::get_quota_labels(&state, &locale).await,
     };
     render_template_with_title!(
         content_template,
@@ -193,7 +197,7 @@ pub async fn show(
         "domains-not-found"
     );
     let locale = crate::handlers::utils::get_user_locale(&headers);
-    let alias_report = db::get_domain_alias_report(&pool, &domain.domain).ok();
+    let alias_report = db::get_domain_alias_report(&pool, &state.config.get(), &domain.domain).ok();
     let existing_aliases = db::get_aliases_for_domain(&pool, &domain.domain).unwrap_or_default();
     let translations = crate::handlers::utils::get_translations_batch(
         &state,
@@ -288,6 +292,7 @@ pub async fn show(
         action_view: &translations["action-view"],
         enable_alias: &translations["aliases-enable-alias"],
         disable_alias: &translations["aliases-disable-alias"],
+        quota: crate::handlers::utils::get_quota_labels(&state, &locale).await,
     };
     render_template_with_title!(
         content_template,
@@ -317,6 +322,9 @@ pub async fn edit(
         domain: domain.domain.clone(),
         transport: domain.transport.clone().unwrap_or_default(),
         enabled: domain.enabled,
+        default_quota_bytes: domain.default_quota_bytes,
+        default_quota_messages: domain.default_quota_messages,
+        quota_cap_bytes: domain.quota_cap_bytes,
     };
 
     let title = get_translation(&state, &locale, "domains-edit-domain").await;
@@ -351,6 +359,7 @@ pub async fn edit(
         form_tooltip_domain: &form_tooltip_domain,
         form_tooltip_transport: &form_tooltip_transport,
         form_tooltip_enable: &form_tooltip_enable,
+        quota: crate::handlers::utils::get_quota_labels(&state, &locale).await,
     };
     Html(content_template.render().unwrap())
 }
@@ -361,13 +370,19 @@ pub async fn create(
     Form(form): Form<DomainForm>,
 ) -> Html<String> {
     // Get current database ID for restriction checks
-    let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+    let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
         .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
 
-    // Check database restrictions
-    if let Err(_status_code) =
-        crate::handlers::utils::check_database_restrictions(&state, &current_db_id, "create_domain")
-    {
+    // Check database restrictions and the admin's grant on this database
+    let admin = crate::handlers::auth::get_session(&state, &headers)
+        .map(|session| session.username)
+        .unwrap_or_default();
+    if let Err(_status_code) = crate::handlers::utils::check_database_restrictions(
+        &state,
+        Some(&admin),
+        &current_db_id,
+        "create_domain",
+    ) {
         let locale = crate::handlers::language::get_user_locale(&headers);
         let error_msg = get_translation(&state, &locale, "error-operation-not-allowed").await;
         let title = get_translation(&state, &locale, "domains-new-domain").await;
@@ -403,6 +418,7 @@ pub async fn create(
             form_tooltip_domain: &form_tooltip_domain,
             form_tooltip_transport: &form_tooltip_transport,
             form_tooltip_enable: &form_tooltip_enable,
+            quota: crate::handlers::utils::get_quota_labels(&state, &locale).await,
         };
         return Html(content_template.render().unwrap());
     }
@@ -411,6 +427,8 @@ pub async fn create(
         .await
         .expect("Failed to get database pool");
 
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
+
     // Validate form data
     if form.domain.trim().is_empty() {
         let locale = crate::handlers::language::get_user_locale(&headers);
@@ -448,6 +466,7 @@ pub async fn create(
             form_tooltip_domain: &form_tooltip_domain,
             form_tooltip_transport: &form_tooltip_transport,
             form_tooltip_enable: &form_tooltip_enable,
+            quota: crate::handlers::utils::get_quota_labels(&state, &locale).await,
         };
         return Html(content_template.render().unwrap());
     }
@@ -456,9 +475,12 @@ pub async fn create(
         domain: form.domain.trim().to_string(),
         transport: Some(form.transport.clone()),
         enabled: form.enabled,
+        default_quota_bytes: form.default_quota_bytes,
+        default_quota_messages: form.default_quota_messages,
+        quota_cap_bytes: form.quota_cap_bytes,
     };
 
-    match db::create_domain(&pool, new_domain) {
+    match db::create_domain(&pool, new_domain, &audit) {
         Ok(_) => {
             let domains = db::get_domains(&pool).unwrap_or_default();
 
@@ -601,6 +623,7 @@ pub async fn create(
                 form_tooltip_domain: &form_tooltip_domain,
                 form_tooltip_transport: &form_tooltip_transport,
                 form_tooltip_enable: &form_tooltip_enable,
+                quota: crate::handlers::utils::get_quota_labels(&state, &locale).await,
             };
             Html(content_template.render().unwrap())
         }
@@ -614,13 +637,19 @@ pub async fn update(
     Form(form): Form<DomainForm>,
 ) -> Html<String> {
     // Get current database ID for restriction checks
-    let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+    let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
         .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
 
-    // Check database restrictions
-    if let Err(_status_code) =
-        crate::handlers::utils::check_database_restrictions(&state, &current_db_id, "update_domain")
-    {
+    // Check database restrictions and the admin's grant on this database
+    let admin = crate::handlers::auth::get_session(&state, &headers)
+        .map(|session| session.username)
+        .unwrap_or_default();
+    if let Err(_status_code) = crate::handlers::utils::check_database_restrictions(
+        &state,
+        Some(&admin),
+        &current_db_id,
+        "update_domain",
+    ) {
         let locale = crate::handlers::language::get_user_locale(&headers);
         let error_msg = get_translation(&state, &locale, "error-operation-not-allowed").await;
         let title = get_translation(&state, &locale, "domains-edit-domain").await;
@@ -656,6 +685,7 @@ pub async fn update(
             form_tooltip_domain: &form_tooltip_domain,
             form_tooltip_transport: &form_tooltip_transport,
             form_tooltip_enable: &form_tooltip_enable,
+            quota: crate::handlers::utils::get_quota_labels(&state, &locale).await,
         };
         return Html(content_template.render().unwrap());
     }
@@ -664,6 +694,8 @@ pub async fn update(
         .await
         .expect("Failed to get database pool");
 
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
+
     // Validate form data
     if form.domain.trim().is_empty() {
         let locale = crate::handlers::language::get_user_locale(&headers);
@@ -701,12 +733,18 @@ pub async fn update(
             form_tooltip_domain: &form_tooltip_domain,
             form_tooltip_transport: &form_tooltip_transport,
             form_tooltip_enable: &form_tooltip_enable,
+            quota: crate::handlers::utils::get_quota_labels(&state, &locale).await,
         };
         return Html(content_template.render().unwrap());
     }
 
     let domain_name = form.domain.clone();
-    match db::update_domain(&pool, id, form) {
+    let quotas = (
+        form.default_quota_bytes,
+        form.default_quota_messages,
+        form.quota_cap_bytes,
+    );
+    match db::update_domain(&pool, id, form, &audit) {
         Ok(_) => {
             let domain = match db::get_domain(&pool, id) {
                 Ok(domain) => domain,
@@ -777,6 +815,7 @@ pub async fn update(
                 action_view: "",
                 enable_alias: "",
                 disable_alias: "",
+                quota: crate::handlers::utils::get_quota_labels(&state, &locale).await,
             };
             Html(content_template.render().unwrap())
         }
@@ -788,9 +827,10 @@ pub async fn update(
                 ) => format!("A domain with the name '{domain_name}' already exists."),
                 diesel::result::Error::DatabaseError(
                     diesel::result::DatabaseErrorKind::CheckViolation,
-                    _,
-                ) => "The domain data does not meet the required constraints. Please check your input.".to_string(),
-                _ => "An unexpected error occurred while updating the domain. Please try again.".to_string(),
+                    info,
+                ) => info.message().to_string(),
+                _ => "An unexpected error occurred while updating the domain. Please try again."
+                    .to_string(),
             };
 
             // Recreate the form for error display
@@ -798,6 +838,9 @@ pub async fn update(
                 domain: domain_name,
                 transport: "virtual".to_string(),
                 enabled: true,
+                default_quota_bytes: quotas.0,
+                default_quota_messages: quotas.1,
+                quota_cap_bytes: quotas.2,
             };
 
             let locale = crate::handlers::language::get_user_locale(&headers);
@@ -834,6 +877,7 @@ pub async fn update(
                 form_tooltip_domain: &form_tooltip_domain,
                 form_tooltip_transport: &form_tooltip_transport,
                 form_tooltip_enable: &form_tooltip_enable,
+                quota: crate::handlers::utils::get_quota_labels(&state, &locale).await,
             };
             Html(content_template.render().unwrap())
         }
@@ -848,8 +892,9 @@ pub async fn delete(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
 
-    match db::delete_domain(&pool, id) {
+    match db::delete_domain(&pool, id, &audit) {
         Ok(_) => {
             let domains = db::get_domains(&pool).unwrap_or_default();
 
@@ -955,9 +1000,10 @@ pub async fn toggle_enabled(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
     let locale = crate::handlers::language::get_user_locale(&headers);
 
-    match db::toggle_domain_enabled(&pool, id) {
+    match db::toggle_domain_enabled(&pool, id, &audit) {
         Ok(_) => {
             let domain = match db::get_domain(&pool, id) {
                 Ok(domain) => domain,
@@ -1028,11 +1074,12 @@ pub async fn toggle_enabled(
                 action_view: "",
                 enable_alias: "",
                 disable_alias: "",
+                quota: crate::handlers::utils::get_quota_labels(&state, &locale).await,
             };
             let content = content_template.render().unwrap();
 
             // Get current database id from session/cookie or default
-            let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+            let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
                 .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
             // Get current database label from db_manager
             let current_db_label = state
@@ -1068,7 +1115,8 @@ pub async fn toggle_enabled_list(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
-    match db::toggle_domain_enabled(&pool, id) {
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
+    match db::toggle_domain_enabled(&pool, id, &audit) {
         Ok(_) => {
             let domains = db::get_domains(&pool).unwrap_or_default();
 
@@ -1174,7 +1222,8 @@ pub async fn toggle_enabled_show(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
-    match db::toggle_domain_enabled(&pool, id) {
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
+    match db::toggle_domain_enabled(&pool, id, &audit) {
         Ok(_) => {
             let domain = match db::get_domain(&pool, id) {
                 Ok(domain) => domain,
@@ -1245,6 +1294,7 @@ pub async fn toggle_enabled_show(
                 action_view: "",
                 enable_alias: "",
                 disable_alias: "",
+                quota: crate::handlers::utils::get_quota_labels(&state, &locale).await,
             };
             Html(content_template.render().unwrap())
         }
@@ -1261,6 +1311,7 @@ pub async fn add_missing_required_aliases(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
     let locale = crate::handlers::language::get_user_locale(&headers);
 
     let domain = match db::get_domain(&pool, id) {
@@ -1271,17 +1322,9 @@ pub async fn add_missing_required_aliases(
         }
     };
 
-    // Load configuration to get required aliases
-    let _config = match crate::config::Config::load() {
-        Ok(config) => config,
-        Err(e) => {
-            tracing::warn!("Failed to load config, using defaults: {:?}", e);
-            crate::config::Config::default()
-        }
-    };
-
     // Get current alias report to see what's missing
-    let alias_report = match db::get_domain_alias_report(&pool, &domain.domain) {
+    let alias_report = match db::get_domain_alias_report(&pool, &state.config.get(), &domain.domain)
+    {
         Ok(report) => report,
         Err(e) => {
             tracing::error!(
@@ -1302,7 +1345,7 @@ pub async fn add_missing_required_aliases(
         .collect();
 
     if !aliases_to_create.is_empty() {
-        match db::create_domain_aliases(&pool, &domain.domain, aliases_to_create) {
+        match db::create_domain_aliases(&pool, &domain.domain, aliases_to_create, &audit) {
             Ok(created_aliases) => {
                 tracing::info!(
                     "Created {} missing required aliases for domain {}",
@@ -1339,6 +1382,7 @@ pub async fn add_missing_required_alias(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
     let locale = crate::handlers::language::get_user_locale(&headers);
 
     let domain = match db::get_domain(&pool, id) {
@@ -1353,7 +1397,7 @@ pub async fn add_missing_required_alias(
     let destination = format!("admin@{}", domain.domain);
     let aliases_to_create = vec![(alias.clone(), destination)];
 
-    match db::create_domain_aliases(&pool, &domain.domain, aliases_to_create) {
+    match db::create_domain_aliases(&pool, &domain.domain, aliases_to_create, &audit) {
         Ok(_created_aliases) => {
             tracing::info!(
                 "Created missing required alias {} for domain {}",
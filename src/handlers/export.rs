@@ -0,0 +1,143 @@
+use crate::bundle::{self, BundleFormat};
+use crate::config::{AdminRole, DatabaseConfig};
+use crate::templates::export::*;
+use crate::{render_template_with_title, AppState};
+use askama::Template;
+use axum::{
+    extract::{Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{Html, IntoResponse, Response},
+};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct DownloadQuery {
+    pub database: String,
+    #[serde(default)]
+    pub format: BundleFormat,
+    /// Checkbox value; present when hashes were requested
+    pub password_hashes: Option<String>,
+}
+
+/// Databases the current admin may export; a bundle holds every domain, so
+/// admins limited to some domains get none
+fn exportable_databases(state: &AppState, headers: &HeaderMap) -> Vec<DatabaseConfig> {
+    match crate::handlers::auth::get_admin(state, headers) {
+        Some(admin) if !admin.is_domain_admin() => {
+            crate::handlers::auth::get_accessible_databases(state, headers)
+        }
+        _ => Vec::new(),
+    }
+}
+
+pub async fn index(State(state): State<AppState>, headers: HeaderMap) -> Html<String> {
+    let locale = crate::handlers::utils::get_user_locale(&headers);
+    let translations = crate::handlers::utils::get_translations_batch(
+        &state,
+        &locale,
+        &[
+            "export-title",
+            "export-description",
+            "export-format",
+            "export-password-hashes",
+            "export-password-hashes-hint",
+            "export-download",
+            "export-no-databases",
+        ],
+    )
+    .await;
+
+    let admin = crate::handlers::auth::get_admin(&state, &headers);
+    let databases: Vec<ExportDatabase> = exportable_databases(&state, &headers)
+        .into_iter()
+        .map(|db| ExportDatabase {
+            can_export_hashes: admin
+                .as_ref()
+                .is_some_and(|admin| admin.role_for_database(&db.id) == Some(AdminRole::Edit)),
+            id: db.id,
+            label: db.label,
+        })
+        .collect();
+
+    let content_template = ExportTemplate {
+        title: &translations["export-title"],
+        description: &translations["export-description"],
+        format_label: &translations["export-format"],
+        password_hashes_label: &translations["export-password-hashes"],
+        password_hashes_hint: &translations["export-password-hashes-hint"],
+        download: &translations["export-download"],
+        no_databases: &translations["export-no-databases"],
+        databases: &databases,
+    };
+    render_template_with_title!(
+        content_template,
+        content_template.title,
+        &state,
+        &locale,
+        &headers
+    )
+}
+
+/// The bundle of one database as a JSON or YAML attachment
+pub async fn download(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<DownloadQuery>,
+) -> Response {
+    let Some(db) = exportable_databases(&state, &headers)
+        .into_iter()
+        .find(|db| db.id == query.database)
+    else {
+        return (StatusCode::NOT_FOUND, "Database not found").into_response();
+    };
+
+    // Password hashes are as sensitive as the database credentials
+    let password_hashes = query.password_hashes.is_some();
+    if password_hashes
+        && crate::handlers::auth::get_admin(&state, &headers)
+            .and_then(|admin| admin.role_for_database(&db.id))
+            != Some(AdminRole::Edit)
+    {
+        return (
+            StatusCode::FORBIDDEN,
+            "Exporting password hashes requires edit access",
+        )
+            .into_response();
+    }
+
+    let Some(pool) = state.db_manager.get_pool(&db.id).await else {
+        return (StatusCode::NOT_FOUND, "Database not found").into_response();
+    };
+    let document = bundle::export(&pool, &db, password_hashes)
+        .map_err(|e| e.to_string())
+        .and_then(|bundle| {
+            let document = bundle.to_string(query.format)?;
+            Ok((bundle.file_name(query.format), document))
+        });
+    let (filename, document) = match document {
+        Ok(document) => document,
+        Err(e) => {
+            tracing::error!("Failed to export database {}: {}", db.id, e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to export database",
+            )
+                .into_response();
+        }
+    };
+
+    (
+        [
+            (
+                header::CONTENT_TYPE,
+                format!("{}; charset=utf-8", query.format.content_type()),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+        ],
+        document,
+    )
+        .into_response()
+}
@@ -0,0 +1,467 @@
+use crate::i18n::get_translation;
+use crate::import::{
+    self, ImportError, ImportFile, ImportKind, ImportOptions, ImportPlan, RowStatus,
+};
+use crate::templates::import::*;
+use crate::{render_template_with_title, AppState};
+use askama::Template;
+use axum::{
+    extract::{Multipart, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{Html, IntoResponse, Response},
+};
+use clap::ValueEnum;
+use std::collections::HashMap;
+
+const STATUSES: [RowStatus; 5] = [
+    RowStatus::Create,
+    RowStatus::Update,
+    RowStatus::Unchanged,
+    RowStatus::Conflict,
+    RowStatus::Error,
+];
+
+/// The fields of the upload or preview form
+#[derive(Debug, Default)]
+struct Submission {
+    kind: Option<ImportKind>,
+    content: String,
+    update_existing: bool,
+    mapping: Vec<String>,
+}
+
+/// Read the multipart form; an uploaded file replaces the carried-over content
+async fn read_submission(mut multipart: Multipart) -> Result<Submission, String> {
+    let mut submission = Submission::default();
+    while let Some(field) = multipart.next_field().await.map_err(|e| e.to_string())? {
+        let name = field.name().unwrap_or_default().to_string();
+        match name.as_str() {
+            "file" => {
+                let bytes = field.bytes().await.map_err(|e| e.to_string())?;
+                if !bytes.is_empty() {
+                    submission.content = String::from_utf8_lossy(&bytes).into_owned();
+                }
+            }
+            _ => {
+                let text = field.text().await.map_err(|e| e.to_string())?;
+                match name.as_str() {
+                    "kind" => submission.kind = ImportKind::from_str(&text, true).ok(),
+                    "content" if submission.content.is_empty() => submission.content = text,
+                    "update_existing" => submission.update_existing = true,
+                    "map" => submission.mapping.push(text),
+                    _ => {}
+                }
+            }
+        }
+    }
+    Ok(submission)
+}
+
+/// Whether the current admin may create and update records of `kind` here
+fn permissions(state: &AppState, headers: &HeaderMap, kind: ImportKind) -> (bool, bool) {
+    let current_db_id = crate::handlers::auth::get_selected_database(state, headers)
+        .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
+    let admin = crate::handlers::auth::get_session(state, headers)
+        .map(|session| session.username)
+        .unwrap_or_default();
+    let allowed = |create| {
+        crate::handlers::utils::check_database_restrictions(
+            state,
+            Some(&admin),
+            &current_db_id,
+            kind.operation(create),
+        )
+        .is_ok()
+    };
+    (allowed(true), allowed(false))
+}
+
+/// Parse, map and plan a submission, returning the translated problem on failure
+async fn prepare(
+    state: &AppState,
+    headers: &HeaderMap,
+    locale: &str,
+    submission: &Submission,
+) -> Result<(ImportFile, ImportPlan), String> {
+    let kind = submission.kind.unwrap_or(ImportKind::Users);
+    if submission.content.trim().is_empty() {
+        return Err(get_translation(state, locale, "import-no-file").await);
+    }
+    let file = import::read(kind, &submission.content).and_then(|mut file| {
+        file.set_mapping(&submission.mapping)?;
+        file.check_columns()?;
+        Ok(file)
+    });
+    let file = match file {
+        Ok(file) => file,
+        Err(e) => return Err(file_error(state, locale, &e).await),
+    };
+
+    let pool = crate::handlers::utils::get_current_db_pool(state, headers)
+        .await
+        .map_err(|e| e.to_string())?;
+    let scope = crate::handlers::utils::get_address_scope(state, headers);
+    let policy = crate::handlers::utils::get_password_policy(state, headers);
+    let (can_create, can_update) = permissions(state, headers, kind);
+    let options = ImportOptions {
+        update_existing: submission.update_existing,
+        scope: &scope,
+        policy: &policy,
+        can_create,
+        can_update,
+    };
+    match import::plan(&pool, &file, &options) {
+        Ok(plan) => Ok((file, plan)),
+        Err(e) => {
+            tracing::error!("Failed to plan import: {:?}", e);
+            Err(get_translation(state, locale, "form-error").await)
+        }
+    }
+}
+
+async fn file_error(state: &AppState, locale: &str, error: &ImportError) -> String {
+    let args = HashMap::from([("detail".to_string(), error.detail().to_string())]);
+    crate::i18n::get_translation_with_args(state, locale, error.message_id(), args).await
+}
+
+/// The translated problems of each row, or the changes of an update
+async fn row_views(
+    state: &AppState,
+    locale: &str,
+    plan: &ImportPlan,
+    labels: &HashMap<String, String>,
+) -> Vec<ImportRowView> {
+    let mut views = Vec::with_capacity(plan.rows.len());
+    for row in &plan.rows {
+        let mut details = Vec::new();
+        for issue in &row.issues {
+            details.push(
+                crate::i18n::get_translation_with_args(
+                    state,
+                    locale,
+                    issue.message_id(),
+                    issue.message_args(),
+                )
+                .await,
+            );
+        }
+        if row.issues.is_empty() {
+            details.extend(row.changes.iter().map(ToString::to_string));
+        }
+        views.push(ImportRowView {
+            line: row.line,
+            key: row.key.clone(),
+            status: row.status.name(),
+            status_label: labels[row.status.message_id()].clone(),
+            details,
+        });
+    }
+    views
+}
+
+fn kind_label(labels: &HashMap<String, String>, kind: ImportKind) -> String {
+    labels
+        .get(&format!("nav-{}", kind.name()))
+        .cloned()
+        .unwrap_or_else(|| kind.name().to_string())
+}
+
+async fn render_index(
+    state: &AppState,
+    headers: &HeaderMap,
+    submission: &Submission,
+    error: Option<String>,
+) -> Html<String> {
+    let locale = crate::handlers::utils::get_user_locale(headers);
+    let translations = crate::handlers::utils::get_translations_batch(
+        state,
+        &locale,
+        &[
+            "import-title",
+            "import-description",
+            "import-kind",
+            "import-file",
+            "import-file-hint",
+            "import-update-existing",
+            "import-preview",
+            "nav-domains",
+            "nav-users",
+            "nav-aliases",
+            "nav-relays",
+            "nav-relocated",
+            "nav-clients",
+        ],
+    )
+    .await;
+    let kind_options: Vec<(String, String)> = ImportKind::ALL
+        .iter()
+        .map(|kind| (kind.name().to_string(), kind_label(&translations, *kind)))
+        .collect();
+    let selected_kind = submission.kind.unwrap_or(ImportKind::Users);
+
+    let title = &translations["import-title"];
+    let content_template = ImportTemplate {
+        title,
+        description: &translations["import-description"],
+        kind_label: &translations["import-kind"],
+        file_label: &translations["import-file"],
+        file_hint: &translations["import-file-hint"],
+        update_existing_label: &translations["import-update-existing"],
+        preview: &translations["import-preview"],
+        kind_options: &kind_options,
+        selected_kind: selected_kind.name(),
+        update_existing: submission.update_existing,
+        error,
+    };
+    render_template_with_title!(content_template, title, state, &locale, headers)
+}
+
+/// The preview of a planned import; `notice` reports an applied or failed import
+async fn render_preview(
+    state: &AppState,
+    headers: &HeaderMap,
+    submission: &Submission,
+    file: &ImportFile,
+    plan: &ImportPlan,
+    notice: Option<(String, bool)>,
+    applied: bool,
+) -> Html<String> {
+    let locale = crate::handlers::utils::get_user_locale(headers);
+    let translations = crate::handlers::utils::get_translations_batch(
+        state,
+        &locale,
+        &[
+            "import-title",
+            "import-mapping-title",
+            "import-mapping-ignore",
+            "import-update-existing",
+            "import-update-preview",
+            "import-apply",
+            "import-apply-hint",
+            "import-download-report",
+            "import-start-over",
+            "import-table-header-line",
+            "import-table-header-key",
+            "import-table-header-status",
+            "import-table-header-details",
+            "import-status-create",
+            "import-status-update",
+            "import-status-unchanged",
+            "import-status-conflict",
+            "import-status-error",
+            "nav-domains",
+            "nav-users",
+            "nav-aliases",
+            "nav-relays",
+            "nav-relocated",
+            "nav-clients",
+        ],
+    )
+    .await;
+
+    let columns: Vec<ImportColumn> = file
+        .columns
+        .iter()
+        .map(|column| ImportColumn {
+            header: column.header.clone(),
+            field: column.field.unwrap_or_default().to_string(),
+        })
+        .collect();
+    let counts: Vec<(&'static str, String, usize)> = STATUSES
+        .iter()
+        .map(|status| {
+            (
+                status.name(),
+                translations[status.message_id()].clone(),
+                plan.count(*status),
+            )
+        })
+        .collect();
+    let rows = row_views(state, &locale, plan, &translations).await;
+    let (notice, failed) = match notice {
+        Some((notice, failed)) => (Some(notice), failed),
+        None => (None, false),
+    };
+
+    let title = &translations["import-title"];
+    let content_template = ImportPreviewTemplate {
+        title,
+        mapping_title: &translations["import-mapping-title"],
+        mapping_ignore: &translations["import-mapping-ignore"],
+        update_existing_label: &translations["import-update-existing"],
+        update_preview: &translations["import-update-preview"],
+        apply: &translations["import-apply"],
+        apply_hint: &translations["import-apply-hint"],
+        download_report: &translations["import-download-report"],
+        start_over: &translations["import-start-over"],
+        table_header_line: &translations["import-table-header-line"],
+        table_header_key: &translations["import-table-header-key"],
+        table_header_status: &translations["import-table-header-status"],
+        table_header_details: &translations["import-table-header-details"],
+        kind: plan.kind.name(),
+        kind_label: &kind_label(&translations, plan.kind),
+        content: &submission.content,
+        update_existing: submission.update_existing,
+        fields: plan.kind.fields(),
+        columns: &columns,
+        counts: &counts,
+        rows: &rows,
+        has_problems: plan.problems().next().is_some(),
+        can_apply: plan.count(RowStatus::Create) + plan.count(RowStatus::Update) > 0,
+        notice,
+        failed,
+        applied,
+    };
+    render_template_with_title!(content_template, title, state, &locale, headers)
+}
+
+pub async fn index(State(state): State<AppState>, headers: HeaderMap) -> Html<String> {
+    render_index(&state, &headers, &Submission::default(), None).await
+}
+
+pub async fn preview(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    multipart: Multipart,
+) -> Html<String> {
+    let locale = crate::handlers::utils::get_user_locale(&headers);
+    let submission = match read_submission(multipart).await {
+        Ok(submission) => submission,
+        Err(e) => return render_index(&state, &headers, &Submission::default(), Some(e)).await,
+    };
+    match prepare(&state, &headers, &locale, &submission).await {
+        Ok((file, plan)) => {
+            render_preview(&state, &headers, &submission, &file, &plan, None, false).await
+        }
+        Err(error) => render_index(&state, &headers, &submission, Some(error)).await,
+    }
+}
+
+pub async fn apply(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    multipart: Multipart,
+) -> Html<String> {
+    let locale = crate::handlers::utils::get_user_locale(&headers);
+    let submission = match read_submission(multipart).await {
+        Ok(submission) => submission,
+        Err(e) => return render_index(&state, &headers, &Submission::default(), Some(e)).await,
+    };
+    let (file, plan) = match prepare(&state, &headers, &locale, &submission).await {
+        Ok(prepared) => prepared,
+        Err(error) => return render_index(&state, &headers, &submission, Some(error)).await,
+    };
+
+    let writes = plan.clone().into_writes();
+    if writes.is_empty() {
+        let notice = get_translation(&state, &locale, "import-nothing-to-apply").await;
+        return render_preview(
+            &state,
+            &headers,
+            &submission,
+            &file,
+            &plan,
+            Some((notice, true)),
+            false,
+        )
+        .await;
+    }
+
+    let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
+        .await
+        .map_err(|e| e.to_string());
+    let pool = match pool {
+        Ok(pool) => pool,
+        Err(error) => return render_index(&state, &headers, &submission, Some(error)).await,
+    };
+    let scheme = crate::handlers::utils::get_password_scheme(&state, &headers);
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
+    let (notice, applied) = match crate::db::apply_import(&pool, writes, scheme, &audit) {
+        Ok(count) => {
+            let args = HashMap::from([
+                ("count".to_string(), count.to_string()),
+                ("skipped".to_string(), plan.problems().count().to_string()),
+            ]);
+            let notice =
+                crate::i18n::get_translation_with_args(&state, &locale, "import-applied", args)
+                    .await;
+            (notice, true)
+        }
+        Err(failure) => {
+            tracing::error!("Failed to apply import: {}", failure);
+            let args = HashMap::from([("detail".to_string(), failure.to_string())]);
+            let notice =
+                crate::i18n::get_translation_with_args(&state, &locale, "import-failed", args)
+                    .await;
+            (notice, false)
+        }
+    };
+    render_preview(
+        &state,
+        &headers,
+        &submission,
+        &file,
+        &plan,
+        Some((notice, !applied)),
+        applied,
+    )
+    .await
+}
+
+/// The conflicts and errors of a planned import as a CSV download
+pub async fn report(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    multipart: Multipart,
+) -> Response {
+    let locale = crate::handlers::utils::get_user_locale(&headers);
+    let submission = match read_submission(multipart).await {
+        Ok(submission) => submission,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    let (_, plan) = match prepare(&state, &headers, &locale, &submission).await {
+        Ok(prepared) => prepared,
+        Err(error) => return (StatusCode::UNPROCESSABLE_ENTITY, error).into_response(),
+    };
+
+    let labels = crate::handlers::utils::get_translations_batch(
+        &state,
+        &locale,
+        &[
+            "import-status-create",
+            "import-status-update",
+            "import-status-unchanged",
+            "import-status-conflict",
+            "import-status-error",
+        ],
+    )
+    .await;
+    let views = row_views(&state, &locale, &plan, &labels).await;
+    let rows: Vec<import::ReportRow> = plan
+        .rows
+        .iter()
+        .zip(views)
+        .filter(|(planned, _)| planned.status.is_problem())
+        .map(|(_, row)| import::ReportRow {
+            line: row.line,
+            key: row.key,
+            status: row.status,
+            message: row.details.join("; "),
+        })
+        .collect();
+
+    (
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"import-{}-errors.csv\"",
+                    plan.kind.name()
+                ),
+            ),
+        ],
+        import::report_csv(&rows),
+    )
+        .into_response()
+}
@@ -0,0 +1,265 @@
+use crate::bundle::BundleFormat;
+use crate::handlers::restore::{change_views, plan_into, restorable_databases, status_counts};
+use crate::i18n::get_translation;
+use crate::legacy::{self, LegacyError, LegacyOptions, LegacySchema};
+use crate::restore::RestoreMode;
+use crate::templates::legacy::*;
+use crate::{render_template_with_title, AppState};
+use askama::Template;
+use axum::{
+    extract::{Multipart, State},
+    http::HeaderMap,
+    response::Html,
+};
+use clap::ValueEnum;
+use std::collections::HashMap;
+
+/// The fields of the source form
+#[derive(Debug, Default)]
+struct Submission {
+    database: Option<String>,
+    schema: LegacySchema,
+    mode: RestoreMode,
+    url: String,
+    dump: String,
+    options: LegacyOptions,
+}
+
+async fn read_submission(mut multipart: Multipart) -> Result<Submission, String> {
+    let mut submission = Submission::default();
+    while let Some(field) = multipart.next_field().await.map_err(|e| e.to_string())? {
+        let name = field.name().unwrap_or_default().to_string();
+        if name == "file" {
+            let bytes = field.bytes().await.map_err(|e| e.to_string())?;
+            submission.dump = String::from_utf8_lossy(&bytes).into_owned();
+            continue;
+        }
+        let text = field.text().await.map_err(|e| e.to_string())?;
+        let text = text.trim();
+        match name.as_str() {
+            "database" => submission.database = Some(text.to_string()),
+            "schema" => submission.schema = LegacySchema::from_str(text, true).unwrap_or_default(),
+            "mode" => submission.mode = RestoreMode::from_str(text, true).unwrap_or_default(),
+            "url" => submission.url = text.to_string(),
+            "home" if !text.is_empty() => submission.options.home = text.to_string(),
+            "uid" => submission.options.uid = text.parse().unwrap_or(submission.options.uid),
+            "gid" => submission.options.gid = text.parse().unwrap_or(submission.options.gid),
+            _ => {}
+        }
+    }
+    Ok(submission)
+}
+
+async fn legacy_error(state: &AppState, locale: &str, error: &LegacyError) -> String {
+    let args = HashMap::from([("detail".to_string(), error.detail().to_string())]);
+    crate::i18n::get_translation_with_args(state, locale, error.message_id(), args).await
+}
+
+async fn render_index(
+    state: &AppState,
+    headers: &HeaderMap,
+    submission: &Submission,
+    error: Option<String>,
+) -> Html<String> {
+    let locale = crate::handlers::utils::get_user_locale(headers);
+    let translations = crate::handlers::utils::get_translations_batch(
+        state,
+        &locale,
+        &[
+            "legacy-title",
+            "legacy-description",
+            "legacy-schema",
+            "legacy-url",
+            "legacy-url-hint",
+            "legacy-file",
+            "legacy-file-hint",
+            "legacy-home",
+            "legacy-uid",
+            "legacy-gid",
+            "legacy-preview",
+            "restore-database",
+            "restore-mode",
+            "restore-mode-merge",
+            "restore-mode-replace",
+            "restore-no-databases",
+        ],
+    )
+    .await;
+    let databases: Vec<(String, String)> = restorable_databases(state, headers)
+        .into_iter()
+        .map(|db| (db.id, db.label))
+        .collect();
+    let schema_options: Vec<(String, String)> = LegacySchema::value_variants()
+        .iter()
+        .map(|schema| (schema.name().to_string(), schema.label().to_string()))
+        .collect();
+    let mode_options: Vec<(String, String)> = RestoreMode::value_variants()
+        .iter()
+        .map(|mode| {
+            let label = translations[&format!("restore-mode-{}", mode.name())].clone();
+            (mode.name().to_string(), label)
+        })
+        .collect();
+
+    let title = &translations["legacy-title"];
+    let content_template = LegacyTemplate {
+        title,
+        description: &translations["legacy-description"],
+        schema_label: &translations["legacy-schema"],
+        database_label: &translations["restore-database"],
+        mode_label: &translations["restore-mode"],
+        url_label: &translations["legacy-url"],
+        url_hint: &translations["legacy-url-hint"],
+        file_label: &translations["legacy-file"],
+        file_hint: &translations["legacy-file-hint"],
+        home_label: &translations["legacy-home"],
+        uid_label: &translations["legacy-uid"],
+        gid_label: &translations["legacy-gid"],
+        preview: &translations["legacy-preview"],
+        no_databases: &translations["restore-no-databases"],
+        databases: &databases,
+        selected_database: submission.database.as_deref().unwrap_or_default(),
+        schema_options: &schema_options,
+        selected_schema: submission.schema.name(),
+        mode_options: &mode_options,
+        selected_mode: submission.mode.name(),
+        url: &submission.url,
+        options: &submission.options,
+        error,
+    };
+    render_template_with_title!(content_template, title, state, &locale, headers)
+}
+
+pub async fn index(State(state): State<AppState>, headers: HeaderMap) -> Html<String> {
+    render_index(&state, &headers, &Submission::default(), None).await
+}
+
+/// Read and convert the source, then show the changes restoring it makes
+pub async fn preview(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    multipart: Multipart,
+) -> Html<String> {
+    let locale = crate::handlers::utils::get_user_locale(&headers);
+    let submission = match read_submission(multipart).await {
+        Ok(submission) => submission,
+        Err(e) => return render_index(&state, &headers, &Submission::default(), Some(e)).await,
+    };
+    let target = restorable_databases(&state, &headers)
+        .into_iter()
+        .find(|db| submission.database.as_ref() == Some(&db.id));
+    let Some(target) = target else {
+        let error = get_translation(&state, &locale, "restore-no-databases").await;
+        return render_index(&state, &headers, &submission, Some(error)).await;
+    };
+
+    let tables = if !submission.url.is_empty() {
+        crate::db::read_legacy_tables(&submission.url, submission.schema.tables())
+    } else if !submission.dump.trim().is_empty() {
+        legacy::read_dump(&submission.dump)
+    } else {
+        let error = get_translation(&state, &locale, "legacy-no-source").await;
+        return render_index(&state, &headers, &submission, Some(error)).await;
+    };
+    let tables = match tables {
+        Ok(tables) => tables,
+        Err(e) => {
+            let error = legacy_error(&state, &locale, &e).await;
+            return render_index(&state, &headers, &submission, Some(error)).await;
+        }
+    };
+    let conversion = legacy::convert(submission.schema, &tables, &submission.options);
+    let plan = match plan_into(
+        &state,
+        &headers,
+        &locale,
+        &target,
+        &conversion.bundle,
+        submission.mode,
+    )
+    .await
+    {
+        Ok(plan) => plan,
+        Err(error) => return render_index(&state, &headers, &submission, Some(error)).await,
+    };
+
+    let translations = crate::handlers::utils::get_translations_batch(
+        &state,
+        &locale,
+        &[
+            "legacy-title",
+            "legacy-apply",
+            "legacy-apply-hint",
+            "legacy-notes",
+            "legacy-table-header-note",
+            "legacy-table-header-value",
+            "restore-start-over",
+            "restore-no-changes",
+            "restore-table-header-table",
+            "restore-table-header-key",
+            "restore-table-header-status",
+            "restore-table-header-details",
+            "restore-status-create",
+            "restore-status-update",
+            "restore-status-delete",
+            "restore-status-unchanged",
+            "restore-status-error",
+        ],
+    )
+    .await;
+    let args = HashMap::from([
+        ("source".to_string(), submission.schema.label().to_string()),
+        (
+            "count".to_string(),
+            conversion.bundle.record_count().to_string(),
+        ),
+        ("target".to_string(), target.label.clone()),
+        ("mode".to_string(), submission.mode.name().to_string()),
+    ]);
+    let summary =
+        crate::i18n::get_translation_with_args(&state, &locale, "legacy-summary", args).await;
+    let counts = status_counts(&plan, &translations);
+    let changes = change_views(&state, &locale, &plan, &translations).await;
+    let mut notes = Vec::new();
+    for note in &conversion.notes {
+        notes.push(LegacyNoteView {
+            table: note.table.clone(),
+            key: note.key.clone(),
+            issue_label: get_translation(&state, &locale, note.issue.message_id()).await,
+            detail: note.detail.clone(),
+        });
+    }
+    let content = match conversion.bundle.to_string(BundleFormat::Json) {
+        Ok(content) => content,
+        Err(e) => {
+            tracing::error!("Failed to write the converted bundle: {}", e);
+            let error = get_translation(&state, &locale, "form-error").await;
+            return render_index(&state, &headers, &submission, Some(error)).await;
+        }
+    };
+
+    let title = &translations["legacy-title"];
+    let content_template = LegacyPreviewTemplate {
+        title,
+        summary: &summary,
+        apply: &translations["legacy-apply"],
+        apply_hint: &translations["legacy-apply-hint"],
+        start_over: &translations["restore-start-over"],
+        no_changes: &translations["restore-no-changes"],
+        notes_title: &translations["legacy-notes"],
+        table_header_table: &translations["restore-table-header-table"],
+        table_header_key: &translations["restore-table-header-key"],
+        table_header_status: &translations["restore-table-header-status"],
+        table_header_details: &translations["restore-table-header-details"],
+        table_header_note: &translations["legacy-table-header-note"],
+        table_header_value: &translations["legacy-table-header-value"],
+        database: &target.id,
+        mode: submission.mode.name(),
+        content: &content,
+        counts: &counts,
+        changes: &changes,
+        notes: &notes,
+        can_apply: plan.writes().next().is_some(),
+    };
+    render_template_with_title!(content_template, title, &state, &locale, &headers)
+}
@@ -0,0 +1,139 @@
+use crate::config::{AdminRole, DatabaseConfig};
+use crate::mail_config::{self, GeneratedFile};
+use crate::templates::mail_config::*;
+use crate::{render_template_with_title, AppState};
+use askama::Template;
+use axum::{
+    extract::{Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{Html, IntoResponse, Response},
+};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct DownloadQuery {
+    pub database: Option<String>,
+}
+
+/// Databases the current admin may edit; the generated files contain their credentials
+fn editable_databases(state: &AppState, headers: &HeaderMap) -> Vec<DatabaseConfig> {
+    let Some(admin) = crate::handlers::auth::get_admin(state, headers) else {
+        return Vec::new();
+    };
+    crate::handlers::auth::get_accessible_databases(state, headers)
+        .into_iter()
+        .filter(|db| admin.role_for_database(&db.id) == Some(AdminRole::Edit))
+        .collect()
+}
+
+/// The generated files for `db`, with Sieve scripts for its vacations
+async fn generate_files(
+    state: &AppState,
+    db: &DatabaseConfig,
+) -> Result<Vec<GeneratedFile>, String> {
+    let mut files = mail_config::generate(db)?;
+    if db.features.disabled {
+        return Ok(files);
+    }
+    if let Some(pool) = state.db_manager.get_pool(&db.id).await {
+        match crate::db::get_vacations(&pool) {
+            Ok(vacations) => files.extend(mail_config::sieve_scripts(&vacations)),
+            Err(e) => tracing::warn!("Failed to load vacations for {}: {}", db.id, e),
+        }
+    }
+    Ok(files)
+}
+
+pub async fn index(State(state): State<AppState>, headers: HeaderMap) -> Html<String> {
+    let locale = crate::handlers::utils::get_user_locale(&headers);
+    let translations = crate::handlers::utils::get_translations_batch(
+        &state,
+        &locale,
+        &[
+            "mail-config-title",
+            "mail-config-description",
+            "mail-config-download-all",
+            "mail-config-download-database",
+            "mail-config-no-databases",
+            "mail-config-credentials-warning",
+        ],
+    )
+    .await;
+
+    let mut databases: Vec<MailConfigDatabase> = Vec::new();
+    for db in editable_databases(&state, &headers) {
+        let (files, error) = match generate_files(&state, &db).await {
+            Ok(files) => (files, None),
+            Err(e) => (Vec::new(), Some(e)),
+        };
+        databases.push(MailConfigDatabase {
+            id: db.id,
+            label: db.label,
+            files,
+            error,
+        });
+    }
+
+    let content_template = MailConfigTemplate {
+        title: &translations["mail-config-title"],
+        description: &translations["mail-config-description"],
+        download_all: &translations["mail-config-download-all"],
+        download_database: &translations["mail-config-download-database"],
+        no_databases: &translations["mail-config-no-databases"],
+        credentials_warning: &translations["mail-config-credentials-warning"],
+        databases: &databases,
+    };
+    render_template_with_title!(
+        content_template,
+        content_template.title,
+        &state,
+        &locale,
+        &headers
+    )
+}
+
+/// ZIP of the generated files, for one database or every editable database
+pub async fn download(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<DownloadQuery>,
+) -> Response {
+    let databases: Vec<DatabaseConfig> = editable_databases(&state, &headers)
+        .into_iter()
+        .filter(|db| query.database.as_ref().is_none_or(|id| &db.id == id))
+        .collect();
+    if databases.is_empty() {
+        return (StatusCode::NOT_FOUND, "Database not found").into_response();
+    }
+
+    let mut archive: Vec<(String, Vec<GeneratedFile>)> = Vec::new();
+    for db in &databases {
+        match generate_files(&state, db).await {
+            Ok(files) => archive.push((db.id.clone(), files)),
+            Err(e) => return (StatusCode::UNPROCESSABLE_ENTITY, e).into_response(),
+        }
+    }
+    let bytes = match mail_config::zip_archive(&archive) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::error!("Failed to build mail config archive: {}", e);
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to build archive").into_response();
+        }
+    };
+
+    let filename = match &query.database {
+        Some(id) => format!("mail-config-{id}.zip"),
+        None => "mail-config.zip".to_string(),
+    };
+    (
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+        ],
+        bytes,
+    )
+        .into_response()
+}
@@ -1,5 +1,8 @@
 pub mod about;
+pub mod alias_domains;
 pub mod aliases;
+pub mod api;
+pub mod audit;
 pub mod auth;
 pub mod backups;
 pub mod clients;
@@ -7,23 +10,33 @@ pub mod config;
 pub mod dashboard;
 pub mod database;
 pub mod domains;
+pub mod export;
+pub mod import;
 pub mod language;
+pub mod legacy;
+pub mod mail_config;
+pub mod portal;
 pub mod relays;
 pub mod relocated;
 pub mod reports;
+pub mod restore;
 pub mod stats;
+pub mod sync;
 pub mod theme;
 pub mod users;
 pub mod utils;
+pub mod vacation;
 
+use crate::AppState;
 use axum::{
+    extract::State,
     http::{HeaderMap, StatusCode},
     response::Response,
 };
 
-pub async fn not_found(headers: HeaderMap) -> Response {
+pub async fn not_found(State(state): State<AppState>, headers: HeaderMap) -> Response {
     // Check if user is authenticated
-    if crate::handlers::auth::is_authenticated(&headers) {
+    if crate::handlers::auth::is_authenticated(&state, &headers) {
         // Authenticated users get 403 Forbidden for unknown routes
         Response::builder()
             .status(StatusCode::FORBIDDEN)
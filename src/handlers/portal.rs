@@ -0,0 +1,450 @@
+//! Self-service portal for mailbox users
+//!
+//! Portal routes sit outside the admin authentication and use their own
+//! session cookie; see [`crate::portal`] for how forwarding is stored.
+
+use crate::db::{self, AuditContext};
+use crate::handlers::auth::{login_error_fragment, render_login_page, LoginKind, LoginRequest};
+use crate::i18n::get_translation;
+use crate::models::{AdminSession, AliasForm, User};
+use crate::portal::{parse_forward_addresses, Forwarding, ForwardingError};
+use crate::templates::portal::PortalTemplate;
+use crate::{AppState, DbPool};
+use askama::Template;
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::{Html, IntoResponse, Response},
+    Form,
+};
+use diesel::OptionalExtension;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Deserialize)]
+pub struct PortalPasswordForm {
+    pub current_password: String,
+    pub new_password: String,
+    pub confirm_password: String,
+}
+
+#[derive(Deserialize)]
+pub struct PortalForwardingForm {
+    #[serde(default)]
+    pub addresses: String,
+    #[serde(default)]
+    #[serde(deserialize_with = "crate::models::deserialize_checkbox")]
+    pub keep_copy: bool,
+}
+
+/// The signed-in mailbox user behind a portal request
+struct Mailbox {
+    session: AdminSession,
+    pool: DbPool,
+    user: User,
+}
+
+impl Mailbox {
+    fn audit(&self) -> AuditContext {
+        AuditContext::new(
+            format!("portal:{}", self.user.id),
+            &self.session.database_id,
+        )
+    }
+}
+
+/// Load the mailbox behind the portal session
+///
+/// Sessions whose mailbox has since been removed or disabled, or whose
+/// database has been disabled, are revoked.
+async fn current_mailbox(state: &AppState, headers: &HeaderMap) -> Option<Mailbox> {
+    let session = state.sessions.mailbox_from_headers(headers)?;
+    let database_disabled = state
+        .config
+        .get()
+        .is_database_disabled(&session.database_id);
+    let pool = match state.db_manager.get_pool(&session.database_id).await {
+        Some(pool) if !database_disabled => pool,
+        _ => {
+            state.sessions.revoke(&session.id);
+            return None;
+        }
+    };
+    match db::get_user_by_id(&pool, &session.username) {
+        Ok(user) if user.enabled => Some(Mailbox {
+            session,
+            pool,
+            user,
+        }),
+        _ => {
+            println!(
+                "🔐 [PORTAL] ❌ Session for unavailable mailbox '{}'",
+                session.username
+            );
+            state.sessions.revoke(&session.id);
+            None
+        }
+    }
+}
+
+fn redirect(location: &str) -> Response {
+    Response::builder()
+        .status(StatusCode::FOUND)
+        .header("Location", location)
+        .body("".into())
+        .unwrap()
+}
+
+pub async fn login_form(State(state): State<AppState>, headers: HeaderMap) -> Html<String> {
+    let locale = crate::handlers::language::get_user_locale(&headers);
+    Html(render_login_page(&state, &locale, "", LoginKind::Mailbox).await)
+}
+
+pub async fn login(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Form(request): Form<LoginRequest>,
+) -> Response {
+    let locale = crate::handlers::language::get_user_locale(&headers);
+    let is_htmx = headers.get("hx-request").is_some();
+    let address = request.id.trim();
+
+    let error_key = if address.is_empty() || request.password.trim().is_empty() {
+        "login-error-empty-fields"
+    } else if let Some((database_id, user)) = crate::portal::authenticate(
+        &state.db_manager,
+        &state.config.get(),
+        address,
+        &request.password,
+    )
+    .await
+    {
+        println!("🔐 [PORTAL] ✅ Login successful for mailbox '{}'", user.id);
+        let session = match state.sessions.create_mailbox(&user.id, &database_id) {
+            Ok(session) => session,
+            Err(e) => {
+                tracing::error!("Failed to create portal session: {:?}", e);
+                return Response::builder()
+                    .status(StatusCode::INTERNAL_SERVER_ERROR)
+                    .body("Failed to create session".into())
+                    .unwrap();
+            }
+        };
+        let cookie_value = state.sessions.cookie_header(&session);
+        let builder = if is_htmx {
+            Response::builder()
+                .status(StatusCode::OK)
+                .header("HX-Redirect", "/portal")
+        } else {
+            Response::builder()
+                .status(StatusCode::FOUND)
+                .header("Location", "/portal")
+        };
+        return builder
+            .header("Set-Cookie", cookie_value)
+            .body("".into())
+            .unwrap();
+    } else {
+        println!("🔐 [PORTAL] ❌ Login failed for mailbox '{address}'");
+        "login-error-invalid-credentials"
+    };
+
+    let error = get_translation(&state, &locale, error_key).await;
+    if is_htmx {
+        Html(login_error_fragment(&error)).into_response()
+    } else {
+        Html(render_login_page(&state, &locale, &error, LoginKind::Mailbox).await).into_response()
+    }
+}
+
+pub async fn logout(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    state.sessions.revoke_mailbox_from_headers(&headers);
+
+    Response::builder()
+        .status(StatusCode::FOUND)
+        .header("Location", "/portal/login")
+        .header("Set-Cookie", state.sessions.clear_portal_cookie_header())
+        .body("".into())
+        .unwrap()
+}
+
+pub async fn index(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    let Some(mailbox) = current_mailbox(&state, &headers).await else {
+        return redirect("/portal/login");
+    };
+    let locale = crate::handlers::language::get_user_locale(&headers);
+    Html(render_portal(&state, &locale, &mailbox, None, None, None).await).into_response()
+}
+
+pub async fn change_password(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Form(form): Form<PortalPasswordForm>,
+) -> Response {
+    let Some(mut mailbox) = current_mailbox(&state, &headers).await else {
+        return redirect("/portal/login");
+    };
+    let locale = crate::handlers::language::get_user_locale(&headers);
+    let config = state.config.get();
+    let database_id = mailbox.session.database_id.clone();
+
+    let error = if crate::handlers::utils::check_database_restrictions(
+        &state,
+        None,
+        &database_id,
+        "change_password",
+    )
+    .is_err()
+    {
+        Some(get_translation(&state, &locale, "portal-password-blocked").await)
+    } else if !matches!(
+        crate::password::verify_password(&form.current_password, &mailbox.user.crypt),
+        Ok(true)
+    ) {
+        Some(get_translation(&state, &locale, "portal-error-current-password").await)
+    } else if form.new_password != form.confirm_password {
+        Some(get_translation(&state, &locale, "error-passwords-do-not-match").await)
+    } else if form.new_password == form.current_password {
+        Some(get_translation(&state, &locale, "portal-error-same-password").await)
+    } else {
+        crate::handlers::utils::check_password_against(
+            &state,
+            &locale,
+            config.password_policy(&database_id),
+            &form.new_password,
+            &mailbox.user.id,
+            &mailbox.user.name,
+        )
+        .await
+    };
+    if error.is_some() {
+        return Html(render_portal(&state, &locale, &mailbox, None, error, None).await)
+            .into_response();
+    }
+
+    let result = db::update_own_password(
+        &mailbox.pool,
+        mailbox.user.id.clone(),
+        &form.new_password,
+        config.password_scheme(&database_id),
+        &mailbox.audit(),
+    )
+    .and_then(|_| db::get_user_by_id(&mailbox.pool, &mailbox.user.id));
+    let (notice, error) = match result {
+        Ok(user) => {
+            mailbox.user = user;
+            let notice = get_translation(&state, &locale, "portal-password-changed").await;
+            (Some(notice), None)
+        }
+        Err(e) => {
+            tracing::error!("Failed to change password of {}: {:?}", mailbox.user.id, e);
+            let error = get_translation(&state, &locale, "error-failed-to-update-password").await;
+            (None, Some(error))
+        }
+    };
+    Html(render_portal(&state, &locale, &mailbox, notice, error, None).await).into_response()
+}
+
+pub async fn update_forwarding(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Form(form): Form<PortalForwardingForm>,
+) -> Response {
+    let Some(mailbox) = current_mailbox(&state, &headers).await else {
+        return redirect("/portal/login");
+    };
+    let locale = crate::handlers::language::get_user_locale(&headers);
+    let submitted = Some((form.addresses.clone(), form.keep_copy));
+
+    let blocked = mailbox.user.change_password
+        || crate::handlers::utils::check_database_restrictions(
+            &state,
+            None,
+            &mailbox.session.database_id,
+            "update_alias",
+        )
+        .is_err();
+    if blocked {
+        let error = get_translation(&state, &locale, "portal-forwarding-blocked").await;
+        return Html(render_portal(&state, &locale, &mailbox, None, Some(error), submitted).await)
+            .into_response();
+    }
+
+    let addresses = match parse_forward_addresses(&form.addresses, &mailbox.user.id) {
+        Ok(addresses) => addresses,
+        Err(e) => {
+            let args = HashMap::from([
+                (
+                    "address".to_string(),
+                    match &e {
+                        ForwardingError::InvalidAddress(address) => address.clone(),
+                        ForwardingError::TooMany => String::new(),
+                    },
+                ),
+                ("max".to_string(), crate::portal::MAX_FORWARDS.to_string()),
+            ]);
+            let error =
+                crate::i18n::get_translation_with_args(&state, &locale, e.message_id(), args).await;
+            return Html(
+                render_portal(&state, &locale, &mailbox, None, Some(error), submitted).await,
+            )
+            .into_response();
+        }
+    };
+
+    let audit = mailbox.audit();
+    let result = db::get_alias_by_mail(&mailbox.pool, &mailbox.user.id)
+        .optional()
+        .and_then(|existing| {
+            let forwarding = Forwarding {
+                addresses,
+                keep_copy: form.keep_copy,
+                enabled: existing.as_ref().is_none_or(|alias| alias.enabled),
+            };
+            match (existing, forwarding.destination(&mailbox.user.id)) {
+                (Some(alias), Some(destination)) => db::update_alias(
+                    &mailbox.pool,
+                    alias.pkid,
+                    AliasForm {
+                        mail: alias.mail,
+                        destination,
+                        enabled: alias.enabled,
+                        valid_from: alias.valid_from,
+                        valid_until: alias.valid_until,
+                        return_url: None,
+                    },
+                    &audit,
+                )
+                .map(|_| ()),
+                (None, Some(destination)) => db::create_alias(
+                    &mailbox.pool,
+                    AliasForm {
+                        mail: mailbox.user.id.clone(),
+                        destination,
+                        enabled: forwarding.enabled,
+                        valid_from: None,
+                        valid_until: None,
+                        return_url: None,
+                    },
+                    &audit,
+                )
+                .map(|_| ()),
+                (Some(alias), None) => {
+                    db::delete_alias(&mailbox.pool, alias.pkid, &audit).map(|_| ())
+                }
+                (None, None) => Ok(()),
+            }
+        });
+    match result {
+        Ok(()) => {
+            let notice = get_translation(&state, &locale, "portal-forwarding-saved").await;
+            Html(render_portal(&state, &locale, &mailbox, Some(notice), None, None).await)
+                .into_response()
+        }
+        Err(e) => {
+            tracing::error!(
+                "Failed to update forwarding of {}: {:?}",
+                mailbox.user.id,
+                e
+            );
+            let error = get_translation(&state, &locale, "portal-forwarding-failed").await;
+            Html(render_portal(&state, &locale, &mailbox, None, Some(error), submitted).await)
+                .into_response()
+        }
+    }
+}
+
+/// Render the portal page
+///
+/// `submitted` carries forwarding input to show again after a rejected change.
+async fn render_portal(
+    state: &AppState,
+    locale: &str,
+    mailbox: &Mailbox,
+    notice: Option<String>,
+    error: Option<String>,
+    submitted: Option<(String, bool)>,
+) -> String {
+    let user = &mailbox.user;
+    let database_id = &mailbox.session.database_id;
+    let aliases = db::get_aliases_for_destination(&mailbox.pool, &user.id).unwrap_or_else(|e| {
+        tracing::error!("Failed to load aliases for {}: {:?}", user.id, e);
+        Vec::new()
+    });
+    let own_alias = db::get_alias_by_mail(&mailbox.pool, &user.id)
+        .optional()
+        .unwrap_or_else(|e| {
+            tracing::error!("Failed to load forwarding for {}: {:?}", user.id, e);
+            None
+        });
+    let forwarding = Forwarding::from_alias(&user.id, own_alias.as_ref());
+    let (forwarding_addresses, keep_copy) =
+        submitted.unwrap_or_else(|| (forwarding.addresses.join("\n"), forwarding.keep_copy));
+    let can_change_password = crate::handlers::utils::check_database_restrictions(
+        state,
+        None,
+        database_id,
+        "change_password",
+    )
+    .is_ok();
+    let can_edit_forwarding = !user.change_password
+        && crate::handlers::utils::check_database_restrictions(
+            state,
+            None,
+            database_id,
+            "update_alias",
+        )
+        .is_ok();
+
+    PortalTemplate {
+        title: get_translation(state, locale, "portal-title").await,
+        app_title: get_translation(state, locale, "app-title").await,
+        app_subtitle: get_translation(state, locale, "app-subtitle").await,
+        theme_toggle: get_translation(state, locale, "theme-toggle").await,
+        language_selector: get_translation(state, locale, "language-selector").await,
+        language_english: get_translation(state, locale, "language-english").await,
+        language_spanish: get_translation(state, locale, "language-spanish").await,
+        language_french: get_translation(state, locale, "language-french").await,
+        language_norwegian: get_translation(state, locale, "language-norwegian").await,
+        language_german: get_translation(state, locale, "language-german").await,
+        sign_out: get_translation(state, locale, "portal-sign-out").await,
+        current_locale: locale.to_string(),
+        address: user.id.clone(),
+        name: user.name.clone(),
+        notice,
+        error,
+        must_change_password: user.change_password,
+        must_change_password_notice: get_translation(state, locale, "portal-must-change-password")
+            .await,
+        can_change_password,
+        password_blocked_notice: get_translation(state, locale, "portal-password-blocked").await,
+        password_title: get_translation(state, locale, "portal-password-title").await,
+        current_password_label: get_translation(state, locale, "portal-current-password").await,
+        new_password_label: get_translation(state, locale, "users-new-password-label").await,
+        confirm_password_label: get_translation(state, locale, "users-confirm-password-label")
+            .await,
+        change_password_button: get_translation(state, locale, "users-change-password-button")
+            .await,
+        aliases,
+        aliases_title: get_translation(state, locale, "portal-aliases-title").await,
+        aliases_help: get_translation(state, locale, "portal-aliases-help").await,
+        aliases_empty: get_translation(state, locale, "portal-aliases-empty").await,
+        alias_disabled: get_translation(state, locale, "status-disabled").await,
+        can_edit_forwarding,
+        forwarding_enabled: forwarding.enabled,
+        forwarding_title: get_translation(state, locale, "portal-forwarding-title").await,
+        forwarding_help: get_translation(state, locale, "portal-forwarding-help").await,
+        forwarding_addresses,
+        forwarding_addresses_label: get_translation(state, locale, "portal-forwarding-addresses")
+            .await,
+        keep_copy,
+        keep_copy_label: get_translation(state, locale, "portal-forwarding-keep-copy").await,
+        forwarding_disabled_notice: get_translation(state, locale, "portal-forwarding-disabled")
+            .await,
+        forwarding_blocked_notice: get_translation(state, locale, "portal-forwarding-blocked")
+            .await,
+        save_forwarding_button: get_translation(state, locale, "portal-forwarding-save").await,
+    }
+    .render()
+    .unwrap()
+}
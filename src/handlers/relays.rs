@@ -91,7 +91,7 @@ pub async fn list_relays(State(state): State<AppState>, headers: HeaderMap) -> H
     if is_htmx_request(&headers) {
         Html(content)
     } else {
-        let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+        let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
             .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
         let current_db_label = state
             .db_manager
@@ -190,7 +190,7 @@ pub async fn show_relay(
     if is_htmx_request(&headers) {
         Html(content)
     } else {
-        let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+        let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
             .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
         let current_db_label = state
             .db_manager
@@ -268,7 +268,7 @@ pub async fn create_form(State(state): State<AppState>, headers: HeaderMap) -> H
     if is_htmx_request(&headers) {
         Html(content)
     } else {
-        let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+        let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
             .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
         let current_db_label = state
             .db_manager
@@ -301,11 +301,12 @@ pub async fn create_relay(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
     let locale = crate::handlers::language::get_user_locale(&headers);
 
     debug!("Handling relay create request");
 
-    match db::create_relay(&pool, form) {
+    match db::create_relay(&pool, form, &audit) {
         Ok(relay) => {
             info!("Successfully created relay: {}", relay.recipient);
             Html(format!(
@@ -390,7 +391,7 @@ pub async fn edit_form(
     if is_htmx_request(&headers) {
         Html(content)
     } else {
-        let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+        let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
             .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
         let current_db_label = state
             .db_manager
@@ -424,11 +425,12 @@ pub async fn update_relay(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
     let locale = crate::handlers::language::get_user_locale(&headers);
 
     debug!("Handling relay update request for ID: {}", relay_id);
 
-    match db::update_relay(&pool, relay_id, form) {
+    match db::update_relay(&pool, relay_id, form, &audit) {
         Ok(relay) => {
             info!("Successfully updated relay: {}", relay.recipient);
             Html(format!(
@@ -457,11 +459,12 @@ pub async fn delete_relay(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
     let locale = crate::handlers::language::get_user_locale(&headers);
 
     debug!("Handling relay delete request for ID: {}", relay_id);
 
-    match db::delete_relay(&pool, relay_id) {
+    match db::delete_relay(&pool, relay_id, &audit) {
         Ok(_) => {
             info!("Successfully deleted relay ID: {}", relay_id);
             Html("<script>window.location.href='/relays';</script>".to_string())
@@ -487,11 +490,12 @@ pub async fn toggle_enabled(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
     let locale = crate::handlers::language::get_user_locale(&headers);
 
     debug!("Handling relay toggle enabled request for ID: {}", relay_id);
 
-    match db::toggle_relay_enabled(&pool, relay_id) {
+    match db::toggle_relay_enabled(&pool, relay_id, &audit) {
         Ok(relay) => {
             let enabled_text = if relay.enabled {
                 get_translation(&state, &locale, "status-enabled").await
@@ -79,6 +79,8 @@ pub async fn list_relocated(State(state): State<AppState>, headers: HeaderMap) -
         empty_description: &empty_description,
         relocated,
         relocated_list_description: &relocated_list_description,
+        validity: crate::handlers::utils::get_validity_labels(&state, &locale).await,
+        now: chrono::Utc::now().naive_utc(),
     };
 
     let content = match content_template.render() {
@@ -99,7 +101,7 @@ pub async fn list_relocated(State(state): State<AppState>, headers: HeaderMap) -
         Html(content)
     } else {
         // Get current database id from session/cookie or default
-        let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+        let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
             .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
         // Get current database label from db_manager
         let current_db_label = state
@@ -195,6 +197,8 @@ pub async fn show_relocated(
         relocated_info_title: &translations["relocated-info-title"],
         relocated_info_description: &translations["relocated-info-description"],
         relocated,
+        validity: crate::handlers::utils::get_validity_labels(&state, &locale).await,
+        now: chrono::Utc::now().naive_utc(),
     };
 
     // Use the new render template macro
@@ -211,6 +215,8 @@ pub async fn create_form(State(state): State<AppState>, headers: HeaderMap) -> H
         old_address: "".to_string(),
         new_address: "".to_string(),
         enabled: true,
+        valid_from: None,
+        valid_until: None,
     };
 
     let title = get_translation(&state, &locale, "relocated-new-relocated").await;
@@ -244,6 +250,7 @@ pub async fn create_form(State(state): State<AppState>, headers: HeaderMap) -> H
         back_to_list: &back_to_list,
         placeholder_old_address: &placeholder_old_address,
         placeholder_new_address: &placeholder_new_address,
+        validity: crate::handlers::utils::get_validity_labels(&state, &locale).await,
     };
 
     let content = match content_template.render() {
@@ -258,7 +265,7 @@ pub async fn create_form(State(state): State<AppState>, headers: HeaderMap) -> H
         Html(content)
     } else {
         // Get current database id from session/cookie or default
-        let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+        let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
             .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
         // Get current database label from db_manager
         let current_db_label = state
@@ -293,11 +300,16 @@ pub async fn create_relocated(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
     let locale = crate::handlers::language::get_user_locale(&headers);
 
     debug!("Handling relocated create request");
 
-    match db::create_relocated(&pool, form) {
+    if let Err(e) = crate::validity::check_window(form.valid_from, form.valid_until) {
+        return Html(get_translation(&state, &locale, e.message_id()).await);
+    }
+
+    match db::create_relocated(&pool, form, &audit) {
         Ok(relocated) => {
             info!(
                 "Successfully created relocated entry: {}",
@@ -344,6 +356,8 @@ pub async fn edit_form(
         old_address: relocated.old_address.clone(),
         new_address: relocated.new_address.clone(),
         enabled: relocated.enabled,
+        valid_from: relocated.valid_from,
+        valid_until: relocated.valid_until,
     };
 
     let title = get_translation(&state, &locale, "relocated-edit-relocated").await;
@@ -377,6 +391,7 @@ pub async fn edit_form(
         back_to_list: &back_to_list,
         placeholder_old_address: &placeholder_old_address,
         placeholder_new_address: &placeholder_new_address,
+        validity: crate::handlers::utils::get_validity_labels(&state, &locale).await,
     };
 
     let content = match content_template.render() {
@@ -391,7 +406,7 @@ pub async fn edit_form(
         Html(content)
     } else {
         // Get current database id from session/cookie or default
-        let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+        let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
             .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
         // Get current database label from db_manager
         let current_db_label = state
@@ -427,11 +442,16 @@ pub async fn update_relocated(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
     let locale = crate::handlers::language::get_user_locale(&headers);
 
     debug!("Handling relocated update request for ID: {}", relocated_id);
 
-    match db::update_relocated(&pool, relocated_id, form) {
+    if let Err(e) = crate::validity::check_window(form.valid_from, form.valid_until) {
+        return Html(get_translation(&state, &locale, e.message_id()).await);
+    }
+
+    match db::update_relocated(&pool, relocated_id, form, &audit) {
         Ok(relocated) => {
             info!(
                 "Successfully updated relocated entry: {}",
@@ -463,11 +483,12 @@ pub async fn delete_relocated(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
     let locale = crate::handlers::language::get_user_locale(&headers);
 
     debug!("Handling relocated delete request for ID: {}", relocated_id);
 
-    match db::delete_relocated(&pool, relocated_id) {
+    match db::delete_relocated(&pool, relocated_id, &audit) {
         Ok(_) => {
             info!("Successfully deleted relocated entry ID: {}", relocated_id);
             Html("<script>window.location.href='/relocated';</script>".to_string())
@@ -493,6 +514,7 @@ pub async fn toggle_enabled(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
     let locale = crate::handlers::language::get_user_locale(&headers);
 
     debug!(
@@ -500,7 +522,7 @@ pub async fn toggle_enabled(
         relocated_id
     );
 
-    match db::toggle_relocated_enabled(&pool, relocated_id) {
+    match db::toggle_relocated_enabled(&pool, relocated_id, &audit) {
         Ok(relocated) => {
             let enabled_text = if relocated.enabled {
                 get_translation(&state, &locale, "status-enabled").await
@@ -1,11 +1,13 @@
+use crate::models::DeliveryOutcome;
 use crate::templates::layout::BaseTemplate;
 use crate::templates::reports::{
     AliasCrossDomainReportTemplate, CrossDatabaseFeatureToggleReportTemplate,
     CrossDatabaseMatrixReportTemplate, CrossDatabaseMigrationReportTemplate,
-    CrossDatabaseUserDistributionReportTemplate, ExternalForwarderReportTemplate,
-    MatrixReportTemplate, OrphanedReportTemplate, ReportsListTemplate,
+    CrossDatabaseUserDistributionReportTemplate, DeliveryReportTemplate, DeliveryRow,
+    ExternalForwarderReportTemplate, MatrixReportTemplate, OrphanedReportTemplate,
+    ReportsListTemplate,
 };
-use crate::{db, i18n::get_translation, AppState};
+use crate::{db, i18n::get_translation, render_template_with_title, AppState};
 use askama::Template;
 use axum::{
     extract::State,
@@ -30,6 +32,7 @@ pub async fn matrix_report(
     let status_missing = get_translation(&state, &locale, "reports-status-missing").await;
     let status_disabled = get_translation(&state, &locale, "reports-status-disabled").await;
     let legend_title = get_translation(&state, &locale, "reports-legend-title").await;
+    let alias_domain_of = get_translation(&state, &locale, "reports-alias-domain-of").await;
     let no_domains = get_translation(&state, &locale, "reports-no-domains").await;
     let no_domains_description =
         get_translation(&state, &locale, "reports-no-domains-description").await;
@@ -38,7 +41,7 @@ pub async fn matrix_report(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
-    let report = match db::get_domain_alias_matrix_report(&pool) {
+    let report = match db::get_domain_alias_matrix_report(&pool, &state.config.get()) {
         Ok(report) => report,
         Err(e) => {
             tracing::error!("Error generating matrix report: {:?}", e);
@@ -57,6 +60,7 @@ pub async fn matrix_report(
         status_missing: &status_missing,
         status_disabled: &status_disabled,
         legend_title: &legend_title,
+        alias_domain_of: &alias_domain_of,
         no_domains: &no_domains,
         no_domains_description: &no_domains_description,
         report: &report,
@@ -72,7 +76,7 @@ pub async fn matrix_report(
 
     // Create the base template
     // Get current database id from session/cookie or default
-    let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+    let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
         .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
     // Get current database label from db_manager
     let current_db_label = state
@@ -128,17 +132,21 @@ pub async fn cross_database_domain_matrix_report(
     let no_domains_description =
         get_translation(&state, &locale, "reports-no-domains-description").await;
 
+    // Only include databases the admin has been granted access to
+    let databases = crate::handlers::auth::get_accessible_databases(&state, &headers);
+
     // Get cross-database domain matrix report data
-    let report = match db::get_cross_database_domain_matrix_report(&state.db_manager).await {
-        Ok(report) => report,
-        Err(e) => {
-            tracing::error!(
-                "Error generating cross-database domain matrix report: {:?}",
-                e
-            );
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
-        }
-    };
+    let report =
+        match db::get_cross_database_domain_matrix_report(&state.db_manager, &databases).await {
+            Ok(report) => report,
+            Err(e) => {
+                tracing::error!(
+                    "Error generating cross-database domain matrix report: {:?}",
+                    e
+                );
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        };
 
     // Create the cross-database matrix report template
     let content_template = CrossDatabaseMatrixReportTemplate {
@@ -168,7 +176,7 @@ pub async fn cross_database_domain_matrix_report(
 
     // Create the base template
     // Get current database id from session/cookie or default
-    let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+    let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
         .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
     // Get current database label from db_manager
     let current_db_label = state
@@ -229,6 +237,9 @@ pub async fn reports_list(
         get_translation(&state, &locale, "reports-alias-cross-domain-title").await;
     let alias_cross_domain_report_description =
         get_translation(&state, &locale, "reports-alias-cross-domain-description").await;
+    let delivery_report_title = get_translation(&state, &locale, "reports-delivery-title").await;
+    let delivery_report_description =
+        get_translation(&state, &locale, "reports-delivery-description").await;
     let cross_database_matrix_report_title =
         get_translation(&state, &locale, "reports-cross-db-matrix-title").await;
     let cross_database_matrix_report_description =
@@ -267,6 +278,8 @@ pub async fn reports_list(
         external_forwarders_report_description: &external_forwarders_report_description,
         alias_cross_domain_report_title: &alias_cross_domain_report_title,
         alias_cross_domain_report_description: &alias_cross_domain_report_description,
+        delivery_report_title: &delivery_report_title,
+        delivery_report_description: &delivery_report_description,
         cross_database_matrix_report_title: &cross_database_matrix_report_title,
         cross_database_matrix_report_description: &cross_database_matrix_report_description,
         cross_database_user_distribution_report_title:
@@ -291,7 +304,7 @@ pub async fn reports_list(
 
     // Create the base template
     // Get current database id from session/cookie or default
-    let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+    let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
         .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
     // Get current database label from db_manager
     let current_db_label = state
@@ -345,9 +358,21 @@ pub async fn orphaned_report(
         }
     };
 
+    let translations =
+        crate::handlers::utils::get_translations_batch(&state, &locale, DELIVERY_KEYS).await;
+    let outcomes: Vec<String> = report
+        .unresolved_destinations
+        .iter()
+        .map(|entry| {
+            let key = outcome_key(&entry.outcome);
+            translations.get(&key).cloned().unwrap_or(key)
+        })
+        .collect();
+
     let content_template = OrphanedReportTemplate {
         title: "Orphaned Aliases & Users",
         report: &report,
+        outcomes: &outcomes,
     };
 
     let content = match content_template.render() {
@@ -360,7 +385,7 @@ pub async fn orphaned_report(
 
     // Create the base template
     // Get current database id from session/cookie or default
-    let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+    let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
         .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
     // Get current database label from db_manager
     let current_db_label = state
@@ -432,7 +457,7 @@ pub async fn external_forwarders_report(
 
     // Create the base template
     // Get current database id from session/cookie or default
-    let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+    let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
         .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
     // Get current database label from db_manager
     let current_db_label = state
@@ -506,7 +531,7 @@ pub async fn alias_cross_domain_report(
 
     // Create the base template
     // Get current database id from session/cookie or default
-    let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+    let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
         .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
     // Get current database label from db_manager
     let current_db_label = state
@@ -543,6 +568,135 @@ pub async fn alias_cross_domain_report(
     }
 }
 
+pub(crate) const DELIVERY_KEYS: &[&str] = &[
+    "reports-delivery-title",
+    "reports-delivery-description",
+    "reports-delivery-address-label",
+    "reports-delivery-submit",
+    "reports-delivery-route-header",
+    "reports-delivery-final-header",
+    "reports-delivery-no-final",
+    "reports-delivery-problems",
+    "reports-delivery-disabled",
+    "reports-delivery-outcome-relocated",
+    "reports-delivery-outcome-alias",
+    "reports-delivery-outcome-alias-domain",
+    "reports-delivery-outcome-catch-all",
+    "reports-delivery-outcome-mailbox",
+    "reports-delivery-outcome-relay",
+    "reports-delivery-outcome-external",
+    "reports-delivery-outcome-loop",
+    "reports-delivery-outcome-unknown-user",
+    "reports-delivery-outcome-unknown-domain",
+    "reports-delivery-outcome-invalid-address",
+    "reports-delivery-outcome-too-deep",
+    "audit-entity-domain",
+    "audit-entity-user",
+    "audit-entity-alias",
+    "audit-entity-backup",
+    "audit-entity-relocated",
+    "audit-entity-alias-domain",
+];
+
+/// The translation key describing a delivery outcome
+pub(crate) fn outcome_key(outcome: &DeliveryOutcome) -> String {
+    let kind = match outcome {
+        DeliveryOutcome::Alias {
+            catch_all: true, ..
+        } => "catch-all".to_string(),
+        outcome => outcome.kind().replace('_', "-"),
+    };
+    format!("reports-delivery-outcome-{kind}")
+}
+
+fn delivery_rows(
+    trace: &crate::models::DeliveryTrace,
+    translations: &std::collections::HashMap<String, String>,
+) -> Vec<DeliveryRow> {
+    let translate = |key: String| translations.get(&key).cloned().unwrap_or(key);
+    trace
+        .hops
+        .iter()
+        .map(|hop| DeliveryRow {
+            label: translate(outcome_key(&hop.outcome)),
+            detail: hop.outcome.detail(),
+            problem: hop.outcome.is_problem(),
+            disabled: hop
+                .disabled
+                .iter()
+                .map(|entry| {
+                    format!(
+                        "{} {}",
+                        translate(format!("audit-entity-{}", entry.entity)),
+                        entry.name
+                    )
+                })
+                .collect(),
+            hop: hop.clone(),
+        })
+        .collect()
+}
+
+// Delivery simulator
+pub async fn delivery_report(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
+) -> Html<String> {
+    let locale = crate::handlers::language::get_user_locale(&headers);
+    let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
+        .await
+        .expect("Failed to get database pool");
+    let address = params.get("address").cloned().unwrap_or_default();
+    let translations =
+        crate::handlers::utils::get_translations_batch(&state, &locale, DELIVERY_KEYS).await;
+
+    let trace = if address.trim().is_empty() {
+        None
+    } else {
+        match crate::delivery::trace(&pool, &address) {
+            Ok(trace) => Some(trace),
+            Err(e) => {
+                tracing::error!("Error tracing delivery to {}: {:?}", address, e);
+                None
+            }
+        }
+    };
+    let rows = trace
+        .as_ref()
+        .map(|trace| delivery_rows(trace, &translations))
+        .unwrap_or_default();
+    let final_rows: Vec<DeliveryRow> = rows
+        .iter()
+        .filter(|row| row.hop.outcome.is_destination())
+        .cloned()
+        .collect();
+
+    let content_template = DeliveryReportTemplate {
+        title: &translations["reports-delivery-title"],
+        description: &translations["reports-delivery-description"],
+        address_label: &translations["reports-delivery-address-label"],
+        submit: &translations["reports-delivery-submit"],
+        route_header: &translations["reports-delivery-route-header"],
+        final_header: &translations["reports-delivery-final-header"],
+        no_final: &translations["reports-delivery-no-final"],
+        problems: &translations["reports-delivery-problems"],
+        disabled_label: &translations["reports-delivery-disabled"],
+        address: &address,
+        traced: trace.is_some(),
+        has_problems: trace.as_ref().is_some_and(|trace| trace.has_problems()),
+        rows: &rows,
+        final_rows: &final_rows,
+    };
+    render_template_with_title!(
+        content_template,
+        content_template.title,
+        &state,
+        &locale,
+        &headers
+    )
+}
+
 // Cross-database User Distribution Report
 pub async fn cross_database_user_distribution_report(
     State(state): State<AppState>,
@@ -568,8 +722,16 @@ pub async fn cross_database_user_distribution_report(
         get_translation(&state, &locale, "reports-no-users-description").await;
     let disabled = get_translation(&state, &locale, "reports-disabled").await;
 
+    // Only include databases the admin has been granted access to
+    let databases = crate::handlers::auth::get_accessible_databases(&state, &headers);
+
     // Get cross-database user distribution report data
-    let report = match db::get_cross_database_user_distribution_report(&state.db_manager).await {
+    let report = match db::get_cross_database_user_distribution_report(
+        &state.db_manager,
+        &databases,
+    )
+    .await
+    {
         Ok(report) => report,
         Err(e) => {
             tracing::error!(
@@ -607,7 +769,7 @@ pub async fn cross_database_user_distribution_report(
     };
 
     // Create the base template
-    let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+    let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
         .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
     let current_db_label = state
         .db_manager
@@ -668,8 +830,11 @@ pub async fn cross_database_feature_toggle_report(
     let enabled = get_translation(&state, &locale, "reports-enabled").await;
     let disabled = get_translation(&state, &locale, "reports-disabled").await;
 
+    // Only include databases the admin has been granted access to
+    let databases = crate::handlers::auth::get_accessible_databases(&state, &headers);
+
     // Get cross-database feature toggle report data
-    let report = match db::get_cross_database_feature_toggle_report(&state.db_manager).await {
+    let report = match db::get_cross_database_feature_toggle_report(&databases).await {
         Ok(report) => report,
         Err(e) => {
             tracing::error!(
@@ -707,7 +872,7 @@ pub async fn cross_database_feature_toggle_report(
     };
 
     // Create the base template
-    let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+    let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
         .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
     let current_db_label = state
         .db_manager
@@ -761,8 +926,12 @@ pub async fn cross_database_migration_report(
     let migration_count_header =
         get_translation(&state, &locale, "reports-migration-count-header").await;
 
+    // Only include databases the admin has been granted access to
+    let databases = crate::handlers::auth::get_accessible_databases(&state, &headers);
+
     // Get cross-database migration report data
-    let report = match db::get_cross_database_migration_report(&state.db_manager).await {
+    let report = match db::get_cross_database_migration_report(&state.db_manager, &databases).await
+    {
         Ok(report) => report,
         Err(e) => {
             tracing::error!("Error generating cross-database migration report: {:?}", e);
@@ -793,7 +962,7 @@ pub async fn cross_database_migration_report(
     };
 
     // Create the base template
-    let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+    let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
         .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
     let current_db_label = state
         .db_manager
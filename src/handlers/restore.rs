@@ -0,0 +1,398 @@
+use crate::bundle::{self, Bundle, BundleError};
+use crate::config::{AdminRole, DatabaseConfig};
+use crate::i18n::get_translation;
+use crate::restore::{self, RestoreAction, RestoreMode, RestorePlan};
+use crate::templates::restore::*;
+use crate::{render_template_with_title, AppState};
+use askama::Template;
+use axum::{
+    extract::{Multipart, State},
+    http::HeaderMap,
+    response::Html,
+};
+use clap::ValueEnum;
+use std::collections::HashMap;
+
+/// The statuses of a change, in the order previews count them
+pub const STATUSES: [&str; 5] = ["create", "update", "delete", "unchanged", "error"];
+
+/// The fields of the upload or preview form
+#[derive(Debug, Default)]
+struct Submission {
+    database: Option<String>,
+    mode: RestoreMode,
+    content: String,
+}
+
+/// Read the multipart form; an uploaded file replaces the carried-over content
+async fn read_submission(mut multipart: Multipart) -> Result<Submission, String> {
+    let mut submission = Submission::default();
+    while let Some(field) = multipart.next_field().await.map_err(|e| e.to_string())? {
+        let name = field.name().unwrap_or_default().to_string();
+        match name.as_str() {
+            "file" => {
+                let bytes = field.bytes().await.map_err(|e| e.to_string())?;
+                if !bytes.is_empty() {
+                    submission.content = String::from_utf8_lossy(&bytes).into_owned();
+                }
+            }
+            _ => {
+                let text = field.text().await.map_err(|e| e.to_string())?;
+                match name.as_str() {
+                    "database" => submission.database = Some(text),
+                    "mode" => {
+                        submission.mode = RestoreMode::from_str(&text, true).unwrap_or_default()
+                    }
+                    "content" if submission.content.is_empty() => submission.content = text,
+                    _ => {}
+                }
+            }
+        }
+    }
+    Ok(submission)
+}
+
+/// Databases the current admin may restore into: those they can edit, unless
+/// they are limited to some domains
+pub fn restorable_databases(state: &AppState, headers: &HeaderMap) -> Vec<DatabaseConfig> {
+    let Some(admin) = crate::handlers::auth::get_admin(state, headers) else {
+        return Vec::new();
+    };
+    if admin.is_domain_admin() {
+        return Vec::new();
+    }
+    crate::handlers::auth::get_accessible_databases(state, headers)
+        .into_iter()
+        .filter(|db| admin.role_for_database(&db.id) == Some(AdminRole::Edit))
+        .collect()
+}
+
+async fn bundle_error(state: &AppState, locale: &str, error: &BundleError) -> String {
+    let args = HashMap::from([("detail".to_string(), error.detail())]);
+    crate::i18n::get_translation_with_args(state, locale, error.message_id(), args).await
+}
+
+/// Plan restoring `incoming` into `target`, checking each change against the
+/// target's restrictions for the current admin
+pub async fn plan_into(
+    state: &AppState,
+    headers: &HeaderMap,
+    locale: &str,
+    target: &DatabaseConfig,
+    incoming: &Bundle,
+    mode: RestoreMode,
+) -> Result<RestorePlan, String> {
+    let Some(pool) = state.db_manager.get_pool(&target.id).await else {
+        return Err(get_translation(state, locale, "restore-no-databases").await);
+    };
+    let admin = crate::handlers::auth::get_session(state, headers)
+        .map(|session| session.username)
+        .unwrap_or_default();
+    let allowed = |operation: &str| {
+        crate::handlers::utils::check_database_restrictions(
+            state,
+            Some(&admin),
+            &target.id,
+            operation,
+        )
+        .is_ok()
+    };
+    match restore::plan_for(&pool, target, incoming, mode, allowed) {
+        Ok(plan) => Ok(plan),
+        Err(e) => {
+            tracing::error!("Failed to plan restore into {}: {:?}", target.id, e);
+            Err(get_translation(state, locale, "form-error").await)
+        }
+    }
+}
+
+/// Parse and plan a submission, returning the translated problem on failure
+async fn prepare(
+    state: &AppState,
+    headers: &HeaderMap,
+    locale: &str,
+    submission: &Submission,
+) -> Result<(DatabaseConfig, Bundle, RestorePlan), String> {
+    let target = restorable_databases(state, headers)
+        .into_iter()
+        .find(|db| submission.database.as_ref() == Some(&db.id));
+    let Some(target) = target else {
+        return Err(get_translation(state, locale, "restore-no-databases").await);
+    };
+    if submission.content.trim().is_empty() {
+        return Err(get_translation(state, locale, "restore-no-file").await);
+    }
+    let incoming = match bundle::parse(&submission.content) {
+        Ok(incoming) => incoming,
+        Err(e) => return Err(bundle_error(state, locale, &e).await),
+    };
+    let plan = plan_into(state, headers, locale, &target, &incoming, submission.mode).await?;
+    Ok((target, incoming, plan))
+}
+
+/// The changed records of a plan with translated labels; unchanged records are only counted
+pub async fn change_views(
+    state: &AppState,
+    locale: &str,
+    plan: &RestorePlan,
+    labels: &HashMap<String, String>,
+) -> Vec<RestoreChangeView> {
+    let mut views = Vec::new();
+    for change in &plan.changes {
+        if change.action == RestoreAction::Unchanged {
+            continue;
+        }
+        let details = match change.issue {
+            Some(issue) => vec![get_translation(state, locale, issue.message_id()).await],
+            None => change.changes.iter().map(ToString::to_string).collect(),
+        };
+        views.push(RestoreChangeView {
+            table_label: get_translation(state, locale, &change.table.message_id()).await,
+            key: change.key.clone(),
+            status: change.status(),
+            status_label: labels[&format!("restore-status-{}", change.status())].clone(),
+            details,
+        });
+    }
+    views
+}
+
+/// The number of changes with each status, for the summary above a diff
+pub fn status_counts(
+    plan: &RestorePlan,
+    labels: &HashMap<String, String>,
+) -> Vec<(&'static str, String, usize)> {
+    STATUSES
+        .iter()
+        .map(|status| {
+            let count = plan
+                .changes
+                .iter()
+                .filter(|change| change.status() == *status)
+                .count();
+            (
+                *status,
+                labels[&format!("restore-status-{status}")].clone(),
+                count,
+            )
+        })
+        .collect()
+}
+
+async fn render_index(
+    state: &AppState,
+    headers: &HeaderMap,
+    submission: &Submission,
+    error: Option<String>,
+) -> Html<String> {
+    let locale = crate::handlers::utils::get_user_locale(headers);
+    let translations = crate::handlers::utils::get_translations_batch(
+        state,
+        &locale,
+        &[
+            "restore-title",
+            "restore-description",
+            "restore-database",
+            "restore-mode",
+            "restore-mode-merge",
+            "restore-mode-replace",
+            "restore-file",
+            "restore-file-hint",
+            "restore-preview",
+            "restore-no-databases",
+        ],
+    )
+    .await;
+    let databases: Vec<(String, String)> = restorable_databases(state, headers)
+        .into_iter()
+        .map(|db| (db.id, db.label))
+        .collect();
+    let mode_options: Vec<(String, String)> = RestoreMode::value_variants()
+        .iter()
+        .map(|mode| {
+            let label = translations[&format!("restore-mode-{}", mode.name())].clone();
+            (mode.name().to_string(), label)
+        })
+        .collect();
+
+    let title = &translations["restore-title"];
+    let content_template = RestoreTemplate {
+        title,
+        description: &translations["restore-description"],
+        database_label: &translations["restore-database"],
+        mode_label: &translations["restore-mode"],
+        file_label: &translations["restore-file"],
+        file_hint: &translations["restore-file-hint"],
+        preview: &translations["restore-preview"],
+        no_databases: &translations["restore-no-databases"],
+        databases: &databases,
+        selected_database: submission.database.as_deref().unwrap_or_default(),
+        mode_options: &mode_options,
+        selected_mode: submission.mode.name(),
+        error,
+    };
+    render_template_with_title!(content_template, title, state, &locale, headers)
+}
+
+/// The preview of a planned restore; `notice` reports an applied or failed restore
+async fn render_preview(
+    state: &AppState,
+    headers: &HeaderMap,
+    submission: &Submission,
+    target: &DatabaseConfig,
+    incoming: &Bundle,
+    plan: &RestorePlan,
+    notice: Option<(String, bool)>,
+) -> Html<String> {
+    let locale = crate::handlers::utils::get_user_locale(headers);
+    let translations = crate::handlers::utils::get_translations_batch(
+        state,
+        &locale,
+        &[
+            "restore-title",
+            "restore-apply",
+            "restore-apply-hint",
+            "restore-start-over",
+            "restore-no-changes",
+            "restore-table-header-table",
+            "restore-table-header-key",
+            "restore-table-header-status",
+            "restore-table-header-details",
+            "restore-status-create",
+            "restore-status-update",
+            "restore-status-delete",
+            "restore-status-unchanged",
+            "restore-status-error",
+        ],
+    )
+    .await;
+    let args = HashMap::from([
+        ("source".to_string(), incoming.database.clone()),
+        (
+            "exported".to_string(),
+            incoming
+                .exported_at
+                .format("%Y-%m-%d %H:%M UTC")
+                .to_string(),
+        ),
+        ("target".to_string(), target.label.clone()),
+        ("mode".to_string(), submission.mode.name().to_string()),
+    ]);
+    let summary =
+        crate::i18n::get_translation_with_args(state, &locale, "restore-summary", args).await;
+    let counts = status_counts(plan, &translations);
+    let changes = change_views(state, &locale, plan, &translations).await;
+    let (notice, failed) = match notice {
+        Some((notice, failed)) => (Some(notice), failed),
+        None => (None, false),
+    };
+
+    let title = &translations["restore-title"];
+    let content_template = RestorePreviewTemplate {
+        title,
+        summary: &summary,
+        apply: &translations["restore-apply"],
+        apply_hint: &translations["restore-apply-hint"],
+        start_over: &translations["restore-start-over"],
+        no_changes: &translations["restore-no-changes"],
+        table_header_table: &translations["restore-table-header-table"],
+        table_header_key: &translations["restore-table-header-key"],
+        table_header_status: &translations["restore-table-header-status"],
+        table_header_details: &translations["restore-table-header-details"],
+        database: &target.id,
+        mode: submission.mode.name(),
+        content: &submission.content,
+        counts: &counts,
+        changes: &changes,
+        can_apply: notice.is_none() && plan.writes().next().is_some(),
+        notice,
+        failed,
+    };
+    render_template_with_title!(content_template, title, state, &locale, headers)
+}
+
+pub async fn index(State(state): State<AppState>, headers: HeaderMap) -> Html<String> {
+    render_index(&state, &headers, &Submission::default(), None).await
+}
+
+pub async fn preview(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    multipart: Multipart,
+) -> Html<String> {
+    let locale = crate::handlers::utils::get_user_locale(&headers);
+    let submission = match read_submission(multipart).await {
+        Ok(submission) => submission,
+        Err(e) => return render_index(&state, &headers, &Submission::default(), Some(e)).await,
+    };
+    match prepare(&state, &headers, &locale, &submission).await {
+        Ok((target, incoming, plan)) => {
+            render_preview(
+                &state,
+                &headers,
+                &submission,
+                &target,
+                &incoming,
+                &plan,
+                None,
+            )
+            .await
+        }
+        Err(error) => render_index(&state, &headers, &submission, Some(error)).await,
+    }
+}
+
+pub async fn apply(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    multipart: Multipart,
+) -> Html<String> {
+    let locale = crate::handlers::utils::get_user_locale(&headers);
+    let submission = match read_submission(multipart).await {
+        Ok(submission) => submission,
+        Err(e) => return render_index(&state, &headers, &Submission::default(), Some(e)).await,
+    };
+    let (target, incoming, plan) = match prepare(&state, &headers, &locale, &submission).await {
+        Ok(prepared) => prepared,
+        Err(error) => return render_index(&state, &headers, &submission, Some(error)).await,
+    };
+    let Some(pool) = state.db_manager.get_pool(&target.id).await else {
+        let error = get_translation(&state, &locale, "restore-no-databases").await;
+        return render_index(&state, &headers, &submission, Some(error)).await;
+    };
+
+    // Changes are recorded against the target, not the selected database
+    let mut audit = crate::handlers::utils::get_audit_context(&state, &headers);
+    audit.database_id = target.id.clone();
+    let result = crate::db::apply_restore(&pool, &target, &plan, &audit).map_err(|e| e.to_string());
+    let (notice, applied) = match result {
+        Ok(count) => {
+            let args = HashMap::from([
+                ("count".to_string(), count.to_string()),
+                ("skipped".to_string(), plan.problems().count().to_string()),
+            ]);
+            let notice =
+                crate::i18n::get_translation_with_args(&state, &locale, "restore-applied", args)
+                    .await;
+            (notice, true)
+        }
+        Err(detail) => {
+            tracing::error!("Failed to restore into {}: {}", target.id, detail);
+            let args = HashMap::from([("detail".to_string(), detail)]);
+            let notice =
+                crate::i18n::get_translation_with_args(&state, &locale, "restore-failed", args)
+                    .await;
+            (notice, false)
+        }
+    };
+    render_preview(
+        &state,
+        &headers,
+        &submission,
+        &target,
+        &incoming,
+        &plan,
+        Some((notice, !applied)),
+    )
+    .await
+}
@@ -1,5 +1,5 @@
 use crate::templates::stats::StatsTemplate;
-use crate::{db, get_system_stats_or_default, render_template, AppState};
+use crate::{config, db, get_system_stats_or_default, render_template, AppState};
 use askama::Template;
 use axum::{extract::State, http::HeaderMap, response::Html};
 
@@ -14,6 +14,14 @@ pub async fn index(State(state): State<AppState>, headers: HeaderMap) -> Html<St
 
     let domain_stats = db::get_domain_stats(&pool).unwrap_or_default();
 
+    // Usage snapshots from the usage collector
+    let largest_mailboxes = db::get_largest_mailboxes(&pool, 10, 30).unwrap_or_default();
+    let usage_history = db::get_usage_history(&pool, 30).unwrap_or_default();
+    let usage_collected = largest_mailboxes
+        .first()
+        .map(|usage| usage.collected.format("%Y-%m-%d %H:%M").to_string());
+    let usage_enabled = state.config.get().usage.source != config::UsageSource::None;
+
     // Use the batch translation fetcher for all statistics translations
     let translations = crate::handlers::utils::get_translations_batch(
         &state,
@@ -42,6 +50,15 @@ pub async fn index(State(state): State<AppState>, headers: HeaderMap) -> Html<St
             "stats-quota-usage-percentage",
             "stats-quota-total",
             "stats-quota-used",
+            "stats-usage-not-collected",
+            "stats-usage-last-collected",
+            "stats-largest-mailboxes",
+            "stats-table-header-mailbox",
+            "stats-table-header-size",
+            "stats-table-header-messages",
+            "stats-table-header-quota",
+            "stats-table-header-growth",
+            "stats-usage-history",
             "stats-recent-activity-title",
             "stats-recent-domains",
             "stats-recent-users",
@@ -78,6 +95,15 @@ pub async fn index(State(state): State<AppState>, headers: HeaderMap) -> Html<St
         quota_usage_percentage: &translations["stats-quota-usage-percentage"],
         quota_total: &translations["stats-quota-total"],
         quota_used: &translations["stats-quota-used"],
+        usage_not_collected: &translations["stats-usage-not-collected"],
+        usage_last_collected: &translations["stats-usage-last-collected"],
+        largest_mailboxes_title: &translations["stats-largest-mailboxes"],
+        table_header_mailbox: &translations["stats-table-header-mailbox"],
+        table_header_size: &translations["stats-table-header-size"],
+        table_header_messages: &translations["stats-table-header-messages"],
+        table_header_quota: &translations["stats-table-header-quota"],
+        table_header_growth: &translations["stats-table-header-growth"],
+        usage_history_title: &translations["stats-usage-history"],
         recent_activity_title: &translations["stats-recent-activity-title"],
         recent_domains: &translations["stats-recent-domains"],
         recent_users: &translations["stats-recent-users"],
@@ -88,6 +114,10 @@ pub async fn index(State(state): State<AppState>, headers: HeaderMap) -> Html<St
         recent_clients: &translations["stats-recent-clients"],
         system_stats,
         domain_stats,
+        largest_mailboxes,
+        usage_history,
+        usage_collected,
+        usage_enabled,
     };
 
     // Use the new render template macro
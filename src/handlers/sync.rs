@@ -0,0 +1,346 @@
+use crate::config::DatabaseConfig;
+use crate::handlers::restore::{restorable_databases, status_counts, STATUSES};
+use crate::i18n::get_translation;
+use crate::restore::BundleTable;
+use crate::sync::{self, SyncDiff, SyncSide};
+use crate::templates::sync::*;
+use crate::{render_template_with_title, AppState};
+use askama::Template;
+use axum::{
+    extract::{Form, Query, State},
+    http::HeaderMap,
+    response::Html,
+};
+use std::collections::HashMap;
+
+/// The compared databases and tables and the chosen records; `table` and
+/// `select` repeat
+#[derive(Debug, Default)]
+struct Selection {
+    source: String,
+    target: String,
+    tables: Vec<BundleTable>,
+    chosen: Vec<String>,
+}
+
+impl Selection {
+    fn from_fields(fields: Vec<(String, String)>) -> Self {
+        let mut selection = Selection::default();
+        for (name, value) in fields {
+            match name.as_str() {
+                "source" => selection.source = value,
+                "target" => selection.target = value,
+                "table" => selection.tables.extend(BundleTable::parse(&value)),
+                "select" => selection.chosen.push(value),
+                _ => {}
+            }
+        }
+        selection
+    }
+}
+
+/// Compare the selected databases, returning the translated problem on failure
+async fn compare(
+    state: &AppState,
+    headers: &HeaderMap,
+    locale: &str,
+    selection: &Selection,
+) -> Result<(DatabaseConfig, DatabaseConfig, SyncDiff), String> {
+    let source = crate::handlers::auth::get_accessible_databases(state, headers)
+        .into_iter()
+        .find(|db| db.id == selection.source);
+    let target = restorable_databases(state, headers)
+        .into_iter()
+        .find(|db| db.id == selection.target);
+    let (Some(source), Some(target)) = (source, target) else {
+        return Err(get_translation(state, locale, "sync-no-databases").await);
+    };
+    if source.id == target.id {
+        return Err(get_translation(state, locale, "sync-same-database").await);
+    }
+    if selection.tables.is_empty() {
+        return Err(get_translation(state, locale, "sync-no-tables").await);
+    }
+    let (Some(source_pool), Some(target_pool)) = (
+        state.db_manager.get_pool(&source.id).await,
+        state.db_manager.get_pool(&target.id).await,
+    ) else {
+        return Err(get_translation(state, locale, "sync-no-databases").await);
+    };
+
+    let admin = crate::handlers::auth::get_session(state, headers)
+        .map(|session| session.username)
+        .unwrap_or_default();
+    let allowed = |operation: &str| {
+        crate::handlers::utils::check_database_restrictions(
+            state,
+            Some(&admin),
+            &target.id,
+            operation,
+        )
+        .is_ok()
+    };
+    match sync::diff(
+        &source_pool,
+        &source,
+        &target_pool,
+        &target,
+        &selection.tables,
+        allowed,
+    ) {
+        Ok(diff) => Ok((source, target, diff)),
+        Err(e) => {
+            tracing::error!(
+                "Failed to compare {} with {}: {:?}",
+                source.id,
+                target.id,
+                e
+            );
+            Err(get_translation(state, locale, "form-error").await)
+        }
+    }
+}
+
+/// The differing records of a diff with translated labels
+async fn row_views(
+    state: &AppState,
+    locale: &str,
+    diff: &SyncDiff,
+    labels: &HashMap<String, String>,
+) -> Vec<SyncRowView> {
+    let mut views = Vec::new();
+    for change in diff.differences() {
+        let details = match change.issue {
+            Some(issue) => vec![get_translation(state, locale, issue.message_id()).await],
+            None => change
+                .changes
+                .iter()
+                .map(|field| match field.field {
+                    "crypt" => labels["sync-password-differs"].clone(),
+                    _ => field.to_string(),
+                })
+                .collect(),
+        };
+        let modified = |side| {
+            diff.modified(side, change)
+                .map(|m| m.format("%Y-%m-%d %H:%M:%S").to_string())
+                .unwrap_or_default()
+        };
+        views.push(SyncRowView {
+            id: sync::row_id(change),
+            table_label: get_translation(state, locale, &change.table.message_id()).await,
+            key: change.key.clone(),
+            status: change.status(),
+            status_label: labels[&format!("restore-status-{}", change.status())].clone(),
+            details,
+            source_modified: modified(SyncSide::Source),
+            target_modified: modified(SyncSide::Target),
+            newer: diff
+                .newer(change)
+                .map(|side| side.name())
+                .unwrap_or_default(),
+            selectable: change.is_write(),
+        });
+    }
+    views
+}
+
+/// The comparison form, with the diff of `compared`; `notice` reports a
+/// problem or an applied sync
+async fn render(
+    state: &AppState,
+    headers: &HeaderMap,
+    selection: &Selection,
+    compared: Option<&(DatabaseConfig, DatabaseConfig, SyncDiff)>,
+    notice: Option<(String, bool)>,
+) -> Html<String> {
+    let locale = crate::handlers::utils::get_user_locale(headers);
+    let translations = crate::handlers::utils::get_translations_batch(
+        state,
+        &locale,
+        &[
+            "sync-title",
+            "sync-description",
+            "sync-source",
+            "sync-target",
+            "sync-tables",
+            "sync-compare",
+            "sync-no-databases",
+            "sync-no-differences",
+            "sync-apply",
+            "sync-apply-hint",
+            "sync-newer",
+            "sync-password-differs",
+            "sync-table-header-select",
+            "sync-table-header-source-modified",
+            "sync-table-header-target-modified",
+            "sync-status-create",
+            "sync-status-update",
+            "sync-status-delete",
+            "sync-status-unchanged",
+            "sync-status-error",
+            "restore-table-header-table",
+            "restore-table-header-key",
+            "restore-table-header-status",
+            "restore-table-header-details",
+        ],
+    )
+    .await;
+    // The shared preview helpers look statuses up by their restore labels
+    let mut labels = translations.clone();
+    for status in STATUSES {
+        labels.insert(
+            format!("restore-status-{status}"),
+            translations[&format!("sync-status-{status}")].clone(),
+        );
+    }
+
+    let sources: Vec<(String, String)> =
+        crate::handlers::auth::get_accessible_databases(state, headers)
+            .into_iter()
+            .map(|db| (db.id, db.label))
+            .collect();
+    let targets: Vec<(String, String)> = restorable_databases(state, headers)
+        .into_iter()
+        .map(|db| (db.id, db.label))
+        .collect();
+    let mut table_options = Vec::new();
+    for table in BundleTable::ALL {
+        table_options.push((
+            table.name().to_string(),
+            get_translation(state, &locale, &table.message_id()).await,
+            selection.tables.contains(&table),
+        ));
+    }
+
+    let (summary, counts, rows, can_apply) = match compared {
+        Some((source, target, diff)) => {
+            let args = HashMap::from([
+                ("source".to_string(), source.label.clone()),
+                ("target".to_string(), target.label.clone()),
+            ]);
+            let summary =
+                crate::i18n::get_translation_with_args(state, &locale, "sync-summary", args).await;
+            let rows = row_views(state, &locale, diff, &labels).await;
+            let can_apply = rows.iter().any(|row| row.selectable);
+            (
+                Some(summary),
+                status_counts(&diff.plan, &labels),
+                rows,
+                can_apply,
+            )
+        }
+        None => (None, Vec::new(), Vec::new(), false),
+    };
+    let (notice, failed) = match notice {
+        Some((notice, failed)) => (Some(notice), failed),
+        None => (None, false),
+    };
+
+    let title = &translations["sync-title"];
+    let content_template = SyncTemplate {
+        title,
+        description: &translations["sync-description"],
+        source_label: &translations["sync-source"],
+        target_label: &translations["sync-target"],
+        tables_label: &translations["sync-tables"],
+        compare: &translations["sync-compare"],
+        no_databases: &translations["sync-no-databases"],
+        no_differences: &translations["sync-no-differences"],
+        apply: &translations["sync-apply"],
+        apply_hint: &translations["sync-apply-hint"],
+        newer_label: &translations["sync-newer"],
+        table_header_select: &translations["sync-table-header-select"],
+        table_header_table: &translations["restore-table-header-table"],
+        table_header_key: &translations["restore-table-header-key"],
+        table_header_status: &translations["restore-table-header-status"],
+        table_header_details: &translations["restore-table-header-details"],
+        table_header_source_modified: &translations["sync-table-header-source-modified"],
+        table_header_target_modified: &translations["sync-table-header-target-modified"],
+        sources: &sources,
+        targets: &targets,
+        selected_source: &selection.source,
+        selected_target: &selection.target,
+        table_options: &table_options,
+        summary,
+        counts: &counts,
+        rows: &rows,
+        can_apply,
+        notice,
+        failed,
+    };
+    render_template_with_title!(content_template, title, state, &locale, headers)
+}
+
+/// The comparison form, and the diff once databases are chosen
+pub async fn index(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(fields): Query<Vec<(String, String)>>,
+) -> Html<String> {
+    let locale = crate::handlers::utils::get_user_locale(&headers);
+    let mut selection = Selection::from_fields(fields);
+    if selection.source.is_empty() && selection.target.is_empty() {
+        selection.tables = sync::DEFAULT_TABLES.to_vec();
+        return render(&state, &headers, &selection, None, None).await;
+    }
+    match compare(&state, &headers, &locale, &selection).await {
+        Ok(compared) => render(&state, &headers, &selection, Some(&compared), None).await,
+        Err(error) => render(&state, &headers, &selection, None, Some((error, true))).await,
+    }
+}
+
+/// Sync the chosen records into the target and show what differs afterwards
+pub async fn apply(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Form(fields): Form<Vec<(String, String)>>,
+) -> Html<String> {
+    let locale = crate::handlers::utils::get_user_locale(&headers);
+    let selection = Selection::from_fields(fields);
+    let compared = match compare(&state, &headers, &locale, &selection).await {
+        Ok(compared) => compared,
+        Err(error) => return render(&state, &headers, &selection, None, Some((error, true))).await,
+    };
+    // Recomputed, so only records that still differ are written
+    let plan = compared.2.selection(&selection.chosen);
+    if plan.writes().next().is_none() {
+        let error = get_translation(&state, &locale, "sync-no-selection").await;
+        return render(
+            &state,
+            &headers,
+            &selection,
+            Some(&compared),
+            Some((error, true)),
+        )
+        .await;
+    }
+    let (_, target, _) = &compared;
+    let Some(pool) = state.db_manager.get_pool(&target.id).await else {
+        let error = get_translation(&state, &locale, "sync-no-databases").await;
+        return render(&state, &headers, &selection, None, Some((error, true))).await;
+    };
+
+    // Changes are recorded against the target, not the selected database
+    let mut audit = crate::handlers::utils::get_audit_context(&state, &headers);
+    audit.database_id = target.id.clone();
+    let notice = match crate::db::apply_restore(&pool, target, &plan, &audit) {
+        Ok(count) => {
+            let args = HashMap::from([("count".to_string(), count.to_string())]);
+            let notice =
+                crate::i18n::get_translation_with_args(&state, &locale, "sync-applied", args).await;
+            (notice, false)
+        }
+        Err(e) => {
+            tracing::error!("Failed to sync into {}: {}", target.id, e);
+            let args = HashMap::from([("detail".to_string(), e.to_string())]);
+            let notice =
+                crate::i18n::get_translation_with_args(&state, &locale, "sync-failed", args).await;
+            (notice, true)
+        }
+    };
+    match compare(&state, &headers, &locale, &selection).await {
+        Ok(compared) => render(&state, &headers, &selection, Some(&compared), Some(notice)).await,
+        Err(_) => render(&state, &headers, &selection, None, Some(notice)).await,
+    }
+}
@@ -1,3 +1,4 @@
+use crate::password::PasswordScheme;
 use crate::templates::layout::BaseTemplate;
 use crate::templates::users::*;
 use crate::{
@@ -7,8 +8,8 @@ use crate::{
 use askama::Template;
 use axum::{
     extract::{Path, State},
-    http::HeaderMap,
-    response::Html,
+    http::{header, HeaderMap},
+    response::{Html, IntoResponse},
     Form,
 };
 use serde::Deserialize;
@@ -21,7 +22,7 @@ pub struct ChangePasswordForm {
 
 // Helper function to get current database info
 async fn get_current_db_info(state: &AppState, headers: &HeaderMap) -> (String, String) {
-    let current_db_id = crate::handlers::auth::get_selected_database(headers)
+    let current_db_id = crate::handlers::auth::get_selected_database(state, headers)
         .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
     let current_db_label = state
         .db_manager
@@ -81,7 +82,16 @@ async fn build_user_list_template(
     }
 }
 
-async fn build_user_show_template(state: &AppState, locale: &str, user: User) -> UserShowTemplate {
+async fn build_user_show_template(
+    state: &AppState,
+    locale: &str,
+    user: User,
+    scheme: PasswordScheme,
+) -> UserShowTemplate {
+    let hash_scheme = match crate::password::identify(&user.crypt) {
+        Some(info) => info.scheme.to_string(),
+        None => get_translation(state, locale, "users-password-hash-unknown").await,
+    };
     UserShowTemplate {
         title: get_translation(state, locale, "users-show-user-title").await,
         view_edit_settings: get_translation(state, locale, "users-view-edit-settings").await,
@@ -133,7 +143,12 @@ async fn build_user_show_template(state: &AppState, locale: &str, user: User) ->
             "users-require-password-change-button",
         )
         .await,
+        password_hash: get_translation(state, locale, "users-password-hash").await,
+        password_needs_rehash: get_translation(state, locale, "users-password-needs-rehash").await,
+        hash_scheme,
+        needs_rehash: crate::password::needs_rehash(&user.crypt, scheme),
         user,
+        quota: crate::handlers::utils::get_quota_labels(state, locale).await,
     }
 }
 
@@ -193,6 +208,8 @@ async fn build_user_form_template(
         users_tooltip_maildir: get_translation(state, locale, "users-tooltip-maildir").await,
         users_placeholder_maildir: get_translation(state, locale, "users-placeholder-maildir")
             .await,
+        generate_password: get_translation(state, locale, "users-generate-password").await,
+        quota: crate::handlers::utils::get_quota_labels(state, locale).await,
     }
 }
 
@@ -201,8 +218,9 @@ pub async fn list(State(state): State<AppState>, headers: HeaderMap) -> Html<Str
         .await
         .expect("Failed to get database pool");
     let locale = crate::handlers::utils::get_user_locale(&headers);
+    let scope = crate::handlers::utils::get_address_scope(&state, &headers);
     // Get users (use regular function for now)
-    let users = match db::get_users(&pool) {
+    let users = match db::get_users_in_scope(&pool, &scope) {
         Ok(users) => users,
         Err(_) => vec![],
     };
@@ -269,6 +287,8 @@ pub async fn new(State(state): State<AppState>, headers: HeaderMap) -> Html<Stri
         maildir: "/var/spool/mail/virtual".to_string(),
         enabled: true,
         change_password: false,
+        quota_bytes: None,
+        quota_messages: None,
     };
     let translations = crate::handlers::utils::get_translations_batch(
         &state,
@@ -338,6 +358,8 @@ pub async fn new(State(state): State<AppState>, headers: HeaderMap) -> Html<Stri
         users_tooltip_maildir: get_translation(&state, &locale, "users-tooltip-maildir").await,
         users_placeholder_maildir: get_translation(&state, &locale, "users-placeholder-maildir")
             .await,
+        generate_password: get_translation(&state, &locale, "users-generate-password").await,
+        quota: crate::handlers::utils::get_quota_labels(&state, &locale).await,
     };
     render_template!(content_template, &state, &locale, &headers)
 }
@@ -350,14 +372,21 @@ pub async fn show(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let scope = crate::handlers::utils::get_address_scope(&state, &headers);
     let user = get_entity_or_not_found!(
-        db::get_user(&pool, id),
+        db::get_user_in_scope(&pool, &id, &scope),
         &state,
         &crate::handlers::utils::get_user_locale(&headers),
         "users-not-found"
     );
     let locale = crate::handlers::utils::get_user_locale(&headers);
-    let content_template = build_user_show_template(&state, &locale, user).await;
+    let content_template = build_user_show_template(
+        &state,
+        &locale,
+        user,
+        crate::handlers::utils::get_password_scheme(&state, &headers),
+    )
+    .await;
     render_template!(content_template, &state, &locale, &headers)
 }
 
@@ -370,8 +399,9 @@ pub async fn edit(
         .await
         .expect("Failed to get database pool");
     let locale = crate::handlers::language::get_user_locale(&headers);
+    let scope = crate::handlers::utils::get_address_scope(&state, &headers);
 
-    let user = match db::get_user(&pool, id) {
+    let user = match db::get_user_in_scope(&pool, &id, &scope) {
         Ok(user) => user,
         Err(_) => return Html("User not found".to_string()),
     };
@@ -383,6 +413,8 @@ pub async fn edit(
         maildir: user.maildir.clone(),
         enabled: user.enabled,
         change_password: user.change_password,
+        quota_bytes: user.quota_bytes,
+        quota_messages: user.quota_messages,
     };
 
     let content_template = build_user_form_template(&state, &locale, Some(user), form, None).await;
@@ -401,7 +433,8 @@ pub async fn edit(
             current_db_id,
         )
         .await
-        .unwrap();
+        .unwrap()
+        .for_request(&state, &headers);
         Html(template.render().unwrap())
     }
 }
@@ -412,12 +445,22 @@ pub async fn create(
     Form(form): Form<UserForm>,
 ) -> Html<String> {
     // Get current database ID for restriction checks
-    let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+    let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
         .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
 
-    // Check database restrictions
-    if let Err(_status_code) =
-        crate::handlers::utils::check_database_restrictions(&state, &current_db_id, "create_user")
+    // Check database restrictions and the admin's grant on this database
+    let admin = crate::handlers::auth::get_session(&state, &headers)
+        .map(|session| session.username)
+        .unwrap_or_default();
+    let scope = crate::handlers::utils::get_address_scope(&state, &headers);
+    if crate::handlers::utils::check_database_restrictions(
+        &state,
+        Some(&admin),
+        &current_db_id,
+        "create_user",
+    )
+    .is_err()
+        || !scope.allows(&form.id)
     {
         let locale = crate::handlers::language::get_user_locale(&headers);
         let error_msg = get_translation(&state, &locale, "error-operation-not-allowed").await;
@@ -438,7 +481,8 @@ pub async fn create(
                 current_db_id,
             )
             .await
-            .unwrap();
+            .unwrap()
+            .for_request(&state, &headers);
             return Html(template.render().unwrap());
         }
     }
@@ -446,11 +490,25 @@ pub async fn create(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
     let locale = crate::handlers::language::get_user_locale(&headers);
 
-    // Validate required fields
-    if form.id.trim().is_empty() {
-        let error_msg = get_translation(&state, &locale, "validation-username-required").await;
+    // Validate required fields and the password policy
+    let error_msg = if form.id.trim().is_empty() {
+        Some(get_translation(&state, &locale, "validation-username-required").await)
+    } else {
+        crate::handlers::utils::check_password_policy(
+            &state,
+            &headers,
+            &locale,
+            &form.password,
+            &form.id,
+            &form.name,
+        )
+        .await
+    };
+    if let Some(error_msg) = error_msg {
         let form_template =
             build_user_form_template(&state, &locale, None, form.clone(), Some(error_msg)).await;
         let content = form_template.render().unwrap();
@@ -468,14 +526,16 @@ pub async fn create(
                 current_db_id,
             )
             .await
-            .unwrap();
+            .unwrap()
+            .for_request(&state, &headers);
             Html(template.render().unwrap())
         }
     } else {
         // Create user directly (no domain validation needed)
-        match db::create_user(&pool, form.clone()) {
+        let scheme = crate::handlers::utils::get_password_scheme(&state, &headers);
+        match db::create_user(&pool, form.clone(), scheme, &audit) {
             Ok(_) => {
-                let users = match db::get_users(&pool) {
+                let users = match db::get_users_in_scope(&pool, &scope) {
                     Ok(users) => users,
                     Err(e) => {
                         eprintln!("Error getting users: {e:?}");
@@ -501,13 +561,22 @@ pub async fn create(
                         current_db_id,
                     )
                     .await
-                    .unwrap();
+                    .unwrap()
+                    .for_request(&state, &headers);
                     Html(template.render().unwrap())
                 }
             }
             Err(e) => {
                 let error_msg = if e.to_string().contains("Duplicate entry") {
                     get_translation(&state, &locale, "error-duplicate-user").await
+                } else if matches!(
+                    e,
+                    diesel::result::Error::DatabaseError(
+                        diesel::result::DatabaseErrorKind::CheckViolation,
+                        _
+                    )
+                ) {
+                    get_translation(&state, &locale, "error-quota-cap").await
                 } else {
                     get_translation(&state, &locale, "error-unexpected").await
                 };
@@ -531,7 +600,8 @@ pub async fn create(
                         current_db_id,
                     )
                     .await
-                    .unwrap();
+                    .unwrap()
+                    .for_request(&state, &headers);
                     Html(template.render().unwrap())
                 }
             }
@@ -546,12 +616,23 @@ pub async fn update(
     Form(form): Form<UserForm>,
 ) -> Html<String> {
     // Get current database ID for restriction checks
-    let current_db_id = crate::handlers::auth::get_selected_database(&headers)
+    let current_db_id = crate::handlers::auth::get_selected_database(&state, &headers)
         .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
 
-    // Check database restrictions
-    if let Err(_status_code) =
-        crate::handlers::utils::check_database_restrictions(&state, &current_db_id, "update_user")
+    // Check database restrictions and the admin's grant on this database
+    let admin = crate::handlers::auth::get_session(&state, &headers)
+        .map(|session| session.username)
+        .unwrap_or_default();
+    // Domain admins may neither edit nor move users outside their domains
+    let scope = crate::handlers::utils::get_address_scope(&state, &headers);
+    if crate::handlers::utils::check_database_restrictions(
+        &state,
+        Some(&admin),
+        &current_db_id,
+        "update_user",
+    )
+    .is_err()
+        || !scope.allows(&form.id)
     {
         let locale = crate::handlers::language::get_user_locale(&headers);
         let error_msg = get_translation(&state, &locale, "error-operation-not-allowed").await;
@@ -560,7 +641,7 @@ pub async fn update(
         let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
             .await
             .expect("Failed to get database pool");
-        let existing_user = match db::get_user(&pool, id.clone()) {
+        let existing_user = match db::get_user_in_scope(&pool, &id, &scope) {
             Ok(user) => user,
             Err(_) => return Html("User not found".to_string()),
         };
@@ -588,7 +669,8 @@ pub async fn update(
                 current_db_id,
             )
             .await
-            .unwrap();
+            .unwrap()
+            .for_request(&state, &headers);
             return Html(template.render().unwrap());
         }
     }
@@ -596,10 +678,11 @@ pub async fn update(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
     let locale = crate::handlers::language::get_user_locale(&headers);
 
     // First get the existing user
-    let existing_user = match db::get_user(&pool, id.clone()) {
+    let existing_user = match db::get_user_in_scope(&pool, &id, &scope) {
         Ok(user) => user,
         Err(_) => return Html("User not found".to_string()),
     };
@@ -630,18 +713,25 @@ pub async fn update(
                 current_db_id,
             )
             .await
-            .unwrap();
+            .unwrap()
+            .for_request(&state, &headers);
             Html(template.render().unwrap())
         }
     } else {
-        match db::update_user(&pool, id.clone(), form.clone()) {
+        match db::update_user(&pool, id.clone(), form.clone(), &audit) {
             Ok(_) => {
                 let user = match db::get_user(&pool, id.clone()) {
                     Ok(user) => user,
                     Err(_) => return Html("User not found".to_string()),
                 };
 
-                let content_template = build_user_show_template(&state, &locale, user).await;
+                let content_template = build_user_show_template(
+                    &state,
+                    &locale,
+                    user,
+                    crate::handlers::utils::get_password_scheme(&state, &headers),
+                )
+                .await;
                 let content = content_template.render().unwrap();
 
                 if crate::handlers::utils::is_htmx_request(&headers) {
@@ -658,13 +748,22 @@ pub async fn update(
                         current_db_id,
                     )
                     .await
-                    .unwrap();
+                    .unwrap()
+                    .for_request(&state, &headers);
                     Html(template.render().unwrap())
                 }
             }
             Err(e) => {
                 let error_msg = if e.to_string().contains("Duplicate entry") {
                     get_translation(&state, &locale, "error-duplicate-user").await
+                } else if matches!(
+                    e,
+                    diesel::result::Error::DatabaseError(
+                        diesel::result::DatabaseErrorKind::CheckViolation,
+                        _
+                    )
+                ) {
+                    get_translation(&state, &locale, "error-quota-cap").await
                 } else {
                     get_translation(&state, &locale, "error-unexpected").await
                 };
@@ -693,7 +792,8 @@ pub async fn update(
                         current_db_id,
                     )
                     .await
-                    .unwrap();
+                    .unwrap()
+                    .for_request(&state, &headers);
                     Html(template.render().unwrap())
                 }
             }
@@ -709,11 +809,16 @@ pub async fn delete(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
     let locale = crate::handlers::language::get_user_locale(&headers);
+    let scope = crate::handlers::utils::get_address_scope(&state, &headers);
+    if !scope.allows(&id) {
+        return Html("User not found".to_string());
+    }
 
-    match db::delete_user(&pool, id) {
+    match db::delete_user(&pool, id, &audit) {
         Ok(_) => {
-            let users = db::get_users(&pool).unwrap_or_default();
+            let users = db::get_users_in_scope(&pool, &scope).unwrap_or_default();
             let paginated = PaginatedResult::new(users.clone(), 0, 1, 20);
             let content_template =
                 build_user_list_template(&state, &locale, users, paginated).await;
@@ -731,11 +836,16 @@ pub async fn toggle_enabled(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
     let locale = crate::handlers::language::get_user_locale(&headers);
+    let scope = crate::handlers::utils::get_address_scope(&state, &headers);
+    if !scope.allows(&id) {
+        return Html("User not found".to_string());
+    }
 
-    match db::toggle_user_enabled(&pool, id.clone()) {
+    match db::toggle_user_enabled(&pool, id.clone(), &audit) {
         Ok(_) => {
-            let users = db::get_users(&pool).unwrap_or_default();
+            let users = db::get_users_in_scope(&pool, &scope).unwrap_or_default();
             let paginated = PaginatedResult::new(users.clone(), 0, 1, 20);
             let content_template =
                 build_user_list_template(&state, &locale, users, paginated).await;
@@ -753,11 +863,16 @@ pub async fn toggle_enabled_list(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
     let locale = crate::handlers::language::get_user_locale(&headers);
+    let scope = crate::handlers::utils::get_address_scope(&state, &headers);
+    if !scope.allows(&id) {
+        return Html("User not found".to_string());
+    }
 
-    match db::toggle_user_enabled(&pool, id.clone()) {
+    match db::toggle_user_enabled(&pool, id.clone(), &audit) {
         Ok(_) => {
-            let users = db::get_users(&pool).unwrap_or_default();
+            let users = db::get_users_in_scope(&pool, &scope).unwrap_or_default();
             let paginated = PaginatedResult::new(users.clone(), 0, 1, 20);
             let content_template =
                 build_user_list_template(&state, &locale, users, paginated).await;
@@ -775,16 +890,27 @@ pub async fn toggle_enabled_show(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
     let locale = crate::handlers::language::get_user_locale(&headers);
+    let scope = crate::handlers::utils::get_address_scope(&state, &headers);
+    if !scope.allows(&id) {
+        return Html("User not found".to_string());
+    }
 
-    match db::toggle_user_enabled(&pool, id.clone()) {
+    match db::toggle_user_enabled(&pool, id.clone(), &audit) {
         Ok(_) => {
             let user = match db::get_user(&pool, id.clone()) {
                 Ok(user) => user,
                 Err(_) => return Html("User not found".to_string()),
             };
 
-            let content_template = build_user_show_template(&state, &locale, user).await;
+            let content_template = build_user_show_template(
+                &state,
+                &locale,
+                user,
+                crate::handlers::utils::get_password_scheme(&state, &headers),
+            )
+            .await;
             Html(content_template.render().unwrap())
         }
         Err(_) => Html("Failed to toggle user status".to_string()),
@@ -802,7 +928,8 @@ pub async fn change_password_form(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
-    let user = match db::get_user(&pool, id.clone()) {
+    let scope = crate::handlers::utils::get_address_scope(&state, &headers);
+    let user = match db::get_user_in_scope(&pool, &id, &scope) {
         Ok(user) => user,
         Err(_) => return Html("User not found".to_string()),
     };
@@ -821,8 +948,10 @@ pub async fn change_password_post(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
     let locale = crate::handlers::language::get_user_locale(&headers);
-    let user = match db::get_user(&pool, id.clone()) {
+    let scope = crate::handlers::utils::get_address_scope(&state, &headers);
+    let user = match db::get_user_in_scope(&pool, &id, &scope) {
         Ok(user) => user,
         Err(_) => return Html("User not found".to_string()),
     };
@@ -831,14 +960,29 @@ pub async fn change_password_post(
         let content = render_change_password_form(&user, Some(error_msg), &state, &locale).await;
         return Html(content);
     }
-    if form.new_password.len() < 8 {
-        let error_msg = get_translation(&state, &locale, "error-password-too-short").await;
+    if let Some(error_msg) = crate::handlers::utils::check_password_policy(
+        &state,
+        &headers,
+        &locale,
+        &form.new_password,
+        &user.id,
+        &user.name,
+    )
+    .await
+    {
         let content = render_change_password_form(&user, Some(error_msg), &state, &locale).await;
         return Html(content);
     }
-    match db::update_user_password(&pool, id.clone(), &form.new_password) {
+    let scheme = crate::handlers::utils::get_password_scheme(&state, &headers);
+    match db::update_user_password(&pool, id.clone(), &form.new_password, scheme, &audit) {
         Ok(_) => {
-            let content_template = build_user_show_template(&state, &locale, user).await;
+            let content_template = build_user_show_template(
+                &state,
+                &locale,
+                user,
+                crate::handlers::utils::get_password_scheme(&state, &headers),
+            )
+            .await;
             let content = content_template.render().unwrap();
             Html(content)
         }
@@ -877,11 +1021,31 @@ async fn render_change_password_form(
         cancel_button: get_translation(state, locale, "users-cancel-button").await,
         change_password_button: get_translation(state, locale, "users-change-password-button")
             .await,
+        generate_password: get_translation(state, locale, "users-generate-password").await,
     }
     .render()
     .unwrap()
 }
 
+/// A random password that satisfies the selected database's policy
+///
+/// It is shown once and not stored, so the response must not be cached.
+pub async fn generate_password(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let locale = crate::handlers::language::get_user_locale(&headers);
+    let policy = crate::handlers::utils::get_password_policy(&state, &headers);
+    let template = GeneratedPasswordTemplate {
+        password: crate::password::generate_password(&policy),
+        notice: get_translation(&state, &locale, "users-generated-password-notice").await,
+    };
+    (
+        [(header::CACHE_CONTROL, "no-store")],
+        Html(template.render().unwrap()),
+    )
+}
+
 pub async fn toggle_change_password(
     State(state): State<AppState>,
     Path(id): Path<String>,
@@ -890,9 +1054,11 @@ pub async fn toggle_change_password(
     let pool = crate::handlers::utils::get_current_db_pool(&state, &headers)
         .await
         .expect("Failed to get database pool");
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
     let locale = crate::handlers::language::get_user_locale(&headers);
+    let scope = crate::handlers::utils::get_address_scope(&state, &headers);
 
-    let user = match db::get_user(&pool, id.clone()) {
+    let user = match db::get_user_in_scope(&pool, &id, &scope) {
         Ok(user) => user,
         Err(_) => return Html("User not found".to_string()),
     };
@@ -905,10 +1071,12 @@ pub async fn toggle_change_password(
         maildir: user.maildir.clone(),
         enabled: user.enabled,
         change_password: !user.change_password, // Toggle the value
+        quota_bytes: user.quota_bytes,
+        quota_messages: user.quota_messages,
     };
 
     // Update the user with the toggled change_password field
-    match db::update_user(&pool, id.clone(), form) {
+    match db::update_user(&pool, id.clone(), form, &audit) {
         Ok(_) => {
             // Get the updated user
             let updated_user = match db::get_user(&pool, id.clone()) {
@@ -916,7 +1084,13 @@ pub async fn toggle_change_password(
                 Err(_) => return Html("User not found".to_string()),
             };
 
-            let content_template = build_user_show_template(&state, &locale, updated_user).await;
+            let content_template = build_user_show_template(
+                &state,
+                &locale,
+                updated_user,
+                crate::handlers::utils::get_password_scheme(&state, &headers),
+            )
+            .await;
             let content = content_template.render().unwrap();
 
             if crate::handlers::utils::is_htmx_request(&headers) {
@@ -932,7 +1106,8 @@ pub async fn toggle_change_password(
                     current_db_id,
                 )
                 .await
-                .unwrap();
+                .unwrap()
+                .for_request(&state, &headers);
                 Html(template.render().unwrap())
             }
         }
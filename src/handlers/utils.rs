@@ -1,3 +1,6 @@
+use crate::config::{AdminRole, Config};
+use crate::templates::quota::QuotaLabels;
+use crate::templates::validity::ValidityLabels;
 use crate::{i18n::get_translation, AppState};
 use askama::Template;
 use axum::http::HeaderMap;
@@ -39,7 +42,7 @@ macro_rules! render_template {
             Html(content)
         } else {
             // Get current database id from session/cookie or default
-            let current_db_id = $crate::handlers::auth::get_selected_database($headers)
+            let current_db_id = $crate::handlers::auth::get_selected_database($state, $headers)
                 .unwrap_or_else(|| $state.db_manager.get_default_db_id().to_string());
             // Get current database label from db_manager
             let current_db_label = $state
@@ -60,7 +63,7 @@ macro_rules! render_template {
             )
             .await
             {
-                Ok(template) => template,
+                Ok(template) => template.for_request($state, $headers),
                 Err(e) => {
                     tracing::error!("Failed to create base template: {:?}", e);
                     return Html("Error creating template".to_string());
@@ -95,7 +98,7 @@ macro_rules! render_template_with_title {
             Html(content)
         } else {
             // Get current database id from session/cookie or default
-            let current_db_id = $crate::handlers::auth::get_selected_database($headers)
+            let current_db_id = $crate::handlers::auth::get_selected_database($state, $headers)
                 .unwrap_or_else(|| $state.db_manager.get_default_db_id().to_string());
             // Get current database label from db_manager
             let current_db_label = $state
@@ -116,7 +119,7 @@ macro_rules! render_template_with_title {
             )
             .await
             {
-                Ok(template) => template,
+                Ok(template) => template.for_request($state, $headers),
                 Err(e) => {
                     tracing::error!("Failed to create base template: {:?}", e);
                     return Html("Error creating template".to_string());
@@ -206,6 +209,7 @@ macro_rules! default_system_stats {
             used_quota: 0,
             quota_usage_percent: 0.0,
             enabled_domains_and_backups: 0,
+            expiring_aliases: 0,
         }
     }};
 }
@@ -239,14 +243,98 @@ pub async fn get_current_db_pool(
     headers: &HeaderMap,
 ) -> Result<crate::DbPool, Box<dyn std::error::Error>> {
     // Get the selected database from the session, or fall back to default
-    let selected_db = crate::handlers::auth::get_selected_database(headers)
+    let selected_db = crate::handlers::auth::get_selected_database(state, headers)
         .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
 
+    // Authenticated admins only get pools for databases they have been granted
+    let pool = match crate::handlers::auth::get_admin(state, headers) {
+        Some(admin) => {
+            state
+                .db_manager
+                .get_pool_for_admin(&admin, &selected_db)
+                .await
+        }
+        None => state.db_manager.get_pool(&selected_db).await,
+    };
+
+    pool.ok_or_else(|| format!("No database pool available for '{selected_db}'").into())
+}
+
+/// Identify the acting admin and selected database for audit log entries
+pub fn get_audit_context(state: &AppState, headers: &HeaderMap) -> crate::db::AuditContext {
+    match crate::handlers::auth::get_session(state, headers) {
+        Some(session) => crate::db::AuditContext::new(session.username, session.database_id),
+        None => crate::db::AuditContext::new("unknown", state.db_manager.get_default_db_id()),
+    }
+}
+
+/// Users and aliases the current admin may see and change
+pub fn get_address_scope(state: &AppState, headers: &HeaderMap) -> crate::db::AddressScope {
+    crate::handlers::auth::get_admin(state, headers)
+        .map(|admin| admin.address_scope())
+        .unwrap_or_default()
+}
+
+/// Password scheme of the selected database
+pub fn get_password_scheme(
+    state: &AppState,
+    headers: &HeaderMap,
+) -> crate::password::PasswordScheme {
+    let audit = get_audit_context(state, headers);
+    state.config.get().password_scheme(&audit.database_id)
+}
+
+/// Password policy of the selected database
+pub fn get_password_policy(
+    state: &AppState,
+    headers: &HeaderMap,
+) -> crate::password::PasswordPolicy {
+    let audit = get_audit_context(state, headers);
     state
-        .db_manager
-        .get_pool(&selected_db)
-        .await
-        .ok_or_else(|| format!("No database pool available for '{selected_db}'").into())
+        .config
+        .get()
+        .password_policy(&audit.database_id)
+        .clone()
+}
+
+/// Check a new mailbox password against the selected database's policy
+///
+/// Returns the translated problems, or `None` if the password is acceptable.
+pub async fn check_password_policy(
+    state: &AppState,
+    headers: &HeaderMap,
+    locale: &str,
+    password: &str,
+    address: &str,
+    name: &str,
+) -> Option<String> {
+    let policy = get_password_policy(state, headers);
+    check_password_against(state, locale, &policy, password, address, name).await
+}
+
+/// [`check_password_policy`] against a given policy, for callers without an
+/// admin session such as the mailbox portal
+pub async fn check_password_against(
+    state: &AppState,
+    locale: &str,
+    policy: &crate::password::PasswordPolicy,
+    password: &str,
+    address: &str,
+    name: &str,
+) -> Option<String> {
+    let violations = crate::password::check_policy(policy, password, address, name);
+    if violations.is_empty() {
+        return None;
+    }
+    let mut messages = Vec::new();
+    for violation in violations {
+        let args = HashMap::from([("min".to_string(), policy.min_length.to_string())]);
+        messages.push(
+            crate::i18n::get_translation_with_args(state, locale, violation.message_id(), args)
+                .await,
+        );
+    }
+    Some(messages.join(" "))
 }
 
 /// Batch translation fetcher
@@ -306,6 +394,70 @@ pub async fn get_table_translations(state: &AppState, locale: &str) -> HashMap<S
     .await
 }
 
+/// Labels for the quota fields on the user and domain pages
+pub async fn get_quota_labels(state: &AppState, locale: &str) -> QuotaLabels {
+    let mut t = get_translations_batch(
+        state,
+        locale,
+        &[
+            "quota-title",
+            "quota-size",
+            "quota-messages",
+            "quota-default-size",
+            "quota-default-messages",
+            "quota-cap",
+            "quota-size-hint",
+            "quota-user-hint",
+            "quota-domain-hint",
+            "quota-cap-hint",
+            "quota-domain-default",
+        ],
+    )
+    .await;
+    let mut take = |key: &str| t.remove(key).unwrap_or_default();
+    QuotaLabels {
+        title: take("quota-title"),
+        size: take("quota-size"),
+        messages: take("quota-messages"),
+        default_size: take("quota-default-size"),
+        default_messages: take("quota-default-messages"),
+        cap: take("quota-cap"),
+        size_hint: take("quota-size-hint"),
+        user_hint: take("quota-user-hint"),
+        domain_hint: take("quota-domain-hint"),
+        cap_hint: take("quota-cap-hint"),
+        domain_default: take("quota-domain-default"),
+    }
+}
+
+/// Labels for the validity window on the alias and relocated pages
+pub async fn get_validity_labels(state: &AppState, locale: &str) -> ValidityLabels {
+    let mut t = get_translations_batch(
+        state,
+        locale,
+        &[
+            "validity-title",
+            "validity-valid-from",
+            "validity-valid-until",
+            "validity-hint",
+            "validity-starts",
+            "validity-expires",
+            "validity-expired",
+        ],
+    )
+    .await;
+    let mut take = |key: &str| t.remove(key).unwrap_or_default();
+    ValidityLabels {
+        title: take("validity-title"),
+        valid_from: take("validity-valid-from"),
+        valid_until: take("validity-valid-until"),
+        hint: take("validity-hint"),
+        starts: take("validity-starts"),
+        expires: take("validity-expires"),
+        expired: take("validity-expired"),
+    }
+}
+
 /// Helper function to render a template with proper error handling
 pub async fn render_template_with_layout<T>(
     template: T,
@@ -328,7 +480,7 @@ where
         Html(content)
     } else {
         // Get current database id from session/cookie or default
-        let current_db_id = crate::handlers::auth::get_selected_database(headers)
+        let current_db_id = crate::handlers::auth::get_selected_database(state, headers)
             .unwrap_or_else(|| state.db_manager.get_default_db_id().to_string());
         // Get current database label from db_manager
         let current_db_label = state
@@ -349,7 +501,7 @@ where
         )
         .await
         {
-            Ok(template) => template,
+            Ok(template) => template.for_request(state, headers),
             Err(e) => {
                 error!("Failed to create base template: {:?}", e);
                 return Html("Error creating template".to_string());
@@ -377,12 +529,39 @@ pub async fn handle_not_found<T>(
 }
 
 /// Check database feature restrictions and return error if operation is not allowed
+///
+/// `admin` is the username of the admin performing the operation, who must hold
+/// an edit grant on the database. Pass `None` for trusted local callers that
+/// act without an admin identity; the feature toggles still apply.
 pub fn check_database_restrictions(
     state: &AppState,
+    admin: Option<&str>,
+    database_id: &str,
+    operation: &str,
+) -> Result<(), StatusCode> {
+    check_config_restrictions(&state.config.get(), admin, database_id, operation)
+}
+
+/// [`check_database_restrictions`] against a bare [`Config`], for callers
+/// without an [`AppState`] such as the command-line tool
+pub fn check_config_restrictions(
+    config: &Config,
+    admin: Option<&str>,
     database_id: &str,
     operation: &str,
 ) -> Result<(), StatusCode> {
-    let config = &state.config;
+    // Check the admin's grant on this database
+    if let Some(username) = admin {
+        if config.admin_role_for_database(username, database_id) != Some(AdminRole::Edit) {
+            tracing::warn!(
+                "Operation '{}' blocked on database '{}': Admin '{}' has no edit grant",
+                operation,
+                database_id,
+                username
+            );
+            return Err(StatusCode::FORBIDDEN);
+        }
+    }
 
     // Check if database is completely disabled
     if config.is_database_disabled(database_id) {
@@ -414,7 +593,9 @@ pub fn check_database_restrictions(
             );
             return Err(StatusCode::FORBIDDEN);
         }
-        "create_domain" | "update_domain" if config.is_new_domains_blocked(database_id) => {
+        "create_domain" | "update_domain" | "create_alias_domain" | "update_alias_domain"
+            if config.is_new_domains_blocked(database_id) =>
+        {
             tracing::warn!(
                 "Operation '{}' blocked on database '{}': New domains are not allowed",
                 operation,
@@ -422,7 +603,7 @@ pub fn check_database_restrictions(
             );
             return Err(StatusCode::FORBIDDEN);
         }
-        "update_user" if config.is_password_updates_blocked(database_id) => {
+        "update_user" | "change_password" if config.is_password_updates_blocked(database_id) => {
             tracing::warn!(
                 "Operation '{}' blocked on database '{}': Password updates are not allowed",
                 operation,
@@ -438,7 +619,7 @@ pub fn check_database_restrictions(
 
 /// Check if the current database has any write restrictions
 pub fn get_database_restrictions_info(state: &AppState, database_id: &str) -> Vec<String> {
-    let config = &state.config;
+    let config = state.config.get();
     let mut restrictions = Vec::new();
 
     if config.is_database_disabled(database_id) {
@@ -0,0 +1,258 @@
+use crate::models::{Vacation, VacationForm};
+use crate::templates::vacation::VacationTemplate;
+use crate::{db, vacation, AppState, DbPool};
+use askama::Template;
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    response::Html,
+    Form,
+};
+
+const TRANSLATION_KEYS: &[&str] = &[
+    "vacation-title",
+    "vacation-status-none",
+    "vacation-status-active",
+    "vacation-status-inactive",
+    "vacation-subject",
+    "vacation-body",
+    "vacation-active-from",
+    "vacation-active-until",
+    "vacation-dates-hint",
+    "vacation-reply-interval",
+    "vacation-enabled",
+    "vacation-save",
+    "vacation-delete",
+    "vacation-delete-confirm",
+    "vacation-saved",
+];
+
+/// The form values for a stored vacation, or an empty enabled one
+fn form_for(vacation: Option<&Vacation>) -> VacationForm {
+    match vacation {
+        Some(vacation) => VacationForm {
+            subject: vacation.subject.clone(),
+            body: vacation.body.clone(),
+            active_from: vacation.active_from,
+            active_until: vacation.active_until,
+            reply_interval_days: vacation.reply_interval_days,
+            enabled: vacation.enabled,
+        },
+        None => VacationForm {
+            subject: String::new(),
+            body: String::new(),
+            active_from: None,
+            active_until: None,
+            reply_interval_days: vacation::DEFAULT_REPLY_INTERVAL_DAYS,
+            enabled: true,
+        },
+    }
+}
+
+/// The selected database, if mailbox `id` is there and within the admin's domain scope
+async fn user_pool(state: &AppState, headers: &HeaderMap, id: &str) -> Option<DbPool> {
+    let pool = crate::handlers::utils::get_current_db_pool(state, headers)
+        .await
+        .expect("Failed to get database pool");
+    let scope = crate::handlers::utils::get_address_scope(state, headers);
+    db::get_user_in_scope(&pool, id, &scope).ok()?;
+    Some(pool)
+}
+
+/// Whether the admin may change vacations on the selected database
+fn can_edit(state: &AppState, headers: &HeaderMap) -> bool {
+    let Some(session) = crate::handlers::auth::get_session(state, headers) else {
+        return false;
+    };
+    crate::handlers::utils::check_database_restrictions(
+        state,
+        Some(&session.username),
+        &session.database_id,
+        "update_vacation",
+    )
+    .is_ok()
+}
+
+async fn render(
+    state: &AppState,
+    headers: &HeaderMap,
+    user_id: &str,
+    stored: Option<&Vacation>,
+    form: &VacationForm,
+    message: Option<&str>,
+    error: Option<String>,
+) -> Html<String> {
+    let locale = crate::handlers::utils::get_user_locale(headers);
+    let translations =
+        crate::handlers::utils::get_translations_batch(state, &locale, TRANSLATION_KEYS).await;
+    let today = chrono::Local::now().date_naive();
+    let status = match stored {
+        None => "vacation-status-none",
+        Some(vacation) if vacation.is_active(today) => "vacation-status-active",
+        Some(_) => "vacation-status-inactive",
+    };
+    let message = message.map(|key| translations[key].as_str());
+
+    let template = VacationTemplate {
+        title: &translations["vacation-title"],
+        status: &translations[status],
+        subject: &translations["vacation-subject"],
+        body: &translations["vacation-body"],
+        active_from: &translations["vacation-active-from"],
+        active_until: &translations["vacation-active-until"],
+        dates_hint: &translations["vacation-dates-hint"],
+        reply_interval: &translations["vacation-reply-interval"],
+        enabled: &translations["vacation-enabled"],
+        save: &translations["vacation-save"],
+        delete: &translations["vacation-delete"],
+        delete_confirm: &translations["vacation-delete-confirm"],
+        user_id,
+        form,
+        exists: stored.is_some(),
+        can_edit: can_edit(state, headers),
+        message,
+        error: error.as_deref(),
+    };
+    match template.render() {
+        Ok(content) => Html(content),
+        Err(e) => {
+            tracing::error!("Failed to render vacation template: {:?}", e);
+            Html("Failed to render vacation".to_string())
+        }
+    }
+}
+
+pub async fn show(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Html<String> {
+    let Some(pool) = user_pool(&state, &headers, &id).await else {
+        return Html("User not found".to_string());
+    };
+    let stored = db::get_vacation(&pool, &id).unwrap_or_else(|e| {
+        tracing::error!("Failed to load vacation for {}: {:?}", id, e);
+        None
+    });
+    let form = form_for(stored.as_ref());
+    render(&state, &headers, &id, stored.as_ref(), &form, None, None).await
+}
+
+pub async fn save(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    Form(form): Form<VacationForm>,
+) -> Html<String> {
+    let Some(pool) = user_pool(&state, &headers, &id).await else {
+        return Html("User not found".to_string());
+    };
+    let locale = crate::handlers::utils::get_user_locale(&headers);
+    let stored = db::get_vacation(&pool, &id).ok().flatten();
+
+    let problem = if can_edit(&state, &headers) {
+        vacation::validate(&form).err()
+    } else {
+        Some("error-operation-not-allowed")
+    };
+    if let Some(key) = problem {
+        let error = crate::i18n::get_translation(&state, &locale, key).await;
+        return render(
+            &state,
+            &headers,
+            &id,
+            stored.as_ref(),
+            &form,
+            None,
+            Some(error),
+        )
+        .await;
+    }
+
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
+    let saved = match db::save_vacation(&pool, &id, form.clone(), &audit) {
+        Ok(saved) => saved,
+        Err(e) => {
+            tracing::error!("Failed to save vacation for {}: {:?}", id, e);
+            return render(
+                &state,
+                &headers,
+                &id,
+                stored.as_ref(),
+                &form,
+                None,
+                Some(format!("Error saving vacation: {e}")),
+            )
+            .await;
+        }
+    };
+
+    let today = chrono::Local::now().date_naive();
+    let config = state.config.get();
+    if let Err(e) = vacation::sync_user(
+        &pool,
+        config.vacation.transport_domain.as_deref(),
+        &id,
+        saved.is_active(today),
+        &audit,
+    ) {
+        tracing::error!("Failed to update vacation alias for {}: {:?}", id, e);
+    }
+
+    let form = form_for(Some(&saved));
+    render(
+        &state,
+        &headers,
+        &id,
+        Some(&saved),
+        &form,
+        Some("vacation-saved"),
+        None,
+    )
+    .await
+}
+
+pub async fn delete(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Html<String> {
+    let Some(pool) = user_pool(&state, &headers, &id).await else {
+        return Html("User not found".to_string());
+    };
+    let stored = db::get_vacation(&pool, &id).ok().flatten();
+    if !can_edit(&state, &headers) {
+        let locale = crate::handlers::utils::get_user_locale(&headers);
+        let error =
+            crate::i18n::get_translation(&state, &locale, "error-operation-not-allowed").await;
+        let form = form_for(stored.as_ref());
+        return render(
+            &state,
+            &headers,
+            &id,
+            stored.as_ref(),
+            &form,
+            None,
+            Some(error),
+        )
+        .await;
+    }
+
+    let audit = crate::handlers::utils::get_audit_context(&state, &headers);
+    if let Err(e) = db::delete_vacation(&pool, &id, &audit) {
+        tracing::error!("Failed to delete vacation for {}: {:?}", id, e);
+    }
+    let config = state.config.get();
+    if let Err(e) = vacation::sync_user(
+        &pool,
+        config.vacation.transport_domain.as_deref(),
+        &id,
+        false,
+        &audit,
+    ) {
+        tracing::error!("Failed to update vacation alias for {}: {:?}", id, e);
+    }
+
+    let form = form_for(None);
+    render(&state, &headers, &id, None, &form, None, None).await
+}
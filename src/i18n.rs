@@ -83,7 +83,7 @@ impl I18n {
 
         // Simple variable substitution: { $variable }
         for (key, value) in args {
-            let placeholder = format!("{{ ${{{key}}} }}");
+            let placeholder = format!("{{ ${key} }}");
             message = message.replace(&placeholder, &value);
         }
 
@@ -0,0 +1,1193 @@
+//! Bulk import of domains, users, aliases, relays, relocated entries and
+//! clients from CSV or TSV files
+//!
+//! An import is planned before anything is written. The header row is mapped
+//! to form fields, each row is turned into the same form the web UI submits
+//! and checked with the same rules, and the result is compared with the
+//! database. That gives a dry run of creates, updates, conflicts and errors.
+//! Applying writes the creates and updates of a file in one transaction (see
+//! [`crate::db::apply_import`]); rows with conflicts or errors are never
+//! written and make up the error report.
+//!
+//! Columns left out of the file keep their current values when a record is
+//! updated, and get the form defaults when one is created.
+
+use crate::db::{self, AddressScope};
+use crate::delivery::{self, DestinationError};
+use crate::models::*;
+use crate::password::{self, PasswordPolicy, PolicyViolation};
+use crate::quota;
+use crate::validity::{self, InvalidWindow};
+use crate::DbPool;
+use chrono::NaiveDateTime;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Form field names with their values as text
+pub type FieldValues = Vec<(&'static str, String)>;
+
+/// The records an import file holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportKind {
+    Domains,
+    Users,
+    Aliases,
+    Relays,
+    Relocated,
+    Clients,
+}
+
+impl ImportKind {
+    pub const ALL: [ImportKind; 6] = [
+        ImportKind::Domains,
+        ImportKind::Users,
+        ImportKind::Aliases,
+        ImportKind::Relays,
+        ImportKind::Relocated,
+        ImportKind::Clients,
+    ];
+
+    /// Name used in URLs, file names and on the command line
+    pub fn name(&self) -> &'static str {
+        match self {
+            ImportKind::Domains => "domains",
+            ImportKind::Users => "users",
+            ImportKind::Aliases => "aliases",
+            ImportKind::Relays => "relays",
+            ImportKind::Relocated => "relocated",
+            ImportKind::Clients => "clients",
+        }
+    }
+
+    /// The form fields a column can be mapped to, key first
+    pub fn fields(&self) -> &'static [&'static str] {
+        match self {
+            ImportKind::Domains => &[
+                "domain",
+                "transport",
+                "enabled",
+                "default_quota_bytes",
+                "default_quota_messages",
+                "quota_cap_bytes",
+            ],
+            ImportKind::Users => &[
+                "id",
+                "password",
+                "name",
+                "enabled",
+                "quota_bytes",
+                "quota_messages",
+            ],
+            ImportKind::Aliases => &[
+                "mail",
+                "destination",
+                "enabled",
+                "valid_from",
+                "valid_until",
+            ],
+            ImportKind::Relays => &["recipient", "status", "enabled"],
+            ImportKind::Relocated => &[
+                "old_address",
+                "new_address",
+                "enabled",
+                "valid_from",
+                "valid_until",
+            ],
+            ImportKind::Clients => &["client", "status", "enabled"],
+        }
+    }
+
+    /// The field that identifies an existing record
+    pub fn key_field(&self) -> &'static str {
+        self.fields()[0]
+    }
+
+    /// Other header names understood for a field, such as PostfixAdmin's `goto`
+    fn synonyms(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            ImportKind::Domains => &[
+                ("name", "domain"),
+                ("default_quota", "default_quota_bytes"),
+                ("quota", "default_quota_bytes"),
+                ("quota_cap", "quota_cap_bytes"),
+            ],
+            ImportKind::Users => &[
+                ("email", "id"),
+                ("address", "id"),
+                ("username", "id"),
+                ("mailbox", "id"),
+                ("full_name", "name"),
+                ("display_name", "name"),
+                ("quota", "quota_bytes"),
+            ],
+            ImportKind::Aliases => &[
+                ("address", "mail"),
+                ("alias", "mail"),
+                ("source", "mail"),
+                ("goto", "destination"),
+                ("destinations", "destination"),
+                ("forward_to", "destination"),
+            ],
+            ImportKind::Relays => &[("address", "recipient")],
+            ImportKind::Relocated => &[
+                ("old", "old_address"),
+                ("new", "new_address"),
+                ("from", "old_address"),
+                ("to", "new_address"),
+            ],
+            ImportKind::Clients => &[("ip", "client"), ("address", "client")],
+        }
+    }
+
+    /// The field a header names, if any
+    pub fn field_for_header(&self, header: &str) -> Option<&'static str> {
+        let name = header.trim().to_lowercase().replace([' ', '-'], "_");
+        if name == "active" {
+            return Some("enabled");
+        }
+        self.fields()
+            .iter()
+            .copied()
+            .find(|field| *field == name)
+            .or_else(|| {
+                self.synonyms()
+                    .iter()
+                    .find(|(synonym, _)| *synonym == name)
+                    .map(|(_, field)| *field)
+            })
+    }
+
+    /// The restriction checked before creating (or updating) a record
+    pub fn operation(&self, create: bool) -> &'static str {
+        match (self, create) {
+            (ImportKind::Domains, true) => "create_domain",
+            (ImportKind::Domains, false) => "update_domain",
+            (ImportKind::Users, true) => "create_user",
+            (ImportKind::Users, false) => "update_user",
+            (ImportKind::Aliases, true) => "create_alias",
+            (ImportKind::Aliases, false) => "update_alias",
+            (ImportKind::Relays, true) => "create_relay",
+            (ImportKind::Relays, false) => "update_relay",
+            (ImportKind::Relocated, true) => "create_relocated",
+            (ImportKind::Relocated, false) => "update_relocated",
+            (ImportKind::Clients, true) => "create_client",
+            (ImportKind::Clients, false) => "update_client",
+        }
+    }
+
+    /// Form values of a new record for fields the file leaves out
+    fn defaults(&self) -> FieldValues {
+        let mut values: FieldValues = self
+            .fields()
+            .iter()
+            .map(|field| (*field, String::new()))
+            .collect();
+        for (field, value) in values.iter_mut() {
+            match (*self, *field) {
+                (_, "enabled") => *value = "true".to_string(),
+                (ImportKind::Domains, "transport") => *value = "virtual".to_string(),
+                _ => {}
+            }
+        }
+        values
+    }
+}
+
+impl std::fmt::Display for ImportKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Why a file could not be read or mapped
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportError {
+    /// The file has no header row
+    Empty,
+    /// The file is not valid CSV
+    Csv(String),
+    /// A mapping names a header the file does not have
+    UnknownHeader(String),
+    /// A mapping names a field the record does not have
+    UnknownField(String),
+    /// No column is mapped to the key field
+    MissingKey(&'static str),
+    /// Two columns are mapped to the same field
+    DuplicateField(&'static str),
+}
+
+impl ImportError {
+    /// Fluent message ID, which takes the detail as `$detail`
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            ImportError::Empty => "import-error-empty",
+            ImportError::Csv(_) => "import-error-csv",
+            ImportError::UnknownHeader(_) => "import-error-unknown-header",
+            ImportError::UnknownField(_) => "import-error-unknown-field",
+            ImportError::MissingKey(_) => "import-error-missing-key",
+            ImportError::DuplicateField(_) => "import-error-duplicate-field",
+        }
+    }
+
+    pub fn detail(&self) -> &str {
+        match self {
+            ImportError::Empty => "",
+            ImportError::Csv(detail)
+            | ImportError::UnknownHeader(detail)
+            | ImportError::UnknownField(detail) => detail,
+            ImportError::MissingKey(field) | ImportError::DuplicateField(field) => field,
+        }
+    }
+}
+
+impl std::fmt::Display for ImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImportError::Empty => f.write_str("The file has no header row"),
+            ImportError::Csv(detail) => write!(f, "The file could not be read: {detail}"),
+            ImportError::UnknownHeader(header) => write!(f, "The file has no column '{header}'"),
+            ImportError::UnknownField(field) => write!(f, "Unknown field '{field}'"),
+            ImportError::MissingKey(field) => write!(f, "No column is mapped to '{field}'"),
+            ImportError::DuplicateField(field) => {
+                write!(f, "More than one column is mapped to '{field}'")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+/// A column of the file and the field it is imported into
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Column {
+    pub header: String,
+    pub field: Option<&'static str>,
+}
+
+#[derive(Debug, Clone)]
+struct SourceRow {
+    line: usize,
+    values: Vec<String>,
+}
+
+/// A parsed import file with its column mapping
+#[derive(Debug, Clone)]
+pub struct ImportFile {
+    pub kind: ImportKind,
+    pub columns: Vec<Column>,
+    rows: Vec<SourceRow>,
+}
+
+/// The field delimiter of a file, from its header row: tab, semicolon (as
+/// spreadsheets write CSV in many locales) or comma
+pub fn delimiter(content: &str) -> u8 {
+    let header = content.lines().next().unwrap_or_default();
+    if header.contains('\t') {
+        b'\t'
+    } else if header.contains(';') && !header.contains(',') {
+        b';'
+    } else {
+        b','
+    }
+}
+
+/// Parse a CSV or TSV file, mapping the columns by their headers
+pub fn read(kind: ImportKind, content: &str) -> Result<ImportFile, ImportError> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter(content))
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+
+    let headers = reader
+        .headers()
+        .map_err(|e| ImportError::Csv(e.to_string()))?
+        .clone();
+    if headers.iter().all(str::is_empty) {
+        return Err(ImportError::Empty);
+    }
+
+    let mut columns: Vec<Column> = Vec::new();
+    for header in headers.iter() {
+        let field = kind
+            .field_for_header(header)
+            .filter(|field| !columns.iter().any(|column| column.field == Some(field)));
+        columns.push(Column {
+            header: header.to_string(),
+            field,
+        });
+    }
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| ImportError::Csv(e.to_string()))?;
+        if record.iter().all(str::is_empty) {
+            continue;
+        }
+        rows.push(SourceRow {
+            line: record
+                .position()
+                .map_or(0, |position| position.line() as usize),
+            values: record.iter().map(str::to_string).collect(),
+        });
+    }
+
+    Ok(ImportFile {
+        kind,
+        columns,
+        rows,
+    })
+}
+
+impl ImportFile {
+    /// Number of data rows
+    pub fn row_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn field(&self, name: &str) -> Result<Option<&'static str>, ImportError> {
+        match name.trim() {
+            "" | "-" => Ok(None),
+            name => self
+                .kind
+                .fields()
+                .iter()
+                .copied()
+                .find(|field| *field == name)
+                .map(Some)
+                .ok_or_else(|| ImportError::UnknownField(name.to_string())),
+        }
+    }
+
+    /// Import the column with `header` into `field`; an empty field or `-` skips it
+    pub fn map_column(&mut self, header: &str, field: &str) -> Result<(), ImportError> {
+        let field = self.field(field)?;
+        let column = self
+            .columns
+            .iter_mut()
+            .find(|column| column.header.eq_ignore_ascii_case(header.trim()))
+            .ok_or_else(|| ImportError::UnknownHeader(header.to_string()))?;
+        column.field = field;
+        Ok(())
+    }
+
+    /// Replace the mapping of every column, in order, as the preview page submits it
+    pub fn set_mapping(&mut self, fields: &[String]) -> Result<(), ImportError> {
+        if fields.len() != self.columns.len() {
+            return Ok(());
+        }
+        for (index, name) in fields.iter().enumerate() {
+            self.columns[index].field = self.field(name)?;
+        }
+        Ok(())
+    }
+
+    /// Check that the key is mapped and no field is mapped twice
+    pub fn check_columns(&self) -> Result<(), ImportError> {
+        let mut seen = HashSet::new();
+        for field in self.columns.iter().filter_map(|column| column.field) {
+            if !seen.insert(field) {
+                return Err(ImportError::DuplicateField(field));
+            }
+        }
+        let key = self.kind.key_field();
+        if !seen.contains(key) {
+            return Err(ImportError::MissingKey(key));
+        }
+        Ok(())
+    }
+
+    /// The mapped values of a row; blank `enabled` cells are left out
+    fn values(&self, row: &SourceRow) -> FieldValues {
+        self.columns
+            .iter()
+            .zip(&row.values)
+            .filter_map(|(column, value)| column.field.map(|field| (field, value.clone())))
+            .filter(|(field, value)| *field != "enabled" || !value.is_empty())
+            .collect()
+    }
+}
+
+/// A problem with one row; the last two are conflicts, the rest errors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RowIssue {
+    /// A required field is empty
+    Missing(&'static str),
+    /// A value could not be parsed
+    Invalid(String),
+    Destination(DestinationError),
+    Window(InvalidWindow),
+    Password(PolicyViolation),
+    /// A new user needs a password
+    PasswordRequired,
+    /// The address is outside the domains the admin manages
+    OutOfScope,
+    /// The database does not allow the create or update
+    NotAllowed,
+    /// The same key appeared earlier in the file, on this line
+    Duplicate(usize),
+    /// The record exists and updates were not requested
+    Exists,
+}
+
+impl RowIssue {
+    pub fn is_conflict(&self) -> bool {
+        matches!(self, RowIssue::Duplicate(_) | RowIssue::Exists)
+    }
+
+    /// Fluent message ID of the problem; see [`RowIssue::message_args`]
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            RowIssue::Missing(_) => "import-error-missing",
+            RowIssue::Invalid(_) => "import-error-invalid",
+            RowIssue::Destination(error) => error.message_id(),
+            RowIssue::Window(error) => error.message_id(),
+            RowIssue::Password(violation) => violation.message_id(),
+            RowIssue::PasswordRequired => "import-error-password-required",
+            RowIssue::OutOfScope => "import-error-out-of-scope",
+            RowIssue::NotAllowed => "error-operation-not-allowed",
+            RowIssue::Duplicate(_) => "import-conflict-duplicate",
+            RowIssue::Exists => "import-conflict-exists",
+        }
+    }
+
+    /// Arguments of the message: `$field`, `$detail`, `$address`, `$min` or `$line`
+    pub fn message_args(&self) -> HashMap<String, String> {
+        let (name, value) = match self {
+            RowIssue::Missing(field) => ("field", field.to_string()),
+            RowIssue::Invalid(detail) => ("detail", detail.clone()),
+            RowIssue::Destination(error) => ("address", error.address().to_string()),
+            RowIssue::Password(PolicyViolation::TooShort(min)) => ("min", min.to_string()),
+            RowIssue::Duplicate(line) => ("line", line.to_string()),
+            _ => return HashMap::new(),
+        };
+        HashMap::from([(name.to_string(), value)])
+    }
+}
+
+impl std::fmt::Display for RowIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RowIssue::Missing(field) => write!(f, "The {field} column is empty"),
+            RowIssue::Invalid(detail) => write!(f, "Invalid value: {detail}"),
+            RowIssue::Destination(error) => write!(f, "{error}"),
+            RowIssue::Window(error) => write!(f, "{error}"),
+            RowIssue::Password(violation) => write!(f, "{violation}"),
+            RowIssue::PasswordRequired => f.write_str("A password is required for new users"),
+            RowIssue::OutOfScope => f.write_str("The address is outside the domains you manage"),
+            RowIssue::NotAllowed => f.write_str("The database does not allow this change"),
+            RowIssue::Duplicate(line) => write!(f, "Already in the file on line {line}"),
+            RowIssue::Exists => f.write_str("Already exists and updates were not requested"),
+        }
+    }
+}
+
+/// What applying the import does with a row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RowStatus {
+    Create,
+    Update,
+    Unchanged,
+    Conflict,
+    Error,
+}
+
+impl RowStatus {
+    pub fn name(&self) -> &'static str {
+        match self {
+            RowStatus::Create => "create",
+            RowStatus::Update => "update",
+            RowStatus::Unchanged => "unchanged",
+            RowStatus::Conflict => "conflict",
+            RowStatus::Error => "error",
+        }
+    }
+
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            RowStatus::Create => "import-status-create",
+            RowStatus::Update => "import-status-update",
+            RowStatus::Unchanged => "import-status-unchanged",
+            RowStatus::Conflict => "import-status-conflict",
+            RowStatus::Error => "import-status-error",
+        }
+    }
+
+    /// Rows left out when the import is applied, listed in the error report
+    pub fn is_problem(&self) -> bool {
+        matches!(self, RowStatus::Conflict | RowStatus::Error)
+    }
+}
+
+/// A field an update changes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldChange {
+    pub field: &'static str,
+    pub old: String,
+    pub new: String,
+}
+
+impl std::fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} → {}", self.field, self.old, self.new)
+    }
+}
+
+/// A record an import writes, with the existing record it replaces
+#[derive(Debug, Clone)]
+pub enum ImportWrite {
+    CreateDomain(DomainForm),
+    UpdateDomain(i32, DomainForm),
+    CreateUser(UserForm),
+    UpdateUser(String, UserForm),
+    CreateAlias(AliasForm),
+    UpdateAlias(i32, AliasForm),
+    CreateRelay(RelayForm),
+    UpdateRelay(i32, RelayForm),
+    CreateRelocated(RelocatedForm),
+    UpdateRelocated(i32, RelocatedForm),
+    CreateClient(ClientForm),
+    UpdateClient(i32, ClientForm),
+}
+
+/// A write that failed while applying an import; nothing of the file was written
+#[derive(Debug)]
+pub struct ImportFailure {
+    /// Line of the file whose write failed, if the failure belongs to one
+    pub line: Option<usize>,
+    pub error: diesel::result::Error,
+}
+
+impl From<diesel::result::Error> for ImportFailure {
+    fn from(error: diesel::result::Error) -> Self {
+        ImportFailure { line: None, error }
+    }
+}
+
+impl std::fmt::Display for ImportFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "Line {line}: {}", self.error),
+            None => write!(f, "{}", self.error),
+        }
+    }
+}
+
+impl std::error::Error for ImportFailure {}
+
+/// How an existing record is addressed for updates
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordId {
+    Pkid(i32),
+    User(String),
+}
+
+/// A record already in the database, as form values
+pub trait ImportSource {
+    fn key(&self) -> &str;
+    fn record_id(&self) -> RecordId;
+    fn values(&self) -> FieldValues;
+}
+
+fn enabled_value(enabled: bool) -> String {
+    enabled.to_string()
+}
+
+fn size_value(bytes: Option<i64>) -> String {
+    bytes.map(quota::format_size).unwrap_or_default()
+}
+
+fn count_value(count: Option<i64>) -> String {
+    count.map(|count| count.to_string()).unwrap_or_default()
+}
+
+fn datetime_value(value: Option<NaiveDateTime>) -> String {
+    value
+        .map(|value| value.format("%Y-%m-%dT%H:%M:%S").to_string())
+        .unwrap_or_default()
+}
+
+impl ImportSource for Domain {
+    fn key(&self) -> &str {
+        &self.domain
+    }
+
+    fn record_id(&self) -> RecordId {
+        RecordId::Pkid(self.pkid)
+    }
+
+    fn values(&self) -> FieldValues {
+        vec![
+            ("domain", self.domain.clone()),
+            ("transport", self.transport.clone().unwrap_or_default()),
+            ("enabled", enabled_value(self.enabled)),
+            ("default_quota_bytes", size_value(self.default_quota_bytes)),
+            (
+                "default_quota_messages",
+                count_value(self.default_quota_messages),
+            ),
+            ("quota_cap_bytes", size_value(self.quota_cap_bytes)),
+        ]
+    }
+}
+
+impl ImportSource for User {
+    fn key(&self) -> &str {
+        &self.id
+    }
+
+    fn record_id(&self) -> RecordId {
+        RecordId::User(self.id.clone())
+    }
+
+    fn values(&self) -> FieldValues {
+        vec![
+            ("id", self.id.clone()),
+            ("password", String::new()),
+            ("name", self.name.clone()),
+            ("enabled", enabled_value(self.enabled)),
+            ("change_password", enabled_value(self.change_password)),
+            ("quota_bytes", size_value(self.quota_bytes)),
+            ("quota_messages", count_value(self.quota_messages)),
+        ]
+    }
+}
+
+impl ImportSource for Alias {
+    fn key(&self) -> &str {
+        &self.mail
+    }
+
+    fn record_id(&self) -> RecordId {
+        RecordId::Pkid(self.pkid)
+    }
+
+    fn values(&self) -> FieldValues {
+        vec![
+            ("mail", self.mail.clone()),
+            ("destination", self.destination.clone()),
+            ("enabled", enabled_value(self.enabled)),
+            ("valid_from", datetime_value(self.valid_from)),
+            ("valid_until", datetime_value(self.valid_until)),
+        ]
+    }
+}
+
+impl ImportSource for Relay {
+    fn key(&self) -> &str {
+        &self.recipient
+    }
+
+    fn record_id(&self) -> RecordId {
+        RecordId::Pkid(self.pkid)
+    }
+
+    fn values(&self) -> FieldValues {
+        vec![
+            ("recipient", self.recipient.clone()),
+            ("status", self.status.clone()),
+            ("enabled", enabled_value(self.enabled)),
+        ]
+    }
+}
+
+impl ImportSource for Relocated {
+    fn key(&self) -> &str {
+        &self.old_address
+    }
+
+    fn record_id(&self) -> RecordId {
+        RecordId::Pkid(self.pkid)
+    }
+
+    fn values(&self) -> FieldValues {
+        vec![
+            ("old_address", self.old_address.clone()),
+            ("new_address", self.new_address.clone()),
+            ("enabled", enabled_value(self.enabled)),
+            ("valid_from", datetime_value(self.valid_from)),
+            ("valid_until", datetime_value(self.valid_until)),
+        ]
+    }
+}
+
+impl ImportSource for Client {
+    fn key(&self) -> &str {
+        &self.client
+    }
+
+    fn record_id(&self) -> RecordId {
+        RecordId::Pkid(self.id)
+    }
+
+    fn values(&self) -> FieldValues {
+        vec![
+            ("client", self.client.clone()),
+            ("status", self.status.clone()),
+            ("enabled", enabled_value(self.enabled)),
+        ]
+    }
+}
+
+/// Options of an import that come from the admin and the database config
+pub struct ImportOptions<'a> {
+    /// Update records that already exist instead of reporting them as conflicts
+    pub update_existing: bool,
+    pub scope: &'a AddressScope,
+    pub policy: &'a PasswordPolicy,
+    /// Whether the database allows creating records of this kind
+    pub can_create: bool,
+    /// Whether the database allows updating records of this kind
+    pub can_update: bool,
+}
+
+/// The records of one kind already in the database, by lower-cased key
+#[derive(Debug, Default)]
+pub struct Existing {
+    records: HashMap<String, (RecordId, FieldValues)>,
+}
+
+impl Existing {
+    pub fn new<T: ImportSource>(records: &[T]) -> Self {
+        Existing {
+            records: records
+                .iter()
+                .map(|record| {
+                    (
+                        record.key().to_lowercase(),
+                        (record.record_id(), record.values()),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Load the records an import file of `kind` is compared with
+    pub fn load(pool: &DbPool, kind: ImportKind) -> Result<Self, diesel::result::Error> {
+        Ok(match kind {
+            ImportKind::Domains => Existing::new(&db::get_domains(pool)?),
+            ImportKind::Users => Existing::new(&db::get_users(pool)?),
+            ImportKind::Aliases => Existing::new(&db::get_aliases(pool)?),
+            ImportKind::Relays => Existing::new(&db::get_relays(pool)?),
+            ImportKind::Relocated => Existing::new(&db::get_relocated(pool)?),
+            ImportKind::Clients => Existing::new(&db::get_clients(pool)?),
+        })
+    }
+}
+
+/// A row of the file and what applying the import does with it
+#[derive(Debug, Clone)]
+pub struct PlannedRow {
+    pub line: usize,
+    pub key: String,
+    pub status: RowStatus,
+    /// Fields an update changes; empty for other rows
+    pub changes: Vec<FieldChange>,
+    pub issues: Vec<RowIssue>,
+    write: Option<ImportWrite>,
+}
+
+/// The dry run of an import file
+#[derive(Debug, Clone)]
+pub struct ImportPlan {
+    pub kind: ImportKind,
+    pub rows: Vec<PlannedRow>,
+}
+
+impl ImportPlan {
+    /// Number of rows with `status`
+    pub fn count(&self, status: RowStatus) -> usize {
+        self.rows.iter().filter(|row| row.status == status).count()
+    }
+
+    /// The rows left out when the import is applied
+    pub fn problems(&self) -> impl Iterator<Item = &PlannedRow> {
+        self.rows.iter().filter(|row| row.status.is_problem())
+    }
+
+    /// The creates and updates, with the line each comes from
+    pub fn into_writes(self) -> Vec<(usize, ImportWrite)> {
+        self.rows
+            .into_iter()
+            .filter_map(|row| row.write.map(|write| (row.line, write)))
+            .collect()
+    }
+}
+
+/// Plan an import against the database
+pub fn plan(
+    pool: &DbPool,
+    file: &ImportFile,
+    options: &ImportOptions,
+) -> Result<ImportPlan, diesel::result::Error> {
+    let existing = Existing::load(pool, file.kind)?;
+    Ok(plan_with(file, &existing, options))
+}
+
+/// Plan an import against records already loaded
+pub fn plan_with(file: &ImportFile, existing: &Existing, options: &ImportOptions) -> ImportPlan {
+    let key_column = file
+        .columns
+        .iter()
+        .position(|column| column.field == Some(file.kind.key_field()));
+    let mut seen: HashMap<String, usize> = HashMap::new();
+
+    let rows = file
+        .rows
+        .iter()
+        .map(|row| {
+            let raw_key = key_column
+                .and_then(|index| row.values.get(index))
+                .cloned()
+                .unwrap_or_default();
+            let mut planned = plan_row(file, row, raw_key.trim(), existing, options);
+            if !planned.key.is_empty() {
+                let key = planned.key.to_lowercase();
+                if let Some(first) = seen.get(&key) {
+                    planned.issues.insert(0, RowIssue::Duplicate(*first));
+                    planned.status = RowStatus::Conflict;
+                    planned.write = None;
+                } else {
+                    seen.insert(key, row.line);
+                }
+            }
+            planned
+        })
+        .collect();
+
+    ImportPlan {
+        kind: file.kind,
+        rows,
+    }
+}
+
+fn plan_row(
+    file: &ImportFile,
+    row: &SourceRow,
+    raw_key: &str,
+    existing: &Existing,
+    options: &ImportOptions,
+) -> PlannedRow {
+    let kind = file.kind;
+    let mut planned = PlannedRow {
+        line: row.line,
+        key: raw_key.to_string(),
+        status: RowStatus::Error,
+        changes: Vec::new(),
+        issues: Vec::new(),
+        write: None,
+    };
+    if raw_key.is_empty() {
+        planned.issues.push(RowIssue::Missing(kind.key_field()));
+        return planned;
+    }
+
+    let current = existing.records.get(&raw_key.to_lowercase());
+    let mut values: BTreeMap<&'static str, String> = match current {
+        Some((_, values)) => values.iter().cloned().collect(),
+        None => kind.defaults().into_iter().collect(),
+    };
+    for (field, value) in file.values(row) {
+        if field == "enabled" {
+            match parse_flag(&value) {
+                Some(flag) => values.insert(field, flag.to_string()),
+                None => {
+                    planned
+                        .issues
+                        .push(RowIssue::Invalid(format!("enabled: {value}")));
+                    continue;
+                }
+            };
+        } else {
+            values.insert(field, value);
+        }
+    }
+
+    let write = match build(kind, &values, current.is_none(), options) {
+        Ok(write) => write,
+        Err(issues) => {
+            planned.issues.extend(issues);
+            return planned;
+        }
+    };
+    planned.key = write.key().to_string();
+    if !planned.issues.is_empty() {
+        return planned;
+    }
+
+    let Some((id, old_values)) = current else {
+        if !options.can_create {
+            planned.issues.push(RowIssue::NotAllowed);
+            return planned;
+        }
+        planned.status = RowStatus::Create;
+        planned.write = Some(write);
+        return planned;
+    };
+
+    // Compare the forms rather than the raw text, so `2G` and `2147483648` match
+    let old_values = old_values.iter().cloned().collect();
+    let before = match build(kind, &old_values, false, options) {
+        Ok(before) => before.values(),
+        Err(_) => old_values.into_iter().collect(),
+    };
+    planned.changes = write
+        .values()
+        .into_iter()
+        .zip(before)
+        .filter(|((_, new), (_, old))| new != old)
+        .map(|((field, new), (_, old))| FieldChange { field, old, new })
+        .collect();
+
+    if planned.changes.is_empty() {
+        planned.status = RowStatus::Unchanged;
+    } else if !options.update_existing {
+        planned.status = RowStatus::Conflict;
+        planned.issues.push(RowIssue::Exists);
+    } else if !options.can_update {
+        planned.issues.push(RowIssue::NotAllowed);
+    } else {
+        planned.status = RowStatus::Update;
+        planned.write = Some(write.into_update(id.clone()));
+    }
+    planned
+}
+
+/// Read a yes/no cell as spreadsheets and other systems write it
+fn parse_flag(value: &str) -> Option<bool> {
+    match value.to_lowercase().as_str() {
+        "1" | "true" | "yes" | "y" | "on" | "x" => Some(true),
+        "0" | "false" | "no" | "n" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+/// Deserialize form values the way the web form submission is
+fn form<T: DeserializeOwned>(values: &BTreeMap<&'static str, String>) -> Result<T, Vec<RowIssue>> {
+    let encoded =
+        serde_urlencoded::to_string(values).map_err(|e| vec![RowIssue::Invalid(e.to_string())])?;
+    serde_urlencoded::from_str(&encoded).map_err(|e| vec![RowIssue::Invalid(e.to_string())])
+}
+
+fn required(issues: &mut Vec<RowIssue>, field: &'static str, value: &str) {
+    if value.trim().is_empty() {
+        issues.push(RowIssue::Missing(field));
+    }
+}
+
+fn window(issues: &mut Vec<RowIssue>, from: Option<NaiveDateTime>, until: Option<NaiveDateTime>) {
+    if let Err(e) = validity::check_window(from, until) {
+        issues.push(RowIssue::Window(e));
+    }
+}
+
+/// Turn the values of a row into a form and check it as the web UI does
+fn build(
+    kind: ImportKind,
+    values: &BTreeMap<&'static str, String>,
+    create: bool,
+    options: &ImportOptions,
+) -> Result<ImportWrite, Vec<RowIssue>> {
+    let mut issues = Vec::new();
+    let write = match kind {
+        ImportKind::Domains => {
+            let mut form: DomainForm = form(values)?;
+            form.domain = form.domain.trim().to_string();
+            required(&mut issues, "domain", &form.domain);
+            ImportWrite::CreateDomain(form)
+        }
+        ImportKind::Users => {
+            let mut form: UserForm = form(values)?;
+            form.id = form.id.trim().to_string();
+            required(&mut issues, "id", &form.id);
+            if !options.scope.allows(&form.id) {
+                issues.push(RowIssue::OutOfScope);
+            }
+            if form.password.is_empty() {
+                if create {
+                    issues.push(RowIssue::PasswordRequired);
+                }
+            } else {
+                issues.extend(
+                    password::check_policy(options.policy, &form.password, &form.id, &form.name)
+                        .into_iter()
+                        .map(RowIssue::Password),
+                );
+            }
+            ImportWrite::CreateUser(form)
+        }
+        ImportKind::Aliases => {
+            let form: AliasForm = form::<AliasForm>(values)?.normalized();
+            required(&mut issues, "mail", &form.mail);
+            if !options.scope.allows(&form.mail) {
+                issues.push(RowIssue::OutOfScope);
+            }
+            if let Err(e) = delivery::check_destinations(&form.destinations()) {
+                issues.push(RowIssue::Destination(e));
+            }
+            window(&mut issues, form.valid_from, form.valid_until);
+            ImportWrite::CreateAlias(form)
+        }
+        ImportKind::Relays => {
+            let form: RelayForm = form(values)?;
+            required(&mut issues, "recipient", &form.recipient);
+            required(&mut issues, "status", &form.status);
+            ImportWrite::CreateRelay(form)
+        }
+        ImportKind::Relocated => {
+            let form: RelocatedForm = form(values)?;
+            required(&mut issues, "old_address", &form.old_address);
+            required(&mut issues, "new_address", &form.new_address);
+            window(&mut issues, form.valid_from, form.valid_until);
+            ImportWrite::CreateRelocated(form)
+        }
+        ImportKind::Clients => {
+            let form: ClientForm = form(values)?;
+            required(&mut issues, "client", &form.client);
+            required(&mut issues, "status", &form.status);
+            ImportWrite::CreateClient(form)
+        }
+    };
+    if issues.is_empty() {
+        Ok(write)
+    } else {
+        Err(issues)
+    }
+}
+
+impl ImportWrite {
+    /// The key of the record written
+    pub fn key(&self) -> &str {
+        match self {
+            ImportWrite::CreateDomain(form) | ImportWrite::UpdateDomain(_, form) => &form.domain,
+            ImportWrite::CreateUser(form) | ImportWrite::UpdateUser(_, form) => &form.id,
+            ImportWrite::CreateAlias(form) | ImportWrite::UpdateAlias(_, form) => &form.mail,
+            ImportWrite::CreateRelay(form) | ImportWrite::UpdateRelay(_, form) => &form.recipient,
+            ImportWrite::CreateRelocated(form) | ImportWrite::UpdateRelocated(_, form) => {
+                &form.old_address
+            }
+            ImportWrite::CreateClient(form) | ImportWrite::UpdateClient(_, form) => &form.client,
+        }
+    }
+
+    /// Turn a create into an update of the record `id`
+    fn into_update(self, id: RecordId) -> Self {
+        match (self, id) {
+            (ImportWrite::CreateDomain(form), RecordId::Pkid(pkid)) => {
+                ImportWrite::UpdateDomain(pkid, form)
+            }
+            (ImportWrite::CreateUser(form), RecordId::User(user_id)) => {
+                ImportWrite::UpdateUser(user_id, form)
+            }
+            (ImportWrite::CreateAlias(form), RecordId::Pkid(pkid)) => {
+                ImportWrite::UpdateAlias(pkid, form)
+            }
+            (ImportWrite::CreateRelay(form), RecordId::Pkid(pkid)) => {
+                ImportWrite::UpdateRelay(pkid, form)
+            }
+            (ImportWrite::CreateRelocated(form), RecordId::Pkid(pkid)) => {
+                ImportWrite::UpdateRelocated(pkid, form)
+            }
+            (ImportWrite::CreateClient(form), RecordId::Pkid(pkid)) => {
+                ImportWrite::UpdateClient(pkid, form)
+            }
+            (write, _) => write,
+        }
+    }
+
+    /// The importable fields as text, in the order of [`ImportKind::fields`]
+    fn values(&self) -> FieldValues {
+        match self {
+            ImportWrite::CreateDomain(form) | ImportWrite::UpdateDomain(_, form) => vec![
+                ("domain", form.domain.clone()),
+                ("transport", form.transport.clone()),
+                ("enabled", enabled_value(form.enabled)),
+                ("default_quota_bytes", size_value(form.default_quota_bytes)),
+                (
+                    "default_quota_messages",
+                    count_value(form.default_quota_messages),
+                ),
+                ("quota_cap_bytes", size_value(form.quota_cap_bytes)),
+            ],
+            ImportWrite::CreateUser(form) | ImportWrite::UpdateUser(_, form) => vec![
+                ("id", form.id.clone()),
+                (
+                    "password",
+                    if form.password.is_empty() {
+                        String::new()
+                    } else {
+                        "********".to_string()
+                    },
+                ),
+                ("name", form.name.clone()),
+                ("enabled", enabled_value(form.enabled)),
+                ("quota_bytes", size_value(form.quota_bytes)),
+                ("quota_messages", count_value(form.quota_messages)),
+            ],
+            ImportWrite::CreateAlias(form) | ImportWrite::UpdateAlias(_, form) => vec![
+                ("mail", form.mail.clone()),
+                ("destination", form.destination.clone()),
+                ("enabled", enabled_value(form.enabled)),
+                ("valid_from", datetime_value(form.valid_from)),
+                ("valid_until", datetime_value(form.valid_until)),
+            ],
+            ImportWrite::CreateRelay(form) | ImportWrite::UpdateRelay(_, form) => vec![
+                ("recipient", form.recipient.clone()),
+                ("status", form.status.clone()),
+                ("enabled", enabled_value(form.enabled)),
+            ],
+            ImportWrite::CreateRelocated(form) | ImportWrite::UpdateRelocated(_, form) => vec![
+                ("old_address", form.old_address.clone()),
+                ("new_address", form.new_address.clone()),
+                ("enabled", enabled_value(form.enabled)),
+                ("valid_from", datetime_value(form.valid_from)),
+                ("valid_until", datetime_value(form.valid_until)),
+            ],
+            ImportWrite::CreateClient(form) | ImportWrite::UpdateClient(_, form) => vec![
+                ("client", form.client.clone()),
+                ("status", form.status.clone()),
+                ("enabled", enabled_value(form.enabled)),
+            ],
+        }
+    }
+}
+
+/// A line of the dry run or error report
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReportRow {
+    pub line: usize,
+    pub key: String,
+    pub status: &'static str,
+    pub message: String,
+}
+
+impl ReportRow {
+    /// The row with its issues, or its changes, as English text
+    pub fn from_planned(row: &PlannedRow) -> Self {
+        let details: Vec<String> = if row.issues.is_empty() {
+            row.changes.iter().map(ToString::to_string).collect()
+        } else {
+            row.issues.iter().map(ToString::to_string).collect()
+        };
+        ReportRow {
+            line: row.line,
+            key: row.key.clone(),
+            status: row.status.name(),
+            message: details.join("; "),
+        }
+    }
+}
+
+/// The report as a CSV file that can be fixed up and imported again
+pub fn report_csv(rows: &[ReportRow]) -> String {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for row in rows {
+        // Writing to a Vec cannot fail
+        let _ = writer.serialize(row);
+    }
+    String::from_utf8(writer.into_inner().unwrap_or_default()).unwrap_or_default()
+}
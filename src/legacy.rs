@@ -0,0 +1,914 @@
+//! Converting PostfixAdmin and ISPmail databases into export bundles
+//!
+//! Servers taken over from other tools keep their mail data in PostfixAdmin's
+//! `domain`, `mailbox`, `alias`, `alias_domain` and `vacation` tables or in the
+//! `virtual_domains`, `virtual_users` and `virtual_aliases` tables of the
+//! ISPmail guides. Their rows are read from the source database, which is only
+//! ever queried (see [`crate::db::read_legacy_tables`]), or from a `mysqldump`
+//! of it, and converted into a [`Bundle`] that is restored like any other.
+//!
+//! Password hashes are given the `{SCHEME}` prefix Dovecot needs to read them
+//! whatever its `default_pass_scheme`. Every value that has no counterpart in
+//! Sorting Office is reported as a [`LegacyNote`] rather than dropped silently.
+
+use crate::bundle::{
+    Bundle, BundleAlias, BundleAliasDomain, BundleBackup, BundleDomain, BundleUser, BundleVacation,
+};
+use crate::delivery::split_destinations;
+use crate::password;
+use crate::vacation::{DEFAULT_REPLY_INTERVAL_DAYS, MAX_REPLY_INTERVAL_DAYS};
+use chrono::{Datelike, NaiveDate};
+use std::collections::HashMap;
+
+/// A source row as lower-cased column names and their values as text
+pub type Row = HashMap<String, Option<String>>;
+
+/// The rows of each source table, by lower-cased table name
+pub type Tables = HashMap<String, Vec<Row>>;
+
+/// PostfixAdmin stores domain quotas in megabytes of this many bytes, its
+/// default `quota_multiplier`
+const QUOTA_MULTIPLIER: i64 = 1_024_000;
+
+/// The layout of the source database
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum LegacySchema {
+    /// PostfixAdmin 2.x and 3.x
+    #[default]
+    #[value(name = "postfixadmin")]
+    PostfixAdmin,
+    /// The `virtual_*` tables of the ISPmail guides
+    #[value(name = "ispmail")]
+    Ispmail,
+}
+
+impl LegacySchema {
+    pub fn name(&self) -> &'static str {
+        match self {
+            LegacySchema::PostfixAdmin => "postfixadmin",
+            LegacySchema::Ispmail => "ispmail",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LegacySchema::PostfixAdmin => "PostfixAdmin",
+            LegacySchema::Ispmail => "ISPmail",
+        }
+    }
+
+    /// The source tables that are converted
+    pub fn tables(&self) -> &'static [&'static str] {
+        match self {
+            LegacySchema::PostfixAdmin => {
+                &["domain", "mailbox", "alias", "alias_domain", "vacation"]
+            }
+            LegacySchema::Ispmail => &["virtual_domains", "virtual_users", "virtual_aliases"],
+        }
+    }
+}
+
+/// Why the source could not be read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LegacyError {
+    /// The source database refused the connection
+    Connect(String),
+    /// A query against the source database failed
+    Query(String),
+    /// The dump is not one we can read
+    Dump(String),
+}
+
+impl LegacyError {
+    /// Fluent message ID, which takes the detail as `$detail`
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            LegacyError::Connect(_) => "legacy-error-connect",
+            LegacyError::Query(_) => "legacy-error-query",
+            LegacyError::Dump(_) => "legacy-error-dump",
+        }
+    }
+
+    pub fn detail(&self) -> &str {
+        match self {
+            LegacyError::Connect(detail)
+            | LegacyError::Query(detail)
+            | LegacyError::Dump(detail) => detail,
+        }
+    }
+}
+
+impl std::fmt::Display for LegacyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LegacyError::Connect(detail) => {
+                write!(f, "Could not connect to the source database: {detail}")
+            }
+            LegacyError::Query(detail) => write!(f, "Could not read the source database: {detail}"),
+            LegacyError::Dump(detail) => write!(f, "Could not read the dump: {detail}"),
+        }
+    }
+}
+
+impl std::error::Error for LegacyError {}
+
+/// Something in the source that was not converted as it stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LegacyIssue {
+    /// The source has no such table
+    MissingTable,
+    /// A column with a value that has no counterpart
+    DroppedField,
+    /// An alias that forwards nowhere
+    NoDestination,
+    /// A PostfixAdmin vacation address, which Sorting Office adds itself
+    AutoreplyDestination,
+    /// A password hash that does not name its scheme, copied unchanged
+    UnknownHash,
+}
+
+impl LegacyIssue {
+    /// Fluent message ID of the explanation
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            LegacyIssue::MissingTable => "legacy-note-missing-table",
+            LegacyIssue::DroppedField => "legacy-note-dropped-field",
+            LegacyIssue::NoDestination => "legacy-note-no-destination",
+            LegacyIssue::AutoreplyDestination => "legacy-note-autoreply",
+            LegacyIssue::UnknownHash => "legacy-note-unknown-hash",
+        }
+    }
+}
+
+impl std::fmt::Display for LegacyIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            LegacyIssue::MissingTable => "table not found",
+            LegacyIssue::DroppedField => "value not converted",
+            LegacyIssue::NoDestination => "alias without destinations not converted",
+            LegacyIssue::AutoreplyDestination => "vacation destination removed",
+            LegacyIssue::UnknownHash => "password hash of unknown scheme copied unchanged",
+        })
+    }
+}
+
+/// A report of something in the source that was not converted as it stands
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LegacyNote {
+    /// The source table
+    pub table: String,
+    /// The natural key of the source row, empty for a whole table
+    pub key: String,
+    pub issue: LegacyIssue,
+    /// The value concerned, e.g. `phone = 555-0100`
+    pub detail: String,
+}
+
+impl std::fmt::Display for LegacyNote {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.table)?;
+        if !self.key.is_empty() {
+            write!(f, " {}", self.key)?;
+        }
+        write!(f, ": {}", self.issue)?;
+        if !self.detail.is_empty() {
+            write!(f, " ({})", self.detail)?;
+        }
+        Ok(())
+    }
+}
+
+/// Values the source does not store, given to every converted user
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LegacyOptions {
+    /// The mail home that the users' maildirs are relative to
+    pub home: String,
+    pub uid: u16,
+    pub gid: u16,
+}
+
+impl Default for LegacyOptions {
+    fn default() -> Self {
+        LegacyOptions {
+            home: "/var/vmail".to_string(),
+            uid: 5000,
+            gid: 5000,
+        }
+    }
+}
+
+/// The bundle converted from a source and what could not be converted
+#[derive(Debug, Clone)]
+pub struct Conversion {
+    pub bundle: Bundle,
+    pub notes: Vec<LegacyNote>,
+}
+
+// Dump reading
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// A keyword, unquoted identifier or number
+    Word(String),
+    /// A backtick-quoted identifier
+    Name(String),
+    /// A quoted string
+    Text(String),
+    Symbol(char),
+}
+
+/// Split a dump into statements of tokens, leaving out comments
+fn tokenize(content: &str) -> Result<Vec<Vec<Token>>, LegacyError> {
+    let chars: Vec<char> = content.chars().collect();
+    let mut statements = Vec::new();
+    let mut statement = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '#'
+            || (c == '-' && next == Some('-') && chars.get(i + 2).is_none_or(|c| c.is_whitespace()))
+        {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c == '/' && next == Some('*') {
+            // Including MySQL's `/*!40101 ... */` conditional statements
+            let end = (i + 2..chars.len().saturating_sub(1))
+                .find(|&j| chars[j] == '*' && chars[j + 1] == '/')
+                .ok_or_else(|| LegacyError::Dump("unterminated comment".to_string()))?;
+            i = end + 2;
+        } else if c == '\'' || c == '"' || c == '`' {
+            let mut text = String::new();
+            i += 1;
+            loop {
+                let Some(&ch) = chars.get(i) else {
+                    return Err(LegacyError::Dump("unterminated string".to_string()));
+                };
+                if ch == c {
+                    if chars.get(i + 1) == Some(&c) {
+                        text.push(c);
+                        i += 2;
+                        continue;
+                    }
+                    i += 1;
+                    break;
+                }
+                if ch == '\\' && c != '`' {
+                    let Some(&escaped) = chars.get(i + 1) else {
+                        return Err(LegacyError::Dump("unterminated string".to_string()));
+                    };
+                    text.push(match escaped {
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        '0' => '\0',
+                        'b' => '\u{8}',
+                        'Z' => '\u{1a}',
+                        other => other,
+                    });
+                    i += 2;
+                    continue;
+                }
+                text.push(ch);
+                i += 1;
+            }
+            statement.push(if c == '`' {
+                Token::Name(text)
+            } else {
+                Token::Text(text)
+            });
+        } else if c == ';' {
+            if !statement.is_empty() {
+                statements.push(std::mem::take(&mut statement));
+            }
+            i += 1;
+        } else if "(),=".contains(c) {
+            statement.push(Token::Symbol(c));
+            i += 1;
+        } else {
+            let start = i;
+            while i < chars.len() && !chars[i].is_whitespace() && !"(),=;'\"`".contains(chars[i]) {
+                i += 1;
+            }
+            statement.push(Token::Word(chars[start..i].iter().collect()));
+        }
+    }
+    if !statement.is_empty() {
+        statements.push(statement);
+    }
+    Ok(statements)
+}
+
+fn is_keyword(token: Option<&Token>, keyword: &str) -> bool {
+    matches!(token, Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword))
+}
+
+/// Read a possibly qualified table name at `*i`, e.g. `` `mail`.`domain` ``
+fn table_name(tokens: &[Token], i: &mut usize) -> Option<String> {
+    let name = match tokens.get(*i)? {
+        Token::Name(name) => name.clone(),
+        Token::Word(word) => word.rsplit('.').next()?.to_string(),
+        _ => return None,
+    };
+    *i += 1;
+    // `` `db`.`table` `` tokenizes as a name, a `.` word and a name
+    if matches!(tokens.get(*i), Some(Token::Word(dot)) if dot == ".") {
+        *i += 1;
+        return table_name(tokens, i);
+    }
+    Some(name.to_lowercase())
+}
+
+/// The columns of a `CREATE TABLE` statement, in order
+fn create_table(tokens: &[Token]) -> Option<(String, Vec<String>)> {
+    let mut i = 1;
+    while !is_keyword(tokens.get(i), "TABLE") {
+        if i > 2 {
+            return None;
+        }
+        i += 1;
+    }
+    i += 1;
+    if is_keyword(tokens.get(i), "IF") {
+        i += 3;
+    }
+    let table = table_name(tokens, &mut i)?;
+    if tokens.get(i) != Some(&Token::Symbol('(')) {
+        return None;
+    }
+
+    let mut columns = Vec::new();
+    let mut depth = 1;
+    let mut item_start = true;
+    for token in &tokens[i + 1..] {
+        match token {
+            Token::Symbol('(') => depth += 1,
+            Token::Symbol(')') => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            Token::Symbol(',') if depth == 1 => {
+                item_start = true;
+                continue;
+            }
+            Token::Name(name) if item_start && depth == 1 => columns.push(name.to_lowercase()),
+            Token::Word(word) if item_start && depth == 1 => {
+                const INDEXES: [&str; 9] = [
+                    "PRIMARY",
+                    "KEY",
+                    "UNIQUE",
+                    "INDEX",
+                    "CONSTRAINT",
+                    "FOREIGN",
+                    "FULLTEXT",
+                    "SPATIAL",
+                    "CHECK",
+                ];
+                if !INDEXES.iter().any(|index| word.eq_ignore_ascii_case(index)) {
+                    columns.push(word.to_lowercase());
+                }
+            }
+            _ => {}
+        }
+        item_start = false;
+    }
+    Some((table, columns))
+}
+
+/// The rows of an `INSERT` or `REPLACE` statement
+fn insert(
+    tokens: &[Token],
+    created: &HashMap<String, Vec<String>>,
+) -> Result<Option<(String, Vec<Row>)>, LegacyError> {
+    let mut i = 1;
+    while !is_keyword(tokens.get(i), "INTO") {
+        if !matches!(tokens.get(i), Some(Token::Word(_))) {
+            return Ok(None);
+        }
+        i += 1;
+    }
+    i += 1;
+    let Some(table) = table_name(tokens, &mut i) else {
+        return Ok(None);
+    };
+    let broken = |what: &str| LegacyError::Dump(format!("{what} in INSERT INTO {table}"));
+
+    let mut columns = Vec::new();
+    if tokens.get(i) == Some(&Token::Symbol('(')) {
+        i += 1;
+        loop {
+            match tokens.get(i) {
+                Some(Token::Name(name)) | Some(Token::Word(name)) => {
+                    columns.push(name.to_lowercase())
+                }
+                Some(Token::Symbol(',')) => {}
+                Some(Token::Symbol(')')) => break,
+                _ => return Err(broken("unexpected column list")),
+            }
+            i += 1;
+        }
+        i += 1;
+    } else if let Some(known) = created.get(&table) {
+        columns = known.clone();
+    } else {
+        return Err(broken("no CREATE TABLE or column list"));
+    }
+    if !is_keyword(tokens.get(i), "VALUES") && !is_keyword(tokens.get(i), "VALUE") {
+        return Err(broken("no VALUES"));
+    }
+    i += 1;
+
+    let mut rows = Vec::new();
+    while tokens.get(i) == Some(&Token::Symbol('(')) {
+        i += 1;
+        let mut values = Vec::new();
+        loop {
+            match tokens.get(i) {
+                Some(Token::Text(text)) => values.push(Some(text.clone())),
+                // A character set introducer such as `_binary 'x'`
+                Some(Token::Word(word))
+                    if word.starts_with('_')
+                        && matches!(tokens.get(i + 1), Some(Token::Text(_))) => {}
+                Some(Token::Word(word)) if word.eq_ignore_ascii_case("NULL") => values.push(None),
+                Some(Token::Word(word)) => values.push(Some(word.clone())),
+                Some(Token::Symbol(',')) => {}
+                Some(Token::Symbol(')')) => break,
+                _ => return Err(broken("unexpected value")),
+            }
+            i += 1;
+        }
+        i += 1;
+        if values.len() != columns.len() {
+            return Err(broken(&format!(
+                "{} values for {} columns",
+                values.len(),
+                columns.len()
+            )));
+        }
+        rows.push(columns.iter().cloned().zip(values).collect());
+        if tokens.get(i) == Some(&Token::Symbol(',')) {
+            i += 1;
+        }
+    }
+    Ok(Some((table, rows)))
+}
+
+/// Read the tables of a `mysqldump` or phpMyAdmin SQL export
+///
+/// Only `CREATE TABLE` and `INSERT` statements are read; the rest is skipped.
+pub fn read_dump(content: &str) -> Result<Tables, LegacyError> {
+    let content = content.trim_start_matches('\u{feff}');
+    let mut created: HashMap<String, Vec<String>> = HashMap::new();
+    let mut tables = Tables::new();
+    for tokens in tokenize(content)? {
+        if is_keyword(tokens.first(), "CREATE") {
+            if let Some((table, columns)) = create_table(&tokens) {
+                tables.entry(table.clone()).or_default();
+                created.insert(table, columns);
+            }
+        } else if is_keyword(tokens.first(), "INSERT") || is_keyword(tokens.first(), "REPLACE") {
+            if let Some((table, rows)) = insert(&tokens, &created)? {
+                tables.entry(table).or_default().extend(rows);
+            }
+        }
+    }
+    Ok(tables)
+}
+
+// Conversion
+
+fn text(row: &Row, column: &str) -> String {
+    row.get(column)
+        .and_then(|value| value.as_deref())
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
+
+/// A boolean column, or `default` if the source has no such column
+fn flag(row: &Row, column: &str, default: bool) -> bool {
+    match row.get(column) {
+        None => default,
+        Some(value) => matches!(
+            value
+                .as_deref()
+                .map(|v| v.trim().to_ascii_lowercase())
+                .as_deref(),
+            Some("1" | "true" | "t" | "y" | "yes")
+        ),
+    }
+}
+
+fn number(row: &Row, column: &str) -> Option<i64> {
+    text(row, column).parse().ok()
+}
+
+fn date(row: &Row, column: &str) -> Option<NaiveDate> {
+    let value = text(row, column);
+    NaiveDate::parse_from_str(value.get(..10)?, "%Y-%m-%d").ok()
+}
+
+/// Whether a value is empty or a default that means "not set"
+fn is_blank(value: Option<&str>) -> bool {
+    match value.map(str::trim) {
+        None | Some("") | Some("0") => true,
+        Some(value) => value.starts_with("0000-00-00") || value.starts_with("1000-01-01"),
+    }
+}
+
+/// The domain and local part of an address
+fn address_parts(address: &str) -> (&str, &str) {
+    match address.rsplit_once('@') {
+        Some((local, domain)) => (domain, local),
+        None => ("", address),
+    }
+}
+
+struct Converter<'a> {
+    tables: &'a Tables,
+    options: &'a LegacyOptions,
+    notes: Vec<LegacyNote>,
+}
+
+impl<'a> Converter<'a> {
+    fn note(&mut self, table: &str, key: &str, issue: LegacyIssue, detail: String) {
+        self.notes.push(LegacyNote {
+            table: table.to_string(),
+            key: key.to_string(),
+            issue,
+            detail,
+        });
+    }
+
+    /// The rows of a source table, noting a table the source lacks
+    fn rows(&mut self, table: &str) -> &'a [Row] {
+        match self.tables.get(table) {
+            Some(rows) => rows,
+            None => {
+                self.note(table, "", LegacyIssue::MissingTable, String::new());
+                &[]
+            }
+        }
+    }
+
+    /// Note the values of every column other than `known`
+    fn unknown_columns(&mut self, table: &str, key: &str, row: &Row, known: &[&str]) {
+        let mut columns: Vec<&String> = row
+            .keys()
+            .filter(|column| !known.contains(&column.as_str()))
+            .collect();
+        columns.sort();
+        for column in columns {
+            let value = row[column].as_deref();
+            if !is_blank(value) {
+                let detail = format!("{column} = {}", value.unwrap_or_default());
+                self.note(table, key, LegacyIssue::DroppedField, detail);
+            }
+        }
+    }
+
+    /// A positive size in bytes, noting a negative one
+    fn size(&mut self, table: &str, key: &str, row: &Row, column: &str, unit: i64) -> Option<i64> {
+        match number(row, column)? {
+            size if size > 0 => Some(size.saturating_mul(unit)),
+            0 => None,
+            size => {
+                let detail = format!("{column} = {size}");
+                self.note(table, key, LegacyIssue::DroppedField, detail);
+                None
+            }
+        }
+    }
+
+    fn password_hash(&mut self, table: &str, key: &str, hash: &str) -> Option<String> {
+        if hash.is_empty() {
+            return None;
+        }
+        match password::with_prefix(hash) {
+            Some(prefixed) => Some(prefixed),
+            None => {
+                self.note(table, key, LegacyIssue::UnknownHash, String::new());
+                Some(hash.to_string())
+            }
+        }
+    }
+
+    fn user(&self, id: &str, name: String, maildir: String) -> BundleUser {
+        BundleUser {
+            id: id.to_string(),
+            name,
+            maildir,
+            home: self.options.home.clone(),
+            uid: self.options.uid,
+            gid: self.options.gid,
+            enabled: true,
+            change_password: false,
+            quota_bytes: None,
+            quota_messages: None,
+            password_hash: None,
+        }
+    }
+
+    /// An alias to `destinations`, leaving out the address itself when it is
+    /// the only one, which just marks a mailbox
+    fn alias(
+        &mut self,
+        table: &str,
+        mail: &str,
+        destinations: Vec<String>,
+        enabled: bool,
+    ) -> Option<BundleAlias> {
+        let mut kept: Vec<String> = Vec::new();
+        for destination in destinations {
+            let (_, local) = address_parts(&destination);
+            if table == "alias" && local.contains('#') {
+                self.note(table, mail, LegacyIssue::AutoreplyDestination, destination);
+            } else if !kept.contains(&destination) {
+                kept.push(destination);
+            }
+        }
+        if kept.is_empty() {
+            self.note(table, mail, LegacyIssue::NoDestination, String::new());
+            return None;
+        }
+        if kept.len() == 1 && kept[0].eq_ignore_ascii_case(mail) {
+            return None;
+        }
+        Some(BundleAlias {
+            mail: mail.to_string(),
+            destination: kept.join(", "),
+            enabled,
+            valid_from: None,
+            valid_until: None,
+        })
+    }
+
+    fn postfixadmin(&mut self, bundle: &mut Bundle) {
+        for row in self.rows("domain") {
+            let domain = text(row, "domain");
+            // `ALL` is PostfixAdmin's placeholder granting superadmins every domain
+            if domain.is_empty() || domain.eq_ignore_ascii_case("ALL") {
+                continue;
+            }
+            self.unknown_columns(
+                "domain",
+                &domain,
+                row,
+                &[
+                    "domain",
+                    "transport",
+                    "backupmx",
+                    "active",
+                    "maxquota",
+                    "quota",
+                    "created",
+                    "modified",
+                ],
+            );
+            let transport = Some(text(row, "transport")).filter(|t| !t.is_empty());
+            let enabled = flag(row, "active", true);
+            if flag(row, "backupmx", false) {
+                bundle.backups.push(BundleBackup {
+                    domain,
+                    transport,
+                    enabled,
+                });
+                continue;
+            }
+            let default_quota_bytes =
+                self.size("domain", &domain, row, "maxquota", QUOTA_MULTIPLIER);
+            let quota_cap_bytes = self.size("domain", &domain, row, "quota", QUOTA_MULTIPLIER);
+            bundle.domains.push(BundleDomain {
+                domain,
+                transport,
+                enabled,
+                default_quota_bytes,
+                default_quota_messages: None,
+                quota_cap_bytes,
+            });
+        }
+
+        for row in self.rows("mailbox") {
+            let id = text(row, "username");
+            if id.is_empty() {
+                continue;
+            }
+            self.unknown_columns(
+                "mailbox",
+                &id,
+                row,
+                &[
+                    "username",
+                    "password",
+                    "name",
+                    "maildir",
+                    "quota",
+                    "local_part",
+                    "domain",
+                    "active",
+                    "created",
+                    "modified",
+                    "token",
+                    "token_validity",
+                    "password_expiry",
+                ],
+            );
+            let maildir = match text(row, "maildir") {
+                maildir if maildir.is_empty() => {
+                    let (domain, local) = address_parts(&id);
+                    format!("{domain}/{local}/")
+                }
+                maildir => maildir,
+            };
+            let mut user = self.user(&id, text(row, "name"), maildir);
+            user.enabled = flag(row, "active", true);
+            user.quota_bytes = self.size("mailbox", &id, row, "quota", 1);
+            user.password_hash = self.password_hash("mailbox", &id, &text(row, "password"));
+            bundle.users.push(user);
+        }
+
+        for row in self.rows("alias") {
+            let mail = text(row, "address");
+            if mail.is_empty() {
+                continue;
+            }
+            self.unknown_columns(
+                "alias",
+                &mail,
+                row,
+                &["address", "goto", "domain", "active", "created", "modified"],
+            );
+            let destinations = split_destinations(&text(row, "goto"));
+            if let Some(alias) = self.alias("alias", &mail, destinations, flag(row, "active", true))
+            {
+                bundle.aliases.push(alias);
+            }
+        }
+
+        for row in self.rows("alias_domain") {
+            let alias_domain = text(row, "alias_domain");
+            if alias_domain.is_empty() {
+                continue;
+            }
+            self.unknown_columns(
+                "alias_domain",
+                &alias_domain,
+                row,
+                &[
+                    "alias_domain",
+                    "target_domain",
+                    "active",
+                    "created",
+                    "modified",
+                ],
+            );
+            bundle.alias_domains.push(BundleAliasDomain {
+                alias_domain,
+                target_domain: text(row, "target_domain"),
+                enabled: flag(row, "active", true),
+            });
+        }
+
+        for row in self.rows("vacation") {
+            let user_id = text(row, "email");
+            if user_id.is_empty() {
+                continue;
+            }
+            self.unknown_columns(
+                "vacation",
+                &user_id,
+                row,
+                &[
+                    "email",
+                    "subject",
+                    "body",
+                    "active",
+                    "activefrom",
+                    "activeuntil",
+                    "interval_time",
+                    "cache",
+                    "domain",
+                    "created",
+                    "modified",
+                ],
+            );
+            // PostfixAdmin fills in 2000-01-01 and 2038-01-18 for open-ended replies
+            let active_from = date(row, "activefrom").filter(|d| d.year() > 2000);
+            let active_until = date(row, "activeuntil").filter(|d| d.year() < 2038);
+            // PostfixAdmin counts seconds, with 0 for a single reply
+            let reply_interval_days = match number(row, "interval_time") {
+                Some(seconds) if seconds > 0 => {
+                    ((seconds + 86_399) / 86_400).min(MAX_REPLY_INTERVAL_DAYS as i64) as i32
+                }
+                _ => DEFAULT_REPLY_INTERVAL_DAYS,
+            };
+            bundle.vacations.push(BundleVacation {
+                user_id,
+                subject: text(row, "subject"),
+                body: text(row, "body"),
+                active_from,
+                active_until,
+                reply_interval_days,
+                enabled: flag(row, "active", true),
+            });
+        }
+    }
+
+    fn ispmail(&mut self, bundle: &mut Bundle) {
+        for row in self.rows("virtual_domains") {
+            let domain = text(row, "name");
+            if domain.is_empty() {
+                continue;
+            }
+            self.unknown_columns("virtual_domains", &domain, row, &["id", "name"]);
+            bundle.domains.push(BundleDomain {
+                domain,
+                transport: None,
+                enabled: true,
+                default_quota_bytes: None,
+                default_quota_messages: None,
+                quota_cap_bytes: None,
+            });
+        }
+
+        for row in self.rows("virtual_users") {
+            let id = text(row, "email");
+            if id.is_empty() {
+                continue;
+            }
+            self.unknown_columns(
+                "virtual_users",
+                &id,
+                row,
+                &["id", "domain_id", "email", "password", "quota"],
+            );
+            // The guides deliver to /var/vmail/<domain>/<user>/Maildir
+            let (domain, local) = address_parts(&id);
+            let maildir = format!("{domain}/{local}/Maildir/");
+            let mut user = self.user(&id, String::new(), maildir);
+            user.quota_bytes = self.size("virtual_users", &id, row, "quota", 1);
+            user.password_hash = self.password_hash("virtual_users", &id, &text(row, "password"));
+            bundle.users.push(user);
+        }
+
+        // One row per destination, gathered into one alias per source
+        let mut sources: Vec<(String, Vec<String>)> = Vec::new();
+        for row in self.rows("virtual_aliases") {
+            let source = text(row, "source");
+            if source.is_empty() {
+                continue;
+            }
+            self.unknown_columns(
+                "virtual_aliases",
+                &source,
+                row,
+                &["id", "domain_id", "source", "destination"],
+            );
+            let destinations = split_destinations(&text(row, "destination"));
+            match sources
+                .iter_mut()
+                .find(|(mail, _)| mail.eq_ignore_ascii_case(&source))
+            {
+                Some((_, existing)) => existing.extend(destinations),
+                None => sources.push((source, destinations)),
+            }
+        }
+        for (mail, destinations) in sources {
+            if let Some(alias) = self.alias("virtual_aliases", &mail, destinations, true) {
+                bundle.aliases.push(alias);
+            }
+        }
+    }
+}
+
+/// Convert the tables of a source into a bundle named after its schema
+pub fn convert(schema: LegacySchema, tables: &Tables, options: &LegacyOptions) -> Conversion {
+    let mut bundle = Bundle::new(schema.name(), true);
+    let mut converter = Converter {
+        tables,
+        options,
+        notes: Vec::new(),
+    };
+    match schema {
+        LegacySchema::PostfixAdmin => converter.postfixadmin(&mut bundle),
+        LegacySchema::Ispmail => converter.ispmail(&mut bundle),
+    }
+
+    bundle.domains.sort_by_key(|d| d.domain.to_lowercase());
+    bundle.backups.sort_by_key(|b| b.domain.to_lowercase());
+    bundle.users.sort_by_key(|u| u.id.to_lowercase());
+    bundle.aliases.sort_by_key(|a| a.mail.to_lowercase());
+    bundle
+        .alias_domains
+        .sort_by_key(|d| d.alias_domain.to_lowercase());
+    bundle.vacations.sort_by_key(|v| v.user_id.to_lowercase());
+    Conversion {
+        bundle,
+        notes: converter.notes,
+    }
+}
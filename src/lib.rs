@@ -0,0 +1,42 @@
+use diesel::mysql::MysqlConnection;
+use diesel::r2d2::{self, ConnectionManager};
+use diesel_migrations::{embed_migrations, EmbeddedMigrations};
+
+pub mod bundle;
+pub mod cli;
+pub mod config;
+pub mod db;
+pub mod delivery;
+pub mod handlers;
+pub mod i18n;
+pub mod import;
+pub mod legacy;
+pub mod mail_config;
+pub mod models;
+pub mod password;
+pub mod portal;
+pub mod quota;
+pub mod reload;
+pub mod restore;
+pub mod schema;
+pub mod session;
+pub mod sync;
+pub mod templates;
+pub mod usage;
+pub mod vacation;
+pub mod validity;
+
+#[cfg(test)]
+pub mod tests;
+
+// Production uses MySQL
+pub type DbPool = r2d2::Pool<ConnectionManager<MysqlConnection>>;
+pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!();
+
+#[derive(Clone)]
+pub struct AppState {
+    pub db_manager: db::DatabaseManager,
+    pub i18n: i18n::I18n,
+    pub config: config::SharedConfig,
+    pub sessions: session::SessionStore,
+}
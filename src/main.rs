@@ -3,35 +3,13 @@ use axum::{
     routing::{get, post, put},
     Router,
 };
-use diesel::mysql::MysqlConnection;
-use diesel::r2d2::{self, ConnectionManager};
-use diesel_migrations::{embed_migrations, EmbeddedMigrations};
+use sortingoffice::{
+    config, db, handlers, i18n, reload, session, usage, vacation, validity, AppState,
+};
 use std::net::SocketAddr;
 use tower_http::trace::TraceLayer;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-pub mod config;
-pub mod db;
-pub mod handlers;
-pub mod i18n;
-pub mod models;
-pub mod schema;
-pub mod templates;
-
-#[cfg(test)]
-pub mod tests;
-
-// Production uses MySQL
-pub type DbPool = r2d2::Pool<ConnectionManager<MysqlConnection>>;
-pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!();
-
-#[derive(Clone)]
-pub struct AppState {
-    db_manager: db::DatabaseManager,
-    i18n: i18n::I18n,
-    config: config::Config,
-}
-
 #[tokio::main]
 async fn main() {
     dotenvy::dotenv().ok();
@@ -41,28 +19,14 @@ async fn main() {
         .with(tracing_subscriber::filter::EnvFilter::from_default_env())
         .init();
 
-    // Load configuration first
-    let config = config::Config::load().expect("Failed to load configuration");
+    // Load configuration first, remembering the file so the config page can save to it
+    let shared_config = config::SharedConfig::load().expect("Failed to load configuration");
+    let config = shared_config.get();
 
     // Create database manager with multiple databases
-    let db_manager = if config.databases.is_empty() {
-        // Fallback to single database from environment variable
-        let db_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
-        let fallback_config = vec![config::DatabaseConfig {
-            id: "primary".to_string(),
-            label: "Primary Database".to_string(),
-            url: db_url,
-            features: config::DatabaseFeatures::default(),
-            field_map: std::collections::HashMap::new(),
-        }];
-        db::DatabaseManager::new(fallback_config)
-            .await
-            .expect("Failed to create database manager")
-    } else {
-        db::DatabaseManager::new(config.databases.clone())
-            .await
-            .expect("Failed to create database manager")
-    };
+    let db_manager = db::DatabaseManager::from_config(&config)
+        .await
+        .expect("Failed to create database manager");
 
     // Run database migrations on all configured databases
     if let Err(e) = db_manager.run_migrations_on_all_databases().await {
@@ -85,12 +49,53 @@ async fn main() {
         .await
         .expect("Failed to load French locale");
 
+    // Session store, optionally backed by the admin_sessions table
+    let session_pool = match config.session.store {
+        config::SessionStoreKind::Mysql => {
+            let session_db = config
+                .session
+                .database
+                .clone()
+                .unwrap_or_else(|| db_manager.get_default_db_id().to_string());
+            db_manager.get_pool(&session_db).await
+        }
+        config::SessionStoreKind::Memory => None,
+    };
+    let sessions = session::SessionStore::new(&config.session, session_pool);
+
+    // Periodically purge expired sessions
+    let purge_sessions = sessions.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(300));
+        loop {
+            interval.tick().await;
+            match purge_sessions.purge_expired() {
+                Ok(0) => {}
+                Ok(count) => tracing::debug!("Purged {} expired sessions", count),
+                Err(e) => tracing::warn!("Failed to purge expired sessions: {:?}", e),
+            }
+        }
+    });
+
     let app_state = AppState {
         db_manager,
         i18n,
-        config,
+        config: shared_config,
+        sessions,
     };
 
+    // Reload the config file on SIGHUP and when it changes
+    reload::spawn_watchers(app_state.clone());
+
+    // Record mailbox usage snapshots for the statistics page
+    usage::spawn_collector(app_state.clone());
+
+    // Start and end vacations on the Postfix vacation transport
+    vacation::spawn_scheduler(app_state.clone());
+
+    // Enable and disable aliases and relocated entries at their validity windows
+    validity::spawn_scheduler(app_state.clone());
+
     // Create read-only routes (require authentication but not edit permissions)
     let read_only_routes = Router::new()
         .route("/", get(handlers::dashboard::index))
@@ -98,13 +103,22 @@ async fn main() {
         // Read-only domain operations
         .route("/domains", get(handlers::domains::list))
         .route("/domains/{id}", get(handlers::domains::show))
+        .route(
+            "/domains/{id}/alias-domains",
+            get(handlers::alias_domains::for_domain),
+        )
         // Read-only user operations
         .route("/users", get(handlers::users::list))
         .route("/users/{id}", get(handlers::users::show))
+        .route("/users/{id}/vacation", get(handlers::vacation::show))
         // Read-only alias operations
         .route("/aliases", get(handlers::aliases::list))
         .route("/aliases/{id}", get(handlers::aliases::show))
         .route("/aliases/search", get(handlers::aliases::search))
+        .route(
+            "/aliases/{id}/destinations",
+            get(handlers::aliases::destinations),
+        )
         .route(
             "/aliases/domain-search",
             get(handlers::aliases::domain_search),
@@ -117,6 +131,9 @@ async fn main() {
         // Read-only relocated operations
         .route("/relocated", get(handlers::relocated::list_relocated))
         .route("/relocated/{id}", get(handlers::relocated::show_relocated))
+        // Read-only alias domain operations
+        .route("/alias-domains", get(handlers::alias_domains::list))
+        .route("/alias-domains/{id}", get(handlers::alias_domains::show))
         // Read-only client operations
         .route("/clients", get(handlers::clients::list_clients))
         .route("/clients/{id}", get(handlers::clients::show_client))
@@ -137,6 +154,10 @@ async fn main() {
             "/reports/alias-cross-domain",
             get(handlers::reports::alias_cross_domain_report),
         )
+        .route("/reports/delivery", get(handlers::reports::delivery_report))
+        // Export bundles; hashes are refused without edit access
+        .route("/export", get(handlers::export::index))
+        .route("/export/download", get(handlers::export::download))
         .route(
             "/reports/cross-database-user-distribution",
             get(handlers::reports::cross_database_user_distribution_report),
@@ -149,6 +170,12 @@ async fn main() {
             "/reports/cross-database-migration",
             get(handlers::reports::cross_database_migration_report),
         )
+        // Audit log
+        .route("/audit", get(handlers::audit::list))
+        .route(
+            "/audit/history/{entity_type}/{entity_id}",
+            get(handlers::audit::history),
+        )
         // Configuration
         .route("/config", get(handlers::config::view_config))
         // Database selection
@@ -169,6 +196,27 @@ async fn main() {
     // Create edit routes (require edit permissions)
     let edit_routes = Router::new()
         // Domain edit operations
+        // Configuration
+        .route("/config/update", post(handlers::config::update_config))
+        // Postfix and Dovecot lookup config, which includes database credentials
+        .route("/mail-config", get(handlers::mail_config::index))
+        .route(
+            "/mail-config/download",
+            get(handlers::mail_config::download),
+        )
+        // Bulk import
+        .route("/import", get(handlers::import::index))
+        .route("/import/preview", post(handlers::import::preview))
+        .route("/import/apply", post(handlers::import::apply))
+        .route("/import/report", post(handlers::import::report))
+        // Restoring export bundles
+        .route("/restore", get(handlers::restore::index))
+        .route("/restore/preview", post(handlers::restore::preview))
+        .route("/restore/apply", post(handlers::restore::apply))
+        .route("/legacy", get(handlers::legacy::index))
+        .route("/legacy/preview", post(handlers::legacy::preview))
+        .route("/sync", get(handlers::sync::index))
+        .route("/sync/apply", post(handlers::sync::apply))
         .route("/domains", post(handlers::domains::create))
         .route("/domains/new", get(handlers::domains::new))
         .route(
@@ -195,6 +243,10 @@ async fn main() {
         // User edit operations
         .route("/users", post(handlers::users::create))
         .route("/users/new", get(handlers::users::new))
+        .route(
+            "/users/generate-password",
+            get(handlers::users::generate_password),
+        )
         .route(
             "/users/{id}",
             put(handlers::users::update).delete(handlers::users::delete),
@@ -221,9 +273,17 @@ async fn main() {
             "/users/{id}/toggle-change-password",
             post(handlers::users::toggle_change_password),
         )
+        .route(
+            "/users/{id}/vacation",
+            post(handlers::vacation::save).delete(handlers::vacation::delete),
+        )
         // Alias edit operations
         .route("/aliases", post(handlers::aliases::create))
         .route("/aliases/new", get(handlers::aliases::new))
+        .route(
+            "/aliases/destinations",
+            post(handlers::aliases::destination_editor),
+        )
         .route(
             "/aliases/{id}",
             put(handlers::aliases::update).delete(handlers::aliases::delete),
@@ -286,6 +346,24 @@ async fn main() {
             "/relocated/{id}/toggle-enabled",
             post(handlers::relocated::toggle_enabled),
         )
+        // Alias domain edit operations
+        .route("/alias-domains", post(handlers::alias_domains::create))
+        .route(
+            "/alias-domains/new",
+            get(handlers::alias_domains::create_form),
+        )
+        .route(
+            "/alias-domains/{id}",
+            put(handlers::alias_domains::update).delete(handlers::alias_domains::delete),
+        )
+        .route(
+            "/alias-domains/{id}/edit",
+            get(handlers::alias_domains::edit_form),
+        )
+        .route(
+            "/alias-domains/{id}/toggle-enabled",
+            post(handlers::alias_domains::toggle_enabled),
+        )
         // Client edit operations
         .route("/clients", post(handlers::clients::create_client))
         .route("/clients/new", get(handlers::clients::create_client_form))
@@ -311,6 +389,138 @@ async fn main() {
             handlers::auth::require_edit_permissions,
         ));
 
+    // JSON API (bearer token authentication, see AUTHENTICATION.md)
+    let api_routes = Router::new()
+        .route(
+            "/api/v1/domains",
+            get(handlers::api::list_domains).post(handlers::api::create_domain),
+        )
+        .route(
+            "/api/v1/domains/{id}",
+            get(handlers::api::show_domain)
+                .put(handlers::api::update_domain)
+                .delete(handlers::api::delete_domain),
+        )
+        .route(
+            "/api/v1/domains/{id}/toggle",
+            post(handlers::api::toggle_domain),
+        )
+        .route(
+            "/api/v1/users",
+            get(handlers::api::list_users).post(handlers::api::create_user),
+        )
+        .route(
+            "/api/v1/users/{id}",
+            get(handlers::api::show_user)
+                .put(handlers::api::update_user)
+                .delete(handlers::api::delete_user),
+        )
+        .route(
+            "/api/v1/users/{id}/toggle",
+            post(handlers::api::toggle_user),
+        )
+        .route(
+            "/api/v1/aliases",
+            get(handlers::api::list_aliases).post(handlers::api::create_alias),
+        )
+        .route(
+            "/api/v1/aliases/{id}",
+            get(handlers::api::show_alias)
+                .put(handlers::api::update_alias)
+                .delete(handlers::api::delete_alias),
+        )
+        .route(
+            "/api/v1/aliases/{id}/toggle",
+            post(handlers::api::toggle_alias),
+        )
+        .route(
+            "/api/v1/backups",
+            get(handlers::api::list_backups).post(handlers::api::create_backup),
+        )
+        .route(
+            "/api/v1/backups/{id}",
+            get(handlers::api::show_backup)
+                .put(handlers::api::update_backup)
+                .delete(handlers::api::delete_backup),
+        )
+        .route(
+            "/api/v1/backups/{id}/toggle",
+            post(handlers::api::toggle_backup),
+        )
+        .route(
+            "/api/v1/relays",
+            get(handlers::api::list_relays).post(handlers::api::create_relay),
+        )
+        .route(
+            "/api/v1/relays/{id}",
+            get(handlers::api::show_relay)
+                .put(handlers::api::update_relay)
+                .delete(handlers::api::delete_relay),
+        )
+        .route(
+            "/api/v1/relays/{id}/toggle",
+            post(handlers::api::toggle_relay),
+        )
+        .route(
+            "/api/v1/relocated",
+            get(handlers::api::list_relocated).post(handlers::api::create_relocated),
+        )
+        .route(
+            "/api/v1/relocated/{id}",
+            get(handlers::api::show_relocated)
+                .put(handlers::api::update_relocated)
+                .delete(handlers::api::delete_relocated),
+        )
+        .route(
+            "/api/v1/relocated/{id}/toggle",
+            post(handlers::api::toggle_relocated),
+        )
+        .route(
+            "/api/v1/clients",
+            get(handlers::api::list_clients).post(handlers::api::create_client),
+        )
+        .route(
+            "/api/v1/clients/{id}",
+            get(handlers::api::show_client)
+                .put(handlers::api::update_client)
+                .delete(handlers::api::delete_client),
+        )
+        .route(
+            "/api/v1/clients/{id}/toggle",
+            post(handlers::api::toggle_client),
+        )
+        .route(
+            "/api/v1/reports/catch-all",
+            get(handlers::api::catch_all_report),
+        )
+        .route("/api/v1/reports/aliases", get(handlers::api::alias_report))
+        .route("/api/v1/reports/matrix", get(handlers::api::matrix_report))
+        .route(
+            "/api/v1/reports/missing-aliases",
+            get(handlers::api::missing_aliases_report),
+        )
+        .route(
+            "/api/v1/reports/orphaned",
+            get(handlers::api::orphaned_report),
+        )
+        .route(
+            "/api/v1/reports/external-forwarders",
+            get(handlers::api::external_forwarders_report),
+        )
+        .route(
+            "/api/v1/reports/alias-cross-domain",
+            get(handlers::api::alias_cross_domain_report),
+        )
+        .route(
+            "/api/v1/reports/delivery",
+            get(handlers::api::delivery_report),
+        )
+        .with_state(app_state.clone())
+        .layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            handlers::api::require_api_token,
+        ));
+
     // Create the main app with public and protected routes
     let app = Router::new()
         // Public routes (no authentication required)
@@ -319,17 +529,32 @@ async fn main() {
             get(handlers::auth::login_form).post(handlers::auth::login),
         )
         .route("/logout", post(handlers::auth::logout))
+        // Self-service portal for mailbox users, with its own sessions
+        .route(
+            "/portal/login",
+            get(handlers::portal::login_form).post(handlers::portal::login),
+        )
+        .route("/portal/logout", post(handlers::portal::logout))
+        .route("/portal", get(handlers::portal::index))
+        .route("/portal/password", post(handlers::portal::change_password))
+        .route(
+            "/portal/forwarding",
+            post(handlers::portal::update_forwarding),
+        )
         // Theme and language
         .route("/theme/toggle", post(handlers::theme::toggle_theme))
         .route("/language/set", post(handlers::language::set_language))
+        // OpenAPI document for the JSON API, viewable at /static/api-docs.html
+        .route("/api/openapi.json", get(handlers::api::openapi_json))
         // Serve static files at /static from the ./static directory
         .nest_service("/static", tower_http::services::ServeDir::new("./static"))
         // Merge read-only and edit routes
         .merge(read_only_routes)
         .merge(edit_routes)
+        .merge(api_routes)
+        .fallback(handlers::not_found)
         .with_state(app_state)
-        .layer(TraceLayer::new_for_http())
-        .fallback(handlers::not_found);
+        .layer(TraceLayer::new_for_http());
 
     let addr = SocketAddr::from(([0, 0, 0, 0], 3000));
     tracing::debug!("listening on {}", addr);
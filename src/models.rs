@@ -1,18 +1,87 @@
 use crate::schema::*;
-use chrono::NaiveDateTime;
+use chrono::{NaiveDate, NaiveDateTime};
 use diesel::prelude::*;
-use diesel::sql_types::{Bool, Text};
+use diesel::sql_types::{BigInt, Bool, Text, Timestamp};
 use serde::{Deserialize, Deserializer, Serialize};
+use utoipa::{IntoParams, ToSchema};
 
-fn deserialize_checkbox<'de, D>(deserializer: D) -> Result<bool, D::Error>
+pub(crate) fn deserialize_checkbox<'de, D>(deserializer: D) -> Result<bool, D::Error>
 where
     D: Deserializer<'de>,
 {
-    let opt = Option::<String>::deserialize(deserializer)?;
-    Ok(matches!(
-        opt.as_deref(),
-        Some("on") | Some("true") | Some("1")
-    ))
+    // HTML forms send checkbox strings; JSON API clients send booleans
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Checkbox {
+        Bool(bool),
+        Text(String),
+    }
+
+    let opt = Option::<Checkbox>::deserialize(deserializer)?;
+    Ok(match opt {
+        Some(Checkbox::Bool(value)) => value,
+        Some(Checkbox::Text(text)) => matches!(text.as_str(), "on" | "true" | "1"),
+        None => false,
+    })
+}
+
+/// Accept a quota from a form field or JSON: an empty string leaves it unset,
+/// text goes through `parse`, and JSON numbers are taken as is
+fn deserialize_quota<'de, D>(
+    deserializer: D,
+    parse: fn(&str) -> Result<i64, String>,
+) -> Result<Option<i64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Quota {
+        Number(i64),
+        Text(String),
+    }
+
+    match Option::<Quota>::deserialize(deserializer)? {
+        Some(Quota::Number(value)) if value < 0 => Err(serde::de::Error::custom(format!(
+            "Quota must not be negative: {value}"
+        ))),
+        Some(Quota::Number(value)) => Ok(Some(value)),
+        Some(Quota::Text(text)) if text.trim().is_empty() => Ok(None),
+        Some(Quota::Text(text)) => parse(&text).map(Some).map_err(serde::de::Error::custom),
+        None => Ok(None),
+    }
+}
+
+fn deserialize_quota_size<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_quota(deserializer, crate::quota::parse_size)
+}
+
+fn deserialize_quota_count<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_quota(deserializer, crate::quota::parse_count)
+}
+
+/// A quota size for display, or `default` when unset; `0` is unlimited
+fn quota_size_display(bytes: Option<i64>, default: &str) -> String {
+    match bytes {
+        Some(0) => "∞".to_string(),
+        Some(bytes) => crate::quota::human_size(bytes),
+        None => default.to_string(),
+    }
+}
+
+/// A message count for display, or `default` when unset; `0` is unlimited
+fn quota_count_display(count: Option<i64>, default: &str) -> String {
+    match count {
+        Some(0) => "∞".to_string(),
+        Some(count) => count.to_string(),
+        None => default.to_string(),
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -50,10 +119,30 @@ pub struct SystemStats {
     pub quota_usage_percent: f64,
     // Combined enabled stats for dashboard
     pub enabled_domains_and_backups: i64,
+    /// Aliases whose validity window ends within the next week
+    pub expiring_aliases: i64,
+}
+
+impl SystemStats {
+    pub fn total_quota_display(&self) -> String {
+        crate::quota::human_size(self.total_quota)
+    }
+
+    pub fn used_quota_display(&self) -> String {
+        crate::quota::human_size(self.used_quota)
+    }
 }
 
 #[derive(
-    Debug, Serialize, Deserialize, Queryable, Selectable, Identifiable, Clone, QueryableByName,
+    Debug,
+    Serialize,
+    Deserialize,
+    Queryable,
+    Selectable,
+    Identifiable,
+    Clone,
+    QueryableByName,
+    ToSchema,
 )]
 #[diesel(table_name = domains)]
 #[diesel(primary_key(pkid))]
@@ -71,12 +160,33 @@ pub struct Domain {
     pub modified: NaiveDateTime,
     #[diesel(sql_type = diesel::sql_types::Bool)]
     pub enabled: bool,
+    /// Quota for mailboxes without their own, in bytes
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::BigInt>)]
+    pub default_quota_bytes: Option<i64>,
+    /// Message limit for mailboxes without their own
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::BigInt>)]
+    pub default_quota_messages: Option<i64>,
+    /// Most bytes the domain's mailbox quotas may add up to
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::BigInt>)]
+    pub quota_cap_bytes: Option<i64>,
 }
 
 impl Domain {
     pub fn transport_display(&self) -> String {
         self.transport.clone().unwrap_or_else(|| "-".to_string())
     }
+
+    pub fn default_quota_bytes_display(&self) -> String {
+        quota_size_display(self.default_quota_bytes, "∞")
+    }
+
+    pub fn default_quota_messages_display(&self) -> String {
+        quota_count_display(self.default_quota_messages, "∞")
+    }
+
+    pub fn quota_cap_bytes_display(&self) -> String {
+        quota_size_display(self.quota_cap_bytes, "-")
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Insertable)]
@@ -85,9 +195,14 @@ pub struct NewDomain {
     pub domain: String,
     pub transport: Option<String>,
     pub enabled: bool,
+    pub default_quota_bytes: Option<i64>,
+    pub default_quota_messages: Option<i64>,
+    pub quota_cap_bytes: Option<i64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, QueryableByName)]
+#[derive(
+    Debug, Clone, Serialize, Deserialize, Queryable, Selectable, QueryableByName, ToSchema,
+)]
 #[diesel(table_name = users)]
 #[diesel(primary_key(id))]
 #[diesel(check_for_backend(diesel::mysql::Mysql))]
@@ -96,6 +211,7 @@ pub struct User {
     pub id: String,
     #[diesel(sql_type = Bool)]
     pub enabled: bool,
+    /// Password hash; serialized for the audit log, left out of [`PublicUser`]
     pub crypt: String,
     pub name: String,
     pub maildir: String,
@@ -105,6 +221,61 @@ pub struct User {
     pub created: NaiveDateTime,
     pub modified: NaiveDateTime,
     pub change_password: bool,
+    /// Mailbox size limit in bytes; unset uses the domain default, `0` is unlimited
+    pub quota_bytes: Option<i64>,
+    /// Mailbox message limit; unset uses the domain default, `0` is unlimited
+    pub quota_messages: Option<i64>,
+}
+
+impl User {
+    /// The user's own size quota for display; `default` is shown when unset
+    pub fn quota_bytes_display(&self, default: &str) -> String {
+        quota_size_display(self.quota_bytes, default)
+    }
+
+    /// The user's own message quota for display; `default` is shown when unset
+    pub fn quota_messages_display(&self, default: &str) -> String {
+        quota_count_display(self.quota_messages, default)
+    }
+}
+
+/// A user without the password hash, as API responses and CLI output show it
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[schema(as = User)]
+pub struct PublicUser {
+    pub id: String,
+    pub enabled: bool,
+    pub name: String,
+    pub maildir: String,
+    pub home: String,
+    pub uid: u16,
+    pub gid: u16,
+    pub created: NaiveDateTime,
+    pub modified: NaiveDateTime,
+    pub change_password: bool,
+    /// Mailbox size limit in bytes; unset uses the domain default, `0` is unlimited
+    pub quota_bytes: Option<i64>,
+    /// Mailbox message limit; unset uses the domain default, `0` is unlimited
+    pub quota_messages: Option<i64>,
+}
+
+impl From<User> for PublicUser {
+    fn from(user: User) -> Self {
+        PublicUser {
+            id: user.id,
+            enabled: user.enabled,
+            name: user.name,
+            maildir: user.maildir,
+            home: user.home,
+            uid: user.uid,
+            gid: user.gid,
+            created: user.created,
+            modified: user.modified,
+            change_password: user.change_password,
+            quota_bytes: user.quota_bytes,
+            quota_messages: user.quota_messages,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Insertable)]
@@ -119,13 +290,16 @@ pub struct NewUser {
     pub gid: u16,
     pub enabled: bool,
     pub change_password: bool,
+    pub quota_bytes: Option<i64>,
+    pub quota_messages: Option<i64>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct UserForm {
     pub id: String,
     pub password: String,
     pub name: String,
+    #[serde(default)]
     pub maildir: String,
     #[serde(default)]
     #[serde(deserialize_with = "deserialize_checkbox")]
@@ -133,10 +307,41 @@ pub struct UserForm {
     #[serde(default)]
     #[serde(deserialize_with = "deserialize_checkbox")]
     pub change_password: bool,
+    /// Size quota in bytes, or text such as `2G`; empty uses the domain default
+    #[serde(default, deserialize_with = "deserialize_quota_size")]
+    #[schema(value_type = Option<String>, example = "2G")]
+    pub quota_bytes: Option<i64>,
+    /// Message quota; empty uses the domain default
+    #[serde(default, deserialize_with = "deserialize_quota_count")]
+    pub quota_messages: Option<i64>,
+}
+
+impl UserForm {
+    /// The size quota as it goes back into the form field
+    pub fn quota_bytes_input(&self) -> String {
+        self.quota_bytes
+            .map(crate::quota::format_size)
+            .unwrap_or_default()
+    }
+
+    /// The message quota as it goes back into the form field
+    pub fn quota_messages_input(&self) -> String {
+        self.quota_messages
+            .map(|count| count.to_string())
+            .unwrap_or_default()
+    }
 }
 
 #[derive(
-    Debug, Serialize, Deserialize, Queryable, Selectable, Identifiable, Clone, QueryableByName,
+    Debug,
+    Serialize,
+    Deserialize,
+    Queryable,
+    Selectable,
+    Identifiable,
+    Clone,
+    QueryableByName,
+    ToSchema,
 )]
 #[diesel(table_name = aliases)]
 #[diesel(primary_key(pkid))]
@@ -154,12 +359,33 @@ pub struct Alias {
     pub modified: NaiveDateTime,
     #[diesel(sql_type = diesel::sql_types::Bool)]
     pub enabled: bool,
+    #[diesel(sql_type = diesel::sql_types::Nullable<Timestamp>)]
+    pub valid_from: Option<NaiveDateTime>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<Timestamp>)]
+    pub valid_until: Option<NaiveDateTime>,
 }
 
 impl Alias {
     pub fn domain(&self) -> String {
         self.mail.split('@').nth(1).unwrap_or("").to_string()
     }
+
+    /// Whether Postfix uses the alias at `now`: enabled and inside its validity window
+    pub fn is_active(&self, now: NaiveDateTime) -> bool {
+        self.enabled && crate::validity::is_within(self.valid_from, self.valid_until, now)
+    }
+
+    pub fn window_status(&self, now: NaiveDateTime) -> crate::validity::WindowStatus {
+        crate::validity::status(self.valid_from, self.valid_until, now)
+    }
+
+    pub fn valid_from_display(&self) -> String {
+        datetime_display(self.valid_from)
+    }
+
+    pub fn valid_until_display(&self) -> String {
+        datetime_display(self.valid_until)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Insertable)]
@@ -168,28 +394,102 @@ pub struct NewAlias {
     pub mail: String,
     pub destination: String,
     pub enabled: bool,
+    pub valid_from: Option<NaiveDateTime>,
+    pub valid_until: Option<NaiveDateTime>,
 }
 
 // Form data structures for HTMX
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone, ToSchema)]
 pub struct DomainForm {
     pub domain: String,
     pub transport: String,
     #[serde(default)]
     #[serde(deserialize_with = "deserialize_checkbox")]
     pub enabled: bool,
+    /// Default size quota in bytes, or text such as `2G`; empty is unlimited
+    #[serde(default, deserialize_with = "deserialize_quota_size")]
+    #[schema(value_type = Option<String>, example = "2G")]
+    pub default_quota_bytes: Option<i64>,
+    /// Default message quota; empty is unlimited
+    #[serde(default, deserialize_with = "deserialize_quota_count")]
+    pub default_quota_messages: Option<i64>,
+    /// Cap on the sum of mailbox size quotas, or text such as `100G`; empty is no cap
+    #[serde(default, deserialize_with = "deserialize_quota_size")]
+    #[schema(value_type = Option<String>, example = "100G")]
+    pub quota_cap_bytes: Option<i64>,
+}
+
+impl DomainForm {
+    pub fn default_quota_bytes_input(&self) -> String {
+        self.default_quota_bytes
+            .map(crate::quota::format_size)
+            .unwrap_or_default()
+    }
+
+    pub fn default_quota_messages_input(&self) -> String {
+        self.default_quota_messages
+            .map(|count| count.to_string())
+            .unwrap_or_default()
+    }
+
+    pub fn quota_cap_bytes_input(&self) -> String {
+        self.quota_cap_bytes
+            .map(crate::quota::format_size)
+            .unwrap_or_default()
+    }
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, ToSchema)]
 pub struct AliasForm {
     pub mail: String,
     pub destination: String,
     #[serde(default)]
     #[serde(deserialize_with = "deserialize_checkbox")]
     pub enabled: bool,
+    /// Start of the validity window in UTC; empty is no start
+    #[serde(default, deserialize_with = "deserialize_optional_datetime")]
+    #[schema(value_type = Option<String>, example = "2026-11-02T09:00")]
+    pub valid_from: Option<NaiveDateTime>,
+    /// End of the validity window in UTC; empty is no end
+    #[serde(default, deserialize_with = "deserialize_optional_datetime")]
+    #[schema(value_type = Option<String>, example = "2026-11-06T18:00")]
+    pub valid_until: Option<NaiveDateTime>,
     pub return_url: Option<String>,
 }
 
+impl AliasForm {
+    pub fn valid_from_input(&self) -> String {
+        datetime_input(self.valid_from)
+    }
+
+    pub fn valid_until_input(&self) -> String {
+        datetime_input(self.valid_until)
+    }
+
+    /// The destination addresses, lower-cased and without duplicates
+    pub fn destinations(&self) -> Vec<String> {
+        let mut destinations: Vec<String> = Vec::new();
+        for address in crate::delivery::split_destinations(&self.destination) {
+            if !destinations.contains(&address) {
+                destinations.push(address);
+            }
+        }
+        destinations
+    }
+
+    /// Trim the address and store the destinations as one comma-separated list
+    pub fn normalized(mut self) -> Self {
+        self.mail = self.mail.trim().to_string();
+        self.destination = self.destinations().join(", ");
+        self
+    }
+
+    /// Check that there is a destination and that each one is a full address
+    pub fn validate(&self) -> Result<(), crate::delivery::DestinationError> {
+        crate::delivery::check_destinations(&self.destinations())
+    }
+}
+
 // Statistics models
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DomainStats {
@@ -198,9 +498,109 @@ pub struct DomainStats {
     pub alias_count: i64,
     pub total_quota: i64,
     pub used_quota: i64,
+    /// Users whose effective quota is unlimited, so not counted in `total_quota`
+    pub unlimited_users: i64,
+    pub quota_cap: Option<i64>,
+}
+
+impl DomainStats {
+    pub fn total_quota_display(&self) -> String {
+        crate::quota::human_size(self.total_quota)
+    }
+
+    pub fn used_quota_display(&self) -> String {
+        crate::quota::human_size(self.used_quota)
+    }
+
+    pub fn quota_cap_display(&self) -> String {
+        quota_size_display(self.quota_cap, "-")
+    }
+}
+
+/// A mailbox usage reading taken by the usage collector
+#[derive(Debug, Serialize, Deserialize, Queryable, Selectable, Clone)]
+#[diesel(table_name = mailbox_usage)]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct MailboxUsage {
+    pub id: i64,
+    pub user_id: String,
+    pub bytes: i64,
+    pub messages: i64,
+    pub collected: NaiveDateTime,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = mailbox_usage)]
+pub struct NewMailboxUsage {
+    pub user_id: String,
+    pub bytes: i64,
+    pub messages: i64,
+    pub collected: NaiveDateTime,
+}
+
+/// A mailbox's usage at the latest collection, next to its effective quota
+#[derive(Debug, Serialize, Clone, QueryableByName)]
+pub struct MailboxUsageSummary {
+    #[diesel(sql_type = Text)]
+    pub user_id: String,
+    #[diesel(sql_type = BigInt)]
+    pub bytes: i64,
+    #[diesel(sql_type = BigInt)]
+    pub messages: i64,
+    /// Effective size quota, `0` when unlimited
+    #[diesel(sql_type = BigInt)]
+    pub quota_bytes: i64,
+    /// Change in size since the oldest snapshot in the growth window
+    #[diesel(sql_type = BigInt)]
+    pub growth_bytes: i64,
+    #[diesel(sql_type = Timestamp)]
+    pub collected: NaiveDateTime,
+}
+
+impl MailboxUsageSummary {
+    pub fn bytes_display(&self) -> String {
+        crate::quota::human_size(self.bytes)
+    }
+
+    pub fn quota_display(&self) -> String {
+        quota_size_display(Some(self.quota_bytes), "-")
+    }
+
+    /// Share of the quota in use, `None` for unlimited mailboxes
+    pub fn usage_percent(&self) -> Option<i64> {
+        (self.quota_bytes > 0).then(|| self.bytes * 100 / self.quota_bytes)
+    }
+
+    /// Whether the mailbox is at 90% of its quota or more
+    pub fn is_near_quota(&self) -> bool {
+        self.usage_percent().is_some_and(|percent| percent >= 90)
+    }
+
+    pub fn growth_display(&self) -> String {
+        let sign = if self.growth_bytes < 0 { "-" } else { "+" };
+        format!(
+            "{sign}{}",
+            crate::quota::human_size(self.growth_bytes.abs())
+        )
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize, Queryable, Selectable, Identifiable)]
+/// Total mailbox usage at the last collection of a day
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct UsageHistoryPoint {
+    pub day: chrono::NaiveDate,
+    pub bytes: i64,
+    /// Bar length relative to the largest day in the history
+    pub bar_percent: i64,
+}
+
+impl UsageHistoryPoint {
+    pub fn bytes_display(&self) -> String {
+        crate::quota::human_size(self.bytes)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Queryable, Selectable, Identifiable, Clone, ToSchema)]
 #[diesel(table_name = backups)]
 #[diesel(primary_key(pkid))]
 #[diesel(check_for_backend(diesel::mysql::Mysql))]
@@ -221,7 +621,7 @@ pub struct NewBackup {
     pub enabled: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct BackupForm {
     pub domain: String,
     pub transport: String,
@@ -231,7 +631,7 @@ pub struct BackupForm {
 }
 
 // Relay models
-#[derive(Debug, Serialize, Deserialize, Queryable, Selectable, Identifiable)]
+#[derive(Debug, Serialize, Deserialize, Queryable, Selectable, Identifiable, ToSchema)]
 #[diesel(table_name = relays)]
 #[diesel(primary_key(pkid))]
 #[diesel(check_for_backend(diesel::mysql::Mysql))]
@@ -252,7 +652,7 @@ pub struct NewRelay {
     pub enabled: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct RelayForm {
     pub recipient: String,
     pub status: String,
@@ -261,8 +661,63 @@ pub struct RelayForm {
     pub enabled: bool,
 }
 
+// Alias domain models
+#[derive(Debug, Serialize, Deserialize, Queryable, Selectable, Identifiable, Clone, ToSchema)]
+#[diesel(table_name = alias_domains)]
+#[diesel(primary_key(pkid))]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct AliasDomain {
+    pub pkid: i32,
+    pub alias_domain: String,
+    pub target_domain: String,
+    pub created: NaiveDateTime,
+    pub modified: NaiveDateTime,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Insertable)]
+#[diesel(table_name = alias_domains)]
+pub struct NewAliasDomain {
+    pub alias_domain: String,
+    pub target_domain: String,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct AliasDomainForm {
+    pub alias_domain: String,
+    pub target_domain: String,
+    #[serde(default)]
+    #[serde(deserialize_with = "deserialize_checkbox")]
+    pub enabled: bool,
+}
+
+impl AliasDomainForm {
+    /// Trim and lowercase both domain names
+    pub fn normalized(self) -> Self {
+        Self {
+            alias_domain: self.alias_domain.trim().to_lowercase(),
+            target_domain: self.target_domain.trim().to_lowercase(),
+            enabled: self.enabled,
+        }
+    }
+
+    /// Check the domain names, returning the translation key of the problem
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if !crate::config::is_valid_domain(&self.alias_domain)
+            || !crate::config::is_valid_domain(&self.target_domain)
+        {
+            return Err("alias-domains-error-invalid-domain");
+        }
+        if self.alias_domain.eq_ignore_ascii_case(&self.target_domain) {
+            return Err("alias-domains-error-same-domain");
+        }
+        Ok(())
+    }
+}
+
 // Relocated models
-#[derive(Debug, Serialize, Deserialize, Queryable, Selectable, Identifiable)]
+#[derive(Debug, Serialize, Deserialize, Queryable, Selectable, Identifiable, Clone, ToSchema)]
 #[diesel(table_name = relocated)]
 #[diesel(primary_key(pkid))]
 #[diesel(check_for_backend(diesel::mysql::Mysql))]
@@ -273,6 +728,27 @@ pub struct Relocated {
     pub enabled: bool,
     pub created: NaiveDateTime,
     pub modified: NaiveDateTime,
+    pub valid_from: Option<NaiveDateTime>,
+    pub valid_until: Option<NaiveDateTime>,
+}
+
+impl Relocated {
+    /// Whether Postfix uses the entry at `now`: enabled and inside its validity window
+    pub fn is_active(&self, now: NaiveDateTime) -> bool {
+        self.enabled && crate::validity::is_within(self.valid_from, self.valid_until, now)
+    }
+
+    pub fn window_status(&self, now: NaiveDateTime) -> crate::validity::WindowStatus {
+        crate::validity::status(self.valid_from, self.valid_until, now)
+    }
+
+    pub fn valid_from_display(&self) -> String {
+        datetime_display(self.valid_from)
+    }
+
+    pub fn valid_until_display(&self) -> String {
+        datetime_display(self.valid_until)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Insertable)]
@@ -281,19 +757,39 @@ pub struct NewRelocated {
     pub old_address: String,
     pub new_address: String,
     pub enabled: bool,
+    pub valid_from: Option<NaiveDateTime>,
+    pub valid_until: Option<NaiveDateTime>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct RelocatedForm {
     pub old_address: String,
     pub new_address: String,
     #[serde(default)]
     #[serde(deserialize_with = "deserialize_checkbox")]
     pub enabled: bool,
+    /// Start of the validity window in UTC; empty is no start
+    #[serde(default, deserialize_with = "deserialize_optional_datetime")]
+    #[schema(value_type = Option<String>, example = "2026-11-02T09:00")]
+    pub valid_from: Option<NaiveDateTime>,
+    /// End of the validity window in UTC; empty is no end
+    #[serde(default, deserialize_with = "deserialize_optional_datetime")]
+    #[schema(value_type = Option<String>, example = "2027-05-01T00:00")]
+    pub valid_until: Option<NaiveDateTime>,
+}
+
+impl RelocatedForm {
+    pub fn valid_from_input(&self) -> String {
+        datetime_input(self.valid_from)
+    }
+
+    pub fn valid_until_input(&self) -> String {
+        datetime_input(self.valid_until)
+    }
 }
 
 // Client models
-#[derive(Debug, Serialize, Deserialize, Queryable, Selectable, Identifiable, Clone)]
+#[derive(Debug, Serialize, Deserialize, Queryable, Selectable, Identifiable, Clone, ToSchema)]
 #[diesel(table_name = clients)]
 #[diesel(primary_key(id))]
 #[diesel(check_for_backend(diesel::mysql::Mysql))]
@@ -314,7 +810,7 @@ pub struct NewClient {
     pub enabled: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct ClientForm {
     pub client: String,
     pub status: String,
@@ -323,8 +819,409 @@ pub struct ClientForm {
     pub enabled: bool,
 }
 
+/// Accept an optional date from a form field or JSON: an empty string leaves it unset
+fn deserialize_optional_date<'de, D>(deserializer: D) -> Result<Option<NaiveDate>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<String>::deserialize(deserializer)? {
+        Some(text) if text.trim().is_empty() => Ok(None),
+        Some(text) => NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d")
+            .map(Some)
+            .map_err(|_| serde::de::Error::custom(format!("Invalid date: {text}"))),
+        None => Ok(None),
+    }
+}
+
+/// Accept an optional date and time from a form field or JSON: an empty string
+/// leaves it unset, and a date without a time is midnight
+fn deserialize_optional_datetime<'de, D>(deserializer: D) -> Result<Option<NaiveDateTime>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<String>::deserialize(deserializer)? {
+        Some(text) if text.trim().is_empty() => Ok(None),
+        Some(text) => parse_datetime_input(text.trim())
+            .map(Some)
+            .ok_or_else(|| serde::de::Error::custom(format!("Invalid date and time: {text}"))),
+        None => Ok(None),
+    }
+}
+
+/// Parse `2026-11-02T09:00`, with seconds or a space instead of the `T`, or a plain date
+pub fn parse_datetime_input(text: &str) -> Option<NaiveDateTime> {
+    [
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+    .or_else(|| {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d")
+            .ok()
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+    })
+}
+
+/// A date and time for display, in UTC like the stored value
+fn datetime_display(value: Option<NaiveDateTime>) -> String {
+    value
+        .map(|value| value.format("%Y-%m-%d %H:%M UTC").to_string())
+        .unwrap_or_default()
+}
+
+/// A date and time in the format of a `datetime-local` input
+fn datetime_input(value: Option<NaiveDateTime>) -> String {
+    value
+        .map(|value| value.format("%Y-%m-%dT%H:%M").to_string())
+        .unwrap_or_default()
+}
+
+// Vacation models
+#[derive(Debug, Serialize, Deserialize, Queryable, Selectable, Identifiable, Clone, ToSchema)]
+#[diesel(table_name = vacation)]
+#[diesel(primary_key(user_id))]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct Vacation {
+    pub user_id: String,
+    pub subject: String,
+    pub body: String,
+    pub active_from: Option<NaiveDate>,
+    pub active_until: Option<NaiveDate>,
+    pub reply_interval_days: i32,
+    pub enabled: bool,
+    pub created: NaiveDateTime,
+    pub modified: NaiveDateTime,
+}
+
+impl Vacation {
+    /// Whether replies are sent on `today`: enabled and within the optional date range
+    pub fn is_active(&self, today: NaiveDate) -> bool {
+        self.enabled
+            && self.active_from.is_none_or(|from| from <= today)
+            && self.active_until.is_none_or(|until| today <= until)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct VacationForm {
+    pub subject: String,
+    pub body: String,
+    #[serde(default, deserialize_with = "deserialize_optional_date")]
+    pub active_from: Option<NaiveDate>,
+    #[serde(default, deserialize_with = "deserialize_optional_date")]
+    pub active_until: Option<NaiveDate>,
+    #[serde(default = "default_reply_interval_days")]
+    pub reply_interval_days: i32,
+    #[serde(default)]
+    #[serde(deserialize_with = "deserialize_checkbox")]
+    pub enabled: bool,
+}
+
+fn default_reply_interval_days() -> i32 {
+    crate::vacation::DEFAULT_REPLY_INTERVAL_DAYS
+}
+
+// Admin session models
+#[derive(Debug, Clone, Queryable, Selectable, Insertable)]
+#[diesel(table_name = admin_sessions)]
+#[diesel(primary_key(id))]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct AdminSession {
+    pub id: String,
+    pub username: String,
+    pub role: String,
+    pub database_id: String,
+    pub created: NaiveDateTime,
+    pub last_seen: NaiveDateTime,
+}
+
+impl AdminSession {
+    pub fn admin_role(&self) -> Option<crate::config::AdminRole> {
+        crate::config::AdminRole::parse(&self.role)
+    }
+
+    /// Whether this is a mailbox user's portal session rather than an admin's
+    pub fn is_mailbox(&self) -> bool {
+        self.role == crate::session::MAILBOX_ROLE
+    }
+}
+
+// Audit log models
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditEntity {
+    Domain,
+    User,
+    Alias,
+    Backup,
+    Relay,
+    Relocated,
+    Client,
+    Vacation,
+    AliasDomain,
+}
+
+impl AuditEntity {
+    pub const ALL: [AuditEntity; 9] = [
+        AuditEntity::Domain,
+        AuditEntity::User,
+        AuditEntity::Alias,
+        AuditEntity::Backup,
+        AuditEntity::Relay,
+        AuditEntity::Relocated,
+        AuditEntity::Client,
+        AuditEntity::Vacation,
+        AuditEntity::AliasDomain,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditEntity::Domain => "domain",
+            AuditEntity::User => "user",
+            AuditEntity::Alias => "alias",
+            AuditEntity::Backup => "backup",
+            AuditEntity::Relay => "relay",
+            AuditEntity::Relocated => "relocated",
+            AuditEntity::Client => "client",
+            AuditEntity::Vacation => "vacation",
+            AuditEntity::AliasDomain => "alias-domain",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|entity| entity.as_str() == value)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    Create,
+    Update,
+    Delete,
+    Toggle,
+    Password,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Create => "create",
+            AuditAction::Update => "update",
+            AuditAction::Delete => "delete",
+            AuditAction::Toggle => "toggle",
+            AuditAction::Password => "password",
+        }
+    }
+}
+
+/// Models whose changes are recorded in the audit log
+pub trait Audited: Serialize {
+    const ENTITY: AuditEntity;
+
+    /// Key used to find the entity's history (its show page ID)
+    fn audit_id(&self) -> String;
+
+    /// Human-readable name shown in the audit log
+    fn audit_name(&self) -> String;
+}
+
+impl Audited for Domain {
+    const ENTITY: AuditEntity = AuditEntity::Domain;
+    fn audit_id(&self) -> String {
+        self.pkid.to_string()
+    }
+    fn audit_name(&self) -> String {
+        self.domain.clone()
+    }
+}
+
+impl Audited for User {
+    const ENTITY: AuditEntity = AuditEntity::User;
+    fn audit_id(&self) -> String {
+        self.id.clone()
+    }
+    fn audit_name(&self) -> String {
+        self.id.clone()
+    }
+}
+
+impl Audited for Alias {
+    const ENTITY: AuditEntity = AuditEntity::Alias;
+    fn audit_id(&self) -> String {
+        self.pkid.to_string()
+    }
+    fn audit_name(&self) -> String {
+        self.mail.clone()
+    }
+}
+
+impl Audited for Backup {
+    const ENTITY: AuditEntity = AuditEntity::Backup;
+    fn audit_id(&self) -> String {
+        self.pkid.to_string()
+    }
+    fn audit_name(&self) -> String {
+        self.domain.clone()
+    }
+}
+
+impl Audited for Relay {
+    const ENTITY: AuditEntity = AuditEntity::Relay;
+    fn audit_id(&self) -> String {
+        self.pkid.to_string()
+    }
+    fn audit_name(&self) -> String {
+        self.recipient.clone()
+    }
+}
+
+impl Audited for AliasDomain {
+    const ENTITY: AuditEntity = AuditEntity::AliasDomain;
+    fn audit_id(&self) -> String {
+        self.pkid.to_string()
+    }
+    fn audit_name(&self) -> String {
+        self.alias_domain.clone()
+    }
+}
+
+impl Audited for Relocated {
+    const ENTITY: AuditEntity = AuditEntity::Relocated;
+    fn audit_id(&self) -> String {
+        self.pkid.to_string()
+    }
+    fn audit_name(&self) -> String {
+        self.old_address.clone()
+    }
+}
+
+impl Audited for Client {
+    const ENTITY: AuditEntity = AuditEntity::Client;
+    fn audit_id(&self) -> String {
+        self.id.to_string()
+    }
+    fn audit_name(&self) -> String {
+        self.client.clone()
+    }
+}
+
+impl Audited for Vacation {
+    const ENTITY: AuditEntity = AuditEntity::Vacation;
+    fn audit_id(&self) -> String {
+        self.user_id.clone()
+    }
+    fn audit_name(&self) -> String {
+        self.user_id.clone()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable)]
+#[diesel(table_name = audit_log)]
+#[diesel(check_for_backend(diesel::mysql::Mysql))]
+pub struct AuditLogEntry {
+    pub id: i64,
+    pub created: NaiveDateTime,
+    pub admin: String,
+    pub database_id: String,
+    pub entity_type: String,
+    pub entity_id: String,
+    pub entity_name: String,
+    pub action: String,
+    pub changes: String,
+}
+
+impl AuditLogEntry {
+    /// Field-level changes decoded from the stored JSON diff
+    pub fn field_changes(&self) -> Vec<AuditFieldChange> {
+        let Ok(serde_json::Value::Object(fields)) = serde_json::from_str(&self.changes) else {
+            return Vec::new();
+        };
+        fields
+            .into_iter()
+            .map(|(field, change)| AuditFieldChange {
+                field,
+                old: audit_value_display(change.get("old")),
+                new: audit_value_display(change.get("new")),
+            })
+            .collect()
+    }
+
+    /// Link to the entity's show page, if it still has one
+    pub fn entity_url(&self) -> Option<String> {
+        if self.action == AuditAction::Delete.as_str() {
+            return None;
+        }
+        let base = match AuditEntity::parse(&self.entity_type)? {
+            AuditEntity::Domain => "domains",
+            AuditEntity::User => "users",
+            AuditEntity::Alias => "aliases",
+            AuditEntity::Backup => "backups",
+            AuditEntity::Relay => "relays",
+            AuditEntity::Relocated => "relocated",
+            AuditEntity::Client => "clients",
+            // A vacation is shown on its mailbox's page
+            AuditEntity::Vacation => "users",
+            AuditEntity::AliasDomain => "alias-domains",
+        };
+        Some(format!("/{}/{}", base, self.entity_id))
+    }
+}
+
+fn audit_value_display(value: Option<&serde_json::Value>) -> String {
+    match value {
+        None | Some(serde_json::Value::Null) => "-".to_string(),
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AuditFieldChange {
+    pub field: String,
+    pub old: String,
+    pub new: String,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = audit_log)]
+pub struct NewAuditLogEntry {
+    pub created: NaiveDateTime,
+    pub admin: String,
+    pub database_id: String,
+    pub entity_type: String,
+    pub entity_id: String,
+    pub entity_name: String,
+    pub action: String,
+    pub changes: String,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct AuditLogFilter {
+    pub admin: Option<String>,
+    pub entity_type: Option<String>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+}
+
+// Weak password hash report models
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WeakPasswordHash {
+    pub user_id: String,
+    /// Scheme of the stored hash, if it is one we recognise
+    pub scheme: Option<String>,
+    /// Whether the stored hash carries a `{SCHEME}` prefix
+    pub prefixed: bool,
+    pub enabled: bool,
+}
+
 // Catch-all report models
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct CatchAllReport {
     pub domain: String,
     pub catch_all_alias: String,
@@ -332,7 +1229,7 @@ pub struct CatchAllReport {
     pub required_aliases: Vec<RequiredAlias>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct RequiredAlias {
     pub mail: String,
     pub destination: String,
@@ -509,7 +1406,7 @@ impl RequiredAliasConfig {
 }
 
 // Enhanced report models
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct DomainAliasReport {
     pub domain: String,
     pub has_catch_all: bool,
@@ -520,32 +1417,34 @@ pub struct DomainAliasReport {
     pub missing_common_aliases: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct AliasReport {
     pub domains_with_catch_all: Vec<DomainAliasReport>,
     pub domains_without_catch_all: Vec<DomainAliasReport>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct DomainAliasMatrixReport {
     pub domains: Vec<DomainAliasMatrixRow>,
     pub required_aliases_list: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct DomainAliasMatrixRow {
     pub domain: String,
+    /// The target domain, when this row is an alias domain
+    pub alias_for: Option<String>,
     pub catch_all_status: AliasStatus,
     pub required_aliases: Vec<RequiredAliasMatrixItem>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct RequiredAliasMatrixItem {
     pub alias: String,
     pub status: AliasStatus,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub enum AliasStatus {
     Present,
     Missing,
@@ -579,7 +1478,8 @@ impl AliasStatus {
 }
 
 // Pagination models
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct PaginationParams {
     pub page: Option<i64>,
     pub per_page: Option<i64>,
@@ -594,7 +1494,7 @@ impl Default for PaginationParams {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct PaginatedResult<T> {
     pub items: Vec<T>,
     pub total_count: i64,
@@ -606,14 +1506,16 @@ pub struct PaginatedResult<T> {
 }
 
 // Additional report models
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct OrphanedAliasReport {
     pub orphaned_aliases: Vec<OrphanedAlias>,
     pub orphaned_users: Vec<OrphanedUser>,
     pub users_without_aliases: Vec<UserWithoutAlias>,
+    /// Alias destinations that do not reach anyone, one row per destination
+    pub unresolved_destinations: Vec<UnresolvedDestination>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct OrphanedAlias {
     pub mail: String,
     pub destination: String,
@@ -622,7 +1524,17 @@ pub struct OrphanedAlias {
     pub created: NaiveDateTime,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UnresolvedDestination {
+    pub mail: String,
+    pub destination: String,
+    pub domain: String,
+    /// Where mail to the destination stops, e.g. an unknown user
+    pub outcome: DeliveryOutcome,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct OrphanedUser {
     pub id: String,
     pub name: String,
@@ -631,7 +1543,7 @@ pub struct OrphanedUser {
     pub created: NaiveDateTime,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct UserWithoutAlias {
     pub id: String,
     pub name: String,
@@ -640,12 +1552,13 @@ pub struct UserWithoutAlias {
     pub created: NaiveDateTime,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ExternalForwarderReport {
     pub external_forwarders: Vec<ExternalForwarder>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// An alias destination on a domain not managed here, one row per destination
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ExternalForwarder {
     pub mail: String,
     pub destination: String,
@@ -654,26 +1567,28 @@ pub struct ExternalForwarder {
     pub created: NaiveDateTime,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct MissingAliasReport {
     pub domains_missing_aliases: Vec<DomainMissingAliases>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct DomainMissingAliases {
     pub domain: String,
+    /// The target domain, when this is an alias domain
+    pub alias_for: Option<String>,
     pub missing_required_aliases: Vec<String>,
     pub has_catch_all: bool,
     pub catch_all_alias: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct AliasCrossDomainReport {
     pub alias: String,
     pub occurrences: Vec<AliasOccurrence>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct AliasOccurrence {
     pub domain: String,
     pub mail: String,
@@ -681,6 +1596,144 @@ pub struct AliasOccurrence {
     pub enabled: bool,
 }
 
+// Delivery simulator models
+/// How mail to an address is routed, as Postfix would resolve it
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DeliveryTrace {
+    pub address: String,
+    /// Every address visited, depth-first; alias destinations follow their alias
+    pub hops: Vec<DeliveryHop>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DeliveryHop {
+    /// Alias expansion depth, 0 for the recipient
+    pub depth: usize,
+    pub address: String,
+    pub outcome: DeliveryOutcome,
+    /// Disabled entries that matched this address and were skipped
+    pub disabled: Vec<DisabledEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DeliveryOutcome {
+    /// Bounced with a "user has moved" notice
+    Relocated { new_address: String },
+    /// Rewritten to the destinations, which are resolved in turn
+    Alias {
+        mail: String,
+        catch_all: bool,
+        destinations: Vec<String>,
+    },
+    /// On an alias domain, rewritten to the same local part on its target domain
+    AliasDomain {
+        alias_domain: String,
+        address: String,
+    },
+    /// Delivered to a local mailbox
+    Mailbox { maildir: String },
+    /// Accepted for a backup domain and relayed on
+    Relay {
+        domain: String,
+        transport: Option<String>,
+    },
+    /// Sent to a domain not managed here
+    External,
+    /// The address is already being expanded further up
+    Loop,
+    /// A managed domain with no alias or mailbox for the address
+    UnknownUser,
+    /// The recipient's domain is neither a domain nor a backup, so it is not accepted
+    UnknownDomain,
+    /// Not an email address
+    InvalidAddress,
+    /// Alias expansion went deeper than the simulator follows
+    TooDeep,
+}
+
+impl DeliveryOutcome {
+    /// Whether mail stops here without reaching anyone
+    pub fn is_problem(&self) -> bool {
+        matches!(
+            self,
+            DeliveryOutcome::Loop
+                | DeliveryOutcome::UnknownUser
+                | DeliveryOutcome::UnknownDomain
+                | DeliveryOutcome::InvalidAddress
+                | DeliveryOutcome::TooDeep
+        )
+    }
+
+    /// Whether mail is handed to a mailbox, a backup relay or another server here
+    pub fn is_destination(&self) -> bool {
+        matches!(
+            self,
+            DeliveryOutcome::Mailbox { .. }
+                | DeliveryOutcome::Relay { .. }
+                | DeliveryOutcome::External
+        )
+    }
+
+    /// What the hop matched or where it points, e.g. the alias destinations
+    pub fn detail(&self) -> String {
+        match self {
+            DeliveryOutcome::Relocated { new_address } => new_address.clone(),
+            DeliveryOutcome::Alias {
+                mail, destinations, ..
+            } => format!("{mail} → {}", destinations.join(", ")),
+            DeliveryOutcome::AliasDomain {
+                alias_domain,
+                address,
+            } => format!("{alias_domain} → {address}"),
+            DeliveryOutcome::Mailbox { maildir } => maildir.clone(),
+            DeliveryOutcome::Relay { domain, transport } => match transport {
+                Some(transport) => format!("{domain} ({transport})"),
+                None => domain.clone(),
+            },
+            _ => String::new(),
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            DeliveryOutcome::Relocated { .. } => "relocated",
+            DeliveryOutcome::Alias { .. } => "alias",
+            DeliveryOutcome::AliasDomain { .. } => "alias_domain",
+            DeliveryOutcome::Mailbox { .. } => "mailbox",
+            DeliveryOutcome::Relay { .. } => "relay",
+            DeliveryOutcome::External => "external",
+            DeliveryOutcome::Loop => "loop",
+            DeliveryOutcome::UnknownUser => "unknown_user",
+            DeliveryOutcome::UnknownDomain => "unknown_domain",
+            DeliveryOutcome::InvalidAddress => "invalid_address",
+            DeliveryOutcome::TooDeep => "too_deep",
+        }
+    }
+}
+
+/// An entry Postfix ignores because it is disabled
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DisabledEntry {
+    /// `relocated`, `domain`, `backup`, `alias-domain`, `alias` or `user`
+    pub entity: String,
+    pub name: String,
+}
+
+impl DeliveryTrace {
+    /// Mailboxes, relays and external addresses the mail ends up at
+    pub fn final_destinations(&self) -> Vec<&DeliveryHop> {
+        self.hops
+            .iter()
+            .filter(|hop| hop.outcome.is_destination())
+            .collect()
+    }
+
+    pub fn has_problems(&self) -> bool {
+        self.hops.iter().any(|hop| hop.outcome.is_problem())
+    }
+}
+
 // Cross-database domain matrix models
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CrossDatabaseDomainMatrixReport {
@@ -892,4 +1945,17 @@ impl<T> PaginatedResult<T> {
             has_prev,
         }
     }
+
+    /// The same page with each item converted
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> PaginatedResult<U> {
+        PaginatedResult {
+            items: self.items.into_iter().map(f).collect(),
+            total_count: self.total_count,
+            current_page: self.current_page,
+            per_page: self.per_page,
+            total_pages: self.total_pages,
+            has_next: self.has_next,
+            has_prev: self.has_prev,
+        }
+    }
 }
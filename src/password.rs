@@ -0,0 +1,491 @@
+//! Mailbox password hashing in the schemes Dovecot understands
+//!
+//! New hashes are written as `{SCHEME}hash`, so Dovecot verifies them whatever
+//! its `default_pass_scheme`. The `bcrypt` scheme is the exception: it keeps
+//! writing the unprefixed `$2b$` hashes of earlier versions. Verification
+//! accepts every scheme below, with or without a prefix where the hash format
+//! identifies itself.
+//!
+//! New passwords are also checked against the database's [`PasswordPolicy`],
+//! and [`generate_password`] suggests ones that satisfy it.
+
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::Engine;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha512};
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PasswordScheme {
+    /// Unprefixed bcrypt, as written by earlier versions
+    #[serde(rename = "bcrypt")]
+    #[default]
+    Bcrypt,
+    #[serde(rename = "BLF-CRYPT")]
+    BlfCrypt,
+    #[serde(rename = "SHA512-CRYPT")]
+    Sha512Crypt,
+    #[serde(rename = "ARGON2ID")]
+    Argon2id,
+    #[serde(rename = "ARGON2I")]
+    Argon2i,
+    /// The remaining schemes are only verified, for hashes imported from elsewhere
+    #[serde(rename = "SHA256-CRYPT")]
+    Sha256Crypt,
+    #[serde(rename = "MD5-CRYPT")]
+    Md5Crypt,
+    #[serde(rename = "SSHA512")]
+    Ssha512,
+    #[serde(rename = "SHA512")]
+    Sha512,
+    #[serde(rename = "SSHA256")]
+    Ssha256,
+    #[serde(rename = "SHA256")]
+    Sha256,
+    #[serde(rename = "PLAIN")]
+    Plain,
+}
+
+impl PasswordScheme {
+    pub const ALL: [PasswordScheme; 12] = [
+        PasswordScheme::Bcrypt,
+        PasswordScheme::BlfCrypt,
+        PasswordScheme::Sha512Crypt,
+        PasswordScheme::Argon2id,
+        PasswordScheme::Argon2i,
+        PasswordScheme::Sha256Crypt,
+        PasswordScheme::Md5Crypt,
+        PasswordScheme::Ssha512,
+        PasswordScheme::Sha512,
+        PasswordScheme::Ssha256,
+        PasswordScheme::Sha256,
+        PasswordScheme::Plain,
+    ];
+
+    /// The name used in config files, which is Dovecot's except for `bcrypt`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PasswordScheme::Bcrypt => "bcrypt",
+            PasswordScheme::BlfCrypt => "BLF-CRYPT",
+            PasswordScheme::Sha512Crypt => "SHA512-CRYPT",
+            PasswordScheme::Sha256Crypt => "SHA256-CRYPT",
+            PasswordScheme::Argon2id => "ARGON2ID",
+            PasswordScheme::Argon2i => "ARGON2I",
+            PasswordScheme::Md5Crypt => "MD5-CRYPT",
+            PasswordScheme::Ssha512 => "SSHA512",
+            PasswordScheme::Sha512 => "SHA512",
+            PasswordScheme::Ssha256 => "SSHA256",
+            PasswordScheme::Sha256 => "SHA256",
+            PasswordScheme::Plain => "PLAIN",
+        }
+    }
+
+    /// The name Dovecot knows the scheme by; unprefixed bcrypt is `BLF-CRYPT`
+    pub fn dovecot_name(&self) -> &'static str {
+        match self {
+            PasswordScheme::Bcrypt => "BLF-CRYPT",
+            scheme => scheme.as_str(),
+        }
+    }
+
+    /// Parse a scheme name, case-insensitively; `CLEARTEXT` is read as `PLAIN`
+    pub fn parse(name: &str) -> Option<Self> {
+        if name.eq_ignore_ascii_case("CLEARTEXT") {
+            return Some(PasswordScheme::Plain);
+        }
+        Self::ALL
+            .into_iter()
+            .find(|scheme| scheme.as_str().eq_ignore_ascii_case(name))
+    }
+
+    /// Whether new passwords may be hashed with this scheme
+    pub fn can_hash(&self) -> bool {
+        matches!(
+            self,
+            PasswordScheme::Bcrypt
+                | PasswordScheme::BlfCrypt
+                | PasswordScheme::Sha512Crypt
+                | PasswordScheme::Argon2id
+                | PasswordScheme::Argon2i
+        )
+    }
+
+    /// Relative strength, used to find hashes that should be replaced
+    pub fn strength(&self) -> u8 {
+        match self {
+            PasswordScheme::Plain => 0,
+            PasswordScheme::Sha256 | PasswordScheme::Sha512 => 1,
+            PasswordScheme::Ssha256 | PasswordScheme::Ssha512 => 2,
+            PasswordScheme::Md5Crypt => 3,
+            PasswordScheme::Sha256Crypt => 4,
+            PasswordScheme::Sha512Crypt => 5,
+            PasswordScheme::Bcrypt | PasswordScheme::BlfCrypt => 6,
+            PasswordScheme::Argon2i => 7,
+            PasswordScheme::Argon2id => 8,
+        }
+    }
+
+    /// Whether hashes of this scheme are written with a `{SCHEME}` prefix
+    pub fn is_prefixed(&self) -> bool {
+        *self != PasswordScheme::Bcrypt
+    }
+}
+
+impl std::fmt::Display for PasswordScheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// What a stored hash is, as far as its format tells
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HashInfo {
+    pub scheme: PasswordScheme,
+    /// Whether the hash carries a `{SCHEME}` prefix
+    pub prefixed: bool,
+}
+
+/// Scheme of an unprefixed crypt(3) or PHC style hash
+fn identify_crypt(hash: &str) -> Option<PasswordScheme> {
+    if ["$2a$", "$2b$", "$2x$", "$2y$"]
+        .iter()
+        .any(|id| hash.starts_with(id))
+    {
+        Some(PasswordScheme::Bcrypt)
+    } else if hash.starts_with("$6$") {
+        Some(PasswordScheme::Sha512Crypt)
+    } else if hash.starts_with("$5$") {
+        Some(PasswordScheme::Sha256Crypt)
+    } else if hash.starts_with("$1$") {
+        Some(PasswordScheme::Md5Crypt)
+    } else if hash.starts_with("$argon2id$") {
+        Some(PasswordScheme::Argon2id)
+    } else if hash.starts_with("$argon2i$") {
+        Some(PasswordScheme::Argon2i)
+    } else {
+        None
+    }
+}
+
+/// Split a stored hash into its scheme and the hash proper
+fn split_hash(hash: &str) -> Option<(HashInfo, &str)> {
+    if let Some(rest) = hash.strip_prefix('{') {
+        let (name, body) = rest.split_once('}')?;
+        // `{CRYPT}` leaves the algorithm to crypt(3), which the hash itself names
+        let scheme = if name.eq_ignore_ascii_case("CRYPT") {
+            identify_crypt(body)?
+        } else {
+            PasswordScheme::parse(name)?
+        };
+        let scheme = match scheme {
+            // A prefixed bcrypt hash is BLF-CRYPT whatever it is called
+            PasswordScheme::Bcrypt => PasswordScheme::BlfCrypt,
+            scheme => scheme,
+        };
+        return Some((
+            HashInfo {
+                scheme,
+                prefixed: true,
+            },
+            body,
+        ));
+    }
+    let scheme = identify_crypt(hash)?;
+    Some((
+        HashInfo {
+            scheme,
+            prefixed: false,
+        },
+        hash,
+    ))
+}
+
+/// The scheme of a stored hash, or `None` if it is not one we understand
+pub fn identify(hash: &str) -> Option<HashInfo> {
+    split_hash(hash).map(|(info, _)| info)
+}
+
+/// A stored hash with the `{SCHEME}` prefix that lets Dovecot read it whatever
+/// its `default_pass_scheme`, or `None` if the hash does not name its scheme
+pub fn with_prefix(hash: &str) -> Option<String> {
+    let info = identify(hash)?;
+    if info.prefixed {
+        return Some(hash.to_string());
+    }
+    let scheme = match info.scheme {
+        PasswordScheme::Bcrypt => PasswordScheme::BlfCrypt,
+        scheme => scheme,
+    };
+    Some(format!("{{{scheme}}}{hash}"))
+}
+
+/// Whether a stored hash should be replaced next time the password is set
+///
+/// That is the case for hashes we cannot identify, hashes of a weaker scheme
+/// than `scheme`, and unprefixed legacy hashes where `scheme` writes prefixed ones.
+pub fn needs_rehash(hash: &str, scheme: PasswordScheme) -> bool {
+    match identify(hash) {
+        None => true,
+        Some(info) => {
+            info.scheme.strength() < scheme.strength() || (scheme.is_prefixed() && !info.prefixed)
+        }
+    }
+}
+
+fn argon2(algorithm: Algorithm) -> Argon2<'static> {
+    Argon2::new(algorithm, Version::V0x13, Params::default())
+}
+
+/// Hash a password with `scheme`, including its `{SCHEME}` prefix
+pub fn hash_password(password: &str, scheme: PasswordScheme) -> Result<String, String> {
+    let hash = match scheme {
+        PasswordScheme::Bcrypt => {
+            bcrypt::hash(password, bcrypt::DEFAULT_COST).map_err(|e| e.to_string())?
+        }
+        // Dovecot's own BLF-CRYPT hashes use the `$2y$` variant
+        PasswordScheme::BlfCrypt => bcrypt::hash_with_result(password, bcrypt::DEFAULT_COST)
+            .map_err(|e| e.to_string())?
+            .format_for_version(bcrypt::Version::TwoY),
+        PasswordScheme::Sha512Crypt => {
+            pwhash::sha512_crypt::hash(password).map_err(|e| e.to_string())?
+        }
+        PasswordScheme::Argon2id | PasswordScheme::Argon2i => {
+            let mut salt = [0u8; 16];
+            rand::thread_rng().fill_bytes(&mut salt);
+            let salt = SaltString::encode_b64(&salt).map_err(|e| e.to_string())?;
+            let algorithm = if scheme == PasswordScheme::Argon2id {
+                Algorithm::Argon2id
+            } else {
+                Algorithm::Argon2i
+            };
+            argon2(algorithm)
+                .hash_password(password.as_bytes(), &salt)
+                .map_err(|e| e.to_string())?
+                .to_string()
+        }
+        _ => return Err(format!("{scheme} can only be used to verify passwords")),
+    };
+    Ok(if scheme.is_prefixed() {
+        format!("{{{scheme}}}{hash}")
+    } else {
+        hash
+    })
+}
+
+/// Check a base64 `{SHA*}` or `{SSHA*}` hash: the digest, followed by the salt if salted
+fn verify_digest<D: Digest>(password: &str, body: &str, salted: bool) -> Result<bool, String> {
+    let decoded = base64::engine::general_purpose::STANDARD
+        .decode(body.trim())
+        .map_err(|_| "Invalid base64 in password hash".to_string())?;
+    let size = <D as Digest>::output_size();
+    if decoded.len() < size || (!salted && decoded.len() != size) {
+        return Err("Password hash has the wrong length".to_string());
+    }
+    let (expected, salt) = decoded.split_at(size);
+    let mut digest = D::new();
+    digest.update(password.as_bytes());
+    digest.update(salt);
+    Ok(digest.finalize().as_slice() == expected)
+}
+
+/// Check a password against a stored hash of any supported scheme
+///
+/// An `Err` means the hash itself could not be used, not a wrong password.
+pub fn verify_password(password: &str, hash: &str) -> Result<bool, String> {
+    let (info, body) =
+        split_hash(hash).ok_or_else(|| "Unknown password hash format".to_string())?;
+    match info.scheme {
+        PasswordScheme::Bcrypt | PasswordScheme::BlfCrypt => {
+            bcrypt::verify(password, body).map_err(|e| e.to_string())
+        }
+        PasswordScheme::Sha512Crypt => Ok(pwhash::sha512_crypt::verify(password, body)),
+        PasswordScheme::Sha256Crypt => Ok(pwhash::sha256_crypt::verify(password, body)),
+        PasswordScheme::Md5Crypt => Ok(pwhash::md5_crypt::verify(password, body)),
+        PasswordScheme::Argon2id | PasswordScheme::Argon2i => {
+            let parsed = PasswordHash::new(body).map_err(|e| e.to_string())?;
+            Ok(Argon2::default()
+                .verify_password(password.as_bytes(), &parsed)
+                .is_ok())
+        }
+        PasswordScheme::Ssha512 => verify_digest::<Sha512>(password, body, true),
+        PasswordScheme::Sha512 => verify_digest::<Sha512>(password, body, false),
+        PasswordScheme::Ssha256 => verify_digest::<Sha256>(password, body, true),
+        PasswordScheme::Sha256 => verify_digest::<Sha256>(password, body, false),
+        PasswordScheme::Plain => Ok(body == password),
+    }
+}
+
+/// Rules for new mailbox passwords, set globally in `[password_policy]` and
+/// per database in `[databases.password_policy]`
+///
+/// Every rule is off unless configured, so installs without a policy are not
+/// held to rules they never chose.
+#[derive(Debug, Default, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct PasswordPolicy {
+    #[serde(default)]
+    pub min_length: usize,
+    #[serde(default)]
+    pub require_lowercase: bool,
+    #[serde(default)]
+    pub require_uppercase: bool,
+    #[serde(default)]
+    pub require_digit: bool,
+    #[serde(default)]
+    pub require_symbol: bool,
+    /// Reject passwords containing the mailbox address, its local part or the user's name
+    #[serde(default)]
+    pub reject_user_details: bool,
+    /// Reject passwords on the bundled list of common passwords
+    #[serde(default)]
+    pub reject_common: bool,
+}
+
+/// A way in which a password breaks the policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolicyViolation {
+    TooShort(usize),
+    MissingLowercase,
+    MissingUppercase,
+    MissingDigit,
+    MissingSymbol,
+    ContainsAddress,
+    ContainsName,
+    Common,
+}
+
+impl PolicyViolation {
+    /// The i18n message, which takes the minimum length as `$min`
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            PolicyViolation::TooShort(_) => "password-policy-too-short",
+            PolicyViolation::MissingLowercase => "password-policy-missing-lowercase",
+            PolicyViolation::MissingUppercase => "password-policy-missing-uppercase",
+            PolicyViolation::MissingDigit => "password-policy-missing-digit",
+            PolicyViolation::MissingSymbol => "password-policy-missing-symbol",
+            PolicyViolation::ContainsAddress => "password-policy-contains-address",
+            PolicyViolation::ContainsName => "password-policy-contains-name",
+            PolicyViolation::Common => "password-policy-common",
+        }
+    }
+}
+
+impl std::fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PolicyViolation::TooShort(min) => {
+                write!(f, "Password must be at least {min} characters long")
+            }
+            PolicyViolation::MissingLowercase => {
+                f.write_str("Password must contain a lowercase letter")
+            }
+            PolicyViolation::MissingUppercase => {
+                f.write_str("Password must contain an uppercase letter")
+            }
+            PolicyViolation::MissingDigit => f.write_str("Password must contain a digit"),
+            PolicyViolation::MissingSymbol => f.write_str("Password must contain a symbol"),
+            PolicyViolation::ContainsAddress => {
+                f.write_str("Password must not contain the mailbox address")
+            }
+            PolicyViolation::ContainsName => {
+                f.write_str("Password must not contain the user's name")
+            }
+            PolicyViolation::Common => f.write_str("Password is too common"),
+        }
+    }
+}
+
+/// Parts of an address or name shorter than this are not looked for in passwords
+const MIN_USER_DETAIL_LENGTH: usize = 3;
+
+const COMMON_PASSWORDS: &str = include_str!("../resources/common-passwords.txt");
+
+/// Whether a password is on the bundled list, ignoring case
+pub fn is_common_password(password: &str) -> bool {
+    static LIST: std::sync::OnceLock<std::collections::HashSet<&'static str>> =
+        std::sync::OnceLock::new();
+    let list = LIST.get_or_init(|| {
+        COMMON_PASSWORDS
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .collect()
+    });
+    list.contains(password.to_lowercase().as_str())
+}
+
+/// Check a password for the mailbox `address` owned by `name` against `policy`
+///
+/// Every violation is returned, so they can all be shown at once.
+pub fn check_policy(
+    policy: &PasswordPolicy,
+    password: &str,
+    address: &str,
+    name: &str,
+) -> Vec<PolicyViolation> {
+    let mut violations = Vec::new();
+    if password.chars().count() < policy.min_length {
+        violations.push(PolicyViolation::TooShort(policy.min_length));
+    }
+    let has = |class: fn(char) -> bool| password.chars().any(class);
+    if policy.require_lowercase && !has(char::is_lowercase) {
+        violations.push(PolicyViolation::MissingLowercase);
+    }
+    if policy.require_uppercase && !has(char::is_uppercase) {
+        violations.push(PolicyViolation::MissingUppercase);
+    }
+    if policy.require_digit && !has(|c| c.is_ascii_digit()) {
+        violations.push(PolicyViolation::MissingDigit);
+    }
+    if policy.require_symbol && !has(|c| !c.is_alphanumeric() && !c.is_whitespace()) {
+        violations.push(PolicyViolation::MissingSymbol);
+    }
+    if policy.reject_user_details {
+        let lower = password.to_lowercase();
+        let contains = |part: &str| {
+            let part = part.trim().to_lowercase();
+            part.chars().count() >= MIN_USER_DETAIL_LENGTH && lower.contains(&part)
+        };
+        let local_part = address.split('@').next().unwrap_or_default();
+        if contains(address) || contains(local_part) {
+            violations.push(PolicyViolation::ContainsAddress);
+        }
+        if contains(name) || name.split_whitespace().any(contains) {
+            violations.push(PolicyViolation::ContainsName);
+        }
+    }
+    if policy.reject_common && is_common_password(password) {
+        violations.push(PolicyViolation::Common);
+    }
+    violations
+}
+
+/// Characters of generated passwords, leaving out ones that are easily confused
+const GENERATED_CLASSES: [&[u8]; 4] = [
+    b"abcdefghijkmnopqrstuvwxyz",
+    b"ABCDEFGHJKLMNPQRSTUVWXYZ",
+    b"23456789",
+    b"-_.!@#%+=?",
+];
+
+/// Length of generated passwords, unless the policy asks for more
+const GENERATED_LENGTH: usize = 16;
+
+/// Generate a random password with every character class, long enough for `policy`
+pub fn generate_password(policy: &PasswordPolicy) -> String {
+    use rand::seq::SliceRandom;
+    use rand::Rng;
+
+    let mut rng = rand::thread_rng();
+    let length = policy.min_length.max(GENERATED_LENGTH);
+    let all: Vec<u8> = GENERATED_CLASSES.concat();
+    let mut password: Vec<u8> = GENERATED_CLASSES
+        .iter()
+        .map(|class| class[rng.gen_range(0..class.len())])
+        .collect();
+    while password.len() < length {
+        password.push(all[rng.gen_range(0..all.len())]);
+    }
+    password.shuffle(&mut rng);
+    String::from_utf8(password).expect("generated passwords are ASCII")
+}
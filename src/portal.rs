@@ -0,0 +1,132 @@
+//! Self-service portal for mailbox users
+//!
+//! Mailbox users sign in with their address and `users.crypt` password. Their
+//! forwarding is the alias on their own address: its destinations are the
+//! forwarding targets, and listing the address itself keeps a copy in the
+//! mailbox (see [`crate::delivery`]). Without that alias mail is simply
+//! delivered to the mailbox.
+
+use crate::config::Config;
+use crate::db::DatabaseManager;
+use crate::delivery::{is_valid_destination, split_destinations};
+use crate::models::{Alias, User};
+use crate::password;
+
+/// Most forwarding targets a mailbox user may set
+pub const MAX_FORWARDS: usize = 10;
+
+/// Forwarding of a mailbox, as read from the alias on its own address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Forwarding {
+    /// Where mail is forwarded to, excluding the mailbox itself
+    pub addresses: Vec<String>,
+    /// Whether the mailbox keeps a copy of forwarded mail
+    pub keep_copy: bool,
+    /// `false` when an admin has disabled the alias
+    pub enabled: bool,
+}
+
+impl Forwarding {
+    /// Read the forwarding of `address` from its alias, if it has one
+    pub fn from_alias(address: &str, alias: Option<&Alias>) -> Self {
+        let Some(alias) = alias else {
+            return Forwarding {
+                addresses: Vec::new(),
+                keep_copy: true,
+                enabled: true,
+            };
+        };
+        let address = address.to_lowercase();
+        let destinations = split_destinations(&alias.destination);
+        Forwarding {
+            keep_copy: destinations.contains(&address),
+            addresses: destinations
+                .into_iter()
+                .filter(|destination| *destination != address)
+                .collect(),
+            enabled: alias.enabled,
+        }
+    }
+
+    /// The alias destination for this forwarding, or `None` if the alias
+    /// should be removed because nothing is forwarded
+    pub fn destination(&self, address: &str) -> Option<String> {
+        if self.addresses.is_empty() {
+            return None;
+        }
+        let own = self.keep_copy.then(|| address.to_lowercase());
+        Some(
+            own.into_iter()
+                .chain(self.addresses.iter().cloned())
+                .collect::<Vec<_>>()
+                .join(", "),
+        )
+    }
+}
+
+/// Why a forwarding change was rejected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ForwardingError {
+    InvalidAddress(String),
+    TooMany,
+}
+
+impl ForwardingError {
+    /// Fluent message ID describing the problem
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            ForwardingError::InvalidAddress(_) => "portal-forwarding-invalid-address",
+            ForwardingError::TooMany => "portal-forwarding-too-many",
+        }
+    }
+}
+
+/// Parse the forwarding targets a user entered, one per line or comma separated
+///
+/// Duplicates and the user's own address (that is what "keep a copy" is for)
+/// are dropped.
+pub fn parse_forward_addresses(input: &str, address: &str) -> Result<Vec<String>, ForwardingError> {
+    let address = address.to_lowercase();
+    let mut addresses: Vec<String> = Vec::new();
+    for target in split_destinations(input) {
+        if !is_valid_destination(&target) {
+            return Err(ForwardingError::InvalidAddress(target));
+        }
+        if target != address && !addresses.contains(&target) {
+            addresses.push(target);
+        }
+    }
+    if addresses.len() > MAX_FORWARDS {
+        return Err(ForwardingError::TooMany);
+    }
+    Ok(addresses)
+}
+
+/// Find the enabled mailbox `address` whose password is `password`
+///
+/// Databases are searched in configuration order, skipping disabled ones,
+/// and the first match wins. Returns the database ID and the user.
+pub async fn authenticate(
+    db_manager: &DatabaseManager,
+    config: &Config,
+    address: &str,
+    password: &str,
+) -> Option<(String, User)> {
+    for database in db_manager.get_configs() {
+        if config.is_database_disabled(&database.id) {
+            continue;
+        }
+        let Some(pool) = db_manager.get_pool(&database.id).await else {
+            continue;
+        };
+        let Ok(user) = crate::db::get_user_by_id(&pool, address) else {
+            continue;
+        };
+        match password::verify_password(password, &user.crypt) {
+            Ok(true) if user.enabled => return Some((database.id, user)),
+            Ok(_) => {}
+            Err(e) => tracing::warn!("Cannot verify the password of {}: {}", user.id, e),
+        }
+    }
+    None
+}
@@ -0,0 +1,83 @@
+//! Mailbox quota sizes and rules.
+//!
+//! Quotas are stored as plain numbers: `NULL` on a user falls back to the
+//! domain default and `0` means unlimited, matching Dovecot's own convention.
+//! Sizes are entered and shown with binary suffixes (`512M`, `2G`).
+
+const UNITS: [(char, i64); 4] = [
+    ('T', 1 << 40),
+    ('G', 1 << 30),
+    ('M', 1 << 20),
+    ('K', 1 << 10),
+];
+
+/// Parse a size such as `1048576`, `512M`, `1.5G` or `2GiB` into bytes
+pub fn parse_size(input: &str) -> Result<i64, String> {
+    let text = input.trim();
+    let upper = text.to_ascii_uppercase();
+    let number = upper
+        .strip_suffix("IB")
+        .or_else(|| upper.strip_suffix('B'))
+        .unwrap_or(&upper)
+        .trim_end();
+
+    let (digits, multiplier) = match number.chars().last() {
+        Some(suffix) if suffix.is_ascii_alphabetic() => {
+            let Some((_, multiplier)) = UNITS.iter().find(|(unit, _)| *unit == suffix) else {
+                return Err(format!("Unknown size unit in '{text}'"));
+            };
+            (number[..number.len() - 1].trim_end(), *multiplier)
+        }
+        _ => (number, 1),
+    };
+
+    let value: f64 = digits
+        .parse()
+        .map_err(|_| format!("Invalid size '{text}'"))?;
+    if !value.is_finite() || value < 0.0 {
+        return Err(format!("Invalid size '{text}'"));
+    }
+    let bytes = (value * multiplier as f64).round();
+    if bytes > i64::MAX as f64 {
+        return Err(format!("Size '{text}' is too large"));
+    }
+    Ok(bytes as i64)
+}
+
+/// Parse a message count, which may not be negative
+pub fn parse_count(input: &str) -> Result<i64, String> {
+    let text = input.trim();
+    match text.parse::<i64>() {
+        Ok(count) if count >= 0 => Ok(count),
+        _ => Err(format!("Invalid message count '{text}'")),
+    }
+}
+
+/// Format bytes with the largest suffix that keeps the value exact, so the
+/// result parses back to the same number in a form field
+pub fn format_size(bytes: i64) -> String {
+    UNITS
+        .iter()
+        .find(|(_, multiplier)| bytes >= *multiplier && bytes % multiplier == 0)
+        .map(|(unit, multiplier)| format!("{}{unit}", bytes / multiplier))
+        .unwrap_or_else(|| bytes.to_string())
+}
+
+/// Format bytes for display, rounded to one decimal place
+pub fn human_size(bytes: i64) -> String {
+    UNITS
+        .iter()
+        .find(|(_, multiplier)| bytes >= *multiplier)
+        .map(|(unit, multiplier)| {
+            let value = bytes as f64 / *multiplier as f64;
+            let text = format!("{value:.1}");
+            format!("{} {unit}iB", text.trim_end_matches(".0"))
+        })
+        .unwrap_or_else(|| format!("{bytes} B"))
+}
+
+/// The quota that applies to a mailbox: its own value, else the domain default,
+/// else unlimited (`0`)
+pub fn effective(own: Option<i64>, domain_default: Option<i64>) -> i64 {
+    own.or(domain_default).unwrap_or(0)
+}
@@ -0,0 +1,91 @@
+//! Reloading the config file while the server is running
+//!
+//! The config file is reloaded on `SIGHUP` and whenever it changes on disk. A
+//! reload that fails to parse, validate or connect to a new database keeps the
+//! running configuration and is reported on the config page.
+
+use crate::config::ReloadFailure;
+use crate::AppState;
+use std::time::{Duration, SystemTime};
+
+/// How often the config file is checked for changes
+const WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Reload the config file into the running server
+///
+/// Database pools are brought in line first, so the new configuration is only
+/// swapped in once every configured database can be used.
+pub async fn reload_config(state: &AppState) -> Result<(), String> {
+    let result = apply_config_file(state).await;
+    match &result {
+        Ok(()) => {
+            tracing::info!("Configuration reloaded");
+            state.config.set_reload_failure(None);
+        }
+        Err(e) => {
+            tracing::error!("Failed to reload configuration, keeping the current one: {e}");
+            state.config.set_reload_failure(Some(ReloadFailure {
+                error: e.clone(),
+                failed_at: chrono::Utc::now(),
+            }));
+        }
+    }
+    result
+}
+
+async fn apply_config_file(state: &AppState) -> Result<(), String> {
+    let config = state.config.read_file().map_err(|e| e.to_string())?;
+    state
+        .db_manager
+        .reload(&config)
+        .await
+        .map_err(|e| e.to_string())?;
+    state.config.replace(config);
+    Ok(())
+}
+
+/// Modification time and size of the config file, to notice edits
+fn file_stamp(state: &AppState) -> Option<(SystemTime, u64)> {
+    let metadata = std::fs::metadata(state.config.path()?).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Reload the config file on `SIGHUP` and when it changes on disk
+pub fn spawn_watchers(state: AppState) {
+    if state.config.path().is_none() {
+        tracing::info!("No config file loaded, configuration reloading is disabled");
+        return;
+    }
+
+    let watch_state = state.clone();
+    tokio::spawn(async move {
+        let mut stamp = file_stamp(&watch_state);
+        let mut interval = tokio::time::interval(WATCH_INTERVAL);
+        loop {
+            interval.tick().await;
+            let current = file_stamp(&watch_state);
+            // A missing file is usually an editor replacing it; wait for the new one
+            if current.is_some() && current != stamp {
+                stamp = current;
+                let _ = reload_config(&watch_state).await;
+            }
+        }
+    });
+
+    #[cfg(unix)]
+    tokio::spawn(async move {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut hangup = match signal(SignalKind::hangup()) {
+            Ok(hangup) => hangup,
+            Err(e) => {
+                tracing::warn!("Failed to listen for SIGHUP: {}", e);
+                return;
+            }
+        };
+        while hangup.recv().await.is_some() {
+            tracing::info!("Received SIGHUP, reloading configuration");
+            let _ = reload_config(&state).await;
+        }
+    });
+}
@@ -0,0 +1,538 @@
+//! Restoring export bundles into a database
+//!
+//! A restore compares a [`Bundle`] with the bundle of the target database and
+//! plans a create, update or delete for every record whose natural key
+//! differs, checked against the target's feature toggles. Merging keeps
+//! records the bundle does not mention; replacing deletes them. The plan is
+//! the dry run: nothing is written until it is applied with
+//! [`crate::db::apply_restore`], which writes through the target's field map.
+
+use crate::bundle::{self, Bundle};
+use crate::config::DatabaseConfig;
+use crate::import::FieldChange;
+use crate::models::AuditEntity;
+use crate::DbPool;
+use diesel::result::Error;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// A record as logical field names and values
+pub type Record = Map<String, Value>;
+
+/// What happens to target records the bundle does not contain
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum RestoreMode {
+    /// Create and update the bundle's records and keep the others
+    #[default]
+    Merge,
+    /// Also delete the records the bundle does not contain
+    Replace,
+}
+
+impl RestoreMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            RestoreMode::Merge => "merge",
+            RestoreMode::Replace => "replace",
+        }
+    }
+}
+
+/// A table of a bundle, in the order records are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BundleTable {
+    Domains,
+    Backups,
+    AliasDomains,
+    Users,
+    Aliases,
+    Relays,
+    Relocated,
+    Clients,
+    Vacations,
+}
+
+impl BundleTable {
+    pub const ALL: [BundleTable; 9] = [
+        BundleTable::Domains,
+        BundleTable::Backups,
+        BundleTable::AliasDomains,
+        BundleTable::Users,
+        BundleTable::Aliases,
+        BundleTable::Relays,
+        BundleTable::Relocated,
+        BundleTable::Clients,
+        BundleTable::Vacations,
+    ];
+
+    /// The table in the database schema
+    pub fn name(&self) -> &'static str {
+        match self {
+            BundleTable::Domains => "domains",
+            BundleTable::Backups => "backups",
+            BundleTable::AliasDomains => "alias_domains",
+            BundleTable::Users => "users",
+            BundleTable::Aliases => "aliases",
+            BundleTable::Relays => "relays",
+            BundleTable::Relocated => "relocated",
+            BundleTable::Clients => "clients",
+            BundleTable::Vacations => "vacation",
+        }
+    }
+
+    /// The table named `name`, as returned by [`BundleTable::name`]
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|table| table.name() == name)
+    }
+
+    /// The natural key records are matched by
+    pub fn key_field(&self) -> &'static str {
+        match self {
+            BundleTable::Domains | BundleTable::Backups => "domain",
+            BundleTable::AliasDomains => "alias_domain",
+            BundleTable::Users => "id",
+            BundleTable::Aliases => "mail",
+            BundleTable::Relays => "recipient",
+            BundleTable::Relocated => "old_address",
+            BundleTable::Clients => "client",
+            BundleTable::Vacations => "user_id",
+        }
+    }
+
+    /// The columns a restore writes, key first
+    pub fn fields(&self) -> &'static [&'static str] {
+        match self {
+            BundleTable::Domains => &[
+                "domain",
+                "transport",
+                "enabled",
+                "default_quota_bytes",
+                "default_quota_messages",
+                "quota_cap_bytes",
+            ],
+            BundleTable::Backups => &["domain", "transport", "enabled"],
+            BundleTable::AliasDomains => &["alias_domain", "target_domain", "enabled"],
+            BundleTable::Users => &[
+                "id",
+                "crypt",
+                "name",
+                "maildir",
+                "home",
+                "uid",
+                "gid",
+                "enabled",
+                "change_password",
+                "quota_bytes",
+                "quota_messages",
+            ],
+            BundleTable::Aliases => &[
+                "mail",
+                "destination",
+                "enabled",
+                "valid_from",
+                "valid_until",
+            ],
+            BundleTable::Relays => &["recipient", "status", "enabled"],
+            BundleTable::Relocated => &[
+                "old_address",
+                "new_address",
+                "enabled",
+                "valid_from",
+                "valid_until",
+            ],
+            BundleTable::Clients => &["client", "status", "enabled"],
+            BundleTable::Vacations => &[
+                "user_id",
+                "subject",
+                "body",
+                "active_from",
+                "active_until",
+                "reply_interval_days",
+                "enabled",
+            ],
+        }
+    }
+
+    /// The creation and modification time columns, set when records are written
+    pub fn timestamps(&self) -> (&'static str, &'static str) {
+        match self {
+            BundleTable::Clients => ("created_at", "updated_at"),
+            _ => ("created", "modified"),
+        }
+    }
+
+    pub fn entity(&self) -> AuditEntity {
+        match self {
+            BundleTable::Domains => AuditEntity::Domain,
+            BundleTable::Backups => AuditEntity::Backup,
+            BundleTable::AliasDomains => AuditEntity::AliasDomain,
+            BundleTable::Users => AuditEntity::User,
+            BundleTable::Aliases => AuditEntity::Alias,
+            BundleTable::Relays => AuditEntity::Relay,
+            BundleTable::Relocated => AuditEntity::Relocated,
+            BundleTable::Clients => AuditEntity::Client,
+            BundleTable::Vacations => AuditEntity::Vacation,
+        }
+    }
+
+    /// Fluent message ID of the table's label
+    pub fn message_id(&self) -> String {
+        format!("audit-entity-{}", self.entity().as_str().replace('_', "-"))
+    }
+
+    /// The operation checked against the feature toggles, e.g. `create_user`
+    pub fn operation(&self, action: RestoreAction) -> String {
+        let verb = match action {
+            RestoreAction::Create => "create",
+            RestoreAction::Update | RestoreAction::Unchanged => "update",
+            RestoreAction::Delete => "delete",
+        };
+        format!("{verb}_{}", self.entity().as_str())
+    }
+
+    /// The bundle's records of this table
+    pub fn records(&self, bundle: &Bundle) -> Vec<Record> {
+        fn to_records<T: Serialize>(records: &[T]) -> Vec<Record> {
+            records
+                .iter()
+                .filter_map(|record| match serde_json::to_value(record) {
+                    Ok(Value::Object(map)) => Some(map),
+                    _ => None,
+                })
+                .collect()
+        }
+        match self {
+            BundleTable::Domains => to_records(&bundle.domains),
+            BundleTable::Backups => to_records(&bundle.backups),
+            BundleTable::AliasDomains => to_records(&bundle.alias_domains),
+            BundleTable::Users => to_records(&bundle.users)
+                .into_iter()
+                .map(|mut record| {
+                    // Hashes are stored in `crypt`
+                    if let Some(hash) = record.remove("password_hash") {
+                        record.insert("crypt".to_string(), hash);
+                    }
+                    record
+                })
+                .collect(),
+            BundleTable::Aliases => to_records(&bundle.aliases),
+            BundleTable::Relays => to_records(&bundle.relays),
+            BundleTable::Relocated => to_records(&bundle.relocated),
+            BundleTable::Clients => to_records(&bundle.clients),
+            BundleTable::Vacations => to_records(&bundle.vacations),
+        }
+    }
+}
+
+/// What applying the plan does with a record
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RestoreAction {
+    Create,
+    Update,
+    Delete,
+    Unchanged,
+}
+
+impl RestoreAction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            RestoreAction::Create => "create",
+            RestoreAction::Update => "update",
+            RestoreAction::Delete => "delete",
+            RestoreAction::Unchanged => "unchanged",
+        }
+    }
+}
+
+/// Why a change cannot be applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum RestoreIssue {
+    /// The target's feature toggles or the admin's grant forbid it
+    NotAllowed,
+    /// A new user without a password hash in the bundle
+    PasswordRequired,
+}
+
+impl RestoreIssue {
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            RestoreIssue::NotAllowed => "restore-error-not-allowed",
+            RestoreIssue::PasswordRequired => "restore-error-password-required",
+        }
+    }
+}
+
+impl std::fmt::Display for RestoreIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RestoreIssue::NotAllowed => f.write_str("Not allowed on this database"),
+            RestoreIssue::PasswordRequired => f.write_str("The bundle has no password hash"),
+        }
+    }
+}
+
+/// A planned change to one record
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RestoreChange {
+    pub table: BundleTable,
+    pub key: String,
+    pub action: RestoreAction,
+    /// Fields an update changes
+    pub changes: Vec<FieldChange>,
+    pub issue: Option<RestoreIssue>,
+    /// The target's record, for updates and deletes
+    #[serde(skip)]
+    pub before: Option<Record>,
+    /// The bundle's record, for creates and updates
+    #[serde(skip)]
+    pub after: Option<Record>,
+}
+
+impl RestoreChange {
+    /// Whether applying the plan writes this change
+    pub fn is_write(&self) -> bool {
+        self.issue.is_none() && self.action != RestoreAction::Unchanged
+    }
+
+    /// Status shown in previews: the action, or `error` when it cannot be applied
+    pub fn status(&self) -> &'static str {
+        match self.issue {
+            Some(_) => "error",
+            None => self.action.name(),
+        }
+    }
+}
+
+/// Every change restoring a bundle makes, in write order
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RestorePlan {
+    pub mode: RestoreMode,
+    pub changes: Vec<RestoreChange>,
+}
+
+impl RestorePlan {
+    /// Writable changes with the action
+    pub fn count(&self, action: RestoreAction) -> usize {
+        self.changes
+            .iter()
+            .filter(|change| change.action == action && change.issue.is_none())
+            .count()
+    }
+
+    /// Changes that cannot be applied
+    pub fn problems(&self) -> impl Iterator<Item = &RestoreChange> {
+        self.changes.iter().filter(|change| change.issue.is_some())
+    }
+
+    /// Changes applying the plan writes
+    pub fn writes(&self) -> impl Iterator<Item = &RestoreChange> {
+        self.changes.iter().filter(|change| change.is_write())
+    }
+}
+
+/// Why applying a plan failed; nothing was written
+#[derive(Debug)]
+pub struct RestoreFailure {
+    /// The record whose write failed, if the failure belongs to one
+    pub record: Option<(BundleTable, String)>,
+    pub error: Error,
+}
+
+impl From<Error> for RestoreFailure {
+    fn from(error: Error) -> Self {
+        RestoreFailure {
+            record: None,
+            error,
+        }
+    }
+}
+
+impl std::fmt::Display for RestoreFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.record {
+            Some((table, key)) => write!(f, "{} {key}: {}", table.name(), self.error),
+            None => write!(f, "{}", self.error),
+        }
+    }
+}
+
+impl std::error::Error for RestoreFailure {}
+
+/// The target's column for a logical field
+fn column<'a>(table: BundleTable, field: &'a str, db_config: &'a DatabaseConfig) -> &'a str {
+    db_config.mapped_field(table.name(), field).unwrap_or(field)
+}
+
+/// `INSERT` of `fields`, with a placeholder for each, stamping the time columns
+pub fn insert_sql(table: BundleTable, fields: &[&str], db_config: &DatabaseConfig) -> String {
+    let (created, modified) = table.timestamps();
+    let columns: Vec<&str> = fields
+        .iter()
+        .chain([&created, &modified])
+        .map(|field| column(table, field, db_config))
+        .collect();
+    let placeholders: Vec<&str> = fields
+        .iter()
+        .map(|_| "?")
+        .chain(["NOW()", "NOW()"])
+        .collect();
+    format!(
+        "INSERT INTO {} ({}) VALUES ({})",
+        table.name(),
+        columns.join(", "),
+        placeholders.join(", ")
+    )
+}
+
+/// `UPDATE` of `fields` of the record whose key is bound last
+pub fn update_sql(table: BundleTable, fields: &[&str], db_config: &DatabaseConfig) -> String {
+    let (_, modified) = table.timestamps();
+    let assignments: Vec<String> = fields
+        .iter()
+        .map(|field| format!("{} = ?", column(table, field, db_config)))
+        .chain([format!("{} = NOW()", column(table, modified, db_config))])
+        .collect();
+    format!(
+        "UPDATE {} SET {} WHERE {} = ?",
+        table.name(),
+        assignments.join(", "),
+        column(table, table.key_field(), db_config)
+    )
+}
+
+/// `DELETE` of the record whose key is bound
+pub fn delete_sql(table: BundleTable, db_config: &DatabaseConfig) -> String {
+    format!(
+        "DELETE FROM {} WHERE {} = ?",
+        table.name(),
+        column(table, table.key_field(), db_config)
+    )
+}
+
+/// `SELECT` of each record's key and modification time, as `record_key` and `modified`
+pub fn modified_sql(table: BundleTable, db_config: &DatabaseConfig) -> String {
+    let (_, modified) = table.timestamps();
+    format!(
+        "SELECT CAST({} AS CHAR) AS record_key, {} AS modified FROM {}",
+        column(table, table.key_field(), db_config),
+        column(table, modified, db_config),
+        table.name()
+    )
+}
+
+/// A field value as shown in previews
+pub fn display_value(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => s.clone(),
+        Some(value) => value.to_string(),
+    }
+}
+
+fn key_of(table: BundleTable, record: &Record) -> String {
+    display_value(record.get(table.key_field()))
+}
+
+/// Plans restoring `incoming` over the `current` records of the target
+///
+/// Keys match case-insensitively, like the database collation. `allowed` is
+/// asked for each operation, e.g. `create_user`, and changes it refuses are
+/// planned with [`RestoreIssue::NotAllowed`].
+pub fn plan(
+    current: &Bundle,
+    incoming: &Bundle,
+    mode: RestoreMode,
+    allowed: impl Fn(&str) -> bool,
+) -> RestorePlan {
+    let mut changes = Vec::new();
+    for table in BundleTable::ALL {
+        let mut existing: HashMap<String, Record> = table
+            .records(current)
+            .into_iter()
+            .map(|record| (key_of(table, &record).to_lowercase(), record))
+            .collect();
+
+        for record in table.records(incoming) {
+            let key = key_of(table, &record);
+            let before = existing.remove(&key.to_lowercase());
+            let mut change = RestoreChange {
+                table,
+                key,
+                action: RestoreAction::Create,
+                changes: Vec::new(),
+                issue: None,
+                before: None,
+                after: None,
+            };
+            match before {
+                Some(before) => {
+                    change.changes = table
+                        .fields()
+                        .iter()
+                        .filter(|field| **field != table.key_field())
+                        // Bundles without hashes leave the target's passwords alone
+                        .filter(|field| record.get(**field).is_some())
+                        .filter(|field| before.get(**field) != record.get(**field))
+                        .map(|field| FieldChange {
+                            field,
+                            old: display_value(before.get(*field)),
+                            new: display_value(record.get(*field)),
+                        })
+                        .collect();
+                    change.action = if change.changes.is_empty() {
+                        RestoreAction::Unchanged
+                    } else {
+                        RestoreAction::Update
+                    };
+                    change.before = Some(before);
+                }
+                None if table == BundleTable::Users && record.get("crypt").is_none() => {
+                    change.issue = Some(RestoreIssue::PasswordRequired);
+                }
+                None => {}
+            }
+            change.after = Some(record);
+            changes.push(change);
+        }
+
+        if mode == RestoreMode::Replace {
+            let mut removed: Vec<Record> = existing.into_values().collect();
+            removed.sort_by_key(|record| key_of(table, record));
+            changes.extend(removed.into_iter().map(|record| RestoreChange {
+                table,
+                key: key_of(table, &record),
+                action: RestoreAction::Delete,
+                changes: Vec::new(),
+                issue: None,
+                before: Some(record),
+                after: None,
+            }));
+        }
+    }
+
+    for change in &mut changes {
+        if change.issue.is_none()
+            && change.action != RestoreAction::Unchanged
+            && !allowed(&change.table.operation(change.action))
+        {
+            change.issue = Some(RestoreIssue::NotAllowed);
+        }
+    }
+    RestorePlan { mode, changes }
+}
+
+/// Plans restoring `incoming` into the database of `db_config`
+pub fn plan_for(
+    pool: &DbPool,
+    db_config: &DatabaseConfig,
+    incoming: &Bundle,
+    mode: RestoreMode,
+    allowed: impl Fn(&str) -> bool,
+) -> Result<RestorePlan, Error> {
+    let current = bundle::export(pool, db_config, incoming.password_hashes)?;
+    Ok(plan(&current, incoming, mode, allowed))
+}
@@ -1,15 +1,64 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    admin_sessions (id) {
+        #[max_length = 64]
+        id -> Varchar,
+        #[max_length = 255]
+        username -> Varchar,
+        #[max_length = 20]
+        role -> Varchar,
+        #[max_length = 255]
+        database_id -> Varchar,
+        created -> Datetime,
+        last_seen -> Datetime,
+    }
+}
+
+diesel::table! {
+    alias_domains (pkid) {
+        pkid -> Integer,
+        #[max_length = 255]
+        alias_domain -> Varchar,
+        #[max_length = 255]
+        target_domain -> Varchar,
+        created -> Datetime,
+        modified -> Datetime,
+        enabled -> Bool,
+    }
+}
+
 diesel::table! {
     aliases (pkid) {
         pkid -> Integer,
         #[max_length = 255]
         mail -> Varchar,
-        #[max_length = 255]
-        destination -> Varchar,
+        destination -> Text,
         created -> Datetime,
         modified -> Datetime,
         enabled -> Bool,
+        valid_from -> Nullable<Datetime>,
+        valid_until -> Nullable<Datetime>,
+    }
+}
+
+diesel::table! {
+    audit_log (id) {
+        id -> Bigint,
+        created -> Datetime,
+        #[max_length = 255]
+        admin -> Varchar,
+        #[max_length = 255]
+        database_id -> Varchar,
+        #[max_length = 32]
+        entity_type -> Varchar,
+        #[max_length = 255]
+        entity_id -> Varchar,
+        #[max_length = 255]
+        entity_name -> Varchar,
+        #[max_length = 32]
+        action -> Varchar,
+        changes -> Text,
     }
 }
 
@@ -49,6 +98,20 @@ diesel::table! {
         created -> Datetime,
         modified -> Datetime,
         enabled -> Bool,
+        default_quota_bytes -> Nullable<Bigint>,
+        default_quota_messages -> Nullable<Bigint>,
+        quota_cap_bytes -> Nullable<Bigint>,
+    }
+}
+
+diesel::table! {
+    mailbox_usage (id) {
+        id -> Bigint,
+        #[max_length = 255]
+        user_id -> Varchar,
+        bytes -> Bigint,
+        messages -> Bigint,
+        collected -> Datetime,
     }
 }
 
@@ -75,6 +138,8 @@ diesel::table! {
         enabled -> Bool,
         created -> Datetime,
         modified -> Datetime,
+        valid_from -> Nullable<Datetime>,
+        valid_until -> Nullable<Datetime>,
     }
 }
 
@@ -96,9 +161,38 @@ diesel::table! {
         modified -> Datetime,
         enabled -> Bool,
         change_password -> Bool,
+        quota_bytes -> Nullable<Bigint>,
+        quota_messages -> Nullable<Bigint>,
+    }
+}
+
+diesel::table! {
+    vacation (user_id) {
+        #[max_length = 255]
+        user_id -> Varchar,
+        #[max_length = 255]
+        subject -> Varchar,
+        body -> Text,
+        active_from -> Nullable<Date>,
+        active_until -> Nullable<Date>,
+        reply_interval_days -> Integer,
+        enabled -> Bool,
+        created -> Datetime,
+        modified -> Datetime,
     }
 }
 
 diesel::allow_tables_to_appear_in_same_query!(
-    aliases, backups, clients, domains, relays, relocated, users,
+    admin_sessions,
+    alias_domains,
+    aliases,
+    audit_log,
+    backups,
+    clients,
+    domains,
+    mailbox_usage,
+    relays,
+    relocated,
+    users,
+    vacation,
 );
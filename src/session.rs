@@ -0,0 +1,375 @@
+use crate::config::{AdminRole, SessionConfig, SessionStoreKind};
+use crate::models::AdminSession;
+use crate::schema::admin_sessions;
+use crate::DbPool;
+use axum::http::HeaderMap;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{Duration, Utc};
+use diesel::prelude::*;
+use diesel::result::Error;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Name of the cookie carrying the signed session ID
+pub const SESSION_COOKIE: &str = "sortingoffice_session";
+
+/// Name of the cookie carrying a mailbox user's portal session, scoped to `/portal`
+pub const PORTAL_COOKIE: &str = "sortingoffice_portal";
+
+/// Session role for mailbox users signed in to the self-service portal
+pub const MAILBOX_ROLE: &str = "mailbox";
+
+/// How stale `last_seen` may get before a request refreshes it. Keeps the
+/// MySQL store from writing on every single request.
+const TOUCH_INTERVAL_SECONDS: i64 = 60;
+
+#[derive(Clone)]
+enum SessionBackend {
+    Memory(Arc<RwLock<HashMap<String, AdminSession>>>),
+    Mysql(DbPool),
+}
+
+/// Server-side store for admin and portal login sessions
+///
+/// The browser only ever holds a random session ID signed with HMAC-SHA256;
+/// the role and selected database live here and cannot be forged client side.
+/// Mailbox sessions use their own cookie and are never accepted as admin
+/// sessions, or the other way around.
+#[derive(Clone)]
+pub struct SessionStore {
+    backend: SessionBackend,
+    key: Arc<Vec<u8>>,
+    idle_timeout: Duration,
+    absolute_timeout: Duration,
+    secure_cookie: bool,
+}
+
+impl Default for SessionStore {
+    fn default() -> Self {
+        Self::new(&SessionConfig::default(), None)
+    }
+}
+
+impl SessionStore {
+    /// Create a session store from configuration
+    ///
+    /// `pool` is only used when `store = "mysql"`; without it the store falls
+    /// back to memory.
+    pub fn new(config: &SessionConfig, pool: Option<DbPool>) -> Self {
+        let key = match &config.secret {
+            Some(secret) if !secret.is_empty() => secret.as_bytes().to_vec(),
+            _ => {
+                tracing::warn!(
+                    "No session secret configured; generated a random one. Sessions will not survive a restart."
+                );
+                let mut key = vec![0u8; 32];
+                rand::thread_rng().fill_bytes(&mut key);
+                key
+            }
+        };
+
+        let backend = match (&config.store, pool) {
+            (SessionStoreKind::Mysql, Some(pool)) => SessionBackend::Mysql(pool),
+            (SessionStoreKind::Mysql, None) => {
+                tracing::warn!("MySQL session store requested but no pool available; using memory");
+                SessionBackend::Memory(Arc::new(RwLock::new(HashMap::new())))
+            }
+            (SessionStoreKind::Memory, _) => {
+                SessionBackend::Memory(Arc::new(RwLock::new(HashMap::new())))
+            }
+        };
+
+        SessionStore {
+            backend,
+            key: Arc::new(key),
+            idle_timeout: Duration::minutes(config.idle_timeout_minutes as i64),
+            absolute_timeout: Duration::hours(config.absolute_timeout_hours as i64),
+            secure_cookie: config.secure_cookie,
+        }
+    }
+
+    /// Start a new session for a freshly authenticated admin
+    pub fn create(
+        &self,
+        username: &str,
+        role: &AdminRole,
+        database_id: &str,
+    ) -> Result<AdminSession, Error> {
+        self.insert(username, role.as_str(), database_id)
+    }
+
+    /// Start a portal session for a mailbox user of `database_id`
+    pub fn create_mailbox(&self, address: &str, database_id: &str) -> Result<AdminSession, Error> {
+        self.insert(address, MAILBOX_ROLE, database_id)
+    }
+
+    fn insert(&self, username: &str, role: &str, database_id: &str) -> Result<AdminSession, Error> {
+        let mut id_bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut id_bytes);
+        let now = Utc::now().naive_utc();
+
+        let session = AdminSession {
+            id: URL_SAFE_NO_PAD.encode(id_bytes),
+            username: username.to_string(),
+            role: role.to_string(),
+            database_id: database_id.to_string(),
+            created: now,
+            last_seen: now,
+        };
+
+        match &self.backend {
+            SessionBackend::Memory(sessions) => {
+                sessions
+                    .write()
+                    .unwrap()
+                    .insert(session.id.clone(), session.clone());
+            }
+            SessionBackend::Mysql(pool) => {
+                let mut conn = get_conn(pool)?;
+                diesel::insert_into(admin_sessions::table)
+                    .values(&session)
+                    .execute(&mut conn)?;
+            }
+        }
+
+        Ok(session)
+    }
+
+    /// Look up the live admin session referenced by the request cookie
+    ///
+    /// Returns `None` for missing, tampered, unknown or timed-out sessions.
+    /// Timed-out sessions are revoked as a side effect.
+    pub fn from_headers(&self, headers: &HeaderMap) -> Option<AdminSession> {
+        self.lookup(headers, SESSION_COOKIE)
+            .filter(|session| !session.is_mailbox())
+    }
+
+    /// Look up the live portal session referenced by the request cookie
+    pub fn mailbox_from_headers(&self, headers: &HeaderMap) -> Option<AdminSession> {
+        self.lookup(headers, PORTAL_COOKIE)
+            .filter(AdminSession::is_mailbox)
+    }
+
+    fn lookup(&self, headers: &HeaderMap, cookie_name: &str) -> Option<AdminSession> {
+        let session_id = self.cookie_session_id(headers, cookie_name)?;
+        let session = match self.load(&session_id) {
+            Ok(session) => session?,
+            Err(e) => {
+                tracing::error!("Failed to load session: {:?}", e);
+                return None;
+            }
+        };
+
+        let now = Utc::now().naive_utc();
+        if now - session.created > self.absolute_timeout
+            || now - session.last_seen > self.idle_timeout
+        {
+            tracing::debug!("Session for '{}' has expired", session.username);
+            self.revoke(&session_id);
+            return None;
+        }
+
+        if now - session.last_seen > Duration::seconds(TOUCH_INTERVAL_SECONDS) {
+            if let Err(e) = self.touch(&session_id) {
+                tracing::warn!("Failed to refresh session activity: {:?}", e);
+            }
+        }
+
+        Some(session)
+    }
+
+    /// Change the database selected in an existing session
+    pub fn set_database(&self, session_id: &str, database_id: &str) -> Result<bool, Error> {
+        match &self.backend {
+            SessionBackend::Memory(sessions) => {
+                let mut sessions = sessions.write().unwrap();
+                match sessions.get_mut(session_id) {
+                    Some(session) => {
+                        session.database_id = database_id.to_string();
+                        Ok(true)
+                    }
+                    None => Ok(false),
+                }
+            }
+            SessionBackend::Mysql(pool) => {
+                let mut conn = get_conn(pool)?;
+                let updated = diesel::update(admin_sessions::table.find(session_id))
+                    .set(admin_sessions::database_id.eq(database_id))
+                    .execute(&mut conn)?;
+                Ok(updated > 0)
+            }
+        }
+    }
+
+    /// Remove a session so its cookie can no longer be used
+    pub fn revoke(&self, session_id: &str) {
+        match &self.backend {
+            SessionBackend::Memory(sessions) => {
+                sessions.write().unwrap().remove(session_id);
+            }
+            SessionBackend::Mysql(pool) => {
+                let result = get_conn(pool).and_then(|mut conn| {
+                    diesel::delete(admin_sessions::table.find(session_id)).execute(&mut conn)
+                });
+                if let Err(e) = result {
+                    tracing::error!("Failed to revoke session: {:?}", e);
+                }
+            }
+        }
+    }
+
+    /// Revoke the session referenced by the request cookie, if any
+    pub fn revoke_from_headers(&self, headers: &HeaderMap) {
+        if let Some(session_id) = self.session_id_from_headers(headers) {
+            self.revoke(&session_id);
+        }
+    }
+
+    /// Revoke the portal session referenced by the request cookie, if any
+    pub fn revoke_mailbox_from_headers(&self, headers: &HeaderMap) {
+        if let Some(session) = self.mailbox_from_headers(headers) {
+            self.revoke(&session.id);
+        }
+    }
+
+    /// Drop every session past its idle or absolute timeout
+    pub fn purge_expired(&self) -> Result<usize, Error> {
+        let now = Utc::now().naive_utc();
+        let idle_cutoff = now - self.idle_timeout;
+        let absolute_cutoff = now - self.absolute_timeout;
+
+        match &self.backend {
+            SessionBackend::Memory(sessions) => {
+                let mut sessions = sessions.write().unwrap();
+                let before = sessions.len();
+                sessions.retain(|_, s| s.last_seen >= idle_cutoff && s.created >= absolute_cutoff);
+                Ok(before - sessions.len())
+            }
+            SessionBackend::Mysql(pool) => {
+                let mut conn = get_conn(pool)?;
+                diesel::delete(
+                    admin_sessions::table.filter(
+                        admin_sessions::last_seen
+                            .lt(idle_cutoff)
+                            .or(admin_sessions::created.lt(absolute_cutoff)),
+                    ),
+                )
+                .execute(&mut conn)
+            }
+        }
+    }
+
+    /// `Set-Cookie` value establishing the given session in the browser
+    ///
+    /// Mailbox sessions get the portal cookie, which is only sent to `/portal`.
+    pub fn cookie_header(&self, session: &AdminSession) -> String {
+        let (name, path) = if session.is_mailbox() {
+            (PORTAL_COOKIE, "/portal")
+        } else {
+            (SESSION_COOKIE, "/")
+        };
+        let max_age = self.absolute_timeout.num_seconds();
+        let secure = if self.secure_cookie { "; Secure" } else { "" };
+        format!(
+            "{name}={}; Path={path}; Max-Age={max_age}; HttpOnly; SameSite=Lax{secure}",
+            self.sign(&session.id)
+        )
+    }
+
+    /// `Set-Cookie` value removing the session cookie from the browser
+    pub fn clear_cookie_header(&self) -> String {
+        let secure = if self.secure_cookie { "; Secure" } else { "" };
+        format!("{SESSION_COOKIE}=; Path=/; Max-Age=0; HttpOnly; SameSite=Lax{secure}")
+    }
+
+    /// `Set-Cookie` value removing the portal cookie from the browser
+    pub fn clear_portal_cookie_header(&self) -> String {
+        let secure = if self.secure_cookie { "; Secure" } else { "" };
+        format!("{PORTAL_COOKIE}=; Path=/portal; Max-Age=0; HttpOnly; SameSite=Lax{secure}")
+    }
+
+    /// Signed cookie value for a session ID: `<id>.<signature>`
+    pub fn sign(&self, session_id: &str) -> String {
+        let mut mac = HmacSha256::new_from_slice(&self.key).expect("HMAC accepts any key length");
+        mac.update(session_id.as_bytes());
+        let signature = URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes());
+        format!("{session_id}.{signature}")
+    }
+
+    /// Verify a signed cookie value and return the session ID it carries
+    pub fn verify(&self, cookie_value: &str) -> Option<String> {
+        let (session_id, signature) = cookie_value.rsplit_once('.')?;
+        let signature = URL_SAFE_NO_PAD.decode(signature).ok()?;
+        let mut mac = HmacSha256::new_from_slice(&self.key).expect("HMAC accepts any key length");
+        mac.update(session_id.as_bytes());
+        mac.verify_slice(&signature).ok()?;
+        Some(session_id.to_string())
+    }
+
+    /// Extract and verify the session ID from the request cookies
+    pub fn session_id_from_headers(&self, headers: &HeaderMap) -> Option<String> {
+        self.cookie_session_id(headers, SESSION_COOKIE)
+    }
+
+    fn cookie_session_id(&self, headers: &HeaderMap, cookie_name: &str) -> Option<String> {
+        let cookie_str = headers.get("cookie")?.to_str().ok()?;
+        cookie_str
+            .split(';')
+            .filter_map(|cookie| cookie.trim().strip_prefix(cookie_name)?.strip_prefix('='))
+            .find_map(|value| self.verify(value))
+    }
+
+    fn load(&self, session_id: &str) -> Result<Option<AdminSession>, Error> {
+        match &self.backend {
+            SessionBackend::Memory(sessions) => {
+                Ok(sessions.read().unwrap().get(session_id).cloned())
+            }
+            SessionBackend::Mysql(pool) => {
+                let mut conn = get_conn(pool)?;
+                admin_sessions::table
+                    .find(session_id)
+                    .select(AdminSession::as_select())
+                    .first::<AdminSession>(&mut conn)
+                    .optional()
+            }
+        }
+    }
+
+    fn touch(&self, session_id: &str) -> Result<(), Error> {
+        let now = Utc::now().naive_utc();
+        match &self.backend {
+            SessionBackend::Memory(sessions) => {
+                if let Some(session) = sessions.write().unwrap().get_mut(session_id) {
+                    session.last_seen = now;
+                }
+                Ok(())
+            }
+            SessionBackend::Mysql(pool) => {
+                let mut conn = get_conn(pool)?;
+                diesel::update(admin_sessions::table.find(session_id))
+                    .set(admin_sessions::last_seen.eq(now))
+                    .execute(&mut conn)?;
+                Ok(())
+            }
+        }
+    }
+}
+
+fn get_conn(
+    pool: &DbPool,
+) -> Result<diesel::r2d2::PooledConnection<diesel::r2d2::ConnectionManager<MysqlConnection>>, Error>
+{
+    pool.get().map_err(|e| {
+        tracing::error!("Failed to get connection from pool: {:?}", e);
+        Error::DatabaseError(
+            diesel::result::DatabaseErrorKind::Unknown,
+            Box::new(e.to_string()),
+        )
+    })
+}
@@ -0,0 +1,189 @@
+//! Comparing two databases and syncing chosen records one way
+//!
+//! A diff is a replacing [restore plan](crate::restore::plan) of the source's
+//! records over the target's, limited to the compared tables: creates are
+//! records missing from the target, deletes records only the target has and
+//! updates records whose fields differ. Each change carries both sides'
+//! modification times so drift can be judged before syncing. Applying a
+//! [`SyncDiff::selection`] with [`crate::db::apply_restore`] writes the chosen
+//! changes through the target's field map, checked against its feature toggles.
+
+use crate::bundle::{self, Bundle};
+use crate::config::DatabaseConfig;
+use crate::db;
+use crate::restore::{self, BundleTable, RestoreAction, RestoreChange, RestoreMode, RestorePlan};
+use crate::DbPool;
+use chrono::NaiveDateTime;
+use diesel::result::Error;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// The tables compared unless others are chosen: those secondary MX databases share
+pub const DEFAULT_TABLES: [BundleTable; 3] = [
+    BundleTable::Domains,
+    BundleTable::Backups,
+    BundleTable::Relays,
+];
+
+/// Shown instead of password hashes, which a diff never reveals
+pub const HIDDEN: &str = "********";
+
+/// Modification times by table and lower-cased natural key
+pub type ModifiedTimes = HashMap<(BundleTable, String), NaiveDateTime>;
+
+/// One of the two compared databases
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncSide {
+    Source,
+    Target,
+}
+
+impl SyncSide {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SyncSide::Source => "source",
+            SyncSide::Target => "target",
+        }
+    }
+}
+
+/// The differences between a source and a target database
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncDiff {
+    /// Every compared record, as the change syncing it makes to the target
+    pub plan: RestorePlan,
+    pub source_modified: ModifiedTimes,
+    pub target_modified: ModifiedTimes,
+}
+
+/// A record of a diff, as the CLI prints it
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncRow<'a> {
+    #[serde(flatten)]
+    pub change: &'a RestoreChange,
+    pub source_modified: Option<NaiveDateTime>,
+    pub target_modified: Option<NaiveDateTime>,
+    pub newer: Option<SyncSide>,
+}
+
+/// Identifies a change of a diff in a selection, e.g. `domains:example.com`
+pub fn row_id(change: &RestoreChange) -> String {
+    format!("{}:{}", change.table.name(), change.key)
+}
+
+impl SyncDiff {
+    /// Compares the `tables` of `source` with those of `target`; `allowed` is
+    /// asked for each operation on the target, as for a restore
+    pub fn new(
+        source: &Bundle,
+        target: &Bundle,
+        tables: &[BundleTable],
+        allowed: impl Fn(&str) -> bool,
+    ) -> Self {
+        let mut plan = restore::plan(target, source, RestoreMode::Replace, allowed);
+        plan.changes.retain(|change| tables.contains(&change.table));
+        for change in &mut plan.changes {
+            for field in change.changes.iter_mut().filter(|c| c.field == "crypt") {
+                field.old = HIDDEN.to_string();
+                field.new = HIDDEN.to_string();
+            }
+        }
+        SyncDiff {
+            plan,
+            source_modified: ModifiedTimes::new(),
+            target_modified: ModifiedTimes::new(),
+        }
+    }
+
+    /// Records missing from either side or differing between them
+    pub fn differences(&self) -> impl Iterator<Item = &RestoreChange> {
+        self.plan
+            .changes
+            .iter()
+            .filter(|change| change.action != RestoreAction::Unchanged)
+    }
+
+    /// When the record of `change` was last modified on `side`
+    pub fn modified(&self, side: SyncSide, change: &RestoreChange) -> Option<NaiveDateTime> {
+        let times = match side {
+            SyncSide::Source => &self.source_modified,
+            SyncSide::Target => &self.target_modified,
+        };
+        times
+            .get(&(change.table, change.key.to_lowercase()))
+            .copied()
+    }
+
+    /// The side whose record of `change` was modified later, if both have one
+    pub fn newer(&self, change: &RestoreChange) -> Option<SyncSide> {
+        let source = self.modified(SyncSide::Source, change)?;
+        let target = self.modified(SyncSide::Target, change)?;
+        match source.cmp(&target) {
+            std::cmp::Ordering::Greater => Some(SyncSide::Source),
+            std::cmp::Ordering::Less => Some(SyncSide::Target),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+
+    /// The differences with their modification times
+    pub fn rows(&self) -> Vec<SyncRow<'_>> {
+        self.differences()
+            .map(|change| SyncRow {
+                change,
+                source_modified: self.modified(SyncSide::Source, change),
+                target_modified: self.modified(SyncSide::Target, change),
+                newer: self.newer(change),
+            })
+            .collect()
+    }
+
+    /// The plan syncing only the changes whose [`row_id`] is `chosen`
+    pub fn selection(&self, chosen: &[String]) -> RestorePlan {
+        RestorePlan {
+            mode: RestoreMode::Replace,
+            changes: self
+                .differences()
+                .filter(|change| chosen.contains(&row_id(change)))
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+fn modified_times(
+    pool: &DbPool,
+    db_config: &DatabaseConfig,
+    tables: &[BundleTable],
+) -> Result<ModifiedTimes, Error> {
+    let mut times = ModifiedTimes::new();
+    for table in tables {
+        times.extend(
+            db::get_modified_times(pool, db_config, *table)?
+                .into_iter()
+                .map(|(key, modified)| ((*table, key), modified)),
+        );
+    }
+    Ok(times)
+}
+
+/// Compares the `tables` of two databases
+///
+/// Password hashes are read only when users are compared, so that users
+/// missing from the target can be created with theirs.
+pub fn diff(
+    source_pool: &DbPool,
+    source_config: &DatabaseConfig,
+    target_pool: &DbPool,
+    target_config: &DatabaseConfig,
+    tables: &[BundleTable],
+    allowed: impl Fn(&str) -> bool,
+) -> Result<SyncDiff, Error> {
+    let password_hashes = tables.contains(&BundleTable::Users);
+    let source = bundle::export(source_pool, source_config, password_hashes)?;
+    let target = bundle::export(target_pool, target_config, password_hashes)?;
+    let mut diff = SyncDiff::new(&source, &target, tables, allowed);
+    diff.source_modified = modified_times(source_pool, source_config, tables)?;
+    diff.target_modified = modified_times(target_pool, target_config, tables)?;
+    Ok(diff)
+}
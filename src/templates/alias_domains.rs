@@ -0,0 +1,81 @@
+use crate::models::{AliasDomain, AliasDomainForm};
+use askama::Template;
+
+#[derive(Template)]
+#[template(path = "alias_domains/list.html", escape = "html")]
+pub struct AliasDomainListTemplate<'a> {
+    pub title: &'a str,
+    pub description: &'a str,
+    pub add_alias_domain: &'a str,
+    pub table_header_alias_domain: &'a str,
+    pub table_header_target_domain: &'a str,
+    pub table_header_enabled: &'a str,
+    pub table_header_actions: &'a str,
+    pub status_enabled: &'a str,
+    pub status_disabled: &'a str,
+    pub action_view: &'a str,
+    pub action_enable: &'a str,
+    pub action_disable: &'a str,
+    pub empty_title: &'a str,
+    pub empty_description: &'a str,
+    pub alias_domains: Vec<AliasDomain>,
+}
+
+#[derive(Template)]
+#[template(path = "alias_domains/show.html")]
+pub struct AliasDomainShowTemplate<'a> {
+    pub show_title: &'a str,
+    pub view_edit_settings: &'a str,
+    pub back_to_list: &'a str,
+    pub info_title: &'a str,
+    pub info_description: &'a str,
+    pub field_alias_domain: &'a str,
+    pub field_target_domain: &'a str,
+    pub field_enabled: &'a str,
+    pub field_created: &'a str,
+    pub field_modified: &'a str,
+    pub status_enabled: &'a str,
+    pub status_disabled: &'a str,
+    pub action_edit: &'a str,
+    pub action_enable: &'a str,
+    pub action_disable: &'a str,
+    pub action_delete: &'a str,
+    pub delete_confirm: &'a str,
+    pub alias_domain: AliasDomain,
+    /// The target's domain page, if the target still exists
+    pub target_domain_id: Option<i32>,
+}
+
+#[derive(Template)]
+#[template(path = "alias_domains/for_domain.html")]
+pub struct DomainAliasDomainsTemplate<'a> {
+    pub title: &'a str,
+    pub description: &'a str,
+    pub empty: &'a str,
+    pub add_alias_domain: &'a str,
+    pub status_enabled: &'a str,
+    pub status_disabled: &'a str,
+    pub alias_domains: Vec<AliasDomain>,
+}
+
+#[derive(Template)]
+#[template(path = "alias_domains/form.html", escape = "html")]
+pub struct AliasDomainFormTemplate<'a> {
+    pub title: &'a str,
+    pub action: &'a str,
+    pub editing: bool,
+    pub form: AliasDomainForm,
+    /// Domains an alias domain can point at
+    pub domains: Vec<String>,
+    pub error: Option<String>,
+    pub form_error: &'a str,
+    pub field_alias_domain: &'a str,
+    pub field_target_domain: &'a str,
+    pub field_enabled: &'a str,
+    pub field_alias_domain_help: &'a str,
+    pub field_target_domain_help: &'a str,
+    pub placeholder_alias_domain: &'a str,
+    pub select_target: &'a str,
+    pub action_save: &'a str,
+    pub action_cancel: &'a str,
+}
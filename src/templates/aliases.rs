@@ -1,5 +1,8 @@
 use crate::models::{Alias, AliasForm, PaginatedResult};
+use crate::templates::validity::ValidityLabels;
+use crate::validity::WindowStatus;
 use askama::Template;
+use chrono::NaiveDateTime;
 
 #[derive(Template)]
 #[template(path = "aliases/list.html", escape = "html")]
@@ -22,6 +25,12 @@ pub struct AliasesListTemplate<'a> {
     pub disable_alias: &'a str,
     pub empty_title: &'a str,
     pub empty_description: &'a str,
+    /// Only aliases whose validity window ends soon are listed
+    pub expiring_only: bool,
+    pub filter_all: &'a str,
+    pub filter_expiring: &'a str,
+    pub validity: ValidityLabels,
+    pub now: NaiveDateTime,
 }
 
 #[derive(Template)]
@@ -45,6 +54,8 @@ pub struct AliasShowTemplate<'a> {
     pub disable_alias_button: &'a str,
     pub delete_alias: &'a str,
     pub delete_confirm: &'a str,
+    pub validity: ValidityLabels,
+    pub now: NaiveDateTime,
 }
 
 #[derive(Template)]
@@ -61,16 +72,51 @@ pub struct AliasFormTemplate<'a> {
     pub mail_address: &'a str,
     pub destination: &'a str,
     pub placeholder_mail: &'a str,
-    pub placeholder_destination: &'a str,
     pub tooltip_mail: &'a str,
     pub tooltip_destination: &'a str,
     pub active: &'a str,
     pub tooltip_active: &'a str,
+    pub validity: ValidityLabels,
     pub cancel: &'a str,
     pub update_alias: &'a str,
     pub create_alias: &'a str,
 }
 
+/// One destination of an alias, resolved on its own
+#[derive(Debug, Clone)]
+pub struct DestinationRow {
+    pub address: String,
+    /// Translated outcome of the destination itself, empty when it is not resolved
+    pub label: String,
+    /// Whether the address is invalid or mail to it does not reach everyone
+    pub problem: bool,
+    /// Mailboxes, relays and external addresses an alias destination ends up at
+    pub reaches: Vec<String>,
+}
+
+#[derive(Template)]
+#[template(path = "aliases/destination_editor.html", escape = "html")]
+pub struct DestinationEditorTemplate<'a> {
+    /// The destinations as one comma-separated list, as submitted with the form
+    pub destination: &'a str,
+    pub rows: &'a [DestinationRow],
+    pub new_destination: &'a str,
+    pub error: Option<&'a str>,
+    pub placeholder: &'a str,
+    pub add: &'a str,
+    pub remove: &'a str,
+    pub empty: &'a str,
+    pub reaches: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "aliases/destination_list.html", escape = "html")]
+pub struct DestinationListTemplate<'a> {
+    pub rows: &'a [DestinationRow],
+    pub empty: &'a str,
+    pub reaches: &'a str,
+}
+
 #[derive(Template)]
 #[template(path = "aliases/search_results.html", escape = "html")]
 pub struct AliasSearchResultsTemplate<'a> {
@@ -0,0 +1,59 @@
+use crate::models::{AuditFieldChange, AuditLogEntry, PaginatedResult};
+use askama::Template;
+
+/// An audit entry prepared for display, with translated labels and decoded changes
+#[derive(Debug, Clone)]
+pub struct AuditRow {
+    pub entry: AuditLogEntry,
+    pub entity_label: String,
+    pub action_label: String,
+    pub changes: Vec<AuditFieldChange>,
+    pub url: Option<String>,
+}
+
+#[derive(Template)]
+#[template(path = "audit/list.html", escape = "html")]
+pub struct AuditListTemplate<'a> {
+    pub title: &'a str,
+    pub description: &'a str,
+    pub filter_admin: &'a str,
+    pub filter_entity: &'a str,
+    pub filter_from: &'a str,
+    pub filter_to: &'a str,
+    pub filter_all: &'a str,
+    pub filter_apply: &'a str,
+    pub filter_reset: &'a str,
+    pub table_header_time: &'a str,
+    pub table_header_admin: &'a str,
+    pub table_header_database: &'a str,
+    pub table_header_entity: &'a str,
+    pub table_header_name: &'a str,
+    pub table_header_action: &'a str,
+    pub table_header_changes: &'a str,
+    pub empty_title: &'a str,
+    pub empty_description: &'a str,
+    pub no_field_changes: &'a str,
+    pub admins: &'a [String],
+    pub entity_options: &'a [(String, String)],
+    pub selected_admin: &'a str,
+    pub selected_entity: &'a str,
+    pub selected_from: &'a str,
+    pub selected_to: &'a str,
+    pub rows: &'a [AuditRow],
+    pub pagination: &'a PaginatedResult<AuditLogEntry>,
+    pub page_range: &'a [i64],
+    pub max_item: i64,
+}
+
+#[derive(Template)]
+#[template(path = "audit/history.html", escape = "html")]
+pub struct AuditHistoryTemplate<'a> {
+    pub title: &'a str,
+    pub empty_message: &'a str,
+    pub table_header_time: &'a str,
+    pub table_header_admin: &'a str,
+    pub table_header_action: &'a str,
+    pub table_header_changes: &'a str,
+    pub no_field_changes: &'a str,
+    pub rows: &'a [AuditRow],
+}
@@ -9,6 +9,9 @@ pub struct LoginTemplate<'a> {
     pub user_id: &'a str,
     pub password: &'a str,
     pub sign_in: &'a str,
+    pub form_action: &'a str,
+    pub switch_url: &'a str,
+    pub switch_label: &'a str,
     pub app_title: &'a str,
     pub app_subtitle: &'a str,
     pub language_selector: &'a str,
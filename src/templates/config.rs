@@ -38,11 +38,17 @@ pub struct ConfigTemplate<'a> {
     pub feature_no_password_updates: &'a str,
     pub feature_database_disabled: &'a str,
     pub status_enabled: &'a str,
-    pub status_disabled: &'a str,
     // Database Feature Toggles
     pub database_features_header: &'a str,
     pub database_features_description: &'a str,
     pub database_disabled_badge: &'a str,
+    pub success_message: Option<&'a str>,
+    pub error_message: Option<&'a str>,
+    /// Why the config file on disk is not the one in use
+    pub reload_error: Option<&'a str>,
+    /// Link to the generated mail server config, for admins who can edit
+    pub mail_config_link: Option<&'a str>,
     pub config: &'a Config,
-    pub domain_overrides_vec: Vec<(&'a String, &'a crate::config::DomainOverride)>,
+    /// Each override with the alias input placeholder for its domain
+    pub domain_overrides_vec: Vec<(&'a String, &'a crate::config::DomainOverride, String)>,
 }
@@ -16,6 +16,8 @@ pub struct DashboardTemplate<'a> {
     pub enabled_domains_and_backups: &'a str,
     pub enabled_aliases: &'a str,
     pub enabled_users: &'a str,
+    pub allocated_quota: &'a str,
+    pub expiring_aliases: &'a str,
     pub quick_actions: &'a str,
 
     // New quick action sections
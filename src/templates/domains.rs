@@ -1,4 +1,5 @@
 use crate::models::{Backup, Domain, DomainForm, PaginatedResult};
+use crate::templates::quota::QuotaLabels;
 use askama::Template;
 
 #[derive(Template)]
@@ -48,6 +49,7 @@ pub struct DomainShowTemplate<'a> {
     pub domain_details: &'a str,
     pub domain_name: &'a str,
     pub transport: &'a str,
+    pub quota: QuotaLabels,
     pub status: &'a str,
     pub status_active: &'a str,
     pub status_inactive: &'a str,
@@ -104,4 +106,5 @@ pub struct DomainFormTemplate<'a> {
     pub form_tooltip_domain: &'a str,
     pub form_tooltip_transport: &'a str,
     pub form_tooltip_enable: &'a str,
+    pub quota: QuotaLabels,
 }
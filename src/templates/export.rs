@@ -0,0 +1,22 @@
+use askama::Template;
+
+/// A database that can be exported, and whether its password hashes may be included
+#[derive(Debug, Clone)]
+pub struct ExportDatabase {
+    pub id: String,
+    pub label: String,
+    pub can_export_hashes: bool,
+}
+
+#[derive(Template)]
+#[template(path = "export.html", escape = "html")]
+pub struct ExportTemplate<'a> {
+    pub title: &'a str,
+    pub description: &'a str,
+    pub format_label: &'a str,
+    pub password_hashes_label: &'a str,
+    pub password_hashes_hint: &'a str,
+    pub download: &'a str,
+    pub no_databases: &'a str,
+    pub databases: &'a [ExportDatabase],
+}
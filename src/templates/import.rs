@@ -0,0 +1,67 @@
+use askama::Template;
+
+/// A column of the uploaded file and the field it is mapped to
+#[derive(Debug, Clone)]
+pub struct ImportColumn {
+    pub header: String,
+    pub field: String,
+}
+
+/// A row of the preview with translated status and details
+#[derive(Debug, Clone)]
+pub struct ImportRowView {
+    pub line: usize,
+    pub key: String,
+    pub status: &'static str,
+    pub status_label: String,
+    pub details: Vec<String>,
+}
+
+#[derive(Template)]
+#[template(path = "import/index.html", escape = "html")]
+pub struct ImportTemplate<'a> {
+    pub title: &'a str,
+    pub description: &'a str,
+    pub kind_label: &'a str,
+    pub file_label: &'a str,
+    pub file_hint: &'a str,
+    pub update_existing_label: &'a str,
+    pub preview: &'a str,
+    pub kind_options: &'a [(String, String)],
+    pub selected_kind: &'a str,
+    pub update_existing: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Template)]
+#[template(path = "import/preview.html", escape = "html")]
+pub struct ImportPreviewTemplate<'a> {
+    pub title: &'a str,
+    pub mapping_title: &'a str,
+    pub mapping_ignore: &'a str,
+    pub update_existing_label: &'a str,
+    pub update_preview: &'a str,
+    pub apply: &'a str,
+    pub apply_hint: &'a str,
+    pub download_report: &'a str,
+    pub start_over: &'a str,
+    pub table_header_line: &'a str,
+    pub table_header_key: &'a str,
+    pub table_header_status: &'a str,
+    pub table_header_details: &'a str,
+    pub kind: &'a str,
+    pub kind_label: &'a str,
+    pub content: &'a str,
+    pub update_existing: bool,
+    pub fields: &'a [&'static str],
+    pub columns: &'a [ImportColumn],
+    /// Each status with its label and number of rows
+    pub counts: &'a [(&'static str, String, usize)],
+    pub rows: &'a [ImportRowView],
+    pub has_problems: bool,
+    pub can_apply: bool,
+    /// Set once the import has been applied, or has failed
+    pub notice: Option<String>,
+    pub failed: bool,
+    pub applied: bool,
+}
@@ -14,9 +14,16 @@ pub struct BaseTemplate {
     pub nav_users: String,
     pub nav_relays: String,
     pub nav_relocated: String,
+    pub nav_alias_domains: String,
     pub nav_clients: String,
     pub nav_statistics: String,
     pub nav_reports: String,
+    pub nav_audit: String,
+    pub nav_import: String,
+    pub nav_export: String,
+    pub nav_restore: String,
+    pub nav_legacy: String,
+    pub nav_sync: String,
     pub nav_config: String,
     pub nav_database: String,
     pub nav_about: String,
@@ -31,6 +38,8 @@ pub struct BaseTemplate {
     pub current_locale: String,
     pub current_db_label: String,
     pub current_db_id: String,
+    /// Hides the navigation a delegated domain admin cannot use
+    pub domain_admin: bool,
 }
 
 #[derive(Template)]
@@ -47,9 +56,16 @@ pub struct LayoutTemplate<'a> {
     pub nav_users: &'a str,
     pub nav_relays: &'a str,
     pub nav_relocated: &'a str,
+    pub nav_alias_domains: &'a str,
     pub nav_clients: &'a str,
     pub nav_statistics: &'a str,
     pub nav_reports: &'a str,
+    pub nav_audit: &'a str,
+    pub nav_import: &'a str,
+    pub nav_export: &'a str,
+    pub nav_restore: &'a str,
+    pub nav_legacy: &'a str,
+    pub nav_sync: &'a str,
     pub nav_config: &'a str,
     pub nav_database: &'a str,
     pub nav_about: &'a str,
@@ -64,6 +80,7 @@ pub struct LayoutTemplate<'a> {
     pub current_locale: &'a str,
     pub current_db_label: &'a str,
     pub current_db_id: &'a str,
+    pub domain_admin: bool,
 }
 
 impl BaseTemplate {
@@ -86,9 +103,17 @@ impl BaseTemplate {
             nav_users: crate::i18n::get_translation(state, locale, "nav-users").await,
             nav_relays: crate::i18n::get_translation(state, locale, "nav-relays").await,
             nav_relocated: crate::i18n::get_translation(state, locale, "nav-relocated").await,
+            nav_alias_domains: crate::i18n::get_translation(state, locale, "nav-alias-domains")
+                .await,
             nav_clients: crate::i18n::get_translation(state, locale, "nav-clients").await,
             nav_statistics: crate::i18n::get_translation(state, locale, "nav-statistics").await,
             nav_reports: crate::i18n::get_translation(state, locale, "nav-reports").await,
+            nav_audit: crate::i18n::get_translation(state, locale, "nav-audit").await,
+            nav_import: crate::i18n::get_translation(state, locale, "nav-import").await,
+            nav_export: crate::i18n::get_translation(state, locale, "nav-export").await,
+            nav_restore: crate::i18n::get_translation(state, locale, "nav-restore").await,
+            nav_legacy: crate::i18n::get_translation(state, locale, "nav-legacy").await,
+            nav_sync: crate::i18n::get_translation(state, locale, "nav-sync").await,
             nav_config: crate::i18n::get_translation(state, locale, "nav-config").await,
             nav_database: crate::i18n::get_translation(state, locale, "nav-database").await,
             nav_about: crate::i18n::get_translation(state, locale, "nav-about").await,
@@ -105,6 +130,14 @@ impl BaseTemplate {
             current_locale: locale.to_string(),
             current_db_label,
             current_db_id,
+            domain_admin: false,
         })
     }
+
+    /// Adapt the navigation to the admin making the request
+    pub fn for_request(mut self, state: &crate::AppState, headers: &axum::http::HeaderMap) -> Self {
+        self.domain_admin = crate::handlers::auth::get_admin(state, headers)
+            .is_some_and(|admin| admin.is_domain_admin());
+        self
+    }
 }
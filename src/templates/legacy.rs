@@ -0,0 +1,66 @@
+use crate::legacy::LegacyOptions;
+use crate::templates::restore::RestoreChangeView;
+use askama::Template;
+
+/// Something of the source that was not converted, with a translated explanation
+#[derive(Debug, Clone)]
+pub struct LegacyNoteView {
+    pub table: String,
+    pub key: String,
+    pub issue_label: String,
+    pub detail: String,
+}
+
+#[derive(Template)]
+#[template(path = "legacy/index.html", escape = "html")]
+pub struct LegacyTemplate<'a> {
+    pub title: &'a str,
+    pub description: &'a str,
+    pub schema_label: &'a str,
+    pub database_label: &'a str,
+    pub mode_label: &'a str,
+    pub url_label: &'a str,
+    pub url_hint: &'a str,
+    pub file_label: &'a str,
+    pub file_hint: &'a str,
+    pub home_label: &'a str,
+    pub uid_label: &'a str,
+    pub gid_label: &'a str,
+    pub preview: &'a str,
+    pub no_databases: &'a str,
+    pub databases: &'a [(String, String)],
+    pub selected_database: &'a str,
+    pub schema_options: &'a [(String, String)],
+    pub selected_schema: &'a str,
+    pub mode_options: &'a [(String, String)],
+    pub selected_mode: &'a str,
+    pub url: &'a str,
+    pub options: &'a LegacyOptions,
+    pub error: Option<String>,
+}
+
+/// Shares `restore/changes.html` with the restore preview, hence the same field names
+#[derive(Template)]
+#[template(path = "legacy/preview.html", escape = "html")]
+pub struct LegacyPreviewTemplate<'a> {
+    pub title: &'a str,
+    pub summary: &'a str,
+    pub apply: &'a str,
+    pub apply_hint: &'a str,
+    pub start_over: &'a str,
+    pub no_changes: &'a str,
+    pub notes_title: &'a str,
+    pub table_header_table: &'a str,
+    pub table_header_key: &'a str,
+    pub table_header_status: &'a str,
+    pub table_header_details: &'a str,
+    pub table_header_note: &'a str,
+    pub table_header_value: &'a str,
+    pub database: &'a str,
+    pub mode: &'a str,
+    pub content: &'a str,
+    pub counts: &'a [(&'static str, String, usize)],
+    pub changes: &'a [RestoreChangeView],
+    pub notes: &'a [LegacyNoteView],
+    pub can_apply: bool,
+}
@@ -0,0 +1,23 @@
+use crate::mail_config::GeneratedFile;
+use askama::Template;
+
+/// Generated files for one database, or the reason they could not be generated
+#[derive(Debug, Clone)]
+pub struct MailConfigDatabase {
+    pub id: String,
+    pub label: String,
+    pub files: Vec<GeneratedFile>,
+    pub error: Option<String>,
+}
+
+#[derive(Template)]
+#[template(path = "mail_config.html", escape = "html")]
+pub struct MailConfigTemplate<'a> {
+    pub title: &'a str,
+    pub description: &'a str,
+    pub download_all: &'a str,
+    pub download_database: &'a str,
+    pub no_databases: &'a str,
+    pub credentials_warning: &'a str,
+    pub databases: &'a [MailConfigDatabase],
+}
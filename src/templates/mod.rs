@@ -1,5 +1,7 @@
 pub mod about;
+pub mod alias_domains;
 pub mod aliases;
+pub mod audit;
 pub mod auth;
 pub mod backups;
 pub mod clients;
@@ -7,9 +9,19 @@ pub mod config;
 pub mod dashboard;
 pub mod database;
 pub mod domains;
+pub mod export;
+pub mod import;
 pub mod layout;
+pub mod legacy;
+pub mod mail_config;
+pub mod portal;
+pub mod quota;
 pub mod relays;
 pub mod relocated;
 pub mod reports;
+pub mod restore;
 pub mod stats;
+pub mod sync;
 pub mod users;
+pub mod vacation;
+pub mod validity;
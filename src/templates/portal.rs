@@ -0,0 +1,49 @@
+use crate::models::Alias;
+use askama::Template;
+
+/// The mailbox user's portal page
+#[derive(Template)]
+#[template(path = "portal/index.html", escape = "html")]
+pub struct PortalTemplate {
+    pub title: String,
+    pub app_title: String,
+    pub app_subtitle: String,
+    pub theme_toggle: String,
+    pub language_selector: String,
+    pub language_english: String,
+    pub language_spanish: String,
+    pub language_french: String,
+    pub language_norwegian: String,
+    pub language_german: String,
+    pub sign_out: String,
+    pub current_locale: String,
+    pub address: String,
+    pub name: String,
+    pub notice: Option<String>,
+    pub error: Option<String>,
+    pub must_change_password: bool,
+    pub must_change_password_notice: String,
+    pub can_change_password: bool,
+    pub password_blocked_notice: String,
+    pub password_title: String,
+    pub current_password_label: String,
+    pub new_password_label: String,
+    pub confirm_password_label: String,
+    pub change_password_button: String,
+    pub aliases: Vec<Alias>,
+    pub aliases_title: String,
+    pub aliases_help: String,
+    pub aliases_empty: String,
+    pub alias_disabled: String,
+    pub can_edit_forwarding: bool,
+    pub forwarding_enabled: bool,
+    pub forwarding_title: String,
+    pub forwarding_help: String,
+    pub forwarding_addresses: String,
+    pub forwarding_addresses_label: String,
+    pub keep_copy: bool,
+    pub keep_copy_label: String,
+    pub forwarding_disabled_notice: String,
+    pub forwarding_blocked_notice: String,
+    pub save_forwarding_button: String,
+}
@@ -0,0 +1,15 @@
+/// Labels for the quota fields shared by the user and domain pages
+#[derive(Debug, Clone)]
+pub struct QuotaLabels {
+    pub title: String,
+    pub size: String,
+    pub messages: String,
+    pub default_size: String,
+    pub default_messages: String,
+    pub cap: String,
+    pub size_hint: String,
+    pub user_hint: String,
+    pub domain_hint: String,
+    pub cap_hint: String,
+    pub domain_default: String,
+}
@@ -1,5 +1,8 @@
 use crate::models::Relocated;
+use crate::templates::validity::ValidityLabels;
+use crate::validity::WindowStatus;
 use askama::Template;
+use chrono::NaiveDateTime;
 
 #[derive(Template)]
 #[template(path = "relocated/list.html", escape = "html")]
@@ -20,6 +23,8 @@ pub struct RelocatedListTemplate<'a> {
     pub empty_description: &'a str,
     pub relocated: Vec<Relocated>,
     pub relocated_list_description: &'a str,
+    pub validity: ValidityLabels,
+    pub now: NaiveDateTime,
 }
 
 #[derive(Template)]
@@ -45,6 +50,8 @@ pub struct RelocatedShowTemplate<'a> {
     pub relocated_info_title: &'a str,
     pub relocated_info_description: &'a str,
     pub relocated: Relocated,
+    pub validity: ValidityLabels,
+    pub now: NaiveDateTime,
 }
 
 #[derive(Template)]
@@ -63,4 +70,5 @@ pub struct RelocatedFormTemplate<'a> {
     pub back_to_list: &'a str,
     pub placeholder_old_address: &'a str,
     pub placeholder_new_address: &'a str,
+    pub validity: ValidityLabels,
 }
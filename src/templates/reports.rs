@@ -14,6 +14,7 @@ pub struct MatrixReportTemplate<'a> {
     pub status_missing: &'a str,
     pub status_disabled: &'a str,
     pub legend_title: &'a str,
+    pub alias_domain_of: &'a str,
     pub no_domains: &'a str,
     pub no_domains_description: &'a str,
     pub report: &'a crate::models::DomainAliasMatrixReport,
@@ -32,6 +33,8 @@ pub struct ReportsListTemplate<'a> {
     pub external_forwarders_report_description: &'a str,
     pub alias_cross_domain_report_title: &'a str,
     pub alias_cross_domain_report_description: &'a str,
+    pub delivery_report_title: &'a str,
+    pub delivery_report_description: &'a str,
     pub cross_database_matrix_report_title: &'a str,
     pub cross_database_matrix_report_description: &'a str,
     pub cross_database_user_distribution_report_title: &'a str,
@@ -43,11 +46,44 @@ pub struct ReportsListTemplate<'a> {
     pub view_report: &'a str,
 }
 
+/// A delivery hop prepared for display
+#[derive(Debug, Clone)]
+pub struct DeliveryRow {
+    pub hop: crate::models::DeliveryHop,
+    pub label: String,
+    /// What the hop matched or where it points, e.g. the alias destinations
+    pub detail: String,
+    pub problem: bool,
+    /// Disabled entries that were skipped, with translated entity names
+    pub disabled: Vec<String>,
+}
+
+#[derive(Template)]
+#[template(path = "reports/delivery.html", escape = "html")]
+pub struct DeliveryReportTemplate<'a> {
+    pub title: &'a str,
+    pub description: &'a str,
+    pub address_label: &'a str,
+    pub submit: &'a str,
+    pub route_header: &'a str,
+    pub final_header: &'a str,
+    pub no_final: &'a str,
+    pub problems: &'a str,
+    pub disabled_label: &'a str,
+    pub address: &'a str,
+    pub traced: bool,
+    pub has_problems: bool,
+    pub rows: &'a [DeliveryRow],
+    pub final_rows: &'a [DeliveryRow],
+}
+
 #[derive(Template)]
 #[template(path = "reports/orphaned.html", escape = "html")]
 pub struct OrphanedReportTemplate<'a> {
     pub title: &'a str,
     pub report: &'a crate::models::OrphanedAliasReport,
+    /// Translated outcome of each unresolved destination, in report order
+    pub outcomes: &'a [String],
 }
 
 #[derive(Template)]
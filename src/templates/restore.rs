@@ -0,0 +1,52 @@
+use askama::Template;
+
+/// A changed record of a restore preview with translated labels
+#[derive(Debug, Clone)]
+pub struct RestoreChangeView {
+    pub table_label: String,
+    pub key: String,
+    pub status: &'static str,
+    pub status_label: String,
+    pub details: Vec<String>,
+}
+
+#[derive(Template)]
+#[template(path = "restore/index.html", escape = "html")]
+pub struct RestoreTemplate<'a> {
+    pub title: &'a str,
+    pub description: &'a str,
+    pub database_label: &'a str,
+    pub mode_label: &'a str,
+    pub file_label: &'a str,
+    pub file_hint: &'a str,
+    pub preview: &'a str,
+    pub no_databases: &'a str,
+    pub databases: &'a [(String, String)],
+    pub selected_database: &'a str,
+    pub mode_options: &'a [(String, String)],
+    pub selected_mode: &'a str,
+    pub error: Option<String>,
+}
+
+#[derive(Template)]
+#[template(path = "restore/preview.html", escape = "html")]
+pub struct RestorePreviewTemplate<'a> {
+    pub title: &'a str,
+    pub summary: &'a str,
+    pub apply: &'a str,
+    pub apply_hint: &'a str,
+    pub start_over: &'a str,
+    pub no_changes: &'a str,
+    pub table_header_table: &'a str,
+    pub table_header_key: &'a str,
+    pub table_header_status: &'a str,
+    pub table_header_details: &'a str,
+    pub database: &'a str,
+    pub mode: &'a str,
+    pub content: &'a str,
+    pub counts: &'a [(&'static str, String, usize)],
+    pub changes: &'a [RestoreChangeView],
+    pub can_apply: bool,
+    pub notice: Option<String>,
+    pub failed: bool,
+}
@@ -1,4 +1,4 @@
-use crate::models::{DomainStats, SystemStats};
+use crate::models::{DomainStats, MailboxUsageSummary, SystemStats, UsageHistoryPoint};
 use askama::Template;
 
 #[derive(Template)]
@@ -27,6 +27,15 @@ pub struct StatsTemplate<'a> {
     pub quota_usage_percentage: &'a str,
     pub quota_total: &'a str,
     pub quota_used: &'a str,
+    pub usage_not_collected: &'a str,
+    pub usage_last_collected: &'a str,
+    pub largest_mailboxes_title: &'a str,
+    pub table_header_mailbox: &'a str,
+    pub table_header_size: &'a str,
+    pub table_header_messages: &'a str,
+    pub table_header_quota: &'a str,
+    pub table_header_growth: &'a str,
+    pub usage_history_title: &'a str,
     pub recent_activity_title: &'a str,
     pub recent_domains: &'a str,
     pub recent_users: &'a str,
@@ -37,4 +46,10 @@ pub struct StatsTemplate<'a> {
     pub recent_clients: &'a str,
     pub system_stats: SystemStats,
     pub domain_stats: Vec<DomainStats>,
+    pub largest_mailboxes: Vec<MailboxUsageSummary>,
+    pub usage_history: Vec<UsageHistoryPoint>,
+    /// When the snapshots shown were taken, if any have been
+    pub usage_collected: Option<String>,
+    /// Whether a usage source is configured
+    pub usage_enabled: bool,
 }
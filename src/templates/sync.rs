@@ -0,0 +1,53 @@
+use askama::Template;
+
+/// A differing record of a sync diff with translated labels
+#[derive(Debug, Clone)]
+pub struct SyncRowView {
+    /// The value its checkbox submits, see [`crate::sync::row_id`]
+    pub id: String,
+    pub table_label: String,
+    pub key: String,
+    pub status: &'static str,
+    pub status_label: String,
+    pub details: Vec<String>,
+    pub source_modified: String,
+    pub target_modified: String,
+    /// `source` or `target` when that side was modified later
+    pub newer: &'static str,
+    pub selectable: bool,
+}
+
+#[derive(Template)]
+#[template(path = "sync/index.html", escape = "html")]
+pub struct SyncTemplate<'a> {
+    pub title: &'a str,
+    pub description: &'a str,
+    pub source_label: &'a str,
+    pub target_label: &'a str,
+    pub tables_label: &'a str,
+    pub compare: &'a str,
+    pub no_databases: &'a str,
+    pub no_differences: &'a str,
+    pub apply: &'a str,
+    pub apply_hint: &'a str,
+    pub newer_label: &'a str,
+    pub table_header_select: &'a str,
+    pub table_header_table: &'a str,
+    pub table_header_key: &'a str,
+    pub table_header_status: &'a str,
+    pub table_header_details: &'a str,
+    pub table_header_source_modified: &'a str,
+    pub table_header_target_modified: &'a str,
+    pub sources: &'a [(String, String)],
+    pub targets: &'a [(String, String)],
+    pub selected_source: &'a str,
+    pub selected_target: &'a str,
+    /// The tables as name, label and whether they are compared
+    pub table_options: &'a [(String, String, bool)],
+    pub summary: Option<String>,
+    pub counts: &'a [(&'static str, String, usize)],
+    pub rows: &'a [SyncRowView],
+    pub can_apply: bool,
+    pub notice: Option<String>,
+    pub failed: bool,
+}
@@ -1,4 +1,5 @@
 use crate::models::{PaginatedResult, User, UserForm};
+use crate::templates::quota::QuotaLabels;
 use askama::Template;
 
 #[derive(Template)]
@@ -52,6 +53,11 @@ pub struct UserShowTemplate {
     pub password_management_title: String,
     pub change_password_button: String,
     pub require_password_change_button: String,
+    pub password_hash: String,
+    pub password_needs_rehash: String,
+    pub hash_scheme: String,
+    pub needs_rehash: bool,
+    pub quota: QuotaLabels,
 }
 
 #[derive(Template)]
@@ -88,6 +94,8 @@ pub struct UserFormTemplate {
     pub users_maildir: String,
     pub users_tooltip_maildir: String,
     pub users_placeholder_maildir: String,
+    pub generate_password: String,
+    pub quota: QuotaLabels,
 }
 
 #[derive(Template)]
@@ -102,4 +110,12 @@ pub struct ChangePasswordTemplate {
     pub confirm_password_placeholder: String,
     pub cancel_button: String,
     pub change_password_button: String,
+    pub generate_password: String,
+}
+
+#[derive(Template)]
+#[template(path = "users/generated_password.html", escape = "html")]
+pub struct GeneratedPasswordTemplate {
+    pub password: String,
+    pub notice: String,
 }
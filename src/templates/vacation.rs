@@ -0,0 +1,26 @@
+use crate::models::VacationForm;
+use askama::Template;
+
+/// The vacation section of a user's page, loaded after the page itself
+#[derive(Template)]
+#[template(path = "users/vacation.html", escape = "html")]
+pub struct VacationTemplate<'a> {
+    pub title: &'a str,
+    pub status: &'a str,
+    pub subject: &'a str,
+    pub body: &'a str,
+    pub active_from: &'a str,
+    pub active_until: &'a str,
+    pub dates_hint: &'a str,
+    pub reply_interval: &'a str,
+    pub enabled: &'a str,
+    pub save: &'a str,
+    pub delete: &'a str,
+    pub delete_confirm: &'a str,
+    pub user_id: &'a str,
+    pub form: &'a VacationForm,
+    pub exists: bool,
+    pub can_edit: bool,
+    pub message: Option<&'a str>,
+    pub error: Option<&'a str>,
+}
@@ -0,0 +1,11 @@
+/// Labels for the validity window shared by the alias and relocated pages
+#[derive(Debug, Clone)]
+pub struct ValidityLabels {
+    pub title: String,
+    pub valid_from: String,
+    pub valid_until: String,
+    pub hint: String,
+    pub starts: String,
+    pub expires: String,
+    pub expired: String,
+}
@@ -0,0 +1,180 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::Config;
+    use crate::db;
+    use crate::models::*;
+    use crate::tests::common::{cleanup_test_db, setup_test_db, test_audit, unique_test_id};
+
+    fn form(alias_domain: &str, target_domain: &str) -> AliasDomainForm {
+        AliasDomainForm {
+            alias_domain: alias_domain.to_string(),
+            target_domain: target_domain.to_string(),
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn test_form_is_normalized_and_validated() {
+        let normalized = form("  Old.Example ", "EXAMPLE.com").normalized();
+        assert_eq!(normalized.alias_domain, "old.example");
+        assert_eq!(normalized.target_domain, "example.com");
+        assert_eq!(normalized.validate(), Ok(()));
+
+        assert_eq!(
+            form("not a domain", "example.com").validate(),
+            Err("alias-domains-error-invalid-domain")
+        );
+        assert_eq!(
+            form("old.example", "").validate(),
+            Err("alias-domains-error-invalid-domain")
+        );
+        assert_eq!(
+            form("example.com", "Example.com").validate(),
+            Err("alias-domains-error-same-domain")
+        );
+    }
+
+    #[test]
+    fn test_matrix_status_takes_the_best_lookup() {
+        assert!(matches!(
+            db::matrix_status(&[Some(false), Some(true)]),
+            AliasStatus::Present
+        ));
+        assert!(matches!(
+            db::matrix_status(&[None, Some(false)]),
+            AliasStatus::Disabled
+        ));
+        assert!(matches!(
+            db::matrix_status(&[None, None]),
+            AliasStatus::Missing
+        ));
+    }
+
+    #[test]
+    fn test_alias_domains_in_crud_and_reports() {
+        let pool = setup_test_db();
+        cleanup_test_db(&pool);
+        let audit = test_audit();
+        let target = format!("target-{}.com", unique_test_id());
+        let alias_name = format!("alias-{}.com", unique_test_id());
+
+        db::create_domain(
+            &pool,
+            NewDomain {
+                domain: target.clone(),
+                transport: Some("virtual".to_string()),
+                enabled: true,
+                default_quota_bytes: None,
+                default_quota_messages: None,
+                quota_cap_bytes: None,
+            },
+            &audit,
+        )
+        .unwrap();
+
+        // The target has to exist and the alias domain must not be a domain
+        assert_eq!(
+            db::alias_domain_conflict(&pool, &form(&alias_name, "unknown.example"), None).unwrap(),
+            Some("alias-domains-error-unknown-target")
+        );
+        assert_eq!(
+            db::alias_domain_conflict(&pool, &form(&target, &target), None).unwrap(),
+            Some("alias-domains-error-domain-exists")
+        );
+        assert_eq!(
+            db::alias_domain_conflict(&pool, &form(&alias_name, &target), None).unwrap(),
+            None
+        );
+
+        let created = db::create_alias_domain(&pool, form(&alias_name, &target), &audit).unwrap();
+        assert_eq!(
+            db::alias_domain_conflict(&pool, &form(&alias_name, &target), None).unwrap(),
+            Some("alias-domains-error-exists")
+        );
+        assert_eq!(
+            db::alias_domain_conflict(&pool, &form(&alias_name, &target), Some(created.pkid))
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            db::get_alias_domains_for_target(&pool, &target).unwrap()[0].alias_domain,
+            alias_name
+        );
+
+        // Aliases on the target cover the alias domain, and its own aliases are not orphaned
+        for local_part in ["postmaster", "abuse", "webmaster", "admin"] {
+            db::create_alias(
+                &pool,
+                AliasForm {
+                    mail: format!("{local_part}@{target}"),
+                    destination: format!("someone@{target}"),
+                    enabled: true,
+                    return_url: None,
+                    valid_from: None,
+                    valid_until: None,
+                },
+                &audit,
+            )
+            .unwrap();
+        }
+        db::create_alias(
+            &pool,
+            AliasForm {
+                mail: format!("sales@{alias_name}"),
+                destination: format!("someone@{target}"),
+                enabled: true,
+                return_url: None,
+                valid_from: None,
+                valid_until: None,
+            },
+            &audit,
+        )
+        .unwrap();
+
+        let missing = db::get_missing_aliases_report(&pool).unwrap();
+        assert!(missing
+            .domains_missing_aliases
+            .iter()
+            .all(|domain| domain.domain != alias_name));
+        let orphaned = db::get_orphaned_aliases_report(&pool).unwrap();
+        assert!(orphaned
+            .orphaned_aliases
+            .iter()
+            .all(|alias| alias.domain != alias_name));
+        let matrix = db::get_domain_alias_matrix_report(&pool, &Config::default()).unwrap();
+        let row = matrix
+            .domains
+            .iter()
+            .find(|row| row.domain == alias_name)
+            .unwrap();
+        assert_eq!(row.alias_for.as_deref(), Some(target.as_str()));
+        assert!(matches!(
+            row.required_aliases[0].status,
+            AliasStatus::Present
+        ));
+
+        // Disabled, the alias domain stands on its own
+        let toggled = db::toggle_alias_domain_enabled(&pool, created.pkid, &audit).unwrap();
+        assert!(!toggled.enabled);
+        let missing = db::get_missing_aliases_report(&pool).unwrap();
+        let entry = missing
+            .domains_missing_aliases
+            .iter()
+            .find(|domain| domain.domain == alias_name)
+            .unwrap();
+        assert_eq!(entry.alias_for.as_deref(), Some(target.as_str()));
+        assert!(db::get_orphaned_aliases_report(&pool)
+            .unwrap()
+            .orphaned_aliases
+            .iter()
+            .any(|alias| alias.domain == alias_name));
+
+        assert_eq!(
+            db::delete_alias_domain(&pool, created.pkid, &audit).unwrap(),
+            1
+        );
+        assert!(db::get_alias_domain_by_name(&pool, &alias_name).is_err());
+
+        cleanup_test_db(&pool);
+    }
+}
@@ -0,0 +1,248 @@
+#[cfg(test)]
+mod tests {
+    use crate::db::{self, audit_diff};
+    use crate::models::*;
+    use crate::tests::common::{cleanup_test_db, setup_test_db, test_audit, unique_test_id};
+    use serde_json::json;
+
+    fn entry(action: &str, changes: &str) -> AuditLogEntry {
+        AuditLogEntry {
+            id: 1,
+            created: chrono::Utc::now().naive_utc(),
+            admin: "admin".to_string(),
+            database_id: "primary".to_string(),
+            entity_type: "domain".to_string(),
+            entity_id: "42".to_string(),
+            entity_name: "example.com".to_string(),
+            action: action.to_string(),
+            changes: changes.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_audit_diff_only_includes_changed_fields() {
+        let before = json!({"pkid": 1, "domain": "a.com", "enabled": true, "modified": "x"});
+        let after = json!({"pkid": 1, "domain": "b.com", "enabled": true, "modified": "y"});
+
+        let diff = audit_diff(Some(before), Some(after));
+
+        assert_eq!(diff, json!({"domain": {"old": "a.com", "new": "b.com"}}));
+    }
+
+    #[test]
+    fn test_audit_diff_create_and_delete() {
+        let record = json!({"pkid": 3, "mail": "info@a.com", "created": "x"});
+
+        let created = audit_diff(None, Some(record.clone()));
+        assert_eq!(created["mail"], json!({"old": null, "new": "info@a.com"}));
+        assert!(created.get("created").is_none());
+
+        let deleted = audit_diff(Some(record), None);
+        assert_eq!(deleted["pkid"], json!({"old": 3, "new": null}));
+    }
+
+    #[test]
+    fn test_audit_diff_redacts_password_hashes() {
+        let before = json!({"id": "u@a.com", "crypt": "$2b$old"});
+        let after = json!({"id": "u@a.com", "crypt": "$2b$new"});
+
+        let diff = audit_diff(Some(before), Some(after));
+
+        assert_eq!(
+            diff,
+            json!({"crypt": {"old": "[redacted]", "new": "[redacted]"}})
+        );
+    }
+
+    #[test]
+    fn test_password_change_is_audited_as_redacted_hash() {
+        let before = User {
+            id: "u@a.com".to_string(),
+            enabled: true,
+            crypt: "{BLF-CRYPT}$2b$old".to_string(),
+            name: "U".to_string(),
+            maildir: "a.com/u/".to_string(),
+            home: "/var/vmail".to_string(),
+            uid: 5000,
+            gid: 5000,
+            created: chrono::Utc::now().naive_utc(),
+            modified: chrono::Utc::now().naive_utc(),
+            change_password: false,
+            quota_bytes: None,
+            quota_messages: None,
+        };
+        let after = User {
+            crypt: "{BLF-CRYPT}$2b$new".to_string(),
+            ..before.clone()
+        };
+
+        let diff = audit_diff(
+            serde_json::to_value(&before).ok(),
+            serde_json::to_value(&after).ok(),
+        );
+
+        assert_eq!(
+            diff,
+            json!({"crypt": {"old": "[redacted]", "new": "[redacted]"}})
+        );
+    }
+
+    #[test]
+    fn test_audit_entry_field_changes_and_url() {
+        let updated = entry("update", r#"{"enabled":{"old":true,"new":false}}"#);
+        let changes = updated.field_changes();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].field, "enabled");
+        assert_eq!(changes[0].old, "true");
+        assert_eq!(changes[0].new, "false");
+        assert_eq!(updated.entity_url().as_deref(), Some("/domains/42"));
+
+        let deleted = entry("delete", "not json");
+        assert!(deleted.field_changes().is_empty());
+        assert_eq!(deleted.entity_url(), None);
+    }
+
+    #[test]
+    fn test_audit_entity_parse_roundtrip() {
+        for entity in AuditEntity::ALL {
+            assert_eq!(AuditEntity::parse(entity.as_str()), Some(entity));
+        }
+        assert_eq!(AuditEntity::parse("config"), None);
+    }
+
+    #[test]
+    fn test_mutations_record_audit_history() {
+        let pool = setup_test_db();
+        cleanup_test_db(&pool);
+        let audit = test_audit();
+
+        let domain = db::create_domain(
+            &pool,
+            NewDomain {
+                domain: format!("audit-{}.com", unique_test_id()),
+                transport: Some("virtual".to_string()),
+                enabled: true,
+                default_quota_bytes: None,
+                default_quota_messages: None,
+                quota_cap_bytes: None,
+            },
+            &audit,
+        )
+        .unwrap();
+        db::toggle_domain_enabled(&pool, domain.pkid, &audit).unwrap();
+        db::delete_domain(&pool, domain.pkid, &audit).unwrap();
+
+        let history =
+            db::get_entity_audit_history(&pool, AuditEntity::Domain, &domain.pkid.to_string(), 10)
+                .unwrap();
+        let actions: Vec<&str> = history.iter().map(|e| e.action.as_str()).collect();
+        assert_eq!(actions, vec!["delete", "toggle", "create"]);
+        assert!(history.iter().all(|e| e.admin == "test-admin"));
+        assert!(history.iter().all(|e| e.entity_name == domain.domain));
+
+        let toggle_changes = history[1].field_changes();
+        assert_eq!(toggle_changes.len(), 1);
+        assert_eq!(toggle_changes[0].field, "enabled");
+
+        // Deleting a missing record changes nothing and records nothing
+        assert_eq!(db::delete_domain(&pool, domain.pkid, &audit).unwrap(), 0);
+        let filter = AuditLogFilter {
+            entity_type: Some("domain".to_string()),
+            ..Default::default()
+        };
+        let page = db::get_audit_log_paginated(&pool, &filter, 1, 50).unwrap();
+        assert_eq!(page.total_count, 3);
+
+        cleanup_test_db(&pool);
+    }
+
+    #[test]
+    fn test_password_change_records_audit_entry() {
+        let pool = setup_test_db();
+        cleanup_test_db(&pool);
+        let audit = test_audit();
+        let scheme = crate::password::PasswordScheme::default();
+
+        let user = db::create_user(
+            &pool,
+            UserForm {
+                id: format!("{}@audit.test", unique_test_id()),
+                password: "first secret".to_string(),
+                name: "Audit User".to_string(),
+                maildir: String::new(),
+                enabled: true,
+                change_password: false,
+                quota_bytes: None,
+                quota_messages: None,
+            },
+            scheme,
+            &audit,
+        )
+        .unwrap();
+        db::update_user_password(&pool, user.id.clone(), "second secret", scheme, &audit).unwrap();
+
+        let history = db::get_entity_audit_history(&pool, AuditEntity::User, &user.id, 10).unwrap();
+        assert_eq!(history[0].action, "password");
+        let changes = history[0].field_changes();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].field, "crypt");
+        assert_eq!(changes[0].new, "[redacted]");
+
+        cleanup_test_db(&pool);
+    }
+
+    #[test]
+    fn test_audit_log_filters() {
+        let pool = setup_test_db();
+        cleanup_test_db(&pool);
+
+        let alias = db::create_alias(
+            &pool,
+            AliasForm {
+                mail: format!("{}@audit.test", unique_test_id()),
+                destination: "dest@audit.test".to_string(),
+                enabled: true,
+                return_url: None,
+                valid_from: None,
+                valid_until: None,
+            },
+            &db::AuditContext::new("alice", "test"),
+        )
+        .unwrap();
+        db::toggle_alias_enabled(&pool, alias.pkid, &db::AuditContext::new("bob", "test")).unwrap();
+
+        let by_admin = AuditLogFilter {
+            admin: Some("bob".to_string()),
+            ..Default::default()
+        };
+        let page = db::get_audit_log_paginated(&pool, &by_admin, 1, 50).unwrap();
+        assert_eq!(page.total_count, 1);
+        assert_eq!(page.items[0].action, "toggle");
+
+        let today = chrono::Utc::now()
+            .date_naive()
+            .format("%Y-%m-%d")
+            .to_string();
+        let by_date = AuditLogFilter {
+            from: Some(today.clone()),
+            to: Some(today),
+            ..Default::default()
+        };
+        let page = db::get_audit_log_paginated(&pool, &by_date, 1, 50).unwrap();
+        assert_eq!(page.total_count, 2);
+
+        let past = AuditLogFilter {
+            to: Some("2000-01-01".to_string()),
+            ..Default::default()
+        };
+        let page = db::get_audit_log_paginated(&pool, &past, 1, 50).unwrap();
+        assert_eq!(page.total_count, 0);
+
+        assert_eq!(
+            db::get_audit_admins(&pool).unwrap(),
+            vec!["alice".to_string(), "bob".to_string()]
+        );
+
+        cleanup_test_db(&pool);
+    }
+}
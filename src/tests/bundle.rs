@@ -0,0 +1,176 @@
+#[cfg(test)]
+mod tests {
+    use crate::bundle::{self, Bundle, BundleAlias, BundleFormat, BundleUser, BUNDLE_VERSION};
+    use crate::cli::{Cli, Command};
+    use crate::db;
+    use crate::models::User;
+    use crate::tests::common::{
+        cleanup_test_db, database_config, setup_test_db, test_audit, unique_test_id,
+    };
+    use chrono::{DateTime, NaiveDateTime};
+    use clap::Parser;
+    use std::path::Path;
+
+    fn timestamp() -> NaiveDateTime {
+        DateTime::from_timestamp(1_790_000_000, 0)
+            .unwrap()
+            .naive_utc()
+    }
+
+    fn user() -> User {
+        User {
+            id: "alice@example.com".to_string(),
+            enabled: true,
+            crypt: "$2b$12$hash".to_string(),
+            name: "Alice".to_string(),
+            maildir: "example.com/alice/".to_string(),
+            home: "/var/spool/mail/virtual".to_string(),
+            uid: 5000,
+            gid: 5000,
+            created: timestamp(),
+            modified: timestamp(),
+            change_password: false,
+            quota_bytes: Some(1 << 30),
+            quota_messages: None,
+        }
+    }
+
+    fn sample() -> Bundle {
+        let mut bundle = Bundle::new("primary", false);
+        bundle.exported_at = DateTime::from_timestamp(1_790_000_000, 0).unwrap();
+        bundle.users.push(BundleUser::new(user(), false));
+        bundle.aliases.push(BundleAlias {
+            mail: "sales@example.com".to_string(),
+            destination: "alice@example.com".to_string(),
+            enabled: true,
+            valid_from: Some(timestamp()),
+            valid_until: None,
+        });
+        bundle
+    }
+
+    #[test]
+    fn test_bundle_round_trips_json_and_yaml() {
+        let bundle = sample();
+        assert_eq!(bundle.version, BUNDLE_VERSION);
+        assert_eq!(bundle.record_count(), 2);
+
+        let json = bundle.to_string(BundleFormat::Json).unwrap();
+        assert!(json.contains("\"mail\": \"sales@example.com\""));
+        assert!(!json.contains("valid_until"));
+        assert_eq!(serde_json::from_str::<Bundle>(&json).unwrap(), bundle);
+
+        let yaml = bundle.to_string(BundleFormat::Yaml).unwrap();
+        assert!(yaml.starts_with("version: 1\n"));
+        assert_eq!(serde_yaml::from_str::<Bundle>(&yaml).unwrap(), bundle);
+    }
+
+    #[test]
+    fn test_password_hashes_only_on_request() {
+        let without = BundleUser::new(user(), false);
+        assert_eq!(without.password_hash, None);
+        let json = serde_json::to_string(&without).unwrap();
+        assert!(!json.contains("password_hash"));
+        assert!(!json.contains("$2b$"));
+
+        let with = BundleUser::new(user(), true);
+        assert_eq!(with.password_hash.as_deref(), Some("$2b$12$hash"));
+    }
+
+    #[test]
+    fn test_bundle_format_and_file_name() {
+        assert_eq!(
+            BundleFormat::from_path(Path::new("mail.YML")),
+            Some(BundleFormat::Yaml)
+        );
+        assert_eq!(
+            BundleFormat::from_path(Path::new("mail.json")),
+            Some(BundleFormat::Json)
+        );
+        assert_eq!(BundleFormat::from_path(Path::new("mail")), None);
+        assert_eq!(
+            sample().file_name(BundleFormat::Yaml),
+            "sortingoffice-primary-20260921-141320.yaml"
+        );
+    }
+
+    #[test]
+    fn test_field_map_falls_back_to_schema_columns() {
+        let db_config =
+            database_config(&[("domains.domain", "domain_name"), ("enabled", "active")]);
+        assert_eq!(
+            db_config.mapped_field("domains", "domain"),
+            Some("domain_name")
+        );
+        assert_eq!(db_config.mapped_field("aliases", "enabled"), Some("active"));
+        assert_eq!(db_config.mapped_field("domains", "id"), None);
+        assert_eq!(db_config.field_for_table("domains", "id"), "id");
+
+        let sql = db::build_field_mapped_query(
+            "domains",
+            &[("id", "pkid"), ("domain", "domain"), ("enabled", "enabled")],
+            &db_config,
+        );
+        assert_eq!(
+            sql,
+            "SELECT pkid as pkid, domain_name as domain, active as enabled FROM domains"
+        );
+    }
+
+    #[test]
+    fn test_parse_export_command() {
+        let cli = Cli::try_parse_from([
+            "sortingoffice-cli",
+            "--database",
+            "secondary",
+            "export",
+            "mail.yaml",
+            "--password-hashes",
+        ])
+        .unwrap();
+        match cli.command {
+            Command::Export {
+                file,
+                yaml,
+                password_hashes,
+            } => {
+                assert_eq!(file.as_deref(), Some(Path::new("mail.yaml")));
+                assert!(!yaml);
+                assert!(password_hashes);
+            }
+            other => panic!("unexpected command {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_export_reads_database() {
+        let pool = setup_test_db();
+        cleanup_test_db(&pool);
+        let audit = test_audit();
+        let domain = format!("export-{}.com", unique_test_id());
+        db::create_domain(
+            &pool,
+            crate::models::NewDomain {
+                domain: domain.clone(),
+                transport: Some("virtual".to_string()),
+                enabled: true,
+                default_quota_bytes: None,
+                default_quota_messages: None,
+                quota_cap_bytes: None,
+            },
+            &audit,
+        )
+        .unwrap();
+
+        let bundle = bundle::export(&pool, &database_config(&[]), false).unwrap();
+        let exported = bundle
+            .domains
+            .iter()
+            .find(|d| d.domain == domain)
+            .expect("domain exported");
+        assert_eq!(exported.transport.as_deref(), Some("virtual"));
+        assert!(bundle.users.iter().all(|u| u.password_hash.is_none()));
+
+        cleanup_test_db(&pool);
+    }
+}
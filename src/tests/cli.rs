@@ -0,0 +1,147 @@
+#[cfg(test)]
+mod tests {
+    use crate::cli::{
+        render_records, render_rows, Cli, Command, DomainCommand, OutputFormat, ReportCommand,
+    };
+    use crate::models::Domain;
+    use clap::{CommandFactory, Parser};
+
+    fn domain(pkid: i32, name: &str, enabled: bool) -> Domain {
+        let now = chrono::Utc::now().naive_utc();
+        Domain {
+            pkid,
+            domain: name.to_string(),
+            transport: Some("virtual".to_string()),
+            created: now,
+            modified: now,
+            enabled,
+            default_quota_bytes: None,
+            default_quota_messages: None,
+            quota_cap_bytes: None,
+        }
+    }
+
+    #[test]
+    fn test_cli_definition_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_parse_global_flags_after_subcommand() {
+        let cli = Cli::try_parse_from([
+            "sortingoffice-cli",
+            "report",
+            "orphaned",
+            "--format",
+            "json",
+        ])
+        .unwrap();
+        assert_eq!(cli.output, OutputFormat::Json);
+        assert!(matches!(
+            cli.command,
+            Command::Report {
+                report: ReportCommand::Orphaned
+            }
+        ));
+
+        let cli = Cli::try_parse_from([
+            "sortingoffice-cli",
+            "--database",
+            "backup",
+            "domain",
+            "add",
+            "example.com",
+            "--disabled",
+            "--quota-cap",
+            "100G",
+        ])
+        .unwrap();
+        assert_eq!(cli.database.as_deref(), Some("backup"));
+        assert_eq!(cli.output, OutputFormat::Table);
+        match cli.command {
+            Command::Domain {
+                action:
+                    DomainCommand::Add {
+                        domain,
+                        transport,
+                        disabled,
+                        default_quota,
+                        quota_cap,
+                        ..
+                    },
+            } => {
+                assert_eq!(domain, "example.com");
+                assert_eq!(transport, "virtual");
+                assert!(disabled);
+                assert_eq!(default_quota, None);
+                assert_eq!(quota_cap, Some(100 << 30));
+            }
+            other => panic!("unexpected command {other:?}"),
+        }
+
+        assert!(
+            Cli::try_parse_from(["sortingoffice-cli", "--output", "xml", "domain", "list"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_mail_config() {
+        let cli = Cli::try_parse_from([
+            "sortingoffice-cli",
+            "mail-config",
+            "--all",
+            "--zip",
+            "mail.zip",
+        ])
+        .unwrap();
+        match cli.command {
+            Command::MailConfig { all, zip, dir } => {
+                assert!(all);
+                assert_eq!(zip, Some("mail.zip".into()));
+                assert_eq!(dir, None);
+            }
+            other => panic!("unexpected command {other:?}"),
+        }
+
+        assert!(Cli::try_parse_from([
+            "sortingoffice-cli",
+            "mail-config",
+            "--zip",
+            "mail.zip",
+            "--dir",
+            "out",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_render_table_aligns_columns() {
+        let output = render_rows(
+            OutputFormat::Table,
+            &["id", "domain"],
+            &[
+                vec!["1".to_string(), "a.com".to_string()],
+                vec!["10".to_string(), "example.org".to_string()],
+            ],
+        )
+        .unwrap();
+        assert_eq!(output, "id  domain\n1   a.com\n10  example.org\n");
+    }
+
+    #[test]
+    fn test_render_records_csv_and_json() {
+        let domains = vec![domain(1, "a.com", true), domain(2, "b, inc.com", false)];
+
+        let csv = render_records(OutputFormat::Csv, &domains).unwrap();
+        assert_eq!(
+            csv,
+            "id,domain,transport,enabled\n1,a.com,virtual,true\n2,\"b, inc.com\",virtual,false\n"
+        );
+
+        let json = render_records(OutputFormat::Json, &domains).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed[1]["domain"], "b, inc.com");
+        assert_eq!(parsed[1]["enabled"], false);
+    }
+}
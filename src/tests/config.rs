@@ -0,0 +1,387 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::{
+        hash_api_token, AdminCredentials, AdminRole, ApiToken, Config, ConfigError, ConfigUpdate,
+        DomainOverride, SharedConfig,
+    };
+    use crate::db::AddressScope;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    const SAVED_CONFIG: &str = r#"# Aliases every domain needs
+required_aliases = ["postmaster", "abuse"]
+common_aliases = ["info"]
+
+[global_features]
+read_only = false # Set to true to make all databases read-only
+
+[[databases]]
+id = "primary"
+label = "Primary"
+url = "mysql://localhost/primary"
+
+[[databases]]
+id = "replica"
+label = "Replica"
+url = "mysql://localhost/replica"
+
+[databases.features]
+read_only = true
+
+[[admins]]
+username = "alice"
+password_hash = "x"
+"#;
+
+    fn write_temp_config(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "sortingoffice-config-{name}-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+        std::fs::write(&path, SAVED_CONFIG).unwrap();
+        path
+    }
+
+    fn admin_with_grants(databases: Option<HashMap<String, AdminRole>>) -> AdminCredentials {
+        AdminCredentials {
+            username: "alice".to_string(),
+            password_hash: String::new(),
+            role: AdminRole::Edit,
+            databases,
+            api_tokens: vec![],
+            domains: vec![],
+        }
+    }
+
+    #[test]
+    fn test_global_role_applies_without_grants() {
+        let admin = admin_with_grants(None);
+        assert_eq!(admin.role_for_database("eu-prod"), Some(AdminRole::Edit));
+        assert!(admin.can_access_database("anything"));
+    }
+
+    #[test]
+    fn test_per_database_grants() {
+        let admin = admin_with_grants(Some(HashMap::from([
+            ("eu-prod".to_string(), AdminRole::Edit),
+            ("us-prod".to_string(), AdminRole::ReadOnly),
+        ])));
+        assert_eq!(admin.role_for_database("eu-prod"), Some(AdminRole::Edit));
+        assert_eq!(
+            admin.role_for_database("us-prod"),
+            Some(AdminRole::ReadOnly)
+        );
+        assert_eq!(admin.role_for_database("staging"), None);
+        assert!(!admin.can_access_database("staging"));
+    }
+
+    #[test]
+    fn test_wildcard_grant() {
+        let admin = admin_with_grants(Some(HashMap::from([
+            ("eu-prod".to_string(), AdminRole::Edit),
+            ("*".to_string(), AdminRole::ReadOnly),
+        ])));
+        assert_eq!(admin.role_for_database("eu-prod"), Some(AdminRole::Edit));
+        assert_eq!(
+            admin.role_for_database("staging"),
+            Some(AdminRole::ReadOnly)
+        );
+    }
+
+    #[test]
+    fn test_grants_parse_from_toml() {
+        let config: Config = toml::from_str(
+            r#"
+            required_aliases = []
+            common_aliases = []
+
+            [[admins]]
+            username = "alice"
+            password_hash = "x"
+            role = "read-only"
+
+            [admins.databases]
+            eu-prod = "edit"
+            us-prod = "read-only"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.admin_role_for_database("alice", "eu-prod"),
+            Some(AdminRole::Edit)
+        );
+        assert_eq!(
+            config.admin_role_for_database("alice", "us-prod"),
+            Some(AdminRole::ReadOnly)
+        );
+        assert_eq!(config.admin_role_for_database("alice", "staging"), None);
+        assert_eq!(config.admin_role_for_database("bob", "eu-prod"), None);
+    }
+
+    #[test]
+    fn test_find_api_token_matches_hash() {
+        let mut admin = admin_with_grants(None);
+        admin.api_tokens.push(ApiToken {
+            name: "provisioning".to_string(),
+            token_hash: hash_api_token("s3cret").to_uppercase(),
+            database: "eu-prod".to_string(),
+        });
+        let config = Config {
+            admins: vec![admin],
+            ..Config::default()
+        };
+
+        let (admin, token) = config.find_api_token("s3cret").unwrap();
+        assert_eq!(admin.username, "alice");
+        assert_eq!(token.name, "provisioning");
+        assert_eq!(token.database, "eu-prod");
+        assert!(config.find_api_token("wrong").is_none());
+        assert_eq!(hash_api_token("s3cret").len(), 64);
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_aliases_and_domains() {
+        let mut config = Config::default();
+        assert!(config.validate().is_ok());
+
+        config.common_aliases.push("Postmaster".to_string());
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::DuplicateAlias(alias)) if alias == "Postmaster"
+        ));
+
+        config.common_aliases.pop();
+        config.required_aliases.push("no spaces".to_string());
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidAlias(_))
+        ));
+
+        config.required_aliases.pop();
+        config.domain_overrides.insert(
+            "not a domain".to_string(),
+            DomainOverride {
+                required: vec![],
+                common: vec![],
+            },
+        );
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidDomain(_))
+        ));
+    }
+
+    #[test]
+    fn test_domain_admins_are_scoped_to_one_database() {
+        let mut admin = admin_with_grants(None);
+        assert!(!admin.is_domain_admin());
+        assert_eq!(admin.address_scope(), AddressScope::All);
+
+        admin.domains = vec!["example.com".to_string()];
+        assert!(admin.is_domain_admin());
+        assert_eq!(
+            admin.address_scope(),
+            AddressScope::Domains(vec!["example.com".to_string()])
+        );
+
+        // Without grants, or with a wildcard, the domains could be on any database
+        let mut config = Config::default();
+        config.admins.push(admin.clone());
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::DomainAdminDatabases(name)) if name == "alice"
+        ));
+        config.admins[1].databases = Some(HashMap::from([("*".to_string(), AdminRole::Edit)]));
+        assert!(config.validate().is_err());
+
+        config.admins[1].databases =
+            Some(HashMap::from([("eu-prod".to_string(), AdminRole::Edit)]));
+        assert!(config.validate().is_ok());
+
+        config.admins[1].domains.push("not a domain".to_string());
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidDomain(_))
+        ));
+    }
+
+    #[test]
+    fn test_address_scope() {
+        let all = AddressScope::All;
+        assert!(all.allows("alice@example.com"));
+        assert!(all.allows("postmaster"));
+
+        let scope = AddressScope::Domains(vec!["example.com".to_string()]);
+        assert!(scope.allows("alice@example.com"));
+        assert!(scope.allows("Alice@EXAMPLE.com"));
+        assert!(scope.allows("@example.com"));
+        assert!(scope.allows_domain("example.com"));
+        assert!(!scope.allows("alice@example.org"));
+        assert!(!scope.allows("alice@sub.example.com"));
+        assert!(!scope.allows("alice@example.com@example.org"));
+        assert!(!scope.allows("postmaster"));
+        assert!(!scope.allows_domain("other.com"));
+    }
+
+    #[test]
+    fn test_config_editing_requires_edit_on_every_database() {
+        let config = Config::from_file(write_temp_config("grants")).unwrap();
+        assert!(config.can_edit_config(&admin_with_grants(None)));
+        assert!(
+            !config.can_edit_config(&admin_with_grants(Some(HashMap::from([(
+                "primary".to_string(),
+                AdminRole::Edit
+            )]))))
+        );
+    }
+
+    #[test]
+    fn test_save_update_keeps_rest_of_file() {
+        let path = write_temp_config("save");
+        let mut update = ConfigUpdate {
+            required_aliases: vec!["postmaster".to_string(), "hostmaster".to_string()],
+            common_aliases: vec!["abuse".to_string()],
+            ..ConfigUpdate::default()
+        };
+        update.domain_overrides.insert(
+            "example.com".to_string(),
+            DomainOverride {
+                required: vec!["sales".to_string()],
+                common: vec![],
+            },
+        );
+        update.global_features.no_new_users = true;
+        update
+            .database_features
+            .insert("primary".to_string(), Default::default());
+        update.database_features.insert(
+            "replica".to_string(),
+            crate::config::DatabaseFeatures {
+                disabled: true,
+                ..Default::default()
+            },
+        );
+
+        let config = Config::save_update(&path, &update).unwrap();
+        assert_eq!(config.required_aliases, ["postmaster", "hostmaster"]);
+        assert_eq!(
+            config.get_required_aliases_for_domain("example.com"),
+            ["sales"]
+        );
+        assert!(config.global_features.no_new_users);
+        assert!(config.is_database_disabled("replica"));
+        assert!(!config.get_database_features("replica").unwrap().read_only);
+        assert_eq!(config.admins[0].username, "alice");
+
+        let saved = std::fs::read_to_string(&path).unwrap();
+        assert!(saved.starts_with("# Aliases every domain needs\n"));
+        assert!(saved.contains("# Set to true to make all databases read-only"));
+        assert_eq!(
+            std::fs::read_to_string(path.with_extension("toml.bak")).unwrap(),
+            SAVED_CONFIG
+        );
+        // The file on disk reads back as the returned configuration
+        let reloaded = Config::from_file(&path).unwrap();
+        assert_eq!(reloaded.common_aliases, ["abuse"]);
+        assert!(reloaded.is_database_disabled("replica"));
+        assert!(!reloaded.is_database_disabled("primary"));
+    }
+
+    #[test]
+    fn test_invalid_update_is_not_written() {
+        let path = write_temp_config("invalid");
+        let update = ConfigUpdate {
+            required_aliases: vec!["postmaster".to_string()],
+            common_aliases: vec!["postmaster".to_string()],
+            ..ConfigUpdate::default()
+        };
+        assert!(matches!(
+            Config::save_update(&path, &update),
+            Err(ConfigError::DuplicateAlias(_))
+        ));
+
+        let mut update = ConfigUpdate::default();
+        update
+            .database_features
+            .insert("missing".to_string(), Default::default());
+        assert!(matches!(
+            Config::save_update(&path, &update),
+            Err(ConfigError::UnknownDatabase(id)) if id == "missing"
+        ));
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), SAVED_CONFIG);
+    }
+
+    #[test]
+    fn test_shared_config_save_applies_without_restart() {
+        let path = write_temp_config("shared");
+        let shared = SharedConfig::new(Config::from_file(&path).unwrap(), Some(path.clone()));
+        let snapshot = shared.get();
+
+        let update = ConfigUpdate {
+            required_aliases: vec!["postmaster".to_string()],
+            ..ConfigUpdate::default()
+        };
+        shared.save(&update).unwrap();
+
+        assert_eq!(shared.get().required_aliases, ["postmaster"]);
+        assert!(shared.get().common_aliases.is_empty());
+        // Earlier snapshots are unaffected
+        assert_eq!(snapshot.required_aliases, ["postmaster", "abuse"]);
+
+        let unsaved: SharedConfig = Config::default().into();
+        assert!(matches!(
+            unsaved.save(&update),
+            Err(ConfigError::NoConfigFile)
+        ));
+    }
+
+    #[test]
+    fn test_read_file_rejects_duplicate_databases() {
+        let path = write_temp_config("duplicate");
+        let shared = SharedConfig::new(Config::from_file(&path).unwrap(), Some(path.clone()));
+        assert!(shared.read_file().is_ok());
+
+        let content = SAVED_CONFIG.replace(r#"id = "replica""#, r#"id = "primary""#);
+        std::fs::write(&path, content).unwrap();
+        assert!(matches!(
+            shared.read_file(),
+            Err(ConfigError::DuplicateDatabase(id)) if id == "primary"
+        ));
+    }
+
+    #[test]
+    fn test_parse_config_form() {
+        let config = Config::from_file(write_temp_config("form")).unwrap();
+        let fields: Vec<(String, String)> = [
+            ("required_aliases[]", "postmaster"),
+            ("required_aliases[]", " abuse "),
+            ("common_aliases[]", "info"),
+            ("new_common_alias", "ignored"),
+            ("domain_overrides[Example.com][required][]", "sales"),
+            ("domain_overrides[example.com][common][]", ""),
+            ("domain_overrides[empty.org][required][]", ""),
+            ("global_features[no_new_domains]", "on"),
+            ("database_features[replica][disabled]", "on"),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+
+        let update = crate::handlers::config::parse_config_form(&config, &fields);
+        assert_eq!(update.required_aliases, ["postmaster", "abuse"]);
+        assert_eq!(update.common_aliases, ["info"]);
+        assert_eq!(update.domain_overrides["example.com"].required, ["sales"]);
+        assert!(update.domain_overrides["example.com"].common.is_empty());
+        assert!(update.domain_overrides.contains_key("empty.org"));
+        assert!(update.global_features.no_new_domains);
+        assert!(!update.global_features.read_only);
+        // Unchecked boxes turn features off
+        assert!(!update.database_features["replica"].read_only);
+        assert!(update.database_features["replica"].disabled);
+        assert!(!update.database_features["primary"].disabled);
+    }
+}
@@ -0,0 +1,478 @@
+#[cfg(test)]
+mod tests {
+    use crate::delivery::{
+        check_destinations, is_valid_destination, split_destinations, trace, trace_destinations,
+        DeliveryLookup, DestinationError, MAX_DEPTH,
+    };
+    use crate::models::{
+        Alias, AliasDomain, Backup, DeliveryOutcome, DeliveryTrace, DisabledEntry, Domain,
+        Relocated, User,
+    };
+    use diesel::result::Error;
+
+    /// In-memory tables standing in for the database
+    #[derive(Default)]
+    struct Tables {
+        domains: Vec<Domain>,
+        backups: Vec<Backup>,
+        alias_domains: Vec<AliasDomain>,
+        aliases: Vec<Alias>,
+        users: Vec<User>,
+        relocated: Vec<Relocated>,
+    }
+
+    fn now() -> chrono::NaiveDateTime {
+        chrono::Utc::now().naive_utc()
+    }
+
+    impl Tables {
+        fn domain(mut self, domain: &str, enabled: bool) -> Self {
+            self.domains.push(Domain {
+                pkid: 0,
+                domain: domain.to_string(),
+                transport: Some("virtual".to_string()),
+                created: now(),
+                modified: now(),
+                enabled,
+                default_quota_bytes: None,
+                default_quota_messages: None,
+                quota_cap_bytes: None,
+            });
+            self
+        }
+
+        fn backup(mut self, domain: &str, transport: &str) -> Self {
+            self.backups.push(Backup {
+                pkid: 0,
+                domain: domain.to_string(),
+                transport: Some(transport.to_string()),
+                created: now(),
+                modified: now(),
+                enabled: true,
+            });
+            self
+        }
+
+        fn alias_domain(mut self, alias_domain: &str, target_domain: &str, enabled: bool) -> Self {
+            self.alias_domains.push(AliasDomain {
+                pkid: 0,
+                alias_domain: alias_domain.to_string(),
+                target_domain: target_domain.to_string(),
+                created: now(),
+                modified: now(),
+                enabled,
+            });
+            self
+        }
+
+        fn alias(mut self, mail: &str, destination: &str, enabled: bool) -> Self {
+            self.aliases.push(Alias {
+                pkid: 0,
+                mail: mail.to_string(),
+                destination: destination.to_string(),
+                created: now(),
+                modified: now(),
+                enabled,
+                valid_from: None,
+                valid_until: None,
+            });
+            self
+        }
+
+        fn user(mut self, id: &str, enabled: bool) -> Self {
+            self.users.push(User {
+                id: id.to_string(),
+                enabled,
+                crypt: String::new(),
+                name: String::new(),
+                maildir: format!("{id}/"),
+                home: "/var/spool/mail/virtual".to_string(),
+                uid: 5000,
+                gid: 5000,
+                created: now(),
+                modified: now(),
+                change_password: false,
+                quota_bytes: None,
+                quota_messages: None,
+            });
+            self
+        }
+
+        fn relocated(mut self, old_address: &str, new_address: &str) -> Self {
+            self.relocated.push(Relocated {
+                pkid: 0,
+                old_address: old_address.to_string(),
+                new_address: new_address.to_string(),
+                enabled: true,
+                created: now(),
+                modified: now(),
+                valid_from: None,
+                valid_until: None,
+            });
+            self
+        }
+    }
+
+    impl DeliveryLookup for Tables {
+        fn relocated(&self, address: &str) -> Result<Option<Relocated>, Error> {
+            Ok(self
+                .relocated
+                .iter()
+                .find(|r| r.old_address == address)
+                .cloned())
+        }
+
+        fn domain(&self, domain: &str) -> Result<Option<Domain>, Error> {
+            Ok(self.domains.iter().find(|d| d.domain == domain).cloned())
+        }
+
+        fn backup(&self, domain: &str) -> Result<Option<Backup>, Error> {
+            Ok(self.backups.iter().find(|b| b.domain == domain).cloned())
+        }
+
+        fn alias_domain(&self, domain: &str) -> Result<Option<AliasDomain>, Error> {
+            Ok(self
+                .alias_domains
+                .iter()
+                .find(|a| a.alias_domain == domain)
+                .cloned())
+        }
+
+        fn alias(&self, mail: &str) -> Result<Option<Alias>, Error> {
+            Ok(self.aliases.iter().find(|a| a.mail == mail).cloned())
+        }
+
+        fn user(&self, id: &str) -> Result<Option<User>, Error> {
+            Ok(self.users.iter().find(|u| u.id == id).cloned())
+        }
+    }
+
+    fn kinds(trace: &DeliveryTrace) -> Vec<(usize, &str, &str)> {
+        trace
+            .hops
+            .iter()
+            .map(|hop| (hop.depth, hop.address.as_str(), hop.outcome.kind()))
+            .collect()
+    }
+
+    #[test]
+    fn test_split_destinations() {
+        assert_eq!(
+            split_destinations(" A@example.com,b@example.com  c@example.org ,"),
+            vec!["a@example.com", "b@example.com", "c@example.org"]
+        );
+    }
+
+    #[test]
+    fn test_destination_validation() {
+        for valid in ["info@example.com", "first.last+tag@sub.example.org"] {
+            assert!(is_valid_destination(valid), "{valid}");
+        }
+        for invalid in [
+            "info",
+            "@example.com",
+            "info@",
+            "info@localhost",
+            "a@b@example.com",
+            "a b@example.com",
+            "<info>@example.com",
+        ] {
+            assert!(!is_valid_destination(invalid), "{invalid}");
+        }
+
+        assert_eq!(check_destinations(&[]), Err(DestinationError::Missing));
+        assert_eq!(
+            check_destinations(&["info@example.com".to_string(), "info".to_string()]),
+            Err(DestinationError::InvalidAddress("info".to_string()))
+        );
+        assert_eq!(
+            check_destinations(&["info@example.com".to_string()]),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_destinations_are_traced_one_by_one() {
+        let tables = Tables::default()
+            .domain("example.com", true)
+            .alias(
+                "team@example.com",
+                "info@example.com, ops@example.com",
+                true,
+            )
+            .alias("ops@example.com", "gone@example.com", true)
+            .user("info@example.com", true);
+        let destinations = split_destinations(
+            "info@example.com, team@example.com, friend@gmail.com, gone@example.com",
+        );
+
+        let traces = trace_destinations(&tables, "list@example.com", &destinations).unwrap();
+        let first: Vec<(&str, &str)> = traces
+            .iter()
+            .map(|trace| (trace.address.as_str(), trace.hops[0].outcome.kind()))
+            .collect();
+        // Destinations are expanded addresses, so other domains are external, not rejected
+        assert_eq!(
+            first,
+            vec![
+                ("info@example.com", "mailbox"),
+                ("team@example.com", "alias"),
+                ("friend@gmail.com", "external"),
+                ("gone@example.com", "unknown_user"),
+            ]
+        );
+        assert!(!traces[0].has_problems());
+        assert!(traces[1].has_problems());
+        assert_eq!(traces[1].final_destinations().len(), 1);
+
+        // The alias's own address among its destinations is its mailbox
+        let traces = trace_destinations(
+            &tables,
+            "info@example.com",
+            &["info@example.com".to_string()],
+        )
+        .unwrap();
+        assert_eq!(traces[0].hops[0].outcome.kind(), "mailbox");
+    }
+
+    #[test]
+    fn test_mailbox_and_unknown_domain() {
+        let tables = Tables::default()
+            .domain("example.com", true)
+            .user("info@example.com", true);
+
+        let result = trace(&tables, " Info@Example.com ").unwrap();
+        assert_eq!(result.address, "info@example.com");
+        assert_eq!(
+            result.hops[0].outcome,
+            DeliveryOutcome::Mailbox {
+                maildir: "info@example.com/".to_string()
+            }
+        );
+        assert!(!result.has_problems());
+
+        let result = trace(&tables, "someone@elsewhere.org").unwrap();
+        assert_eq!(result.hops[0].outcome, DeliveryOutcome::UnknownDomain);
+        assert!(result.has_problems());
+
+        let result = trace(&tables, "not-an-address").unwrap();
+        assert_eq!(result.hops[0].outcome, DeliveryOutcome::InvalidAddress);
+    }
+
+    #[test]
+    fn test_relocated_is_checked_first() {
+        let tables = Tables::default()
+            .domain("example.com", true)
+            .relocated("old@example.com", "new@example.org")
+            .alias("old@example.com", "info@example.com", true);
+
+        let result = trace(&tables, "old@example.com").unwrap();
+        assert_eq!(result.hops.len(), 1);
+        assert_eq!(
+            result.hops[0].outcome,
+            DeliveryOutcome::Relocated {
+                new_address: "new@example.org".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_alias_expansion_to_mailbox_external_and_relay() {
+        let tables = Tables::default()
+            .domain("example.com", true)
+            .backup("backup.net", "smtp:[mx.backup.net]")
+            .alias(
+                "team@example.com",
+                "info@example.com, friend@gmail.com, ops@backup.net",
+                true,
+            )
+            .user("info@example.com", true);
+
+        let result = trace(&tables, "team@example.com").unwrap();
+        assert_eq!(
+            kinds(&result),
+            vec![
+                (0, "team@example.com", "alias"),
+                (1, "info@example.com", "mailbox"),
+                (1, "friend@gmail.com", "external"),
+                (1, "ops@backup.net", "relay"),
+            ]
+        );
+        assert_eq!(result.final_destinations().len(), 3);
+    }
+
+    #[test]
+    fn test_catch_all_and_self_reference() {
+        let tables = Tables::default()
+            .domain("example.com", true)
+            .alias("@example.com", "info@example.com", true)
+            .alias("boss@example.com", "boss@example.com", true)
+            .user("info@example.com", true)
+            .user("boss@example.com", true)
+            .user("staff@example.com", true);
+
+        // The catch-all also captures mailboxes without their own alias, and
+        // resolving to the same address stops the expansion
+        let result = trace(&tables, "staff@example.com").unwrap();
+        assert_eq!(
+            kinds(&result),
+            vec![
+                (0, "staff@example.com", "alias"),
+                (1, "info@example.com", "alias"),
+                (2, "info@example.com", "mailbox"),
+            ]
+        );
+        assert!(matches!(
+            result.hops[0].outcome,
+            DeliveryOutcome::Alias {
+                catch_all: true,
+                ..
+            }
+        ));
+
+        // An alias to itself delivers to the mailbox without expanding again
+        let result = trace(&tables, "boss@example.com").unwrap();
+        assert_eq!(
+            kinds(&result),
+            vec![
+                (0, "boss@example.com", "alias"),
+                (1, "boss@example.com", "mailbox"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_loops_and_depth_limit() {
+        let tables = Tables::default()
+            .domain("example.com", true)
+            .alias("a@example.com", "b@example.com", true)
+            .alias("b@example.com", "a@example.com", true);
+        let result = trace(&tables, "a@example.com").unwrap();
+        assert_eq!(result.hops.last().unwrap().outcome, DeliveryOutcome::Loop);
+        assert!(result.final_destinations().is_empty());
+
+        let mut tables = Tables::default().domain("example.com", true);
+        for i in 0..=MAX_DEPTH + 1 {
+            tables = tables.alias(
+                &format!("a{i}@example.com"),
+                &format!("a{}@example.com", i + 1),
+                true,
+            );
+        }
+        let result = trace(&tables, "a0@example.com").unwrap();
+        assert_eq!(
+            result.hops.last().unwrap().outcome,
+            DeliveryOutcome::TooDeep
+        );
+    }
+
+    #[test]
+    fn test_disabled_entries_are_skipped_and_reported() {
+        let tables = Tables::default()
+            .domain("example.com", true)
+            .domain("old.example", false)
+            .alias("info@example.com", "someone@example.com", false)
+            .user("info@example.com", false);
+
+        let result = trace(&tables, "info@example.com").unwrap();
+        assert_eq!(result.hops[0].outcome, DeliveryOutcome::UnknownUser);
+        assert_eq!(
+            result.hops[0].disabled,
+            vec![
+                DisabledEntry {
+                    entity: "alias".to_string(),
+                    name: "info@example.com".to_string()
+                },
+                DisabledEntry {
+                    entity: "user".to_string(),
+                    name: "info@example.com".to_string()
+                },
+            ]
+        );
+
+        let result = trace(&tables, "info@old.example").unwrap();
+        assert_eq!(result.hops[0].outcome, DeliveryOutcome::UnknownDomain);
+        assert_eq!(result.hops[0].disabled[0].entity, "domain");
+    }
+
+    #[test]
+    fn test_entries_outside_their_window_are_disabled() {
+        let mut tables = Tables::default()
+            .domain("example.com", true)
+            .relocated("old@example.com", "new@example.org")
+            .alias("old@example.com", "info@example.com", true)
+            .alias("event@example.com", "info@example.com", true)
+            .user("info@example.com", true);
+        let yesterday = chrono::Utc::now().naive_utc() - chrono::Duration::days(1);
+        tables.relocated[0].valid_until = Some(yesterday);
+        tables.aliases[1].valid_until = Some(yesterday);
+
+        let result = trace(&tables, "old@example.com").unwrap();
+        assert_eq!(result.hops[0].outcome.kind(), "alias");
+        assert_eq!(result.hops[0].disabled[0].entity, "relocated");
+
+        let result = trace(&tables, "event@example.com").unwrap();
+        assert_eq!(result.hops[0].outcome, DeliveryOutcome::UnknownUser);
+        assert_eq!(result.hops[0].disabled[0].entity, "alias");
+    }
+
+    #[test]
+    fn test_alias_domain_rewrites_known_addresses() {
+        let tables = Tables::default()
+            .domain("example.com", true)
+            .alias_domain("old.example", "example.com", true)
+            .alias_domain("gone.example", "example.com", false)
+            .alias("sales@old.example", "info@example.com", true)
+            .alias("@old.example", "info@example.com", true)
+            .user("info@example.com", true)
+            .user("boss@example.com", true);
+
+        let result = trace(&tables, "boss@old.example").unwrap();
+        assert_eq!(
+            kinds(&result),
+            vec![
+                (0, "boss@old.example", "alias_domain"),
+                (1, "boss@example.com", "mailbox"),
+            ]
+        );
+        assert_eq!(
+            result.hops[0].outcome,
+            DeliveryOutcome::AliasDomain {
+                alias_domain: "old.example".to_string(),
+                address: "boss@example.com".to_string(),
+            }
+        );
+
+        // Aliases on the alias domain come first, its catch-all last
+        let result = trace(&tables, "sales@old.example").unwrap();
+        assert_eq!(result.hops[0].outcome.kind(), "alias");
+        let result = trace(&tables, "nobody@old.example").unwrap();
+        assert!(matches!(
+            result.hops[0].outcome,
+            DeliveryOutcome::Alias {
+                catch_all: true,
+                ..
+            }
+        ));
+
+        // A disabled alias domain is not accepted
+        let result = trace(&tables, "boss@gone.example").unwrap();
+        assert_eq!(result.hops[0].outcome, DeliveryOutcome::UnknownDomain);
+        assert_eq!(result.hops[0].disabled[0].entity, "alias-domain");
+    }
+
+    #[test]
+    fn test_alias_domain_rejects_unknown_users() {
+        let tables = Tables::default()
+            .domain("example.com", true)
+            .alias_domain("old.example", "example.com", true)
+            .user("info@example.com", true);
+
+        let result = trace(&tables, "nobody@old.example").unwrap();
+        assert_eq!(
+            kinds(&result),
+            vec![(0, "nobody@old.example", "unknown_user")]
+        );
+        assert!(result.has_problems());
+    }
+}
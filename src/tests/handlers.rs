@@ -8,17 +8,43 @@ mod tests {
 
     use tower::ServiceExt;
 
-    use crate::config::{AdminRole, Config, DatabaseConfig, DatabaseFeatures};
+    use crate::config::{AdminCredentials, AdminRole, Config, DatabaseConfig, DatabaseFeatures};
     use crate::handlers;
-    use crate::tests::common::{cleanup_test_db, setup_test_db};
+    use crate::password::PasswordScheme;
+    use crate::tests::common::{cleanup_test_db, setup_test_db, test_audit};
     use crate::AppState;
     use axum::http::HeaderValue;
-    use std::time::{SystemTime, UNIX_EPOCH};
+
+    // Test config with an edit admin ("admin"), a read-only admin ("viewer") and
+    // a domain admin ("delegate") for delegate.example
+    fn test_config() -> Config {
+        let mut config = Config::default();
+        config.admins.push(AdminCredentials {
+            username: "viewer".to_string(),
+            password_hash: config.admins[0].password_hash.clone(),
+            role: AdminRole::ReadOnly,
+            databases: None,
+            api_tokens: vec![],
+            domains: vec![],
+        });
+        config.admins.push(AdminCredentials {
+            username: "delegate".to_string(),
+            password_hash: config.admins[0].password_hash.clone(),
+            role: AdminRole::Edit,
+            databases: Some(std::collections::HashMap::from([(
+                "test".to_string(),
+                AdminRole::Edit,
+            )])),
+            api_tokens: vec![],
+            domains: vec!["delegate.example".to_string()],
+        });
+        config
+    }
 
     async fn create_test_app() -> (Router, AppState) {
         let _pool = setup_test_db();
         let i18n = crate::i18n::I18n::new("en-US").expect("Failed to initialize i18n");
-        let config = Config::default();
+        let config = test_config();
 
         // Create a database manager with the test pool
         let db_config = vec![DatabaseConfig {
@@ -31,6 +57,8 @@ mod tests {
                 }),
             features: DatabaseFeatures::default(),
             field_map: std::collections::HashMap::new(),
+            password_scheme: Default::default(),
+            password_policy: None,
         }];
         let db_manager = crate::db::DatabaseManager::new(db_config)
             .await
@@ -39,7 +67,8 @@ mod tests {
         let state = AppState {
             db_manager,
             i18n,
-            config,
+            config: config.into(),
+            sessions: crate::session::SessionStore::default(),
         };
 
         // Create read-only routes
@@ -134,44 +163,62 @@ mod tests {
         let app = Router::new()
             .merge(read_only_routes)
             .merge(edit_routes)
-            .with_state(state.clone())
-            .fallback(handlers::not_found);
+            .fallback(handlers::not_found)
+            .with_state(state.clone());
 
         (app, state)
     }
 
     async fn create_test_app_with_dbs(db_configs: Vec<DatabaseConfig>) -> (Router, AppState) {
+        create_test_app_with_dbs_and_config(db_configs, test_config()).await
+    }
+
+    async fn create_test_app_with_dbs_and_config(
+        db_configs: Vec<DatabaseConfig>,
+        config: Config,
+    ) -> (Router, AppState) {
         let i18n = crate::i18n::I18n::new("en-US").expect("Failed to initialize i18n");
-        let config = Config::default();
         let db_manager = crate::db::DatabaseManager::new(db_configs)
             .await
             .expect("Failed to create database manager");
         let state = AppState {
             db_manager,
             i18n,
-            config,
+            config: config.into(),
+            sessions: crate::session::SessionStore::default(),
         };
         let app = Router::new()
             .route(
                 "/database/dropdown",
                 axum::routing::get(handlers::database::dropdown),
             )
+            .route(
+                "/database/select",
+                axum::routing::post(handlers::database::select),
+            )
             .with_state(state.clone());
         (app, state)
     }
 
-    // Helper function to create an authenticated cookie with a specific role
-    fn create_auth_cookie(role: AdminRole) -> HeaderValue {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        let expiry = now + 3600; // 1 hour from now
-        let role_str = match role {
-            AdminRole::ReadOnly => "read-only",
-            AdminRole::Edit => "edit",
+    // Helper function to create a session cookie for the test admin with a specific role
+    fn create_auth_cookie(state: &AppState, role: AdminRole) -> HeaderValue {
+        let username = match role {
+            AdminRole::Edit => "admin",
+            AdminRole::ReadOnly => "viewer",
         };
-        let cookie = format!("authenticated={}:{}", expiry, role_str);
+        create_session_cookie(state, username, &role)
+    }
+
+    fn create_session_cookie(state: &AppState, username: &str, role: &AdminRole) -> HeaderValue {
+        let session = state
+            .sessions
+            .create(username, role, &state.db_manager.get_default_db_id())
+            .expect("Failed to create session");
+        let cookie = format!(
+            "{}={}",
+            crate::session::SESSION_COOKIE,
+            state.sessions.sign(&session.id)
+        );
         cookie.parse().unwrap()
     }
 
@@ -193,14 +240,17 @@ mod tests {
             domain: format!("list-test-{}.com", unique_id),
             transport: Some("smtp:localhost".to_string()),
             enabled: true,
+            default_quota_bytes: None,
+            default_quota_messages: None,
+            quota_cap_bytes: None,
         };
-        let _domain = crate::db::create_domain(&pool, new_domain).unwrap();
+        let _domain = crate::db::create_domain(&pool, new_domain, &test_audit()).unwrap();
 
         let response = app
             .oneshot(
                 Request::builder()
                     .uri("/domains")
-                    .header("cookie", create_auth_cookie(AdminRole::ReadOnly))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::ReadOnly))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -243,7 +293,7 @@ mod tests {
                     .method("POST")
                     .uri("/domains")
                     .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
-                    .header("cookie", create_auth_cookie(AdminRole::Edit))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::Edit))
                     .body(Body::from(form_data))
                     .unwrap(),
             )
@@ -285,14 +335,17 @@ mod tests {
             domain: format!("show-test-{}.com", unique_id),
             transport: Some("smtp:localhost".to_string()),
             enabled: true,
+            default_quota_bytes: None,
+            default_quota_messages: None,
+            quota_cap_bytes: None,
         };
-        let _domain = crate::db::create_domain(&pool, new_domain).unwrap();
+        let _domain = crate::db::create_domain(&pool, new_domain, &test_audit()).unwrap();
 
         let response = app
             .oneshot(
                 Request::builder()
                     .uri(format!("/domains/{}", _domain.pkid))
-                    .header("cookie", create_auth_cookie(AdminRole::ReadOnly))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::ReadOnly))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -329,14 +382,17 @@ mod tests {
             domain: format!("edit-test-{}.com", unique_id),
             transport: Some("smtp:localhost".to_string()),
             enabled: true,
+            default_quota_bytes: None,
+            default_quota_messages: None,
+            quota_cap_bytes: None,
         };
-        let _domain = crate::db::create_domain(&pool, new_domain).unwrap();
+        let _domain = crate::db::create_domain(&pool, new_domain, &test_audit()).unwrap();
 
         let response = app
             .oneshot(
                 Request::builder()
                     .uri(format!("/domains/{}/edit", _domain.pkid))
-                    .header("cookie", create_auth_cookie(AdminRole::Edit))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::Edit))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -374,8 +430,11 @@ mod tests {
             domain: format!("update-test-{}.com", unique_id),
             transport: Some("smtp:localhost".to_string()),
             enabled: true,
+            default_quota_bytes: None,
+            default_quota_messages: None,
+            quota_cap_bytes: None,
         };
-        let _domain = crate::db::create_domain(&pool, new_domain).unwrap();
+        let _domain = crate::db::create_domain(&pool, new_domain, &test_audit()).unwrap();
 
         let form_data = format!(
             "domain=updated-test-{}.com&transport=smtp%3Aupdated&enabled=on",
@@ -388,7 +447,7 @@ mod tests {
                     .method("PUT")
                     .uri(format!("/domains/{}", _domain.pkid))
                     .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
-                    .header("cookie", create_auth_cookie(AdminRole::Edit))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::Edit))
                     .body(Body::from(form_data))
                     .unwrap(),
             )
@@ -426,8 +485,11 @@ mod tests {
             domain: format!("toggle-test-{}.com", unique_id),
             transport: Some("smtp:localhost".to_string()),
             enabled: true,
+            default_quota_bytes: None,
+            default_quota_messages: None,
+            quota_cap_bytes: None,
         };
-        let _domain = crate::db::create_domain(&pool, new_domain).unwrap();
+        let _domain = crate::db::create_domain(&pool, new_domain, &test_audit()).unwrap();
 
         // Toggle to disabled
         let response = app
@@ -436,7 +498,7 @@ mod tests {
                 Request::builder()
                     .method("POST")
                     .uri(format!("/domains/{}/toggle", _domain.pkid))
-                    .header("cookie", create_auth_cookie(AdminRole::Edit))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::Edit))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -470,8 +532,11 @@ mod tests {
             domain: format!("list-test-{}.com", unique_id),
             transport: Some("smtp:localhost".to_string()),
             enabled: true,
+            default_quota_bytes: None,
+            default_quota_messages: None,
+            quota_cap_bytes: None,
         };
-        let _domain = crate::db::create_domain(&pool, new_domain).unwrap();
+        let _domain = crate::db::create_domain(&pool, new_domain, &test_audit()).unwrap();
 
         // Create test user with unique name
         let user_form = crate::models::UserForm {
@@ -481,14 +546,18 @@ mod tests {
             enabled: true,
             change_password: false,
             maildir: "testdir".to_string(),
+            quota_bytes: None,
+            quota_messages: None,
         };
-        let _user = crate::db::create_user(&pool, user_form).unwrap();
+        let _user =
+            crate::db::create_user(&pool, user_form, PasswordScheme::default(), &test_audit())
+                .unwrap();
 
         let response = app
             .oneshot(
                 Request::builder()
                     .uri("/users")
-                    .header("cookie", create_auth_cookie(AdminRole::ReadOnly))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::ReadOnly))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -525,8 +594,11 @@ mod tests {
             domain: format!("create-test-{}.com", unique_id),
             transport: Some("smtp:localhost".to_string()),
             enabled: true,
+            default_quota_bytes: None,
+            default_quota_messages: None,
+            quota_cap_bytes: None,
         };
-        let _domain = crate::db::create_domain(&pool, new_domain).unwrap();
+        let _domain = crate::db::create_domain(&pool, new_domain, &test_audit()).unwrap();
 
         let form_data = format!(
             "id=testuser@create-test-{}.com&password=password123&name=Test+User&maildir=testdir&enabled=on",
@@ -539,7 +611,7 @@ mod tests {
                     .method("POST")
                     .uri("/users")
                     .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
-                    .header("cookie", create_auth_cookie(AdminRole::Edit))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::Edit))
                     .body(Body::from(form_data))
                     .unwrap(),
             )
@@ -576,8 +648,11 @@ mod tests {
             domain: format!("show-test-{}.com", unique_id),
             transport: Some("smtp:localhost".to_string()),
             enabled: true,
+            default_quota_bytes: None,
+            default_quota_messages: None,
+            quota_cap_bytes: None,
         };
-        let _domain = crate::db::create_domain(&pool, new_domain).unwrap();
+        let _domain = crate::db::create_domain(&pool, new_domain, &test_audit()).unwrap();
 
         // Create test user
         let user_form = crate::models::UserForm {
@@ -587,14 +662,18 @@ mod tests {
             enabled: true,
             change_password: false,
             maildir: "testdir".to_string(),
+            quota_bytes: None,
+            quota_messages: None,
         };
-        let _user = crate::db::create_user(&pool, user_form).unwrap();
+        let _user =
+            crate::db::create_user(&pool, user_form, PasswordScheme::default(), &test_audit())
+                .unwrap();
 
         let response = app
             .oneshot(
                 Request::builder()
                     .uri(format!("/users/{}", _user.id))
-                    .header("cookie", create_auth_cookie(AdminRole::ReadOnly))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::ReadOnly))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -631,8 +710,11 @@ mod tests {
             domain: format!("edit-test-{}.com", unique_id),
             transport: Some("smtp:localhost".to_string()),
             enabled: true,
+            default_quota_bytes: None,
+            default_quota_messages: None,
+            quota_cap_bytes: None,
         };
-        let _domain = crate::db::create_domain(&pool, new_domain).unwrap();
+        let _domain = crate::db::create_domain(&pool, new_domain, &test_audit()).unwrap();
 
         // Create test user
         let user_form = crate::models::UserForm {
@@ -642,14 +724,18 @@ mod tests {
             enabled: true,
             change_password: false,
             maildir: "testdir".to_string(),
+            quota_bytes: None,
+            quota_messages: None,
         };
-        let _user = crate::db::create_user(&pool, user_form).unwrap();
+        let _user =
+            crate::db::create_user(&pool, user_form, PasswordScheme::default(), &test_audit())
+                .unwrap();
 
         let response = app
             .oneshot(
                 Request::builder()
                     .uri(format!("/users/{}/edit", _user.id))
-                    .header("cookie", create_auth_cookie(AdminRole::Edit))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::Edit))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -687,8 +773,11 @@ mod tests {
             domain: format!("update-test-{}.com", unique_id),
             transport: Some("smtp:localhost".to_string()),
             enabled: true,
+            default_quota_bytes: None,
+            default_quota_messages: None,
+            quota_cap_bytes: None,
         };
-        let _domain = crate::db::create_domain(&pool, new_domain).unwrap();
+        let _domain = crate::db::create_domain(&pool, new_domain, &test_audit()).unwrap();
 
         // Create test user
         let user_form = crate::models::UserForm {
@@ -698,8 +787,12 @@ mod tests {
             enabled: true,
             change_password: false,
             maildir: "testdir".to_string(),
+            quota_bytes: None,
+            quota_messages: None,
         };
-        let _user = crate::db::create_user(&pool, user_form).unwrap();
+        let _user =
+            crate::db::create_user(&pool, user_form, PasswordScheme::default(), &test_audit())
+                .unwrap();
 
         let form_data = format!(
             "id=updateduser@update-test-{}.com&password=password123&name=Updated+User&maildir=testdir&enabled=on",
@@ -712,7 +805,7 @@ mod tests {
                     .method("PUT")
                     .uri(format!("/users/{}", _user.id))
                     .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
-                    .header("cookie", create_auth_cookie(AdminRole::Edit))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::Edit))
                     .body(Body::from(form_data))
                     .unwrap(),
             )
@@ -753,8 +846,11 @@ mod tests {
             domain: format!("toggle-test-{}.com", unique_id),
             transport: Some("smtp:localhost".to_string()),
             enabled: true,
+            default_quota_bytes: None,
+            default_quota_messages: None,
+            quota_cap_bytes: None,
         };
-        let _domain = crate::db::create_domain(&pool, new_domain).unwrap();
+        let _domain = crate::db::create_domain(&pool, new_domain, &test_audit()).unwrap();
 
         // Create test user
         let user_form = crate::models::UserForm {
@@ -764,8 +860,12 @@ mod tests {
             enabled: true,
             change_password: false,
             maildir: "testdir".to_string(),
+            quota_bytes: None,
+            quota_messages: None,
         };
-        let _user = crate::db::create_user(&pool, user_form).unwrap();
+        let _user =
+            crate::db::create_user(&pool, user_form, PasswordScheme::default(), &test_audit())
+                .unwrap();
 
         // Toggle to disabled
         let response = app
@@ -774,7 +874,7 @@ mod tests {
                 Request::builder()
                     .method("POST")
                     .uri(format!("/users/{}/toggle", _user.id))
-                    .header("cookie", create_auth_cookie(AdminRole::Edit))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::Edit))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -807,22 +907,27 @@ mod tests {
             domain: "aliases-list-test.com".to_string(),
             transport: Some("smtp:localhost".to_string()),
             enabled: true,
+            default_quota_bytes: None,
+            default_quota_messages: None,
+            quota_cap_bytes: None,
         };
-        let _domain = crate::db::create_domain(&pool, new_domain).unwrap();
+        let _domain = crate::db::create_domain(&pool, new_domain, &test_audit()).unwrap();
 
         let alias_form = crate::models::AliasForm {
             mail: "test@aliases-list-test.com".to_string(),
             destination: "user@aliases-list-test.com".to_string(),
             enabled: true,
             return_url: None,
+            valid_from: None,
+            valid_until: None,
         };
-        let _alias = crate::db::create_alias(&pool, alias_form).unwrap();
+        let _alias = crate::db::create_alias(&pool, alias_form, &test_audit()).unwrap();
 
         let response = app
             .oneshot(
                 Request::builder()
                     .uri("/aliases")
-                    .header("cookie", create_auth_cookie(AdminRole::ReadOnly))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::ReadOnly))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -859,8 +964,11 @@ mod tests {
             domain: "aliases-create-test.com".to_string(),
             transport: Some("smtp:localhost".to_string()),
             enabled: true,
+            default_quota_bytes: None,
+            default_quota_messages: None,
+            quota_cap_bytes: None,
         };
-        let _domain = crate::db::create_domain(&pool, new_domain).unwrap();
+        let _domain = crate::db::create_domain(&pool, new_domain, &test_audit()).unwrap();
 
         let form_data = "mail=test%40aliases-create-test.com&destination=user%40aliases-create-test.com&enabled=on";
 
@@ -870,7 +978,7 @@ mod tests {
                     .method("POST")
                     .uri("/aliases")
                     .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
-                    .header("cookie", create_auth_cookie(AdminRole::Edit))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::Edit))
                     .body(Body::from(form_data))
                     .unwrap(),
             )
@@ -906,8 +1014,11 @@ mod tests {
             domain: "stats-test.com".to_string(),
             transport: Some("smtp:localhost".to_string()),
             enabled: true,
+            default_quota_bytes: None,
+            default_quota_messages: None,
+            quota_cap_bytes: None,
         };
-        let _domain = crate::db::create_domain(&pool, new_domain).unwrap();
+        let _domain = crate::db::create_domain(&pool, new_domain, &test_audit()).unwrap();
 
         let user_form = crate::models::UserForm {
             id: "testuser@stats-test.com".to_string(),
@@ -916,22 +1027,28 @@ mod tests {
             enabled: true,
             change_password: false,
             maildir: "testdir".to_string(),
+            quota_bytes: None,
+            quota_messages: None,
         };
-        let _user = crate::db::create_user(&pool, user_form).unwrap();
+        let _user =
+            crate::db::create_user(&pool, user_form, PasswordScheme::default(), &test_audit())
+                .unwrap();
 
         let alias_form = crate::models::AliasForm {
             mail: "test@test.com".to_string(),
             destination: "user@test.com".to_string(),
             enabled: true,
             return_url: None,
+            valid_from: None,
+            valid_until: None,
         };
-        let _alias = crate::db::create_alias(&pool, alias_form).unwrap();
+        let _alias = crate::db::create_alias(&pool, alias_form, &test_audit()).unwrap();
 
         let response = app
             .oneshot(
                 Request::builder()
                     .uri("/stats")
-                    .header("cookie", create_auth_cookie(AdminRole::ReadOnly))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::ReadOnly))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -959,7 +1076,7 @@ mod tests {
             .oneshot(
                 Request::builder()
                     .uri("/dashboard")
-                    .header("cookie", create_auth_cookie(AdminRole::ReadOnly))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::ReadOnly))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -987,13 +1104,13 @@ mod tests {
 
     #[tokio::test]
     async fn test_about_handler() {
-        let (app, _state) = create_test_app().await;
+        let (app, state) = create_test_app().await;
 
         let response = app
             .oneshot(
                 Request::builder()
                     .uri("/about")
-                    .header("cookie", create_auth_cookie(AdminRole::ReadOnly))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::ReadOnly))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -1019,7 +1136,7 @@ mod tests {
             .oneshot(
                 Request::builder()
                     .uri("/notfound")
-                    .header("cookie", create_auth_cookie(AdminRole::ReadOnly))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::ReadOnly))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -1057,7 +1174,7 @@ mod tests {
                     .method("POST")
                     .uri("/backups")
                     .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
-                    .header("cookie", create_auth_cookie(AdminRole::Edit))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::Edit))
                     .body(Body::from(form_data))
                     .unwrap(),
             )
@@ -1092,13 +1209,13 @@ mod tests {
             transport: Some("smtp:localhost".to_string()),
             enabled: true,
         };
-        let _backup = crate::db::create_backup(&pool, new_backup).unwrap();
+        let _backup = crate::db::create_backup(&pool, new_backup, &test_audit()).unwrap();
 
         let response = app
             .oneshot(
                 Request::builder()
                     .uri(format!("/backups/{}", _backup.pkid))
-                    .header("cookie", create_auth_cookie(AdminRole::ReadOnly))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::ReadOnly))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -1135,13 +1252,13 @@ mod tests {
             transport: Some("smtp:localhost".to_string()),
             enabled: true,
         };
-        let _backup = crate::db::create_backup(&pool, new_backup).unwrap();
+        let _backup = crate::db::create_backup(&pool, new_backup, &test_audit()).unwrap();
 
         let response = app
             .oneshot(
                 Request::builder()
                     .uri(format!("/backups/{}/edit", _backup.pkid))
-                    .header("cookie", create_auth_cookie(AdminRole::Edit))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::Edit))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -1179,7 +1296,7 @@ mod tests {
             transport: Some("smtp:localhost".to_string()),
             enabled: true,
         };
-        let _backup = crate::db::create_backup(&pool, new_backup).unwrap();
+        let _backup = crate::db::create_backup(&pool, new_backup, &test_audit()).unwrap();
 
         let form_data = "domain=backup-updated-test.com&transport=smtp%3Aupdated&enabled=on";
 
@@ -1189,7 +1306,7 @@ mod tests {
                     .method("PUT")
                     .uri(format!("/backups/{}", _backup.pkid))
                     .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
-                    .header("cookie", create_auth_cookie(AdminRole::Edit))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::Edit))
                     .body(Body::from(form_data))
                     .unwrap(),
             )
@@ -1224,7 +1341,7 @@ mod tests {
             transport: Some("smtp:localhost".to_string()),
             enabled: true,
         };
-        let _backup = crate::db::create_backup(&pool, new_backup).unwrap();
+        let _backup = crate::db::create_backup(&pool, new_backup, &test_audit()).unwrap();
 
         // Toggle to disabled
         let response = app
@@ -1233,7 +1350,7 @@ mod tests {
                 Request::builder()
                     .method("POST")
                     .uri(format!("/backups/{}/toggle", _backup.pkid))
-                    .header("cookie", create_auth_cookie(AdminRole::Edit))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::Edit))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -1269,7 +1386,7 @@ mod tests {
                     .method("POST")
                     .uri("/backups")
                     .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
-                    .header("cookie", create_auth_cookie(AdminRole::Edit))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::Edit))
                     .body(Body::from(form_data))
                     .unwrap(),
             )
@@ -1307,7 +1424,7 @@ mod tests {
             transport: Some("smtp:localhost".to_string()),
             enabled: true,
         };
-        let _backup = crate::db::create_backup(&pool, new_backup).unwrap();
+        let _backup = crate::db::create_backup(&pool, new_backup, &test_audit()).unwrap();
 
         let form_data =
             "domain=backup-updated-content-test.com&transport=smtp%3Aupdated&enabled=on";
@@ -1318,7 +1435,7 @@ mod tests {
                     .method("PUT")
                     .uri(format!("/backups/{}", _backup.pkid))
                     .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
-                    .header("cookie", create_auth_cookie(AdminRole::Edit))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::Edit))
                     .body(Body::from(form_data))
                     .unwrap(),
             )
@@ -1358,14 +1475,14 @@ mod tests {
             transport: Some("smtp:localhost".to_string()),
             enabled: true,
         };
-        let _backup = crate::db::create_backup(&pool, new_backup).unwrap();
+        let _backup = crate::db::create_backup(&pool, new_backup, &test_audit()).unwrap();
 
         let response = app
             .oneshot(
                 Request::builder()
                     .method("DELETE")
                     .uri(format!("/backups/{}", _backup.pkid))
-                    .header("cookie", create_auth_cookie(AdminRole::Edit))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::Edit))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -1403,8 +1520,11 @@ mod tests {
             domain: format!("domain-backup-test-{}.com", unique_id),
             transport: Some("smtp:localhost".to_string()),
             enabled: true,
+            default_quota_bytes: None,
+            default_quota_messages: None,
+            quota_cap_bytes: None,
         };
-        let _domain = crate::db::create_domain(&pool, new_domain).unwrap();
+        let _domain = crate::db::create_domain(&pool, new_domain, &test_audit()).unwrap();
 
         // Create test backup
         let new_backup = crate::models::NewBackup {
@@ -1412,13 +1532,13 @@ mod tests {
             transport: Some("smtp:localhost".to_string()),
             enabled: true,
         };
-        let _backup = crate::db::create_backup(&pool, new_backup).unwrap();
+        let _backup = crate::db::create_backup(&pool, new_backup, &test_audit()).unwrap();
 
         let response = app
             .oneshot(
                 Request::builder()
                     .uri("/domains")
-                    .header("cookie", create_auth_cookie(AdminRole::ReadOnly))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::ReadOnly))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -1456,6 +1576,9 @@ mod tests {
                 password_hash: "$2b$12$KGfzf4xNi5FgHBN0/h2aLukhHgOIKz.mG1pavh4bgAkZpZJvyeBYO"
                     .to_string(),
                 role: AdminRole::Edit,
+                databases: None,
+                api_tokens: vec![],
+                domains: vec![],
             }],
             ..Config::default()
         };
@@ -1469,6 +1592,8 @@ mod tests {
                 }),
             features: DatabaseFeatures::default(),
             field_map: std::collections::HashMap::new(),
+            password_scheme: Default::default(),
+            password_policy: None,
         }];
         let db_manager = crate::db::DatabaseManager::new(db_config)
             .await
@@ -1476,7 +1601,8 @@ mod tests {
         let state = AppState {
             db_manager,
             i18n,
-            config,
+            config: config.into(),
+            sessions: crate::session::SessionStore::default(),
         };
         let headers = HeaderMap::new();
         let req = LoginRequest {
@@ -1493,8 +1619,9 @@ mod tests {
             .unwrap()
             .to_str()
             .unwrap();
-        assert!(set_cookie.contains("authenticated="));
-        assert!(set_cookie.contains("edit"));
+        assert!(set_cookie.contains("sortingoffice_session="));
+        assert!(set_cookie.contains("HttpOnly"));
+        assert!(!set_cookie.contains("edit"));
     }
 
     #[tokio::test]
@@ -1519,6 +1646,8 @@ mod tests {
                 }),
             features: DatabaseFeatures::default(),
             field_map: std::collections::HashMap::new(),
+            password_scheme: Default::default(),
+            password_policy: None,
         }];
         let db_manager = crate::db::DatabaseManager::new(db_config)
             .await
@@ -1526,7 +1655,8 @@ mod tests {
         let state = AppState {
             db_manager,
             i18n,
-            config,
+            config: config.into(),
+            sessions: crate::session::SessionStore::default(),
         };
         let headers = HeaderMap::new();
         let req = LoginRequest {
@@ -1539,84 +1669,99 @@ mod tests {
         assert!(html.contains("Error") || html.contains("error"));
     }
 
+    fn session_headers(cookie: HeaderValue) -> axum::http::HeaderMap {
+        let mut headers = axum::http::HeaderMap::new();
+        headers.insert("cookie", cookie);
+        headers
+    }
+
     #[tokio::test]
     async fn test_is_authenticated_cookie() {
         use crate::handlers::auth::is_authenticated;
-        use axum::http::HeaderMap;
-        use std::time::{SystemTime, UNIX_EPOCH};
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        let expiry = now + 3600;
-        let cookie = format!("authenticated={}:edit", expiry);
-        let mut headers = HeaderMap::new();
-        let header_value = cookie.parse().unwrap();
-        headers.insert("cookie", header_value);
-        assert!(is_authenticated(&headers));
+        let (_app, state) = create_test_app().await;
+        let headers = session_headers(create_auth_cookie(&state, AdminRole::Edit));
+        assert!(is_authenticated(&state, &headers));
     }
 
     #[tokio::test]
     async fn test_is_authenticated_cookie_expired() {
         use crate::handlers::auth::is_authenticated;
-        use axum::http::HeaderMap;
-        let expiry = 1; // long expired
-        let cookie = format!("authenticated={}:edit", expiry);
-        let mut headers = HeaderMap::new();
-        headers.insert("cookie", cookie.parse().unwrap());
-        assert!(!is_authenticated(&headers));
+        let (_app, mut state) = create_test_app().await;
+        state.sessions = crate::session::SessionStore::new(
+            &crate::config::SessionConfig {
+                idle_timeout_minutes: 0,
+                ..Default::default()
+            },
+            None,
+        );
+        let headers = session_headers(create_auth_cookie(&state, AdminRole::Edit));
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        assert!(!is_authenticated(&state, &headers));
     }
 
     #[tokio::test]
     async fn test_is_authenticated_cookie_readonly() {
         use crate::handlers::auth::is_authenticated;
-        use axum::http::HeaderMap;
-        use std::time::{SystemTime, UNIX_EPOCH};
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
+        let (_app, state) = create_test_app().await;
+        let headers = session_headers(create_auth_cookie(&state, AdminRole::ReadOnly));
+        assert!(is_authenticated(&state, &headers));
+    }
+
+    #[tokio::test]
+    async fn test_is_authenticated_rejects_forged_cookies() {
+        use crate::handlers::auth::is_authenticated;
+        let (_app, state) = create_test_app().await;
+
+        // The old plain cookie format is no longer accepted
+        let headers = session_headers("authenticated=9999999999:edit".parse().unwrap());
+        assert!(!is_authenticated(&state, &headers));
+
+        // A valid session ID with a tampered signature is rejected
+        let cookie = create_auth_cookie(&state, AdminRole::Edit);
+        let tampered = format!("{}x", cookie.to_str().unwrap());
+        let headers = session_headers(tampered.parse().unwrap());
+        assert!(!is_authenticated(&state, &headers));
+    }
+
+    #[tokio::test]
+    async fn test_logout_revokes_session() {
+        use crate::handlers::auth::{is_authenticated, logout};
+        use axum::extract::State;
+        let (_app, state) = create_test_app().await;
+        let headers = session_headers(create_auth_cookie(&state, AdminRole::Edit));
+        assert!(is_authenticated(&state, &headers));
+
+        let response = logout(State(state.clone()), headers.clone()).await;
+        let set_cookie = response
+            .headers()
+            .get("set-cookie")
             .unwrap()
-            .as_secs();
-        let expiry = now + 3600;
-        let cookie = format!("authenticated={}:read-only", expiry);
-        let mut headers = HeaderMap::new();
-        let header_value = cookie.parse().unwrap();
-        headers.insert("cookie", header_value);
-        assert!(is_authenticated(&headers));
+            .to_str()
+            .unwrap();
+        assert!(set_cookie.contains("Max-Age=0"));
+
+        // Replaying the old cookie after logout must fail
+        assert!(!is_authenticated(&state, &headers));
     }
 
     #[tokio::test]
     async fn test_has_edit_permissions() {
         use crate::handlers::auth::{has_edit_permissions, is_authenticated};
-        use axum::http::HeaderMap;
-        use std::time::{SystemTime, UNIX_EPOCH};
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        let expiry = now + 3600;
+        let (_app, state) = create_test_app().await;
 
         // Test edit role
-        let cookie = format!("authenticated={}:edit", expiry);
-        let mut headers = HeaderMap::new();
-        let header_value = cookie.parse().unwrap();
-        headers.insert("cookie", header_value);
-        assert!(is_authenticated(&headers));
-        assert!(has_edit_permissions(&headers));
+        let headers = session_headers(create_auth_cookie(&state, AdminRole::Edit));
+        assert!(is_authenticated(&state, &headers));
+        assert!(has_edit_permissions(&state, &headers));
 
         // Test read-only role
-        let cookie = format!("authenticated={}:read-only", expiry);
-        let mut headers = HeaderMap::new();
-        let header_value = cookie.parse().unwrap();
-        headers.insert("cookie", header_value);
-        assert!(is_authenticated(&headers));
-        assert!(!has_edit_permissions(&headers));
+        let headers = session_headers(create_auth_cookie(&state, AdminRole::ReadOnly));
+        assert!(is_authenticated(&state, &headers));
+        assert!(!has_edit_permissions(&state, &headers));
     }
 
     #[tokio::test]
     async fn test_role_based_access_control() {
-        use axum::http::HeaderMap;
-        use std::time::{SystemTime, UNIX_EPOCH};
-
         let (app, state) = create_test_app().await;
         let pool = state
             .db_manager
@@ -1625,16 +1770,8 @@ mod tests {
             .expect("Failed to get database pool");
         cleanup_test_db(&pool);
 
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        let expiry = now + 3600;
-
         // Test read-only user can access read-only routes
-        let cookie = format!("authenticated={}:read-only", expiry);
-        let mut headers = HeaderMap::new();
-        headers.insert("cookie", cookie.parse().unwrap());
+        let headers = session_headers(create_auth_cookie(&state, AdminRole::ReadOnly));
 
         let response = app
             .clone()
@@ -1670,9 +1807,7 @@ mod tests {
         assert_eq!(response.status(), StatusCode::FORBIDDEN);
 
         // Test edit user can access edit routes
-        let cookie = format!("authenticated={}:edit", expiry);
-        let mut headers = HeaderMap::new();
-        headers.insert("cookie", cookie.parse().unwrap());
+        let headers = session_headers(create_auth_cookie(&state, AdminRole::Edit));
 
         let response = app
             .oneshot(
@@ -1694,6 +1829,85 @@ mod tests {
         cleanup_test_db(&pool);
     }
 
+    #[tokio::test]
+    async fn test_domain_admin_is_limited_to_its_domains() {
+        let (app, state) = create_test_app().await;
+        let pool = state
+            .db_manager
+            .get_default_pool()
+            .await
+            .expect("Failed to get database pool");
+        cleanup_test_db(&pool);
+
+        for id in ["alice@delegate.example", "bob@other.example"] {
+            let user_form = crate::models::UserForm {
+                id: id.to_string(),
+                password: "password123".to_string(),
+                name: "Test User".to_string(),
+                enabled: true,
+                change_password: false,
+                maildir: String::new(),
+                quota_bytes: None,
+                quota_messages: None,
+            };
+            crate::db::create_user(&pool, user_form, PasswordScheme::default(), &test_audit())
+                .unwrap();
+        }
+        let cookie = create_session_cookie(&state, "delegate", &AdminRole::Edit);
+        let get = |uri: &str| {
+            Request::builder()
+                .uri(uri)
+                .header("cookie", cookie.clone())
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        // Everything but users and aliases is off limits
+        let response = app.clone().oneshot(get("/domains")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = app.clone().oneshot(get("/users")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body_str = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body_str.contains("alice@delegate.example"));
+        assert!(!body_str.contains("bob@other.example"));
+
+        let response = app
+            .clone()
+            .oneshot(get("/users/bob@other.example"))
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(!String::from_utf8(body.to_vec())
+            .unwrap()
+            .contains("bob@other.example"));
+
+        // Users cannot be created outside the admin's domains
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/users")
+                    .header("cookie", cookie.clone())
+                    .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+                    .body(Body::from(
+                        "id=carol%40other.example&password=Password-123&name=Carol&enabled=on",
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(crate::db::get_user_by_id(&pool, "carol@other.example").is_err());
+
+        cleanup_test_db(&pool);
+    }
+
     #[tokio::test]
     async fn test_not_found_handler_anonymous() {
         let (app, _state) = create_test_app().await;
@@ -1736,8 +1950,11 @@ mod tests {
             domain: format!("search-test-{}.com", unique_id),
             transport: Some("smtp:localhost".to_string()),
             enabled: true,
+            default_quota_bytes: None,
+            default_quota_messages: None,
+            quota_cap_bytes: None,
         };
-        let _domain = crate::db::create_domain(&pool, new_domain).unwrap();
+        let _domain = crate::db::create_domain(&pool, new_domain, &test_audit()).unwrap();
 
         // Create test aliases for search
         let alias1 = crate::models::AliasForm {
@@ -1745,16 +1962,20 @@ mod tests {
             destination: "user@company.com".to_string(),
             enabled: true,
             return_url: None,
+            valid_from: None,
+            valid_until: None,
         };
-        let _alias1 = crate::db::create_alias(&pool, alias1).unwrap();
+        let _alias1 = crate::db::create_alias(&pool, alias1, &test_audit()).unwrap();
 
         let alias2 = crate::models::AliasForm {
             mail: format!("support@search-test-{}.com", unique_id),
             destination: "helpdesk@company.com".to_string(),
             enabled: true,
             return_url: None,
+            valid_from: None,
+            valid_until: None,
         };
-        let _alias2 = crate::db::create_alias(&pool, alias2).unwrap();
+        let _alias2 = crate::db::create_alias(&pool, alias2, &test_audit()).unwrap();
 
         // Test 1: Search with valid query
         let response = app
@@ -1762,7 +1983,7 @@ mod tests {
             .oneshot(
                 Request::builder()
                     .uri(format!("/aliases/search?destination=user"))
-                    .header("cookie", create_auth_cookie(AdminRole::ReadOnly))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::ReadOnly))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -1785,7 +2006,7 @@ mod tests {
             .oneshot(
                 Request::builder()
                     .uri("/aliases/search?destination=a")
-                    .header("cookie", create_auth_cookie(AdminRole::ReadOnly))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::ReadOnly))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -1808,7 +2029,7 @@ mod tests {
             .oneshot(
                 Request::builder()
                     .uri("/aliases/search?destination=")
-                    .header("cookie", create_auth_cookie(AdminRole::ReadOnly))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::ReadOnly))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -1823,7 +2044,7 @@ mod tests {
             .oneshot(
                 Request::builder()
                     .uri("/aliases/search")
-                    .header("cookie", create_auth_cookie(AdminRole::ReadOnly))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::ReadOnly))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -1838,7 +2059,7 @@ mod tests {
             .oneshot(
                 Request::builder()
                     .uri(format!("/aliases/search?destination=admin"))
-                    .header("cookie", create_auth_cookie(AdminRole::ReadOnly))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::ReadOnly))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -1876,8 +2097,11 @@ mod tests {
             domain: format!("search-test-{}.com", unique_id),
             transport: Some("smtp:localhost".to_string()),
             enabled: true,
+            default_quota_bytes: None,
+            default_quota_messages: None,
+            quota_cap_bytes: None,
         };
-        let _domain = crate::db::create_domain(&pool, new_domain).unwrap();
+        let _domain = crate::db::create_domain(&pool, new_domain, &test_audit()).unwrap();
 
         // Test domain search with a query
         let response = app
@@ -1889,7 +2113,7 @@ mod tests {
                         "/aliases/domain-search?domain=search-test-{}",
                         unique_id
                     ))
-                    .header("cookie", create_auth_cookie(AdminRole::Edit))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::Edit))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -1934,6 +2158,8 @@ mod tests {
             url: url1,
             features: DatabaseFeatures::default(),
             field_map: std::collections::HashMap::new(),
+            password_scheme: Default::default(),
+            password_policy: None,
         };
         let db_config2 = DatabaseConfig {
             id: "test2".to_string(),
@@ -1941,15 +2167,17 @@ mod tests {
             url: url2,
             features: DatabaseFeatures::default(),
             field_map: std::collections::HashMap::new(),
+            password_scheme: Default::default(),
+            password_policy: None,
         };
-        let (app, _state) =
+        let (app, state) =
             create_test_app_with_dbs(vec![db_config1.clone(), db_config2.clone()]).await;
 
         let response = app
             .oneshot(
                 Request::builder()
                     .uri("/database/dropdown")
-                    .header("cookie", create_auth_cookie(AdminRole::ReadOnly))
+                    .header("cookie", create_auth_cookie(&state, AdminRole::ReadOnly))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -1967,4 +2195,107 @@ mod tests {
         // Should contain a form for /database/select
         assert!(body_str.contains("/database/select"));
     }
+
+    #[tokio::test]
+    async fn test_database_acl_restricts_dropdown_and_select() {
+        use crate::tests::testcontainers_setup::setup_test_db;
+        use diesel::RunQueryDsl;
+        let container = setup_test_db();
+        let port = container.get_mysql_port();
+        {
+            let pool = container.get_pool();
+            let mut conn = pool.get().unwrap();
+            for db in ["testdb1", "testdb2"] {
+                diesel::sql_query(format!("CREATE DATABASE IF NOT EXISTS {db}"))
+                    .execute(&mut conn)
+                    .unwrap();
+            }
+        }
+        let db_configs = ["test1", "test2"]
+            .iter()
+            .enumerate()
+            .map(|(i, id)| DatabaseConfig {
+                id: id.to_string(),
+                label: format!("Test Database {}", i + 1),
+                url: format!("mysql://root@127.0.0.1:{}/testdb{}", port, i + 1),
+                features: DatabaseFeatures::default(),
+                field_map: std::collections::HashMap::new(),
+                password_scheme: Default::default(),
+                password_policy: None,
+            })
+            .collect();
+
+        // "scoped" may only read test1 and has no access to test2
+        let mut config = test_config();
+        config.admins.push(AdminCredentials {
+            username: "scoped".to_string(),
+            password_hash: config.admins[0].password_hash.clone(),
+            role: AdminRole::Edit,
+            databases: Some(std::collections::HashMap::from([(
+                "test1".to_string(),
+                AdminRole::ReadOnly,
+            )])),
+            api_tokens: vec![],
+            domains: vec![],
+        });
+        let (app, state) = create_test_app_with_dbs_and_config(db_configs, config).await;
+        let cookie = create_session_cookie(&state, "scoped", &AdminRole::Edit);
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/database/dropdown")
+                    .header("cookie", cookie.clone())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body_str = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body_str.contains("Test Database 1"));
+        assert!(!body_str.contains("Test Database 2"));
+
+        // Selecting an ungranted database is forbidden
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/database/select")
+                    .header("cookie", cookie.clone())
+                    .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+                    .body(Body::from("database_id=test2"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        // The session keeps the per-database role, not the global one
+        let mut headers = axum::http::HeaderMap::new();
+        headers.insert("cookie", cookie);
+        assert_eq!(
+            crate::handlers::auth::get_user_role(&state, &headers),
+            Some(AdminRole::ReadOnly)
+        );
+        assert!(crate::handlers::utils::check_database_restrictions(
+            &state,
+            Some("scoped"),
+            "test1",
+            "create_domain"
+        )
+        .is_err());
+        assert!(crate::handlers::utils::check_database_restrictions(
+            &state,
+            Some("admin"),
+            "test1",
+            "create_domain"
+        )
+        .is_ok());
+    }
 }
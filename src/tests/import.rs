@@ -0,0 +1,380 @@
+#[cfg(test)]
+mod tests {
+    use crate::cli::{Cli, Command};
+    use crate::db::{self, AddressScope};
+    use crate::import::{
+        self, Existing, ImportError, ImportKind, ImportOptions, ImportWrite, ReportRow, RowIssue,
+        RowStatus,
+    };
+    use crate::models::*;
+    use crate::password::{PasswordPolicy, PasswordScheme, PolicyViolation};
+    use crate::tests::common::{cleanup_test_db, setup_test_db, test_audit, unique_test_id};
+    use chrono::NaiveDateTime;
+    use clap::Parser;
+
+    fn timestamp() -> NaiveDateTime {
+        chrono::DateTime::from_timestamp(1_790_000_000, 0)
+            .unwrap()
+            .naive_utc()
+    }
+
+    fn alias(pkid: i32, mail: &str, destination: &str) -> Alias {
+        Alias {
+            pkid,
+            mail: mail.to_string(),
+            destination: destination.to_string(),
+            created: timestamp(),
+            modified: timestamp(),
+            enabled: true,
+            valid_from: None,
+            valid_until: None,
+        }
+    }
+
+    fn user(id: &str, name: &str, quota_bytes: Option<i64>) -> User {
+        User {
+            id: id.to_string(),
+            enabled: true,
+            crypt: "hashed".to_string(),
+            name: name.to_string(),
+            maildir: format!("{id}/"),
+            home: "/var/spool/mail/virtual".to_string(),
+            uid: 5000,
+            gid: 5000,
+            created: timestamp(),
+            modified: timestamp(),
+            change_password: false,
+            quota_bytes,
+            quota_messages: None,
+        }
+    }
+
+    fn options<'a>(scope: &'a AddressScope, policy: &'a PasswordPolicy) -> ImportOptions<'a> {
+        ImportOptions {
+            update_existing: false,
+            scope,
+            policy,
+            can_create: true,
+            can_update: true,
+        }
+    }
+
+    #[test]
+    fn test_read_maps_headers_and_skips_blank_rows() {
+        let file = import::read(
+            ImportKind::Aliases,
+            "\u{feff}Address,Goto,Active,Notes\n\
+             sales@example.com,alice@example.com,yes,first\n\
+             ,,,\n\
+             info@example.com,bob@example.com,no,\n",
+        )
+        .unwrap();
+        let fields: Vec<Option<&str>> = file.columns.iter().map(|c| c.field).collect();
+        assert_eq!(
+            fields,
+            vec![Some("mail"), Some("destination"), Some("enabled"), None]
+        );
+        assert_eq!(file.row_count(), 2);
+        assert!(file.check_columns().is_ok());
+    }
+
+    #[test]
+    fn test_delimiter_detection() {
+        assert_eq!(import::delimiter("mail\tdestination\n"), b'\t');
+        assert_eq!(import::delimiter("mail;destination\n"), b';');
+        assert_eq!(import::delimiter("mail,destination;x\n"), b',');
+        assert_eq!(import::delimiter("mail\n"), b',');
+
+        let file = import::read(
+            ImportKind::Relocated,
+            "old\tnew\nold@example.com\tnew@example.org\n",
+        )
+        .unwrap();
+        assert_eq!(file.columns[1].field, Some("new_address"));
+        assert_eq!(file.row_count(), 1);
+    }
+
+    #[test]
+    fn test_column_mapping() {
+        let mut file =
+            import::read(ImportKind::Users, "login,fullname\nalice@example.com,A\n").unwrap();
+        assert_eq!(file.check_columns(), Err(ImportError::MissingKey("id")));
+
+        file.map_column("LOGIN", "id").unwrap();
+        file.map_column("fullname", "name").unwrap();
+        assert!(file.check_columns().is_ok());
+
+        assert_eq!(
+            file.map_column("missing", "id"),
+            Err(ImportError::UnknownHeader("missing".to_string()))
+        );
+        assert_eq!(
+            file.map_column("login", "crypt"),
+            Err(ImportError::UnknownField("crypt".to_string()))
+        );
+
+        file.set_mapping(&["id".to_string(), "id".to_string()])
+            .unwrap();
+        assert_eq!(file.check_columns(), Err(ImportError::DuplicateField("id")));
+        file.set_mapping(&["id".to_string(), "-".to_string()])
+            .unwrap();
+        assert_eq!(file.columns[1].field, None);
+
+        assert_eq!(
+            import::read(ImportKind::Users, "").unwrap_err(),
+            ImportError::Empty
+        );
+    }
+
+    #[test]
+    fn test_plan_aliases() {
+        let existing = Existing::new(&[
+            alias(1, "sales@example.com", "alice@example.com"),
+            alias(2, "info@example.com", "alice@example.com"),
+        ]);
+        let file = import::read(
+            ImportKind::Aliases,
+            "mail,destination,enabled\n\
+             new@example.com,alice@example.com bob@example.com,\n\
+             sales@example.com, Alice@Example.com ,yes\n\
+             info@example.com,bob@example.com,\n\
+             new@example.com,carol@example.com,\n\
+             bad@example.com,not-an-address,\n\
+             other@example.org,alice@example.com,\n\
+             flag@example.com,alice@example.com,maybe\n\
+             ,alice@example.com,\n",
+        )
+        .unwrap();
+        let scope = AddressScope::Domains(vec!["example.com".to_string()]);
+        let policy = PasswordPolicy::default();
+        let plan = import::plan_with(&file, &existing, &options(&scope, &policy));
+
+        let statuses: Vec<(usize, RowStatus)> =
+            plan.rows.iter().map(|row| (row.line, row.status)).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                (2, RowStatus::Create),
+                (3, RowStatus::Unchanged),
+                (4, RowStatus::Conflict),
+                (5, RowStatus::Conflict),
+                (6, RowStatus::Error),
+                (7, RowStatus::Error),
+                (8, RowStatus::Error),
+                (9, RowStatus::Error),
+            ]
+        );
+        assert_eq!(plan.rows[2].issues, vec![RowIssue::Exists]);
+        assert_eq!(plan.rows[2].changes[0].field, "destination");
+        assert_eq!(plan.rows[3].issues, vec![RowIssue::Duplicate(2)]);
+        assert_eq!(plan.rows[3].issues[0].message_args()["line"], "2");
+        assert!(matches!(plan.rows[4].issues[0], RowIssue::Destination(_)));
+        assert_eq!(plan.rows[5].issues, vec![RowIssue::OutOfScope]);
+        assert!(matches!(plan.rows[6].issues[0], RowIssue::Invalid(_)));
+        assert_eq!(plan.rows[7].issues, vec![RowIssue::Missing("mail")]);
+        assert_eq!(plan.count(RowStatus::Error), 4);
+        assert_eq!(plan.problems().count(), 6);
+
+        let writes = plan.into_writes();
+        assert_eq!(writes.len(), 1);
+        match &writes[0] {
+            (2, ImportWrite::CreateAlias(form)) => {
+                assert_eq!(form.destination, "alice@example.com, bob@example.com");
+                assert!(form.enabled);
+            }
+            other => panic!("unexpected write {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_plan_updates_keep_unmapped_fields() {
+        let existing = Existing::new(&[user("alice@example.com", "Alice", Some(2 << 30))]);
+        let file = import::read(
+            ImportKind::Users,
+            "email,name,password\n\
+             alice@example.com,Alice Smith,\n\
+             bob@example.com,Bob,\n\
+             carol@example.com,Carol,short\n",
+        )
+        .unwrap();
+        let scope = AddressScope::All;
+        let policy = PasswordPolicy {
+            min_length: 8,
+            ..PasswordPolicy::default()
+        };
+        let mut opts = options(&scope, &policy);
+        opts.update_existing = true;
+        let plan = import::plan_with(&file, &existing, &opts);
+
+        assert_eq!(plan.rows[0].status, RowStatus::Update);
+        assert_eq!(plan.rows[0].changes.len(), 1);
+        assert_eq!(
+            plan.rows[0].changes[0].to_string(),
+            "name: Alice → Alice Smith"
+        );
+        assert_eq!(plan.rows[1].issues, vec![RowIssue::PasswordRequired]);
+        assert_eq!(
+            plan.rows[2].issues,
+            vec![RowIssue::Password(PolicyViolation::TooShort(8))]
+        );
+
+        match &plan.clone().into_writes()[0] {
+            (2, ImportWrite::UpdateUser(id, form)) => {
+                assert_eq!(id, "alice@example.com");
+                assert_eq!(form.quota_bytes, Some(2 << 30));
+                assert!(form.password.is_empty());
+            }
+            other => panic!("unexpected write {other:?}"),
+        }
+
+        // Without the update grant the change is an error rather than a write
+        opts.can_update = false;
+        let plan = import::plan_with(&file, &existing, &opts);
+        assert_eq!(plan.rows[0].status, RowStatus::Error);
+        assert_eq!(plan.rows[0].issues, vec![RowIssue::NotAllowed]);
+    }
+
+    #[test]
+    fn test_plan_compares_parsed_values() {
+        let existing = Existing::new(&[Domain {
+            pkid: 7,
+            domain: "example.com".to_string(),
+            transport: Some("virtual".to_string()),
+            created: timestamp(),
+            modified: timestamp(),
+            enabled: true,
+            default_quota_bytes: Some(2 << 30),
+            default_quota_messages: None,
+            quota_cap_bytes: None,
+        }]);
+        let file = import::read(
+            ImportKind::Domains,
+            "domain,default_quota_bytes,enabled\n\
+             example.com,2147483648,1\n\
+             example.org,,\n",
+        )
+        .unwrap();
+        let scope = AddressScope::All;
+        let policy = PasswordPolicy::default();
+        let plan = import::plan_with(&file, &existing, &options(&scope, &policy));
+        assert_eq!(plan.rows[0].status, RowStatus::Unchanged);
+        assert_eq!(plan.rows[1].status, RowStatus::Create);
+        match &plan.into_writes()[0].1 {
+            ImportWrite::CreateDomain(form) => {
+                assert_eq!(form.transport, "virtual");
+                assert!(form.enabled);
+            }
+            other => panic!("unexpected write {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_report_csv() {
+        let csv = import::report_csv(&[ReportRow {
+            line: 4,
+            key: "info@example.com".to_string(),
+            status: "conflict",
+            message: "Already exists, and more".to_string(),
+        }]);
+        assert_eq!(
+            csv,
+            "line,key,status,message\n4,info@example.com,conflict,\"Already exists, and more\"\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_import_command() {
+        let cli = Cli::try_parse_from([
+            "sortingoffice-cli",
+            "import",
+            "aliases",
+            "aliases.csv",
+            "--map",
+            "goto=destination",
+            "--map",
+            "notes=-",
+            "--update",
+        ])
+        .unwrap();
+        match cli.command {
+            Command::Import {
+                kind,
+                map,
+                update,
+                apply,
+                ..
+            } => {
+                assert_eq!(kind, ImportKind::Aliases);
+                assert_eq!(
+                    map,
+                    vec![
+                        ("goto".to_string(), "destination".to_string()),
+                        ("notes".to_string(), "-".to_string())
+                    ]
+                );
+                assert!(update);
+                assert!(!apply);
+            }
+            other => panic!("unexpected command {other:?}"),
+        }
+
+        assert!(Cli::try_parse_from([
+            "sortingoffice-cli",
+            "import",
+            "aliases",
+            "aliases.csv",
+            "--map",
+            "goto"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_apply_import_is_all_or_nothing() {
+        let pool = setup_test_db();
+        cleanup_test_db(&pool);
+        let audit = test_audit();
+        let domain = format!("import-{}.com", unique_test_id());
+        let form = |mail: &str| AliasForm {
+            mail: format!("{mail}@{domain}"),
+            destination: format!("alice@{domain}"),
+            enabled: true,
+            valid_from: None,
+            valid_until: None,
+            return_url: None,
+        };
+
+        let written = db::apply_import(
+            &pool,
+            vec![
+                (2, ImportWrite::CreateAlias(form("sales"))),
+                (3, ImportWrite::CreateAlias(form("info"))),
+            ],
+            PasswordScheme::default(),
+            &audit,
+        )
+        .unwrap();
+        assert_eq!(written, 2);
+
+        // The duplicate on line 3 rolls back the alias of line 2
+        let failure = db::apply_import(
+            &pool,
+            vec![
+                (2, ImportWrite::CreateAlias(form("support"))),
+                (3, ImportWrite::CreateAlias(form("sales"))),
+            ],
+            PasswordScheme::default(),
+            &audit,
+        )
+        .unwrap_err();
+        assert_eq!(failure.line, Some(3));
+        let mails: Vec<String> = db::get_aliases(&pool)
+            .unwrap()
+            .into_iter()
+            .map(|alias| alias.mail)
+            .collect();
+        assert!(!mails.contains(&format!("support@{domain}")));
+
+        cleanup_test_db(&pool);
+    }
+}
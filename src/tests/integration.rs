@@ -31,6 +31,8 @@ mod tests {
             ),
             features: DatabaseFeatures::default(),
             field_map: std::collections::HashMap::new(),
+            password_scheme: Default::default(),
+            password_policy: None,
         }];
         let db_manager = crate::db::DatabaseManager::new(db_config)
             .await
@@ -40,7 +42,8 @@ mod tests {
         let state = AppState {
             db_manager,
             i18n,
-            config,
+            config: config.into(),
+            sessions: crate::session::SessionStore::default(),
         };
         let app = Router::new()
             .route("/domains", axum::routing::get(handlers::domains::list))
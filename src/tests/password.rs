@@ -0,0 +1,341 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::{Config, ConfigError, DatabaseConfig, DatabaseFeatures};
+    use crate::password::{
+        check_policy, generate_password, hash_password, identify, is_common_password, needs_rehash,
+        verify_password, HashInfo, PasswordPolicy, PasswordScheme, PolicyViolation,
+    };
+
+    /// Hashes of "secret" as other tools write them
+    const LEGACY_HASHES: &[(&str, PasswordScheme)] = &[
+        (
+            "{MD5-CRYPT}$1$saltsalt$9xy1btjgzLYfb7hivXtC//",
+            PasswordScheme::Md5Crypt,
+        ),
+        (
+            "{SHA256-CRYPT}$5$saltsalt$0IyaXrmV7.sGNS6tirgqHLqX/G.FBvgkYA.lpPdS5sA",
+            PasswordScheme::Sha256Crypt,
+        ),
+        (
+            "{CRYPT}$6$saltsalt$TVLlQcbpFVof5W3Yz4DTP6gRstiNuHwwTt6GLc1E5n0U0aDehy0S5knV8wiOQSpT0Y77vwPZN.Pq.H91p5hVO1",
+            PasswordScheme::Sha512Crypt,
+        ),
+        (
+            "{SSHA512}KO8EsMPQTwZrxxbOkDAOOXEeVCc2grMQg1pnZwZhC1bBQLby8zCmFn7qTZRvoTd+yQdROQQNYHWpTUST4zjTdQECAwQFBgcI",
+            PasswordScheme::Ssha512,
+        ),
+        (
+            "{SSHA256}A7N1lAy5bBb4T6qH9e85zAvHBmzNPhRFbZ105DjjWDIBAgMEBQYHCA==",
+            PasswordScheme::Ssha256,
+        ),
+        (
+            "{SHA512}vSsar3708Jvp9Szi2NWZZ02Bqp1qRCFpbcTZPdBhnWgs5WtNZKnvCXdhztmeD2cmW192CF5bDufKRpayrW/isg==",
+            PasswordScheme::Sha512,
+        ),
+        (
+            "{SHA256}K7gNU3sdo+OL0wNhqoVWhr3g6s1xYv72ol/pe/Unols=",
+            PasswordScheme::Sha256,
+        ),
+        ("{PLAIN}secret", PasswordScheme::Plain),
+        ("{CLEARTEXT}secret", PasswordScheme::Plain),
+    ];
+
+    #[test]
+    fn test_hash_and_verify_each_scheme() {
+        for scheme in PasswordScheme::ALL.into_iter().filter(|s| s.can_hash()) {
+            let hash = hash_password("secret", scheme).unwrap();
+            assert_eq!(
+                identify(&hash),
+                Some(HashInfo {
+                    scheme,
+                    prefixed: scheme.is_prefixed(),
+                }),
+                "{hash}"
+            );
+            assert!(verify_password("secret", &hash).unwrap(), "{hash}");
+            assert!(!verify_password("wrong", &hash).unwrap(), "{hash}");
+            assert!(!needs_rehash(&hash, scheme), "{hash}");
+        }
+    }
+
+    #[test]
+    fn test_hash_prefixes() {
+        assert!(hash_password("secret", PasswordScheme::Bcrypt)
+            .unwrap()
+            .starts_with("$2b$"));
+        assert!(hash_password("secret", PasswordScheme::BlfCrypt)
+            .unwrap()
+            .starts_with("{BLF-CRYPT}$2y$"));
+        assert!(hash_password("secret", PasswordScheme::Sha512Crypt)
+            .unwrap()
+            .starts_with("{SHA512-CRYPT}$6$"));
+        assert!(hash_password("secret", PasswordScheme::Argon2id)
+            .unwrap()
+            .starts_with("{ARGON2ID}$argon2id$"));
+        assert!(hash_password("secret", PasswordScheme::Argon2i)
+            .unwrap()
+            .starts_with("{ARGON2I}$argon2i$"));
+
+        // Verify-only schemes are refused rather than written
+        assert!(hash_password("secret", PasswordScheme::Plain).is_err());
+        assert!(hash_password("secret", PasswordScheme::Ssha512).is_err());
+    }
+
+    #[test]
+    fn test_verify_legacy_hashes() {
+        for (hash, scheme) in LEGACY_HASHES {
+            assert_eq!(identify(hash).map(|info| info.scheme), Some(*scheme));
+            assert!(verify_password("secret", hash).unwrap(), "{hash}");
+            assert!(!verify_password("Secret", hash).unwrap(), "{hash}");
+        }
+
+        // crypt(3) hashes identify themselves without a prefix
+        assert_eq!(
+            identify("$1$saltsalt$9xy1btjgzLYfb7hivXtC//"),
+            Some(HashInfo {
+                scheme: PasswordScheme::Md5Crypt,
+                prefixed: false,
+            })
+        );
+        assert!(verify_password("secret", "$1$saltsalt$9xy1btjgzLYfb7hivXtC//").unwrap());
+
+        // A prefixed bcrypt hash is BLF-CRYPT, however it is named
+        let bcrypt = hash_password("secret", PasswordScheme::Bcrypt).unwrap();
+        assert_eq!(
+            identify(&format!("{{CRYPT}}{bcrypt}")).map(|info| info.scheme),
+            Some(PasswordScheme::BlfCrypt)
+        );
+    }
+
+    #[test]
+    fn test_unusable_hashes() {
+        assert_eq!(identify("not a hash"), None);
+        assert_eq!(identify("{NOPE}abc"), None);
+        assert!(verify_password("secret", "not a hash").is_err());
+        assert!(verify_password("secret", "{SHA256}!!!").is_err());
+        // A salted digest decoded as unsalted has the wrong length
+        assert!(verify_password("secret", &LEGACY_HASHES[4].0.replace("SSHA", "SHA")).is_err());
+    }
+
+    #[test]
+    fn test_needs_rehash() {
+        let bcrypt = hash_password("secret", PasswordScheme::Bcrypt).unwrap();
+
+        // Unprefixed bcrypt is fine as long as the database keeps using it
+        assert!(!needs_rehash(&bcrypt, PasswordScheme::Bcrypt));
+        // Legacy hashes are flagged once the database moves to a prefixed scheme
+        assert!(needs_rehash(&bcrypt, PasswordScheme::BlfCrypt));
+        assert!(needs_rehash(&bcrypt, PasswordScheme::Argon2id));
+
+        let sha512_crypt = LEGACY_HASHES[2].0;
+        assert!(!needs_rehash(sha512_crypt, PasswordScheme::Sha512Crypt));
+        assert!(needs_rehash(sha512_crypt, PasswordScheme::BlfCrypt));
+        // A stronger hash than the configured scheme is left alone
+        let argon2 = hash_password("secret", PasswordScheme::Argon2id).unwrap();
+        assert!(!needs_rehash(&argon2, PasswordScheme::Sha512Crypt));
+
+        for (hash, _) in &LEGACY_HASHES[3..] {
+            assert!(needs_rehash(hash, PasswordScheme::Bcrypt), "{hash}");
+        }
+        assert!(needs_rehash("not a hash", PasswordScheme::Bcrypt));
+    }
+
+    #[test]
+    fn test_scheme_names() {
+        for scheme in PasswordScheme::ALL {
+            assert_eq!(PasswordScheme::parse(scheme.as_str()), Some(scheme));
+            let json = serde_json::to_string(&scheme).unwrap();
+            assert_eq!(json, format!("\"{scheme}\""));
+        }
+        assert_eq!(
+            PasswordScheme::parse("blf-crypt"),
+            Some(PasswordScheme::BlfCrypt)
+        );
+        assert_eq!(PasswordScheme::parse("MD4"), None);
+    }
+
+    #[test]
+    fn test_config_password_scheme() {
+        let mut config = Config {
+            databases: vec![DatabaseConfig {
+                id: "primary".to_string(),
+                label: "Primary".to_string(),
+                url: "mysql://localhost/primary".to_string(),
+                features: DatabaseFeatures::default(),
+                field_map: Default::default(),
+                password_scheme: PasswordScheme::Argon2id,
+                password_policy: None,
+            }],
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+        assert_eq!(config.password_scheme("primary"), PasswordScheme::Argon2id);
+        assert_eq!(config.password_scheme("missing"), PasswordScheme::Bcrypt);
+
+        // Verify-only schemes cannot be configured for new passwords
+        config.databases[0].password_scheme = PasswordScheme::Ssha512;
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::PasswordScheme(id, PasswordScheme::Ssha512)) if id == "primary"
+        ));
+    }
+
+    fn strict_policy() -> PasswordPolicy {
+        PasswordPolicy {
+            min_length: 12,
+            require_lowercase: true,
+            require_uppercase: true,
+            require_digit: true,
+            require_symbol: true,
+            reject_user_details: true,
+            reject_common: true,
+        }
+    }
+
+    #[test]
+    fn test_default_policy() {
+        // Without a configured policy nothing is rejected, as before policies existed
+        let policy = PasswordPolicy::default();
+        for password in ["correct horse", "short", "password123", "alice1"] {
+            assert!(check_policy(&policy, password, "alice@example.com", "Alice").is_empty());
+        }
+
+        let policy = PasswordPolicy {
+            min_length: 8,
+            ..PasswordPolicy::default()
+        };
+        assert_eq!(
+            check_policy(&policy, "short", "alice@example.com", "Alice"),
+            vec![PolicyViolation::TooShort(8)]
+        );
+    }
+
+    #[test]
+    fn test_policy_character_classes() {
+        let policy = strict_policy();
+        assert!(check_policy(&policy, "Tr0ub4dor&3x!", "alice@example.com", "Alice").is_empty());
+        assert_eq!(
+            check_policy(&policy, "tr0ub4dor&3x!", "alice@example.com", "Alice"),
+            vec![PolicyViolation::MissingUppercase]
+        );
+        assert_eq!(
+            check_policy(&policy, "abc", "alice@example.com", "Alice"),
+            vec![
+                PolicyViolation::TooShort(12),
+                PolicyViolation::MissingUppercase,
+                PolicyViolation::MissingDigit,
+                PolicyViolation::MissingSymbol,
+            ]
+        );
+        // Non-ASCII letters count as letters, not symbols, and length is in characters
+        assert_eq!(
+            check_policy(&policy, "Ærlighet1øå", "alice@example.com", "Alice"),
+            vec![
+                PolicyViolation::TooShort(12),
+                PolicyViolation::MissingSymbol
+            ]
+        );
+    }
+
+    #[test]
+    fn test_policy_rejects_user_details() {
+        let policy = PasswordPolicy {
+            reject_user_details: true,
+            ..PasswordPolicy::default()
+        };
+        assert_eq!(
+            check_policy(&policy, "Alice@Example.com!", "alice@example.com", ""),
+            vec![PolicyViolation::ContainsAddress]
+        );
+        assert_eq!(
+            check_policy(&policy, "xxALICExx", "alice@example.com", ""),
+            vec![PolicyViolation::ContainsAddress]
+        );
+        assert_eq!(
+            check_policy(&policy, "smithereens", "jo@example.com", "Jo Smith"),
+            vec![PolicyViolation::ContainsName]
+        );
+        // Parts too short to matter are ignored
+        assert!(check_policy(&policy, "jollygood", "jo@example.com", "Jo").is_empty());
+
+        let lenient = PasswordPolicy::default();
+        assert!(check_policy(&lenient, "alice@example.com", "alice@example.com", "").is_empty());
+    }
+
+    #[test]
+    fn test_common_passwords() {
+        assert!(is_common_password("password123"));
+        assert!(is_common_password("Qwerty123"));
+        assert!(!is_common_password("correct horse battery staple"));
+        // The list's comment lines are not passwords
+        assert!(!is_common_password(
+            "# One per line, lowercase; passwords are compared ignoring case."
+        ));
+
+        let policy = strict_policy();
+        assert_eq!(
+            check_policy(&policy, "P@ssw0rd", "alice@example.com", "Alice"),
+            vec![PolicyViolation::TooShort(12), PolicyViolation::Common]
+        );
+    }
+
+    #[test]
+    fn test_generated_passwords_satisfy_policy() {
+        let policy = strict_policy();
+        for _ in 0..50 {
+            let password = generate_password(&policy);
+            assert_eq!(password.len(), 16);
+            assert!(
+                check_policy(&policy, &password, "alice@example.com", "Alice").is_empty(),
+                "{password}"
+            );
+        }
+
+        let long = PasswordPolicy {
+            min_length: 40,
+            ..strict_policy()
+        };
+        assert_eq!(generate_password(&long).len(), 40);
+        assert_ne!(generate_password(&policy), generate_password(&policy));
+    }
+
+    #[test]
+    fn test_config_password_policy() {
+        let config: Config = toml::from_str(
+            r#"
+            required_aliases = []
+            common_aliases = []
+
+            [password_policy]
+            min_length = 10
+            reject_common = true
+            reject_user_details = true
+
+            [[databases]]
+            id = "primary"
+            label = "Primary"
+            url = "mysql://localhost/primary"
+
+            [[databases]]
+            id = "legacy"
+            label = "Legacy"
+            url = "mysql://localhost/legacy"
+            [databases.password_policy]
+            min_length = 6
+            "#,
+        )
+        .unwrap();
+
+        let global = config.password_policy("primary");
+        assert_eq!(global.min_length, 10);
+        assert!(global.reject_common);
+        assert!(global.reject_user_details);
+        assert_eq!(config.password_policy("unknown"), global);
+
+        // A database's own policy replaces the global one entirely
+        let legacy = config.password_policy("legacy");
+        assert_eq!(legacy.min_length, 6);
+        assert!(!legacy.reject_common);
+        assert!(!legacy.reject_user_details);
+    }
+}
@@ -0,0 +1,177 @@
+#[cfg(test)]
+mod tests {
+    use crate::db;
+    use crate::models::*;
+    use crate::password::PasswordScheme;
+    use crate::portal::{parse_forward_addresses, Forwarding, ForwardingError, MAX_FORWARDS};
+    use crate::tests::common::{cleanup_test_db, setup_test_db, test_audit, unique_test_id};
+
+    fn alias(mail: &str, destination: &str, enabled: bool) -> Alias {
+        let now = chrono::Utc::now().naive_utc();
+        Alias {
+            pkid: 1,
+            mail: mail.to_string(),
+            destination: destination.to_string(),
+            created: now,
+            modified: now,
+            enabled,
+            valid_from: None,
+            valid_until: None,
+        }
+    }
+
+    #[test]
+    fn test_forwarding_from_alias() {
+        let address = "alice@example.com";
+
+        // No alias: everything is delivered to the mailbox
+        let forwarding = Forwarding::from_alias(address, None);
+        assert!(forwarding.addresses.is_empty());
+        assert!(forwarding.keep_copy);
+        assert!(forwarding.enabled);
+
+        let own = alias(address, "Alice@Example.com, alice@elsewhere.org", true);
+        let forwarding = Forwarding::from_alias(address, Some(&own));
+        assert_eq!(forwarding.addresses, vec!["alice@elsewhere.org"]);
+        assert!(forwarding.keep_copy);
+
+        let own = alias(address, "alice@elsewhere.org bob@example.com", false);
+        let forwarding = Forwarding::from_alias(address, Some(&own));
+        assert_eq!(
+            forwarding.addresses,
+            vec!["alice@elsewhere.org", "bob@example.com"]
+        );
+        assert!(!forwarding.keep_copy);
+        assert!(!forwarding.enabled);
+    }
+
+    #[test]
+    fn test_forwarding_destination() {
+        let mut forwarding = Forwarding {
+            addresses: vec!["alice@elsewhere.org".to_string()],
+            keep_copy: true,
+            enabled: true,
+        };
+        assert_eq!(
+            forwarding.destination("Alice@example.com").as_deref(),
+            Some("alice@example.com, alice@elsewhere.org")
+        );
+
+        forwarding.keep_copy = false;
+        assert_eq!(
+            forwarding.destination("alice@example.com").as_deref(),
+            Some("alice@elsewhere.org")
+        );
+
+        // Nothing to forward means the alias goes away
+        forwarding.addresses.clear();
+        assert_eq!(forwarding.destination("alice@example.com"), None);
+    }
+
+    #[test]
+    fn test_parse_forward_addresses() {
+        let address = "alice@example.com";
+        assert_eq!(
+            parse_forward_addresses(
+                "alice@elsewhere.org\nBob@Example.com, alice@elsewhere.org\n\nalice@example.com",
+                address
+            ),
+            Ok(vec![
+                "alice@elsewhere.org".to_string(),
+                "bob@example.com".to_string()
+            ])
+        );
+        assert_eq!(parse_forward_addresses("  ", address), Ok(vec![]));
+
+        for invalid in ["alice", "@example.com", "alice@", "a@b@c"] {
+            assert_eq!(
+                parse_forward_addresses(invalid, address),
+                Err(ForwardingError::InvalidAddress(invalid.to_string()))
+            );
+        }
+
+        let many: Vec<String> = (0..=MAX_FORWARDS)
+            .map(|n| format!("user{n}@example.org"))
+            .collect();
+        assert_eq!(
+            parse_forward_addresses(&many.join("\n"), address),
+            Err(ForwardingError::TooMany)
+        );
+    }
+
+    #[test]
+    fn test_portal_password_change_and_aliases() {
+        let pool = setup_test_db();
+        cleanup_test_db(&pool);
+        let audit = test_audit();
+        let domain = format!("portal-{}.com", unique_test_id());
+        let address = format!("alice@{domain}");
+
+        db::create_user(
+            &pool,
+            UserForm {
+                id: address.clone(),
+                password: "Initial-Password-1".to_string(),
+                name: "Alice".to_string(),
+                maildir: String::new(),
+                enabled: true,
+                change_password: true,
+                quota_bytes: None,
+                quota_messages: None,
+            },
+            PasswordScheme::default(),
+            &audit,
+        )
+        .unwrap();
+
+        // An admin reset keeps the flag; the user's own change clears it
+        db::update_user_password(
+            &pool,
+            address.clone(),
+            "Reset-Password-2",
+            PasswordScheme::default(),
+            &audit,
+        )
+        .unwrap();
+        assert!(db::get_user_by_id(&pool, &address).unwrap().change_password);
+        db::update_own_password(
+            &pool,
+            address.clone(),
+            "Chosen-Password-3",
+            PasswordScheme::default(),
+            &audit,
+        )
+        .unwrap();
+        let user = db::get_user_by_id(&pool, &address).unwrap();
+        assert!(!user.change_password);
+        assert!(crate::password::verify_password("Chosen-Password-3", &user.crypt).unwrap());
+
+        for (mail, destination) in [
+            (format!("info@{domain}"), format!("{address}, bob@{domain}")),
+            (format!("@{domain}"), address.clone()),
+            (format!("other@{domain}"), format!("jimalice@{domain}")),
+            (address.clone(), format!("{address} alice@elsewhere.org")),
+        ] {
+            db::create_alias(
+                &pool,
+                AliasForm {
+                    mail,
+                    destination,
+                    enabled: true,
+                    return_url: None,
+                    valid_from: None,
+                    valid_until: None,
+                },
+                &audit,
+            )
+            .unwrap();
+        }
+
+        // Exact destinations only, and not the mailbox's own forwarding alias
+        let aliases = db::get_aliases_for_destination(&pool, &address).unwrap();
+        let mails: Vec<&str> = aliases.iter().map(|alias| alias.mail.as_str()).collect();
+        assert_eq!(mails, vec![format!("@{domain}"), format!("info@{domain}")]);
+
+        cleanup_test_db(&pool);
+    }
+}
@@ -0,0 +1,200 @@
+#[cfg(test)]
+mod tests {
+    use crate::db;
+    use crate::models::*;
+    use crate::password::PasswordScheme;
+    use crate::quota::{effective, format_size, human_size, parse_count, parse_size};
+    use crate::tests::common::{cleanup_test_db, setup_test_db, test_audit, unique_test_id};
+    use diesel::result::{DatabaseErrorKind, Error};
+
+    const GIB: i64 = 1 << 30;
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("1048576"), Ok(1 << 20));
+        assert_eq!(parse_size("512M"), Ok(512 << 20));
+        assert_eq!(parse_size(" 2g "), Ok(2 * GIB));
+        assert_eq!(parse_size("2GiB"), Ok(2 * GIB));
+        assert_eq!(parse_size("2 GB"), Ok(2 * GIB));
+        assert_eq!(parse_size("1.5G"), Ok(3 * GIB / 2));
+        assert_eq!(parse_size("1T"), Ok(1 << 40));
+        assert_eq!(parse_size("0"), Ok(0));
+
+        for invalid in ["", "G", "-1G", "2X", "lots", "99999999999T"] {
+            assert!(parse_size(invalid).is_err(), "{invalid} should not parse");
+        }
+    }
+
+    #[test]
+    fn test_parse_count() {
+        assert_eq!(parse_count(" 1000 "), Ok(1000));
+        assert!(parse_count("-5").is_err());
+        assert!(parse_count("10k").is_err());
+    }
+
+    #[test]
+    fn test_format_size_roundtrips() {
+        assert_eq!(format_size(2 * GIB), "2G");
+        assert_eq!(format_size(3 * GIB / 2), "1536M");
+        assert_eq!(format_size(1000), "1000");
+        assert_eq!(format_size(0), "0");
+        for bytes in [0, 1000, 1 << 20, 3 * GIB / 2, 5 << 40] {
+            assert_eq!(parse_size(&format_size(bytes)), Ok(bytes));
+        }
+    }
+
+    #[test]
+    fn test_human_size() {
+        assert_eq!(human_size(512), "512 B");
+        assert_eq!(human_size(2 * GIB), "2 GiB");
+        assert_eq!(human_size(3 * GIB / 2), "1.5 GiB");
+    }
+
+    #[test]
+    fn test_effective_quota_falls_back_to_domain_default() {
+        assert_eq!(effective(Some(GIB), Some(2 * GIB)), GIB);
+        assert_eq!(effective(Some(0), Some(2 * GIB)), 0);
+        assert_eq!(effective(None, Some(2 * GIB)), 2 * GIB);
+        assert_eq!(effective(None, None), 0);
+    }
+
+    #[test]
+    fn test_forms_accept_sizes_numbers_and_empty_fields() {
+        let form: UserForm = serde_json::from_str(
+            r#"{"id":"a@b.com","password":"x","name":"A","quota_bytes":"2G","quota_messages":"1000"}"#,
+        )
+        .unwrap();
+        assert_eq!(form.quota_bytes, Some(2 * GIB));
+        assert_eq!(form.quota_messages, Some(1000));
+        assert_eq!(form.quota_bytes_input(), "2G");
+
+        let form: UserForm = serde_urlencoded::from_str(
+            "id=a%40b.com&password=x&name=A&quota_bytes=&quota_messages=",
+        )
+        .unwrap();
+        assert_eq!(form.quota_bytes, None);
+        assert_eq!(form.quota_messages, None);
+        assert_eq!(form.quota_bytes_input(), "");
+
+        let form: DomainForm = serde_json::from_str(
+            r#"{"domain":"b.com","transport":"virtual","default_quota_bytes":1073741824,"quota_cap_bytes":"10G"}"#,
+        )
+        .unwrap();
+        assert_eq!(form.default_quota_bytes, Some(GIB));
+        assert_eq!(form.default_quota_messages, None);
+        assert_eq!(form.quota_cap_bytes, Some(10 * GIB));
+
+        assert!(serde_json::from_str::<DomainForm>(
+            r#"{"domain":"b.com","transport":"virtual","quota_cap_bytes":-1}"#
+        )
+        .is_err());
+        assert!(serde_urlencoded::from_str::<UserForm>(
+            "id=a%40b.com&password=x&name=A&quota_bytes=lots"
+        )
+        .is_err());
+    }
+
+    fn user_form(id: &str, quota_bytes: Option<i64>) -> UserForm {
+        UserForm {
+            id: id.to_string(),
+            password: "password123".to_string(),
+            name: "Quota Test".to_string(),
+            maildir: String::new(),
+            enabled: true,
+            change_password: false,
+            quota_bytes,
+            quota_messages: None,
+        }
+    }
+
+    fn is_cap_violation(result: Result<User, Error>) -> bool {
+        matches!(
+            result,
+            Err(Error::DatabaseError(DatabaseErrorKind::CheckViolation, _))
+        )
+    }
+
+    #[test]
+    fn test_domain_quota_cap_is_enforced() {
+        let pool = setup_test_db();
+        cleanup_test_db(&pool);
+        let audit = test_audit();
+        let domain = format!("quota-{}.com", unique_test_id());
+
+        db::create_domain(
+            &pool,
+            NewDomain {
+                domain: domain.clone(),
+                transport: Some("virtual".to_string()),
+                enabled: true,
+                default_quota_bytes: Some(GIB),
+                default_quota_messages: None,
+                quota_cap_bytes: Some(3 * GIB),
+            },
+            &audit,
+        )
+        .unwrap();
+
+        // The domain default counts towards the cap
+        let first = format!("first@{domain}");
+        db::create_user(
+            &pool,
+            user_form(&first, None),
+            PasswordScheme::default(),
+            &audit,
+        )
+        .unwrap();
+        db::create_user(
+            &pool,
+            user_form(&format!("second@{domain}"), Some(GIB)),
+            PasswordScheme::default(),
+            &audit,
+        )
+        .unwrap();
+
+        let third = format!("third@{domain}");
+        assert!(is_cap_violation(db::create_user(
+            &pool,
+            user_form(&third, Some(2 * GIB)),
+            PasswordScheme::default(),
+            &audit
+        )));
+        assert!(is_cap_violation(db::create_user(
+            &pool,
+            user_form(&third, Some(0)),
+            PasswordScheme::default(),
+            &audit
+        )));
+        db::create_user(
+            &pool,
+            user_form(&third, Some(GIB)),
+            PasswordScheme::default(),
+            &audit,
+        )
+        .unwrap();
+
+        // A mailbox's own quota is replaced, not added, when it is updated
+        assert!(is_cap_violation(db::update_user(
+            &pool,
+            first.clone(),
+            user_form(&first, Some(2 * GIB)),
+            &audit
+        )));
+        let updated = db::update_user(
+            &pool,
+            first.clone(),
+            user_form(&first, Some(GIB / 2)),
+            &audit,
+        )
+        .unwrap();
+        assert_eq!(updated.quota_bytes, Some(GIB / 2));
+
+        let stats = db::get_domain_stats(&pool).unwrap();
+        let stats = stats.iter().find(|s| s.domain == domain).unwrap();
+        assert_eq!(stats.total_quota, GIB / 2 + 2 * GIB);
+        assert_eq!(stats.unlimited_users, 0);
+        assert_eq!(stats.quota_cap, Some(3 * GIB));
+
+        cleanup_test_db(&pool);
+    }
+}
@@ -0,0 +1,266 @@
+#[cfg(test)]
+mod tests {
+    use crate::bundle::{self, Bundle, BundleError, BundleFormat, BUNDLE_VERSION};
+    use crate::cli::{Cli, Command};
+    use crate::db;
+    use crate::restore::{self, BundleTable, RestoreAction, RestoreIssue, RestoreMode};
+    use crate::tests::common::{
+        bundle_domain, bundle_relay, bundle_user, cleanup_test_db, database_config, setup_test_db,
+        test_audit, unique_test_id,
+    };
+    use clap::Parser;
+
+    #[test]
+    fn test_parse_json_and_yaml_bundles() {
+        let mut original = Bundle::new("primary", false);
+        original
+            .domains
+            .push(bundle_domain("example.com", "virtual"));
+        for format in [BundleFormat::Json, BundleFormat::Yaml] {
+            let document = original.to_string(format).unwrap();
+            assert_eq!(bundle::parse(&document).unwrap(), original);
+        }
+
+        // Tables left out are empty
+        let minimal = bundle::parse(
+            "version: 1\ndatabase: primary\nexported_at: 2026-10-18T09:30:00Z\nrelays:\n  - recipient: \"@example.com\"\n    status: OK\n    enabled: true\n",
+        )
+        .unwrap();
+        assert_eq!(minimal.relays, vec![bundle_relay("@example.com")]);
+        assert!(minimal.domains.is_empty());
+
+        let newer = format!(
+            "{{\"version\": {}, \"database\": \"primary\", \"exported_at\": \"2026-10-18T09:30:00Z\"}}",
+            BUNDLE_VERSION + 1
+        );
+        assert_eq!(
+            bundle::parse(&newer),
+            Err(BundleError::Version(BUNDLE_VERSION + 1))
+        );
+        assert!(matches!(
+            bundle::parse("{\"version\": 1"),
+            Err(BundleError::Syntax(_))
+        ));
+    }
+
+    #[test]
+    fn test_plan_merge_and_replace() {
+        let mut current = Bundle::new("secondary", false);
+        current.domains = vec![
+            bundle_domain("example.com", "virtual"),
+            bundle_domain("example.net", "virtual"),
+            bundle_domain("old.example", "virtual"),
+        ];
+        current.relays = vec![bundle_relay("@example.com")];
+        let mut incoming = Bundle::new("primary", false);
+        incoming.domains = vec![
+            bundle_domain("Example.COM", "virtual"),
+            bundle_domain("example.net", "lmtp:unix:private/dovecot-lmtp"),
+            bundle_domain("example.org", "virtual"),
+        ];
+
+        let plan = restore::plan(&current, &incoming, RestoreMode::Merge, |_| true);
+        let actions: Vec<(&str, RestoreAction)> = plan
+            .changes
+            .iter()
+            .map(|change| (change.key.as_str(), change.action))
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                ("Example.COM", RestoreAction::Unchanged),
+                ("example.net", RestoreAction::Update),
+                ("example.org", RestoreAction::Create),
+            ]
+        );
+        assert_eq!(
+            plan.changes[1].changes[0].to_string(),
+            "transport: virtual → lmtp:unix:private/dovecot-lmtp"
+        );
+
+        let plan = restore::plan(&current, &incoming, RestoreMode::Replace, |_| true);
+        let deletes: Vec<(BundleTable, &str)> = plan
+            .changes
+            .iter()
+            .filter(|change| change.action == RestoreAction::Delete)
+            .map(|change| (change.table, change.key.as_str()))
+            .collect();
+        assert_eq!(
+            deletes,
+            vec![
+                (BundleTable::Domains, "old.example"),
+                (BundleTable::Relays, "@example.com")
+            ]
+        );
+        assert_eq!(plan.writes().count(), 4);
+    }
+
+    #[test]
+    fn test_plan_honours_feature_toggles() {
+        let mut current = Bundle::new("secondary", false);
+        current.domains = vec![bundle_domain("example.com", "virtual")];
+        let mut incoming = Bundle::new("primary", true);
+        incoming.domains = vec![
+            bundle_domain("example.com", "smtp:[mx.example.com]"),
+            bundle_domain("example.org", "virtual"),
+        ];
+        incoming.users = vec![bundle_user(
+            "alice@example.org",
+            Some("{BLF-CRYPT}$2b$12$hash"),
+        )];
+
+        // A database with no_new_domains refuses domain creates and updates
+        let plan = restore::plan(&current, &incoming, RestoreMode::Merge, |operation| {
+            !operation.ends_with("_domain")
+        });
+        let issues: Vec<(&str, Option<RestoreIssue>)> = plan
+            .changes
+            .iter()
+            .map(|change| (change.key.as_str(), change.issue))
+            .collect();
+        assert_eq!(
+            issues,
+            vec![
+                ("example.com", Some(RestoreIssue::NotAllowed)),
+                ("example.org", Some(RestoreIssue::NotAllowed)),
+                ("alice@example.org", None),
+            ]
+        );
+        assert_eq!(plan.changes[0].status(), "error");
+        assert_eq!(plan.problems().count(), 2);
+        assert_eq!(plan.count(RestoreAction::Create), 1);
+
+        // A read-only database refuses everything
+        let plan = restore::plan(&current, &incoming, RestoreMode::Replace, |_| false);
+        assert_eq!(plan.writes().count(), 0);
+    }
+
+    #[test]
+    fn test_plan_password_hashes() {
+        let mut current = Bundle::new("secondary", false);
+        current.users = vec![bundle_user("alice@example.com", None)];
+        let mut incoming = Bundle::new("primary", false);
+        incoming.users = vec![
+            bundle_user("alice@example.com", None),
+            bundle_user("bob@example.com", None),
+        ];
+
+        // Without hashes existing passwords are kept and new users cannot be created
+        let plan = restore::plan(&current, &incoming, RestoreMode::Merge, |_| true);
+        assert_eq!(plan.changes[0].action, RestoreAction::Unchanged);
+        assert_eq!(plan.changes[1].issue, Some(RestoreIssue::PasswordRequired));
+
+        current.users = vec![bundle_user("alice@example.com", Some("{PLAIN}old"))];
+        incoming.users = vec![bundle_user("alice@example.com", Some("{PLAIN}new"))];
+        let plan = restore::plan(&current, &incoming, RestoreMode::Merge, |_| true);
+        assert_eq!(plan.changes[0].action, RestoreAction::Update);
+        assert_eq!(plan.changes[0].changes[0].field, "crypt");
+    }
+
+    #[test]
+    fn test_restore_sql_uses_field_map() {
+        let db_config = database_config(&[
+            ("users.id", "email"),
+            ("users.crypt", "password"),
+            ("modified", "updated"),
+        ]);
+        assert_eq!(
+            restore::insert_sql(BundleTable::Users, &["id", "crypt", "name"], &db_config),
+            "INSERT INTO users (email, password, name, created, updated) VALUES (?, ?, ?, NOW(), NOW())"
+        );
+        assert_eq!(
+            restore::update_sql(BundleTable::Users, &["crypt"], &db_config),
+            "UPDATE users SET password = ?, updated = NOW() WHERE email = ?"
+        );
+        assert_eq!(
+            restore::delete_sql(BundleTable::Users, &db_config),
+            "DELETE FROM users WHERE email = ?"
+        );
+        assert_eq!(
+            restore::update_sql(BundleTable::Clients, &["status"], &database_config(&[])),
+            "UPDATE clients SET status = ?, updated_at = NOW() WHERE client = ?"
+        );
+        assert_eq!(
+            restore::delete_sql(BundleTable::Vacations, &database_config(&[])),
+            "DELETE FROM vacation WHERE user_id = ?"
+        );
+    }
+
+    #[test]
+    fn test_parse_restore_command() {
+        let cli = Cli::try_parse_from([
+            "sortingoffice-cli",
+            "restore",
+            "mail.yaml",
+            "--mode",
+            "replace",
+            "--apply",
+        ])
+        .unwrap();
+        match cli.command {
+            Command::Restore { file, mode, apply } => {
+                assert_eq!(file.to_str(), Some("mail.yaml"));
+                assert_eq!(mode, RestoreMode::Replace);
+                assert!(apply);
+            }
+            other => panic!("unexpected command {other:?}"),
+        }
+
+        let cli = Cli::try_parse_from(["sortingoffice-cli", "restore", "-"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::Restore {
+                mode: RestoreMode::Merge,
+                apply: false,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_apply_restore_round_trip() {
+        let pool = setup_test_db();
+        cleanup_test_db(&pool);
+        let audit = test_audit();
+        let db_config = database_config(&[]);
+        let name = format!("restore-{}.com", unique_test_id());
+
+        let mut incoming = Bundle::new("primary", true);
+        incoming.domains = vec![bundle_domain(&name, "virtual")];
+        incoming.users = vec![bundle_user(&format!("alice@{name}"), Some("{PLAIN}secret"))];
+        incoming.relays = vec![bundle_relay(&format!("@{name}"))];
+
+        let plan =
+            restore::plan_for(&pool, &db_config, &incoming, RestoreMode::Merge, |_| true).unwrap();
+        assert_eq!(plan.count(RestoreAction::Create), 3);
+        assert_eq!(
+            db::apply_restore(&pool, &db_config, &plan, &audit).unwrap(),
+            3
+        );
+
+        // Restoring the same bundle again changes nothing
+        let plan =
+            restore::plan_for(&pool, &db_config, &incoming, RestoreMode::Merge, |_| true).unwrap();
+        assert_eq!(plan.writes().count(), 0);
+
+        incoming.relays.clear();
+        let plan = restore::plan_for(
+            &pool,
+            &db_config,
+            &incoming,
+            RestoreMode::Replace,
+            |operation| operation == "delete_relay",
+        )
+        .unwrap();
+        assert_eq!(
+            db::apply_restore(&pool, &db_config, &plan, &audit).unwrap(),
+            1
+        );
+        assert!(db::get_relays(&pool)
+            .unwrap()
+            .iter()
+            .all(|relay| relay.recipient != format!("@{name}")));
+
+        cleanup_test_db(&pool);
+    }
+}
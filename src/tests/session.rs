@@ -0,0 +1,190 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::{AdminRole, SessionConfig};
+    use crate::session::{SessionStore, PORTAL_COOKIE, SESSION_COOKIE};
+    use axum::http::HeaderMap;
+
+    fn store_with_secret(secret: &str) -> SessionStore {
+        SessionStore::new(
+            &SessionConfig {
+                secret: Some(secret.to_string()),
+                ..Default::default()
+            },
+            None,
+        )
+    }
+
+    fn headers_for(store: &SessionStore, session_id: &str) -> HeaderMap {
+        cookie_headers(store, SESSION_COOKIE, session_id)
+    }
+
+    fn cookie_headers(store: &SessionStore, cookie_name: &str, session_id: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        let cookie = format!("{}={}", cookie_name, store.sign(session_id));
+        headers.insert("cookie", cookie.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_sign_and_verify_roundtrip() {
+        let store = store_with_secret("test-secret");
+        let signed = store.sign("abc123");
+        assert_eq!(store.verify(&signed), Some("abc123".to_string()));
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_values() {
+        let store = store_with_secret("test-secret");
+        let signed = store.sign("abc123");
+        let (_, signature) = signed.rsplit_once('.').unwrap();
+
+        assert_eq!(store.verify(&format!("abc124.{}", signature)), None);
+        assert_eq!(store.verify("abc123"), None);
+        assert_eq!(store.verify("abc123.not-a-signature"), None);
+
+        // A different key must not validate the signature
+        let other = store_with_secret("other-secret");
+        assert_eq!(other.verify(&signed), None);
+    }
+
+    #[test]
+    fn test_session_lookup_from_headers() {
+        let store = SessionStore::default();
+        let session = store.create("admin", &AdminRole::Edit, "primary").unwrap();
+
+        let found = store
+            .from_headers(&headers_for(&store, &session.id))
+            .expect("session should be found");
+        assert_eq!(found.username, "admin");
+        assert_eq!(found.admin_role(), Some(AdminRole::Edit));
+        assert_eq!(found.database_id, "primary");
+
+        // Unknown but correctly signed IDs are rejected
+        assert!(store
+            .from_headers(&headers_for(&store, "unknown"))
+            .is_none());
+        assert!(store.from_headers(&HeaderMap::new()).is_none());
+    }
+
+    #[test]
+    fn test_revoke_invalidates_session() {
+        let store = SessionStore::default();
+        let session = store
+            .create("admin", &AdminRole::ReadOnly, "primary")
+            .unwrap();
+        let headers = headers_for(&store, &session.id);
+
+        assert!(store.from_headers(&headers).is_some());
+        store.revoke_from_headers(&headers);
+        assert!(store.from_headers(&headers).is_none());
+    }
+
+    #[test]
+    fn test_set_database_updates_session() {
+        let store = SessionStore::default();
+        let session = store.create("admin", &AdminRole::Edit, "primary").unwrap();
+
+        assert!(store.set_database(&session.id, "secondary").unwrap());
+        let found = store
+            .from_headers(&headers_for(&store, &session.id))
+            .unwrap();
+        assert_eq!(found.database_id, "secondary");
+
+        assert!(!store.set_database("unknown", "secondary").unwrap());
+    }
+
+    #[test]
+    fn test_idle_timeout_expires_session() {
+        let store = SessionStore::new(
+            &SessionConfig {
+                idle_timeout_minutes: 0,
+                ..Default::default()
+            },
+            None,
+        );
+        let session = store.create("admin", &AdminRole::Edit, "primary").unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+
+        assert!(store
+            .from_headers(&headers_for(&store, &session.id))
+            .is_none());
+    }
+
+    #[test]
+    fn test_absolute_timeout_purges_session() {
+        let store = SessionStore::new(
+            &SessionConfig {
+                absolute_timeout_hours: 0,
+                ..Default::default()
+            },
+            None,
+        );
+        store.create("admin", &AdminRole::Edit, "primary").unwrap();
+        store
+            .create("other", &AdminRole::ReadOnly, "primary")
+            .unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+
+        assert_eq!(store.purge_expired().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_cookie_header_attributes() {
+        let store = SessionStore::new(
+            &SessionConfig {
+                secure_cookie: true,
+                ..Default::default()
+            },
+            None,
+        );
+        let session = store.create("admin", &AdminRole::Edit, "primary").unwrap();
+
+        let cookie = store.cookie_header(&session);
+        assert!(cookie.starts_with(&format!("{}=", SESSION_COOKIE)));
+        assert!(cookie.contains("HttpOnly"));
+        assert!(cookie.contains("SameSite=Lax"));
+        assert!(cookie.contains("Secure"));
+        assert!(!cookie.contains("edit"));
+
+        assert!(store.clear_cookie_header().contains("Max-Age=0"));
+    }
+
+    #[test]
+    fn test_mailbox_sessions_are_kept_apart() {
+        let store = SessionStore::default();
+        let mailbox = store
+            .create_mailbox("alice@example.com", "primary")
+            .unwrap();
+        let admin = store.create("admin", &AdminRole::Edit, "primary").unwrap();
+        assert!(mailbox.is_mailbox());
+        assert_eq!(mailbox.admin_role(), None);
+
+        let found = store
+            .mailbox_from_headers(&cookie_headers(&store, PORTAL_COOKIE, &mailbox.id))
+            .expect("portal session should be found");
+        assert_eq!(found.username, "alice@example.com");
+        assert_eq!(found.database_id, "primary");
+
+        // Neither session is accepted in the other's cookie
+        assert!(store
+            .from_headers(&headers_for(&store, &mailbox.id))
+            .is_none());
+        assert!(store
+            .mailbox_from_headers(&cookie_headers(&store, PORTAL_COOKIE, &admin.id))
+            .is_none());
+
+        let cookie = store.cookie_header(&mailbox);
+        assert!(cookie.starts_with(&format!("{}=", PORTAL_COOKIE)));
+        assert!(cookie.contains("Path=/portal;"));
+        assert!(store
+            .clear_portal_cookie_header()
+            .starts_with(&format!("{}=;", PORTAL_COOKIE)));
+
+        let headers = cookie_headers(&store, PORTAL_COOKIE, &mailbox.id);
+        store.revoke_mailbox_from_headers(&headers);
+        assert!(store.mailbox_from_headers(&headers).is_none());
+        assert!(store
+            .from_headers(&headers_for(&store, &admin.id))
+            .is_some());
+    }
+}
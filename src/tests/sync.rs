@@ -0,0 +1,251 @@
+#[cfg(test)]
+mod tests {
+    use crate::bundle::{Bundle, BundleBackup};
+    use crate::cli::{Cli, Command};
+    use crate::db;
+    use crate::restore::{self, BundleTable, RestoreAction, RestoreIssue, RestoreMode};
+    use crate::sync::{self, SyncDiff, SyncSide, DEFAULT_TABLES, HIDDEN};
+    use crate::tests::common::{
+        bundle_domain, bundle_relay, bundle_user, cleanup_test_db, database_config, setup_test_db,
+        test_audit, unique_test_id,
+    };
+    use chrono::NaiveDate;
+    use clap::Parser;
+
+    fn backup(name: &str, enabled: bool) -> BundleBackup {
+        BundleBackup {
+            domain: name.to_string(),
+            transport: Some(":[mx1.example.net]".to_string()),
+            enabled,
+        }
+    }
+
+    fn primary_and_secondary() -> (Bundle, Bundle) {
+        let mut primary = Bundle::new("primary", false);
+        primary.domains = vec![
+            bundle_domain("example.com", "virtual"),
+            bundle_domain("example.org", "virtual"),
+        ];
+        primary.backups = vec![backup("example.net", true)];
+        primary.relays = vec![bundle_relay("@example.com")];
+
+        let mut secondary = Bundle::new("secondary", false);
+        secondary.domains = vec![bundle_domain("Example.com", "virtual")];
+        secondary.backups = vec![backup("example.net", false)];
+        secondary.relays = vec![
+            bundle_relay("@example.com"),
+            bundle_relay("@old.example.com"),
+        ];
+        (primary, secondary)
+    }
+
+    #[test]
+    fn test_diff_shows_missing_and_differing_records() {
+        let (primary, secondary) = primary_and_secondary();
+        let diff = SyncDiff::new(&primary, &secondary, &DEFAULT_TABLES, |_| true);
+
+        let differences: Vec<(BundleTable, &str, RestoreAction)> = diff
+            .differences()
+            .map(|c| (c.table, c.key.as_str(), c.action))
+            .collect();
+        assert_eq!(
+            differences,
+            vec![
+                (BundleTable::Domains, "example.org", RestoreAction::Create),
+                (BundleTable::Backups, "example.net", RestoreAction::Update),
+                (
+                    BundleTable::Relays,
+                    "@old.example.com",
+                    RestoreAction::Delete
+                ),
+            ]
+        );
+        let backup = diff.differences().nth(1).unwrap();
+        assert_eq!(backup.changes[0].to_string(), "enabled: false → true");
+        assert_eq!(diff.plan.count(RestoreAction::Unchanged), 2);
+    }
+
+    #[test]
+    fn test_diff_only_compares_chosen_tables() {
+        let (primary, secondary) = primary_and_secondary();
+        let diff = SyncDiff::new(&primary, &secondary, &[BundleTable::Relays], |_| true);
+        assert!(diff
+            .plan
+            .changes
+            .iter()
+            .all(|change| change.table == BundleTable::Relays));
+        assert_eq!(diff.differences().count(), 1);
+    }
+
+    #[test]
+    fn test_diff_honours_feature_toggles_and_hides_hashes() {
+        let (mut primary, mut secondary) = primary_and_secondary();
+        primary.password_hashes = true;
+        secondary.password_hashes = true;
+        primary.users = vec![bundle_user("alice@example.com", Some("{PLAIN}new"))];
+        secondary.users = vec![bundle_user("alice@example.com", Some("{PLAIN}old"))];
+
+        let tables = [BundleTable::Users, BundleTable::Relays];
+        let diff = SyncDiff::new(&primary, &secondary, &tables, |operation| {
+            operation != "delete_relay"
+        });
+        let alice = &diff.plan.changes[0];
+        assert_eq!(alice.changes[0].field, "crypt");
+        assert_eq!(
+            (alice.changes[0].old.as_str(), alice.changes[0].new.as_str()),
+            (HIDDEN, HIDDEN)
+        );
+        // The record written still carries the source's hash
+        assert_eq!(
+            alice.after.as_ref().unwrap()["crypt"],
+            serde_json::json!("{PLAIN}new")
+        );
+
+        let relay = diff.differences().last().unwrap();
+        assert_eq!(relay.issue, Some(RestoreIssue::NotAllowed));
+        assert!(!relay.is_write());
+    }
+
+    #[test]
+    fn test_selection_and_newer_side() {
+        let (primary, secondary) = primary_and_secondary();
+        let mut diff = SyncDiff::new(&primary, &secondary, &DEFAULT_TABLES, |_| true);
+        let at = |hour| {
+            NaiveDate::from_ymd_opt(2026, 10, 1)
+                .unwrap()
+                .and_hms_opt(hour, 0, 0)
+                .unwrap()
+        };
+        diff.source_modified
+            .insert((BundleTable::Backups, "example.net".to_string()), at(9));
+        diff.target_modified
+            .insert((BundleTable::Backups, "example.net".to_string()), at(12));
+        diff.target_modified
+            .insert((BundleTable::Relays, "@old.example.com".to_string()), at(8));
+
+        let backup = diff.differences().nth(1).unwrap();
+        assert_eq!(diff.modified(SyncSide::Source, backup), Some(at(9)));
+        assert_eq!(diff.newer(backup), Some(SyncSide::Target));
+        let relay = diff.differences().nth(2).unwrap();
+        assert_eq!(diff.newer(relay), None);
+        assert_eq!(sync::row_id(relay), "relays:@old.example.com");
+
+        let rows = diff.rows();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[2].target_modified, Some(at(8)));
+
+        let selection = diff.selection(&[
+            "domains:example.org".to_string(),
+            "relays:@old.example.com".to_string(),
+            "relays:@example.com".to_string(),
+        ]);
+        assert_eq!(selection.mode, RestoreMode::Replace);
+        let chosen: Vec<&str> = selection.changes.iter().map(|c| c.key.as_str()).collect();
+        // Identical records are never part of a selection
+        assert_eq!(chosen, vec!["example.org", "@old.example.com"]);
+    }
+
+    #[test]
+    fn test_modified_sql_uses_field_map() {
+        assert_eq!(
+            restore::modified_sql(BundleTable::Domains, &database_config(&[])),
+            "SELECT CAST(domain AS CHAR) AS record_key, modified AS modified FROM domains"
+        );
+        assert_eq!(
+            restore::modified_sql(
+                BundleTable::Users,
+                &database_config(&[("users.id", "email"), ("modified", "updated")])
+            ),
+            "SELECT CAST(email AS CHAR) AS record_key, updated AS modified FROM users"
+        );
+        assert_eq!(
+            restore::modified_sql(BundleTable::Clients, &database_config(&[])),
+            "SELECT CAST(client AS CHAR) AS record_key, updated_at AS modified FROM clients"
+        );
+        assert_eq!(BundleTable::parse("vacation"), Some(BundleTable::Vacations));
+        assert_eq!(BundleTable::parse("vacations"), None);
+    }
+
+    #[test]
+    fn test_parse_sync_command() {
+        let cli = Cli::try_parse_from([
+            "sortingoffice-cli",
+            "--database",
+            "mx2",
+            "sync",
+            "mx1",
+            "--table",
+            "backups",
+            "--table",
+            "alias_domains",
+            "--only",
+            "backups:example.net",
+            "--apply",
+        ])
+        .unwrap();
+        assert_eq!(cli.database.as_deref(), Some("mx2"));
+        match cli.command {
+            Command::Sync {
+                from,
+                tables,
+                only,
+                delete,
+                apply,
+            } => {
+                assert_eq!(from, "mx1");
+                assert_eq!(
+                    tables,
+                    vec![BundleTable::Backups, BundleTable::AliasDomains]
+                );
+                assert_eq!(only, vec!["backups:example.net".to_string()]);
+                assert!(!delete);
+                assert!(apply);
+            }
+            other => panic!("unexpected command {other:?}"),
+        }
+
+        assert!(
+            Cli::try_parse_from(["sortingoffice-cli", "sync", "mx1", "--table", "mailboxes"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_get_modified_times() {
+        let pool = setup_test_db();
+        cleanup_test_db(&pool);
+        let db_config = database_config(&[]);
+        let name = format!("Sync-{}.com", unique_test_id());
+
+        let mut incoming = Bundle::new("primary", false);
+        incoming.domains = vec![bundle_domain(&name, "virtual")];
+        let plan =
+            restore::plan_for(&pool, &db_config, &incoming, RestoreMode::Merge, |_| true).unwrap();
+        db::apply_restore(&pool, &db_config, &plan, &test_audit()).unwrap();
+
+        let times = db::get_modified_times(&pool, &db_config, BundleTable::Domains).unwrap();
+        assert!(times.contains_key(&name.to_lowercase()));
+
+        // A database compared with itself has no differences
+        let diff = sync::diff(
+            &pool,
+            &db_config,
+            &pool,
+            &db_config,
+            &DEFAULT_TABLES,
+            |_| true,
+        )
+        .unwrap();
+        assert_eq!(diff.differences().count(), 0);
+        let domain = diff
+            .plan
+            .changes
+            .iter()
+            .find(|change| change.key == name)
+            .unwrap();
+        assert_eq!(diff.newer(domain), None);
+        assert!(diff.modified(SyncSide::Target, domain).is_some());
+
+        cleanup_test_db(&pool);
+    }
+}
@@ -0,0 +1,264 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::{UsageConfig, UsageSource};
+    use crate::db;
+    use crate::models::*;
+    use crate::password::PasswordScheme;
+    use crate::tests::common::{cleanup_test_db, setup_test_db, test_audit, unique_test_id};
+    use crate::usage::{
+        collect, collect_samples, maildir_path, parse_doveadm_quota, run_usage_command,
+        scan_maildir, UsageSample,
+    };
+    use chrono::NaiveDate;
+    use diesel::prelude::*;
+    use std::path::PathBuf;
+
+    fn fixtures() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/tests/fixtures/usage")
+    }
+
+    fn user(id: &str, maildir: &str) -> User {
+        let now = chrono::Utc::now().naive_utc();
+        User {
+            id: id.to_string(),
+            crypt: String::new(),
+            name: String::new(),
+            maildir: maildir.to_string(),
+            home: fixtures().join("mail").display().to_string(),
+            uid: 5000,
+            gid: 5000,
+            created: now,
+            modified: now,
+            enabled: true,
+            change_password: false,
+            quota_bytes: None,
+            quota_messages: None,
+        }
+    }
+
+    fn sample(user_id: &str, bytes: i64, messages: i64) -> UsageSample {
+        UsageSample {
+            user_id: user_id.to_string(),
+            bytes,
+            messages,
+        }
+    }
+
+    fn doveadm_config(command: &[&str]) -> UsageConfig {
+        UsageConfig {
+            source: UsageSource::Doveadm,
+            command: command.iter().map(|arg| arg.to_string()).collect(),
+            ..UsageConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_doveadm_quota() {
+        let output = std::fs::read_to_string(fixtures().join("doveadm-quota.tab")).unwrap();
+        let samples = parse_doveadm_quota(&output).unwrap();
+        assert_eq!(
+            samples,
+            vec![
+                sample("alice@example.com", 2048 * 1024, 120),
+                // Two quota roots count once, with the largest value
+                sample("bob@example.com", 512 * 1024, 3),
+                sample("stranger@example.org", 99 * 1024, 1),
+            ]
+        );
+
+        // Without a header the default column order is assumed
+        let samples =
+            parse_doveadm_quota("carol@example.com\tUser quota\tSTORAGE\t1\t-\t0\n").unwrap();
+        assert_eq!(samples, vec![sample("carol@example.com", 1024, 0)]);
+
+        assert_eq!(parse_doveadm_quota("").unwrap(), vec![]);
+        assert!(parse_doveadm_quota("carol@example.com\tUser quota\tSTORAGE\tlots").is_err());
+        assert!(parse_doveadm_quota("Username\tValue\n").is_err());
+    }
+
+    #[test]
+    fn test_scan_maildir_counts_only_messages() {
+        let alice = user("alice@example.com", "example.com/alice/");
+        assert_eq!(
+            maildir_path(&alice),
+            fixtures().join("mail/example.com/alice/")
+        );
+
+        // cur, new and the .Sent folder; not tmp or Dovecot's index files
+        assert_eq!(scan_maildir(&maildir_path(&alice)).unwrap(), (175, 3));
+        assert!(scan_maildir(&fixtures().join("mail/example.com/nobody")).is_err());
+    }
+
+    #[test]
+    fn test_collect_samples_from_maildirs() {
+        let config = UsageConfig {
+            source: UsageSource::Maildir,
+            ..UsageConfig::default()
+        };
+        let users = vec![
+            user("alice@example.com", "example.com/alice/"),
+            // No mail delivered yet, so no maildir
+            user("bob@example.com", "example.com/bob/"),
+        ];
+
+        assert_eq!(
+            collect_samples(&config, &users).unwrap(),
+            vec![sample("alice@example.com", 175, 3)]
+        );
+        assert_eq!(
+            collect_samples(&UsageConfig::default(), &users).unwrap(),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_collect_samples_from_stub_command() {
+        let fixture = fixtures().join("doveadm-quota.tab").display().to_string();
+        let config = doveadm_config(&["cat", &fixture]);
+        let users = vec![
+            user("alice@example.com", "example.com/alice/"),
+            user("bob@example.com", "example.com/bob/"),
+        ];
+
+        // Mailboxes of other databases are left out
+        assert_eq!(
+            collect_samples(&config, &users).unwrap(),
+            vec![
+                sample("alice@example.com", 2048 * 1024, 120),
+                sample("bob@example.com", 512 * 1024, 3),
+            ]
+        );
+
+        assert!(collect_samples(&doveadm_config(&["false"]), &users).is_err());
+        assert!(collect_samples(&doveadm_config(&["/nonexistent/doveadm"]), &users).is_err());
+        assert!(run_usage_command(&[]).is_err());
+    }
+
+    #[test]
+    fn test_daily_usage_keeps_last_run_per_day() {
+        let at = |day: u32, hour: u32| {
+            NaiveDate::from_ymd_opt(2026, 10, day)
+                .unwrap()
+                .and_hms_opt(hour, 0, 0)
+                .unwrap()
+        };
+        let history = db::daily_usage([(at(1, 6), 100), (at(1, 18), 150), (at(3, 6), 300)]);
+
+        assert_eq!(
+            history
+                .iter()
+                .map(|point| (point.day.to_string(), point.bytes, point.bar_percent))
+                .collect::<Vec<_>>(),
+            vec![
+                ("2026-10-01".to_string(), 150, 50),
+                ("2026-10-03".to_string(), 300, 100),
+            ]
+        );
+        assert!(db::daily_usage([]).is_empty());
+    }
+
+    #[test]
+    fn test_mailbox_usage_summary_display() {
+        let mut summary = MailboxUsageSummary {
+            user_id: "alice@example.com".to_string(),
+            bytes: 950 << 20,
+            messages: 10,
+            quota_bytes: 1 << 30,
+            growth_bytes: -(1 << 20),
+            collected: chrono::Utc::now().naive_utc(),
+        };
+        assert_eq!(summary.usage_percent(), Some(92));
+        assert!(summary.is_near_quota());
+        assert_eq!(summary.growth_display(), "-1 MiB");
+
+        summary.quota_bytes = 0;
+        assert_eq!(summary.usage_percent(), None);
+        assert!(!summary.is_near_quota());
+        assert_eq!(summary.quota_display(), "∞");
+    }
+
+    #[test]
+    fn test_collect_records_usage_for_stats() {
+        let pool = setup_test_db();
+        cleanup_test_db(&pool);
+        let audit = test_audit();
+        let domain = format!("usage-{}.com", unique_test_id());
+
+        db::create_domain(
+            &pool,
+            NewDomain {
+                domain: domain.clone(),
+                transport: Some("virtual".to_string()),
+                enabled: true,
+                default_quota_bytes: Some(1 << 30),
+                default_quota_messages: None,
+                quota_cap_bytes: None,
+            },
+            &audit,
+        )
+        .unwrap();
+        let small = format!("small@{domain}");
+        let big = format!("big@{domain}");
+        for id in [&small, &big] {
+            db::create_user(
+                &pool,
+                UserForm {
+                    id: id.clone(),
+                    password: "password123".to_string(),
+                    name: "Usage Test".to_string(),
+                    maildir: String::new(),
+                    enabled: true,
+                    change_password: false,
+                    quota_bytes: None,
+                    quota_messages: None,
+                },
+                PasswordScheme::default(),
+                &audit,
+            )
+            .unwrap();
+        }
+
+        // A stub doveadm reporting 1 MiB and 3 MiB
+        let output = format!(
+            "Username\tQuota name\tType\tValue\tLimit\t%\n\
+             {small}\tUser quota\tSTORAGE\t1024\t-\t0\n\
+             {big}\tUser quota\tSTORAGE\t3072\t-\t0\n\
+             {big}\tUser quota\tMESSAGE\t7\t-\t0\n"
+        );
+        let config = doveadm_config(&["printf", "%s", &output]);
+        assert_eq!(collect(&pool, &config).unwrap(), 2);
+
+        let stats = db::get_system_stats(&pool).unwrap();
+        assert_eq!(stats.used_quota, 4 << 20);
+        let domain_stats = db::get_domain_stats(&pool).unwrap();
+        let domain_stats = domain_stats.iter().find(|s| s.domain == domain).unwrap();
+        assert_eq!(domain_stats.used_quota, 4 << 20);
+
+        let largest = db::get_largest_mailboxes(&pool, 10, 30).unwrap();
+        assert_eq!(largest.len(), 2);
+        assert_eq!(largest[0].user_id, big);
+        assert_eq!(largest[0].messages, 7);
+        assert_eq!(largest[0].quota_bytes, 1 << 30);
+        assert_eq!(largest[0].growth_bytes, 0);
+
+        let history = db::get_usage_history(&pool, 30).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].bytes, 4 << 20);
+
+        // Old snapshots are pruned after each collection
+        let config = UsageConfig {
+            retention_days: 0,
+            ..config
+        };
+        collect(&pool, &config).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(1100));
+        collect(&pool, &config).unwrap();
+        let snapshots: i64 = crate::schema::mailbox_usage::table
+            .count()
+            .get_result(&mut pool.get().unwrap())
+            .unwrap();
+        assert_eq!(snapshots, 2);
+
+        cleanup_test_db(&pool);
+    }
+}
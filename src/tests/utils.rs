@@ -23,6 +23,9 @@ mod tests {
             domain: "example.com".to_string(),
             transport: "smtp:localhost".to_string(),
             enabled: true,
+            default_quota_bytes: None,
+            default_quota_messages: None,
+            quota_cap_bytes: None,
         };
 
         assert!(!valid_domain_form.domain.is_empty());
@@ -40,6 +43,8 @@ mod tests {
             enabled: true,
             change_password: false,
             maildir: "testdir".to_string(),
+            quota_bytes: None,
+            quota_messages: None,
         };
 
         assert!(!valid_user_form.id.is_empty());
@@ -52,6 +57,8 @@ mod tests {
             destination: "user@example.com".to_string(),
             enabled: true,
             return_url: None,
+            valid_from: None,
+            valid_until: None,
         };
 
         assert!(!valid_alias_form.mail.is_empty());
@@ -71,6 +78,9 @@ mod tests {
             created: now,
             modified: now,
             enabled: true,
+            default_quota_bytes: None,
+            default_quota_messages: None,
+            quota_cap_bytes: None,
         };
 
         assert_eq!(domain.pkid, 1);
@@ -92,6 +102,8 @@ mod tests {
             modified: now,
             enabled: true,
             change_password: false,
+            quota_bytes: None,
+            quota_messages: None,
         };
 
         assert_eq!(user.id, "testuser@example.com");
@@ -108,6 +120,8 @@ mod tests {
             created: now,
             modified: now,
             enabled: true,
+            valid_from: None,
+            valid_until: None,
         };
 
         assert_eq!(alias.pkid, 1);
@@ -123,6 +137,9 @@ mod tests {
             domain: "example.com".to_string(),
             transport: Some("smtp:localhost".to_string()),
             enabled: true,
+            default_quota_bytes: None,
+            default_quota_messages: None,
+            quota_cap_bytes: None,
         };
         assert_eq!(new_domain.domain, "example.com");
         assert_eq!(new_domain.transport, Some("smtp:localhost".to_string()));
@@ -139,6 +156,8 @@ mod tests {
             gid: 5000,
             enabled: true,
             change_password: false,
+            quota_bytes: None,
+            quota_messages: None,
         };
 
         assert_eq!(new_user.id, "testuser@example.com");
@@ -153,6 +172,8 @@ mod tests {
             mail: "test@example.com".to_string(),
             destination: "user@example.com".to_string(),
             enabled: true,
+            valid_from: None,
+            valid_until: None,
         };
 
         assert_eq!(new_alias.mail, "test@example.com");
@@ -196,6 +217,7 @@ mod tests {
             used_quota: 250000000,
             quota_usage_percent: 25.0,
             enabled_domains_and_backups: 4,
+            expiring_aliases: 2,
         };
 
         assert_eq!(system_stats.total_domains, 5);
@@ -238,6 +260,8 @@ mod tests {
             alias_count: 20,
             total_quota: 5000000,
             used_quota: 2500000,
+            unlimited_users: 0,
+            quota_cap: None,
         };
 
         assert_eq!(domain_stats.domain, "example.com");
@@ -0,0 +1,229 @@
+#[cfg(test)]
+mod tests {
+    use crate::db;
+    use crate::mail_config::sieve_scripts;
+    use crate::models::*;
+    use crate::password::PasswordScheme;
+    use crate::tests::common::{cleanup_test_db, setup_test_db, test_audit, unique_test_id};
+    use crate::vacation::{
+        alias_change, sieve_script, sync, transport_address, validate, AliasChange,
+    };
+    use chrono::NaiveDate;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 7, day).unwrap()
+    }
+
+    fn vacation(from: Option<NaiveDate>, until: Option<NaiveDate>) -> Vacation {
+        let now = chrono::Utc::now().naive_utc();
+        Vacation {
+            user_id: "alice@example.com".to_string(),
+            subject: "Out of office".to_string(),
+            body: "Back soon.".to_string(),
+            active_from: from,
+            active_until: until,
+            reply_interval_days: 7,
+            enabled: true,
+            created: now,
+            modified: now,
+        }
+    }
+
+    fn form(from: Option<NaiveDate>, until: Option<NaiveDate>) -> VacationForm {
+        VacationForm {
+            subject: "Out of office".to_string(),
+            body: "Back soon.".to_string(),
+            active_from: from,
+            active_until: until,
+            reply_interval_days: 7,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn test_vacation_is_active_within_dates() {
+        let scheduled = vacation(Some(date(10)), Some(date(20)));
+        assert!(!scheduled.is_active(date(9)));
+        assert!(scheduled.is_active(date(10)));
+        assert!(scheduled.is_active(date(20)));
+        assert!(!scheduled.is_active(date(21)));
+        assert!(vacation(None, None).is_active(date(1)));
+        assert!(vacation(None, Some(date(5))).is_active(date(1)));
+
+        let mut disabled = vacation(None, None);
+        disabled.enabled = false;
+        assert!(!disabled.is_active(date(1)));
+    }
+
+    #[test]
+    fn test_validate_vacation_form() {
+        assert_eq!(validate(&form(Some(date(1)), Some(date(1)))), Ok(()));
+        assert_eq!(
+            validate(&form(Some(date(2)), Some(date(1)))),
+            Err("vacation-error-dates")
+        );
+        let mut blank = form(None, None);
+        blank.subject = "  ".to_string();
+        assert_eq!(validate(&blank), Err("vacation-error-subject"));
+        let mut never = form(None, None);
+        never.reply_interval_days = 0;
+        assert_eq!(validate(&never), Err("vacation-error-interval"));
+    }
+
+    #[test]
+    fn test_vacation_form_accepts_empty_dates() {
+        let form: VacationForm = serde_urlencoded::from_str(
+            "subject=Away&body=Later&active_from=&active_until=2026-07-14&reply_interval_days=3",
+        )
+        .unwrap();
+        assert_eq!(form.active_from, None);
+        assert_eq!(form.active_until, Some(date(14)));
+        assert_eq!(form.reply_interval_days, 3);
+        assert!(!form.enabled);
+    }
+
+    #[test]
+    fn test_alias_change_adds_and_removes_transport_address() {
+        let user = "alice@example.com";
+        let address = transport_address("Alice@Example.com", "autoreply.example.com");
+        assert_eq!(address, "alice#example.com@autoreply.example.com");
+
+        assert_eq!(
+            alias_change(user, None, &address, true),
+            Some(AliasChange::Create(format!("{user}, {address}")))
+        );
+        assert_eq!(
+            alias_change(
+                user,
+                Some("alice@example.com, bob@example.com"),
+                &address,
+                true
+            ),
+            Some(AliasChange::Update(format!(
+                "alice@example.com, bob@example.com, {address}"
+            )))
+        );
+        assert_eq!(
+            alias_change(user, Some(&format!("{user}, {address}")), &address, true),
+            None
+        );
+
+        assert_eq!(
+            alias_change(user, Some(&format!("{user}, {address}")), &address, false),
+            Some(AliasChange::Delete)
+        );
+        assert_eq!(
+            alias_change(
+                user,
+                Some(&format!("{user},bob@example.com,{address}")),
+                &address,
+                false
+            ),
+            Some(AliasChange::Update(
+                "alice@example.com, bob@example.com".to_string()
+            ))
+        );
+        assert_eq!(alias_change(user, Some(user), &address, false), None);
+        assert_eq!(alias_change(user, None, &address, false), None);
+    }
+
+    #[test]
+    fn test_sieve_script_without_dates() {
+        let script = sieve_script(&vacation(None, None));
+        assert_eq!(
+            script,
+            "# Generated by Sorting Office for alice@example.com\n\
+             require [\"vacation\"];\n\
+             \n\
+             vacation :days 7 :subject \"Out of office\" :addresses [\"alice@example.com\"] text:\n\
+             Back soon.\n\
+             .\n\
+             ;\n"
+        );
+    }
+
+    #[test]
+    fn test_sieve_script_checks_dates_and_escapes() {
+        let mut away = vacation(Some(date(1)), Some(date(14)));
+        away.subject = "Away \"until\" July".to_string();
+        away.body = "Hi,\n.\n..and bye".to_string();
+        let script = sieve_script(&away);
+
+        assert!(script.contains("require [\"vacation\", \"date\", \"relational\"];"));
+        assert!(script.contains(
+            "if allof (currentdate :value \"ge\" \"date\" \"2026-07-01\", \
+             currentdate :value \"le\" \"date\" \"2026-07-14\") {"
+        ));
+        assert!(script.contains(":subject \"Away \\\"until\\\" July\""));
+        assert!(script.contains("    Hi,\n    ..\n    ...and bye\n    .\n    ;\n}\n"));
+    }
+
+    #[test]
+    fn test_sieve_scripts_skip_disabled_vacations() {
+        let mut disabled = vacation(None, None);
+        disabled.user_id = "bob@example.com".to_string();
+        disabled.enabled = false;
+        let files = sieve_scripts(&[vacation(None, None), disabled]);
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, "sieve/alice@example.com.sieve");
+    }
+
+    #[test]
+    fn test_vacation_aliases_follow_dates() {
+        let pool = setup_test_db();
+        cleanup_test_db(&pool);
+        let audit = test_audit();
+        let domain = format!("vacation-{}.com", unique_test_id());
+        let user_id = format!("alice@{domain}");
+        db::create_user(
+            &pool,
+            UserForm {
+                id: user_id.clone(),
+                password: "password123".to_string(),
+                name: "Vacation Test".to_string(),
+                maildir: String::new(),
+                enabled: true,
+                change_password: false,
+                quota_bytes: None,
+                quota_messages: None,
+            },
+            PasswordScheme::default(),
+            &audit,
+        )
+        .unwrap();
+
+        let saved = db::save_vacation(&pool, &user_id, form(Some(date(1)), Some(date(14))), &audit)
+            .unwrap();
+        assert_eq!(saved.active_until, Some(date(14)));
+        let transport = "autoreply.example.com";
+        let address = transport_address(&user_id, transport);
+
+        // Not started yet: nothing to do
+        assert_eq!(
+            sync(
+                &pool,
+                "test",
+                transport,
+                NaiveDate::from_ymd_opt(2026, 6, 30).unwrap()
+            ),
+            Ok(0)
+        );
+        assert!(db::get_alias_by_mail(&pool, &user_id).is_err());
+
+        // Started: the alias is created, and left alone on the next run
+        assert_eq!(sync(&pool, "test", transport, date(1)), Ok(1));
+        let alias = db::get_alias_by_mail(&pool, &user_id).unwrap();
+        assert_eq!(alias.destination, format!("{user_id}, {address}"));
+        assert_eq!(sync(&pool, "test", transport, date(2)), Ok(0));
+
+        // Ended: the alias only delivered to the mailbox, so it goes again
+        assert_eq!(sync(&pool, "test", transport, date(15)), Ok(1));
+        assert!(db::get_alias_by_mail(&pool, &user_id).is_err());
+
+        // Deleting the mailbox takes its vacation with it
+        db::delete_user(&pool, user_id.clone(), &audit).unwrap();
+        assert!(db::get_vacation(&pool, &user_id).unwrap().is_none());
+
+        cleanup_test_db(&pool);
+    }
+}
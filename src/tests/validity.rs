@@ -0,0 +1,206 @@
+#[cfg(test)]
+mod tests {
+    use crate::cli::{AliasCommand, Cli, Command};
+    use crate::db;
+    use crate::models::*;
+    use crate::tests::common::{cleanup_test_db, setup_test_db, test_audit, unique_test_id};
+    use crate::validity::{apply, check_window, is_within, scheduled_state, status, WindowStatus};
+    use chrono::{Duration, NaiveDate, NaiveDateTime, Utc};
+    use clap::Parser;
+
+    fn at(day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 11, day)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_is_within_window() {
+        let (from, until) = (Some(at(2, 9)), Some(at(6, 18)));
+        assert!(!is_within(from, until, at(2, 8)));
+        assert!(is_within(from, until, at(2, 9)));
+        assert!(is_within(from, until, at(6, 17)));
+        // The end is exclusive, like `valid_until > UTC_TIMESTAMP()` in Postfix
+        assert!(!is_within(from, until, at(6, 18)));
+        assert!(is_within(None, None, at(1, 0)));
+        assert!(is_within(None, until, at(1, 0)));
+        assert!(is_within(from, None, at(30, 0)));
+    }
+
+    #[test]
+    fn test_window_status() {
+        let (from, until) = (Some(at(2, 9)), Some(at(20, 0)));
+        assert_eq!(status(from, until, at(1, 0)), WindowStatus::Pending);
+        assert_eq!(status(from, until, at(3, 0)), WindowStatus::Open);
+        assert_eq!(status(from, until, at(13, 0)), WindowStatus::ExpiringSoon);
+        assert_eq!(status(from, until, at(20, 0)), WindowStatus::Expired);
+        assert_eq!(status(None, None, at(1, 0)), WindowStatus::Open);
+    }
+
+    #[test]
+    fn test_check_window() {
+        assert!(check_window(Some(at(2, 9)), Some(at(2, 10))).is_ok());
+        assert!(check_window(None, Some(at(2, 10))).is_ok());
+        assert!(check_window(Some(at(2, 9)), None).is_ok());
+        let error = check_window(Some(at(2, 9)), Some(at(2, 9))).unwrap_err();
+        assert_eq!(error.message_id(), "validity-error-window");
+        assert!(check_window(Some(at(3, 0)), Some(at(2, 0))).is_err());
+    }
+
+    #[test]
+    fn test_scheduled_state_acts_on_each_boundary_once() {
+        let (from, until) = (Some(at(2, 9)), Some(at(6, 18)));
+        let created = at(1, 0);
+
+        // Nothing to do before the window opens
+        assert_eq!(
+            scheduled_state(false, from, until, created, at(1, 12)),
+            None
+        );
+        // Enabled once it opens, and left alone afterwards
+        assert_eq!(
+            scheduled_state(false, from, until, created, at(2, 9)),
+            Some(true)
+        );
+        assert_eq!(scheduled_state(true, from, until, at(2, 9), at(3, 0)), None);
+        // Disabled once it closes
+        assert_eq!(
+            scheduled_state(true, from, until, at(2, 9), at(6, 18)),
+            Some(false)
+        );
+        assert_eq!(
+            scheduled_state(false, from, until, at(6, 18), at(7, 0)),
+            None
+        );
+
+        // An admin change after a boundary wins over the window
+        assert_eq!(scheduled_state(false, from, None, at(3, 0), at(4, 0)), None);
+        assert_eq!(scheduled_state(true, from, until, at(7, 0), at(8, 0)), None);
+    }
+
+    #[test]
+    fn test_parse_datetime_input() {
+        assert_eq!(parse_datetime_input("2026-11-02T09:00"), Some(at(2, 9)));
+        assert_eq!(parse_datetime_input("2026-11-02T09:00:00"), Some(at(2, 9)));
+        assert_eq!(parse_datetime_input("2026-11-02 09:00"), Some(at(2, 9)));
+        assert_eq!(parse_datetime_input("2026-11-02"), Some(at(2, 0)));
+        assert_eq!(parse_datetime_input("02/11/2026"), None);
+        assert_eq!(parse_datetime_input("2026-11-02T25:00"), None);
+    }
+
+    #[test]
+    fn test_forms_accept_empty_windows() {
+        let form: AliasForm = serde_urlencoded::from_str(
+            "mail=team%40example.com&destination=alice%40example.com&enabled=on\
+             &valid_from=&valid_until=2026-11-06T18%3A00",
+        )
+        .unwrap();
+        assert_eq!(form.valid_from, None);
+        assert_eq!(form.valid_until, Some(at(6, 18)));
+        assert_eq!(form.valid_until_input(), "2026-11-06T18:00");
+
+        let form: RelocatedForm = serde_json::from_str(
+            r#"{"old_address": "old@example.com", "new_address": "new@example.org",
+                "enabled": true, "valid_from": "2026-11-02"}"#,
+        )
+        .unwrap();
+        assert_eq!(form.valid_from, Some(at(2, 0)));
+        assert_eq!(form.valid_until, None);
+
+        assert!(serde_urlencoded::from_str::<AliasForm>(
+            "mail=a%40example.com&destination=b%40example.com&valid_until=soon"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_alias_add_window() {
+        let cli = Cli::try_parse_from([
+            "sortingoffice-cli",
+            "alias",
+            "add",
+            "event@example.com",
+            "alice@example.com",
+            "--valid-until",
+            "2026-11-06T18:00",
+        ])
+        .unwrap();
+        match cli.command {
+            Command::Alias {
+                action:
+                    AliasCommand::Add {
+                        valid_from,
+                        valid_until,
+                        ..
+                    },
+            } => {
+                assert_eq!(valid_from, None);
+                assert_eq!(valid_until, Some(at(6, 18)));
+            }
+            other => panic!("unexpected command {other:?}"),
+        }
+
+        assert!(Cli::try_parse_from([
+            "sortingoffice-cli",
+            "alias",
+            "add",
+            "event@example.com",
+            "alice@example.com",
+            "--valid-from",
+            "next week",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_apply_windows() {
+        let pool = setup_test_db();
+        cleanup_test_db(&pool);
+        let audit = test_audit();
+        let domain = format!("validity-{}.com", unique_test_id());
+        let now = Utc::now().naive_utc();
+
+        let ending = db::create_alias(
+            &pool,
+            AliasForm {
+                mail: format!("event@{domain}"),
+                destination: format!("alice@{domain}"),
+                enabled: true,
+                return_url: None,
+                valid_from: None,
+                valid_until: Some(now + Duration::hours(1)),
+            },
+            &audit,
+        )
+        .unwrap();
+        let starting = db::create_relocated(
+            &pool,
+            RelocatedForm {
+                old_address: format!("old@{domain}"),
+                new_address: "new@example.org".to_string(),
+                enabled: false,
+                valid_from: Some(now + Duration::hours(1)),
+                valid_until: None,
+            },
+            &audit,
+        )
+        .unwrap();
+
+        // Before the boundaries nothing changes
+        assert_eq!(apply(&pool, "test", now), Ok(0));
+
+        // Past the boundaries: the alias is disabled and the relocation enabled, once
+        let later = now + Duration::hours(2);
+        assert_eq!(apply(&pool, "test", later), Ok(2));
+        assert!(!db::get_alias(&pool, ending.pkid).unwrap().enabled);
+        assert!(
+            db::get_relocated_by_old_address(&pool, &starting.old_address)
+                .unwrap()
+                .enabled
+        );
+        assert_eq!(apply(&pool, "test", later), Ok(0));
+
+        cleanup_test_db(&pool);
+    }
+}
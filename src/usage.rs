@@ -0,0 +1,247 @@
+//! Mailbox usage collection
+//!
+//! Usage is read either from `doveadm -f tab quota get -A` (any command that
+//! prints the same output can be configured) or by walking each user's maildir
+//! (`users.maildir` under `users.home`). Every run stores one snapshot per
+//! mailbox in `mailbox_usage`, all sharing the run's timestamp, which the
+//! statistics page reads for current usage, the biggest mailboxes and growth.
+
+use crate::config::{UsageConfig, UsageSource};
+use crate::db::{self, DatabaseManager};
+use crate::models::User;
+use crate::{AppState, DbPool};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// How often the collector checks whether a run is due
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Usage of one mailbox
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsageSample {
+    pub user_id: String,
+    pub bytes: i64,
+    pub messages: i64,
+}
+
+/// Parse `doveadm -f tab quota get -A` output
+///
+/// `STORAGE` values are reported in KiB. A user with several quota roots is
+/// counted once, with the largest value of each type.
+pub fn parse_doveadm_quota(output: &str) -> Result<Vec<UsageSample>, String> {
+    let mut lines = output.lines().filter(|line| !line.trim().is_empty());
+    let mut columns = (0, 2, 3);
+    let mut first = lines.next();
+    if let Some(header) = first {
+        let names: Vec<String> = header
+            .split('\t')
+            .map(|name| name.trim().to_ascii_lowercase())
+            .collect();
+        if names.first().map(String::as_str) == Some("username") {
+            let find = |name: &str| {
+                names
+                    .iter()
+                    .position(|column| column == name)
+                    .ok_or_else(|| format!("doveadm output has no '{name}' column"))
+            };
+            columns = (find("username")?, find("type")?, find("value")?);
+            first = None;
+        }
+    }
+
+    let (user_column, type_column, value_column) = columns;
+    let mut samples: Vec<UsageSample> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    for line in first.into_iter().chain(lines) {
+        let fields: Vec<&str> = line.split('\t').map(str::trim).collect();
+        let field = |column: usize| {
+            fields
+                .get(column)
+                .copied()
+                .ok_or_else(|| format!("Malformed doveadm line: {line}"))
+        };
+        let user_id = field(user_column)?;
+        let value: i64 = field(value_column)?
+            .parse()
+            .map_err(|_| format!("Invalid usage value in doveadm line: {line}"))?;
+
+        let position = *index.entry(user_id.to_string()).or_insert_with(|| {
+            samples.push(UsageSample {
+                user_id: user_id.to_string(),
+                bytes: 0,
+                messages: 0,
+            });
+            samples.len() - 1
+        });
+        let sample = &mut samples[position];
+        match field(type_column)?.to_ascii_uppercase().as_str() {
+            "STORAGE" => sample.bytes = sample.bytes.max(value.saturating_mul(1024)),
+            "MESSAGE" => sample.messages = sample.messages.max(value),
+            _ => {}
+        }
+    }
+    Ok(samples)
+}
+
+/// Run the configured usage command and return what it printed
+pub fn run_usage_command(command: &[String]) -> Result<String, String> {
+    let (program, args) = command
+        .split_first()
+        .ok_or_else(|| "The usage command is empty".to_string())?;
+    let output = std::process::Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run {program}: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "{program} failed ({}): {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    String::from_utf8(output.stdout).map_err(|_| format!("{program} printed invalid UTF-8"))
+}
+
+/// Where a user's maildir lives: `maildir` relative to `home`, unless absolute
+pub fn maildir_path(user: &User) -> PathBuf {
+    Path::new(&user.home).join(&user.maildir)
+}
+
+/// Size and count of the messages in a maildir, including Maildir++ folders
+///
+/// Messages are the files in `cur` and `new` directories; Dovecot's index and
+/// cache files do not count towards the quota.
+pub fn scan_maildir(path: &Path) -> std::io::Result<(i64, i64)> {
+    let mut bytes = 0;
+    let mut messages = 0;
+    let mut pending = vec![path.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let is_message_dir = matches!(
+            dir.file_name().and_then(|name| name.to_str()),
+            Some("cur" | "new")
+        );
+        for entry in std::fs::read_dir(&dir)? {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                pending.push(entry.path());
+            } else if file_type.is_file() && is_message_dir {
+                bytes += entry.metadata()?.len() as i64;
+                messages += 1;
+            }
+        }
+    }
+    Ok((bytes, messages))
+}
+
+/// Read the usage of `users` from the configured source
+///
+/// Mailboxes the source knows nothing about (no maildir yet, or not in the
+/// doveadm output) are left out rather than recorded as empty.
+pub fn collect_samples(config: &UsageConfig, users: &[User]) -> Result<Vec<UsageSample>, String> {
+    match config.source {
+        UsageSource::None => Ok(vec![]),
+        UsageSource::Doveadm => {
+            let known: HashSet<&str> = users.iter().map(|user| user.id.as_str()).collect();
+            let mut samples = parse_doveadm_quota(&run_usage_command(&config.command)?)?;
+            samples.retain(|sample| known.contains(sample.user_id.as_str()));
+            Ok(samples)
+        }
+        UsageSource::Maildir => Ok(users
+            .iter()
+            .filter_map(|user| {
+                let path = maildir_path(user);
+                match scan_maildir(&path) {
+                    Ok((bytes, messages)) => Some(UsageSample {
+                        user_id: user.id.clone(),
+                        bytes,
+                        messages,
+                    }),
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+                    Err(e) => {
+                        tracing::warn!("Failed to scan maildir {}: {}", path.display(), e);
+                        None
+                    }
+                }
+            })
+            .collect()),
+    }
+}
+
+/// Collect usage for one database, store it and prune old snapshots
+///
+/// Returns the number of mailboxes recorded.
+pub fn collect(pool: &DbPool, config: &UsageConfig) -> Result<usize, String> {
+    let users = db::get_users(pool).map_err(|e| e.to_string())?;
+    let samples = collect_samples(config, &users)?;
+    let now = chrono::Utc::now().naive_utc();
+    // Snapshots are compared by timestamp, so keep it at the column's precision
+    let collected = now - Duration::from_nanos(now.and_utc().timestamp_subsec_nanos().into());
+
+    let recorded = if samples.is_empty() {
+        0
+    } else {
+        db::record_mailbox_usage(pool, &samples, collected).map_err(|e| e.to_string())?
+    };
+    let cutoff = collected - chrono::Duration::days(config.retention_days.into());
+    db::prune_mailbox_usage(pool, cutoff).map_err(|e| e.to_string())?;
+    Ok(recorded)
+}
+
+/// Collect usage for every enabled database (or only `database_id`)
+pub async fn collect_databases(
+    db_manager: &DatabaseManager,
+    config: &UsageConfig,
+    database_id: Option<&str>,
+) -> Vec<(String, Result<usize, String>)> {
+    let mut results = Vec::new();
+    for database in db_manager.get_configs() {
+        if database.features.disabled || database_id.is_some_and(|id| id != database.id) {
+            continue;
+        }
+        let result = match db_manager.get_pool(&database.id).await {
+            Some(pool) => {
+                let config = config.clone();
+                tokio::task::spawn_blocking(move || collect(&pool, &config))
+                    .await
+                    .unwrap_or_else(|e| Err(e.to_string()))
+            }
+            None => Err(format!("Unknown database '{}'", database.id)),
+        };
+        results.push((database.id, result));
+    }
+    results
+}
+
+/// Collect usage every `usage.interval_minutes`, following config reloads
+pub fn spawn_collector(state: AppState) {
+    tokio::spawn(async move {
+        let mut last_run: Option<Instant> = None;
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            let config = state.config.get().usage.clone();
+            let period = Duration::from_secs(config.interval_minutes.max(1) * 60);
+            let due = match last_run {
+                Some(last_run) => last_run.elapsed() >= period,
+                None => true,
+            };
+            if config.source == UsageSource::None || !due {
+                continue;
+            }
+
+            last_run = Some(Instant::now());
+            for (database_id, result) in collect_databases(&state.db_manager, &config, None).await {
+                match result {
+                    Ok(count) => {
+                        tracing::debug!("Recorded usage of {} mailboxes in {}", count, database_id)
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to collect mailbox usage for {}: {}", database_id, e)
+                    }
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,277 @@
+//! Vacation auto-replies
+//!
+//! Each mailbox can have one vacation message in the `vacation` table. There
+//! are two ways to get it sent:
+//!
+//! - Postfix setups with a `vacation` transport (as used by PostfixAdmin)
+//!   receive a copy of the mailbox's mail at `user#domain@<transport_domain>`.
+//!   While a vacation is active that address is added to the alias on the
+//!   mailbox's own address, creating the alias if needed; afterwards it is
+//!   removed again, together with the alias if only the mailbox is left.
+//! - Dovecot Pigeonhole setups use the Sieve scripts offered on the mail
+//!   config page, which check the date range themselves.
+
+use crate::db::{self, AuditContext, DatabaseManager};
+use crate::delivery::split_destinations;
+use crate::models::{Vacation, VacationForm};
+use crate::{AppState, DbPool};
+use chrono::NaiveDate;
+use std::time::Duration;
+
+/// Days before the same sender gets another reply, unless set otherwise
+pub const DEFAULT_REPLY_INTERVAL_DAYS: i32 = 7;
+
+/// Longest reply interval accepted
+pub const MAX_REPLY_INTERVAL_DAYS: i32 = 365;
+
+/// How often the vacation aliases are brought in line with the date ranges
+const SYNC_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Check a submitted vacation, returning the translation key of the problem
+pub fn validate(form: &VacationForm) -> Result<(), &'static str> {
+    if form.subject.trim().is_empty() {
+        return Err("vacation-error-subject");
+    }
+    if !(1..=MAX_REPLY_INTERVAL_DAYS).contains(&form.reply_interval_days) {
+        return Err("vacation-error-interval");
+    }
+    if let (Some(from), Some(until)) = (form.active_from, form.active_until) {
+        if until < from {
+            return Err("vacation-error-dates");
+        }
+    }
+    Ok(())
+}
+
+/// Address the vacation transport receives a mailbox's mail on
+///
+/// `alice@example.com` becomes `alice#example.com@<transport_domain>`.
+pub fn transport_address(user_id: &str, transport_domain: &str) -> String {
+    format!(
+        "{}@{}",
+        user_id.to_lowercase().replace('@', "#"),
+        transport_domain.to_lowercase()
+    )
+}
+
+/// What has to happen to a mailbox's own alias for its vacation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AliasChange {
+    /// Create the alias with this destination
+    Create(String),
+    /// Set the existing alias's destination
+    Update(String),
+    /// Delete the alias; it would only deliver to the mailbox itself
+    Delete,
+}
+
+/// The alias change that adds or removes `transport_address`, if one is needed
+///
+/// `destination` is the current destination of the alias on `user_id`, if
+/// there is one.
+pub fn alias_change(
+    user_id: &str,
+    destination: Option<&str>,
+    transport_address: &str,
+    active: bool,
+) -> Option<AliasChange> {
+    let transport_address = transport_address.to_lowercase();
+    let mut addresses = destination.map(split_destinations).unwrap_or_default();
+    let present = addresses.contains(&transport_address);
+
+    if active {
+        if present {
+            return None;
+        }
+        if destination.is_none() {
+            return Some(AliasChange::Create(format!(
+                "{}, {transport_address}",
+                user_id.to_lowercase()
+            )));
+        }
+        addresses.push(transport_address);
+        return Some(AliasChange::Update(addresses.join(", ")));
+    }
+
+    if !present {
+        return None;
+    }
+    addresses.retain(|address| *address != transport_address);
+    if addresses.is_empty() || addresses == [user_id.to_lowercase()] {
+        Some(AliasChange::Delete)
+    } else {
+        Some(AliasChange::Update(addresses.join(", ")))
+    }
+}
+
+/// Quote a Sieve string
+fn sieve_string(value: &str) -> String {
+    let escaped = value
+        .replace(['\r', '\n'], " ")
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"");
+    format!("\"{escaped}\"")
+}
+
+/// Sieve script (Dovecot Pigeonhole) sending the vacation reply
+///
+/// The date range is checked with the `date` extension, so the script can be
+/// installed before the vacation starts and left in place after it ends.
+pub fn sieve_script(vacation: &Vacation) -> String {
+    let mut conditions = Vec::new();
+    if let Some(from) = vacation.active_from {
+        conditions.push(format!(
+            "currentdate :value \"ge\" \"date\" \"{}\"",
+            from.format("%Y-%m-%d")
+        ));
+    }
+    if let Some(until) = vacation.active_until {
+        conditions.push(format!(
+            "currentdate :value \"le\" \"date\" \"{}\"",
+            until.format("%Y-%m-%d")
+        ));
+    }
+
+    // Multi-line strings end at a line holding only a dot, so dot-stuff the body
+    let mut body = String::new();
+    for line in vacation.body.lines() {
+        if line.starts_with('.') {
+            body.push('.');
+        }
+        body.push_str(line);
+        body.push('\n');
+    }
+    let action = format!(
+        "vacation :days {} :subject {} :addresses [{}] text:\n{body}.\n;\n",
+        vacation.reply_interval_days,
+        sieve_string(&vacation.subject),
+        sieve_string(&vacation.user_id),
+    );
+
+    let mut script = format!("# Generated by Sorting Office for {}\n", vacation.user_id);
+    if conditions.is_empty() {
+        script.push_str("require [\"vacation\"];\n\n");
+        script.push_str(&action);
+    } else {
+        script.push_str("require [\"vacation\", \"date\", \"relational\"];\n\n");
+        script.push_str(&format!("if allof ({}) {{\n", conditions.join(", ")));
+        for line in action.lines() {
+            script.push_str("    ");
+            script.push_str(line);
+            script.push('\n');
+        }
+        script.push_str("}\n");
+    }
+    script
+}
+
+/// Add or remove a mailbox's transport address right away, if a transport is configured
+///
+/// Used when a vacation is saved or removed, so the change does not wait for
+/// the scheduler. Returns whether the alias changed.
+pub fn sync_user(
+    pool: &DbPool,
+    transport_domain: Option<&str>,
+    user_id: &str,
+    active: bool,
+    audit: &AuditContext,
+) -> Result<bool, diesel::result::Error> {
+    match transport_domain {
+        Some(domain) => db::sync_vacation_alias(
+            pool,
+            user_id,
+            &transport_address(user_id, domain),
+            active,
+            audit,
+        ),
+        None => Ok(false),
+    }
+}
+
+/// Bring the vacation aliases of one database in line with `today`
+///
+/// Returns the number of aliases changed.
+pub fn sync(
+    pool: &DbPool,
+    database_id: &str,
+    transport_domain: &str,
+    today: NaiveDate,
+) -> Result<usize, String> {
+    let audit = AuditContext::new("system:vacation", database_id);
+    let mut changed = 0;
+    for vacation in db::get_vacations(pool).map_err(|e| e.to_string())? {
+        let active = vacation.is_active(today);
+        if sync_user(
+            pool,
+            Some(transport_domain),
+            &vacation.user_id,
+            active,
+            &audit,
+        )
+        .map_err(|e| e.to_string())?
+        {
+            changed += 1;
+        }
+    }
+    Ok(changed)
+}
+
+/// Sync the vacation aliases of every enabled, writable database
+pub async fn sync_databases(
+    db_manager: &DatabaseManager,
+    transport_domain: &str,
+    today: NaiveDate,
+) -> Vec<(String, Result<usize, String>)> {
+    let mut results = Vec::new();
+    for database in db_manager.get_configs() {
+        if database.features.disabled || database.features.read_only {
+            continue;
+        }
+        let result = match db_manager.get_pool(&database.id).await {
+            Some(pool) => {
+                let database_id = database.id.clone();
+                let transport_domain = transport_domain.to_string();
+                tokio::task::spawn_blocking(move || {
+                    sync(&pool, &database_id, &transport_domain, today)
+                })
+                .await
+                .unwrap_or_else(|e| Err(e.to_string()))
+            }
+            None => Err(format!("Unknown database '{}'", database.id)),
+        };
+        results.push((database.id, result));
+    }
+    results
+}
+
+/// Start and end vacations on the vacation transport as their dates pass
+pub fn spawn_scheduler(state: AppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(SYNC_INTERVAL);
+        loop {
+            interval.tick().await;
+            let config = state.config.get();
+            let Some(transport_domain) = config.vacation.transport_domain.clone() else {
+                continue;
+            };
+            if config.global_features.read_only {
+                continue;
+            }
+
+            let today = chrono::Local::now().date_naive();
+            for (database_id, result) in
+                sync_databases(&state.db_manager, &transport_domain, today).await
+            {
+                match result {
+                    Ok(0) => {}
+                    Ok(count) => {
+                        tracing::info!("Updated {} vacation aliases in {}", count, database_id)
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to sync vacation aliases for {}: {}", database_id, e)
+                    }
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,208 @@
+//! Validity windows for aliases and relocated entries
+//!
+//! An entry with `valid_from` or `valid_until` set only takes effect inside
+//! that window. The generated Postfix queries check the window themselves, so
+//! mail stops at `valid_until` even if nothing else runs. The scheduler here
+//! also moves the `enabled` flag at the boundaries, so the admin pages and
+//! anything else reading the tables agree with Postfix:
+//!
+//! - once `valid_from` has passed, a disabled entry is enabled
+//! - once `valid_until` has passed, an enabled entry is disabled
+//!
+//! Each boundary is acted on once: an entry changed after the boundary (its
+//! `modified` time is later) is left as the admin set it. Times are UTC, like
+//! the `created` and `modified` columns.
+
+use crate::db::{self, AuditContext, DatabaseManager};
+use crate::{AppState, DbPool};
+use chrono::{Duration, NaiveDateTime, Utc};
+
+/// Days ahead an entry counts as expiring soon
+pub const EXPIRING_SOON_DAYS: i64 = 7;
+
+/// How often entries are enabled and disabled at their window boundaries
+const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Whether `now` is inside the window; an unset end is open
+pub fn is_within(
+    valid_from: Option<NaiveDateTime>,
+    valid_until: Option<NaiveDateTime>,
+    now: NaiveDateTime,
+) -> bool {
+    valid_from.is_none_or(|from| from <= now) && valid_until.is_none_or(|until| now < until)
+}
+
+/// Whether the window ends within the next [`EXPIRING_SOON_DAYS`]
+pub fn is_expiring_soon(valid_until: Option<NaiveDateTime>, now: NaiveDateTime) -> bool {
+    valid_until.is_some_and(|until| now < until && until <= expiring_soon_cutoff(now))
+}
+
+/// The end of the "expiring soon" period starting at `now`
+pub fn expiring_soon_cutoff(now: NaiveDateTime) -> NaiveDateTime {
+    now + Duration::days(EXPIRING_SOON_DAYS)
+}
+
+/// Where `now` falls relative to an entry's validity window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowStatus {
+    /// The window has not started yet
+    Pending,
+    /// Inside the window, or there is no window
+    Open,
+    /// Inside the window, which ends within [`EXPIRING_SOON_DAYS`]
+    ExpiringSoon,
+    /// The window has ended
+    Expired,
+}
+
+/// Where `now` falls relative to the window
+pub fn status(
+    valid_from: Option<NaiveDateTime>,
+    valid_until: Option<NaiveDateTime>,
+    now: NaiveDateTime,
+) -> WindowStatus {
+    if valid_until.is_some_and(|until| until <= now) {
+        WindowStatus::Expired
+    } else if valid_from.is_some_and(|from| now < from) {
+        WindowStatus::Pending
+    } else if is_expiring_soon(valid_until, now) {
+        WindowStatus::ExpiringSoon
+    } else {
+        WindowStatus::Open
+    }
+}
+
+/// A validity window that ends before it starts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidWindow;
+
+impl InvalidWindow {
+    /// Translation key of the error message
+    pub fn message_id(&self) -> &'static str {
+        "validity-error-window"
+    }
+}
+
+impl std::fmt::Display for InvalidWindow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Valid until must be later than valid from")
+    }
+}
+
+/// Check that a window with both ends set ends after it starts
+pub fn check_window(
+    valid_from: Option<NaiveDateTime>,
+    valid_until: Option<NaiveDateTime>,
+) -> Result<(), InvalidWindow> {
+    match (valid_from, valid_until) {
+        (Some(from), Some(until)) if until <= from => Err(InvalidWindow),
+        _ => Ok(()),
+    }
+}
+
+/// The `enabled` value the scheduler sets at `now`, if it changes anything
+///
+/// `modified` is when the entry was last changed; a change after the boundary
+/// that has passed wins over the window.
+pub fn scheduled_state(
+    enabled: bool,
+    valid_from: Option<NaiveDateTime>,
+    valid_until: Option<NaiveDateTime>,
+    modified: NaiveDateTime,
+    now: NaiveDateTime,
+) -> Option<bool> {
+    if let Some(until) = valid_until.filter(|until| *until <= now) {
+        return (enabled && modified < until).then_some(false);
+    }
+    if let Some(from) = valid_from.filter(|from| *from <= now) {
+        return (!enabled && modified < from).then_some(true);
+    }
+    None
+}
+
+/// Enable and disable the aliases and relocated entries whose boundaries have
+/// passed, returning how many were changed
+pub fn apply(pool: &DbPool, database_id: &str, now: NaiveDateTime) -> Result<usize, String> {
+    let audit = AuditContext::new("system:validity", database_id);
+    let mut changed = 0;
+    for alias in db::get_scheduled_aliases(pool).map_err(|e| e.to_string())? {
+        if let Some(enabled) = scheduled_state(
+            alias.enabled,
+            alias.valid_from,
+            alias.valid_until,
+            alias.modified,
+            now,
+        ) {
+            db::set_alias_enabled(pool, alias.pkid, enabled, &audit).map_err(|e| e.to_string())?;
+            changed += 1;
+        }
+    }
+    for relocated in db::get_scheduled_relocated(pool).map_err(|e| e.to_string())? {
+        if let Some(enabled) = scheduled_state(
+            relocated.enabled,
+            relocated.valid_from,
+            relocated.valid_until,
+            relocated.modified,
+            now,
+        ) {
+            db::set_relocated_enabled(pool, relocated.pkid, enabled, &audit)
+                .map_err(|e| e.to_string())?;
+            changed += 1;
+        }
+    }
+    Ok(changed)
+}
+
+/// Apply the windows on every enabled, writable database
+pub async fn apply_databases(
+    db_manager: &DatabaseManager,
+    now: NaiveDateTime,
+) -> Vec<(String, Result<usize, String>)> {
+    let mut results = Vec::new();
+    for database in db_manager.get_configs() {
+        if database.features.disabled || database.features.read_only {
+            continue;
+        }
+        let result = match db_manager.get_pool(&database.id).await {
+            Some(pool) => {
+                let database_id = database.id.clone();
+                tokio::task::spawn_blocking(move || apply(&pool, &database_id, now))
+                    .await
+                    .unwrap_or_else(|e| Err(e.to_string()))
+            }
+            None => Err(format!("Unknown database '{}'", database.id)),
+        };
+        results.push((database.id, result));
+    }
+    results
+}
+
+/// Enable and disable entries as their validity windows open and close
+pub fn spawn_scheduler(state: AppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            if state.config.get().global_features.read_only {
+                continue;
+            }
+
+            let now = Utc::now().naive_utc();
+            for (database_id, result) in apply_databases(&state.db_manager, now).await {
+                match result {
+                    Ok(0) => {}
+                    Ok(count) => {
+                        tracing::info!("Applied {} validity windows in {}", count, database_id)
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            "Failed to apply validity windows for {}: {}",
+                            database_id,
+                            e
+                        )
+                    }
+                }
+            }
+        }
+    });
+}
@@ -1,127 +1,103 @@
-// Minimal test: log when mail input is found and when its input event fires
-var mailInput = document.getElementById("mail");
-if (mailInput) {
-  console.log("[test] mail input found at script load");
-  mailInput.addEventListener("input", function () {
-    console.log("[test] mail input event fired, value:", mailInput.value);
+// Alias form: destination editor suggestions and domain suggestions for the mail field.
+// The destination editor is replaced on every add or remove, so its elements are
+// looked up when events fire; the listeners are only installed once per page.
+if (!window.aliasFormInitialized) {
+  window.aliasFormInitialized = true;
+
+  function showResults(results, visible) {
+    if (results) {
+      results.classList.toggle("hidden", !visible);
+    }
+  }
+
+  document.body.addEventListener("htmx:afterSwap", function (e) {
+    if (e.target.id === "search-results") {
+      const input = document.getElementById("destination");
+      const query = input ? input.value.trim() : "";
+      showResults(e.target, query.length >= 2 && e.target.innerHTML.trim() !== "");
+    }
+    if (e.target.id === "domain-search-results") {
+      showResults(e.target, e.target.innerHTML.trim() !== "");
+    }
   });
-} else {
-  console.log("[test] mail input not found at script load");
-}
 
-console.log("[domain-suggest] alias-form.js loaded");
-// Alias form functionality
-  try {
-    const destinationInput = document.getElementById("destination");
-    const searchResults = document.getElementById("search-results");
-  const domainSearchResults = document.getElementById("domain-search-results");
-  const mailDomainInput = document.getElementById("mail-domain");
+  // An address typed but not added yet is still saved with the form
+  document.body.addEventListener("htmx:configRequest", function (e) {
+    const params = e.detail.parameters;
+    const typed = params.new_destination ? params.new_destination.trim() : "";
+    if (e.detail.elt.tagName === "FORM" && typed !== "") {
+      params.destination = params.destination ? params.destination + ", " + typed : typed;
+    }
+  });
 
-  // --- Destination suggestion feature ---
-  if (!destinationInput) {
-    console.warn("[destination-suggest] destinationInput not found");
-  }
-  if (!searchResults) {
-    console.warn("[destination-suggest] searchResults not found");
-  }
-  if (destinationInput && searchResults) {
-    destinationInput.addEventListener("input", function () {
-      console.log("[destination-suggest] input event fired, value:", destinationInput.value);
-    });
-    document.body.addEventListener("htmx:afterSwap", function (e) {
-      if (e.target.id === "search-results") {
-        console.log("[destination-suggest] htmx:afterSwap for search-results");
-        console.log("[destination-suggest] searchResults:", searchResults);
-        const query = destinationInput.value.trim();
-        if (query.length >= 2 && searchResults.innerHTML.trim() !== "") {
-          searchResults.classList.remove("hidden");
-        } else {
-          searchResults.classList.add("hidden");
-        }
+  // Enter in the new destination field adds it instead of submitting the form
+  document.body.addEventListener("keydown", function (e) {
+    if (e.key === "Enter" && e.target.id === "destination") {
+      e.preventDefault();
+      const add = document.getElementById("destination-add");
+      if (add) {
+        add.click();
       }
-    });
-    // --- Click handler for destination suggestions ---
-    searchResults.addEventListener("click", function (e) {
-      const li = e.target.closest("li");
-      if (li && destinationInput) {
-        let valueToInsert = li.getAttribute("data-mail") || li.getAttribute("data-destination") || li.textContent.trim();
-        if (valueToInsert) {
-          destinationInput.value = valueToInsert;
-          searchResults.classList.add("hidden");
-          destinationInput.focus();
-          console.log("[destination-suggest] Inserted '", valueToInsert, "' into destination input");
+    }
+  });
+
+  document.body.addEventListener("click", function (e) {
+    const li = e.target.closest("li");
+    if (!li) {
+      return;
+    }
+
+    // A destination suggestion is added to the list right away
+    const searchResults = li.closest("#search-results");
+    if (searchResults) {
+      const input = document.getElementById("destination");
+      const value =
+        li.getAttribute("data-mail") || li.getAttribute("data-destination") || li.textContent.trim();
+      if (input && value) {
+        input.value = value;
+        showResults(searchResults, false);
+        const add = document.getElementById("destination-add");
+        if (add) {
+          add.click();
         }
       }
-    });
-  }
+      return;
+    }
 
-  // --- Domain suggestion trigger for mail field ---
-  if (!mailInput) {
-    console.warn("[domain-suggest] mailInput not found");
-  }
-  if (!domainSearchResults) {
-    console.warn("[domain-suggest] domainSearchResults not found");
-  }
-  if (!mailDomainInput) {
-    console.warn("[domain-suggest] mailDomainInput not found");
-  }
-  if (mailInput && domainSearchResults && mailDomainInput) {
-    mailInput.addEventListener("input", function (e) {
-      const value = mailInput.value;
-      const atIndex = value.indexOf("@");
-      // Only trigger if '@' is present and cursor is after it, and at least one char after '@'
-      if (
-        atIndex !== -1 &&
-        mailInput.selectionStart > atIndex + 1 &&
-        value.length > atIndex + 1
-      ) {
-        // Set the hidden domain input value to the part after '@'
-        mailDomainInput.value = value.substring(atIndex + 1);
-        console.log("[domain-suggest] Dispatching input event on mail-domain", mailDomainInput.value);
-        mailDomainInput.dispatchEvent(new Event("input", { bubbles: true, composed: true }));
-      } else {
-        // Hide suggestions if not in domain part
-            domainSearchResults.classList.add("hidden");
-        console.log("[domain-suggest] Hiding domain suggestions (not in domain part)");
-        }
-      });
-    document.body.addEventListener("htmx:afterSwap", function (e) {
-      if (e.target.id === "domain-search-results") {
-        console.log("[domain-suggest] htmx:afterSwap for domain-search-results");
-        if (
-          domainSearchResults &&
-          domainSearchResults.innerHTML.trim() !== ""
-        ) {
-          domainSearchResults.classList.remove("hidden");
-          console.log("[domain-suggest] Showing domain suggestions");
-        } else if (domainSearchResults) {
-          domainSearchResults.classList.add("hidden");
-          console.log("[domain-suggest] Hiding domain suggestions (no results)");
-        }
+    const domainSearchResults = li.closest("#domain-search-results");
+    const mailInput = document.getElementById("mail");
+    if (domainSearchResults && mailInput) {
+      const domain = li.getAttribute("data-domain") || li.textContent.trim();
+      if (domain) {
+        const atIndex = mailInput.value.indexOf("@");
+        const prefix = atIndex !== -1 ? mailInput.value.substring(0, atIndex) : mailInput.value;
+        mailInput.value = prefix + "@" + domain;
+        showResults(domainSearchResults, false);
+        mailInput.focus();
       }
-    });
-    // --- Click handler for domain suggestions ---
-    domainSearchResults.addEventListener("click", function (e) {
-      const li = e.target.closest("li");
-      if (li && mailInput) {
-        const domain = li.getAttribute("data-domain") || li.textContent.trim();
-        if (domain) {
-          const currentValue = mailInput.value;
-          const atIndex = currentValue.indexOf("@");
-          let prefix = "";
-          if (atIndex !== -1) {
-            prefix = currentValue.substring(0, atIndex);
-          } else {
-            prefix = currentValue;
-          }
-          mailInput.value = prefix + "@" + domain;
-          domainSearchResults.classList.add("hidden");
-          mailInput.focus();
-          console.log("[domain-suggest] Inserted domain '", domain, "' into mail input");
-        }
-  }
-});
-  }
-} catch (err) {
-  console.error("[domain-suggest] Error in alias-form.js:", err);
+    }
+  });
+
+  // Suggest domains once the cursor is past the '@' of the mail field
+  document.body.addEventListener("input", function (e) {
+    if (e.target.id !== "mail") {
+      return;
+    }
+    const mailInput = e.target;
+    const mailDomainInput = document.getElementById("mail-domain");
+    const domainSearchResults = document.getElementById("domain-search-results");
+    const value = mailInput.value;
+    const atIndex = value.indexOf("@");
+    if (
+      mailDomainInput &&
+      atIndex !== -1 &&
+      mailInput.selectionStart > atIndex + 1 &&
+      value.length > atIndex + 1
+    ) {
+      mailDomainInput.value = value.substring(atIndex + 1);
+      mailDomainInput.dispatchEvent(new Event("input", { bubbles: true, composed: true }));
+    } else {
+      showResults(domainSearchResults, false);
+    }
+  });
 }
@@ -0,0 +1,11 @@
+// Render the generated OpenAPI document with the bundled Swagger UI
+window.addEventListener('DOMContentLoaded', function () {
+    window.ui = SwaggerUIBundle({
+        url: '/api/openapi.json',
+        dom_id: '#swagger-ui',
+        deepLinking: true,
+        persistAuthorization: false,
+        presets: [SwaggerUIBundle.presets.apis],
+        layout: 'BaseLayout'
+    });
+});
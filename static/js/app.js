@@ -100,11 +100,10 @@ document.addEventListener('DOMContentLoaded', function() {
 
 // Progress bar initialization
 document.addEventListener('DOMContentLoaded', function() {
-    const progressBar = document.querySelector('[data-width]');
-    if (progressBar) {
+    document.querySelectorAll('[data-width]').forEach(function(progressBar) {
         const width = progressBar.getAttribute('data-width');
-        progressBar.style.width = width + '%';
-    }
+        progressBar.style.width = Math.min(width, 100) + '%';
+    });
 });
 
 document.body.addEventListener('htmx:afterSwap', function(evt) {
@@ -112,3 +111,14 @@ document.body.addEventListener('htmx:afterSwap', function(evt) {
     evt.target.classList.remove('hidden');
   }
 });
+
+// Fill the password fields of a form with a generated password
+document.body.addEventListener('htmx:afterSwap', function(evt) {
+  const generated = evt.target.querySelector('[data-generated-password]');
+  const form = evt.target.closest('form');
+  if (generated && form) {
+    form.querySelectorAll('[data-generated-password-target]').forEach(function(input) {
+      input.value = generated.getAttribute('data-generated-password');
+    });
+  }
+});
@@ -1,162 +1,89 @@
 // Config page functionality
+//
+// Entries are cloned from the <template> elements on the page so they carry
+// translated labels, and text is only ever set through textContent/value.
 
-function addRequiredAlias() {
-    const input = document.querySelector('input[name="new_required_alias"]');
-    const alias = input.value.trim();
-    if (!alias) return;
-
-    const aliasesList = document.getElementById('required-aliases-list');
-    const aliasDiv = document.createElement('div');
-    aliasDiv.className = 'flex items-center justify-between p-3 bg-red-50 dark:bg-red-900/20 rounded-md';
-    aliasDiv.innerHTML = `
-        <span class="text-sm font-medium text-red-900 dark:text-red-100">${alias}</span>
-        <div class="flex items-center space-x-2">
-            <button type="button" onclick="demoteToCommon('${alias}')" class="text-blue-600 hover:text-blue-800 dark:text-blue-400 dark:hover:text-blue-300 text-xs">
-                Demote to Common
-            </button>
-            <button type="button" onclick="removeRequiredAlias('${alias}')" class="text-red-600 hover:text-red-800 dark:text-red-400 dark:hover:text-red-300">
-                Remove
-            </button>
-        </div>
-        <input type="hidden" name="required_aliases[]" value="${alias}">
-    `;
-    aliasesList.appendChild(aliasDiv);
-    input.value = '';
+function cloneTemplate(id) {
+    return document.getElementById(id).content.firstElementChild.cloneNode(true);
 }
 
-function removeRequiredAlias(alias) {
-    const aliasesList = document.getElementById('required-aliases-list');
-    const aliasDivs = aliasesList.querySelectorAll('div');
-    aliasDivs.forEach(div => {
-        const span = div.querySelector('span');
-        if (span && span.textContent === alias) {
-            div.remove();
-        }
-    });
+function listHasAlias(list, alias) {
+    return Array.from(list.querySelectorAll('input[type="hidden"]'))
+        .some(input => input.value.toLowerCase() === alias.toLowerCase());
 }
 
-function demoteToCommon(alias) {
-    // Implementation of demoteToCommon function
-    console.log('Demote to common:', alias);
+function appendAlias(kind, alias) {
+    const list = document.getElementById(`${kind}-aliases-list`);
+    if (listHasAlias(list, alias)) return;
+
+    const entry = cloneTemplate(`${kind}-alias-template`);
+    entry.querySelector('.alias-name').textContent = alias;
+    entry.querySelector('input[type="hidden"]').value = alias;
+    list.appendChild(entry);
 }
 
-function addCommonAlias() {
-    const input = document.querySelector('input[name="new_common_alias"]');
+// kind is 'required' or 'common'
+function addAlias(kind) {
+    const input = document.querySelector(`input[name="new_${kind}_alias"]`);
     const alias = input.value.trim();
     if (!alias) return;
 
-    const aliasesList = document.getElementById('common-aliases-list');
-    const aliasDiv = document.createElement('div');
-    aliasDiv.className = 'flex items-center justify-between p-3 bg-yellow-50 dark:bg-yellow-900/20 rounded-md';
-    aliasDiv.innerHTML = `
-        <span class="text-sm font-medium text-yellow-900 dark:text-yellow-100">${alias}</span>
-        <div class="flex items-center space-x-2">
-            <button type="button" onclick="promoteToRequired('${alias}')" class="text-green-600 hover:text-green-800 dark:text-green-400 dark:hover:text-green-300 text-xs">
-                Promote to Required
-            </button>
-            <button type="button" onclick="removeCommonAlias('${alias}')" class="text-red-600 hover:text-red-800 dark:text-red-400 dark:hover:text-red-300">
-                Remove
-            </button>
-        </div>
-        <input type="hidden" name="common_aliases[]" value="${alias}">
-    `;
-    aliasesList.appendChild(aliasDiv);
+    appendAlias(kind, alias);
     input.value = '';
 }
 
-function removeCommonAlias(alias) {
-    const aliasesList = document.getElementById('common-aliases-list');
-    const aliasDivs = aliasesList.querySelectorAll('div');
-    aliasDivs.forEach(div => {
-        const span = div.querySelector('span');
-        if (span && span.textContent === alias) {
-            div.remove();
-        }
-    });
+function removeAlias(button) {
+    button.closest('.alias-entry').remove();
 }
 
-function promoteToRequired(alias) {
-    // Implementation of promoteToRequired function
-    console.log('Promote to required:', alias);
+// Promote a common alias to required, or demote a required alias to common
+function moveAlias(button, kind) {
+    const entry = button.closest('.alias-entry');
+    const alias = entry.querySelector('input[type="hidden"]').value;
+    entry.remove();
+    appendAlias(kind, alias);
 }
 
 function addDomainOverride() {
     const input = document.querySelector('input[name="new_domain"]');
-    const domain = input.value.trim();
+    const domain = input.value.trim().toLowerCase();
     if (!domain) return;
 
     const overridesList = document.getElementById('domain-overrides-list');
-    const domainDiv = document.createElement('div');
-    domainDiv.className = 'border border-gray-200 dark:border-gray-600 rounded-md p-4';
-    domainDiv.innerHTML = `
-        <div class="flex items-center justify-between mb-3">
-            <h4 class="text-sm font-medium text-gray-900 dark:text-white">${domain}</h4>
-            <button type="button" onclick="removeDomainOverride('${domain}')" class="text-red-600 hover:text-red-800 dark:text-red-400 dark:hover:text-red-300">
-                Remove Domain
-            </button>
-        </div>
-        <div class="space-y-2">
-            <div class="flex items-center space-x-4">
-                <input type="text" placeholder="Domain alias" class="flex-1 rounded-md border-gray-300 dark:border-gray-600 dark:bg-gray-700 dark:text-white shadow-sm focus:border-indigo-500 focus:ring-indigo-500 sm:text-sm">
-                <button type="button" onclick="addDomainAlias('${domain}')" class="inline-flex items-center px-3 py-2 border border-transparent text-sm leading-4 font-medium rounded-md text-white bg-indigo-600 hover:bg-indigo-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-indigo-500">
-                    Add Alias
-                </button>
-            </div>
-        </div>
-        <input type="hidden" name="domain_overrides[${domain}][]" value="">
-    `;
-    overridesList.appendChild(domainDiv);
+    const exists = Array.from(overridesList.querySelectorAll('.domain-override'))
+        .some(block => block.dataset.domain === domain);
+    if (exists) return;
+
+    const block = cloneTemplate('domain-override-template');
+    block.dataset.domain = domain;
+    block.querySelector('h4').textContent = domain;
+    const aliasInput = block.querySelector('input[type="text"]');
+    aliasInput.placeholder = aliasInput.placeholder.replace('{domain}', domain);
+    // Keeps the domain in the submission when it has no aliases yet
+    block.querySelector('.domain-marker').name = `domain_overrides[${domain}][required][]`;
+    overridesList.appendChild(block);
     input.value = '';
 }
 
-function removeDomainOverride(domain) {
-    const overridesList = document.getElementById('domain-overrides-list');
-    const domainDivs = overridesList.querySelectorAll('div');
-    domainDivs.forEach(div => {
-        const h4 = div.querySelector('h4');
-        if (h4 && h4.textContent === domain) {
-            div.remove();
-        }
-    });
+function removeDomainOverride(button) {
+    button.closest('.domain-override').remove();
 }
 
-function addDomainAlias(domain) {
-    const domainDiv = event.target.closest('div');
-    const input = domainDiv.querySelector('input[type="text"]');
+// kind is 'required' or 'common'
+function addDomainAlias(button, kind) {
+    const block = button.closest('.domain-override');
+    const input = block.querySelector('input[type="text"]');
     const alias = input.value.trim();
     if (!alias) return;
 
-    const aliasesContainer = domainDiv.querySelector('.space-y-2');
-    const aliasDiv = document.createElement('div');
-    aliasDiv.className = 'flex items-center justify-between p-2 bg-gray-50 dark:bg-gray-700 rounded';
-    aliasDiv.innerHTML = `
-        <span class="text-sm text-gray-900 dark:text-white">${alias}</span>
-        <button type="button" onclick="removeDomainAlias('${domain}', '${alias}')" class="text-red-600 hover:text-red-800 dark:text-red-400 dark:hover:text-red-300">
-            Remove
-        </button>
-        <input type="hidden" name="domain_overrides[${domain}][]" value="${alias}">
-    `;
-    aliasesContainer.appendChild(aliasDiv);
+    const list = block.querySelector(`.domain-${kind}-list`);
+    if (!listHasAlias(list, alias)) {
+        const entry = cloneTemplate(`domain-${kind}-alias-template`);
+        entry.querySelector('.alias-name').textContent = alias;
+        const hidden = entry.querySelector('input[type="hidden"]');
+        hidden.name = `domain_overrides[${block.dataset.domain}][${kind}][]`;
+        hidden.value = alias;
+        list.appendChild(entry);
+    }
     input.value = '';
 }
-
-function removeDomainAlias(domain, alias) {
-    const domainDiv = event.target.closest('div');
-    const aliasDivs = domainDiv.querySelectorAll('div');
-    aliasDivs.forEach(div => {
-        const span = div.querySelector('span');
-        if (span && span.textContent === alias) {
-            div.remove();
-        }
-    });
-}
-
-function removeDomainRequiredAlias(domain, alias) {
-    // Implementation of removeDomainRequiredAlias function
-    console.log('Remove domain required alias:', domain, alias);
-}
-
-function removeDomainCommonAlias(domain, alias) {
-    // Implementation of removeDomainCommonAlias function
-    console.log('Remove domain common alias:', domain, alias);
-}